@@ -1,32 +1,89 @@
 use {
+    quoter::{
+        get_minimum_amount_out,
+        pumpswap::{
+            find_canonical_pool_pubkey,
+            find_global_config_pubkey,
+            GlobalConfig,
+            Pool,
+            PoolAccounts,
+        },
+        raydium::{
+            AmmAccounts,
+            AmmInfo,
+            MarketState,
+        },
+    },
     simo_intermediary::{
-        FEE_CHANGE_MANAGER,
-        FEE_CHANGE_TRADER,
-        FEE_DEPOSIT_FUNDS,
-        FEE_INITIALIZE,
-        FEE_WITHDRAW_FUNDS,
+        AllowedMintsAddressResolver,
+        AnyMintTokenAccountAddressResolver,
+        AnyMintTokenAccountAuthorityAddressResolver,
+        ConfigAddressResolver,
+        FeeVaultAddressResolver,
+        IntermediateTokenAccountAddressResolver,
+        METEORA_DLMM_PROGRAM_PUBKEY,
+        METEORA_DYNAMIC_AMM_PROGRAM_PUBKEY,
+        METEORA_VAULT_PROGRAM_PUBKEY,
         PROGRAM_PUBKEY,
+        PUMPFUN_PROGRAM_PUBKEY,
+        PUMPSWAP_PROGRAM_PUBKEY,
         ProgramDerivedAddress,
+        RAYDIUM_CLMM_PROGRAM_PUBKEY,
+        RAYDIUM_CPMM_PROGRAM_PUBKEY,
+        RAYDIUM_LIQUIDITY_POOL_V4_PROGRAM_PUBKEY,
+        RiskLimitsAddressResolver,
+        SPL_ASSOCIATED_TOKEN_ACCOUNT_ID,
+        TemporaryAnyMintTokenAccountAddressResolver,
+        TemporaryAnyMintTokenAccountAuthorityAddressResolver,
         TemporaryWSolTokenAccountAddressResolver,
         TemporaryWSolTokenAccountAuthorityAddressResolver,
+        TraderSeatAddressResolver,
         WSolTokenAccountAddressResolver,
         WSolTokenAccountAuthorityAddressResolver,
-        instruction::Instruction,
-        state::Intermediary,
+        event::{
+            Side,
+            Venue,
+        },
+        instruction::{
+            AnyMintTokenAccountPubkeys,
+            Instruction,
+            LiquidationLeg,
+            RouteHop,
+            RouteLeg,
+        },
+        state::{
+            AllowedMints,
+            Config,
+            Fees,
+            Intermediary,
+            TraderSeat,
+        },
     },
     solana_program::program_pack::Pack,
     solana_rpc_client::rpc_client::RpcClient,
     solana_sdk::{
         account::ReadableAccount,
-        message::Message,
+        address_lookup_table::{
+            state::AddressLookupTable,
+            AddressLookupTableAccount,
+        },
+        message::{
+            v0,
+            Message,
+            VersionedMessage,
+        },
         pubkey::Pubkey,
         signer::{
             Signer,
             keypair::Keypair,
         },
-        transaction::Transaction,
+        transaction::{
+            Transaction,
+            VersionedTransaction,
+        },
     },
     spl_token::state::Account,
+    spl_token_2022::extension::StateWithExtensions,
     std::{
         collections::HashSet,
         error::Error,
@@ -36,11 +93,34 @@ use {
 };
 pub struct CommandProcessor;
 impl CommandProcessor {
+    const ERROR_ALLOWED_MINTS_ALREADY_CONTAINS_MINT: &'static str = "Allowed mints already contains mint.";
+    const ERROR_ALLOWED_MINTS_DOES_NOT_CONTAIN_MINT: &'static str = "Allowed mints does not contain mint.";
+    const ERROR_CONFIG_IS_NOT_INITIALIZED: &'static str = "Config is not initialized.";
+    const ERROR_CONFIG_INVALID_ADMINISTRATOR: &'static str = "Config invalid administrator.";
+    const ERROR_INTERMEDIARY_HAS_OPEN_POSITIONS: &'static str = "Intermediary has open positions.";
+    const ERROR_INTERMEDIARY_HAS_TRADER_SEATS: &'static str = "Intermediary has trader seats.";
     const ERROR_INTERMEDIARY_IS_NOT_INITIALIZED: &'static str = "Intermediary is not initialized.";
     const ERROR_INTERMEDIARY_INVALID_INVESTOR: &'static str = "Intermediary invalid investor.";
+    const ERROR_INTERMEDIARY_INVALID_INVESTOR_OR_MANAGER: &'static str = "Intermediary invalid investor or manager.";
     const ERROR_INTERMEDIARY_INVALID_MANAGER: &'static str = "Intermediary invalid manager.";
+    const ERROR_INTERMEDIARY_INVALID_MAXIMUM_PRICE_DEVIATION: &'static str = "Intermediary invalid maximum price deviation.";
+    const ERROR_INTERMEDIARY_INVALID_PENDING_MANAGER: &'static str = "Intermediary invalid pending manager.";
+    const ERROR_INTERMEDIARY_INVALID_PENDING_TRADER: &'static str = "Intermediary invalid pending trader.";
     const ERROR_INTERMEDIARY_INVALID_TRADER: &'static str = "Intermediary invalid trader.";
+    const ERROR_INTERMEDIARY_IS_ALREADY_MIGRATED: &'static str = "Intermediary is already migrated.";
+    const ERROR_INTERMEDIARY_IS_NOT_MIGRATED: &'static str = "Intermediary is not migrated.";
+    const ERROR_INTERMEDIARY_IS_NOT_PAUSED: &'static str = "Intermediary is not paused.";
+    const ERROR_INTERMEDIARY_PAUSED: &'static str = "Intermediary paused.";
     const ERROR_INVALID_ACCOUNT_LAMPORTS: &'static str = "Invalid account lamports.";
+    const ERROR_INVALID_ACCOUNT_DATA: &'static str = "Invalid account data.";
+    const ERROR_INVALID_ACCOUNT_OWNER: &'static str = "Invalid account owner.";
+    const ERROR_INVALID_ACCOUNT_PUBKEY: &'static str = "Invalid account pubkey.";
+    const ERROR_MINIMUM_OUTPUT_TOKEN_AMOUNT_IS_NOT_SPECIFIED: &'static str = "Either the minimum output token amount or the slippage basis points must be specified.";
+    const ERROR_RISK_LIMITS_INVALID_PARAMETERS: &'static str = "Risk limits invalid parameters.";
+    const ERROR_TRADER_SEAT_ALLOCATION_EXCEEDED: &'static str = "Trader seat allocation exceeded.";
+    const ERROR_TRADER_SEAT_IS_ALREADY_INITIALIZED: &'static str = "Trader seat is already initialized.";
+    const ERROR_TRADER_SEAT_IS_NOT_INITIALIZED: &'static str = "Trader seat is not initialized.";
+    const ERROR_VENUE_IS_NOT_SUPPORTED: &'static str = "Venue is not supported.";
     pub fn initialize<'a>(
         rpc_client: &'a RpcClient,
         intermediary_investor_keypair_file_path: &'a str,
@@ -73,7 +153,8 @@ impl CommandProcessor {
         }
         let intermediary_balance_for_rent_exemption = rpc_client.get_minimum_balance_for_rent_exemption(borsh::max_serialized_size::<Intermediary>().unwrap())?;
         let w_sol_token_account_rent_exemption_balance = rpc_client.get_minimum_balance_for_rent_exemption(<Account as Pack>::LEN)?;
-        if intermediary_investor_account.lamports < (intermediary_balance_for_rent_exemption + w_sol_token_account_rent_exemption_balance + lamports_to_treasury + FEE_INITIALIZE)
+        let (config_pubkey, config) = Self::get_config(rpc_client)?;
+        if intermediary_investor_account.lamports < (intermediary_balance_for_rent_exemption + w_sol_token_account_rent_exemption_balance + lamports_to_treasury + config.fees.initialize)
             || intermediary_manager_account.lamports == 0
             || intermediary_trader_account.lamports == 0
         {
@@ -116,6 +197,8 @@ impl CommandProcessor {
                 &w_sol_token_account_authority_pubkey,
                 &temporary_w_sol_token_account_pubkey,
                 &temporary_w_sol_token_account_authority_pubkey,
+                &config_pubkey,
+                &config.fee_vault_pubkey,
                 lamports_to_treasury,
                 w_sol_token_account_pubkey_bump_seed,
                 w_sol_token_account_authority_pubkey_bump_seed,
@@ -149,10 +232,11 @@ impl CommandProcessor {
         let intermediary_investor_pubkey = intermediary_investor_keypair.pubkey();
         let intermediary_pubkey = Pubkey::from_str(intermediary_pubkey)?;
         let intermediary_investor_account = rpc_client.get_account(&intermediary_investor_pubkey)?;
-        if intermediary_investor_account.lamports < (lamports_to_treasury + FEE_DEPOSIT_FUNDS) {
+        let (config_pubkey, config) = Self::get_config(rpc_client)?;
+        if intermediary_investor_account.lamports < (lamports_to_treasury + config.fees.deposit_funds) {
             return Err(Self::ERROR_INVALID_ACCOUNT_LAMPORTS.into());
         }
-        let intermediary = borsh::from_slice::<Intermediary>(rpc_client.get_account(&intermediary_pubkey)?.data.as_slice())?;
+        let intermediary = Intermediary::load(rpc_client.get_account(&intermediary_pubkey)?.data.as_slice())?;
         if !intermediary.is_initialized {
             return Err(Self::ERROR_INTERMEDIARY_IS_NOT_INITIALIZED.into());
         }
@@ -165,6 +249,8 @@ impl CommandProcessor {
                 &intermediary_investor_pubkey,
                 &intermediary_pubkey,
                 &intermediary.w_sol_token_account_pubkey,
+                &config_pubkey,
+                &config.fee_vault_pubkey,
                 lamports_to_treasury,
             )?,
         ];
@@ -195,10 +281,11 @@ impl CommandProcessor {
         let intermediary_pubkey = Pubkey::from_str(intermediary_pubkey)?;
         let intermediary_investor_account = rpc_client.get_account(&intermediary_investor_pubkey)?;
         let temporary_w_sol_token_account_rent_exemption_balance = rpc_client.get_minimum_balance_for_rent_exemption(<Account as Pack>::LEN)?;
-        if intermediary_investor_account.lamports < (temporary_w_sol_token_account_rent_exemption_balance + FEE_WITHDRAW_FUNDS) {
+        let (config_pubkey, config) = Self::get_config(rpc_client)?;
+        if intermediary_investor_account.lamports < (temporary_w_sol_token_account_rent_exemption_balance + config.fees.withdraw_funds) {
             return Err(Self::ERROR_INVALID_ACCOUNT_LAMPORTS.into());
         }
-        let intermediary = borsh::from_slice::<Intermediary>(rpc_client.get_account(&intermediary_pubkey)?.data.as_slice())?;
+        let intermediary = Intermediary::load(rpc_client.get_account(&intermediary_pubkey)?.data.as_slice())?;
         if !intermediary.is_initialized {
             return Err(Self::ERROR_INTERMEDIARY_IS_NOT_INITIALIZED.into());
         }
@@ -224,6 +311,8 @@ impl CommandProcessor {
                 &intermediary.w_sol_token_account_authority_pubkey,
                 &intermediary.temporary_w_sol_token_account_pubkey,
                 &intermediary.temporary_w_sol_token_account_authority_pubkey,
+                &config_pubkey,
+                &config.fee_vault_pubkey,
                 lamports_from_treasury,
             )?,
         ];
@@ -243,7 +332,7 @@ impl CommandProcessor {
         println!("Signature: {}", &signature);
         Ok(())
     }
-    pub fn change_manager<'a>(
+    pub fn propose_manager<'a>(
         rpc_client: &'a RpcClient,
         intermediary_investor_keypair_file_path: &'a str,
         intermediary_pubkey: &'a str,
@@ -255,10 +344,11 @@ impl CommandProcessor {
         let intermediary_manager_pubkey = Pubkey::from_str(intermediary_manager_pubkey)?;
         let intermediary_investor_account = rpc_client.get_account(&intermediary_investor_pubkey)?;
         let intermediary_manager_account = rpc_client.get_account(&intermediary_manager_pubkey)?;
-        if intermediary_investor_account.lamports < FEE_CHANGE_MANAGER || intermediary_manager_account.lamports == 0 {
+        let (config_pubkey, config) = Self::get_config(rpc_client)?;
+        if intermediary_investor_account.lamports < config.fees.change_manager || intermediary_manager_account.lamports == 0 {
             return Err(Self::ERROR_INVALID_ACCOUNT_LAMPORTS.into());
         }
-        let intermediary = borsh::from_slice::<Intermediary>(rpc_client.get_account(&intermediary_pubkey)?.data.as_slice())?;
+        let intermediary = Intermediary::load(rpc_client.get_account(&intermediary_pubkey)?.data.as_slice())?;
         if !intermediary.is_initialized {
             return Err(Self::ERROR_INTERMEDIARY_IS_NOT_INITIALIZED.into());
         }
@@ -272,11 +362,13 @@ impl CommandProcessor {
             return Err(Self::ERROR_INTERMEDIARY_INVALID_MANAGER.into());
         }
         let instructions = vec![
-            Instruction::change_manager(
+            Instruction::propose_manager(
                 &PROGRAM_PUBKEY,
                 &intermediary_investor_pubkey,
                 &intermediary_pubkey,
                 &intermediary_manager_pubkey,
+                &config_pubkey,
+                &config.fee_vault_pubkey,
             )?,
         ];
         let signers = vec![&intermediary_investor_keypair];
@@ -295,7 +387,7 @@ impl CommandProcessor {
         println!("Signature: {}", &signature);
         Ok(())
     }
-    pub fn change_trader<'a>(
+    pub fn propose_trader<'a>(
         rpc_client: &'a RpcClient,
         intermediary_manager_keypair_file_path: &'a str,
         intermediary_pubkey: &'a str,
@@ -307,10 +399,11 @@ impl CommandProcessor {
         let intermediary_trader_pubkey = Pubkey::from_str(intermediary_trader_pubkey)?;
         let intermediary_manager_account = rpc_client.get_account(&intermediary_manager_pubkey)?;
         let intermediary_trader_account = rpc_client.get_account(&intermediary_trader_pubkey)?;
-        if intermediary_manager_account.lamports < FEE_CHANGE_TRADER || intermediary_trader_account.lamports == 0 {
+        let (config_pubkey, config) = Self::get_config(rpc_client)?;
+        if intermediary_manager_account.lamports < config.fees.change_trader || intermediary_trader_account.lamports == 0 {
             return Err(Self::ERROR_INVALID_ACCOUNT_LAMPORTS.into());
         }
-        let intermediary = borsh::from_slice::<Intermediary>(rpc_client.get_account(&intermediary_pubkey)?.data.as_slice())?;
+        let intermediary = Intermediary::load(rpc_client.get_account(&intermediary_pubkey)?.data.as_slice())?;
         if !intermediary.is_initialized {
             return Err(Self::ERROR_INTERMEDIARY_IS_NOT_INITIALIZED.into());
         }
@@ -324,11 +417,13 @@ impl CommandProcessor {
             return Err(Self::ERROR_INTERMEDIARY_INVALID_TRADER.into());
         }
         let instructions = vec![
-            Instruction::change_trader(
+            Instruction::propose_trader(
                 &PROGRAM_PUBKEY,
                 &intermediary_manager_pubkey,
                 &intermediary_pubkey,
                 &intermediary_trader_pubkey,
+                &config_pubkey,
+                &config.fee_vault_pubkey,
             )?,
         ];
         let signers = vec![&intermediary_manager_keypair];
@@ -347,13 +442,4189 @@ impl CommandProcessor {
         println!("Signature: {}", &signature);
         Ok(())
     }
-    fn load_keypair_from_file<'a>(keypair_file_path: &'a str) -> Result<Keypair, Box<dyn Error + 'static>> {
-        let keypair_file_path_ = Path::new(keypair_file_path);
-        let keypair_file_data = if keypair_file_path_.try_exists()? {
-            std::fs::read_to_string(keypair_file_path_)?
-        } else {
-            return Err("The keypair.json file does not exist.".into());
+    pub fn buy_on_raydium<'a>(
+        rpc_client: &'a RpcClient,
+        intermediary_trader_keypair_file_path: &'a str,
+        intermediary_pubkey: &'a str,
+        any_mint_token_mint_pubkey: &'a str,
+        amm_pool_pubkey: &'a str,
+        input_token_amount: u64,
+        minimum_output_token_amount: Option<u64>,
+        slippage_basis_points: Option<u16>,
+    ) -> Result<(), Box<dyn Error + 'static>> {
+        let intermediary_trader_keypair = Self::load_keypair_from_file(&intermediary_trader_keypair_file_path)?;
+        let intermediary_trader_pubkey = intermediary_trader_keypair.pubkey();
+        let intermediary_pubkey = Pubkey::from_str(intermediary_pubkey)?;
+        let any_mint_token_mint_pubkey = Pubkey::from_str(any_mint_token_mint_pubkey)?;
+        let amm_pool_pubkey = Pubkey::from_str(amm_pool_pubkey)?;
+        if any_mint_token_mint_pubkey == spl_token::native_mint::ID {
+            return Err(Self::ERROR_INVALID_ACCOUNT_PUBKEY.into());
+        }
+        let intermediary_trader_account = rpc_client.get_account(&intermediary_trader_pubkey)?;
+        let token_account_rent_exemption_balance = rpc_client.get_minimum_balance_for_rent_exemption(<Account as Pack>::LEN)?;
+        let (config_pubkey, config) = Self::get_config(rpc_client)?;
+        if intermediary_trader_account.lamports < (2 * token_account_rent_exemption_balance + config.fees.buy_on_raydium) {
+            return Err(Self::ERROR_INVALID_ACCOUNT_LAMPORTS.into());
+        }
+        let intermediary = Intermediary::load(rpc_client.get_account(&intermediary_pubkey)?.data.as_slice())?;
+        if !intermediary.is_initialized {
+            return Err(Self::ERROR_INTERMEDIARY_IS_NOT_INITIALIZED.into());
+        }
+        if intermediary.is_paused {
+            return Err(Self::ERROR_INTERMEDIARY_PAUSED.into());
+        }
+        let (trader_seat_pubkey, trader_seat) = Self::get_trader_seat(
+            rpc_client,
+            &intermediary_pubkey,
+            &intermediary_trader_pubkey,
+        )?;
+        if trader_seat.spent_lamports.saturating_add(input_token_amount) > trader_seat.allocated_lamports.saturating_add(trader_seat.received_lamports) {
+            return Err(Self::ERROR_TRADER_SEAT_ALLOCATION_EXCEEDED.into());
+        }
+        let w_sol_token_account = Account::unpack_unchecked(rpc_client.get_account(&intermediary.w_sol_token_account_pubkey)?.data.as_slice())?;
+        if w_sol_token_account.amount < input_token_amount {
+            return Err(
+                format!(
+                    "The maximum number of lamports from treasury is {}",
+                    w_sol_token_account.amount,
+                )
+                .into(),
+            );
+        }
+        let raydium_amm_accounts = Self::resolve_raydium_amm_accounts(
+            rpc_client,
+            &amm_pool_pubkey,
+            &any_mint_token_mint_pubkey,
+            &spl_token::native_mint::ID,
+        )?;
+        let minimum_output_token_amount = match minimum_output_token_amount {
+            Some(minimum_output_token_amount_) => minimum_output_token_amount_,
+            None => Self::quote_raydium_minimum_output_token_amount(
+                rpc_client,
+                &amm_pool_pubkey,
+                &spl_token::native_mint::ID,
+                input_token_amount,
+                slippage_basis_points.ok_or(Self::ERROR_MINIMUM_OUTPUT_TOKEN_AMOUNT_IS_NOT_SPECIFIED)?,
+            )?,
         };
-        Ok(Keypair::from_bytes(serde_json::from_str::<Vec<u8>>(keypair_file_data.as_str())?.as_slice())?)
+        let any_mint_token_account_address_resolver = AnyMintTokenAccountAddressResolver {
+            any_mint_token_mint_pubkey: &any_mint_token_mint_pubkey,
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+        let (any_mint_token_account_pubkey, any_mint_token_account_pubkey_bump_seed) = any_mint_token_account_address_resolver.find();
+        let any_mint_token_account_authority_address_resolver = AnyMintTokenAccountAuthorityAddressResolver {
+            any_mint_token_account_pubkey: &any_mint_token_account_pubkey,
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+        let (any_mint_token_account_authority_pubkey, any_mint_token_account_authority_pubkey_bump_seed) = any_mint_token_account_authority_address_resolver.find();
+        let allowed_mints_address_resolver = AllowedMintsAddressResolver {
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+        let (allowed_mints_pubkey, _) = allowed_mints_address_resolver.find();
+        let risk_limits_address_resolver = RiskLimitsAddressResolver {
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+        let (risk_limits_pubkey, _) = risk_limits_address_resolver.find();
+        let instructions = vec![
+            Instruction::buy_on_raydium(
+                &PROGRAM_PUBKEY,
+                &intermediary_trader_pubkey,
+                &intermediary_pubkey,
+                &intermediary.w_sol_token_account_pubkey,
+                &intermediary.w_sol_token_account_authority_pubkey,
+                &intermediary.temporary_w_sol_token_account_pubkey,
+                &intermediary.temporary_w_sol_token_account_authority_pubkey,
+                &any_mint_token_account_pubkey,
+                &any_mint_token_account_authority_pubkey,
+                &any_mint_token_mint_pubkey,
+                &RAYDIUM_LIQUIDITY_POOL_V4_PROGRAM_PUBKEY,
+                &amm_pool_pubkey,
+                &raydium_amm_accounts.amm_authority_pubkey,
+                &raydium_amm_accounts.amm_open_orders_pubkey,
+                &raydium_amm_accounts.amm_coin_vault_pubkey,
+                &raydium_amm_accounts.amm_pc_vault_pubkey,
+                &raydium_amm_accounts.market_program_id_pubkey,
+                &raydium_amm_accounts.market_pubkey,
+                &raydium_amm_accounts.market_bids_pubkey,
+                &raydium_amm_accounts.market_asks_pubkey,
+                &raydium_amm_accounts.market_event_queue_pubkey,
+                &raydium_amm_accounts.market_coin_vault_pubkey,
+                &raydium_amm_accounts.market_pc_vault_pubkey,
+                &raydium_amm_accounts.market_vault_signer_pubkey,
+                &allowed_mints_pubkey,
+                &risk_limits_pubkey,
+                &trader_seat_pubkey,
+                &config_pubkey,
+                &config.fee_vault_pubkey,
+                input_token_amount,
+                minimum_output_token_amount,
+                any_mint_token_account_pubkey_bump_seed,
+                any_mint_token_account_authority_pubkey_bump_seed,
+            )?,
+        ];
+        let signers = vec![&intermediary_trader_keypair];
+        let recent_blockhash = rpc_client.get_latest_blockhash()?;
+        let message = Message::new_with_blockhash(
+            instructions.as_slice(),
+            Some(&intermediary_trader_pubkey),
+            &recent_blockhash,
+        );
+        let transaction = Transaction::new(
+            signers.as_slice(),
+            message,
+            recent_blockhash,
+        );
+        let signature = rpc_client.send_transaction(&transaction)?;
+        println!("Signature: {}", &signature);
+        Ok(())
+    }
+    pub fn sell_on_raydium<'a>(
+        rpc_client: &'a RpcClient,
+        intermediary_trader_keypair_file_path: &'a str,
+        intermediary_pubkey: &'a str,
+        any_mint_token_mint_pubkey: &'a str,
+        amm_pool_pubkey: &'a str,
+        input_token_amount: u64,
+        minimum_output_token_amount: Option<u64>,
+        slippage_basis_points: Option<u16>,
+    ) -> Result<(), Box<dyn Error + 'static>> {
+        let intermediary_trader_keypair = Self::load_keypair_from_file(&intermediary_trader_keypair_file_path)?;
+        let intermediary_trader_pubkey = intermediary_trader_keypair.pubkey();
+        let intermediary_pubkey = Pubkey::from_str(intermediary_pubkey)?;
+        let any_mint_token_mint_pubkey = Pubkey::from_str(any_mint_token_mint_pubkey)?;
+        let amm_pool_pubkey = Pubkey::from_str(amm_pool_pubkey)?;
+        if any_mint_token_mint_pubkey == spl_token::native_mint::ID {
+            return Err(Self::ERROR_INVALID_ACCOUNT_PUBKEY.into());
+        }
+        let intermediary_trader_account = rpc_client.get_account(&intermediary_trader_pubkey)?;
+        let token_account_rent_exemption_balance = rpc_client.get_minimum_balance_for_rent_exemption(<Account as Pack>::LEN)?;
+        let (config_pubkey, config) = Self::get_config(rpc_client)?;
+        if intermediary_trader_account.lamports < (token_account_rent_exemption_balance + config.fees.sell_on_raydium) {
+            return Err(Self::ERROR_INVALID_ACCOUNT_LAMPORTS.into());
+        }
+        let intermediary = Intermediary::load(rpc_client.get_account(&intermediary_pubkey)?.data.as_slice())?;
+        if !intermediary.is_initialized {
+            return Err(Self::ERROR_INTERMEDIARY_IS_NOT_INITIALIZED.into());
+        }
+        if intermediary.is_paused {
+            return Err(Self::ERROR_INTERMEDIARY_PAUSED.into());
+        }
+        let (trader_seat_pubkey, _) = Self::get_trader_seat(
+            rpc_client,
+            &intermediary_pubkey,
+            &intermediary_trader_pubkey,
+        )?;
+        let any_mint_token_account_address_resolver = AnyMintTokenAccountAddressResolver {
+            any_mint_token_mint_pubkey: &any_mint_token_mint_pubkey,
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+        let (any_mint_token_account_pubkey, any_mint_token_account_pubkey_bump_seed) = any_mint_token_account_address_resolver.find();
+        let any_mint_token_account = Account::unpack_unchecked(rpc_client.get_account(&any_mint_token_account_pubkey)?.data.as_slice())?;
+        if any_mint_token_account.amount < input_token_amount {
+            return Err(
+                format!(
+                    "The maximum number of tokens to sell is {}",
+                    any_mint_token_account.amount,
+                )
+                .into(),
+            );
+        }
+        let raydium_amm_accounts = Self::resolve_raydium_amm_accounts(
+            rpc_client,
+            &amm_pool_pubkey,
+            &any_mint_token_mint_pubkey,
+            &spl_token::native_mint::ID,
+        )?;
+        let minimum_output_token_amount = match (minimum_output_token_amount, slippage_basis_points) {
+            (None, Some(slippage_basis_points_)) => Some(
+                Self::quote_raydium_minimum_output_token_amount(
+                    rpc_client,
+                    &amm_pool_pubkey,
+                    &any_mint_token_mint_pubkey,
+                    input_token_amount,
+                    slippage_basis_points_,
+                )?,
+            ),
+            _ => minimum_output_token_amount,
+        };
+        let any_mint_token_account_authority_address_resolver = AnyMintTokenAccountAuthorityAddressResolver {
+            any_mint_token_account_pubkey: &any_mint_token_account_pubkey,
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+        let (any_mint_token_account_authority_pubkey, any_mint_token_account_authority_pubkey_bump_seed) = any_mint_token_account_authority_address_resolver.find();
+        let temporary_any_mint_token_account_address_resolver = TemporaryAnyMintTokenAccountAddressResolver {
+            any_mint_token_account_pubkey: &any_mint_token_account_pubkey,
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+        let (temporary_any_mint_token_account_pubkey, temporary_any_mint_token_account_pubkey_bump_seed) = temporary_any_mint_token_account_address_resolver.find();
+        let temporary_any_mint_token_account_authority_address_resolver = TemporaryAnyMintTokenAccountAuthorityAddressResolver {
+            temporary_any_mint_token_account_pubkey: &temporary_any_mint_token_account_pubkey,
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+        let (temporary_any_mint_token_account_authority_pubkey, temporary_any_mint_token_account_authority_pubkey_bump_seed) =
+            temporary_any_mint_token_account_authority_address_resolver.find();
+        let risk_limits_address_resolver = RiskLimitsAddressResolver {
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+        let (risk_limits_pubkey, _) = risk_limits_address_resolver.find();
+        let instructions = vec![
+            Instruction::sell_on_raydium(
+                &PROGRAM_PUBKEY,
+                &intermediary_trader_pubkey,
+                &intermediary_pubkey,
+                &intermediary.w_sol_token_account_pubkey,
+                &any_mint_token_account_pubkey,
+                &any_mint_token_account_authority_pubkey,
+                &temporary_any_mint_token_account_pubkey,
+                &temporary_any_mint_token_account_authority_pubkey,
+                &any_mint_token_mint_pubkey,
+                &RAYDIUM_LIQUIDITY_POOL_V4_PROGRAM_PUBKEY,
+                &amm_pool_pubkey,
+                &raydium_amm_accounts.amm_authority_pubkey,
+                &raydium_amm_accounts.amm_open_orders_pubkey,
+                &raydium_amm_accounts.amm_coin_vault_pubkey,
+                &raydium_amm_accounts.amm_pc_vault_pubkey,
+                &raydium_amm_accounts.market_program_id_pubkey,
+                &raydium_amm_accounts.market_pubkey,
+                &raydium_amm_accounts.market_bids_pubkey,
+                &raydium_amm_accounts.market_asks_pubkey,
+                &raydium_amm_accounts.market_event_queue_pubkey,
+                &raydium_amm_accounts.market_coin_vault_pubkey,
+                &raydium_amm_accounts.market_pc_vault_pubkey,
+                &raydium_amm_accounts.market_vault_signer_pubkey,
+                &risk_limits_pubkey,
+                &trader_seat_pubkey,
+                &config_pubkey,
+                &config.fee_vault_pubkey,
+                input_token_amount,
+                minimum_output_token_amount,
+                any_mint_token_account_pubkey_bump_seed,
+                any_mint_token_account_authority_pubkey_bump_seed,
+                temporary_any_mint_token_account_pubkey_bump_seed,
+                temporary_any_mint_token_account_authority_pubkey_bump_seed,
+            )?,
+        ];
+        let signers = vec![&intermediary_trader_keypair];
+        let recent_blockhash = rpc_client.get_latest_blockhash()?;
+        let message = Message::new_with_blockhash(
+            instructions.as_slice(),
+            Some(&intermediary_trader_pubkey),
+            &recent_blockhash,
+        );
+        let transaction = Transaction::new(
+            signers.as_slice(),
+            message,
+            recent_blockhash,
+        );
+        let signature = rpc_client.send_transaction(&transaction)?;
+        println!("Signature: {}", &signature);
+        Ok(())
+    }
+    pub fn buy_on_pumpswap<'a>(
+        rpc_client: &'a RpcClient,
+        intermediary_trader_keypair_file_path: &'a str,
+        intermediary_pubkey: &'a str,
+        any_mint_token_mint_pubkey: &'a str,
+        pool_pubkey: Option<&'a str>,
+        base_amount_out: u64,
+        maximum_quote_amount_in: u64,
+    ) -> Result<(), Box<dyn Error + 'static>> {
+        let intermediary_trader_keypair = Self::load_keypair_from_file(&intermediary_trader_keypair_file_path)?;
+        let intermediary_trader_pubkey = intermediary_trader_keypair.pubkey();
+        let intermediary_pubkey = Pubkey::from_str(intermediary_pubkey)?;
+        let any_mint_token_mint_pubkey = Pubkey::from_str(any_mint_token_mint_pubkey)?;
+        if any_mint_token_mint_pubkey == spl_token::native_mint::ID {
+            return Err(Self::ERROR_INVALID_ACCOUNT_PUBKEY.into());
+        }
+        let pool_pubkey = Self::resolve_pumpswap_pool_pubkey(
+            pool_pubkey,
+            &any_mint_token_mint_pubkey,
+        )?;
+        let intermediary_trader_account = rpc_client.get_account(&intermediary_trader_pubkey)?;
+        let token_account_rent_exemption_balance = rpc_client.get_minimum_balance_for_rent_exemption(<Account as Pack>::LEN)?;
+        let (config_pubkey, config) = Self::get_config(rpc_client)?;
+        if intermediary_trader_account.lamports < (2 * token_account_rent_exemption_balance + config.fees.buy_on_pumpswap) {
+            return Err(Self::ERROR_INVALID_ACCOUNT_LAMPORTS.into());
+        }
+        let intermediary = Intermediary::load(rpc_client.get_account(&intermediary_pubkey)?.data.as_slice())?;
+        if !intermediary.is_initialized {
+            return Err(Self::ERROR_INTERMEDIARY_IS_NOT_INITIALIZED.into());
+        }
+        if intermediary.is_paused {
+            return Err(Self::ERROR_INTERMEDIARY_PAUSED.into());
+        }
+        let (trader_seat_pubkey, trader_seat) = Self::get_trader_seat(
+            rpc_client,
+            &intermediary_pubkey,
+            &intermediary_trader_pubkey,
+        )?;
+        if trader_seat.spent_lamports.saturating_add(maximum_quote_amount_in) > trader_seat.allocated_lamports.saturating_add(trader_seat.received_lamports) {
+            return Err(Self::ERROR_TRADER_SEAT_ALLOCATION_EXCEEDED.into());
+        }
+        let w_sol_token_account = Account::unpack_unchecked(rpc_client.get_account(&intermediary.w_sol_token_account_pubkey)?.data.as_slice())?;
+        if w_sol_token_account.amount < maximum_quote_amount_in {
+            return Err(
+                format!(
+                    "The maximum number of lamports from treasury is {}",
+                    w_sol_token_account.amount,
+                )
+                .into(),
+            );
+        }
+        let pumpswap_pool_accounts = Self::resolve_pumpswap_pool_accounts(
+            rpc_client,
+            &pool_pubkey,
+            &any_mint_token_mint_pubkey,
+            &spl_token::native_mint::ID,
+        )?;
+        let any_mint_token_account_address_resolver = AnyMintTokenAccountAddressResolver {
+            any_mint_token_mint_pubkey: &any_mint_token_mint_pubkey,
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+        let (any_mint_token_account_pubkey, any_mint_token_account_pubkey_bump_seed) = any_mint_token_account_address_resolver.find();
+        let any_mint_token_account_authority_address_resolver = AnyMintTokenAccountAuthorityAddressResolver {
+            any_mint_token_account_pubkey: &any_mint_token_account_pubkey,
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+        let (any_mint_token_account_authority_pubkey, any_mint_token_account_authority_pubkey_bump_seed) = any_mint_token_account_authority_address_resolver.find();
+        let allowed_mints_address_resolver = AllowedMintsAddressResolver {
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+        let (allowed_mints_pubkey, _) = allowed_mints_address_resolver.find();
+        let risk_limits_address_resolver = RiskLimitsAddressResolver {
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+        let (risk_limits_pubkey, _) = risk_limits_address_resolver.find();
+        let instructions = vec![
+            Instruction::buy_on_pumpswap(
+                &PROGRAM_PUBKEY,
+                &intermediary_trader_pubkey,
+                &intermediary_pubkey,
+                &intermediary.w_sol_token_account_pubkey,
+                &intermediary.w_sol_token_account_authority_pubkey,
+                &intermediary.temporary_w_sol_token_account_pubkey,
+                &intermediary.temporary_w_sol_token_account_authority_pubkey,
+                &any_mint_token_account_pubkey,
+                &any_mint_token_account_authority_pubkey,
+                &any_mint_token_mint_pubkey,
+                &PUMPSWAP_PROGRAM_PUBKEY,
+                &pool_pubkey,
+                &pumpswap_pool_accounts.global_config_pubkey,
+                &pumpswap_pool_accounts.pool_base_token_account_pubkey,
+                &pumpswap_pool_accounts.pool_quote_token_account_pubkey,
+                &pumpswap_pool_accounts.protocol_fee_recipient_pubkey,
+                &pumpswap_pool_accounts.protocol_fee_recipient_token_account_pubkey,
+                &pumpswap_pool_accounts.base_token_program_pubkey,
+                &pumpswap_pool_accounts.quote_token_program_pubkey,
+                &pumpswap_pool_accounts.event_authority_pubkey,
+                &allowed_mints_pubkey,
+                &risk_limits_pubkey,
+                &trader_seat_pubkey,
+                &config_pubkey,
+                &config.fee_vault_pubkey,
+                base_amount_out,
+                maximum_quote_amount_in,
+                any_mint_token_account_pubkey_bump_seed,
+                any_mint_token_account_authority_pubkey_bump_seed,
+            )?,
+        ];
+        let signers = vec![&intermediary_trader_keypair];
+        let recent_blockhash = rpc_client.get_latest_blockhash()?;
+        let message = Message::new_with_blockhash(
+            instructions.as_slice(),
+            Some(&intermediary_trader_pubkey),
+            &recent_blockhash,
+        );
+        let transaction = Transaction::new(
+            signers.as_slice(),
+            message,
+            recent_blockhash,
+        );
+        let signature = rpc_client.send_transaction(&transaction)?;
+        println!("Signature: {}", &signature);
+        Ok(())
+    }
+    pub fn sell_on_pumpswap<'a>(
+        rpc_client: &'a RpcClient,
+        intermediary_trader_keypair_file_path: &'a str,
+        intermediary_pubkey: &'a str,
+        any_mint_token_mint_pubkey: &'a str,
+        pool_pubkey: Option<&'a str>,
+        input_token_amount: u64,
+        minimum_output_token_amount: Option<u64>,
+        slippage_basis_points: Option<u16>,
+    ) -> Result<(), Box<dyn Error + 'static>> {
+        let intermediary_trader_keypair = Self::load_keypair_from_file(&intermediary_trader_keypair_file_path)?;
+        let intermediary_trader_pubkey = intermediary_trader_keypair.pubkey();
+        let intermediary_pubkey = Pubkey::from_str(intermediary_pubkey)?;
+        let any_mint_token_mint_pubkey = Pubkey::from_str(any_mint_token_mint_pubkey)?;
+        if any_mint_token_mint_pubkey == spl_token::native_mint::ID {
+            return Err(Self::ERROR_INVALID_ACCOUNT_PUBKEY.into());
+        }
+        let pool_pubkey = Self::resolve_pumpswap_pool_pubkey(
+            pool_pubkey,
+            &any_mint_token_mint_pubkey,
+        )?;
+        let intermediary_trader_account = rpc_client.get_account(&intermediary_trader_pubkey)?;
+        let token_account_rent_exemption_balance = rpc_client.get_minimum_balance_for_rent_exemption(<Account as Pack>::LEN)?;
+        let (config_pubkey, config) = Self::get_config(rpc_client)?;
+        if intermediary_trader_account.lamports < (token_account_rent_exemption_balance + config.fees.sell_on_pumpswap) {
+            return Err(Self::ERROR_INVALID_ACCOUNT_LAMPORTS.into());
+        }
+        let intermediary = Intermediary::load(rpc_client.get_account(&intermediary_pubkey)?.data.as_slice())?;
+        if !intermediary.is_initialized {
+            return Err(Self::ERROR_INTERMEDIARY_IS_NOT_INITIALIZED.into());
+        }
+        if intermediary.is_paused {
+            return Err(Self::ERROR_INTERMEDIARY_PAUSED.into());
+        }
+        let (trader_seat_pubkey, _) = Self::get_trader_seat(
+            rpc_client,
+            &intermediary_pubkey,
+            &intermediary_trader_pubkey,
+        )?;
+        let any_mint_token_account_address_resolver = AnyMintTokenAccountAddressResolver {
+            any_mint_token_mint_pubkey: &any_mint_token_mint_pubkey,
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+        let (any_mint_token_account_pubkey, any_mint_token_account_pubkey_bump_seed) = any_mint_token_account_address_resolver.find();
+        let any_mint_token_account = StateWithExtensions::<spl_token_2022::state::Account>::unpack(rpc_client.get_account(&any_mint_token_account_pubkey)?.data.as_slice())?.base;
+        if any_mint_token_account.amount < input_token_amount {
+            return Err(
+                format!(
+                    "The maximum number of tokens to sell is {}",
+                    any_mint_token_account.amount,
+                )
+                .into(),
+            );
+        }
+        let pumpswap_pool_accounts = Self::resolve_pumpswap_pool_accounts(
+            rpc_client,
+            &pool_pubkey,
+            &any_mint_token_mint_pubkey,
+            &spl_token::native_mint::ID,
+        )?;
+        let minimum_output_token_amount = match (minimum_output_token_amount, slippage_basis_points) {
+            (None, Some(slippage_basis_points_)) => Some(
+                Self::quote_pumpswap_minimum_output_token_amount(
+                    rpc_client,
+                    &pool_pubkey,
+                    &any_mint_token_mint_pubkey,
+                    input_token_amount,
+                    slippage_basis_points_,
+                )?,
+            ),
+            _ => minimum_output_token_amount,
+        };
+        let temporary_any_mint_token_account_address_resolver = TemporaryAnyMintTokenAccountAddressResolver {
+            any_mint_token_account_pubkey: &any_mint_token_account_pubkey,
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+                let any_mint_token_account_authority_address_resolver = AnyMintTokenAccountAuthorityAddressResolver {
+            any_mint_token_account_pubkey: &any_mint_token_account_pubkey,
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+        let (any_mint_token_account_authority_pubkey, any_mint_token_account_authority_pubkey_bump_seed) = any_mint_token_account_authority_address_resolver.find();
+let (temporary_any_mint_token_account_pubkey, temporary_any_mint_token_account_pubkey_bump_seed) = temporary_any_mint_token_account_address_resolver.find();
+        let temporary_any_mint_token_account_authority_address_resolver = TemporaryAnyMintTokenAccountAuthorityAddressResolver {
+            temporary_any_mint_token_account_pubkey: &temporary_any_mint_token_account_pubkey,
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+        let (temporary_any_mint_token_account_authority_pubkey, temporary_any_mint_token_account_authority_pubkey_bump_seed) =
+            temporary_any_mint_token_account_authority_address_resolver.find();
+        let risk_limits_address_resolver = RiskLimitsAddressResolver {
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+        let (risk_limits_pubkey, _) = risk_limits_address_resolver.find();
+        let instructions = vec![
+            Instruction::sell_on_pumpswap(
+                &PROGRAM_PUBKEY,
+                &intermediary_trader_pubkey,
+                &intermediary_pubkey,
+                &intermediary.w_sol_token_account_pubkey,
+                &intermediary.temporary_w_sol_token_account_pubkey,
+                &any_mint_token_account_pubkey,
+                &any_mint_token_account_authority_pubkey,
+                &temporary_any_mint_token_account_pubkey,
+                &temporary_any_mint_token_account_authority_pubkey,
+                &any_mint_token_mint_pubkey,
+                &PUMPSWAP_PROGRAM_PUBKEY,
+                &pool_pubkey,
+                &pumpswap_pool_accounts.global_config_pubkey,
+                &pumpswap_pool_accounts.pool_base_token_account_pubkey,
+                &pumpswap_pool_accounts.pool_quote_token_account_pubkey,
+                &pumpswap_pool_accounts.protocol_fee_recipient_pubkey,
+                &pumpswap_pool_accounts.protocol_fee_recipient_token_account_pubkey,
+                &pumpswap_pool_accounts.base_token_program_pubkey,
+                &pumpswap_pool_accounts.quote_token_program_pubkey,
+                &pumpswap_pool_accounts.event_authority_pubkey,
+                &risk_limits_pubkey,
+                &trader_seat_pubkey,
+                &config_pubkey,
+                &config.fee_vault_pubkey,
+                input_token_amount,
+                minimum_output_token_amount,
+                any_mint_token_account_pubkey_bump_seed,
+                any_mint_token_account_authority_pubkey_bump_seed,
+                temporary_any_mint_token_account_pubkey_bump_seed,
+                temporary_any_mint_token_account_authority_pubkey_bump_seed,
+            )?,
+        ];
+        let signers = vec![&intermediary_trader_keypair];
+        let recent_blockhash = rpc_client.get_latest_blockhash()?;
+        let message = Message::new_with_blockhash(
+            instructions.as_slice(),
+            Some(&intermediary_trader_pubkey),
+            &recent_blockhash,
+        );
+        let transaction = Transaction::new(
+            signers.as_slice(),
+            message,
+            recent_blockhash,
+        );
+        let signature = rpc_client.send_transaction(&transaction)?;
+        println!("Signature: {}", &signature);
+        Ok(())
+    }
+    pub fn initialize_config<'a>(
+        rpc_client: &'a RpcClient,
+        administrator_keypair_file_path: &'a str,
+        fee_recipient_pubkey: &'a str,
+        fees: Fees,
+    ) -> Result<(), Box<dyn Error + 'static>> {
+        let administrator_keypair = Self::load_keypair_from_file(&administrator_keypair_file_path)?;
+        let administrator_pubkey = administrator_keypair.pubkey();
+        let fee_recipient_pubkey = Pubkey::from_str(fee_recipient_pubkey)?;
+        let config_address_resolver = ConfigAddressResolver;
+        let (config_pubkey, config_pubkey_bump_seed) = config_address_resolver.find();
+        println!("Config: {}", &config_pubkey);
+        let fee_vault_address_resolver = FeeVaultAddressResolver {
+            config_pubkey: &config_pubkey,
+        };
+        let (fee_vault_pubkey, fee_vault_pubkey_bump_seed) = fee_vault_address_resolver.find();
+        println!("FeeVault: {}", &fee_vault_pubkey);
+        if rpc_client.get_account(&config_pubkey).is_ok() {
+            return Err("Config is already initialized.".into());
+        }
+        let config_rent_exemption_balance = rpc_client.get_minimum_balance_for_rent_exemption(borsh::max_serialized_size::<Config>().unwrap())?;
+        let fee_vault_rent_exemption_balance = rpc_client.get_minimum_balance_for_rent_exemption(0)?;
+        if rpc_client.get_account(&administrator_pubkey)?.lamports < (config_rent_exemption_balance + fee_vault_rent_exemption_balance) {
+            return Err(Self::ERROR_INVALID_ACCOUNT_LAMPORTS.into());
+        }
+        let instructions = vec![
+            Instruction::initialize_config(
+                &PROGRAM_PUBKEY,
+                &administrator_pubkey,
+                &config_pubkey,
+                &fee_vault_pubkey,
+                &fee_recipient_pubkey,
+                &solana_sdk::bpf_loader_upgradeable::get_program_data_address(&PROGRAM_PUBKEY),
+                fees,
+                config_pubkey_bump_seed,
+                fee_vault_pubkey_bump_seed,
+            )?,
+        ];
+        let signers = vec![&administrator_keypair];
+        let recent_blockhash = rpc_client.get_latest_blockhash()?;
+        let message = Message::new_with_blockhash(
+            instructions.as_slice(),
+            Some(&administrator_pubkey),
+            &recent_blockhash,
+        );
+        let transaction = Transaction::new(
+            signers.as_slice(),
+            message,
+            recent_blockhash,
+        );
+        let signature = rpc_client.send_transaction(&transaction)?;
+        println!("Signature: {}", &signature);
+        Ok(())
     }
+    pub fn update_config<'a>(
+        rpc_client: &'a RpcClient,
+        administrator_keypair_file_path: &'a str,
+        fee_recipient_pubkey: &'a str,
+        fees: Fees,
+    ) -> Result<(), Box<dyn Error + 'static>> {
+        let administrator_keypair = Self::load_keypair_from_file(&administrator_keypair_file_path)?;
+        let administrator_pubkey = administrator_keypair.pubkey();
+        let fee_recipient_pubkey = Pubkey::from_str(fee_recipient_pubkey)?;
+        let (config_pubkey, config) = Self::get_config(rpc_client)?;
+        if administrator_pubkey != config.administrator_pubkey {
+            return Err(Self::ERROR_CONFIG_INVALID_ADMINISTRATOR.into());
+        }
+        let instructions = vec![
+            Instruction::update_config(
+                &PROGRAM_PUBKEY,
+                &administrator_pubkey,
+                &config_pubkey,
+                &fee_recipient_pubkey,
+                fees,
+            )?,
+        ];
+        let signers = vec![&administrator_keypair];
+        let recent_blockhash = rpc_client.get_latest_blockhash()?;
+        let message = Message::new_with_blockhash(
+            instructions.as_slice(),
+            Some(&administrator_pubkey),
+            &recent_blockhash,
+        );
+        let transaction = Transaction::new(
+            signers.as_slice(),
+            message,
+            recent_blockhash,
+        );
+        let signature = rpc_client.send_transaction(&transaction)?;
+        println!("Signature: {}", &signature);
+        Ok(())
+    }
+    pub fn sweep_fees<'a>(rpc_client: &'a RpcClient, administrator_keypair_file_path: &'a str) -> Result<(), Box<dyn Error + 'static>> {
+        let administrator_keypair = Self::load_keypair_from_file(&administrator_keypair_file_path)?;
+        let administrator_pubkey = administrator_keypair.pubkey();
+        let (config_pubkey, config) = Self::get_config(rpc_client)?;
+        if administrator_pubkey != config.administrator_pubkey {
+            return Err(Self::ERROR_CONFIG_INVALID_ADMINISTRATOR.into());
+        }
+        let instructions = vec![
+            Instruction::sweep_fees(
+                &PROGRAM_PUBKEY,
+                &administrator_pubkey,
+                &config_pubkey,
+                &config.fee_vault_pubkey,
+                &config.fee_recipient_pubkey,
+            )?,
+        ];
+        let signers = vec![&administrator_keypair];
+        let recent_blockhash = rpc_client.get_latest_blockhash()?;
+        let message = Message::new_with_blockhash(
+            instructions.as_slice(),
+            Some(&administrator_pubkey),
+            &recent_blockhash,
+        );
+        let transaction = Transaction::new(
+            signers.as_slice(),
+            message,
+            recent_blockhash,
+        );
+        let signature = rpc_client.send_transaction(&transaction)?;
+        println!("Signature: {}", &signature);
+        Ok(())
+    }
+    pub fn buy_on_raydium_exact_out<'a>(
+        rpc_client: &'a RpcClient,
+        intermediary_trader_keypair_file_path: &'a str,
+        intermediary_pubkey: &'a str,
+        any_mint_token_mint_pubkey: &'a str,
+        amm_pool_pubkey: &'a str,
+        maximum_amount_in: u64,
+        amount_out: u64,
+    ) -> Result<(), Box<dyn Error + 'static>> {
+        let intermediary_trader_keypair = Self::load_keypair_from_file(&intermediary_trader_keypair_file_path)?;
+        let intermediary_trader_pubkey = intermediary_trader_keypair.pubkey();
+        let intermediary_pubkey = Pubkey::from_str(intermediary_pubkey)?;
+        let any_mint_token_mint_pubkey = Pubkey::from_str(any_mint_token_mint_pubkey)?;
+        let amm_pool_pubkey = Pubkey::from_str(amm_pool_pubkey)?;
+        if any_mint_token_mint_pubkey == spl_token::native_mint::ID {
+            return Err(Self::ERROR_INVALID_ACCOUNT_PUBKEY.into());
+        }
+        let intermediary_trader_account = rpc_client.get_account(&intermediary_trader_pubkey)?;
+        let token_account_rent_exemption_balance = rpc_client.get_minimum_balance_for_rent_exemption(<Account as Pack>::LEN)?;
+        let (config_pubkey, config) = Self::get_config(rpc_client)?;
+        if intermediary_trader_account.lamports < (2 * token_account_rent_exemption_balance + config.fees.buy_on_raydium) {
+            return Err(Self::ERROR_INVALID_ACCOUNT_LAMPORTS.into());
+        }
+        let intermediary = Intermediary::load(rpc_client.get_account(&intermediary_pubkey)?.data.as_slice())?;
+        if !intermediary.is_initialized {
+            return Err(Self::ERROR_INTERMEDIARY_IS_NOT_INITIALIZED.into());
+        }
+        if intermediary.is_paused {
+            return Err(Self::ERROR_INTERMEDIARY_PAUSED.into());
+        }
+        let (trader_seat_pubkey, trader_seat) = Self::get_trader_seat(
+            rpc_client,
+            &intermediary_pubkey,
+            &intermediary_trader_pubkey,
+        )?;
+        if trader_seat.spent_lamports.saturating_add(maximum_amount_in) > trader_seat.allocated_lamports.saturating_add(trader_seat.received_lamports) {
+            return Err(Self::ERROR_TRADER_SEAT_ALLOCATION_EXCEEDED.into());
+        }
+        let w_sol_token_account = Account::unpack_unchecked(rpc_client.get_account(&intermediary.w_sol_token_account_pubkey)?.data.as_slice())?;
+        if w_sol_token_account.amount < maximum_amount_in {
+            return Err(
+                format!(
+                    "The maximum number of lamports from treasury is {}",
+                    w_sol_token_account.amount,
+                )
+                .into(),
+            );
+        }
+        let raydium_amm_accounts = Self::resolve_raydium_amm_accounts(
+            rpc_client,
+            &amm_pool_pubkey,
+            &any_mint_token_mint_pubkey,
+            &spl_token::native_mint::ID,
+        )?;
+        let any_mint_token_account_address_resolver = AnyMintTokenAccountAddressResolver {
+            any_mint_token_mint_pubkey: &any_mint_token_mint_pubkey,
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+        let (any_mint_token_account_pubkey, any_mint_token_account_pubkey_bump_seed) = any_mint_token_account_address_resolver.find();
+        let any_mint_token_account_authority_address_resolver = AnyMintTokenAccountAuthorityAddressResolver {
+            any_mint_token_account_pubkey: &any_mint_token_account_pubkey,
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+        let (any_mint_token_account_authority_pubkey, any_mint_token_account_authority_pubkey_bump_seed) = any_mint_token_account_authority_address_resolver.find();
+        let allowed_mints_address_resolver = AllowedMintsAddressResolver {
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+        let (allowed_mints_pubkey, _) = allowed_mints_address_resolver.find();
+        let risk_limits_address_resolver = RiskLimitsAddressResolver {
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+        let (risk_limits_pubkey, _) = risk_limits_address_resolver.find();
+        let instructions = vec![
+            Instruction::buy_on_raydium_exact_out(
+                &PROGRAM_PUBKEY,
+                &intermediary_trader_pubkey,
+                &intermediary_pubkey,
+                &intermediary.w_sol_token_account_pubkey,
+                &intermediary.w_sol_token_account_authority_pubkey,
+                &intermediary.temporary_w_sol_token_account_pubkey,
+                &intermediary.temporary_w_sol_token_account_authority_pubkey,
+                &any_mint_token_account_pubkey,
+                &any_mint_token_account_authority_pubkey,
+                &any_mint_token_mint_pubkey,
+                &RAYDIUM_LIQUIDITY_POOL_V4_PROGRAM_PUBKEY,
+                &amm_pool_pubkey,
+                &raydium_amm_accounts.amm_authority_pubkey,
+                &raydium_amm_accounts.amm_open_orders_pubkey,
+                &raydium_amm_accounts.amm_coin_vault_pubkey,
+                &raydium_amm_accounts.amm_pc_vault_pubkey,
+                &raydium_amm_accounts.market_program_id_pubkey,
+                &raydium_amm_accounts.market_pubkey,
+                &raydium_amm_accounts.market_bids_pubkey,
+                &raydium_amm_accounts.market_asks_pubkey,
+                &raydium_amm_accounts.market_event_queue_pubkey,
+                &raydium_amm_accounts.market_coin_vault_pubkey,
+                &raydium_amm_accounts.market_pc_vault_pubkey,
+                &raydium_amm_accounts.market_vault_signer_pubkey,
+                &allowed_mints_pubkey,
+                &risk_limits_pubkey,
+                &trader_seat_pubkey,
+                &config_pubkey,
+                &config.fee_vault_pubkey,
+                maximum_amount_in,
+                amount_out,
+                any_mint_token_account_pubkey_bump_seed,
+                any_mint_token_account_authority_pubkey_bump_seed,
+            )?,
+        ];
+        let signers = vec![&intermediary_trader_keypair];
+        let recent_blockhash = rpc_client.get_latest_blockhash()?;
+        let message = Message::new_with_blockhash(
+            instructions.as_slice(),
+            Some(&intermediary_trader_pubkey),
+            &recent_blockhash,
+        );
+        let transaction = Transaction::new(
+            signers.as_slice(),
+            message,
+            recent_blockhash,
+        );
+        let signature = rpc_client.send_transaction(&transaction)?;
+        println!("Signature: {}", &signature);
+        Ok(())
+    }
+    pub fn sell_on_raydium_exact_out<'a>(
+        rpc_client: &'a RpcClient,
+        intermediary_trader_keypair_file_path: &'a str,
+        intermediary_pubkey: &'a str,
+        any_mint_token_mint_pubkey: &'a str,
+        amm_pool_pubkey: &'a str,
+        maximum_amount_in: u64,
+        amount_out: u64,
+    ) -> Result<(), Box<dyn Error + 'static>> {
+        let intermediary_trader_keypair = Self::load_keypair_from_file(&intermediary_trader_keypair_file_path)?;
+        let intermediary_trader_pubkey = intermediary_trader_keypair.pubkey();
+        let intermediary_pubkey = Pubkey::from_str(intermediary_pubkey)?;
+        let any_mint_token_mint_pubkey = Pubkey::from_str(any_mint_token_mint_pubkey)?;
+        let amm_pool_pubkey = Pubkey::from_str(amm_pool_pubkey)?;
+        if any_mint_token_mint_pubkey == spl_token::native_mint::ID {
+            return Err(Self::ERROR_INVALID_ACCOUNT_PUBKEY.into());
+        }
+        let intermediary_trader_account = rpc_client.get_account(&intermediary_trader_pubkey)?;
+        let (config_pubkey, config) = Self::get_config(rpc_client)?;
+        if intermediary_trader_account.lamports < config.fees.sell_on_raydium {
+            return Err(Self::ERROR_INVALID_ACCOUNT_LAMPORTS.into());
+        }
+        let intermediary = Intermediary::load(rpc_client.get_account(&intermediary_pubkey)?.data.as_slice())?;
+        if !intermediary.is_initialized {
+            return Err(Self::ERROR_INTERMEDIARY_IS_NOT_INITIALIZED.into());
+        }
+        if intermediary.is_paused {
+            return Err(Self::ERROR_INTERMEDIARY_PAUSED.into());
+        }
+        let (trader_seat_pubkey, _) = Self::get_trader_seat(
+            rpc_client,
+            &intermediary_pubkey,
+            &intermediary_trader_pubkey,
+        )?;
+        let any_mint_token_account_address_resolver = AnyMintTokenAccountAddressResolver {
+            any_mint_token_mint_pubkey: &any_mint_token_mint_pubkey,
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+        let (any_mint_token_account_pubkey, any_mint_token_account_pubkey_bump_seed) = any_mint_token_account_address_resolver.find();
+        let any_mint_token_account = Account::unpack_unchecked(rpc_client.get_account(&any_mint_token_account_pubkey)?.data.as_slice())?;
+        if any_mint_token_account.amount < maximum_amount_in {
+            return Err(
+                format!(
+                    "The maximum number of tokens to sell is {}",
+                    any_mint_token_account.amount,
+                )
+                .into(),
+            );
+        }
+        let raydium_amm_accounts = Self::resolve_raydium_amm_accounts(
+            rpc_client,
+            &amm_pool_pubkey,
+            &any_mint_token_mint_pubkey,
+            &spl_token::native_mint::ID,
+        )?;
+        let any_mint_token_account_authority_address_resolver = AnyMintTokenAccountAuthorityAddressResolver {
+            any_mint_token_account_pubkey: &any_mint_token_account_pubkey,
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+        let (any_mint_token_account_authority_pubkey, any_mint_token_account_authority_pubkey_bump_seed) = any_mint_token_account_authority_address_resolver.find();
+        let temporary_any_mint_token_account_address_resolver = TemporaryAnyMintTokenAccountAddressResolver {
+            any_mint_token_account_pubkey: &any_mint_token_account_pubkey,
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+        let (temporary_any_mint_token_account_pubkey, temporary_any_mint_token_account_pubkey_bump_seed) = temporary_any_mint_token_account_address_resolver.find();
+        let temporary_any_mint_token_account_authority_address_resolver = TemporaryAnyMintTokenAccountAuthorityAddressResolver {
+            temporary_any_mint_token_account_pubkey: &temporary_any_mint_token_account_pubkey,
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+        let (temporary_any_mint_token_account_authority_pubkey, temporary_any_mint_token_account_authority_pubkey_bump_seed) =
+            temporary_any_mint_token_account_authority_address_resolver.find();
+        let risk_limits_address_resolver = RiskLimitsAddressResolver {
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+        let (risk_limits_pubkey, _) = risk_limits_address_resolver.find();
+        let instructions = vec![
+            Instruction::sell_on_raydium_exact_out(
+                &PROGRAM_PUBKEY,
+                &intermediary_trader_pubkey,
+                &intermediary_pubkey,
+                &intermediary.w_sol_token_account_pubkey,
+                &any_mint_token_account_pubkey,
+                &any_mint_token_account_authority_pubkey,
+                &temporary_any_mint_token_account_pubkey,
+                &temporary_any_mint_token_account_authority_pubkey,
+                &any_mint_token_mint_pubkey,
+                &RAYDIUM_LIQUIDITY_POOL_V4_PROGRAM_PUBKEY,
+                &amm_pool_pubkey,
+                &raydium_amm_accounts.amm_authority_pubkey,
+                &raydium_amm_accounts.amm_open_orders_pubkey,
+                &raydium_amm_accounts.amm_coin_vault_pubkey,
+                &raydium_amm_accounts.amm_pc_vault_pubkey,
+                &raydium_amm_accounts.market_program_id_pubkey,
+                &raydium_amm_accounts.market_pubkey,
+                &raydium_amm_accounts.market_bids_pubkey,
+                &raydium_amm_accounts.market_asks_pubkey,
+                &raydium_amm_accounts.market_event_queue_pubkey,
+                &raydium_amm_accounts.market_coin_vault_pubkey,
+                &raydium_amm_accounts.market_pc_vault_pubkey,
+                &raydium_amm_accounts.market_vault_signer_pubkey,
+                &risk_limits_pubkey,
+                &trader_seat_pubkey,
+                &config_pubkey,
+                &config.fee_vault_pubkey,
+                maximum_amount_in,
+                amount_out,
+                any_mint_token_account_pubkey_bump_seed,
+                any_mint_token_account_authority_pubkey_bump_seed,
+                temporary_any_mint_token_account_pubkey_bump_seed,
+                temporary_any_mint_token_account_authority_pubkey_bump_seed,
+            )?,
+        ];
+        let signers = vec![&intermediary_trader_keypair];
+        let recent_blockhash = rpc_client.get_latest_blockhash()?;
+        let message = Message::new_with_blockhash(
+            instructions.as_slice(),
+            Some(&intermediary_trader_pubkey),
+            &recent_blockhash,
+        );
+        let transaction = Transaction::new(
+            signers.as_slice(),
+            message,
+            recent_blockhash,
+        );
+        let signature = rpc_client.send_transaction(&transaction)?;
+        println!("Signature: {}", &signature);
+        Ok(())
+    }
+    pub fn set_risk_limits<'a>(
+        rpc_client: &'a RpcClient,
+        intermediary_manager_keypair_file_path: &'a str,
+        intermediary_pubkey: &'a str,
+        maximum_lamports_per_trade: u64,
+        maximum_treasury_share_per_trade_basis_points: u16,
+        maximum_trades_per_slot_window: u64,
+        slot_window_length: u64,
+    ) -> Result<(), Box<dyn Error + 'static>> {
+        let intermediary_manager_keypair = Self::load_keypair_from_file(&intermediary_manager_keypair_file_path)?;
+        let intermediary_manager_pubkey = intermediary_manager_keypair.pubkey();
+        let intermediary_pubkey = Pubkey::from_str(intermediary_pubkey)?;
+        if maximum_treasury_share_per_trade_basis_points > 10_000 || slot_window_length == 0 {
+            return Err(Self::ERROR_RISK_LIMITS_INVALID_PARAMETERS.into());
+        }
+        let intermediary = Intermediary::load(rpc_client.get_account(&intermediary_pubkey)?.data.as_slice())?;
+        if !intermediary.is_initialized {
+            return Err(Self::ERROR_INTERMEDIARY_IS_NOT_INITIALIZED.into());
+        }
+        if intermediary_manager_pubkey != intermediary.manager_pubkey {
+            return Err(Self::ERROR_INTERMEDIARY_INVALID_MANAGER.into());
+        }
+        let risk_limits_address_resolver = RiskLimitsAddressResolver {
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+        let (risk_limits_pubkey, risk_limits_pubkey_bump_seed) = risk_limits_address_resolver.find();
+        let instructions = vec![
+            Instruction::set_risk_limits(
+                &PROGRAM_PUBKEY,
+                &intermediary_manager_pubkey,
+                &intermediary_pubkey,
+                &risk_limits_pubkey,
+                maximum_lamports_per_trade,
+                maximum_treasury_share_per_trade_basis_points,
+                maximum_trades_per_slot_window,
+                slot_window_length,
+                risk_limits_pubkey_bump_seed,
+            )?,
+        ];
+        let signers = vec![&intermediary_manager_keypair];
+        let recent_blockhash = rpc_client.get_latest_blockhash()?;
+        let message = Message::new_with_blockhash(
+            instructions.as_slice(),
+            Some(&intermediary_manager_pubkey),
+            &recent_blockhash,
+        );
+        let transaction = Transaction::new(
+            signers.as_slice(),
+            message,
+            recent_blockhash,
+        );
+        let signature = rpc_client.send_transaction(&transaction)?;
+        println!("Signature: {}", &signature);
+        Ok(())
+    }
+    pub fn set_maximum_price_deviation<'a>(
+        rpc_client: &'a RpcClient,
+        intermediary_manager_keypair_file_path: &'a str,
+        intermediary_pubkey: &'a str,
+        maximum_price_deviation_basis_points: u16,
+    ) -> Result<(), Box<dyn Error + 'static>> {
+        let intermediary_manager_keypair = Self::load_keypair_from_file(&intermediary_manager_keypair_file_path)?;
+        let intermediary_manager_pubkey = intermediary_manager_keypair.pubkey();
+        let intermediary_pubkey = Pubkey::from_str(intermediary_pubkey)?;
+        if maximum_price_deviation_basis_points > 10_000 {
+            return Err(Self::ERROR_INTERMEDIARY_INVALID_MAXIMUM_PRICE_DEVIATION.into());
+        }
+        let intermediary = Intermediary::load(rpc_client.get_account(&intermediary_pubkey)?.data.as_slice())?;
+        if !intermediary.is_initialized {
+            return Err(Self::ERROR_INTERMEDIARY_IS_NOT_INITIALIZED.into());
+        }
+        if intermediary.version != Intermediary::VERSION {
+            return Err(Self::ERROR_INTERMEDIARY_IS_NOT_MIGRATED.into());
+        }
+        if intermediary_manager_pubkey != intermediary.manager_pubkey {
+            return Err(Self::ERROR_INTERMEDIARY_INVALID_MANAGER.into());
+        }
+        let instructions = vec![
+            Instruction::set_maximum_price_deviation(
+                &PROGRAM_PUBKEY,
+                &intermediary_manager_pubkey,
+                &intermediary_pubkey,
+                maximum_price_deviation_basis_points,
+            )?,
+        ];
+        let signers = vec![&intermediary_manager_keypair];
+        let recent_blockhash = rpc_client.get_latest_blockhash()?;
+        let message = Message::new_with_blockhash(
+            instructions.as_slice(),
+            Some(&intermediary_manager_pubkey),
+            &recent_blockhash,
+        );
+        let transaction = Transaction::new(
+            signers.as_slice(),
+            message,
+            recent_blockhash,
+        );
+        let signature = rpc_client.send_transaction(&transaction)?;
+        println!("Signature: {}", &signature);
+        Ok(())
+    }
+    pub fn add_allowed_mint<'a>(
+        rpc_client: &'a RpcClient,
+        intermediary_manager_keypair_file_path: &'a str,
+        intermediary_pubkey: &'a str,
+        any_mint_token_mint_pubkey: &'a str,
+    ) -> Result<(), Box<dyn Error + 'static>> {
+        let intermediary_manager_keypair = Self::load_keypair_from_file(&intermediary_manager_keypair_file_path)?;
+        let intermediary_manager_pubkey = intermediary_manager_keypair.pubkey();
+        let intermediary_pubkey = Pubkey::from_str(intermediary_pubkey)?;
+        let any_mint_token_mint_pubkey = Pubkey::from_str(any_mint_token_mint_pubkey)?;
+        if any_mint_token_mint_pubkey == spl_token::native_mint::ID {
+            return Err(Self::ERROR_INVALID_ACCOUNT_PUBKEY.into());
+        }
+        let intermediary = Intermediary::load(rpc_client.get_account(&intermediary_pubkey)?.data.as_slice())?;
+        if !intermediary.is_initialized {
+            return Err(Self::ERROR_INTERMEDIARY_IS_NOT_INITIALIZED.into());
+        }
+        if intermediary_manager_pubkey != intermediary.manager_pubkey {
+            return Err(Self::ERROR_INTERMEDIARY_INVALID_MANAGER.into());
+        }
+        let allowed_mints_address_resolver = AllowedMintsAddressResolver {
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+        let (allowed_mints_pubkey, allowed_mints_pubkey_bump_seed) = allowed_mints_address_resolver.find();
+        let any_mint_token_program_pubkey = rpc_client.get_account(&any_mint_token_mint_pubkey)?.owner;
+        if any_mint_token_program_pubkey != spl_token::ID && any_mint_token_program_pubkey != spl_token_2022::ID {
+            return Err(Self::ERROR_INVALID_ACCOUNT_OWNER.into());
+        }
+        if let Ok(allowed_mints_account) = rpc_client.get_account(&allowed_mints_pubkey) {
+            let allowed_mints = borsh::from_slice::<AllowedMints>(allowed_mints_account.data.as_slice())?;
+            if allowed_mints.mint_pubkeys.contains(&any_mint_token_mint_pubkey) {
+                return Err(Self::ERROR_ALLOWED_MINTS_ALREADY_CONTAINS_MINT.into());
+            }
+        }
+        let instructions = vec![
+            Instruction::add_allowed_mint(
+                &PROGRAM_PUBKEY,
+                &intermediary_manager_pubkey,
+                &intermediary_pubkey,
+                &allowed_mints_pubkey,
+                &any_mint_token_mint_pubkey,
+                allowed_mints_pubkey_bump_seed,
+            )?,
+        ];
+        let signers = vec![&intermediary_manager_keypair];
+        let recent_blockhash = rpc_client.get_latest_blockhash()?;
+        let message = Message::new_with_blockhash(
+            instructions.as_slice(),
+            Some(&intermediary_manager_pubkey),
+            &recent_blockhash,
+        );
+        let transaction = Transaction::new(
+            signers.as_slice(),
+            message,
+            recent_blockhash,
+        );
+        let signature = rpc_client.send_transaction(&transaction)?;
+        println!("Signature: {}", &signature);
+        Ok(())
+    }
+    pub fn remove_allowed_mint<'a>(
+        rpc_client: &'a RpcClient,
+        intermediary_manager_keypair_file_path: &'a str,
+        intermediary_pubkey: &'a str,
+        any_mint_token_mint_pubkey: &'a str,
+    ) -> Result<(), Box<dyn Error + 'static>> {
+        let intermediary_manager_keypair = Self::load_keypair_from_file(&intermediary_manager_keypair_file_path)?;
+        let intermediary_manager_pubkey = intermediary_manager_keypair.pubkey();
+        let intermediary_pubkey = Pubkey::from_str(intermediary_pubkey)?;
+        let any_mint_token_mint_pubkey = Pubkey::from_str(any_mint_token_mint_pubkey)?;
+        if any_mint_token_mint_pubkey == spl_token::native_mint::ID {
+            return Err(Self::ERROR_INVALID_ACCOUNT_PUBKEY.into());
+        }
+        let intermediary = Intermediary::load(rpc_client.get_account(&intermediary_pubkey)?.data.as_slice())?;
+        if !intermediary.is_initialized {
+            return Err(Self::ERROR_INTERMEDIARY_IS_NOT_INITIALIZED.into());
+        }
+        if intermediary_manager_pubkey != intermediary.manager_pubkey {
+            return Err(Self::ERROR_INTERMEDIARY_INVALID_MANAGER.into());
+        }
+        let allowed_mints_address_resolver = AllowedMintsAddressResolver {
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+        let (allowed_mints_pubkey, _) = allowed_mints_address_resolver.find();
+        let allowed_mints = borsh::from_slice::<AllowedMints>(rpc_client.get_account(&allowed_mints_pubkey)?.data.as_slice())?;
+        if !allowed_mints.mint_pubkeys.contains(&any_mint_token_mint_pubkey) {
+            return Err(Self::ERROR_ALLOWED_MINTS_DOES_NOT_CONTAIN_MINT.into());
+        }
+        let instructions = vec![
+            Instruction::remove_allowed_mint(
+                &PROGRAM_PUBKEY,
+                &intermediary_manager_pubkey,
+                &intermediary_pubkey,
+                &allowed_mints_pubkey,
+                &any_mint_token_mint_pubkey,
+            )?,
+        ];
+        let signers = vec![&intermediary_manager_keypair];
+        let recent_blockhash = rpc_client.get_latest_blockhash()?;
+        let message = Message::new_with_blockhash(
+            instructions.as_slice(),
+            Some(&intermediary_manager_pubkey),
+            &recent_blockhash,
+        );
+        let transaction = Transaction::new(
+            signers.as_slice(),
+            message,
+            recent_blockhash,
+        );
+        let signature = rpc_client.send_transaction(&transaction)?;
+        println!("Signature: {}", &signature);
+        Ok(())
+    }
+    pub fn add_trader_seat<'a>(
+        rpc_client: &'a RpcClient,
+        intermediary_manager_keypair_file_path: &'a str,
+        intermediary_pubkey: &'a str,
+        intermediary_trader_pubkey: &'a str,
+        allocated_lamports: u64,
+    ) -> Result<(), Box<dyn Error + 'static>> {
+        let intermediary_manager_keypair = Self::load_keypair_from_file(&intermediary_manager_keypair_file_path)?;
+        let intermediary_manager_pubkey = intermediary_manager_keypair.pubkey();
+        let intermediary_pubkey = Pubkey::from_str(intermediary_pubkey)?;
+        let intermediary_trader_pubkey = Pubkey::from_str(intermediary_trader_pubkey)?;
+        let mut account_differentiator = HashSet::<&'_ Pubkey>::with_capacity(3);
+        if !account_differentiator.insert(&intermediary_manager_pubkey)
+            || !account_differentiator.insert(&intermediary_pubkey)
+            || !account_differentiator.insert(&intermediary_trader_pubkey)
+        {
+            return Err(Self::ERROR_INVALID_ACCOUNT_PUBKEY.into());
+        }
+        if rpc_client.get_account(&intermediary_trader_pubkey)?.owner != solana_sdk::system_program::ID {
+            return Err(Self::ERROR_INVALID_ACCOUNT_OWNER.into());
+        }
+        let intermediary = Intermediary::load(rpc_client.get_account(&intermediary_pubkey)?.data.as_slice())?;
+        if !intermediary.is_initialized {
+            return Err(Self::ERROR_INTERMEDIARY_IS_NOT_INITIALIZED.into());
+        }
+        if intermediary_manager_pubkey != intermediary.manager_pubkey {
+            return Err(Self::ERROR_INTERMEDIARY_INVALID_MANAGER.into());
+        }
+        let trader_seat_address_resolver = TraderSeatAddressResolver {
+            intermediary_pubkey: &intermediary_pubkey,
+            trader_pubkey: &intermediary_trader_pubkey,
+        };
+        let (trader_seat_pubkey, trader_seat_pubkey_bump_seed) = trader_seat_address_resolver.find();
+        if rpc_client.get_account(&trader_seat_pubkey).is_ok() {
+            return Err(Self::ERROR_TRADER_SEAT_IS_ALREADY_INITIALIZED.into());
+        }
+        let instructions = vec![
+            Instruction::add_trader_seat(
+                &PROGRAM_PUBKEY,
+                &intermediary_manager_pubkey,
+                &intermediary_pubkey,
+                &intermediary_trader_pubkey,
+                &trader_seat_pubkey,
+                allocated_lamports,
+                trader_seat_pubkey_bump_seed,
+            )?,
+        ];
+        let signers = vec![&intermediary_manager_keypair];
+        let recent_blockhash = rpc_client.get_latest_blockhash()?;
+        let message = Message::new_with_blockhash(
+            instructions.as_slice(),
+            Some(&intermediary_manager_pubkey),
+            &recent_blockhash,
+        );
+        let transaction = Transaction::new(
+            signers.as_slice(),
+            message,
+            recent_blockhash,
+        );
+        let signature = rpc_client.send_transaction(&transaction)?;
+        println!("Signature: {}", &signature);
+        Ok(())
+    }
+    pub fn remove_trader_seat<'a>(
+        rpc_client: &'a RpcClient,
+        intermediary_manager_keypair_file_path: &'a str,
+        intermediary_pubkey: &'a str,
+        intermediary_trader_pubkey: &'a str,
+    ) -> Result<(), Box<dyn Error + 'static>> {
+        let intermediary_manager_keypair = Self::load_keypair_from_file(&intermediary_manager_keypair_file_path)?;
+        let intermediary_manager_pubkey = intermediary_manager_keypair.pubkey();
+        let intermediary_pubkey = Pubkey::from_str(intermediary_pubkey)?;
+        let intermediary_trader_pubkey = Pubkey::from_str(intermediary_trader_pubkey)?;
+        let intermediary = Intermediary::load(rpc_client.get_account(&intermediary_pubkey)?.data.as_slice())?;
+        if !intermediary.is_initialized {
+            return Err(Self::ERROR_INTERMEDIARY_IS_NOT_INITIALIZED.into());
+        }
+        if intermediary_manager_pubkey != intermediary.manager_pubkey {
+            return Err(Self::ERROR_INTERMEDIARY_INVALID_MANAGER.into());
+        }
+        let (trader_seat_pubkey, trader_seat) = Self::get_trader_seat(
+            rpc_client,
+            &intermediary_pubkey,
+            &intermediary_trader_pubkey,
+        )?;
+        let instructions = vec![
+            Instruction::remove_trader_seat(
+                &PROGRAM_PUBKEY,
+                &intermediary_manager_pubkey,
+                &intermediary_pubkey,
+                &trader_seat_pubkey,
+            )?,
+        ];
+        let signers = vec![&intermediary_manager_keypair];
+        let recent_blockhash = rpc_client.get_latest_blockhash()?;
+        let message = Message::new_with_blockhash(
+            instructions.as_slice(),
+            Some(&intermediary_manager_pubkey),
+            &recent_blockhash,
+        );
+        let transaction = Transaction::new(
+            signers.as_slice(),
+            message,
+            recent_blockhash,
+        );
+        let signature = rpc_client.send_transaction(&transaction)?;
+        println!("Signature: {}", &signature);
+        println!(
+            "Spent lamports: {}. Received lamports: {}.",
+            trader_seat.spent_lamports,
+            trader_seat.received_lamports,
+        );
+        Ok(())
+    }
+    pub fn pause<'a>(
+        rpc_client: &'a RpcClient,
+        authority_keypair_file_path: &'a str,
+        intermediary_pubkey: &'a str,
+    ) -> Result<(), Box<dyn Error + 'static>> {
+        let authority_keypair = Self::load_keypair_from_file(&authority_keypair_file_path)?;
+        let authority_pubkey = authority_keypair.pubkey();
+        let intermediary_pubkey = Pubkey::from_str(intermediary_pubkey)?;
+        let intermediary = Intermediary::load(rpc_client.get_account(&intermediary_pubkey)?.data.as_slice())?;
+        if !intermediary.is_initialized {
+            return Err(Self::ERROR_INTERMEDIARY_IS_NOT_INITIALIZED.into());
+        }
+        if authority_pubkey != intermediary.investor_pubkey && authority_pubkey != intermediary.manager_pubkey {
+            return Err(Self::ERROR_INTERMEDIARY_INVALID_INVESTOR.into());
+        }
+        if intermediary.is_paused {
+            return Err(Self::ERROR_INTERMEDIARY_PAUSED.into());
+        }
+        let instructions = vec![
+            Instruction::pause(
+                &PROGRAM_PUBKEY,
+                &authority_pubkey,
+                &intermediary_pubkey,
+            )?,
+        ];
+        let signers = vec![&authority_keypair];
+        let recent_blockhash = rpc_client.get_latest_blockhash()?;
+        let message = Message::new_with_blockhash(
+            instructions.as_slice(),
+            Some(&authority_pubkey),
+            &recent_blockhash,
+        );
+        let transaction = Transaction::new(
+            signers.as_slice(),
+            message,
+            recent_blockhash,
+        );
+        let signature = rpc_client.send_transaction(&transaction)?;
+        println!("Signature: {}", &signature);
+        Ok(())
+    }
+    pub fn unpause<'a>(
+        rpc_client: &'a RpcClient,
+        intermediary_investor_keypair_file_path: &'a str,
+        intermediary_pubkey: &'a str,
+    ) -> Result<(), Box<dyn Error + 'static>> {
+        let intermediary_investor_keypair = Self::load_keypair_from_file(&intermediary_investor_keypair_file_path)?;
+        let intermediary_investor_pubkey = intermediary_investor_keypair.pubkey();
+        let intermediary_pubkey = Pubkey::from_str(intermediary_pubkey)?;
+        let intermediary = Intermediary::load(rpc_client.get_account(&intermediary_pubkey)?.data.as_slice())?;
+        if !intermediary.is_initialized {
+            return Err(Self::ERROR_INTERMEDIARY_IS_NOT_INITIALIZED.into());
+        }
+        if intermediary_investor_pubkey != intermediary.investor_pubkey {
+            return Err(Self::ERROR_INTERMEDIARY_INVALID_INVESTOR.into());
+        }
+        if !intermediary.is_paused {
+            return Err(Self::ERROR_INTERMEDIARY_IS_NOT_PAUSED.into());
+        }
+        let instructions = vec![
+            Instruction::unpause(
+                &PROGRAM_PUBKEY,
+                &intermediary_investor_pubkey,
+                &intermediary_pubkey,
+            )?,
+        ];
+        let signers = vec![&intermediary_investor_keypair];
+        let recent_blockhash = rpc_client.get_latest_blockhash()?;
+        let message = Message::new_with_blockhash(
+            instructions.as_slice(),
+            Some(&intermediary_investor_pubkey),
+            &recent_blockhash,
+        );
+        let transaction = Transaction::new(
+            signers.as_slice(),
+            message,
+            recent_blockhash,
+        );
+        let signature = rpc_client.send_transaction(&transaction)?;
+        println!("Signature: {}", &signature);
+        Ok(())
+    }
+    pub fn migrate_intermediary<'a>(
+        rpc_client: &'a RpcClient,
+        authority_keypair_file_path: &'a str,
+        intermediary_pubkey: &'a str,
+    ) -> Result<(), Box<dyn Error + 'static>> {
+        let authority_keypair = Self::load_keypair_from_file(&authority_keypair_file_path)?;
+        let authority_pubkey = authority_keypair.pubkey();
+        let intermediary_pubkey = Pubkey::from_str(intermediary_pubkey)?;
+        let intermediary = Intermediary::load(rpc_client.get_account(&intermediary_pubkey)?.data.as_slice())?;
+        if !intermediary.is_initialized {
+            return Err(Self::ERROR_INTERMEDIARY_IS_NOT_INITIALIZED.into());
+        }
+        if authority_pubkey != intermediary.investor_pubkey && authority_pubkey != intermediary.manager_pubkey {
+            return Err(Self::ERROR_INTERMEDIARY_INVALID_INVESTOR.into());
+        }
+        if intermediary.version == Intermediary::VERSION {
+            return Err(Self::ERROR_INTERMEDIARY_IS_ALREADY_MIGRATED.into());
+        }
+        let instructions = vec![
+            Instruction::migrate_intermediary(
+                &PROGRAM_PUBKEY,
+                &authority_pubkey,
+                &intermediary_pubkey,
+            )?,
+        ];
+        let signers = vec![&authority_keypair];
+        let recent_blockhash = rpc_client.get_latest_blockhash()?;
+        let message = Message::new_with_blockhash(
+            instructions.as_slice(),
+            Some(&authority_pubkey),
+            &recent_blockhash,
+        );
+        let transaction = Transaction::new(
+            signers.as_slice(),
+            message,
+            recent_blockhash,
+        );
+        let signature = rpc_client.send_transaction(&transaction)?;
+        println!("Signature: {}", &signature);
+        Ok(())
+    }
+    pub fn liquidate_all<'a>(
+        rpc_client: &'a RpcClient,
+        authority_keypair_file_path: &'a str,
+        intermediary_pubkey: &'a str,
+        any_mint_token_mint_pubkeys: &'a str,
+        venues: &'a str,
+        pool_pubkeys: &'a str,
+        minimum_output_token_amounts: &'a str,
+    ) -> Result<(), Box<dyn Error + 'static>> {
+        let authority_keypair = Self::load_keypair_from_file(&authority_keypair_file_path)?;
+        let authority_pubkey = authority_keypair.pubkey();
+        let intermediary_pubkey = Pubkey::from_str(intermediary_pubkey)?;
+        let any_mint_token_mint_pubkeys = any_mint_token_mint_pubkeys.split(',').map(Pubkey::from_str).collect::<Result<Vec<Pubkey>, _>>()?;
+        let venues = venues
+            .split(',')
+            .map(
+                |venue| {
+                    match venue {
+                        "raydium" => Ok(Venue::Raydium),
+                        "pumpswap" => Ok(Venue::Pumpswap),
+                        _ => Err(Self::ERROR_VENUE_IS_NOT_SUPPORTED),
+                    }
+                },
+            )
+            .collect::<Result<Vec<Venue>, _>>()?;
+        let pool_pubkeys = pool_pubkeys.split(',').map(Pubkey::from_str).collect::<Result<Vec<Pubkey>, _>>()?;
+        let minimum_output_token_amounts = minimum_output_token_amounts.split(',').map(str::parse::<u64>).collect::<Result<Vec<u64>, _>>()?;
+        if any_mint_token_mint_pubkeys.len() != venues.len() || any_mint_token_mint_pubkeys.len() != pool_pubkeys.len() || any_mint_token_mint_pubkeys.len() != minimum_output_token_amounts.len() {
+            return Err("The quantities of mints, venues, pools and minimum output token amounts must be equal.".into());
+        }
+        let intermediary = Intermediary::load(rpc_client.get_account(&intermediary_pubkey)?.data.as_slice())?;
+        if !intermediary.is_initialized {
+            return Err(Self::ERROR_INTERMEDIARY_IS_NOT_INITIALIZED.into());
+        }
+        if authority_pubkey != intermediary.investor_pubkey && authority_pubkey != intermediary.manager_pubkey {
+            return Err(Self::ERROR_INTERMEDIARY_INVALID_INVESTOR_OR_MANAGER.into());
+        }
+        let mut liquidation_legs = Vec::<LiquidationLeg>::with_capacity(any_mint_token_mint_pubkeys.len());
+        for (((any_mint_token_mint_pubkey, venue), pool_pubkey), minimum_output_token_amount) in any_mint_token_mint_pubkeys
+            .into_iter()
+            .zip(venues)
+            .zip(pool_pubkeys)
+            .zip(minimum_output_token_amounts)
+        {
+            if any_mint_token_mint_pubkey == spl_token::native_mint::ID {
+                return Err(Self::ERROR_INVALID_ACCOUNT_PUBKEY.into());
+            }
+            let (any_mint_token_account_pubkey, _) = AnyMintTokenAccountAddressResolver {
+                any_mint_token_mint_pubkey: &any_mint_token_mint_pubkey,
+                intermediary_pubkey: &intermediary_pubkey,
+            }
+            .find();
+            let any_mint_token_account_account = rpc_client.get_account(&any_mint_token_account_pubkey)?;
+            let any_mint_token_program_pubkey = any_mint_token_account_account.owner;
+            let any_mint_token_account = StateWithExtensions::<spl_token_2022::state::Account>::unpack(any_mint_token_account_account.data.as_slice())?.base;
+            let (any_mint_token_account_authority_pubkey, _) = AnyMintTokenAccountAuthorityAddressResolver {
+                any_mint_token_account_pubkey: &any_mint_token_account_pubkey,
+                intermediary_pubkey: &intermediary_pubkey,
+            }
+            .find();
+            if any_mint_token_account.owner != any_mint_token_account_authority_pubkey {
+                return Err(
+                    format!(
+                        "The token account {} is not owned by the intermediary",
+                        &any_mint_token_account_pubkey,
+                    )
+                    .into(),
+                );
+            }
+            let (_, venue_account_pubkeys) = Self::resolve_venue_account_pubkeys(
+                rpc_client,
+                venue,
+                &pool_pubkey,
+                &any_mint_token_mint_pubkey,
+                &spl_token::native_mint::ID,
+            )?;
+            liquidation_legs.push(
+                LiquidationLeg {
+                    any_mint_token_mint_pubkey,
+                    any_mint_token_account_pubkey,
+                    any_mint_token_account_authority_pubkey,
+                    any_mint_token_program_pubkey,
+                    venue,
+                    venue_account_pubkeys,
+                    minimum_output_token_amount,
+                },
+            );
+        }
+        let instructions = vec![
+            Instruction::liquidate_all(
+                &PROGRAM_PUBKEY,
+                &authority_pubkey,
+                &intermediary_pubkey,
+                &intermediary.w_sol_token_account_pubkey,
+                &intermediary.temporary_w_sol_token_account_pubkey,
+                liquidation_legs.as_slice(),
+            )?,
+        ];
+        let signers = vec![&authority_keypair];
+        let recent_blockhash = rpc_client.get_latest_blockhash()?;
+        let message = Message::new_with_blockhash(
+            instructions.as_slice(),
+            Some(&authority_pubkey),
+            &recent_blockhash,
+        );
+        let transaction = Transaction::new(
+            signers.as_slice(),
+            message,
+            recent_blockhash,
+        );
+        let signature = rpc_client.send_transaction(&transaction)?;
+        println!("Signature: {}", &signature);
+        Ok(())
+    }
+    pub fn withdraw_token<'a>(
+        rpc_client: &'a RpcClient,
+        intermediary_investor_keypair_file_path: &'a str,
+        intermediary_pubkey: &'a str,
+        any_mint_token_mint_pubkey: &'a str,
+        amount: u64,
+    ) -> Result<(), Box<dyn Error + 'static>> {
+        let intermediary_investor_keypair = Self::load_keypair_from_file(&intermediary_investor_keypair_file_path)?;
+        let intermediary_investor_pubkey = intermediary_investor_keypair.pubkey();
+        let intermediary_pubkey = Pubkey::from_str(intermediary_pubkey)?;
+        let any_mint_token_mint_pubkey = Pubkey::from_str(any_mint_token_mint_pubkey)?;
+        if any_mint_token_mint_pubkey == spl_token::native_mint::ID {
+            return Err(Self::ERROR_INVALID_ACCOUNT_PUBKEY.into());
+        }
+        let intermediary = Intermediary::load(rpc_client.get_account(&intermediary_pubkey)?.data.as_slice())?;
+        if !intermediary.is_initialized {
+            return Err(Self::ERROR_INTERMEDIARY_IS_NOT_INITIALIZED.into());
+        }
+        if intermediary_investor_pubkey != intermediary.investor_pubkey {
+            return Err(Self::ERROR_INTERMEDIARY_INVALID_INVESTOR.into());
+        }
+        let (any_mint_token_account_pubkey, _) = AnyMintTokenAccountAddressResolver {
+            any_mint_token_mint_pubkey: &any_mint_token_mint_pubkey,
+            intermediary_pubkey: &intermediary_pubkey,
+        }
+        .find();
+        let (any_mint_token_account_authority_pubkey, _) = AnyMintTokenAccountAuthorityAddressResolver {
+            any_mint_token_account_pubkey: &any_mint_token_account_pubkey,
+            intermediary_pubkey: &intermediary_pubkey,
+        }
+        .find();
+        let any_mint_token_program_pubkey = rpc_client.get_account(&any_mint_token_mint_pubkey)?.owner;
+        if any_mint_token_program_pubkey != spl_token::ID && any_mint_token_program_pubkey != spl_token_2022::ID {
+            return Err(Self::ERROR_INVALID_ACCOUNT_OWNER.into());
+        }
+        let any_mint_token_account = StateWithExtensions::<spl_token_2022::state::Account>::unpack(rpc_client.get_account(&any_mint_token_account_pubkey)?.data.as_slice())?.base;
+        if any_mint_token_account.owner != any_mint_token_account_authority_pubkey {
+            return Err(
+                format!(
+                    "The token account {} is not owned by the intermediary",
+                    &any_mint_token_account_pubkey,
+                )
+                .into(),
+            );
+        }
+        if any_mint_token_account.amount < amount {
+            return Err(
+                format!(
+                    "The maximum number of tokens to withdraw is {}",
+                    any_mint_token_account.amount,
+                )
+                .into(),
+            );
+        }
+        let intermediary_investor_token_account_pubkey = simo_intermediary::extern_source::get_associated_token_address(
+            &intermediary_investor_pubkey,
+            &any_mint_token_mint_pubkey,
+            &any_mint_token_program_pubkey,
+        );
+        let instructions = vec![
+            Instruction::withdraw_token(
+                &PROGRAM_PUBKEY,
+                &intermediary_investor_pubkey,
+                &intermediary_pubkey,
+                &any_mint_token_mint_pubkey,
+                &any_mint_token_account_pubkey,
+                &any_mint_token_account_authority_pubkey,
+                &intermediary_investor_token_account_pubkey,
+                &any_mint_token_program_pubkey,
+                amount,
+            )?,
+        ];
+        let signers = vec![&intermediary_investor_keypair];
+        let recent_blockhash = rpc_client.get_latest_blockhash()?;
+        let message = Message::new_with_blockhash(
+            instructions.as_slice(),
+            Some(&intermediary_investor_pubkey),
+            &recent_blockhash,
+        );
+        let transaction = Transaction::new(
+            signers.as_slice(),
+            message,
+            recent_blockhash,
+        );
+        let signature = rpc_client.send_transaction(&transaction)?;
+        println!("Signature: {}", &signature);
+        Ok(())
+    }
+    pub fn close<'a>(
+        rpc_client: &'a RpcClient,
+        intermediary_investor_keypair_file_path: &'a str,
+        intermediary_pubkey: &'a str,
+    ) -> Result<(), Box<dyn Error + 'static>> {
+        let intermediary_investor_keypair = Self::load_keypair_from_file(&intermediary_investor_keypair_file_path)?;
+        let intermediary_investor_pubkey = intermediary_investor_keypair.pubkey();
+        let intermediary_pubkey = Pubkey::from_str(intermediary_pubkey)?;
+        let intermediary = Intermediary::load(rpc_client.get_account(&intermediary_pubkey)?.data.as_slice())?;
+        if !intermediary.is_initialized {
+            return Err(Self::ERROR_INTERMEDIARY_IS_NOT_INITIALIZED.into());
+        }
+        if intermediary_investor_pubkey != intermediary.investor_pubkey {
+            return Err(Self::ERROR_INTERMEDIARY_INVALID_INVESTOR.into());
+        }
+        let (risk_limits_pubkey, _) = RiskLimitsAddressResolver {
+            intermediary_pubkey: &intermediary_pubkey,
+        }
+        .find();
+        let (allowed_mints_pubkey, _) = AllowedMintsAddressResolver {
+            intermediary_pubkey: &intermediary_pubkey,
+        }
+        .find();
+        let mut any_mint_token_accounts = Vec::<AnyMintTokenAccountPubkeys>::new();
+        if let Ok(allowed_mints_account) = rpc_client.get_account(&allowed_mints_pubkey) {
+            let allowed_mints = borsh::from_slice::<AllowedMints>(allowed_mints_account.data.as_slice())?;
+            for any_mint_token_mint_pubkey in allowed_mints.mint_pubkeys.iter() {
+                let (any_mint_token_account_pubkey, _) = AnyMintTokenAccountAddressResolver {
+                    any_mint_token_mint_pubkey,
+                    intermediary_pubkey: &intermediary_pubkey,
+                }
+                .find();
+                let any_mint_token_account_account = match rpc_client.get_account(&any_mint_token_account_pubkey) {
+                    Ok(any_mint_token_account_account_) => any_mint_token_account_account_,
+                    Err(_) => continue,
+                };
+                let any_mint_token_account = StateWithExtensions::<spl_token_2022::state::Account>::unpack(any_mint_token_account_account.data.as_slice())?.base;
+                let (any_mint_token_account_authority_pubkey, _) = AnyMintTokenAccountAuthorityAddressResolver {
+                    any_mint_token_account_pubkey: &any_mint_token_account_pubkey,
+                    intermediary_pubkey: &intermediary_pubkey,
+                }
+                .find();
+                if any_mint_token_account.owner != any_mint_token_account_authority_pubkey {
+                    continue;
+                }
+                if any_mint_token_account.amount != 0 {
+                    return Err(
+                        format!(
+                            "The token account {} still holds {} tokens of mint {}",
+                            any_mint_token_account_pubkey, any_mint_token_account.amount, any_mint_token_mint_pubkey,
+                        )
+                        .into(),
+                    );
+                }
+                any_mint_token_accounts.push(
+                    AnyMintTokenAccountPubkeys {
+                        any_mint_token_mint_pubkey: *any_mint_token_mint_pubkey,
+                        any_mint_token_account_pubkey,
+                        any_mint_token_account_authority_pubkey,
+                        any_mint_token_program_pubkey: any_mint_token_account_account.owner,
+                    },
+                );
+            }
+        }
+        // Positions of mints removed from the allowed mints can not be found here and have to be closed beforehand.
+        if intermediary.open_positions_quantity as usize > any_mint_token_accounts.len() {
+            return Err(Self::ERROR_INTERMEDIARY_HAS_OPEN_POSITIONS.into());
+        }
+        let mut trader_seat_pubkeys = Vec::<Pubkey>::new();
+        for (trader_seat_pubkey, trader_seat_account) in rpc_client.get_program_accounts(&PROGRAM_PUBKEY)?.into_iter() {
+            let trader_seat = match borsh::from_slice::<TraderSeat>(trader_seat_account.data.as_slice()) {
+                Ok(trader_seat_) => trader_seat_,
+                Err(_) => continue,
+            };
+            if !trader_seat.is_initialized || trader_seat.intermediary_pubkey != intermediary_pubkey {
+                continue;
+            }
+            let trader_seat_address_resolver = TraderSeatAddressResolver {
+                intermediary_pubkey: &intermediary_pubkey,
+                trader_pubkey: &trader_seat.trader_pubkey,
+            };
+            if trader_seat_pubkey != trader_seat_address_resolver.create(trader_seat.trader_seat_pubkey_bump_seed)? {
+                continue;
+            }
+            trader_seat_pubkeys.push(trader_seat_pubkey);
+        }
+        if intermediary.trader_seats_quantity as usize > trader_seat_pubkeys.len() {
+            return Err(Self::ERROR_INTERMEDIARY_HAS_TRADER_SEATS.into());
+        }
+        let instructions = vec![
+            Instruction::close_intermediary(
+                &PROGRAM_PUBKEY,
+                &intermediary_investor_pubkey,
+                &intermediary_pubkey,
+                &intermediary.w_sol_token_account_pubkey,
+                &intermediary.w_sol_token_account_authority_pubkey,
+                &risk_limits_pubkey,
+                &allowed_mints_pubkey,
+                any_mint_token_accounts.as_slice(),
+                trader_seat_pubkeys.as_slice(),
+            )?,
+        ];
+        let signers = vec![&intermediary_investor_keypair];
+        let recent_blockhash = rpc_client.get_latest_blockhash()?;
+        let message = Message::new_with_blockhash(
+            instructions.as_slice(),
+            Some(&intermediary_investor_pubkey),
+            &recent_blockhash,
+        );
+        let transaction = Transaction::new(
+            signers.as_slice(),
+            message,
+            recent_blockhash,
+        );
+        let signature = rpc_client.send_transaction(&transaction)?;
+        println!("Signature: {}", &signature);
+        Ok(())
+    }
+    pub fn accept_manager<'a>(
+        rpc_client: &'a RpcClient,
+        intermediary_manager_keypair_file_path: &'a str,
+        intermediary_pubkey: &'a str,
+    ) -> Result<(), Box<dyn Error + 'static>> {
+        let intermediary_manager_keypair = Self::load_keypair_from_file(&intermediary_manager_keypair_file_path)?;
+        let intermediary_manager_pubkey = intermediary_manager_keypair.pubkey();
+        let intermediary_pubkey = Pubkey::from_str(intermediary_pubkey)?;
+        let intermediary = Intermediary::load(rpc_client.get_account(&intermediary_pubkey)?.data.as_slice())?;
+        if !intermediary.is_initialized {
+            return Err(Self::ERROR_INTERMEDIARY_IS_NOT_INITIALIZED.into());
+        }
+        if intermediary.pending_manager_pubkey == Pubkey::default() || intermediary_manager_pubkey != intermediary.pending_manager_pubkey {
+            return Err(Self::ERROR_INTERMEDIARY_INVALID_PENDING_MANAGER.into());
+        }
+        let instructions = vec![
+            Instruction::accept_manager(
+                &PROGRAM_PUBKEY,
+                &intermediary_manager_pubkey,
+                &intermediary_pubkey,
+            )?,
+        ];
+        let signers = vec![&intermediary_manager_keypair];
+        let recent_blockhash = rpc_client.get_latest_blockhash()?;
+        let message = Message::new_with_blockhash(
+            instructions.as_slice(),
+            Some(&intermediary_manager_pubkey),
+            &recent_blockhash,
+        );
+        let transaction = Transaction::new(
+            signers.as_slice(),
+            message,
+            recent_blockhash,
+        );
+        let signature = rpc_client.send_transaction(&transaction)?;
+        println!("Signature: {}", &signature);
+        Ok(())
+    }
+    pub fn accept_trader<'a>(
+        rpc_client: &'a RpcClient,
+        intermediary_trader_keypair_file_path: &'a str,
+        intermediary_pubkey: &'a str,
+    ) -> Result<(), Box<dyn Error + 'static>> {
+        let intermediary_trader_keypair = Self::load_keypair_from_file(&intermediary_trader_keypair_file_path)?;
+        let intermediary_trader_pubkey = intermediary_trader_keypair.pubkey();
+        let intermediary_pubkey = Pubkey::from_str(intermediary_pubkey)?;
+        let intermediary = Intermediary::load(rpc_client.get_account(&intermediary_pubkey)?.data.as_slice())?;
+        if !intermediary.is_initialized {
+            return Err(Self::ERROR_INTERMEDIARY_IS_NOT_INITIALIZED.into());
+        }
+        if intermediary.pending_trader_pubkey == Pubkey::default() || intermediary_trader_pubkey != intermediary.pending_trader_pubkey {
+            return Err(Self::ERROR_INTERMEDIARY_INVALID_PENDING_TRADER.into());
+        }
+        let instructions = vec![
+            Instruction::accept_trader(
+                &PROGRAM_PUBKEY,
+                &intermediary_trader_pubkey,
+                &intermediary_pubkey,
+            )?,
+        ];
+        let signers = vec![&intermediary_trader_keypair];
+        let recent_blockhash = rpc_client.get_latest_blockhash()?;
+        let message = Message::new_with_blockhash(
+            instructions.as_slice(),
+            Some(&intermediary_trader_pubkey),
+            &recent_blockhash,
+        );
+        let transaction = Transaction::new(
+            signers.as_slice(),
+            message,
+            recent_blockhash,
+        );
+        let signature = rpc_client.send_transaction(&transaction)?;
+        println!("Signature: {}", &signature);
+        Ok(())
+    }
+    pub fn cancel_manager_proposal<'a>(
+        rpc_client: &'a RpcClient,
+        authority_keypair_file_path: &'a str,
+        intermediary_pubkey: &'a str,
+    ) -> Result<(), Box<dyn Error + 'static>> {
+        let authority_keypair = Self::load_keypair_from_file(&authority_keypair_file_path)?;
+        let authority_pubkey = authority_keypair.pubkey();
+        let intermediary_pubkey = Pubkey::from_str(intermediary_pubkey)?;
+        let intermediary = Intermediary::load(rpc_client.get_account(&intermediary_pubkey)?.data.as_slice())?;
+        if !intermediary.is_initialized {
+            return Err(Self::ERROR_INTERMEDIARY_IS_NOT_INITIALIZED.into());
+        }
+        if authority_pubkey != intermediary.investor_pubkey && authority_pubkey != intermediary.manager_pubkey {
+            return Err(Self::ERROR_INTERMEDIARY_INVALID_INVESTOR.into());
+        }
+        if intermediary.pending_manager_pubkey == Pubkey::default() {
+            return Err(Self::ERROR_INTERMEDIARY_INVALID_PENDING_MANAGER.into());
+        }
+        let instructions = vec![
+            Instruction::cancel_manager_proposal(
+                &PROGRAM_PUBKEY,
+                &authority_pubkey,
+                &intermediary_pubkey,
+            )?,
+        ];
+        let signers = vec![&authority_keypair];
+        let recent_blockhash = rpc_client.get_latest_blockhash()?;
+        let message = Message::new_with_blockhash(
+            instructions.as_slice(),
+            Some(&authority_pubkey),
+            &recent_blockhash,
+        );
+        let transaction = Transaction::new(
+            signers.as_slice(),
+            message,
+            recent_blockhash,
+        );
+        let signature = rpc_client.send_transaction(&transaction)?;
+        println!("Signature: {}", &signature);
+        Ok(())
+    }
+    pub fn cancel_trader_proposal<'a>(
+        rpc_client: &'a RpcClient,
+        authority_keypair_file_path: &'a str,
+        intermediary_pubkey: &'a str,
+    ) -> Result<(), Box<dyn Error + 'static>> {
+        let authority_keypair = Self::load_keypair_from_file(&authority_keypair_file_path)?;
+        let authority_pubkey = authority_keypair.pubkey();
+        let intermediary_pubkey = Pubkey::from_str(intermediary_pubkey)?;
+        let intermediary = Intermediary::load(rpc_client.get_account(&intermediary_pubkey)?.data.as_slice())?;
+        if !intermediary.is_initialized {
+            return Err(Self::ERROR_INTERMEDIARY_IS_NOT_INITIALIZED.into());
+        }
+        if authority_pubkey != intermediary.manager_pubkey && authority_pubkey != intermediary.trader_pubkey {
+            return Err(Self::ERROR_INTERMEDIARY_INVALID_MANAGER.into());
+        }
+        if intermediary.pending_trader_pubkey == Pubkey::default() {
+            return Err(Self::ERROR_INTERMEDIARY_INVALID_PENDING_TRADER.into());
+        }
+        let instructions = vec![
+            Instruction::cancel_trader_proposal(
+                &PROGRAM_PUBKEY,
+                &authority_pubkey,
+                &intermediary_pubkey,
+            )?,
+        ];
+        let signers = vec![&authority_keypair];
+        let recent_blockhash = rpc_client.get_latest_blockhash()?;
+        let message = Message::new_with_blockhash(
+            instructions.as_slice(),
+            Some(&authority_pubkey),
+            &recent_blockhash,
+        );
+        let transaction = Transaction::new(
+            signers.as_slice(),
+            message,
+            recent_blockhash,
+        );
+        let signature = rpc_client.send_transaction(&transaction)?;
+        println!("Signature: {}", &signature);
+        Ok(())
+    }
+    pub fn buy_on_raydium_cpmm<'a>(
+        rpc_client: &'a RpcClient,
+        intermediary_trader_keypair_file_path: &'a str,
+        intermediary_pubkey: &'a str,
+        any_mint_token_mint_pubkey: &'a str,
+        pool_state_pubkey: &'a str,
+        input_token_amount: u64,
+        minimum_output_token_amount: u64,
+    ) -> Result<(), Box<dyn Error + 'static>> {
+        let intermediary_trader_keypair = Self::load_keypair_from_file(&intermediary_trader_keypair_file_path)?;
+        let intermediary_trader_pubkey = intermediary_trader_keypair.pubkey();
+        let intermediary_pubkey = Pubkey::from_str(intermediary_pubkey)?;
+        let any_mint_token_mint_pubkey = Pubkey::from_str(any_mint_token_mint_pubkey)?;
+        let pool_state_pubkey = Pubkey::from_str(pool_state_pubkey)?;
+        if any_mint_token_mint_pubkey == spl_token::native_mint::ID {
+            return Err(Self::ERROR_INVALID_ACCOUNT_PUBKEY.into());
+        }
+        let intermediary_trader_account = rpc_client.get_account(&intermediary_trader_pubkey)?;
+        let token_account_rent_exemption_balance = rpc_client.get_minimum_balance_for_rent_exemption(<Account as Pack>::LEN)?;
+        let (config_pubkey, config) = Self::get_config(rpc_client)?;
+        if intermediary_trader_account.lamports < (2 * token_account_rent_exemption_balance + config.fees.buy_on_raydium_cpmm) {
+            return Err(Self::ERROR_INVALID_ACCOUNT_LAMPORTS.into());
+        }
+        let intermediary = Intermediary::load(rpc_client.get_account(&intermediary_pubkey)?.data.as_slice())?;
+        if !intermediary.is_initialized {
+            return Err(Self::ERROR_INTERMEDIARY_IS_NOT_INITIALIZED.into());
+        }
+        if intermediary.is_paused {
+            return Err(Self::ERROR_INTERMEDIARY_PAUSED.into());
+        }
+        let (trader_seat_pubkey, trader_seat) = Self::get_trader_seat(
+            rpc_client,
+            &intermediary_pubkey,
+            &intermediary_trader_pubkey,
+        )?;
+        if trader_seat.spent_lamports.saturating_add(input_token_amount) > trader_seat.allocated_lamports.saturating_add(trader_seat.received_lamports) {
+            return Err(Self::ERROR_TRADER_SEAT_ALLOCATION_EXCEEDED.into());
+        }
+        let w_sol_token_account = Account::unpack_unchecked(rpc_client.get_account(&intermediary.w_sol_token_account_pubkey)?.data.as_slice())?;
+        if w_sol_token_account.amount < input_token_amount {
+            return Err(
+                format!(
+                    "The maximum number of lamports from treasury is {}",
+                    w_sol_token_account.amount,
+                )
+                .into(),
+            );
+        }
+        let raydium_cpmm_pool_accounts = Self::resolve_raydium_cpmm_pool_accounts(
+            rpc_client,
+            &pool_state_pubkey,
+            &any_mint_token_mint_pubkey,
+        )?;
+        let any_mint_token_account_address_resolver = AnyMintTokenAccountAddressResolver {
+            any_mint_token_mint_pubkey: &any_mint_token_mint_pubkey,
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+        let (any_mint_token_account_pubkey, any_mint_token_account_pubkey_bump_seed) = any_mint_token_account_address_resolver.find();
+        let any_mint_token_account_authority_address_resolver = AnyMintTokenAccountAuthorityAddressResolver {
+            any_mint_token_account_pubkey: &any_mint_token_account_pubkey,
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+        let (any_mint_token_account_authority_pubkey, any_mint_token_account_authority_pubkey_bump_seed) = any_mint_token_account_authority_address_resolver.find();
+        let allowed_mints_address_resolver = AllowedMintsAddressResolver {
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+        let (allowed_mints_pubkey, _) = allowed_mints_address_resolver.find();
+        let risk_limits_address_resolver = RiskLimitsAddressResolver {
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+        let (risk_limits_pubkey, _) = risk_limits_address_resolver.find();
+        let instructions = vec![
+            Instruction::buy_on_raydium_cpmm(
+                &PROGRAM_PUBKEY,
+                &intermediary_trader_pubkey,
+                &intermediary_pubkey,
+                &intermediary.w_sol_token_account_pubkey,
+                &intermediary.w_sol_token_account_authority_pubkey,
+                &intermediary.temporary_w_sol_token_account_pubkey,
+                &intermediary.temporary_w_sol_token_account_authority_pubkey,
+                &any_mint_token_account_pubkey,
+                &any_mint_token_account_authority_pubkey,
+                &any_mint_token_mint_pubkey,
+                &raydium_cpmm_pool_accounts.any_mint_token_program_pubkey,
+                &raydium_cpmm_pool_accounts.authority_pubkey,
+                &raydium_cpmm_pool_accounts.amm_config_pubkey,
+                &pool_state_pubkey,
+                &raydium_cpmm_pool_accounts.w_sol_vault_pubkey,
+                &raydium_cpmm_pool_accounts.any_mint_vault_pubkey,
+                &raydium_cpmm_pool_accounts.observation_state_pubkey,
+                &allowed_mints_pubkey,
+                &risk_limits_pubkey,
+                &trader_seat_pubkey,
+                &config_pubkey,
+                &config.fee_vault_pubkey,
+                input_token_amount,
+                minimum_output_token_amount,
+                any_mint_token_account_pubkey_bump_seed,
+                any_mint_token_account_authority_pubkey_bump_seed,
+            )?,
+        ];
+        let signers = vec![&intermediary_trader_keypair];
+        let recent_blockhash = rpc_client.get_latest_blockhash()?;
+        let message = Message::new_with_blockhash(
+            instructions.as_slice(),
+            Some(&intermediary_trader_pubkey),
+            &recent_blockhash,
+        );
+        let transaction = Transaction::new(
+            signers.as_slice(),
+            message,
+            recent_blockhash,
+        );
+        let signature = rpc_client.send_transaction(&transaction)?;
+        println!("Signature: {}", &signature);
+        Ok(())
+    }
+    pub fn sell_on_raydium_cpmm<'a>(
+        rpc_client: &'a RpcClient,
+        intermediary_trader_keypair_file_path: &'a str,
+        intermediary_pubkey: &'a str,
+        any_mint_token_mint_pubkey: &'a str,
+        pool_state_pubkey: &'a str,
+        input_token_amount: u64,
+        minimum_output_token_amount: Option<u64>,
+    ) -> Result<(), Box<dyn Error + 'static>> {
+        let intermediary_trader_keypair = Self::load_keypair_from_file(&intermediary_trader_keypair_file_path)?;
+        let intermediary_trader_pubkey = intermediary_trader_keypair.pubkey();
+        let intermediary_pubkey = Pubkey::from_str(intermediary_pubkey)?;
+        let any_mint_token_mint_pubkey = Pubkey::from_str(any_mint_token_mint_pubkey)?;
+        let pool_state_pubkey = Pubkey::from_str(pool_state_pubkey)?;
+        if any_mint_token_mint_pubkey == spl_token::native_mint::ID {
+            return Err(Self::ERROR_INVALID_ACCOUNT_PUBKEY.into());
+        }
+        let intermediary_trader_account = rpc_client.get_account(&intermediary_trader_pubkey)?;
+        let token_account_rent_exemption_balance = rpc_client.get_minimum_balance_for_rent_exemption(<Account as Pack>::LEN)?;
+        let (config_pubkey, config) = Self::get_config(rpc_client)?;
+        if intermediary_trader_account.lamports < (token_account_rent_exemption_balance + config.fees.sell_on_raydium_cpmm) {
+            return Err(Self::ERROR_INVALID_ACCOUNT_LAMPORTS.into());
+        }
+        let intermediary = Intermediary::load(rpc_client.get_account(&intermediary_pubkey)?.data.as_slice())?;
+        if !intermediary.is_initialized {
+            return Err(Self::ERROR_INTERMEDIARY_IS_NOT_INITIALIZED.into());
+        }
+        if intermediary.is_paused {
+            return Err(Self::ERROR_INTERMEDIARY_PAUSED.into());
+        }
+        let (trader_seat_pubkey, _) = Self::get_trader_seat(
+            rpc_client,
+            &intermediary_pubkey,
+            &intermediary_trader_pubkey,
+        )?;
+        let any_mint_token_account_address_resolver = AnyMintTokenAccountAddressResolver {
+            any_mint_token_mint_pubkey: &any_mint_token_mint_pubkey,
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+        let (any_mint_token_account_pubkey, any_mint_token_account_pubkey_bump_seed) = any_mint_token_account_address_resolver.find();
+        let any_mint_token_account = StateWithExtensions::<spl_token_2022::state::Account>::unpack(rpc_client.get_account(&any_mint_token_account_pubkey)?.data.as_slice())?.base;
+        if any_mint_token_account.amount < input_token_amount {
+            return Err(
+                format!(
+                    "The maximum number of tokens to sell is {}",
+                    any_mint_token_account.amount,
+                )
+                .into(),
+            );
+        }
+        let raydium_cpmm_pool_accounts = Self::resolve_raydium_cpmm_pool_accounts(
+            rpc_client,
+            &pool_state_pubkey,
+            &any_mint_token_mint_pubkey,
+        )?;
+        let any_mint_token_account_authority_address_resolver = AnyMintTokenAccountAuthorityAddressResolver {
+            any_mint_token_account_pubkey: &any_mint_token_account_pubkey,
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+        let (any_mint_token_account_authority_pubkey, any_mint_token_account_authority_pubkey_bump_seed) = any_mint_token_account_authority_address_resolver.find();
+        let temporary_any_mint_token_account_address_resolver = TemporaryAnyMintTokenAccountAddressResolver {
+            any_mint_token_account_pubkey: &any_mint_token_account_pubkey,
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+        let (temporary_any_mint_token_account_pubkey, temporary_any_mint_token_account_pubkey_bump_seed) = temporary_any_mint_token_account_address_resolver.find();
+        let temporary_any_mint_token_account_authority_address_resolver = TemporaryAnyMintTokenAccountAuthorityAddressResolver {
+            temporary_any_mint_token_account_pubkey: &temporary_any_mint_token_account_pubkey,
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+        let (temporary_any_mint_token_account_authority_pubkey, temporary_any_mint_token_account_authority_pubkey_bump_seed) =
+            temporary_any_mint_token_account_authority_address_resolver.find();
+        let risk_limits_address_resolver = RiskLimitsAddressResolver {
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+        let (risk_limits_pubkey, _) = risk_limits_address_resolver.find();
+        let instructions = vec![
+            Instruction::sell_on_raydium_cpmm(
+                &PROGRAM_PUBKEY,
+                &intermediary_trader_pubkey,
+                &intermediary_pubkey,
+                &intermediary.w_sol_token_account_pubkey,
+                &any_mint_token_account_pubkey,
+                &any_mint_token_account_authority_pubkey,
+                &temporary_any_mint_token_account_pubkey,
+                &temporary_any_mint_token_account_authority_pubkey,
+                &any_mint_token_mint_pubkey,
+                &raydium_cpmm_pool_accounts.any_mint_token_program_pubkey,
+                &raydium_cpmm_pool_accounts.authority_pubkey,
+                &raydium_cpmm_pool_accounts.amm_config_pubkey,
+                &pool_state_pubkey,
+                &raydium_cpmm_pool_accounts.w_sol_vault_pubkey,
+                &raydium_cpmm_pool_accounts.any_mint_vault_pubkey,
+                &raydium_cpmm_pool_accounts.observation_state_pubkey,
+                &risk_limits_pubkey,
+                &trader_seat_pubkey,
+                &config_pubkey,
+                &config.fee_vault_pubkey,
+                input_token_amount,
+                minimum_output_token_amount,
+                any_mint_token_account_pubkey_bump_seed,
+                any_mint_token_account_authority_pubkey_bump_seed,
+                temporary_any_mint_token_account_pubkey_bump_seed,
+                temporary_any_mint_token_account_authority_pubkey_bump_seed,
+            )?,
+        ];
+        let signers = vec![&intermediary_trader_keypair];
+        let recent_blockhash = rpc_client.get_latest_blockhash()?;
+        let message = Message::new_with_blockhash(
+            instructions.as_slice(),
+            Some(&intermediary_trader_pubkey),
+            &recent_blockhash,
+        );
+        let transaction = Transaction::new(
+            signers.as_slice(),
+            message,
+            recent_blockhash,
+        );
+        let signature = rpc_client.send_transaction(&transaction)?;
+        println!("Signature: {}", &signature);
+        Ok(())
+    }
+    pub fn buy_on_raydium_clmm<'a>(
+        rpc_client: &'a RpcClient,
+        intermediary_trader_keypair_file_path: &'a str,
+        intermediary_pubkey: &'a str,
+        any_mint_token_mint_pubkey: &'a str,
+        pool_state_pubkey: &'a str,
+        input_token_amount: u64,
+        minimum_output_token_amount: u64,
+    ) -> Result<(), Box<dyn Error + 'static>> {
+        let intermediary_trader_keypair = Self::load_keypair_from_file(&intermediary_trader_keypair_file_path)?;
+        let intermediary_trader_pubkey = intermediary_trader_keypair.pubkey();
+        let intermediary_pubkey = Pubkey::from_str(intermediary_pubkey)?;
+        let any_mint_token_mint_pubkey = Pubkey::from_str(any_mint_token_mint_pubkey)?;
+        let pool_state_pubkey = Pubkey::from_str(pool_state_pubkey)?;
+        if any_mint_token_mint_pubkey == spl_token::native_mint::ID {
+            return Err(Self::ERROR_INVALID_ACCOUNT_PUBKEY.into());
+        }
+        let intermediary_trader_account = rpc_client.get_account(&intermediary_trader_pubkey)?;
+        let token_account_rent_exemption_balance = rpc_client.get_minimum_balance_for_rent_exemption(<Account as Pack>::LEN)?;
+        let (config_pubkey, config) = Self::get_config(rpc_client)?;
+        if intermediary_trader_account.lamports < (2 * token_account_rent_exemption_balance + config.fees.buy_on_raydium_clmm) {
+            return Err(Self::ERROR_INVALID_ACCOUNT_LAMPORTS.into());
+        }
+        let intermediary = Intermediary::load(rpc_client.get_account(&intermediary_pubkey)?.data.as_slice())?;
+        if !intermediary.is_initialized {
+            return Err(Self::ERROR_INTERMEDIARY_IS_NOT_INITIALIZED.into());
+        }
+        if intermediary.is_paused {
+            return Err(Self::ERROR_INTERMEDIARY_PAUSED.into());
+        }
+        let (trader_seat_pubkey, trader_seat) = Self::get_trader_seat(
+            rpc_client,
+            &intermediary_pubkey,
+            &intermediary_trader_pubkey,
+        )?;
+        if trader_seat.spent_lamports.saturating_add(input_token_amount) > trader_seat.allocated_lamports.saturating_add(trader_seat.received_lamports) {
+            return Err(Self::ERROR_TRADER_SEAT_ALLOCATION_EXCEEDED.into());
+        }
+        let w_sol_token_account = Account::unpack_unchecked(rpc_client.get_account(&intermediary.w_sol_token_account_pubkey)?.data.as_slice())?;
+        if w_sol_token_account.amount < input_token_amount {
+            return Err(
+                format!(
+                    "The maximum number of lamports from treasury is {}",
+                    w_sol_token_account.amount,
+                )
+                .into(),
+            );
+        }
+        let raydium_clmm_pool_accounts = Self::resolve_raydium_clmm_pool_accounts(
+            rpc_client,
+            &pool_state_pubkey,
+            &any_mint_token_mint_pubkey,
+            true,
+        )?;
+        let any_mint_token_account_address_resolver = AnyMintTokenAccountAddressResolver {
+            any_mint_token_mint_pubkey: &any_mint_token_mint_pubkey,
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+        let (any_mint_token_account_pubkey, any_mint_token_account_pubkey_bump_seed) = any_mint_token_account_address_resolver.find();
+        let any_mint_token_account_authority_address_resolver = AnyMintTokenAccountAuthorityAddressResolver {
+            any_mint_token_account_pubkey: &any_mint_token_account_pubkey,
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+        let (any_mint_token_account_authority_pubkey, any_mint_token_account_authority_pubkey_bump_seed) = any_mint_token_account_authority_address_resolver.find();
+        let allowed_mints_address_resolver = AllowedMintsAddressResolver {
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+        let (allowed_mints_pubkey, _) = allowed_mints_address_resolver.find();
+        let risk_limits_address_resolver = RiskLimitsAddressResolver {
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+        let (risk_limits_pubkey, _) = risk_limits_address_resolver.find();
+        let instructions = vec![
+            Instruction::buy_on_raydium_clmm(
+                &PROGRAM_PUBKEY,
+                &intermediary_trader_pubkey,
+                &intermediary_pubkey,
+                &intermediary.w_sol_token_account_pubkey,
+                &intermediary.w_sol_token_account_authority_pubkey,
+                &intermediary.temporary_w_sol_token_account_pubkey,
+                &intermediary.temporary_w_sol_token_account_authority_pubkey,
+                &any_mint_token_account_pubkey,
+                &any_mint_token_account_authority_pubkey,
+                &any_mint_token_mint_pubkey,
+                &raydium_clmm_pool_accounts.amm_config_pubkey,
+                &pool_state_pubkey,
+                &raydium_clmm_pool_accounts.w_sol_vault_pubkey,
+                &raydium_clmm_pool_accounts.any_mint_vault_pubkey,
+                &raydium_clmm_pool_accounts.observation_state_pubkey,
+                &allowed_mints_pubkey,
+                &risk_limits_pubkey,
+                &trader_seat_pubkey,
+                &config_pubkey,
+                &config.fee_vault_pubkey,
+                raydium_clmm_pool_accounts.tick_array_pubkeys.as_slice(),
+                input_token_amount,
+                minimum_output_token_amount,
+                any_mint_token_account_pubkey_bump_seed,
+                any_mint_token_account_authority_pubkey_bump_seed,
+            )?,
+        ];
+        let signers = vec![&intermediary_trader_keypair];
+        let recent_blockhash = rpc_client.get_latest_blockhash()?;
+        let message = Message::new_with_blockhash(
+            instructions.as_slice(),
+            Some(&intermediary_trader_pubkey),
+            &recent_blockhash,
+        );
+        let transaction = Transaction::new(
+            signers.as_slice(),
+            message,
+            recent_blockhash,
+        );
+        let signature = rpc_client.send_transaction(&transaction)?;
+        println!("Signature: {}", &signature);
+        Ok(())
+    }
+    pub fn sell_on_raydium_clmm<'a>(
+        rpc_client: &'a RpcClient,
+        intermediary_trader_keypair_file_path: &'a str,
+        intermediary_pubkey: &'a str,
+        any_mint_token_mint_pubkey: &'a str,
+        pool_state_pubkey: &'a str,
+        input_token_amount: u64,
+        minimum_output_token_amount: Option<u64>,
+    ) -> Result<(), Box<dyn Error + 'static>> {
+        let intermediary_trader_keypair = Self::load_keypair_from_file(&intermediary_trader_keypair_file_path)?;
+        let intermediary_trader_pubkey = intermediary_trader_keypair.pubkey();
+        let intermediary_pubkey = Pubkey::from_str(intermediary_pubkey)?;
+        let any_mint_token_mint_pubkey = Pubkey::from_str(any_mint_token_mint_pubkey)?;
+        let pool_state_pubkey = Pubkey::from_str(pool_state_pubkey)?;
+        if any_mint_token_mint_pubkey == spl_token::native_mint::ID {
+            return Err(Self::ERROR_INVALID_ACCOUNT_PUBKEY.into());
+        }
+        let intermediary_trader_account = rpc_client.get_account(&intermediary_trader_pubkey)?;
+        let token_account_rent_exemption_balance = rpc_client.get_minimum_balance_for_rent_exemption(<Account as Pack>::LEN)?;
+        let (config_pubkey, config) = Self::get_config(rpc_client)?;
+        if intermediary_trader_account.lamports < (token_account_rent_exemption_balance + config.fees.sell_on_raydium_clmm) {
+            return Err(Self::ERROR_INVALID_ACCOUNT_LAMPORTS.into());
+        }
+        let intermediary = Intermediary::load(rpc_client.get_account(&intermediary_pubkey)?.data.as_slice())?;
+        if !intermediary.is_initialized {
+            return Err(Self::ERROR_INTERMEDIARY_IS_NOT_INITIALIZED.into());
+        }
+        if intermediary.is_paused {
+            return Err(Self::ERROR_INTERMEDIARY_PAUSED.into());
+        }
+        let (trader_seat_pubkey, _) = Self::get_trader_seat(
+            rpc_client,
+            &intermediary_pubkey,
+            &intermediary_trader_pubkey,
+        )?;
+        let any_mint_token_account_address_resolver = AnyMintTokenAccountAddressResolver {
+            any_mint_token_mint_pubkey: &any_mint_token_mint_pubkey,
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+        let (any_mint_token_account_pubkey, any_mint_token_account_pubkey_bump_seed) = any_mint_token_account_address_resolver.find();
+        let any_mint_token_account = StateWithExtensions::<spl_token_2022::state::Account>::unpack(rpc_client.get_account(&any_mint_token_account_pubkey)?.data.as_slice())?.base;
+        if any_mint_token_account.amount < input_token_amount {
+            return Err(
+                format!(
+                    "The maximum number of tokens to sell is {}",
+                    any_mint_token_account.amount,
+                )
+                .into(),
+            );
+        }
+        let raydium_clmm_pool_accounts = Self::resolve_raydium_clmm_pool_accounts(
+            rpc_client,
+            &pool_state_pubkey,
+            &any_mint_token_mint_pubkey,
+            false,
+        )?;
+        let any_mint_token_account_authority_address_resolver = AnyMintTokenAccountAuthorityAddressResolver {
+            any_mint_token_account_pubkey: &any_mint_token_account_pubkey,
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+        let (any_mint_token_account_authority_pubkey, any_mint_token_account_authority_pubkey_bump_seed) = any_mint_token_account_authority_address_resolver.find();
+        let temporary_any_mint_token_account_address_resolver = TemporaryAnyMintTokenAccountAddressResolver {
+            any_mint_token_account_pubkey: &any_mint_token_account_pubkey,
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+        let (temporary_any_mint_token_account_pubkey, temporary_any_mint_token_account_pubkey_bump_seed) = temporary_any_mint_token_account_address_resolver.find();
+        let temporary_any_mint_token_account_authority_address_resolver = TemporaryAnyMintTokenAccountAuthorityAddressResolver {
+            temporary_any_mint_token_account_pubkey: &temporary_any_mint_token_account_pubkey,
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+        let (temporary_any_mint_token_account_authority_pubkey, temporary_any_mint_token_account_authority_pubkey_bump_seed) =
+            temporary_any_mint_token_account_authority_address_resolver.find();
+        let risk_limits_address_resolver = RiskLimitsAddressResolver {
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+        let (risk_limits_pubkey, _) = risk_limits_address_resolver.find();
+        let instructions = vec![
+            Instruction::sell_on_raydium_clmm(
+                &PROGRAM_PUBKEY,
+                &intermediary_trader_pubkey,
+                &intermediary_pubkey,
+                &intermediary.w_sol_token_account_pubkey,
+                &any_mint_token_account_pubkey,
+                &any_mint_token_account_authority_pubkey,
+                &temporary_any_mint_token_account_pubkey,
+                &temporary_any_mint_token_account_authority_pubkey,
+                &any_mint_token_mint_pubkey,
+                &raydium_clmm_pool_accounts.amm_config_pubkey,
+                &pool_state_pubkey,
+                &raydium_clmm_pool_accounts.w_sol_vault_pubkey,
+                &raydium_clmm_pool_accounts.any_mint_vault_pubkey,
+                &raydium_clmm_pool_accounts.observation_state_pubkey,
+                &risk_limits_pubkey,
+                &trader_seat_pubkey,
+                &config_pubkey,
+                &config.fee_vault_pubkey,
+                raydium_clmm_pool_accounts.tick_array_pubkeys.as_slice(),
+                input_token_amount,
+                minimum_output_token_amount,
+                any_mint_token_account_pubkey_bump_seed,
+                any_mint_token_account_authority_pubkey_bump_seed,
+                temporary_any_mint_token_account_pubkey_bump_seed,
+                temporary_any_mint_token_account_authority_pubkey_bump_seed,
+            )?,
+        ];
+        let signers = vec![&intermediary_trader_keypair];
+        let recent_blockhash = rpc_client.get_latest_blockhash()?;
+        let message = Message::new_with_blockhash(
+            instructions.as_slice(),
+            Some(&intermediary_trader_pubkey),
+            &recent_blockhash,
+        );
+        let transaction = Transaction::new(
+            signers.as_slice(),
+            message,
+            recent_blockhash,
+        );
+        let signature = rpc_client.send_transaction(&transaction)?;
+        println!("Signature: {}", &signature);
+        Ok(())
+    }
+    pub fn buy_on_meteora_dlmm<'a>(
+        rpc_client: &'a RpcClient,
+        intermediary_trader_keypair_file_path: &'a str,
+        intermediary_pubkey: &'a str,
+        any_mint_token_mint_pubkey: &'a str,
+        pool_pubkey: &'a str,
+        input_token_amount: u64,
+        minimum_output_token_amount: u64,
+    ) -> Result<(), Box<dyn Error + 'static>> {
+        let intermediary_trader_keypair = Self::load_keypair_from_file(&intermediary_trader_keypair_file_path)?;
+        let intermediary_trader_pubkey = intermediary_trader_keypair.pubkey();
+        let intermediary_pubkey = Pubkey::from_str(intermediary_pubkey)?;
+        let any_mint_token_mint_pubkey = Pubkey::from_str(any_mint_token_mint_pubkey)?;
+        let pool_pubkey = Pubkey::from_str(pool_pubkey)?;
+        if any_mint_token_mint_pubkey == spl_token::native_mint::ID {
+            return Err(Self::ERROR_INVALID_ACCOUNT_PUBKEY.into());
+        }
+        let intermediary_trader_account = rpc_client.get_account(&intermediary_trader_pubkey)?;
+        let token_account_rent_exemption_balance = rpc_client.get_minimum_balance_for_rent_exemption(<Account as Pack>::LEN)?;
+        let (config_pubkey, config) = Self::get_config(rpc_client)?;
+        if intermediary_trader_account.lamports < (2 * token_account_rent_exemption_balance + config.fees.buy_on_meteora_dlmm) {
+            return Err(Self::ERROR_INVALID_ACCOUNT_LAMPORTS.into());
+        }
+        let intermediary = Intermediary::load(rpc_client.get_account(&intermediary_pubkey)?.data.as_slice())?;
+        if !intermediary.is_initialized {
+            return Err(Self::ERROR_INTERMEDIARY_IS_NOT_INITIALIZED.into());
+        }
+        if intermediary.is_paused {
+            return Err(Self::ERROR_INTERMEDIARY_PAUSED.into());
+        }
+        let (trader_seat_pubkey, trader_seat) = Self::get_trader_seat(
+            rpc_client,
+            &intermediary_pubkey,
+            &intermediary_trader_pubkey,
+        )?;
+        if trader_seat.spent_lamports.saturating_add(input_token_amount) > trader_seat.allocated_lamports.saturating_add(trader_seat.received_lamports) {
+            return Err(Self::ERROR_TRADER_SEAT_ALLOCATION_EXCEEDED.into());
+        }
+        let w_sol_token_account = Account::unpack_unchecked(rpc_client.get_account(&intermediary.w_sol_token_account_pubkey)?.data.as_slice())?;
+        if w_sol_token_account.amount < input_token_amount {
+            return Err(
+                format!(
+                    "The maximum number of lamports from treasury is {}",
+                    w_sol_token_account.amount,
+                )
+                .into(),
+            );
+        }
+        let meteora_dlmm_accounts = Self::resolve_meteora_dlmm_accounts(
+            rpc_client,
+            &pool_pubkey,
+            &any_mint_token_mint_pubkey,
+            true,
+        )?;
+        let any_mint_token_account_address_resolver = AnyMintTokenAccountAddressResolver {
+            any_mint_token_mint_pubkey: &any_mint_token_mint_pubkey,
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+        let (any_mint_token_account_pubkey, any_mint_token_account_pubkey_bump_seed) = any_mint_token_account_address_resolver.find();
+        let any_mint_token_account_authority_address_resolver = AnyMintTokenAccountAuthorityAddressResolver {
+            any_mint_token_account_pubkey: &any_mint_token_account_pubkey,
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+        let (any_mint_token_account_authority_pubkey, any_mint_token_account_authority_pubkey_bump_seed) = any_mint_token_account_authority_address_resolver.find();
+        let allowed_mints_address_resolver = AllowedMintsAddressResolver {
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+        let (allowed_mints_pubkey, _) = allowed_mints_address_resolver.find();
+        let risk_limits_address_resolver = RiskLimitsAddressResolver {
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+        let (risk_limits_pubkey, _) = risk_limits_address_resolver.find();
+        let instructions = vec![
+            Instruction::buy_on_meteora_dlmm(
+                &PROGRAM_PUBKEY,
+                &intermediary_trader_pubkey,
+                &intermediary_pubkey,
+                &intermediary.w_sol_token_account_pubkey,
+                &intermediary.w_sol_token_account_authority_pubkey,
+                &intermediary.temporary_w_sol_token_account_pubkey,
+                &intermediary.temporary_w_sol_token_account_authority_pubkey,
+                &any_mint_token_account_pubkey,
+                &any_mint_token_account_authority_pubkey,
+                &any_mint_token_mint_pubkey,
+                &meteora_dlmm_accounts.any_mint_token_program_pubkey,
+                &pool_pubkey,
+                &meteora_dlmm_accounts.bin_array_bitmap_extension_pubkey,
+                &meteora_dlmm_accounts.w_sol_reserve_pubkey,
+                &meteora_dlmm_accounts.any_mint_reserve_pubkey,
+                &meteora_dlmm_accounts.oracle_pubkey,
+                &meteora_dlmm_accounts.event_authority_pubkey,
+                &allowed_mints_pubkey,
+                &risk_limits_pubkey,
+                &trader_seat_pubkey,
+                &config_pubkey,
+                &config.fee_vault_pubkey,
+                meteora_dlmm_accounts.bin_array_pubkeys.as_slice(),
+                input_token_amount,
+                minimum_output_token_amount,
+                any_mint_token_account_pubkey_bump_seed,
+                any_mint_token_account_authority_pubkey_bump_seed,
+            )?,
+        ];
+        let signers = vec![&intermediary_trader_keypair];
+        let recent_blockhash = rpc_client.get_latest_blockhash()?;
+        let message = Message::new_with_blockhash(
+            instructions.as_slice(),
+            Some(&intermediary_trader_pubkey),
+            &recent_blockhash,
+        );
+        let transaction = Transaction::new(
+            signers.as_slice(),
+            message,
+            recent_blockhash,
+        );
+        let signature = rpc_client.send_transaction(&transaction)?;
+        println!("Signature: {}", &signature);
+        Ok(())
+    }
+    pub fn sell_on_meteora_dlmm<'a>(
+        rpc_client: &'a RpcClient,
+        intermediary_trader_keypair_file_path: &'a str,
+        intermediary_pubkey: &'a str,
+        any_mint_token_mint_pubkey: &'a str,
+        pool_pubkey: &'a str,
+        input_token_amount: u64,
+        minimum_output_token_amount: Option<u64>,
+    ) -> Result<(), Box<dyn Error + 'static>> {
+        let intermediary_trader_keypair = Self::load_keypair_from_file(&intermediary_trader_keypair_file_path)?;
+        let intermediary_trader_pubkey = intermediary_trader_keypair.pubkey();
+        let intermediary_pubkey = Pubkey::from_str(intermediary_pubkey)?;
+        let any_mint_token_mint_pubkey = Pubkey::from_str(any_mint_token_mint_pubkey)?;
+        let pool_pubkey = Pubkey::from_str(pool_pubkey)?;
+        if any_mint_token_mint_pubkey == spl_token::native_mint::ID {
+            return Err(Self::ERROR_INVALID_ACCOUNT_PUBKEY.into());
+        }
+        let intermediary_trader_account = rpc_client.get_account(&intermediary_trader_pubkey)?;
+        let token_account_rent_exemption_balance = rpc_client.get_minimum_balance_for_rent_exemption(<Account as Pack>::LEN)?;
+        let (config_pubkey, config) = Self::get_config(rpc_client)?;
+        if intermediary_trader_account.lamports < (token_account_rent_exemption_balance + config.fees.sell_on_meteora_dlmm) {
+            return Err(Self::ERROR_INVALID_ACCOUNT_LAMPORTS.into());
+        }
+        let intermediary = Intermediary::load(rpc_client.get_account(&intermediary_pubkey)?.data.as_slice())?;
+        if !intermediary.is_initialized {
+            return Err(Self::ERROR_INTERMEDIARY_IS_NOT_INITIALIZED.into());
+        }
+        if intermediary.is_paused {
+            return Err(Self::ERROR_INTERMEDIARY_PAUSED.into());
+        }
+        let (trader_seat_pubkey, _) = Self::get_trader_seat(
+            rpc_client,
+            &intermediary_pubkey,
+            &intermediary_trader_pubkey,
+        )?;
+        let any_mint_token_account_address_resolver = AnyMintTokenAccountAddressResolver {
+            any_mint_token_mint_pubkey: &any_mint_token_mint_pubkey,
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+        let (any_mint_token_account_pubkey, any_mint_token_account_pubkey_bump_seed) = any_mint_token_account_address_resolver.find();
+        let any_mint_token_account = StateWithExtensions::<spl_token_2022::state::Account>::unpack(rpc_client.get_account(&any_mint_token_account_pubkey)?.data.as_slice())?.base;
+        if any_mint_token_account.amount < input_token_amount {
+            return Err(
+                format!(
+                    "The maximum number of tokens to sell is {}",
+                    any_mint_token_account.amount,
+                )
+                .into(),
+            );
+        }
+        let meteora_dlmm_accounts = Self::resolve_meteora_dlmm_accounts(
+            rpc_client,
+            &pool_pubkey,
+            &any_mint_token_mint_pubkey,
+            false,
+        )?;
+        let any_mint_token_account_authority_address_resolver = AnyMintTokenAccountAuthorityAddressResolver {
+            any_mint_token_account_pubkey: &any_mint_token_account_pubkey,
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+        let (any_mint_token_account_authority_pubkey, any_mint_token_account_authority_pubkey_bump_seed) = any_mint_token_account_authority_address_resolver.find();
+        let temporary_any_mint_token_account_address_resolver = TemporaryAnyMintTokenAccountAddressResolver {
+            any_mint_token_account_pubkey: &any_mint_token_account_pubkey,
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+        let (temporary_any_mint_token_account_pubkey, temporary_any_mint_token_account_pubkey_bump_seed) = temporary_any_mint_token_account_address_resolver.find();
+        let temporary_any_mint_token_account_authority_address_resolver = TemporaryAnyMintTokenAccountAuthorityAddressResolver {
+            temporary_any_mint_token_account_pubkey: &temporary_any_mint_token_account_pubkey,
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+        let (temporary_any_mint_token_account_authority_pubkey, temporary_any_mint_token_account_authority_pubkey_bump_seed) =
+            temporary_any_mint_token_account_authority_address_resolver.find();
+        let risk_limits_address_resolver = RiskLimitsAddressResolver {
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+        let (risk_limits_pubkey, _) = risk_limits_address_resolver.find();
+        let instructions = vec![
+            Instruction::sell_on_meteora_dlmm(
+                &PROGRAM_PUBKEY,
+                &intermediary_trader_pubkey,
+                &intermediary_pubkey,
+                &intermediary.w_sol_token_account_pubkey,
+                &any_mint_token_account_pubkey,
+                &any_mint_token_account_authority_pubkey,
+                &temporary_any_mint_token_account_pubkey,
+                &temporary_any_mint_token_account_authority_pubkey,
+                &any_mint_token_mint_pubkey,
+                &meteora_dlmm_accounts.any_mint_token_program_pubkey,
+                &pool_pubkey,
+                &meteora_dlmm_accounts.bin_array_bitmap_extension_pubkey,
+                &meteora_dlmm_accounts.w_sol_reserve_pubkey,
+                &meteora_dlmm_accounts.any_mint_reserve_pubkey,
+                &meteora_dlmm_accounts.oracle_pubkey,
+                &meteora_dlmm_accounts.event_authority_pubkey,
+                &risk_limits_pubkey,
+                &trader_seat_pubkey,
+                &config_pubkey,
+                &config.fee_vault_pubkey,
+                meteora_dlmm_accounts.bin_array_pubkeys.as_slice(),
+                input_token_amount,
+                minimum_output_token_amount,
+                any_mint_token_account_pubkey_bump_seed,
+                any_mint_token_account_authority_pubkey_bump_seed,
+                temporary_any_mint_token_account_pubkey_bump_seed,
+                temporary_any_mint_token_account_authority_pubkey_bump_seed,
+            )?,
+        ];
+        let signers = vec![&intermediary_trader_keypair];
+        let recent_blockhash = rpc_client.get_latest_blockhash()?;
+        let message = Message::new_with_blockhash(
+            instructions.as_slice(),
+            Some(&intermediary_trader_pubkey),
+            &recent_blockhash,
+        );
+        let transaction = Transaction::new(
+            signers.as_slice(),
+            message,
+            recent_blockhash,
+        );
+        let signature = rpc_client.send_transaction(&transaction)?;
+        println!("Signature: {}", &signature);
+        Ok(())
+    }
+    pub fn buy_on_meteora_dynamic_amm<'a>(
+        rpc_client: &'a RpcClient,
+        intermediary_trader_keypair_file_path: &'a str,
+        intermediary_pubkey: &'a str,
+        any_mint_token_mint_pubkey: &'a str,
+        pool_pubkey: &'a str,
+        input_token_amount: u64,
+        minimum_output_token_amount: u64,
+    ) -> Result<(), Box<dyn Error + 'static>> {
+        let intermediary_trader_keypair = Self::load_keypair_from_file(&intermediary_trader_keypair_file_path)?;
+        let intermediary_trader_pubkey = intermediary_trader_keypair.pubkey();
+        let intermediary_pubkey = Pubkey::from_str(intermediary_pubkey)?;
+        let any_mint_token_mint_pubkey = Pubkey::from_str(any_mint_token_mint_pubkey)?;
+        let pool_pubkey = Pubkey::from_str(pool_pubkey)?;
+        if any_mint_token_mint_pubkey == spl_token::native_mint::ID {
+            return Err(Self::ERROR_INVALID_ACCOUNT_PUBKEY.into());
+        }
+        let intermediary_trader_account = rpc_client.get_account(&intermediary_trader_pubkey)?;
+        let token_account_rent_exemption_balance = rpc_client.get_minimum_balance_for_rent_exemption(<Account as Pack>::LEN)?;
+        let (config_pubkey, config) = Self::get_config(rpc_client)?;
+        if intermediary_trader_account.lamports < (2 * token_account_rent_exemption_balance + config.fees.buy_on_meteora_dynamic_amm) {
+            return Err(Self::ERROR_INVALID_ACCOUNT_LAMPORTS.into());
+        }
+        let intermediary = Intermediary::load(rpc_client.get_account(&intermediary_pubkey)?.data.as_slice())?;
+        if !intermediary.is_initialized {
+            return Err(Self::ERROR_INTERMEDIARY_IS_NOT_INITIALIZED.into());
+        }
+        if intermediary.is_paused {
+            return Err(Self::ERROR_INTERMEDIARY_PAUSED.into());
+        }
+        let (trader_seat_pubkey, trader_seat) = Self::get_trader_seat(
+            rpc_client,
+            &intermediary_pubkey,
+            &intermediary_trader_pubkey,
+        )?;
+        if trader_seat.spent_lamports.saturating_add(input_token_amount) > trader_seat.allocated_lamports.saturating_add(trader_seat.received_lamports) {
+            return Err(Self::ERROR_TRADER_SEAT_ALLOCATION_EXCEEDED.into());
+        }
+        let w_sol_token_account = Account::unpack_unchecked(rpc_client.get_account(&intermediary.w_sol_token_account_pubkey)?.data.as_slice())?;
+        if w_sol_token_account.amount < input_token_amount {
+            return Err(
+                format!(
+                    "The maximum number of lamports from treasury is {}",
+                    w_sol_token_account.amount,
+                )
+                .into(),
+            );
+        }
+        let meteora_dynamic_amm_accounts = Self::resolve_meteora_dynamic_amm_accounts(
+            rpc_client,
+            &pool_pubkey,
+            &any_mint_token_mint_pubkey,
+            true,
+        )?;
+        let any_mint_token_account_address_resolver = AnyMintTokenAccountAddressResolver {
+            any_mint_token_mint_pubkey: &any_mint_token_mint_pubkey,
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+        let (any_mint_token_account_pubkey, any_mint_token_account_pubkey_bump_seed) = any_mint_token_account_address_resolver.find();
+        let any_mint_token_account_authority_address_resolver = AnyMintTokenAccountAuthorityAddressResolver {
+            any_mint_token_account_pubkey: &any_mint_token_account_pubkey,
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+        let (any_mint_token_account_authority_pubkey, any_mint_token_account_authority_pubkey_bump_seed) = any_mint_token_account_authority_address_resolver.find();
+        let allowed_mints_address_resolver = AllowedMintsAddressResolver {
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+        let (allowed_mints_pubkey, _) = allowed_mints_address_resolver.find();
+        let risk_limits_address_resolver = RiskLimitsAddressResolver {
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+        let (risk_limits_pubkey, _) = risk_limits_address_resolver.find();
+        let instructions = vec![
+            Instruction::buy_on_meteora_dynamic_amm(
+                &PROGRAM_PUBKEY,
+                &intermediary_trader_pubkey,
+                &intermediary_pubkey,
+                &intermediary.w_sol_token_account_pubkey,
+                &intermediary.w_sol_token_account_authority_pubkey,
+                &intermediary.temporary_w_sol_token_account_pubkey,
+                &intermediary.temporary_w_sol_token_account_authority_pubkey,
+                &any_mint_token_account_pubkey,
+                &any_mint_token_account_authority_pubkey,
+                &any_mint_token_mint_pubkey,
+                &pool_pubkey,
+                &meteora_dynamic_amm_accounts.w_sol_vault_pubkey,
+                &meteora_dynamic_amm_accounts.any_mint_vault_pubkey,
+                &meteora_dynamic_amm_accounts.w_sol_token_vault_pubkey,
+                &meteora_dynamic_amm_accounts.any_mint_token_vault_pubkey,
+                &meteora_dynamic_amm_accounts.w_sol_vault_lp_mint_pubkey,
+                &meteora_dynamic_amm_accounts.any_mint_vault_lp_mint_pubkey,
+                &meteora_dynamic_amm_accounts.w_sol_vault_lp_pubkey,
+                &meteora_dynamic_amm_accounts.any_mint_vault_lp_pubkey,
+                &meteora_dynamic_amm_accounts.protocol_token_fee_pubkey,
+                &allowed_mints_pubkey,
+                &risk_limits_pubkey,
+                &trader_seat_pubkey,
+                &config_pubkey,
+                &config.fee_vault_pubkey,
+                input_token_amount,
+                minimum_output_token_amount,
+                any_mint_token_account_pubkey_bump_seed,
+                any_mint_token_account_authority_pubkey_bump_seed,
+            )?,
+        ];
+        let signers = vec![&intermediary_trader_keypair];
+        let recent_blockhash = rpc_client.get_latest_blockhash()?;
+        let message = Message::new_with_blockhash(
+            instructions.as_slice(),
+            Some(&intermediary_trader_pubkey),
+            &recent_blockhash,
+        );
+        let transaction = Transaction::new(
+            signers.as_slice(),
+            message,
+            recent_blockhash,
+        );
+        let signature = rpc_client.send_transaction(&transaction)?;
+        println!("Signature: {}", &signature);
+        Ok(())
+    }
+    pub fn sell_on_meteora_dynamic_amm<'a>(
+        rpc_client: &'a RpcClient,
+        intermediary_trader_keypair_file_path: &'a str,
+        intermediary_pubkey: &'a str,
+        any_mint_token_mint_pubkey: &'a str,
+        pool_pubkey: &'a str,
+        input_token_amount: u64,
+        minimum_output_token_amount: Option<u64>,
+    ) -> Result<(), Box<dyn Error + 'static>> {
+        let intermediary_trader_keypair = Self::load_keypair_from_file(&intermediary_trader_keypair_file_path)?;
+        let intermediary_trader_pubkey = intermediary_trader_keypair.pubkey();
+        let intermediary_pubkey = Pubkey::from_str(intermediary_pubkey)?;
+        let any_mint_token_mint_pubkey = Pubkey::from_str(any_mint_token_mint_pubkey)?;
+        let pool_pubkey = Pubkey::from_str(pool_pubkey)?;
+        if any_mint_token_mint_pubkey == spl_token::native_mint::ID {
+            return Err(Self::ERROR_INVALID_ACCOUNT_PUBKEY.into());
+        }
+        let intermediary_trader_account = rpc_client.get_account(&intermediary_trader_pubkey)?;
+        let token_account_rent_exemption_balance = rpc_client.get_minimum_balance_for_rent_exemption(<Account as Pack>::LEN)?;
+        let (config_pubkey, config) = Self::get_config(rpc_client)?;
+        if intermediary_trader_account.lamports < (token_account_rent_exemption_balance + config.fees.sell_on_meteora_dynamic_amm) {
+            return Err(Self::ERROR_INVALID_ACCOUNT_LAMPORTS.into());
+        }
+        let intermediary = Intermediary::load(rpc_client.get_account(&intermediary_pubkey)?.data.as_slice())?;
+        if !intermediary.is_initialized {
+            return Err(Self::ERROR_INTERMEDIARY_IS_NOT_INITIALIZED.into());
+        }
+        if intermediary.is_paused {
+            return Err(Self::ERROR_INTERMEDIARY_PAUSED.into());
+        }
+        let (trader_seat_pubkey, _) = Self::get_trader_seat(
+            rpc_client,
+            &intermediary_pubkey,
+            &intermediary_trader_pubkey,
+        )?;
+        let any_mint_token_account_address_resolver = AnyMintTokenAccountAddressResolver {
+            any_mint_token_mint_pubkey: &any_mint_token_mint_pubkey,
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+        let (any_mint_token_account_pubkey, any_mint_token_account_pubkey_bump_seed) = any_mint_token_account_address_resolver.find();
+        let any_mint_token_account = StateWithExtensions::<spl_token_2022::state::Account>::unpack(rpc_client.get_account(&any_mint_token_account_pubkey)?.data.as_slice())?.base;
+        if any_mint_token_account.amount < input_token_amount {
+            return Err(
+                format!(
+                    "The maximum number of tokens to sell is {}",
+                    any_mint_token_account.amount,
+                )
+                .into(),
+            );
+        }
+        let meteora_dynamic_amm_accounts = Self::resolve_meteora_dynamic_amm_accounts(
+            rpc_client,
+            &pool_pubkey,
+            &any_mint_token_mint_pubkey,
+            false,
+        )?;
+        let any_mint_token_account_authority_address_resolver = AnyMintTokenAccountAuthorityAddressResolver {
+            any_mint_token_account_pubkey: &any_mint_token_account_pubkey,
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+        let (any_mint_token_account_authority_pubkey, any_mint_token_account_authority_pubkey_bump_seed) = any_mint_token_account_authority_address_resolver.find();
+        let temporary_any_mint_token_account_address_resolver = TemporaryAnyMintTokenAccountAddressResolver {
+            any_mint_token_account_pubkey: &any_mint_token_account_pubkey,
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+        let (temporary_any_mint_token_account_pubkey, temporary_any_mint_token_account_pubkey_bump_seed) = temporary_any_mint_token_account_address_resolver.find();
+        let temporary_any_mint_token_account_authority_address_resolver = TemporaryAnyMintTokenAccountAuthorityAddressResolver {
+            temporary_any_mint_token_account_pubkey: &temporary_any_mint_token_account_pubkey,
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+        let (temporary_any_mint_token_account_authority_pubkey, temporary_any_mint_token_account_authority_pubkey_bump_seed) =
+            temporary_any_mint_token_account_authority_address_resolver.find();
+        let risk_limits_address_resolver = RiskLimitsAddressResolver {
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+        let (risk_limits_pubkey, _) = risk_limits_address_resolver.find();
+        let instructions = vec![
+            Instruction::sell_on_meteora_dynamic_amm(
+                &PROGRAM_PUBKEY,
+                &intermediary_trader_pubkey,
+                &intermediary_pubkey,
+                &intermediary.w_sol_token_account_pubkey,
+                &any_mint_token_account_pubkey,
+                &any_mint_token_account_authority_pubkey,
+                &temporary_any_mint_token_account_pubkey,
+                &temporary_any_mint_token_account_authority_pubkey,
+                &any_mint_token_mint_pubkey,
+                &pool_pubkey,
+                &meteora_dynamic_amm_accounts.w_sol_vault_pubkey,
+                &meteora_dynamic_amm_accounts.any_mint_vault_pubkey,
+                &meteora_dynamic_amm_accounts.w_sol_token_vault_pubkey,
+                &meteora_dynamic_amm_accounts.any_mint_token_vault_pubkey,
+                &meteora_dynamic_amm_accounts.w_sol_vault_lp_mint_pubkey,
+                &meteora_dynamic_amm_accounts.any_mint_vault_lp_mint_pubkey,
+                &meteora_dynamic_amm_accounts.w_sol_vault_lp_pubkey,
+                &meteora_dynamic_amm_accounts.any_mint_vault_lp_pubkey,
+                &meteora_dynamic_amm_accounts.protocol_token_fee_pubkey,
+                &risk_limits_pubkey,
+                &trader_seat_pubkey,
+                &config_pubkey,
+                &config.fee_vault_pubkey,
+                input_token_amount,
+                minimum_output_token_amount,
+                any_mint_token_account_pubkey_bump_seed,
+                any_mint_token_account_authority_pubkey_bump_seed,
+                temporary_any_mint_token_account_pubkey_bump_seed,
+                temporary_any_mint_token_account_authority_pubkey_bump_seed,
+            )?,
+        ];
+        let signers = vec![&intermediary_trader_keypair];
+        let recent_blockhash = rpc_client.get_latest_blockhash()?;
+        let message = Message::new_with_blockhash(
+            instructions.as_slice(),
+            Some(&intermediary_trader_pubkey),
+            &recent_blockhash,
+        );
+        let transaction = Transaction::new(
+            signers.as_slice(),
+            message,
+            recent_blockhash,
+        );
+        let signature = rpc_client.send_transaction(&transaction)?;
+        println!("Signature: {}", &signature);
+        Ok(())
+    }
+    pub fn buy_on_pumpfun_curve<'a>(
+        rpc_client: &'a RpcClient,
+        intermediary_trader_keypair_file_path: &'a str,
+        intermediary_pubkey: &'a str,
+        any_mint_token_mint_pubkey: &'a str,
+        token_amount_out: u64,
+        maximum_lamports_in: u64,
+    ) -> Result<(), Box<dyn Error + 'static>> {
+        let intermediary_trader_keypair = Self::load_keypair_from_file(&intermediary_trader_keypair_file_path)?;
+        let intermediary_trader_pubkey = intermediary_trader_keypair.pubkey();
+        let intermediary_pubkey = Pubkey::from_str(intermediary_pubkey)?;
+        let any_mint_token_mint_pubkey = Pubkey::from_str(any_mint_token_mint_pubkey)?;
+        if any_mint_token_mint_pubkey == spl_token::native_mint::ID {
+            return Err(Self::ERROR_INVALID_ACCOUNT_PUBKEY.into());
+        }
+        let intermediary_trader_account = rpc_client.get_account(&intermediary_trader_pubkey)?;
+        let token_account_rent_exemption_balance = rpc_client.get_minimum_balance_for_rent_exemption(<Account as Pack>::LEN)?;
+        let (config_pubkey, config) = Self::get_config(rpc_client)?;
+        if intermediary_trader_account.lamports < (2 * token_account_rent_exemption_balance + config.fees.buy_on_pumpfun_curve) {
+            return Err(Self::ERROR_INVALID_ACCOUNT_LAMPORTS.into());
+        }
+        let intermediary = Intermediary::load(rpc_client.get_account(&intermediary_pubkey)?.data.as_slice())?;
+        if !intermediary.is_initialized {
+            return Err(Self::ERROR_INTERMEDIARY_IS_NOT_INITIALIZED.into());
+        }
+        if intermediary.is_paused {
+            return Err(Self::ERROR_INTERMEDIARY_PAUSED.into());
+        }
+        let (trader_seat_pubkey, trader_seat) = Self::get_trader_seat(
+            rpc_client,
+            &intermediary_pubkey,
+            &intermediary_trader_pubkey,
+        )?;
+        if trader_seat.spent_lamports.saturating_add(maximum_lamports_in) > trader_seat.allocated_lamports.saturating_add(trader_seat.received_lamports) {
+            return Err(Self::ERROR_TRADER_SEAT_ALLOCATION_EXCEEDED.into());
+        }
+        let w_sol_token_account = Account::unpack_unchecked(rpc_client.get_account(&intermediary.w_sol_token_account_pubkey)?.data.as_slice())?;
+        if w_sol_token_account.amount < maximum_lamports_in {
+            return Err(
+                format!(
+                    "The maximum number of lamports from treasury is {}",
+                    w_sol_token_account.amount,
+                )
+                .into(),
+            );
+        }
+        let pumpfun_curve_accounts = Self::resolve_pumpfun_curve_accounts(
+            rpc_client,
+            &any_mint_token_mint_pubkey,
+        )?;
+        let any_mint_token_account_address_resolver = AnyMintTokenAccountAddressResolver {
+            any_mint_token_mint_pubkey: &any_mint_token_mint_pubkey,
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+        let (any_mint_token_account_pubkey, any_mint_token_account_pubkey_bump_seed) = any_mint_token_account_address_resolver.find();
+        let any_mint_token_account_authority_address_resolver = AnyMintTokenAccountAuthorityAddressResolver {
+            any_mint_token_account_pubkey: &any_mint_token_account_pubkey,
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+        let (any_mint_token_account_authority_pubkey, any_mint_token_account_authority_pubkey_bump_seed) = any_mint_token_account_authority_address_resolver.find();
+        let allowed_mints_address_resolver = AllowedMintsAddressResolver {
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+        let (allowed_mints_pubkey, _) = allowed_mints_address_resolver.find();
+        let risk_limits_address_resolver = RiskLimitsAddressResolver {
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+        let (risk_limits_pubkey, _) = risk_limits_address_resolver.find();
+        let instructions = vec![
+            Instruction::buy_on_pumpfun_curve(
+                &PROGRAM_PUBKEY,
+                &intermediary_trader_pubkey,
+                &intermediary_pubkey,
+                &intermediary.w_sol_token_account_pubkey,
+                &intermediary.w_sol_token_account_authority_pubkey,
+                &intermediary.temporary_w_sol_token_account_pubkey,
+                &intermediary.temporary_w_sol_token_account_authority_pubkey,
+                &any_mint_token_account_pubkey,
+                &any_mint_token_account_authority_pubkey,
+                &any_mint_token_mint_pubkey,
+                &pumpfun_curve_accounts.global_pubkey,
+                &pumpfun_curve_accounts.fee_recipient_pubkey,
+                &pumpfun_curve_accounts.bonding_curve_pubkey,
+                &pumpfun_curve_accounts.associated_bonding_curve_pubkey,
+                &pumpfun_curve_accounts.event_authority_pubkey,
+                &allowed_mints_pubkey,
+                &risk_limits_pubkey,
+                &trader_seat_pubkey,
+                &config_pubkey,
+                &config.fee_vault_pubkey,
+                token_amount_out,
+                maximum_lamports_in,
+                any_mint_token_account_pubkey_bump_seed,
+                any_mint_token_account_authority_pubkey_bump_seed,
+            )?,
+        ];
+        let signers = vec![&intermediary_trader_keypair];
+        let recent_blockhash = rpc_client.get_latest_blockhash()?;
+        let message = Message::new_with_blockhash(
+            instructions.as_slice(),
+            Some(&intermediary_trader_pubkey),
+            &recent_blockhash,
+        );
+        let transaction = Transaction::new(
+            signers.as_slice(),
+            message,
+            recent_blockhash,
+        );
+        let signature = rpc_client.send_transaction(&transaction)?;
+        println!("Signature: {}", &signature);
+        Ok(())
+    }
+    pub fn sell_on_pumpfun_curve<'a>(
+        rpc_client: &'a RpcClient,
+        intermediary_trader_keypair_file_path: &'a str,
+        intermediary_pubkey: &'a str,
+        any_mint_token_mint_pubkey: &'a str,
+        input_token_amount: u64,
+        minimum_output_token_amount: Option<u64>,
+    ) -> Result<(), Box<dyn Error + 'static>> {
+        let intermediary_trader_keypair = Self::load_keypair_from_file(&intermediary_trader_keypair_file_path)?;
+        let intermediary_trader_pubkey = intermediary_trader_keypair.pubkey();
+        let intermediary_pubkey = Pubkey::from_str(intermediary_pubkey)?;
+        let any_mint_token_mint_pubkey = Pubkey::from_str(any_mint_token_mint_pubkey)?;
+        if any_mint_token_mint_pubkey == spl_token::native_mint::ID {
+            return Err(Self::ERROR_INVALID_ACCOUNT_PUBKEY.into());
+        }
+        let intermediary_trader_account = rpc_client.get_account(&intermediary_trader_pubkey)?;
+        let token_account_rent_exemption_balance = rpc_client.get_minimum_balance_for_rent_exemption(<Account as Pack>::LEN)?;
+        let (config_pubkey, config) = Self::get_config(rpc_client)?;
+        if intermediary_trader_account.lamports < (token_account_rent_exemption_balance + config.fees.sell_on_pumpfun_curve) {
+            return Err(Self::ERROR_INVALID_ACCOUNT_LAMPORTS.into());
+        }
+        let intermediary = Intermediary::load(rpc_client.get_account(&intermediary_pubkey)?.data.as_slice())?;
+        if !intermediary.is_initialized {
+            return Err(Self::ERROR_INTERMEDIARY_IS_NOT_INITIALIZED.into());
+        }
+        if intermediary.is_paused {
+            return Err(Self::ERROR_INTERMEDIARY_PAUSED.into());
+        }
+        let (trader_seat_pubkey, _) = Self::get_trader_seat(
+            rpc_client,
+            &intermediary_pubkey,
+            &intermediary_trader_pubkey,
+        )?;
+        let any_mint_token_account_address_resolver = AnyMintTokenAccountAddressResolver {
+            any_mint_token_mint_pubkey: &any_mint_token_mint_pubkey,
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+        let (any_mint_token_account_pubkey, any_mint_token_account_pubkey_bump_seed) = any_mint_token_account_address_resolver.find();
+        let any_mint_token_account = Account::unpack(rpc_client.get_account(&any_mint_token_account_pubkey)?.data.as_slice())?;
+        if any_mint_token_account.amount < input_token_amount {
+            return Err(
+                format!(
+                    "The maximum number of tokens to sell is {}",
+                    any_mint_token_account.amount,
+                )
+                .into(),
+            );
+        }
+        let pumpfun_curve_accounts = Self::resolve_pumpfun_curve_accounts(
+            rpc_client,
+            &any_mint_token_mint_pubkey,
+        )?;
+        let temporary_any_mint_token_account_address_resolver = TemporaryAnyMintTokenAccountAddressResolver {
+            any_mint_token_account_pubkey: &any_mint_token_account_pubkey,
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+                let any_mint_token_account_authority_address_resolver = AnyMintTokenAccountAuthorityAddressResolver {
+            any_mint_token_account_pubkey: &any_mint_token_account_pubkey,
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+        let (any_mint_token_account_authority_pubkey, any_mint_token_account_authority_pubkey_bump_seed) = any_mint_token_account_authority_address_resolver.find();
+let (temporary_any_mint_token_account_pubkey, temporary_any_mint_token_account_pubkey_bump_seed) = temporary_any_mint_token_account_address_resolver.find();
+        let temporary_any_mint_token_account_authority_address_resolver = TemporaryAnyMintTokenAccountAuthorityAddressResolver {
+            temporary_any_mint_token_account_pubkey: &temporary_any_mint_token_account_pubkey,
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+        let (temporary_any_mint_token_account_authority_pubkey, temporary_any_mint_token_account_authority_pubkey_bump_seed) =
+            temporary_any_mint_token_account_authority_address_resolver.find();
+        let risk_limits_address_resolver = RiskLimitsAddressResolver {
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+        let (risk_limits_pubkey, _) = risk_limits_address_resolver.find();
+        let instructions = vec![
+            Instruction::sell_on_pumpfun_curve(
+                &PROGRAM_PUBKEY,
+                &intermediary_trader_pubkey,
+                &intermediary_pubkey,
+                &intermediary.w_sol_token_account_pubkey,
+                &any_mint_token_account_pubkey,
+                &any_mint_token_account_authority_pubkey,
+                &temporary_any_mint_token_account_pubkey,
+                &temporary_any_mint_token_account_authority_pubkey,
+                &any_mint_token_mint_pubkey,
+                &pumpfun_curve_accounts.global_pubkey,
+                &pumpfun_curve_accounts.fee_recipient_pubkey,
+                &pumpfun_curve_accounts.bonding_curve_pubkey,
+                &pumpfun_curve_accounts.associated_bonding_curve_pubkey,
+                &pumpfun_curve_accounts.event_authority_pubkey,
+                &risk_limits_pubkey,
+                &trader_seat_pubkey,
+                &config_pubkey,
+                &config.fee_vault_pubkey,
+                input_token_amount,
+                minimum_output_token_amount,
+                any_mint_token_account_pubkey_bump_seed,
+                any_mint_token_account_authority_pubkey_bump_seed,
+                temporary_any_mint_token_account_pubkey_bump_seed,
+                temporary_any_mint_token_account_authority_pubkey_bump_seed,
+            )?,
+        ];
+        let signers = vec![&intermediary_trader_keypair];
+        let recent_blockhash = rpc_client.get_latest_blockhash()?;
+        let message = Message::new_with_blockhash(
+            instructions.as_slice(),
+            Some(&intermediary_trader_pubkey),
+            &recent_blockhash,
+        );
+        let transaction = Transaction::new(
+            signers.as_slice(),
+            message,
+            recent_blockhash,
+        );
+        let signature = rpc_client.send_transaction(&transaction)?;
+        println!("Signature: {}", &signature);
+        Ok(())
+    }
+    pub fn swap<'a>(
+        rpc_client: &'a RpcClient,
+        intermediary_trader_keypair_file_path: &'a str,
+        intermediary_pubkey: &'a str,
+        venue: &'a str,
+        side: &'a str,
+        any_mint_token_mint_pubkey: &'a str,
+        pool_pubkey: &'a str,
+        amount_in: u64,
+        min_out: u64,
+    ) -> Result<(), Box<dyn Error + 'static>> {
+        let intermediary_trader_keypair = Self::load_keypair_from_file(&intermediary_trader_keypair_file_path)?;
+        let intermediary_trader_pubkey = intermediary_trader_keypair.pubkey();
+        let intermediary_pubkey = Pubkey::from_str(intermediary_pubkey)?;
+        let any_mint_token_mint_pubkey = Pubkey::from_str(any_mint_token_mint_pubkey)?;
+        let pool_pubkey = Pubkey::from_str(pool_pubkey)?;
+        let venue = match venue {
+            "raydium" => Venue::Raydium,
+            "pumpswap" => Venue::Pumpswap,
+            _ => return Err(Self::ERROR_VENUE_IS_NOT_SUPPORTED.into()),
+        };
+        let side = match side {
+            "buy" => Side::Buy,
+            "sell" => Side::Sell,
+            _ => return Err("The side is neither buy nor sell.".into()),
+        };
+        if any_mint_token_mint_pubkey == spl_token::native_mint::ID {
+            return Err(Self::ERROR_INVALID_ACCOUNT_PUBKEY.into());
+        }
+        let intermediary_trader_account = rpc_client.get_account(&intermediary_trader_pubkey)?;
+        let token_account_rent_exemption_balance = rpc_client.get_minimum_balance_for_rent_exemption(<Account as Pack>::LEN)?;
+        let (config_pubkey, config) = Self::get_config(rpc_client)?;
+        let (token_accounts_to_create_quantity, fee) = match (venue, side) {
+            (Venue::Raydium, Side::Buy) => (2, config.fees.buy_on_raydium),
+            (Venue::Raydium, Side::Sell) => (1, config.fees.sell_on_raydium),
+            (Venue::Pumpswap, Side::Buy) => (2, config.fees.buy_on_pumpswap),
+            (_, _) => (1, config.fees.sell_on_pumpswap),
+        };
+        if intermediary_trader_account.lamports < (token_accounts_to_create_quantity * token_account_rent_exemption_balance + fee) {
+            return Err(Self::ERROR_INVALID_ACCOUNT_LAMPORTS.into());
+        }
+        let intermediary = Intermediary::load(rpc_client.get_account(&intermediary_pubkey)?.data.as_slice())?;
+        if !intermediary.is_initialized {
+            return Err(Self::ERROR_INTERMEDIARY_IS_NOT_INITIALIZED.into());
+        }
+        if intermediary.is_paused {
+            return Err(Self::ERROR_INTERMEDIARY_PAUSED.into());
+        }
+        let (trader_seat_pubkey, trader_seat) = Self::get_trader_seat(
+            rpc_client,
+            &intermediary_pubkey,
+            &intermediary_trader_pubkey,
+        )?;
+        let any_mint_token_account_address_resolver = AnyMintTokenAccountAddressResolver {
+            any_mint_token_mint_pubkey: &any_mint_token_mint_pubkey,
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+        let (any_mint_token_account_pubkey, any_mint_token_account_pubkey_bump_seed) = any_mint_token_account_address_resolver.find();
+        match side {
+            Side::Buy => {
+                if trader_seat.spent_lamports.saturating_add(amount_in) > trader_seat.allocated_lamports.saturating_add(trader_seat.received_lamports) {
+                    return Err(Self::ERROR_TRADER_SEAT_ALLOCATION_EXCEEDED.into());
+                }
+                let w_sol_token_account = Account::unpack_unchecked(rpc_client.get_account(&intermediary.w_sol_token_account_pubkey)?.data.as_slice())?;
+                if w_sol_token_account.amount < amount_in {
+                    return Err(
+                        format!(
+                            "The maximum number of lamports from treasury is {}",
+                            w_sol_token_account.amount,
+                        )
+                        .into(),
+                    );
+                }
+            }
+            Side::Sell => {
+                let any_mint_token_account = StateWithExtensions::<spl_token_2022::state::Account>::unpack(rpc_client.get_account(&any_mint_token_account_pubkey)?.data.as_slice())?.base;
+                if any_mint_token_account.amount < amount_in {
+                    return Err(
+                        format!(
+                            "The maximum number of tokens to sell is {}",
+                            any_mint_token_account.amount,
+                        )
+                        .into(),
+                    );
+                }
+            }
+        }
+        let (any_mint_token_program_pubkey, venue_account_pubkeys) = Self::resolve_venue_account_pubkeys(
+            rpc_client,
+            venue,
+            &pool_pubkey,
+            &any_mint_token_mint_pubkey,
+            &spl_token::native_mint::ID,
+        )?;
+        let temporary_any_mint_token_account_address_resolver = TemporaryAnyMintTokenAccountAddressResolver {
+            any_mint_token_account_pubkey: &any_mint_token_account_pubkey,
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+        let (temporary_any_mint_token_account_pubkey, temporary_any_mint_token_account_pubkey_bump_seed) = temporary_any_mint_token_account_address_resolver.find();
+        let any_mint_token_account_authority_address_resolver = AnyMintTokenAccountAuthorityAddressResolver {
+            any_mint_token_account_pubkey: &any_mint_token_account_pubkey,
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+        let (any_mint_token_account_authority_pubkey, any_mint_token_account_authority_pubkey_bump_seed) = any_mint_token_account_authority_address_resolver.find();
+        let temporary_any_mint_token_account_authority_address_resolver = TemporaryAnyMintTokenAccountAuthorityAddressResolver {
+            temporary_any_mint_token_account_pubkey: &temporary_any_mint_token_account_pubkey,
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+        let (temporary_any_mint_token_account_authority_pubkey, temporary_any_mint_token_account_authority_pubkey_bump_seed) =
+            temporary_any_mint_token_account_authority_address_resolver.find();
+        let allowed_mints_address_resolver = AllowedMintsAddressResolver {
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+        let (allowed_mints_pubkey, _) = allowed_mints_address_resolver.find();
+        let risk_limits_address_resolver = RiskLimitsAddressResolver {
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+        let (risk_limits_pubkey, _) = risk_limits_address_resolver.find();
+        let instructions = vec![
+            Instruction::swap(
+                &PROGRAM_PUBKEY,
+                &intermediary_trader_pubkey,
+                &intermediary_pubkey,
+                &intermediary.w_sol_token_account_pubkey,
+                &intermediary.w_sol_token_account_authority_pubkey,
+                &intermediary.temporary_w_sol_token_account_pubkey,
+                &intermediary.temporary_w_sol_token_account_authority_pubkey,
+                &any_mint_token_account_pubkey,
+                &any_mint_token_account_authority_pubkey,
+                &temporary_any_mint_token_account_pubkey,
+                &temporary_any_mint_token_account_authority_pubkey,
+                &any_mint_token_mint_pubkey,
+                &any_mint_token_program_pubkey,
+                &allowed_mints_pubkey,
+                &risk_limits_pubkey,
+                &trader_seat_pubkey,
+                &config_pubkey,
+                &config.fee_vault_pubkey,
+                venue,
+                venue_account_pubkeys.as_slice(),
+                side,
+                amount_in,
+                min_out,
+                any_mint_token_account_pubkey_bump_seed,
+                any_mint_token_account_authority_pubkey_bump_seed,
+                temporary_any_mint_token_account_pubkey_bump_seed,
+                temporary_any_mint_token_account_authority_pubkey_bump_seed,
+            )?,
+        ];
+        let signers = vec![&intermediary_trader_keypair];
+        let recent_blockhash = rpc_client.get_latest_blockhash()?;
+        let message = Message::new_with_blockhash(
+            instructions.as_slice(),
+            Some(&intermediary_trader_pubkey),
+            &recent_blockhash,
+        );
+        let transaction = Transaction::new(
+            signers.as_slice(),
+            message,
+            recent_blockhash,
+        );
+        let signature = rpc_client.send_transaction(&transaction)?;
+        println!("Signature: {}", &signature);
+        Ok(())
+    }
+    pub fn routed_swap<'a>(
+        rpc_client: &'a RpcClient,
+        intermediary_trader_keypair_file_path: &'a str,
+        intermediary_pubkey: &'a str,
+        side: &'a str,
+        any_mint_token_mint_pubkey: &'a str,
+        venues: &'a str,
+        pool_pubkeys: &'a str,
+        intermediate_token_mint_pubkeys: &'a str,
+        amount_in: u64,
+        min_out: u64,
+        address_lookup_table_pubkey: Option<&'a str>,
+    ) -> Result<(), Box<dyn Error + 'static>> {
+        let intermediary_trader_keypair = Self::load_keypair_from_file(&intermediary_trader_keypair_file_path)?;
+        let intermediary_trader_pubkey = intermediary_trader_keypair.pubkey();
+        let intermediary_pubkey = Pubkey::from_str(intermediary_pubkey)?;
+        let any_mint_token_mint_pubkey = Pubkey::from_str(any_mint_token_mint_pubkey)?;
+        let venues = venues
+            .split(',')
+            .map(
+                |venue| {
+                    match venue {
+                        "raydium" => Ok(Venue::Raydium),
+                        "pumpswap" => Ok(Venue::Pumpswap),
+                        _ => Err(Self::ERROR_VENUE_IS_NOT_SUPPORTED),
+                    }
+                },
+            )
+            .collect::<Result<Vec<Venue>, _>>()?;
+        let pool_pubkeys = pool_pubkeys.split(',').map(Pubkey::from_str).collect::<Result<Vec<Pubkey>, _>>()?;
+        let intermediate_token_mint_pubkeys = intermediate_token_mint_pubkeys.split(',').map(Pubkey::from_str).collect::<Result<Vec<Pubkey>, _>>()?;
+        let side = match side {
+            "buy" => Side::Buy,
+            "sell" => Side::Sell,
+            _ => return Err("The side is neither buy nor sell.".into()),
+        };
+        if venues.len() != pool_pubkeys.len() || venues.len() != intermediate_token_mint_pubkeys.len() + 1 || venues.len() < 2 || venues.len() > 3 {
+            return Err("The route needs two or three venues, one pool per venue and one intermediate mint between each two venues.".into());
+        }
+        if side == Side::Buy && venues.contains(&Venue::Pumpswap) {
+            return Err("PumpSwap can not take a leg of a buy route.".into());
+        }
+        if any_mint_token_mint_pubkey == spl_token::native_mint::ID
+            || intermediate_token_mint_pubkeys
+                .iter()
+                .any(|intermediate_token_mint_pubkey| *intermediate_token_mint_pubkey == spl_token::native_mint::ID || *intermediate_token_mint_pubkey == any_mint_token_mint_pubkey)
+            || intermediate_token_mint_pubkeys.iter().collect::<HashSet<&Pubkey>>().len() != intermediate_token_mint_pubkeys.len()
+        {
+            return Err(Self::ERROR_INVALID_ACCOUNT_PUBKEY.into());
+        }
+        let intermediary_trader_account = rpc_client.get_account(&intermediary_trader_pubkey)?;
+        let token_account_rent_exemption_balance = rpc_client.get_minimum_balance_for_rent_exemption(<Account as Pack>::LEN)?;
+        let (config_pubkey, config) = Self::get_config(rpc_client)?;
+        let fee = venues
+            .iter()
+            .map(
+                |venue| {
+                    match (*venue, side) {
+                        (Venue::Raydium, Side::Buy) => config.fees.buy_on_raydium,
+                        (Venue::Raydium, Side::Sell) => config.fees.sell_on_raydium,
+                        (Venue::Pumpswap, Side::Buy) => config.fees.buy_on_pumpswap,
+                        (_, _) => config.fees.sell_on_pumpswap,
+                    }
+                },
+            )
+            .sum::<u64>();
+        let intermediary = Intermediary::load(rpc_client.get_account(&intermediary_pubkey)?.data.as_slice())?;
+        if !intermediary.is_initialized {
+            return Err(Self::ERROR_INTERMEDIARY_IS_NOT_INITIALIZED.into());
+        }
+        if intermediary.is_paused {
+            return Err(Self::ERROR_INTERMEDIARY_PAUSED.into());
+        }
+        let any_mint_token_account_address_resolver = AnyMintTokenAccountAddressResolver {
+            any_mint_token_mint_pubkey: &any_mint_token_mint_pubkey,
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+        let (any_mint_token_account_pubkey, any_mint_token_account_pubkey_bump_seed) = any_mint_token_account_address_resolver.find();
+        let any_mint_token_account_authority_address_resolver = AnyMintTokenAccountAuthorityAddressResolver {
+            any_mint_token_account_pubkey: &any_mint_token_account_pubkey,
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+        let (any_mint_token_account_authority_pubkey, any_mint_token_account_authority_pubkey_bump_seed) = any_mint_token_account_authority_address_resolver.find();
+        let mut route_hops = Vec::<RouteHop>::with_capacity(intermediate_token_mint_pubkeys.len());
+        let mut token_accounts_to_create_quantity = 1;
+        for intermediate_token_mint_pubkey in intermediate_token_mint_pubkeys.iter() {
+            if rpc_client.get_account(intermediate_token_mint_pubkey)?.owner != spl_token::ID {
+                return Err(Self::ERROR_INVALID_ACCOUNT_OWNER.into());
+            }
+            let intermediate_token_account_address_resolver = IntermediateTokenAccountAddressResolver {
+                intermediate_token_mint_pubkey,
+                any_mint_token_account_pubkey: &any_mint_token_account_pubkey,
+                intermediary_pubkey: &intermediary_pubkey,
+            };
+            let (intermediate_token_account_pubkey, intermediate_token_account_pubkey_bump_seed) = intermediate_token_account_address_resolver.find();
+            if rpc_client.get_account(&intermediate_token_account_pubkey).is_err() {
+                token_accounts_to_create_quantity += 1;
+            }
+            route_hops.push(
+                RouteHop {
+                    intermediate_token_mint_pubkey: *intermediate_token_mint_pubkey,
+                    intermediate_token_account_pubkey,
+                    intermediate_token_account_pubkey_bump_seed,
+                },
+            );
+        }
+        if intermediary_trader_account.lamports < (token_accounts_to_create_quantity * token_account_rent_exemption_balance + fee) {
+            return Err(Self::ERROR_INVALID_ACCOUNT_LAMPORTS.into());
+        }
+        let (trader_seat_pubkey, trader_seat) = Self::get_trader_seat(
+            rpc_client,
+            &intermediary_pubkey,
+            &intermediary_trader_pubkey,
+        )?;
+        // The route runs from the first mint to the last one, each leg trading the base token of its pool against the quote token.
+        let mut route_token_mint_pubkeys = Vec::<Pubkey>::with_capacity(venues.len() + 1);
+        match side {
+            Side::Buy => {
+                if trader_seat.spent_lamports.saturating_add(amount_in) > trader_seat.allocated_lamports.saturating_add(trader_seat.received_lamports) {
+                    return Err(Self::ERROR_TRADER_SEAT_ALLOCATION_EXCEEDED.into());
+                }
+                let w_sol_token_account = Account::unpack_unchecked(rpc_client.get_account(&intermediary.w_sol_token_account_pubkey)?.data.as_slice())?;
+                if w_sol_token_account.amount < amount_in {
+                    return Err(
+                        format!(
+                            "The maximum number of lamports from treasury is {}",
+                            w_sol_token_account.amount,
+                        )
+                        .into(),
+                    );
+                }
+                route_token_mint_pubkeys.push(spl_token::native_mint::ID);
+                route_token_mint_pubkeys.extend(intermediate_token_mint_pubkeys.iter());
+                route_token_mint_pubkeys.push(any_mint_token_mint_pubkey);
+            }
+            Side::Sell => {
+                let any_mint_token_account = StateWithExtensions::<spl_token_2022::state::Account>::unpack(rpc_client.get_account(&any_mint_token_account_pubkey)?.data.as_slice())?.base;
+                if any_mint_token_account.amount < amount_in {
+                    return Err(
+                        format!(
+                            "The maximum number of tokens to sell is {}",
+                            any_mint_token_account.amount,
+                        )
+                        .into(),
+                    );
+                }
+                route_token_mint_pubkeys.push(any_mint_token_mint_pubkey);
+                route_token_mint_pubkeys.extend(intermediate_token_mint_pubkeys.iter());
+                route_token_mint_pubkeys.push(spl_token::native_mint::ID);
+            }
+        }
+        let mut route_legs = Vec::<RouteLeg>::with_capacity(venues.len());
+        for (index, (venue, pool_pubkey)) in venues.iter().zip(pool_pubkeys.iter()).enumerate() {
+            let (quote_token_mint_pubkey, base_token_mint_pubkey) = match side {
+                Side::Buy => (&route_token_mint_pubkeys[index], &route_token_mint_pubkeys[index + 1]),
+                Side::Sell => (&route_token_mint_pubkeys[index + 1], &route_token_mint_pubkeys[index]),
+            };
+            let (_, venue_account_pubkeys) = Self::resolve_venue_account_pubkeys(
+                rpc_client,
+                *venue,
+                pool_pubkey,
+                base_token_mint_pubkey,
+                quote_token_mint_pubkey,
+            )?;
+            route_legs.push(
+                RouteLeg {
+                    venue: *venue,
+                    venue_account_pubkeys,
+                },
+            );
+        }
+        let any_mint_token_program_pubkey = rpc_client.get_account(&any_mint_token_mint_pubkey)?.owner;
+        let allowed_mints_address_resolver = AllowedMintsAddressResolver {
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+        let (allowed_mints_pubkey, _) = allowed_mints_address_resolver.find();
+        let risk_limits_address_resolver = RiskLimitsAddressResolver {
+            intermediary_pubkey: &intermediary_pubkey,
+        };
+        let (risk_limits_pubkey, _) = risk_limits_address_resolver.find();
+        let instructions = vec![
+            Instruction::routed_swap(
+                &PROGRAM_PUBKEY,
+                &intermediary_trader_pubkey,
+                &intermediary_pubkey,
+                &intermediary.w_sol_token_account_pubkey,
+                &intermediary.w_sol_token_account_authority_pubkey,
+                &intermediary.temporary_w_sol_token_account_pubkey,
+                &any_mint_token_account_pubkey,
+                &any_mint_token_account_authority_pubkey,
+                &any_mint_token_mint_pubkey,
+                &any_mint_token_program_pubkey,
+                &allowed_mints_pubkey,
+                &risk_limits_pubkey,
+                &trader_seat_pubkey,
+                &config_pubkey,
+                &config.fee_vault_pubkey,
+                route_hops.as_slice(),
+                route_legs.as_slice(),
+                side,
+                amount_in,
+                min_out,
+                any_mint_token_account_pubkey_bump_seed,
+                any_mint_token_account_authority_pubkey_bump_seed,
+            )?,
+        ];
+        // A route of two or three venues rarely fits the static accounts of a transaction, the lookup table carries the rest.
+        let address_lookup_table_accounts = match address_lookup_table_pubkey {
+            Some(address_lookup_table_pubkey_) => {
+                let address_lookup_table_pubkey_ = Pubkey::from_str(address_lookup_table_pubkey_)?;
+                let address_lookup_table_account = rpc_client.get_account(&address_lookup_table_pubkey_)?;
+                vec![
+                    AddressLookupTableAccount {
+                        key: address_lookup_table_pubkey_,
+                        addresses: AddressLookupTable::deserialize(address_lookup_table_account.data.as_slice())?.addresses.to_vec(),
+                    },
+                ]
+            }
+            None => vec![],
+        };
+        let signers = vec![&intermediary_trader_keypair];
+        let recent_blockhash = rpc_client.get_latest_blockhash()?;
+        let message = v0::Message::try_compile(
+            &intermediary_trader_pubkey,
+            instructions.as_slice(),
+            address_lookup_table_accounts.as_slice(),
+            recent_blockhash,
+        )?;
+        let transaction = VersionedTransaction::try_new(
+            VersionedMessage::V0(message),
+            signers.as_slice(),
+        )?;
+        let signature = rpc_client.send_transaction(&transaction)?;
+        println!("Signature: {}", &signature);
+        Ok(())
+    }
+    fn get_config<'a>(rpc_client: &'a RpcClient) -> Result<(Pubkey, Config), Box<dyn Error + 'static>> {
+        let (config_pubkey, _) = ConfigAddressResolver.find();
+        let config_account = rpc_client.get_account(&config_pubkey)?;
+        if config_account.owner != PROGRAM_PUBKEY {
+            return Err(Self::ERROR_INVALID_ACCOUNT_OWNER.into());
+        }
+        let config = borsh::from_slice::<Config>(config_account.data.as_slice())?;
+        if !config.is_initialized {
+            return Err(Self::ERROR_CONFIG_IS_NOT_INITIALIZED.into());
+        }
+        Ok(
+            (
+                config_pubkey,
+                config,
+            ),
+        )
+    }
+    fn get_trader_seat<'a>(
+        rpc_client: &'a RpcClient,
+        intermediary_pubkey: &'a Pubkey,
+        intermediary_trader_pubkey: &'a Pubkey,
+    ) -> Result<(Pubkey, TraderSeat), Box<dyn Error + 'static>> {
+        let trader_seat_address_resolver = TraderSeatAddressResolver {
+            intermediary_pubkey,
+            trader_pubkey: intermediary_trader_pubkey,
+        };
+        let (trader_seat_pubkey, _) = trader_seat_address_resolver.find();
+        let trader_seat_account = match rpc_client.get_account(&trader_seat_pubkey) {
+            Ok(trader_seat_account_) => trader_seat_account_,
+            Err(_) => return Err(Self::ERROR_TRADER_SEAT_IS_NOT_INITIALIZED.into()),
+        };
+        if trader_seat_account.owner != PROGRAM_PUBKEY {
+            return Err(Self::ERROR_INVALID_ACCOUNT_OWNER.into());
+        }
+        let trader_seat = borsh::from_slice::<TraderSeat>(trader_seat_account.data.as_slice())?;
+        if !trader_seat.is_initialized {
+            return Err(Self::ERROR_TRADER_SEAT_IS_NOT_INITIALIZED.into());
+        }
+        Ok(
+            (
+                trader_seat_pubkey,
+                trader_seat,
+            ),
+        )
+    }
+    // https://github.com/raydium-io/raydium-amm/blob/d10a8e9fab9f7a3d87b4ae3891e3e4c24b75c041/program/src/state.rs#L320
+    // https://github.com/openbook-dex/program/blob/c85e56deeaead43abbc33b7301058838b9c5136d/dex/src/state.rs#L291
+    fn resolve_raydium_amm_accounts<'a>(
+        rpc_client: &'a RpcClient,
+        amm_pool_pubkey: &'a Pubkey,
+        any_mint_token_mint_pubkey: &'a Pubkey,
+        quote_token_mint_pubkey: &'a Pubkey,
+    ) -> Result<AmmAccounts, Box<dyn Error + 'static>> {
+        let (amm_info, amm_accounts) = Self::fetch_raydium_amm_accounts(
+            rpc_client,
+            amm_pool_pubkey,
+        )?;
+        let is_pool_trades_mint_against_quote_token = (amm_info.coin_vault_mint_pubkey == *any_mint_token_mint_pubkey && amm_info.pc_vault_mint_pubkey == *quote_token_mint_pubkey)
+            || (amm_info.coin_vault_mint_pubkey == *quote_token_mint_pubkey && amm_info.pc_vault_mint_pubkey == *any_mint_token_mint_pubkey);
+        if !is_pool_trades_mint_against_quote_token {
+            return Err("The pool does not trade the mint against the quote token.".into());
+        }
+        Ok(amm_accounts)
+    }
+    // Resolves every pool and market account of a Raydium AMM v4 swap from the pool pubkey alone.
+    fn fetch_raydium_amm_accounts<'a>(rpc_client: &'a RpcClient, amm_pool_pubkey: &'a Pubkey) -> Result<(AmmInfo, AmmAccounts), Box<dyn Error + 'static>> {
+        let amm_pool_account = rpc_client.get_account(amm_pool_pubkey)?;
+        if amm_pool_account.owner != RAYDIUM_LIQUIDITY_POOL_V4_PROGRAM_PUBKEY {
+            return Err(Self::ERROR_INVALID_ACCOUNT_OWNER.into());
+        }
+        let amm_info = AmmInfo::load(amm_pool_account.data.as_slice())?;
+        let market_account = rpc_client.get_account(&amm_info.market_pubkey)?;
+        if market_account.owner != amm_info.market_program_id_pubkey {
+            return Err(Self::ERROR_INVALID_ACCOUNT_OWNER.into());
+        }
+        let market_state = MarketState::load(market_account.data.as_slice())?;
+        let amm_accounts = AmmAccounts::resolve(
+            &amm_info,
+            &market_state,
+        )?;
+        Ok(
+            (
+                amm_info,
+                amm_accounts,
+            ),
+        )
+    }
+    fn quote_raydium_minimum_output_token_amount<'a>(
+        rpc_client: &'a RpcClient,
+        amm_pool_pubkey: &'a Pubkey,
+        input_token_mint_pubkey: &'a Pubkey,
+        input_token_amount: u64,
+        slippage_basis_points: u16,
+    ) -> Result<u64, Box<dyn Error + 'static>> {
+        let amm_pool_account = rpc_client.get_account(amm_pool_pubkey)?;
+        if amm_pool_account.owner != RAYDIUM_LIQUIDITY_POOL_V4_PROGRAM_PUBKEY {
+            return Err(Self::ERROR_INVALID_ACCOUNT_OWNER.into());
+        }
+        let amm_info = AmmInfo::load(amm_pool_account.data.as_slice())?;
+        let amm_coin_vault = Account::unpack_unchecked(rpc_client.get_account(&amm_info.coin_vault_pubkey)?.data.as_slice())?;
+        let amm_pc_vault = Account::unpack_unchecked(rpc_client.get_account(&amm_info.pc_vault_pubkey)?.data.as_slice())?;
+        let (coin_reserve, pc_reserve) = amm_info.get_reserves(
+            amm_coin_vault.amount,
+            amm_pc_vault.amount,
+        )?;
+        let quote = if amm_info.coin_vault_mint_pubkey == *input_token_mint_pubkey {
+            amm_info.quote_exact_in(
+                coin_reserve,
+                pc_reserve,
+                input_token_amount,
+            )?
+        } else {
+            amm_info.quote_exact_in(
+                pc_reserve,
+                coin_reserve,
+                input_token_amount,
+            )?
+        };
+        let minimum_output_token_amount = get_minimum_amount_out(
+            quote.amount_out,
+            slippage_basis_points,
+        )?;
+        println!("QuotedOutputTokenAmount: {}", &quote.amount_out);
+        println!("MinimumOutputTokenAmount: {}", &minimum_output_token_amount);
+        Ok(minimum_output_token_amount)
+    }
+    // Only sells are quoted: the buy instruction takes an exact base amount out and a maximum quote amount in.
+    fn quote_pumpswap_minimum_output_token_amount<'a>(
+        rpc_client: &'a RpcClient,
+        pool_pubkey: &'a Pubkey,
+        any_mint_token_mint_pubkey: &'a Pubkey,
+        input_token_amount: u64,
+        slippage_basis_points: u16,
+    ) -> Result<u64, Box<dyn Error + 'static>> {
+        let pool_account = rpc_client.get_account(pool_pubkey)?;
+        if pool_account.owner != PUMPSWAP_PROGRAM_PUBKEY {
+            return Err(Self::ERROR_INVALID_ACCOUNT_OWNER.into());
+        }
+        let pool = Pool::load(pool_account.data.as_slice())?;
+        if pool.base_mint_pubkey != *any_mint_token_mint_pubkey {
+            return Err(Self::ERROR_INVALID_ACCOUNT_PUBKEY.into());
+        }
+        let global_config = GlobalConfig::load(rpc_client.get_account(&find_global_config_pubkey())?.data.as_slice())?;
+        let pool_base_token_account = StateWithExtensions::<spl_token_2022::state::Account>::unpack(rpc_client.get_account(&pool.pool_base_token_account_pubkey)?.data.as_slice())?.base;
+        let pool_quote_token_account = StateWithExtensions::<spl_token_2022::state::Account>::unpack(rpc_client.get_account(&pool.pool_quote_token_account_pubkey)?.data.as_slice())?.base;
+        let quote = global_config.quote_sell_exact_in(
+            pool_base_token_account.amount,
+            pool_quote_token_account.amount,
+            input_token_amount,
+        )?;
+        let minimum_output_token_amount = get_minimum_amount_out(
+            quote.amount_out,
+            slippage_basis_points,
+        )?;
+        println!("QuotedOutputTokenAmount: {}", &quote.amount_out);
+        println!("MinimumOutputTokenAmount: {}", &minimum_output_token_amount);
+        Ok(minimum_output_token_amount)
+    }
+    fn resolve_venue_account_pubkeys<'a>(
+        rpc_client: &'a RpcClient,
+        venue: Venue,
+        pool_pubkey: &'a Pubkey,
+        base_token_mint_pubkey: &'a Pubkey,
+        quote_token_mint_pubkey: &'a Pubkey,
+    ) -> Result<(Pubkey, Vec<Pubkey>), Box<dyn Error + 'static>> {
+        let (base_token_program_pubkey, venue_account_pubkeys) = match venue {
+            Venue::Raydium => {
+                let raydium_amm_accounts = Self::resolve_raydium_amm_accounts(
+                    rpc_client,
+                    pool_pubkey,
+                    base_token_mint_pubkey,
+                    quote_token_mint_pubkey,
+                )?;
+                (
+                    rpc_client.get_account(base_token_mint_pubkey)?.owner,
+                    vec![
+                        RAYDIUM_LIQUIDITY_POOL_V4_PROGRAM_PUBKEY,
+                        *pool_pubkey,
+                        raydium_amm_accounts.amm_authority_pubkey,
+                        raydium_amm_accounts.amm_open_orders_pubkey,
+                        raydium_amm_accounts.amm_coin_vault_pubkey,
+                        raydium_amm_accounts.amm_pc_vault_pubkey,
+                        raydium_amm_accounts.market_program_id_pubkey,
+                        raydium_amm_accounts.market_pubkey,
+                        raydium_amm_accounts.market_bids_pubkey,
+                        raydium_amm_accounts.market_asks_pubkey,
+                        raydium_amm_accounts.market_event_queue_pubkey,
+                        raydium_amm_accounts.market_coin_vault_pubkey,
+                        raydium_amm_accounts.market_pc_vault_pubkey,
+                        raydium_amm_accounts.market_vault_signer_pubkey,
+                    ],
+                )
+            }
+            _ => {
+                let pumpswap_pool_accounts = Self::resolve_pumpswap_pool_accounts(
+                    rpc_client,
+                    pool_pubkey,
+                    base_token_mint_pubkey,
+                    quote_token_mint_pubkey,
+                )?;
+                (
+                    pumpswap_pool_accounts.base_token_program_pubkey,
+                    vec![
+                        PUMPSWAP_PROGRAM_PUBKEY,
+                        *pool_pubkey,
+                        pumpswap_pool_accounts.global_config_pubkey,
+                        pumpswap_pool_accounts.pool_base_token_account_pubkey,
+                        pumpswap_pool_accounts.pool_quote_token_account_pubkey,
+                        pumpswap_pool_accounts.protocol_fee_recipient_pubkey,
+                        pumpswap_pool_accounts.protocol_fee_recipient_token_account_pubkey,
+                        pumpswap_pool_accounts.quote_token_program_pubkey,
+                        SPL_ASSOCIATED_TOKEN_ACCOUNT_ID,
+                        pumpswap_pool_accounts.event_authority_pubkey,
+                    ],
+                )
+            }
+        };
+        Ok(
+            (
+                base_token_program_pubkey,
+                venue_account_pubkeys,
+            ),
+        )
+    }
+    // From IDL (https://www.npmjs.com/package/@pump-fun/pump-swap-sdk)
+    fn resolve_pumpswap_pool_accounts<'a>(
+        rpc_client: &'a RpcClient,
+        pool_pubkey: &'a Pubkey,
+        any_mint_token_mint_pubkey: &'a Pubkey,
+        quote_token_mint_pubkey: &'a Pubkey,
+    ) -> Result<PoolAccounts, Box<dyn Error + 'static>> {
+        let pool_account = rpc_client.get_account(pool_pubkey)?;
+        if pool_account.owner != PUMPSWAP_PROGRAM_PUBKEY {
+            return Err(Self::ERROR_INVALID_ACCOUNT_OWNER.into());
+        }
+        let pool = Pool::load(pool_account.data.as_slice())?;
+        if pool.base_mint_pubkey != *any_mint_token_mint_pubkey || pool.quote_mint_pubkey != *quote_token_mint_pubkey {
+            return Err("The pool does not trade the mint against the quote token.".into());
+        }
+        let global_config_account = rpc_client.get_account(&find_global_config_pubkey())?;
+        if global_config_account.owner != PUMPSWAP_PROGRAM_PUBKEY {
+            return Err(Self::ERROR_INVALID_ACCOUNT_OWNER.into());
+        }
+        let global_config = GlobalConfig::load(global_config_account.data.as_slice())?;
+        let base_token_program_pubkey = rpc_client.get_account(&pool.base_mint_pubkey)?.owner;
+        Ok(
+            PoolAccounts::resolve(
+                &pool,
+                &global_config,
+                &base_token_program_pubkey,
+            )?,
+        )
+    }
+    // The pool is the canonical one of the mint when it is not given.
+    fn resolve_pumpswap_pool_pubkey<'a>(pool_pubkey: Option<&'a str>, any_mint_token_mint_pubkey: &'a Pubkey) -> Result<Pubkey, Box<dyn Error + 'static>> {
+        let pool_pubkey = match pool_pubkey {
+            Some(pool_pubkey_) => Pubkey::from_str(pool_pubkey_)?,
+            None => {
+                let pool_pubkey_ = find_canonical_pool_pubkey(any_mint_token_mint_pubkey);
+                println!("Pool: {}", &pool_pubkey_);
+                pool_pubkey_
+            }
+        };
+        Ok(pool_pubkey)
+    }
+    // https://github.com/raydium-io/raydium-cp-swap/blob/master/programs/cp-swap/src/states/pool.rs
+    fn resolve_raydium_cpmm_pool_accounts<'a>(
+        rpc_client: &'a RpcClient,
+        pool_state_pubkey: &'a Pubkey,
+        any_mint_token_mint_pubkey: &'a Pubkey,
+    ) -> Result<RaydiumCpmmPoolAccounts, Box<dyn Error + 'static>> {
+        const POOL_STATE_MINIMUM_LENGTH: usize = 328;
+        let pool_state_account = rpc_client.get_account(pool_state_pubkey)?;
+        if pool_state_account.owner != RAYDIUM_CPMM_PROGRAM_PUBKEY {
+            return Err(Self::ERROR_INVALID_ACCOUNT_OWNER.into());
+        }
+        if pool_state_account.data.len() < POOL_STATE_MINIMUM_LENGTH {
+            return Err(Self::ERROR_INVALID_ACCOUNT_DATA.into());
+        }
+        let amm_config_pubkey = Pubkey::try_from(&pool_state_account.data[8..40])?;
+        let token_0_vault_pubkey = Pubkey::try_from(&pool_state_account.data[72..104])?;
+        let token_1_vault_pubkey = Pubkey::try_from(&pool_state_account.data[104..136])?;
+        let token_0_mint_pubkey = Pubkey::try_from(&pool_state_account.data[168..200])?;
+        let token_1_mint_pubkey = Pubkey::try_from(&pool_state_account.data[200..232])?;
+        let token_0_program_pubkey = Pubkey::try_from(&pool_state_account.data[232..264])?;
+        let token_1_program_pubkey = Pubkey::try_from(&pool_state_account.data[264..296])?;
+        let observation_state_pubkey = Pubkey::try_from(&pool_state_account.data[296..328])?;
+        let (w_sol_vault_pubkey, any_mint_vault_pubkey, any_mint_token_program_pubkey) =
+            if token_0_mint_pubkey == spl_token::native_mint::ID && token_1_mint_pubkey == *any_mint_token_mint_pubkey {
+                (
+                    token_0_vault_pubkey,
+                    token_1_vault_pubkey,
+                    token_1_program_pubkey,
+                )
+            } else if token_0_mint_pubkey == *any_mint_token_mint_pubkey && token_1_mint_pubkey == spl_token::native_mint::ID {
+                (
+                    token_1_vault_pubkey,
+                    token_0_vault_pubkey,
+                    token_0_program_pubkey,
+                )
+            } else {
+                return Err("The pool does not trade the mint against WSol.".into());
+            };
+        let (authority_pubkey, _) = Pubkey::find_program_address(
+            [
+                b"vault_and_lp_mint_auth_seed".as_slice(),
+            ]
+            .as_slice(),
+            &RAYDIUM_CPMM_PROGRAM_PUBKEY,
+        );
+        Ok(
+            RaydiumCpmmPoolAccounts {
+                authority_pubkey,
+                amm_config_pubkey,
+                w_sol_vault_pubkey,
+                any_mint_vault_pubkey,
+                any_mint_token_program_pubkey,
+                observation_state_pubkey,
+            },
+        )
+    }
+    // https://github.com/raydium-io/raydium-clmm/blob/master/programs/amm/src/states/pool.rs
+    // https://github.com/raydium-io/raydium-clmm/blob/master/programs/amm/src/states/tick_array.rs
+    // Passes the bitmap extension when it exists and the first initialized tick arrays in the swap direction.
+    fn resolve_raydium_clmm_pool_accounts<'a>(
+        rpc_client: &'a RpcClient,
+        pool_state_pubkey: &'a Pubkey,
+        any_mint_token_mint_pubkey: &'a Pubkey,
+        is_w_sol_input: bool,
+    ) -> Result<RaydiumClmmPoolAccounts, Box<dyn Error + 'static>> {
+        const POOL_STATE_MINIMUM_LENGTH: usize = 273;
+        const TICK_ARRAY_SIZE: i32 = 60;
+        const TICK_ARRAYS_QUANTITY: usize = 3;
+        const TICK_ARRAY_CANDIDATES_QUANTITY: i32 = 10;
+        let pool_state_account = rpc_client.get_account(pool_state_pubkey)?;
+        if pool_state_account.owner != RAYDIUM_CLMM_PROGRAM_PUBKEY {
+            return Err(Self::ERROR_INVALID_ACCOUNT_OWNER.into());
+        }
+        if pool_state_account.data.len() < POOL_STATE_MINIMUM_LENGTH {
+            return Err(Self::ERROR_INVALID_ACCOUNT_DATA.into());
+        }
+        let amm_config_pubkey = Pubkey::try_from(&pool_state_account.data[9..41])?;
+        let token_mint_0_pubkey = Pubkey::try_from(&pool_state_account.data[73..105])?;
+        let token_mint_1_pubkey = Pubkey::try_from(&pool_state_account.data[105..137])?;
+        let token_vault_0_pubkey = Pubkey::try_from(&pool_state_account.data[137..169])?;
+        let token_vault_1_pubkey = Pubkey::try_from(&pool_state_account.data[169..201])?;
+        let observation_state_pubkey = Pubkey::try_from(&pool_state_account.data[201..233])?;
+        let tick_spacing = u16::from_le_bytes(pool_state_account.data[235..237].try_into()?);
+        let tick_current = i32::from_le_bytes(pool_state_account.data[269..273].try_into()?);
+        let (w_sol_vault_pubkey, any_mint_vault_pubkey, is_w_sol_token_0) =
+            if token_mint_0_pubkey == spl_token::native_mint::ID && token_mint_1_pubkey == *any_mint_token_mint_pubkey {
+                (
+                    token_vault_0_pubkey,
+                    token_vault_1_pubkey,
+                    true,
+                )
+            } else if token_mint_0_pubkey == *any_mint_token_mint_pubkey && token_mint_1_pubkey == spl_token::native_mint::ID {
+                (
+                    token_vault_1_pubkey,
+                    token_vault_0_pubkey,
+                    false,
+                )
+            } else {
+                return Err("The pool does not trade the mint against WSol.".into());
+            };
+        // Selling token 0 for token 1 moves the current tick down.
+        let is_zero_for_one = is_w_sol_input == is_w_sol_token_0;
+        let ticks_in_tick_array = i32::from(tick_spacing) * TICK_ARRAY_SIZE;
+        let current_tick_array_start_index = tick_current.div_euclid(ticks_in_tick_array) * ticks_in_tick_array;
+        let (tick_array_bitmap_extension_pubkey, _) = Pubkey::find_program_address(
+            [
+                b"pool_tick_array_bitmap_extension".as_slice(),
+                pool_state_pubkey.as_ref(),
+            ]
+            .as_slice(),
+            &RAYDIUM_CLMM_PROGRAM_PUBKEY,
+        );
+        let mut candidate_pubkeys = vec![tick_array_bitmap_extension_pubkey];
+        for index in 0..TICK_ARRAY_CANDIDATES_QUANTITY {
+            let tick_array_start_index = if is_zero_for_one {
+                current_tick_array_start_index - index * ticks_in_tick_array
+            } else {
+                current_tick_array_start_index + index * ticks_in_tick_array
+            };
+            let (tick_array_pubkey, _) = Pubkey::find_program_address(
+                [
+                    b"tick_array".as_slice(),
+                    pool_state_pubkey.as_ref(),
+                    tick_array_start_index.to_be_bytes().as_slice(),
+                ]
+                .as_slice(),
+                &RAYDIUM_CLMM_PROGRAM_PUBKEY,
+            );
+            candidate_pubkeys.push(tick_array_pubkey);
+        }
+        let candidate_accounts = rpc_client.get_multiple_accounts(candidate_pubkeys.as_slice())?;
+        let mut tick_array_pubkeys = Vec::<Pubkey>::with_capacity(1 + TICK_ARRAYS_QUANTITY);
+        if candidate_accounts[0].is_some() {
+            tick_array_pubkeys.push(tick_array_bitmap_extension_pubkey);
+        }
+        let mut tick_arrays_quantity: usize = 0;
+        for (candidate_pubkey, candidate_account) in candidate_pubkeys[1..].iter().zip(candidate_accounts[1..].iter()) {
+            if tick_arrays_quantity == TICK_ARRAYS_QUANTITY {
+                break;
+            }
+            if candidate_account.is_some() {
+                tick_array_pubkeys.push(*candidate_pubkey);
+                tick_arrays_quantity += 1;
+            }
+        }
+        if tick_arrays_quantity == 0 {
+            return Err("The pool has no initialized tick arrays near the current price.".into());
+        }
+        Ok(
+            RaydiumClmmPoolAccounts {
+                amm_config_pubkey,
+                w_sol_vault_pubkey,
+                any_mint_vault_pubkey,
+                observation_state_pubkey,
+                tick_array_pubkeys,
+            },
+        )
+    }
+    // https://github.com/MeteoraAg/dlmm-sdk/blob/main/programs/lb_clmm/src/state/lb_pair.rs
+    // https://github.com/MeteoraAg/dlmm-sdk/blob/main/programs/lb_clmm/src/state/bin.rs
+    // Passes the first initialized bin arrays in the swap direction, starting from the active bin.
+    fn resolve_meteora_dlmm_accounts<'a>(
+        rpc_client: &'a RpcClient,
+        lb_pair_pubkey: &'a Pubkey,
+        any_mint_token_mint_pubkey: &'a Pubkey,
+        is_w_sol_input: bool,
+    ) -> Result<MeteoraDlmmAccounts, Box<dyn Error + 'static>> {
+        const LB_PAIR_MINIMUM_LENGTH: usize = 584;
+        const MAX_BIN_PER_ARRAY: i64 = 70;
+        const BIN_ARRAYS_QUANTITY: usize = 3;
+        const BIN_ARRAY_CANDIDATES_QUANTITY: i64 = 10;
+        let lb_pair_account = rpc_client.get_account(lb_pair_pubkey)?;
+        if lb_pair_account.owner != METEORA_DLMM_PROGRAM_PUBKEY {
+            return Err(Self::ERROR_INVALID_ACCOUNT_OWNER.into());
+        }
+        if lb_pair_account.data.len() < LB_PAIR_MINIMUM_LENGTH {
+            return Err(Self::ERROR_INVALID_ACCOUNT_DATA.into());
+        }
+        let active_id = i32::from_le_bytes(lb_pair_account.data[76..80].try_into()?);
+        let token_x_mint_pubkey = Pubkey::try_from(&lb_pair_account.data[88..120])?;
+        let token_y_mint_pubkey = Pubkey::try_from(&lb_pair_account.data[120..152])?;
+        let reserve_x_pubkey = Pubkey::try_from(&lb_pair_account.data[152..184])?;
+        let reserve_y_pubkey = Pubkey::try_from(&lb_pair_account.data[184..216])?;
+        let oracle_pubkey = Pubkey::try_from(&lb_pair_account.data[552..584])?;
+        let (w_sol_reserve_pubkey, any_mint_reserve_pubkey, is_w_sol_token_x) =
+            if token_x_mint_pubkey == spl_token::native_mint::ID && token_y_mint_pubkey == *any_mint_token_mint_pubkey {
+                (
+                    reserve_x_pubkey,
+                    reserve_y_pubkey,
+                    true,
+                )
+            } else if token_x_mint_pubkey == *any_mint_token_mint_pubkey && token_y_mint_pubkey == spl_token::native_mint::ID {
+                (
+                    reserve_y_pubkey,
+                    reserve_x_pubkey,
+                    false,
+                )
+            } else {
+                return Err("The pool does not trade the mint against WSol.".into());
+            };
+        let any_mint_token_program_pubkey = rpc_client.get_account(any_mint_token_mint_pubkey)?.owner;
+        // Selling token X for token Y moves the active bin down.
+        let is_swap_for_y = is_w_sol_input == is_w_sol_token_x;
+        let active_bin_array_index = i64::from(active_id).div_euclid(MAX_BIN_PER_ARRAY);
+        let (bin_array_bitmap_extension_pubkey, _) = Pubkey::find_program_address(
+            [
+                b"bitmap".as_slice(),
+                lb_pair_pubkey.as_ref(),
+            ]
+            .as_slice(),
+            &METEORA_DLMM_PROGRAM_PUBKEY,
+        );
+        let mut candidate_pubkeys = vec![bin_array_bitmap_extension_pubkey];
+        for index in 0..BIN_ARRAY_CANDIDATES_QUANTITY {
+            let bin_array_index = if is_swap_for_y {
+                active_bin_array_index - index
+            } else {
+                active_bin_array_index + index
+            };
+            let (bin_array_pubkey, _) = Pubkey::find_program_address(
+                [
+                    b"bin_array".as_slice(),
+                    lb_pair_pubkey.as_ref(),
+                    bin_array_index.to_le_bytes().as_slice(),
+                ]
+                .as_slice(),
+                &METEORA_DLMM_PROGRAM_PUBKEY,
+            );
+            candidate_pubkeys.push(bin_array_pubkey);
+        }
+        let candidate_accounts = rpc_client.get_multiple_accounts(candidate_pubkeys.as_slice())?;
+        // The program id stands in for the optional bitmap extension.
+        let bin_array_bitmap_extension_pubkey = if candidate_accounts[0].is_some() {
+            bin_array_bitmap_extension_pubkey
+        } else {
+            METEORA_DLMM_PROGRAM_PUBKEY
+        };
+        let mut bin_array_pubkeys = Vec::<Pubkey>::with_capacity(BIN_ARRAYS_QUANTITY);
+        for (candidate_pubkey, candidate_account) in candidate_pubkeys[1..].iter().zip(candidate_accounts[1..].iter()) {
+            if bin_array_pubkeys.len() == BIN_ARRAYS_QUANTITY {
+                break;
+            }
+            if candidate_account.is_some() {
+                bin_array_pubkeys.push(*candidate_pubkey);
+            }
+        }
+        if bin_array_pubkeys.is_empty() {
+            return Err("The pool has no initialized bin arrays near the active bin.".into());
+        }
+        let (event_authority_pubkey, _) = Pubkey::find_program_address(
+            [
+                b"__event_authority".as_slice(),
+            ]
+            .as_slice(),
+            &METEORA_DLMM_PROGRAM_PUBKEY,
+        );
+        Ok(
+            MeteoraDlmmAccounts {
+                any_mint_token_program_pubkey,
+                bin_array_bitmap_extension_pubkey,
+                w_sol_reserve_pubkey,
+                any_mint_reserve_pubkey,
+                oracle_pubkey,
+                event_authority_pubkey,
+                bin_array_pubkeys,
+            },
+        )
+    }
+    // https://github.com/MeteoraAg/damm-v1-sdk/blob/main/programs/dynamic-amm/src/state.rs
+    // https://github.com/MeteoraAg/vault-sdk/blob/main/programs/vault/src/state.rs
+    fn resolve_meteora_dynamic_amm_accounts<'a>(
+        rpc_client: &'a RpcClient,
+        pool_pubkey: &'a Pubkey,
+        any_mint_token_mint_pubkey: &'a Pubkey,
+        is_w_sol_input: bool,
+    ) -> Result<MeteoraDynamicAmmAccounts, Box<dyn Error + 'static>> {
+        const POOL_MINIMUM_LENGTH: usize = 298;
+        const VAULT_MINIMUM_LENGTH: usize = 147;
+        let pool_account = rpc_client.get_account(pool_pubkey)?;
+        if pool_account.owner != METEORA_DYNAMIC_AMM_PROGRAM_PUBKEY {
+            return Err(Self::ERROR_INVALID_ACCOUNT_OWNER.into());
+        }
+        if pool_account.data.len() < POOL_MINIMUM_LENGTH {
+            return Err(Self::ERROR_INVALID_ACCOUNT_DATA.into());
+        }
+        let token_a_mint_pubkey = Pubkey::try_from(&pool_account.data[40..72])?;
+        let token_b_mint_pubkey = Pubkey::try_from(&pool_account.data[72..104])?;
+        let a_vault_pubkey = Pubkey::try_from(&pool_account.data[104..136])?;
+        let b_vault_pubkey = Pubkey::try_from(&pool_account.data[136..168])?;
+        let a_vault_lp_pubkey = Pubkey::try_from(&pool_account.data[168..200])?;
+        let b_vault_lp_pubkey = Pubkey::try_from(&pool_account.data[200..232])?;
+        let protocol_token_a_fee_pubkey = Pubkey::try_from(&pool_account.data[234..266])?;
+        let protocol_token_b_fee_pubkey = Pubkey::try_from(&pool_account.data[266..298])?;
+        let (w_sol_vault_pubkey, any_mint_vault_pubkey, w_sol_vault_lp_pubkey, any_mint_vault_lp_pubkey, w_sol_protocol_token_fee_pubkey, any_mint_protocol_token_fee_pubkey) =
+            if token_a_mint_pubkey == spl_token::native_mint::ID && token_b_mint_pubkey == *any_mint_token_mint_pubkey {
+                (
+                    a_vault_pubkey,
+                    b_vault_pubkey,
+                    a_vault_lp_pubkey,
+                    b_vault_lp_pubkey,
+                    protocol_token_a_fee_pubkey,
+                    protocol_token_b_fee_pubkey,
+                )
+            } else if token_a_mint_pubkey == *any_mint_token_mint_pubkey && token_b_mint_pubkey == spl_token::native_mint::ID {
+                (
+                    b_vault_pubkey,
+                    a_vault_pubkey,
+                    b_vault_lp_pubkey,
+                    a_vault_lp_pubkey,
+                    protocol_token_b_fee_pubkey,
+                    protocol_token_a_fee_pubkey,
+                )
+            } else {
+                return Err("The pool does not trade the mint against WSol.".into());
+            };
+        let vault_accounts = rpc_client.get_multiple_accounts(
+            [
+                w_sol_vault_pubkey,
+                any_mint_vault_pubkey,
+            ]
+            .as_slice(),
+        )?;
+        let mut vault_token_vault_and_lp_mint_pubkeys = Vec::<(Pubkey, Pubkey)>::with_capacity(2);
+        for vault_account in vault_accounts.into_iter() {
+            let vault_account_ = vault_account.ok_or(Self::ERROR_INVALID_ACCOUNT_DATA)?;
+            if vault_account_.owner != METEORA_VAULT_PROGRAM_PUBKEY {
+                return Err(Self::ERROR_INVALID_ACCOUNT_OWNER.into());
+            }
+            if vault_account_.data.len() < VAULT_MINIMUM_LENGTH {
+                return Err(Self::ERROR_INVALID_ACCOUNT_DATA.into());
+            }
+            vault_token_vault_and_lp_mint_pubkeys.push(
+                (
+                    Pubkey::try_from(&vault_account_.data[19..51])?,
+                    Pubkey::try_from(&vault_account_.data[115..147])?,
+                ),
+            );
+        }
+        let (w_sol_token_vault_pubkey, w_sol_vault_lp_mint_pubkey) = vault_token_vault_and_lp_mint_pubkeys[0];
+        let (any_mint_token_vault_pubkey, any_mint_vault_lp_mint_pubkey) = vault_token_vault_and_lp_mint_pubkeys[1];
+        // The protocol fee is taken in the input token.
+        let protocol_token_fee_pubkey = if is_w_sol_input {
+            w_sol_protocol_token_fee_pubkey
+        } else {
+            any_mint_protocol_token_fee_pubkey
+        };
+        Ok(
+            MeteoraDynamicAmmAccounts {
+                w_sol_vault_pubkey,
+                any_mint_vault_pubkey,
+                w_sol_token_vault_pubkey,
+                any_mint_token_vault_pubkey,
+                w_sol_vault_lp_mint_pubkey,
+                any_mint_vault_lp_mint_pubkey,
+                w_sol_vault_lp_pubkey,
+                any_mint_vault_lp_pubkey,
+                protocol_token_fee_pubkey,
+            },
+        )
+    }
+    // https://github.com/pump-fun/pump-public-docs
+    fn resolve_pumpfun_curve_accounts<'a>(
+        rpc_client: &'a RpcClient,
+        any_mint_token_mint_pubkey: &'a Pubkey,
+    ) -> Result<PumpfunCurveAccounts, Box<dyn Error + 'static>> {
+        const GLOBAL_MINIMUM_LENGTH: usize = 73;
+        const BONDING_CURVE_MINIMUM_LENGTH: usize = 49;
+        let (global_pubkey, _) = Pubkey::find_program_address(
+            [
+                b"global".as_slice(),
+            ]
+            .as_slice(),
+            &PUMPFUN_PROGRAM_PUBKEY,
+        );
+        let (bonding_curve_pubkey, _) = Pubkey::find_program_address(
+            [
+                b"bonding-curve".as_slice(),
+                any_mint_token_mint_pubkey.as_ref(),
+            ]
+            .as_slice(),
+            &PUMPFUN_PROGRAM_PUBKEY,
+        );
+        let (event_authority_pubkey, _) = Pubkey::find_program_address(
+            [
+                b"__event_authority".as_slice(),
+            ]
+            .as_slice(),
+            &PUMPFUN_PROGRAM_PUBKEY,
+        );
+        let global_account = rpc_client.get_account(&global_pubkey)?;
+        if global_account.owner != PUMPFUN_PROGRAM_PUBKEY {
+            return Err(Self::ERROR_INVALID_ACCOUNT_OWNER.into());
+        }
+        if global_account.data.len() < GLOBAL_MINIMUM_LENGTH {
+            return Err(Self::ERROR_INVALID_ACCOUNT_DATA.into());
+        }
+        let fee_recipient_pubkey = Pubkey::try_from(&global_account.data[41..73])?;
+        let bonding_curve_account = rpc_client.get_account(&bonding_curve_pubkey)?;
+        if bonding_curve_account.owner != PUMPFUN_PROGRAM_PUBKEY {
+            return Err(Self::ERROR_INVALID_ACCOUNT_OWNER.into());
+        }
+        if bonding_curve_account.data.len() < BONDING_CURVE_MINIMUM_LENGTH {
+            return Err(Self::ERROR_INVALID_ACCOUNT_DATA.into());
+        }
+        if bonding_curve_account.data[48] != 0 {
+            return Err("The bonding curve is complete, the mint trades on PumpSwap.".into());
+        }
+        let (associated_bonding_curve_pubkey, _) = Pubkey::find_program_address(
+            [
+                bonding_curve_pubkey.as_ref(),
+                spl_token::ID.as_ref(),
+                any_mint_token_mint_pubkey.as_ref(),
+            ]
+            .as_slice(),
+            &SPL_ASSOCIATED_TOKEN_ACCOUNT_ID,
+        );
+        Ok(
+            PumpfunCurveAccounts {
+                global_pubkey,
+                fee_recipient_pubkey,
+                bonding_curve_pubkey,
+                associated_bonding_curve_pubkey,
+                event_authority_pubkey,
+            },
+        )
+    }
+    fn load_keypair_from_file<'a>(keypair_file_path: &'a str) -> Result<Keypair, Box<dyn Error + 'static>> {
+        let keypair_file_path_ = Path::new(keypair_file_path);
+        let keypair_file_data = if keypair_file_path_.try_exists()? {
+            std::fs::read_to_string(keypair_file_path_)?
+        } else {
+            return Err("The keypair.json file does not exist.".into());
+        };
+        Ok(Keypair::from_bytes(serde_json::from_str::<Vec<u8>>(keypair_file_data.as_str())?.as_slice())?)
+    }
+}
+struct RaydiumCpmmPoolAccounts {
+    authority_pubkey: Pubkey,
+    amm_config_pubkey: Pubkey,
+    w_sol_vault_pubkey: Pubkey,
+    any_mint_vault_pubkey: Pubkey,
+    any_mint_token_program_pubkey: Pubkey,
+    observation_state_pubkey: Pubkey,
+}
+struct RaydiumClmmPoolAccounts {
+    amm_config_pubkey: Pubkey,
+    w_sol_vault_pubkey: Pubkey,
+    any_mint_vault_pubkey: Pubkey,
+    observation_state_pubkey: Pubkey,
+    tick_array_pubkeys: Vec<Pubkey>,
+}
+struct MeteoraDlmmAccounts {
+    any_mint_token_program_pubkey: Pubkey,
+    bin_array_bitmap_extension_pubkey: Pubkey,
+    w_sol_reserve_pubkey: Pubkey,
+    any_mint_reserve_pubkey: Pubkey,
+    oracle_pubkey: Pubkey,
+    event_authority_pubkey: Pubkey,
+    bin_array_pubkeys: Vec<Pubkey>,
+}
+struct MeteoraDynamicAmmAccounts {
+    w_sol_vault_pubkey: Pubkey,
+    any_mint_vault_pubkey: Pubkey,
+    w_sol_token_vault_pubkey: Pubkey,
+    any_mint_token_vault_pubkey: Pubkey,
+    w_sol_vault_lp_mint_pubkey: Pubkey,
+    any_mint_vault_lp_mint_pubkey: Pubkey,
+    w_sol_vault_lp_pubkey: Pubkey,
+    any_mint_vault_lp_pubkey: Pubkey,
+    protocol_token_fee_pubkey: Pubkey,
+}
+struct PumpfunCurveAccounts {
+    global_pubkey: Pubkey,
+    fee_recipient_pubkey: Pubkey,
+    bonding_curve_pubkey: Pubkey,
+    associated_bonding_curve_pubkey: Pubkey,
+    event_authority_pubkey: Pubkey,
 }
@@ -5,6 +5,7 @@ use {
         Command,
     },
     command_processor::CommandProcessor,
+    simo_intermediary::state::Fees,
     solana_rpc_client::rpc_client::RpcClient,
     std::{
         error::Error,
@@ -24,14 +25,99 @@ impl Processor {
         const COMMAND_INITIALIZE: &'static str = "initialize";
         const COMMAND_DEPOSIT_FUNDS: &'static str = "deposit_funds";
         const COMMAND_WITHDRAW_FUNDS: &'static str = "withdraw_funds";
-        const COMMAND_CHANGE_MANAGER: &'static str = "change_manager";
-        const COMMAND_CHANGE_TRADER: &'static str = "change_trader";
+        const COMMAND_PROPOSE_MANAGER: &'static str = "propose_manager";
+        const COMMAND_PROPOSE_TRADER: &'static str = "propose_trader";
+        const COMMAND_BUY_ON_RAYDIUM: &'static str = "buy_on_raydium";
+        const COMMAND_SELL_ON_RAYDIUM: &'static str = "sell_on_raydium";
+        const COMMAND_BUY_ON_PUMPSWAP: &'static str = "buy_on_pumpswap";
+        const COMMAND_SELL_ON_PUMPSWAP: &'static str = "sell_on_pumpswap";
+        const COMMAND_INITIALIZE_CONFIG: &'static str = "initialize_config";
+        const COMMAND_UPDATE_CONFIG: &'static str = "update_config";
+        const COMMAND_SWEEP_FEES: &'static str = "sweep_fees";
+        const COMMAND_BUY_ON_RAYDIUM_EXACT_OUT: &'static str = "buy_on_raydium_exact_out";
+        const COMMAND_SELL_ON_RAYDIUM_EXACT_OUT: &'static str = "sell_on_raydium_exact_out";
+        const COMMAND_SET_RISK_LIMITS: &'static str = "set_risk_limits";
+        const COMMAND_SET_MAXIMUM_PRICE_DEVIATION: &'static str = "set_maximum_price_deviation";
+        const COMMAND_ADD_ALLOWED_MINT: &'static str = "add_allowed_mint";
+        const COMMAND_REMOVE_ALLOWED_MINT: &'static str = "remove_allowed_mint";
+        const COMMAND_CLOSE: &'static str = "close";
+        const COMMAND_ACCEPT_MANAGER: &'static str = "accept_manager";
+        const COMMAND_ACCEPT_TRADER: &'static str = "accept_trader";
+        const COMMAND_CANCEL_MANAGER_PROPOSAL: &'static str = "cancel_manager_proposal";
+        const COMMAND_CANCEL_TRADER_PROPOSAL: &'static str = "cancel_trader_proposal";
+        const COMMAND_ADD_TRADER_SEAT: &'static str = "add_trader_seat";
+        const COMMAND_REMOVE_TRADER_SEAT: &'static str = "remove_trader_seat";
+        const COMMAND_PAUSE: &'static str = "pause";
+        const COMMAND_UNPAUSE: &'static str = "unpause";
+        const COMMAND_MIGRATE_INTERMEDIARY: &'static str = "migrate_intermediary";
+        const COMMAND_LIQUIDATE_ALL: &'static str = "liquidate_all";
+        const COMMAND_WITHDRAW_TOKEN: &'static str = "withdraw_token";
+        const COMMAND_BUY_ON_RAYDIUM_CPMM: &'static str = "buy_on_raydium_cpmm";
+        const COMMAND_SELL_ON_RAYDIUM_CPMM: &'static str = "sell_on_raydium_cpmm";
+        const COMMAND_BUY_ON_RAYDIUM_CLMM: &'static str = "buy_on_raydium_clmm";
+        const COMMAND_SELL_ON_RAYDIUM_CLMM: &'static str = "sell_on_raydium_clmm";
+        const COMMAND_BUY_ON_METEORA_DLMM: &'static str = "buy_on_meteora_dlmm";
+        const COMMAND_SELL_ON_METEORA_DLMM: &'static str = "sell_on_meteora_dlmm";
+        const COMMAND_BUY_ON_METEORA_DYNAMIC_AMM: &'static str = "buy_on_meteora_dynamic_amm";
+        const COMMAND_SELL_ON_METEORA_DYNAMIC_AMM: &'static str = "sell_on_meteora_dynamic_amm";
+        const COMMAND_BUY_ON_PUMPFUN_CURVE: &'static str = "buy_on_pumpfun_curve";
+        const COMMAND_SELL_ON_PUMPFUN_CURVE: &'static str = "sell_on_pumpfun_curve";
+        const COMMAND_SWAP: &'static str = "swap";
+        const COMMAND_ROUTED_SWAP: &'static str = "routed_swap";
+        const ADMINISTRATOR: &'static str = "administrator";
+        const AUTHORITY: &'static str = "authority";
+        const FEE_RECIPIENT: &'static str = "fee_recipient";
+        const FEE_INITIALIZE: &'static str = "fee_initialize";
+        const FEE_DEPOSIT_FUNDS: &'static str = "fee_deposit_funds";
+        const FEE_WITHDRAW_FUNDS: &'static str = "fee_withdraw_funds";
+        const FEE_BUY_ON_RAYDIUM: &'static str = "fee_buy_on_raydium";
+        const FEE_SELL_ON_RAYDIUM: &'static str = "fee_sell_on_raydium";
+        const FEE_BUY_ON_PUMPSWAP: &'static str = "fee_buy_on_pumpswap";
+        const FEE_SELL_ON_PUMPSWAP: &'static str = "fee_sell_on_pumpswap";
+        const FEE_CHANGE_MANAGER: &'static str = "fee_change_manager";
+        const FEE_CHANGE_TRADER: &'static str = "fee_change_trader";
+        const FEE_BUY_ON_RAYDIUM_CPMM: &'static str = "fee_buy_on_raydium_cpmm";
+        const FEE_SELL_ON_RAYDIUM_CPMM: &'static str = "fee_sell_on_raydium_cpmm";
+        const FEE_BUY_ON_RAYDIUM_CLMM: &'static str = "fee_buy_on_raydium_clmm";
+        const FEE_SELL_ON_RAYDIUM_CLMM: &'static str = "fee_sell_on_raydium_clmm";
+        const FEE_BUY_ON_METEORA_DLMM: &'static str = "fee_buy_on_meteora_dlmm";
+        const FEE_SELL_ON_METEORA_DLMM: &'static str = "fee_sell_on_meteora_dlmm";
+        const FEE_BUY_ON_METEORA_DYNAMIC_AMM: &'static str = "fee_buy_on_meteora_dynamic_amm";
+        const FEE_SELL_ON_METEORA_DYNAMIC_AMM: &'static str = "fee_sell_on_meteora_dynamic_amm";
+        const FEE_BUY_ON_PUMPFUN_CURVE: &'static str = "fee_buy_on_pumpfun_curve";
+        const FEE_SELL_ON_PUMPFUN_CURVE: &'static str = "fee_sell_on_pumpfun_curve";
         const INTERMEDIARY_INVESTOR: &'static str = "intermediary_investor";
         const INTERMEDIARY_MANAGER: &'static str = "intermediary_manager";
         const INTERMEDIARY_TRADER: &'static str = "intermediary_trader";
         const LAMPORTS_TO_TREASURY: &'static str = "lamports_to_treasury";
         const LAMPORTS_FROM_TREASURY: &'static str = "lamports_from_treasury";
         const INTERMEDIARY: &'static str = "intermediary";
+        const MINT: &'static str = "mint";
+        const POOL: &'static str = "pool";
+        const INPUT_TOKEN_AMOUNT: &'static str = "input_token_amount";
+        const MINIMUM_OUTPUT_TOKEN_AMOUNT: &'static str = "minimum_output_token_amount";
+        const SLIPPAGE_BASIS_POINTS: &'static str = "slippage_bps";
+        const BASE_AMOUNT_OUT: &'static str = "base_amount_out";
+        const MAXIMUM_QUOTE_AMOUNT_IN: &'static str = "maximum_quote_amount_in";
+        const MAXIMUM_AMOUNT_IN: &'static str = "maximum_amount_in";
+        const AMOUNT_OUT: &'static str = "amount_out";
+        const MAXIMUM_LAMPORTS_PER_TRADE: &'static str = "maximum_lamports_per_trade";
+        const MAXIMUM_TREASURY_SHARE_PER_TRADE_BASIS_POINTS: &'static str = "maximum_treasury_share_per_trade_basis_points";
+        const MAXIMUM_PRICE_DEVIATION_BASIS_POINTS: &'static str = "maximum_price_deviation_basis_points";
+        const MAXIMUM_TRADES_PER_SLOT_WINDOW: &'static str = "maximum_trades_per_slot_window";
+        const SLOT_WINDOW_LENGTH: &'static str = "slot_window_length";
+        const ALLOCATED_LAMPORTS: &'static str = "allocated_lamports";
+        const MINTS: &'static str = "mints";
+        const POOLS: &'static str = "pools";
+        const MINIMUM_OUTPUT_TOKEN_AMOUNTS: &'static str = "minimum_output_token_amounts";
+        const TOKEN_AMOUNT: &'static str = "token_amount";
+        const VENUE: &'static str = "venue";
+        const SIDE: &'static str = "side";
+        const AMOUNT_IN: &'static str = "amount_in";
+        const MIN_OUT: &'static str = "min_out";
+        const VENUES: &'static str = "venues";
+        const INTERMEDIATE_MINTS: &'static str = "intermediate_mints";
+        const LOOKUP_TABLE: &'static str = "lookup_table";
         const SOLANA_RPC_URL: &'static str = "solana_rpc_url";
         let arg_matches = clap::command!()
             .arg_required_else_help(true)
@@ -56,17 +142,331 @@ impl Processor {
                     .arg(Arg::new(LAMPORTS_FROM_TREASURY).required(true).long(LAMPORTS_FROM_TREASURY).help("Lamports from treasury.")),
             )
             .subcommand(
-                Command::new(COMMAND_CHANGE_MANAGER)
+                Command::new(COMMAND_PROPOSE_MANAGER)
                     .arg(Arg::new(INTERMEDIARY_INVESTOR).required(true).long(INTERMEDIARY_INVESTOR).help("Fee payer keypair.json file path."))
                     .arg(Arg::new(INTERMEDIARY).required(true).long(INTERMEDIARY).help("Intermediary pubkey."))
                     .arg(Arg::new(INTERMEDIARY_MANAGER).required(true).long(INTERMEDIARY_MANAGER).help("Intermediary manager pubkey.")),
             )
             .subcommand(
-                Command::new(COMMAND_CHANGE_TRADER)
+                Command::new(COMMAND_PROPOSE_TRADER)
                     .arg(Arg::new(INTERMEDIARY_MANAGER).required(true).long(INTERMEDIARY_MANAGER).help("Fee payer keypair.json file path."))
                     .arg(Arg::new(INTERMEDIARY).required(true).long(INTERMEDIARY).help("Intermediary pubkey."))
                     .arg(Arg::new(INTERMEDIARY_TRADER).required(true).long(INTERMEDIARY_TRADER).help("Intermediary trader pubkey.")),
             )
+            .subcommand(
+                Command::new(COMMAND_BUY_ON_RAYDIUM)
+                    .arg(Arg::new(INTERMEDIARY_TRADER).required(true).long(INTERMEDIARY_TRADER).help("Fee payer keypair.json file path."))
+                    .arg(Arg::new(INTERMEDIARY).required(true).long(INTERMEDIARY).help("Intermediary pubkey."))
+                    .arg(Arg::new(MINT).required(true).long(MINT).help("Token mint pubkey."))
+                    .arg(Arg::new(POOL).required(true).long(POOL).help("Raydium AMM v4 pool pubkey."))
+                    .arg(Arg::new(INPUT_TOKEN_AMOUNT).required(true).long(INPUT_TOKEN_AMOUNT).help("Lamports from treasury to spend."))
+                    .arg(Arg::new(MINIMUM_OUTPUT_TOKEN_AMOUNT).required_unless_present(SLIPPAGE_BASIS_POINTS).long(MINIMUM_OUTPUT_TOKEN_AMOUNT).help("Minimum token amount to receive."))
+                    .arg(Arg::new(SLIPPAGE_BASIS_POINTS).required(false).long(SLIPPAGE_BASIS_POINTS).conflicts_with(MINIMUM_OUTPUT_TOKEN_AMOUNT).help("Slippage basis points to derive the minimum output from a quote.")),
+            )
+            .subcommand(
+                Command::new(COMMAND_SELL_ON_RAYDIUM)
+                    .arg(Arg::new(INTERMEDIARY_TRADER).required(true).long(INTERMEDIARY_TRADER).help("Fee payer keypair.json file path."))
+                    .arg(Arg::new(INTERMEDIARY).required(true).long(INTERMEDIARY).help("Intermediary pubkey."))
+                    .arg(Arg::new(MINT).required(true).long(MINT).help("Token mint pubkey."))
+                    .arg(Arg::new(POOL).required(true).long(POOL).help("Raydium AMM v4 pool pubkey."))
+                    .arg(Arg::new(INPUT_TOKEN_AMOUNT).required(true).long(INPUT_TOKEN_AMOUNT).help("Token amount to sell."))
+                    .arg(Arg::new(MINIMUM_OUTPUT_TOKEN_AMOUNT).required(false).long(MINIMUM_OUTPUT_TOKEN_AMOUNT).help("Minimum lamports to treasury."))
+                    .arg(Arg::new(SLIPPAGE_BASIS_POINTS).required(false).long(SLIPPAGE_BASIS_POINTS).conflicts_with(MINIMUM_OUTPUT_TOKEN_AMOUNT).help("Slippage basis points to derive the minimum output from a quote.")),
+            )
+            .subcommand(
+                Command::new(COMMAND_BUY_ON_PUMPSWAP)
+                    .arg(Arg::new(INTERMEDIARY_TRADER).required(true).long(INTERMEDIARY_TRADER).help("Fee payer keypair.json file path."))
+                    .arg(Arg::new(INTERMEDIARY).required(true).long(INTERMEDIARY).help("Intermediary pubkey."))
+                    .arg(Arg::new(MINT).required(true).long(MINT).help("Token mint pubkey."))
+                    .arg(Arg::new(POOL).required(false).long(POOL).help("PumpSwap pool pubkey, the canonical pool of the mint by default."))
+                    .arg(Arg::new(BASE_AMOUNT_OUT).required(true).long(BASE_AMOUNT_OUT).help("Token amount to receive."))
+                    .arg(Arg::new(MAXIMUM_QUOTE_AMOUNT_IN).required(true).long(MAXIMUM_QUOTE_AMOUNT_IN).help("Maximum lamports from treasury to spend.")),
+            )
+            .subcommand(
+                Command::new(COMMAND_SELL_ON_PUMPSWAP)
+                    .arg(Arg::new(INTERMEDIARY_TRADER).required(true).long(INTERMEDIARY_TRADER).help("Fee payer keypair.json file path."))
+                    .arg(Arg::new(INTERMEDIARY).required(true).long(INTERMEDIARY).help("Intermediary pubkey."))
+                    .arg(Arg::new(MINT).required(true).long(MINT).help("Token mint pubkey."))
+                    .arg(Arg::new(POOL).required(false).long(POOL).help("PumpSwap pool pubkey, the canonical pool of the mint by default."))
+                    .arg(Arg::new(INPUT_TOKEN_AMOUNT).required(true).long(INPUT_TOKEN_AMOUNT).help("Token amount to sell."))
+                    .arg(Arg::new(MINIMUM_OUTPUT_TOKEN_AMOUNT).required(false).long(MINIMUM_OUTPUT_TOKEN_AMOUNT).help("Minimum lamports to treasury."))
+                    .arg(Arg::new(SLIPPAGE_BASIS_POINTS).required(false).long(SLIPPAGE_BASIS_POINTS).conflicts_with(MINIMUM_OUTPUT_TOKEN_AMOUNT).help("Slippage basis points to derive the minimum output from a quote.")),
+            )
+            .subcommand(
+                Command::new(COMMAND_INITIALIZE_CONFIG)
+                    .arg(Arg::new(ADMINISTRATOR).required(true).long(ADMINISTRATOR).help("Program upgrade authority keypair.json file path."))
+                    .arg(Arg::new(FEE_RECIPIENT).required(true).long(FEE_RECIPIENT).help("Fee recipient pubkey."))
+                    .arg(Arg::new(FEE_INITIALIZE).required(true).long(FEE_INITIALIZE).help("Lamports charged on initialize."))
+                    .arg(Arg::new(FEE_DEPOSIT_FUNDS).required(true).long(FEE_DEPOSIT_FUNDS).help("Lamports charged on deposit_funds."))
+                    .arg(Arg::new(FEE_WITHDRAW_FUNDS).required(true).long(FEE_WITHDRAW_FUNDS).help("Lamports charged on withdraw_funds."))
+                    .arg(Arg::new(FEE_BUY_ON_RAYDIUM).required(true).long(FEE_BUY_ON_RAYDIUM).help("Lamports charged on buy_on_raydium."))
+                    .arg(Arg::new(FEE_SELL_ON_RAYDIUM).required(true).long(FEE_SELL_ON_RAYDIUM).help("Lamports charged on sell_on_raydium."))
+                    .arg(Arg::new(FEE_BUY_ON_PUMPSWAP).required(true).long(FEE_BUY_ON_PUMPSWAP).help("Lamports charged on buy_on_pumpswap."))
+                    .arg(Arg::new(FEE_SELL_ON_PUMPSWAP).required(true).long(FEE_SELL_ON_PUMPSWAP).help("Lamports charged on sell_on_pumpswap."))
+                    .arg(Arg::new(FEE_CHANGE_MANAGER).required(true).long(FEE_CHANGE_MANAGER).help("Lamports charged on change_manager."))
+                    .arg(Arg::new(FEE_CHANGE_TRADER).required(true).long(FEE_CHANGE_TRADER).help("Lamports charged on change_trader."))
+                    .arg(Arg::new(FEE_BUY_ON_RAYDIUM_CPMM).required(true).long(FEE_BUY_ON_RAYDIUM_CPMM).help("Lamports charged on buy_on_raydium_cpmm."))
+                    .arg(Arg::new(FEE_SELL_ON_RAYDIUM_CPMM).required(true).long(FEE_SELL_ON_RAYDIUM_CPMM).help("Lamports charged on sell_on_raydium_cpmm."))
+                    .arg(Arg::new(FEE_BUY_ON_RAYDIUM_CLMM).required(true).long(FEE_BUY_ON_RAYDIUM_CLMM).help("Lamports charged on buy_on_raydium_clmm."))
+                    .arg(Arg::new(FEE_SELL_ON_RAYDIUM_CLMM).required(true).long(FEE_SELL_ON_RAYDIUM_CLMM).help("Lamports charged on sell_on_raydium_clmm."))
+                    .arg(Arg::new(FEE_BUY_ON_METEORA_DLMM).required(true).long(FEE_BUY_ON_METEORA_DLMM).help("Lamports charged on buy_on_meteora_dlmm."))
+                    .arg(Arg::new(FEE_SELL_ON_METEORA_DLMM).required(true).long(FEE_SELL_ON_METEORA_DLMM).help("Lamports charged on sell_on_meteora_dlmm."))
+                    .arg(Arg::new(FEE_BUY_ON_METEORA_DYNAMIC_AMM).required(true).long(FEE_BUY_ON_METEORA_DYNAMIC_AMM).help("Lamports charged on buy_on_meteora_dynamic_amm."))
+                    .arg(Arg::new(FEE_SELL_ON_METEORA_DYNAMIC_AMM).required(true).long(FEE_SELL_ON_METEORA_DYNAMIC_AMM).help("Lamports charged on sell_on_meteora_dynamic_amm."))
+                    .arg(Arg::new(FEE_BUY_ON_PUMPFUN_CURVE).required(true).long(FEE_BUY_ON_PUMPFUN_CURVE).help("Lamports charged on buy_on_pumpfun_curve."))
+                    .arg(Arg::new(FEE_SELL_ON_PUMPFUN_CURVE).required(true).long(FEE_SELL_ON_PUMPFUN_CURVE).help("Lamports charged on sell_on_pumpfun_curve.")),
+            )
+            .subcommand(
+                Command::new(COMMAND_UPDATE_CONFIG)
+                    .arg(Arg::new(ADMINISTRATOR).required(true).long(ADMINISTRATOR).help("Fee payer keypair.json file path."))
+                    .arg(Arg::new(FEE_RECIPIENT).required(true).long(FEE_RECIPIENT).help("Fee recipient pubkey."))
+                    .arg(Arg::new(FEE_INITIALIZE).required(true).long(FEE_INITIALIZE).help("Lamports charged on initialize."))
+                    .arg(Arg::new(FEE_DEPOSIT_FUNDS).required(true).long(FEE_DEPOSIT_FUNDS).help("Lamports charged on deposit_funds."))
+                    .arg(Arg::new(FEE_WITHDRAW_FUNDS).required(true).long(FEE_WITHDRAW_FUNDS).help("Lamports charged on withdraw_funds."))
+                    .arg(Arg::new(FEE_BUY_ON_RAYDIUM).required(true).long(FEE_BUY_ON_RAYDIUM).help("Lamports charged on buy_on_raydium."))
+                    .arg(Arg::new(FEE_SELL_ON_RAYDIUM).required(true).long(FEE_SELL_ON_RAYDIUM).help("Lamports charged on sell_on_raydium."))
+                    .arg(Arg::new(FEE_BUY_ON_PUMPSWAP).required(true).long(FEE_BUY_ON_PUMPSWAP).help("Lamports charged on buy_on_pumpswap."))
+                    .arg(Arg::new(FEE_SELL_ON_PUMPSWAP).required(true).long(FEE_SELL_ON_PUMPSWAP).help("Lamports charged on sell_on_pumpswap."))
+                    .arg(Arg::new(FEE_CHANGE_MANAGER).required(true).long(FEE_CHANGE_MANAGER).help("Lamports charged on change_manager."))
+                    .arg(Arg::new(FEE_CHANGE_TRADER).required(true).long(FEE_CHANGE_TRADER).help("Lamports charged on change_trader."))
+                    .arg(Arg::new(FEE_BUY_ON_RAYDIUM_CPMM).required(true).long(FEE_BUY_ON_RAYDIUM_CPMM).help("Lamports charged on buy_on_raydium_cpmm."))
+                    .arg(Arg::new(FEE_SELL_ON_RAYDIUM_CPMM).required(true).long(FEE_SELL_ON_RAYDIUM_CPMM).help("Lamports charged on sell_on_raydium_cpmm."))
+                    .arg(Arg::new(FEE_BUY_ON_RAYDIUM_CLMM).required(true).long(FEE_BUY_ON_RAYDIUM_CLMM).help("Lamports charged on buy_on_raydium_clmm."))
+                    .arg(Arg::new(FEE_SELL_ON_RAYDIUM_CLMM).required(true).long(FEE_SELL_ON_RAYDIUM_CLMM).help("Lamports charged on sell_on_raydium_clmm."))
+                    .arg(Arg::new(FEE_BUY_ON_METEORA_DLMM).required(true).long(FEE_BUY_ON_METEORA_DLMM).help("Lamports charged on buy_on_meteora_dlmm."))
+                    .arg(Arg::new(FEE_SELL_ON_METEORA_DLMM).required(true).long(FEE_SELL_ON_METEORA_DLMM).help("Lamports charged on sell_on_meteora_dlmm."))
+                    .arg(Arg::new(FEE_BUY_ON_METEORA_DYNAMIC_AMM).required(true).long(FEE_BUY_ON_METEORA_DYNAMIC_AMM).help("Lamports charged on buy_on_meteora_dynamic_amm."))
+                    .arg(Arg::new(FEE_SELL_ON_METEORA_DYNAMIC_AMM).required(true).long(FEE_SELL_ON_METEORA_DYNAMIC_AMM).help("Lamports charged on sell_on_meteora_dynamic_amm."))
+                    .arg(Arg::new(FEE_BUY_ON_PUMPFUN_CURVE).required(true).long(FEE_BUY_ON_PUMPFUN_CURVE).help("Lamports charged on buy_on_pumpfun_curve."))
+                    .arg(Arg::new(FEE_SELL_ON_PUMPFUN_CURVE).required(true).long(FEE_SELL_ON_PUMPFUN_CURVE).help("Lamports charged on sell_on_pumpfun_curve.")),
+            )
+            .subcommand(Command::new(COMMAND_SWEEP_FEES).arg(Arg::new(ADMINISTRATOR).required(true).long(ADMINISTRATOR).help("Fee payer keypair.json file path.")))
+            .subcommand(
+                Command::new(COMMAND_BUY_ON_RAYDIUM_EXACT_OUT)
+                    .arg(Arg::new(INTERMEDIARY_TRADER).required(true).long(INTERMEDIARY_TRADER).help("Fee payer keypair.json file path."))
+                    .arg(Arg::new(INTERMEDIARY).required(true).long(INTERMEDIARY).help("Intermediary pubkey."))
+                    .arg(Arg::new(MINT).required(true).long(MINT).help("Token mint pubkey."))
+                    .arg(Arg::new(POOL).required(true).long(POOL).help("Raydium AMM v4 pool pubkey."))
+                    .arg(Arg::new(MAXIMUM_AMOUNT_IN).required(true).long(MAXIMUM_AMOUNT_IN).help("Maximum lamports from treasury to spend."))
+                    .arg(Arg::new(AMOUNT_OUT).required(true).long(AMOUNT_OUT).help("Token amount to receive.")),
+            )
+            .subcommand(
+                Command::new(COMMAND_SELL_ON_RAYDIUM_EXACT_OUT)
+                    .arg(Arg::new(INTERMEDIARY_TRADER).required(true).long(INTERMEDIARY_TRADER).help("Fee payer keypair.json file path."))
+                    .arg(Arg::new(INTERMEDIARY).required(true).long(INTERMEDIARY).help("Intermediary pubkey."))
+                    .arg(Arg::new(MINT).required(true).long(MINT).help("Token mint pubkey."))
+                    .arg(Arg::new(POOL).required(true).long(POOL).help("Raydium AMM v4 pool pubkey."))
+                    .arg(Arg::new(MAXIMUM_AMOUNT_IN).required(true).long(MAXIMUM_AMOUNT_IN).help("Maximum token amount to sell."))
+                    .arg(Arg::new(AMOUNT_OUT).required(true).long(AMOUNT_OUT).help("Lamports to treasury.")),
+            )
+            .subcommand(
+                Command::new(COMMAND_SET_RISK_LIMITS)
+                    .arg(Arg::new(INTERMEDIARY_MANAGER).required(true).long(INTERMEDIARY_MANAGER).help("Fee payer keypair.json file path."))
+                    .arg(Arg::new(INTERMEDIARY).required(true).long(INTERMEDIARY).help("Intermediary pubkey."))
+                    .arg(Arg::new(MAXIMUM_LAMPORTS_PER_TRADE).required(true).long(MAXIMUM_LAMPORTS_PER_TRADE).help("Maximum lamports from treasury per trade."))
+                    .arg(Arg::new(MAXIMUM_TREASURY_SHARE_PER_TRADE_BASIS_POINTS).required(true).long(MAXIMUM_TREASURY_SHARE_PER_TRADE_BASIS_POINTS).help("Maximum treasury share per trade in basis points."))
+                    .arg(Arg::new(MAXIMUM_TRADES_PER_SLOT_WINDOW).required(true).long(MAXIMUM_TRADES_PER_SLOT_WINDOW).help("Maximum trades per slot window."))
+                    .arg(Arg::new(SLOT_WINDOW_LENGTH).required(true).long(SLOT_WINDOW_LENGTH).help("Slot window length in slots.")),
+            )
+            .subcommand(
+                Command::new(COMMAND_SET_MAXIMUM_PRICE_DEVIATION)
+                    .arg(Arg::new(INTERMEDIARY_MANAGER).required(true).long(INTERMEDIARY_MANAGER).help("Fee payer keypair.json file path."))
+                    .arg(Arg::new(INTERMEDIARY).required(true).long(INTERMEDIARY).help("Intermediary pubkey."))
+                    .arg(Arg::new(MAXIMUM_PRICE_DEVIATION_BASIS_POINTS).required(true).long(MAXIMUM_PRICE_DEVIATION_BASIS_POINTS).help("Maximum shortfall of the realized output against the pool reserves in basis points, 0 disables the check.")),
+            )
+            .subcommand(
+                Command::new(COMMAND_ADD_ALLOWED_MINT)
+                    .arg(Arg::new(INTERMEDIARY_MANAGER).required(true).long(INTERMEDIARY_MANAGER).help("Fee payer keypair.json file path."))
+                    .arg(Arg::new(INTERMEDIARY).required(true).long(INTERMEDIARY).help("Intermediary pubkey."))
+                    .arg(Arg::new(MINT).required(true).long(MINT).help("Token mint pubkey.")),
+            )
+            .subcommand(
+                Command::new(COMMAND_REMOVE_ALLOWED_MINT)
+                    .arg(Arg::new(INTERMEDIARY_MANAGER).required(true).long(INTERMEDIARY_MANAGER).help("Fee payer keypair.json file path."))
+                    .arg(Arg::new(INTERMEDIARY).required(true).long(INTERMEDIARY).help("Intermediary pubkey."))
+                    .arg(Arg::new(MINT).required(true).long(MINT).help("Token mint pubkey.")),
+            )
+            .subcommand(
+                Command::new(COMMAND_CLOSE)
+                    .arg(Arg::new(INTERMEDIARY_INVESTOR).required(true).long(INTERMEDIARY_INVESTOR).help("Fee payer keypair.json file path."))
+                    .arg(Arg::new(INTERMEDIARY).required(true).long(INTERMEDIARY).help("Intermediary pubkey.")),
+            )
+            .subcommand(
+                Command::new(COMMAND_ACCEPT_MANAGER)
+                    .arg(Arg::new(INTERMEDIARY_MANAGER).required(true).long(INTERMEDIARY_MANAGER).help("Fee payer keypair.json file path."))
+                    .arg(Arg::new(INTERMEDIARY).required(true).long(INTERMEDIARY).help("Intermediary pubkey.")),
+            )
+            .subcommand(
+                Command::new(COMMAND_ACCEPT_TRADER)
+                    .arg(Arg::new(INTERMEDIARY_TRADER).required(true).long(INTERMEDIARY_TRADER).help("Fee payer keypair.json file path."))
+                    .arg(Arg::new(INTERMEDIARY).required(true).long(INTERMEDIARY).help("Intermediary pubkey.")),
+            )
+            .subcommand(
+                Command::new(COMMAND_CANCEL_MANAGER_PROPOSAL)
+                    .arg(Arg::new(AUTHORITY).required(true).long(AUTHORITY).help("Fee payer keypair.json file path. Intermediary investor or manager."))
+                    .arg(Arg::new(INTERMEDIARY).required(true).long(INTERMEDIARY).help("Intermediary pubkey.")),
+            )
+            .subcommand(
+                Command::new(COMMAND_CANCEL_TRADER_PROPOSAL)
+                    .arg(Arg::new(AUTHORITY).required(true).long(AUTHORITY).help("Fee payer keypair.json file path. Intermediary manager or trader."))
+                    .arg(Arg::new(INTERMEDIARY).required(true).long(INTERMEDIARY).help("Intermediary pubkey.")),
+            )
+            .subcommand(
+                Command::new(COMMAND_ADD_TRADER_SEAT)
+                    .arg(Arg::new(INTERMEDIARY_MANAGER).required(true).long(INTERMEDIARY_MANAGER).help("Fee payer keypair.json file path."))
+                    .arg(Arg::new(INTERMEDIARY).required(true).long(INTERMEDIARY).help("Intermediary pubkey."))
+                    .arg(Arg::new(INTERMEDIARY_TRADER).required(true).long(INTERMEDIARY_TRADER).help("Intermediary trader pubkey."))
+                    .arg(Arg::new(ALLOCATED_LAMPORTS).required(true).long(ALLOCATED_LAMPORTS).help("Lamports from treasury the trader may spend.")),
+            )
+            .subcommand(
+                Command::new(COMMAND_REMOVE_TRADER_SEAT)
+                    .arg(Arg::new(INTERMEDIARY_MANAGER).required(true).long(INTERMEDIARY_MANAGER).help("Fee payer keypair.json file path."))
+                    .arg(Arg::new(INTERMEDIARY).required(true).long(INTERMEDIARY).help("Intermediary pubkey."))
+                    .arg(Arg::new(INTERMEDIARY_TRADER).required(true).long(INTERMEDIARY_TRADER).help("Intermediary trader pubkey.")),
+            )
+            .subcommand(
+                Command::new(COMMAND_PAUSE)
+                    .arg(Arg::new(AUTHORITY).required(true).long(AUTHORITY).help("Fee payer keypair.json file path. Intermediary investor or manager."))
+                    .arg(Arg::new(INTERMEDIARY).required(true).long(INTERMEDIARY).help("Intermediary pubkey.")),
+            )
+            .subcommand(
+                Command::new(COMMAND_UNPAUSE)
+                    .arg(Arg::new(INTERMEDIARY_INVESTOR).required(true).long(INTERMEDIARY_INVESTOR).help("Fee payer keypair.json file path."))
+                    .arg(Arg::new(INTERMEDIARY).required(true).long(INTERMEDIARY).help("Intermediary pubkey.")),
+            )
+            .subcommand(
+                Command::new(COMMAND_MIGRATE_INTERMEDIARY)
+                    .arg(Arg::new(AUTHORITY).required(true).long(AUTHORITY).help("Fee payer keypair.json file path. Intermediary investor or manager."))
+                    .arg(Arg::new(INTERMEDIARY).required(true).long(INTERMEDIARY).help("Intermediary pubkey.")),
+            )
+            .subcommand(
+                Command::new(COMMAND_LIQUIDATE_ALL)
+                    .arg(Arg::new(AUTHORITY).required(true).long(AUTHORITY).help("Fee payer keypair.json file path. Intermediary investor or manager."))
+                    .arg(Arg::new(INTERMEDIARY).required(true).long(INTERMEDIARY).help("Intermediary pubkey."))
+                    .arg(Arg::new(MINTS).required(true).long(MINTS).help("Comma-separated token mint pubkeys."))
+                    .arg(Arg::new(VENUES).required(true).long(VENUES).help("Comma-separated venues, raydium or pumpswap, one per mint."))
+                    .arg(Arg::new(POOLS).required(true).long(POOLS).help("Comma-separated pool pubkeys, one per mint."))
+                    .arg(Arg::new(MINIMUM_OUTPUT_TOKEN_AMOUNTS).required(true).long(MINIMUM_OUTPUT_TOKEN_AMOUNTS).help("Comma-separated minimum lamports to receive, one per mint.")),
+            )
+            .subcommand(
+                Command::new(COMMAND_WITHDRAW_TOKEN)
+                    .arg(Arg::new(INTERMEDIARY_INVESTOR).required(true).long(INTERMEDIARY_INVESTOR).help("Fee payer keypair.json file path."))
+                    .arg(Arg::new(INTERMEDIARY).required(true).long(INTERMEDIARY).help("Intermediary pubkey."))
+                    .arg(Arg::new(MINT).required(true).long(MINT).help("Token mint pubkey."))
+                    .arg(Arg::new(TOKEN_AMOUNT).required(true).long(TOKEN_AMOUNT).help("Token amount to withdraw to the investor associated token account.")),
+            )
+            .subcommand(
+                Command::new(COMMAND_BUY_ON_RAYDIUM_CPMM)
+                    .arg(Arg::new(INTERMEDIARY_TRADER).required(true).long(INTERMEDIARY_TRADER).help("Fee payer keypair.json file path."))
+                    .arg(Arg::new(INTERMEDIARY).required(true).long(INTERMEDIARY).help("Intermediary pubkey."))
+                    .arg(Arg::new(MINT).required(true).long(MINT).help("Token mint pubkey."))
+                    .arg(Arg::new(POOL).required(true).long(POOL).help("Raydium CPMM pool pubkey."))
+                    .arg(Arg::new(INPUT_TOKEN_AMOUNT).required(true).long(INPUT_TOKEN_AMOUNT).help("Lamports from treasury to spend."))
+                    .arg(Arg::new(MINIMUM_OUTPUT_TOKEN_AMOUNT).required(true).long(MINIMUM_OUTPUT_TOKEN_AMOUNT).help("Minimum token amount to receive.")),
+            )
+            .subcommand(
+                Command::new(COMMAND_SELL_ON_RAYDIUM_CPMM)
+                    .arg(Arg::new(INTERMEDIARY_TRADER).required(true).long(INTERMEDIARY_TRADER).help("Fee payer keypair.json file path."))
+                    .arg(Arg::new(INTERMEDIARY).required(true).long(INTERMEDIARY).help("Intermediary pubkey."))
+                    .arg(Arg::new(MINT).required(true).long(MINT).help("Token mint pubkey."))
+                    .arg(Arg::new(POOL).required(true).long(POOL).help("Raydium CPMM pool pubkey."))
+                    .arg(Arg::new(INPUT_TOKEN_AMOUNT).required(true).long(INPUT_TOKEN_AMOUNT).help("Token amount to sell."))
+                    .arg(Arg::new(MINIMUM_OUTPUT_TOKEN_AMOUNT).required(false).long(MINIMUM_OUTPUT_TOKEN_AMOUNT).help("Minimum lamports to treasury.")),
+            )
+            .subcommand(
+                Command::new(COMMAND_BUY_ON_RAYDIUM_CLMM)
+                    .arg(Arg::new(INTERMEDIARY_TRADER).required(true).long(INTERMEDIARY_TRADER).help("Fee payer keypair.json file path."))
+                    .arg(Arg::new(INTERMEDIARY).required(true).long(INTERMEDIARY).help("Intermediary pubkey."))
+                    .arg(Arg::new(MINT).required(true).long(MINT).help("Token mint pubkey."))
+                    .arg(Arg::new(POOL).required(true).long(POOL).help("Raydium CLMM pool pubkey."))
+                    .arg(Arg::new(INPUT_TOKEN_AMOUNT).required(true).long(INPUT_TOKEN_AMOUNT).help("Lamports from treasury to spend."))
+                    .arg(Arg::new(MINIMUM_OUTPUT_TOKEN_AMOUNT).required(true).long(MINIMUM_OUTPUT_TOKEN_AMOUNT).help("Minimum token amount to receive.")),
+            )
+            .subcommand(
+                Command::new(COMMAND_SELL_ON_RAYDIUM_CLMM)
+                    .arg(Arg::new(INTERMEDIARY_TRADER).required(true).long(INTERMEDIARY_TRADER).help("Fee payer keypair.json file path."))
+                    .arg(Arg::new(INTERMEDIARY).required(true).long(INTERMEDIARY).help("Intermediary pubkey."))
+                    .arg(Arg::new(MINT).required(true).long(MINT).help("Token mint pubkey."))
+                    .arg(Arg::new(POOL).required(true).long(POOL).help("Raydium CLMM pool pubkey."))
+                    .arg(Arg::new(INPUT_TOKEN_AMOUNT).required(true).long(INPUT_TOKEN_AMOUNT).help("Token amount to sell."))
+                    .arg(Arg::new(MINIMUM_OUTPUT_TOKEN_AMOUNT).required(false).long(MINIMUM_OUTPUT_TOKEN_AMOUNT).help("Minimum lamports to treasury.")),
+            )
+            .subcommand(
+                Command::new(COMMAND_BUY_ON_METEORA_DLMM)
+                    .arg(Arg::new(INTERMEDIARY_TRADER).required(true).long(INTERMEDIARY_TRADER).help("Fee payer keypair.json file path."))
+                    .arg(Arg::new(INTERMEDIARY).required(true).long(INTERMEDIARY).help("Intermediary pubkey."))
+                    .arg(Arg::new(MINT).required(true).long(MINT).help("Token mint pubkey."))
+                    .arg(Arg::new(POOL).required(true).long(POOL).help("Meteora DLMM pool pubkey."))
+                    .arg(Arg::new(INPUT_TOKEN_AMOUNT).required(true).long(INPUT_TOKEN_AMOUNT).help("Lamports from treasury to spend."))
+                    .arg(Arg::new(MINIMUM_OUTPUT_TOKEN_AMOUNT).required(true).long(MINIMUM_OUTPUT_TOKEN_AMOUNT).help("Minimum token amount to receive.")),
+            )
+            .subcommand(
+                Command::new(COMMAND_SELL_ON_METEORA_DLMM)
+                    .arg(Arg::new(INTERMEDIARY_TRADER).required(true).long(INTERMEDIARY_TRADER).help("Fee payer keypair.json file path."))
+                    .arg(Arg::new(INTERMEDIARY).required(true).long(INTERMEDIARY).help("Intermediary pubkey."))
+                    .arg(Arg::new(MINT).required(true).long(MINT).help("Token mint pubkey."))
+                    .arg(Arg::new(POOL).required(true).long(POOL).help("Meteora DLMM pool pubkey."))
+                    .arg(Arg::new(INPUT_TOKEN_AMOUNT).required(true).long(INPUT_TOKEN_AMOUNT).help("Token amount to sell."))
+                    .arg(Arg::new(MINIMUM_OUTPUT_TOKEN_AMOUNT).required(false).long(MINIMUM_OUTPUT_TOKEN_AMOUNT).help("Minimum lamports to treasury.")),
+            )
+            .subcommand(
+                Command::new(COMMAND_BUY_ON_METEORA_DYNAMIC_AMM)
+                    .arg(Arg::new(INTERMEDIARY_TRADER).required(true).long(INTERMEDIARY_TRADER).help("Fee payer keypair.json file path."))
+                    .arg(Arg::new(INTERMEDIARY).required(true).long(INTERMEDIARY).help("Intermediary pubkey."))
+                    .arg(Arg::new(MINT).required(true).long(MINT).help("Token mint pubkey."))
+                    .arg(Arg::new(POOL).required(true).long(POOL).help("Meteora dynamic AMM pool pubkey."))
+                    .arg(Arg::new(INPUT_TOKEN_AMOUNT).required(true).long(INPUT_TOKEN_AMOUNT).help("Lamports from treasury to spend."))
+                    .arg(Arg::new(MINIMUM_OUTPUT_TOKEN_AMOUNT).required(true).long(MINIMUM_OUTPUT_TOKEN_AMOUNT).help("Minimum token amount to receive.")),
+            )
+            .subcommand(
+                Command::new(COMMAND_SELL_ON_METEORA_DYNAMIC_AMM)
+                    .arg(Arg::new(INTERMEDIARY_TRADER).required(true).long(INTERMEDIARY_TRADER).help("Fee payer keypair.json file path."))
+                    .arg(Arg::new(INTERMEDIARY).required(true).long(INTERMEDIARY).help("Intermediary pubkey."))
+                    .arg(Arg::new(MINT).required(true).long(MINT).help("Token mint pubkey."))
+                    .arg(Arg::new(POOL).required(true).long(POOL).help("Meteora dynamic AMM pool pubkey."))
+                    .arg(Arg::new(INPUT_TOKEN_AMOUNT).required(true).long(INPUT_TOKEN_AMOUNT).help("Token amount to sell."))
+                    .arg(Arg::new(MINIMUM_OUTPUT_TOKEN_AMOUNT).required(false).long(MINIMUM_OUTPUT_TOKEN_AMOUNT).help("Minimum lamports to treasury.")),
+            )
+            .subcommand(
+                Command::new(COMMAND_BUY_ON_PUMPFUN_CURVE)
+                    .arg(Arg::new(INTERMEDIARY_TRADER).required(true).long(INTERMEDIARY_TRADER).help("Fee payer keypair.json file path."))
+                    .arg(Arg::new(INTERMEDIARY).required(true).long(INTERMEDIARY).help("Intermediary pubkey."))
+                    .arg(Arg::new(MINT).required(true).long(MINT).help("Token mint pubkey."))
+                    .arg(Arg::new(BASE_AMOUNT_OUT).required(true).long(BASE_AMOUNT_OUT).help("Token amount to receive."))
+                    .arg(Arg::new(MAXIMUM_QUOTE_AMOUNT_IN).required(true).long(MAXIMUM_QUOTE_AMOUNT_IN).help("Maximum lamports from treasury to spend.")),
+            )
+            .subcommand(
+                Command::new(COMMAND_SELL_ON_PUMPFUN_CURVE)
+                    .arg(Arg::new(INTERMEDIARY_TRADER).required(true).long(INTERMEDIARY_TRADER).help("Fee payer keypair.json file path."))
+                    .arg(Arg::new(INTERMEDIARY).required(true).long(INTERMEDIARY).help("Intermediary pubkey."))
+                    .arg(Arg::new(MINT).required(true).long(MINT).help("Token mint pubkey."))
+                    .arg(Arg::new(INPUT_TOKEN_AMOUNT).required(true).long(INPUT_TOKEN_AMOUNT).help("Token amount to sell."))
+                    .arg(Arg::new(MINIMUM_OUTPUT_TOKEN_AMOUNT).required(false).long(MINIMUM_OUTPUT_TOKEN_AMOUNT).help("Minimum lamports to treasury.")),
+            )
+            .subcommand(
+                Command::new(COMMAND_SWAP)
+                    .arg(Arg::new(INTERMEDIARY_TRADER).required(true).long(INTERMEDIARY_TRADER).help("Fee payer keypair.json file path."))
+                    .arg(Arg::new(INTERMEDIARY).required(true).long(INTERMEDIARY).help("Intermediary pubkey."))
+                    .arg(Arg::new(VENUE).required(true).long(VENUE).help("raydium or pumpswap."))
+                    .arg(Arg::new(SIDE).required(true).long(SIDE).help("buy or sell."))
+                    .arg(Arg::new(MINT).required(true).long(MINT).help("Token mint pubkey."))
+                    .arg(Arg::new(POOL).required(true).long(POOL).help("Pool pubkey on the venue."))
+                    .arg(Arg::new(AMOUNT_IN).required(true).long(AMOUNT_IN).help("Lamports from treasury to spend on buy, token amount to sell on sell."))
+                    .arg(Arg::new(MIN_OUT).required(true).long(MIN_OUT).help("Minimum token amount to receive on buy, minimum lamports to treasury on sell.")),
+            )
+            .subcommand(
+                Command::new(COMMAND_ROUTED_SWAP)
+                    .arg(Arg::new(INTERMEDIARY_TRADER).required(true).long(INTERMEDIARY_TRADER).help("Fee payer keypair.json file path."))
+                    .arg(Arg::new(INTERMEDIARY).required(true).long(INTERMEDIARY).help("Intermediary pubkey."))
+                    .arg(Arg::new(SIDE).required(true).long(SIDE).help("buy or sell."))
+                    .arg(Arg::new(MINT).required(true).long(MINT).help("Token mint pubkey."))
+                    .arg(Arg::new(VENUES).required(true).long(VENUES).help("Comma-separated venues, raydium or pumpswap, one per leg in route order."))
+                    .arg(Arg::new(POOLS).required(true).long(POOLS).help("Comma-separated pool pubkeys, one per venue."))
+                    .arg(Arg::new(INTERMEDIATE_MINTS).required(true).long(INTERMEDIATE_MINTS).help("Comma-separated intermediate token mint pubkeys, one between each two legs."))
+                    .arg(Arg::new(AMOUNT_IN).required(true).long(AMOUNT_IN).help("Lamports from treasury to spend on buy, token amount to sell on sell."))
+                    .arg(Arg::new(MIN_OUT).required(true).long(MIN_OUT).help("Minimum token amount to receive on buy, minimum lamports to treasury on sell."))
+                    .arg(Arg::new(LOOKUP_TABLE).required(false).long(LOOKUP_TABLE).help("Address lookup table pubkey holding the route accounts.")),
+            )
             .arg(Arg::new(SOLANA_RPC_URL).required(true).long(SOLANA_RPC_URL))
             .get_matches();
         let solana_rpc_url = arg_matches.get_one::<String>(SOLANA_RPC_URL).unwrap();
@@ -100,22 +500,417 @@ impl Processor {
                     arg_matches_.get_one::<String>(LAMPORTS_FROM_TREASURY).unwrap().parse::<u64>()?,
                 )
             }
-            (COMMAND_CHANGE_MANAGER, arg_matches_) => {
-                CommandProcessor::change_manager(
+            (COMMAND_PROPOSE_MANAGER, arg_matches_) => {
+                CommandProcessor::propose_manager(
+                    &rpc_client,
+                    arg_matches_.get_one::<String>(INTERMEDIARY_INVESTOR).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(INTERMEDIARY).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(INTERMEDIARY_MANAGER).unwrap().as_str(),
+                )
+            }
+            (COMMAND_PROPOSE_TRADER, arg_matches_) => {
+                CommandProcessor::propose_trader(
+                    &rpc_client,
+                    arg_matches_.get_one::<String>(INTERMEDIARY_MANAGER).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(INTERMEDIARY).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(INTERMEDIARY_TRADER).unwrap().as_str(),
+                )
+            }
+            (COMMAND_BUY_ON_RAYDIUM, arg_matches_) => {
+                CommandProcessor::buy_on_raydium(
+                    &rpc_client,
+                    arg_matches_.get_one::<String>(INTERMEDIARY_TRADER).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(INTERMEDIARY).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(MINT).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(POOL).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(INPUT_TOKEN_AMOUNT).unwrap().parse::<u64>()?,
+                    arg_matches_.get_one::<String>(MINIMUM_OUTPUT_TOKEN_AMOUNT).map(|minimum_output_token_amount| minimum_output_token_amount.parse::<u64>()).transpose()?,
+                    arg_matches_.get_one::<String>(SLIPPAGE_BASIS_POINTS).map(|slippage_basis_points| slippage_basis_points.parse::<u16>()).transpose()?,
+                )
+            }
+            (COMMAND_SELL_ON_RAYDIUM, arg_matches_) => {
+                CommandProcessor::sell_on_raydium(
+                    &rpc_client,
+                    arg_matches_.get_one::<String>(INTERMEDIARY_TRADER).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(INTERMEDIARY).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(MINT).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(POOL).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(INPUT_TOKEN_AMOUNT).unwrap().parse::<u64>()?,
+                    arg_matches_.get_one::<String>(MINIMUM_OUTPUT_TOKEN_AMOUNT).map(|minimum_output_token_amount| minimum_output_token_amount.parse::<u64>()).transpose()?,
+                    arg_matches_.get_one::<String>(SLIPPAGE_BASIS_POINTS).map(|slippage_basis_points| slippage_basis_points.parse::<u16>()).transpose()?,
+                )
+            }
+            (COMMAND_BUY_ON_PUMPSWAP, arg_matches_) => {
+                CommandProcessor::buy_on_pumpswap(
+                    &rpc_client,
+                    arg_matches_.get_one::<String>(INTERMEDIARY_TRADER).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(INTERMEDIARY).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(MINT).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(POOL).map(String::as_str),
+                    arg_matches_.get_one::<String>(BASE_AMOUNT_OUT).unwrap().parse::<u64>()?,
+                    arg_matches_.get_one::<String>(MAXIMUM_QUOTE_AMOUNT_IN).unwrap().parse::<u64>()?,
+                )
+            }
+            (COMMAND_SELL_ON_PUMPSWAP, arg_matches_) => {
+                CommandProcessor::sell_on_pumpswap(
+                    &rpc_client,
+                    arg_matches_.get_one::<String>(INTERMEDIARY_TRADER).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(INTERMEDIARY).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(MINT).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(POOL).map(String::as_str),
+                    arg_matches_.get_one::<String>(INPUT_TOKEN_AMOUNT).unwrap().parse::<u64>()?,
+                    arg_matches_.get_one::<String>(MINIMUM_OUTPUT_TOKEN_AMOUNT).map(|minimum_output_token_amount| minimum_output_token_amount.parse::<u64>()).transpose()?,
+                    arg_matches_.get_one::<String>(SLIPPAGE_BASIS_POINTS).map(|slippage_basis_points| slippage_basis_points.parse::<u16>()).transpose()?,
+                )
+            }
+            (COMMAND_INITIALIZE_CONFIG, arg_matches_) => {
+                CommandProcessor::initialize_config(
+                    &rpc_client,
+                    arg_matches_.get_one::<String>(ADMINISTRATOR).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(FEE_RECIPIENT).unwrap().as_str(),
+                    Fees {
+                        initialize: arg_matches_.get_one::<String>(FEE_INITIALIZE).unwrap().parse::<u64>()?,
+                        deposit_funds: arg_matches_.get_one::<String>(FEE_DEPOSIT_FUNDS).unwrap().parse::<u64>()?,
+                        withdraw_funds: arg_matches_.get_one::<String>(FEE_WITHDRAW_FUNDS).unwrap().parse::<u64>()?,
+                        buy_on_raydium: arg_matches_.get_one::<String>(FEE_BUY_ON_RAYDIUM).unwrap().parse::<u64>()?,
+                        sell_on_raydium: arg_matches_.get_one::<String>(FEE_SELL_ON_RAYDIUM).unwrap().parse::<u64>()?,
+                        buy_on_pumpswap: arg_matches_.get_one::<String>(FEE_BUY_ON_PUMPSWAP).unwrap().parse::<u64>()?,
+                        sell_on_pumpswap: arg_matches_.get_one::<String>(FEE_SELL_ON_PUMPSWAP).unwrap().parse::<u64>()?,
+                        change_manager: arg_matches_.get_one::<String>(FEE_CHANGE_MANAGER).unwrap().parse::<u64>()?,
+                        change_trader: arg_matches_.get_one::<String>(FEE_CHANGE_TRADER).unwrap().parse::<u64>()?,
+                        buy_on_raydium_cpmm: arg_matches_.get_one::<String>(FEE_BUY_ON_RAYDIUM_CPMM).unwrap().parse::<u64>()?,
+                        sell_on_raydium_cpmm: arg_matches_.get_one::<String>(FEE_SELL_ON_RAYDIUM_CPMM).unwrap().parse::<u64>()?,
+                        buy_on_raydium_clmm: arg_matches_.get_one::<String>(FEE_BUY_ON_RAYDIUM_CLMM).unwrap().parse::<u64>()?,
+                        sell_on_raydium_clmm: arg_matches_.get_one::<String>(FEE_SELL_ON_RAYDIUM_CLMM).unwrap().parse::<u64>()?,
+                        buy_on_meteora_dlmm: arg_matches_.get_one::<String>(FEE_BUY_ON_METEORA_DLMM).unwrap().parse::<u64>()?,
+                        sell_on_meteora_dlmm: arg_matches_.get_one::<String>(FEE_SELL_ON_METEORA_DLMM).unwrap().parse::<u64>()?,
+                        buy_on_meteora_dynamic_amm: arg_matches_.get_one::<String>(FEE_BUY_ON_METEORA_DYNAMIC_AMM).unwrap().parse::<u64>()?,
+                        sell_on_meteora_dynamic_amm: arg_matches_.get_one::<String>(FEE_SELL_ON_METEORA_DYNAMIC_AMM).unwrap().parse::<u64>()?,
+                        buy_on_pumpfun_curve: arg_matches_.get_one::<String>(FEE_BUY_ON_PUMPFUN_CURVE).unwrap().parse::<u64>()?,
+                        sell_on_pumpfun_curve: arg_matches_.get_one::<String>(FEE_SELL_ON_PUMPFUN_CURVE).unwrap().parse::<u64>()?,
+                    },
+                )
+            }
+            (COMMAND_UPDATE_CONFIG, arg_matches_) => {
+                CommandProcessor::update_config(
+                    &rpc_client,
+                    arg_matches_.get_one::<String>(ADMINISTRATOR).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(FEE_RECIPIENT).unwrap().as_str(),
+                    Fees {
+                        initialize: arg_matches_.get_one::<String>(FEE_INITIALIZE).unwrap().parse::<u64>()?,
+                        deposit_funds: arg_matches_.get_one::<String>(FEE_DEPOSIT_FUNDS).unwrap().parse::<u64>()?,
+                        withdraw_funds: arg_matches_.get_one::<String>(FEE_WITHDRAW_FUNDS).unwrap().parse::<u64>()?,
+                        buy_on_raydium: arg_matches_.get_one::<String>(FEE_BUY_ON_RAYDIUM).unwrap().parse::<u64>()?,
+                        sell_on_raydium: arg_matches_.get_one::<String>(FEE_SELL_ON_RAYDIUM).unwrap().parse::<u64>()?,
+                        buy_on_pumpswap: arg_matches_.get_one::<String>(FEE_BUY_ON_PUMPSWAP).unwrap().parse::<u64>()?,
+                        sell_on_pumpswap: arg_matches_.get_one::<String>(FEE_SELL_ON_PUMPSWAP).unwrap().parse::<u64>()?,
+                        change_manager: arg_matches_.get_one::<String>(FEE_CHANGE_MANAGER).unwrap().parse::<u64>()?,
+                        change_trader: arg_matches_.get_one::<String>(FEE_CHANGE_TRADER).unwrap().parse::<u64>()?,
+                        buy_on_raydium_cpmm: arg_matches_.get_one::<String>(FEE_BUY_ON_RAYDIUM_CPMM).unwrap().parse::<u64>()?,
+                        sell_on_raydium_cpmm: arg_matches_.get_one::<String>(FEE_SELL_ON_RAYDIUM_CPMM).unwrap().parse::<u64>()?,
+                        buy_on_raydium_clmm: arg_matches_.get_one::<String>(FEE_BUY_ON_RAYDIUM_CLMM).unwrap().parse::<u64>()?,
+                        sell_on_raydium_clmm: arg_matches_.get_one::<String>(FEE_SELL_ON_RAYDIUM_CLMM).unwrap().parse::<u64>()?,
+                        buy_on_meteora_dlmm: arg_matches_.get_one::<String>(FEE_BUY_ON_METEORA_DLMM).unwrap().parse::<u64>()?,
+                        sell_on_meteora_dlmm: arg_matches_.get_one::<String>(FEE_SELL_ON_METEORA_DLMM).unwrap().parse::<u64>()?,
+                        buy_on_meteora_dynamic_amm: arg_matches_.get_one::<String>(FEE_BUY_ON_METEORA_DYNAMIC_AMM).unwrap().parse::<u64>()?,
+                        sell_on_meteora_dynamic_amm: arg_matches_.get_one::<String>(FEE_SELL_ON_METEORA_DYNAMIC_AMM).unwrap().parse::<u64>()?,
+                        buy_on_pumpfun_curve: arg_matches_.get_one::<String>(FEE_BUY_ON_PUMPFUN_CURVE).unwrap().parse::<u64>()?,
+                        sell_on_pumpfun_curve: arg_matches_.get_one::<String>(FEE_SELL_ON_PUMPFUN_CURVE).unwrap().parse::<u64>()?,
+                    },
+                )
+            }
+            (COMMAND_SWEEP_FEES, arg_matches_) => {
+                CommandProcessor::sweep_fees(
+                    &rpc_client,
+                    arg_matches_.get_one::<String>(ADMINISTRATOR).unwrap().as_str(),
+                )
+            }
+            (COMMAND_BUY_ON_RAYDIUM_EXACT_OUT, arg_matches_) => {
+                CommandProcessor::buy_on_raydium_exact_out(
+                    &rpc_client,
+                    arg_matches_.get_one::<String>(INTERMEDIARY_TRADER).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(INTERMEDIARY).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(MINT).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(POOL).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(MAXIMUM_AMOUNT_IN).unwrap().parse::<u64>()?,
+                    arg_matches_.get_one::<String>(AMOUNT_OUT).unwrap().parse::<u64>()?,
+                )
+            }
+            (COMMAND_SELL_ON_RAYDIUM_EXACT_OUT, arg_matches_) => {
+                CommandProcessor::sell_on_raydium_exact_out(
+                    &rpc_client,
+                    arg_matches_.get_one::<String>(INTERMEDIARY_TRADER).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(INTERMEDIARY).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(MINT).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(POOL).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(MAXIMUM_AMOUNT_IN).unwrap().parse::<u64>()?,
+                    arg_matches_.get_one::<String>(AMOUNT_OUT).unwrap().parse::<u64>()?,
+                )
+            }
+            (COMMAND_SET_RISK_LIMITS, arg_matches_) => {
+                CommandProcessor::set_risk_limits(
+                    &rpc_client,
+                    arg_matches_.get_one::<String>(INTERMEDIARY_MANAGER).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(INTERMEDIARY).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(MAXIMUM_LAMPORTS_PER_TRADE).unwrap().parse::<u64>()?,
+                    arg_matches_.get_one::<String>(MAXIMUM_TREASURY_SHARE_PER_TRADE_BASIS_POINTS).unwrap().parse::<u16>()?,
+                    arg_matches_.get_one::<String>(MAXIMUM_TRADES_PER_SLOT_WINDOW).unwrap().parse::<u64>()?,
+                    arg_matches_.get_one::<String>(SLOT_WINDOW_LENGTH).unwrap().parse::<u64>()?,
+                )
+            }
+            (COMMAND_SET_MAXIMUM_PRICE_DEVIATION, arg_matches_) => {
+                CommandProcessor::set_maximum_price_deviation(
+                    &rpc_client,
+                    arg_matches_.get_one::<String>(INTERMEDIARY_MANAGER).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(INTERMEDIARY).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(MAXIMUM_PRICE_DEVIATION_BASIS_POINTS).unwrap().parse::<u16>()?,
+                )
+            }
+            (COMMAND_ADD_ALLOWED_MINT, arg_matches_) => {
+                CommandProcessor::add_allowed_mint(
+                    &rpc_client,
+                    arg_matches_.get_one::<String>(INTERMEDIARY_MANAGER).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(INTERMEDIARY).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(MINT).unwrap().as_str(),
+                )
+            }
+            (COMMAND_REMOVE_ALLOWED_MINT, arg_matches_) => {
+                CommandProcessor::remove_allowed_mint(
+                    &rpc_client,
+                    arg_matches_.get_one::<String>(INTERMEDIARY_MANAGER).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(INTERMEDIARY).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(MINT).unwrap().as_str(),
+                )
+            }
+            (COMMAND_CLOSE, arg_matches_) => {
+                CommandProcessor::close(
                     &rpc_client,
                     arg_matches_.get_one::<String>(INTERMEDIARY_INVESTOR).unwrap().as_str(),
                     arg_matches_.get_one::<String>(INTERMEDIARY).unwrap().as_str(),
+                )
+            }
+            (COMMAND_ACCEPT_MANAGER, arg_matches_) => {
+                CommandProcessor::accept_manager(
+                    &rpc_client,
+                    arg_matches_.get_one::<String>(INTERMEDIARY_MANAGER).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(INTERMEDIARY).unwrap().as_str(),
+                )
+            }
+            (COMMAND_ACCEPT_TRADER, arg_matches_) => {
+                CommandProcessor::accept_trader(
+                    &rpc_client,
+                    arg_matches_.get_one::<String>(INTERMEDIARY_TRADER).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(INTERMEDIARY).unwrap().as_str(),
+                )
+            }
+            (COMMAND_CANCEL_MANAGER_PROPOSAL, arg_matches_) => {
+                CommandProcessor::cancel_manager_proposal(
+                    &rpc_client,
+                    arg_matches_.get_one::<String>(AUTHORITY).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(INTERMEDIARY).unwrap().as_str(),
+                )
+            }
+            (COMMAND_CANCEL_TRADER_PROPOSAL, arg_matches_) => {
+                CommandProcessor::cancel_trader_proposal(
+                    &rpc_client,
+                    arg_matches_.get_one::<String>(AUTHORITY).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(INTERMEDIARY).unwrap().as_str(),
+                )
+            }
+            (COMMAND_ADD_TRADER_SEAT, arg_matches_) => {
+                CommandProcessor::add_trader_seat(
+                    &rpc_client,
                     arg_matches_.get_one::<String>(INTERMEDIARY_MANAGER).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(INTERMEDIARY).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(INTERMEDIARY_TRADER).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(ALLOCATED_LAMPORTS).unwrap().parse::<u64>()?,
                 )
             }
-            (COMMAND_CHANGE_TRADER, arg_matches_) => {
-                CommandProcessor::change_trader(
+            (COMMAND_REMOVE_TRADER_SEAT, arg_matches_) => {
+                CommandProcessor::remove_trader_seat(
                     &rpc_client,
                     arg_matches_.get_one::<String>(INTERMEDIARY_MANAGER).unwrap().as_str(),
                     arg_matches_.get_one::<String>(INTERMEDIARY).unwrap().as_str(),
                     arg_matches_.get_one::<String>(INTERMEDIARY_TRADER).unwrap().as_str(),
                 )
             }
+            (COMMAND_PAUSE, arg_matches_) => {
+                CommandProcessor::pause(
+                    &rpc_client,
+                    arg_matches_.get_one::<String>(AUTHORITY).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(INTERMEDIARY).unwrap().as_str(),
+                )
+            }
+            (COMMAND_UNPAUSE, arg_matches_) => {
+                CommandProcessor::unpause(
+                    &rpc_client,
+                    arg_matches_.get_one::<String>(INTERMEDIARY_INVESTOR).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(INTERMEDIARY).unwrap().as_str(),
+                )
+            }
+            (COMMAND_MIGRATE_INTERMEDIARY, arg_matches_) => {
+                CommandProcessor::migrate_intermediary(
+                    &rpc_client,
+                    arg_matches_.get_one::<String>(AUTHORITY).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(INTERMEDIARY).unwrap().as_str(),
+                )
+            }
+            (COMMAND_LIQUIDATE_ALL, arg_matches_) => {
+                CommandProcessor::liquidate_all(
+                    &rpc_client,
+                    arg_matches_.get_one::<String>(AUTHORITY).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(INTERMEDIARY).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(MINTS).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(VENUES).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(POOLS).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(MINIMUM_OUTPUT_TOKEN_AMOUNTS).unwrap().as_str(),
+                )
+            }
+            (COMMAND_WITHDRAW_TOKEN, arg_matches_) => {
+                CommandProcessor::withdraw_token(
+                    &rpc_client,
+                    arg_matches_.get_one::<String>(INTERMEDIARY_INVESTOR).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(INTERMEDIARY).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(MINT).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(TOKEN_AMOUNT).unwrap().parse::<u64>()?,
+                )
+            }
+            (COMMAND_BUY_ON_RAYDIUM_CPMM, arg_matches_) => {
+                CommandProcessor::buy_on_raydium_cpmm(
+                    &rpc_client,
+                    arg_matches_.get_one::<String>(INTERMEDIARY_TRADER).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(INTERMEDIARY).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(MINT).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(POOL).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(INPUT_TOKEN_AMOUNT).unwrap().parse::<u64>()?,
+                    arg_matches_.get_one::<String>(MINIMUM_OUTPUT_TOKEN_AMOUNT).unwrap().parse::<u64>()?,
+                )
+            }
+            (COMMAND_SELL_ON_RAYDIUM_CPMM, arg_matches_) => {
+                CommandProcessor::sell_on_raydium_cpmm(
+                    &rpc_client,
+                    arg_matches_.get_one::<String>(INTERMEDIARY_TRADER).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(INTERMEDIARY).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(MINT).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(POOL).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(INPUT_TOKEN_AMOUNT).unwrap().parse::<u64>()?,
+                    arg_matches_.get_one::<String>(MINIMUM_OUTPUT_TOKEN_AMOUNT).map(|minimum_output_token_amount| minimum_output_token_amount.parse::<u64>()).transpose()?,
+                )
+            }
+            (COMMAND_BUY_ON_RAYDIUM_CLMM, arg_matches_) => {
+                CommandProcessor::buy_on_raydium_clmm(
+                    &rpc_client,
+                    arg_matches_.get_one::<String>(INTERMEDIARY_TRADER).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(INTERMEDIARY).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(MINT).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(POOL).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(INPUT_TOKEN_AMOUNT).unwrap().parse::<u64>()?,
+                    arg_matches_.get_one::<String>(MINIMUM_OUTPUT_TOKEN_AMOUNT).unwrap().parse::<u64>()?,
+                )
+            }
+            (COMMAND_SELL_ON_RAYDIUM_CLMM, arg_matches_) => {
+                CommandProcessor::sell_on_raydium_clmm(
+                    &rpc_client,
+                    arg_matches_.get_one::<String>(INTERMEDIARY_TRADER).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(INTERMEDIARY).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(MINT).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(POOL).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(INPUT_TOKEN_AMOUNT).unwrap().parse::<u64>()?,
+                    arg_matches_.get_one::<String>(MINIMUM_OUTPUT_TOKEN_AMOUNT).map(|minimum_output_token_amount| minimum_output_token_amount.parse::<u64>()).transpose()?,
+                )
+            }
+            (COMMAND_BUY_ON_METEORA_DLMM, arg_matches_) => {
+                CommandProcessor::buy_on_meteora_dlmm(
+                    &rpc_client,
+                    arg_matches_.get_one::<String>(INTERMEDIARY_TRADER).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(INTERMEDIARY).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(MINT).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(POOL).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(INPUT_TOKEN_AMOUNT).unwrap().parse::<u64>()?,
+                    arg_matches_.get_one::<String>(MINIMUM_OUTPUT_TOKEN_AMOUNT).unwrap().parse::<u64>()?,
+                )
+            }
+            (COMMAND_SELL_ON_METEORA_DLMM, arg_matches_) => {
+                CommandProcessor::sell_on_meteora_dlmm(
+                    &rpc_client,
+                    arg_matches_.get_one::<String>(INTERMEDIARY_TRADER).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(INTERMEDIARY).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(MINT).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(POOL).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(INPUT_TOKEN_AMOUNT).unwrap().parse::<u64>()?,
+                    arg_matches_.get_one::<String>(MINIMUM_OUTPUT_TOKEN_AMOUNT).map(|minimum_output_token_amount| minimum_output_token_amount.parse::<u64>()).transpose()?,
+                )
+            }
+            (COMMAND_BUY_ON_METEORA_DYNAMIC_AMM, arg_matches_) => {
+                CommandProcessor::buy_on_meteora_dynamic_amm(
+                    &rpc_client,
+                    arg_matches_.get_one::<String>(INTERMEDIARY_TRADER).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(INTERMEDIARY).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(MINT).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(POOL).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(INPUT_TOKEN_AMOUNT).unwrap().parse::<u64>()?,
+                    arg_matches_.get_one::<String>(MINIMUM_OUTPUT_TOKEN_AMOUNT).unwrap().parse::<u64>()?,
+                )
+            }
+            (COMMAND_SELL_ON_METEORA_DYNAMIC_AMM, arg_matches_) => {
+                CommandProcessor::sell_on_meteora_dynamic_amm(
+                    &rpc_client,
+                    arg_matches_.get_one::<String>(INTERMEDIARY_TRADER).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(INTERMEDIARY).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(MINT).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(POOL).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(INPUT_TOKEN_AMOUNT).unwrap().parse::<u64>()?,
+                    arg_matches_.get_one::<String>(MINIMUM_OUTPUT_TOKEN_AMOUNT).map(|minimum_output_token_amount| minimum_output_token_amount.parse::<u64>()).transpose()?,
+                )
+            }
+            (COMMAND_BUY_ON_PUMPFUN_CURVE, arg_matches_) => {
+                CommandProcessor::buy_on_pumpfun_curve(
+                    &rpc_client,
+                    arg_matches_.get_one::<String>(INTERMEDIARY_TRADER).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(INTERMEDIARY).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(MINT).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(BASE_AMOUNT_OUT).unwrap().parse::<u64>()?,
+                    arg_matches_.get_one::<String>(MAXIMUM_QUOTE_AMOUNT_IN).unwrap().parse::<u64>()?,
+                )
+            }
+            (COMMAND_SELL_ON_PUMPFUN_CURVE, arg_matches_) => {
+                CommandProcessor::sell_on_pumpfun_curve(
+                    &rpc_client,
+                    arg_matches_.get_one::<String>(INTERMEDIARY_TRADER).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(INTERMEDIARY).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(MINT).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(INPUT_TOKEN_AMOUNT).unwrap().parse::<u64>()?,
+                    arg_matches_.get_one::<String>(MINIMUM_OUTPUT_TOKEN_AMOUNT).map(|minimum_output_token_amount| minimum_output_token_amount.parse::<u64>()).transpose()?,
+                )
+            }
+            (COMMAND_SWAP, arg_matches_) => {
+                CommandProcessor::swap(
+                    &rpc_client,
+                    arg_matches_.get_one::<String>(INTERMEDIARY_TRADER).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(INTERMEDIARY).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(VENUE).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(SIDE).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(MINT).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(POOL).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(AMOUNT_IN).unwrap().parse::<u64>()?,
+                    arg_matches_.get_one::<String>(MIN_OUT).unwrap().parse::<u64>()?,
+                )
+            }
+            (COMMAND_ROUTED_SWAP, arg_matches_) => {
+                CommandProcessor::routed_swap(
+                    &rpc_client,
+                    arg_matches_.get_one::<String>(INTERMEDIARY_TRADER).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(INTERMEDIARY).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(SIDE).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(MINT).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(VENUES).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(POOLS).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(INTERMEDIATE_MINTS).unwrap().as_str(),
+                    arg_matches_.get_one::<String>(AMOUNT_IN).unwrap().parse::<u64>()?,
+                    arg_matches_.get_one::<String>(MIN_OUT).unwrap().parse::<u64>()?,
+                    arg_matches_.get_one::<String>(LOOKUP_TABLE).map(String::as_str),
+                )
+            }
             _ => Err(LOGIC_ERROR.into()),
         }
     }
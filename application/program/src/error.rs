@@ -43,6 +43,66 @@ pub enum Error {
     TokenAccountInvalidAmount,
     #[error("TokenAccountInvalidMint")]
     TokenAccountInvalidMint,
+    #[error("ConfigIsNotInitialized")]
+    ConfigIsNotInitialized,
+    #[error("ConfigInvalidAdministrator")]
+    ConfigInvalidAdministrator,
+    #[error("ConfigInvalidFeeRecipient")]
+    ConfigInvalidFeeRecipient,
+    #[error("ConfigInvalidFeeVault")]
+    ConfigInvalidFeeVault,
+    #[error("TokenAccountInvalidAuthority")]
+    TokenAccountInvalidAuthority,
+    #[error("RiskLimitsIsNotInitialized")]
+    RiskLimitsIsNotInitialized,
+    #[error("RiskLimitsInvalidParameters")]
+    RiskLimitsInvalidParameters,
+    #[error("RiskLimitsMaximumLamportsPerTradeExceeded")]
+    RiskLimitsMaximumLamportsPerTradeExceeded,
+    #[error("RiskLimitsMaximumTreasurySharePerTradeExceeded")]
+    RiskLimitsMaximumTreasurySharePerTradeExceeded,
+    #[error("RiskLimitsMaximumTradesPerSlotWindowExceeded")]
+    RiskLimitsMaximumTradesPerSlotWindowExceeded,
+    #[error("AllowedMintsIsNotInitialized")]
+    AllowedMintsIsNotInitialized,
+    #[error("AllowedMintsAlreadyContainsMint")]
+    AllowedMintsAlreadyContainsMint,
+    #[error("AllowedMintsDoesNotContainMint")]
+    AllowedMintsDoesNotContainMint,
+    #[error("IntermediaryInvalidPendingManager")]
+    IntermediaryInvalidPendingManager,
+    #[error("IntermediaryInvalidPendingTrader")]
+    IntermediaryInvalidPendingTrader,
+    #[error("TraderSeatIsNotInitialized")]
+    TraderSeatIsNotInitialized,
+    #[error("TraderSeatIsAlreadyInitialized")]
+    TraderSeatIsAlreadyInitialized,
+    #[error("TraderSeatInvalidTrader")]
+    TraderSeatInvalidTrader,
+    #[error("TraderSeatAllocationExceeded")]
+    TraderSeatAllocationExceeded,
+    #[error("IntermediaryPaused")]
+    IntermediaryPaused,
+    #[error("IntermediaryIsNotPaused")]
+    IntermediaryIsNotPaused,
+    #[error("IntermediaryIsNotMigrated")]
+    IntermediaryIsNotMigrated,
+    #[error("IntermediaryIsAlreadyMigrated")]
+    IntermediaryIsAlreadyMigrated,
+    #[error("VenueIsNotSupported")]
+    VenueIsNotSupported,
+    #[error("RouteInvalidLegsQuantity")]
+    RouteInvalidLegsQuantity,
+    #[error("IntermediaryInvalidMaximumPriceDeviation")]
+    IntermediaryInvalidMaximumPriceDeviation,
+    #[error("PriceDeviationExceeded")]
+    PriceDeviationExceeded,
+    #[error("IntermediaryInvalidInvestorOrManager")]
+    IntermediaryInvalidInvestorOrManager,
+    #[error("IntermediaryHasOpenPositions")]
+    IntermediaryHasOpenPositions,
+    #[error("IntermediaryHasTraderSeats")]
+    IntermediaryHasTraderSeats,
 }
 impl From<Error> for ProgramError {
     fn from(e: Error) -> Self {
@@ -76,6 +136,36 @@ impl PrintProgramError for Error {
             Error::TokenAccountInsufficientAmount => solana_program::msg!("Token account insufficient amount."),
             Error::TokenAccountInvalidAmount => solana_program::msg!("Token account invalid amount."),
             Error::TokenAccountInvalidMint => solana_program::msg!("Token account invalid mint."),
+            Error::ConfigIsNotInitialized => solana_program::msg!("Config is not initialized."),
+            Error::ConfigInvalidAdministrator => solana_program::msg!("Config invalid administrator."),
+            Error::ConfigInvalidFeeRecipient => solana_program::msg!("Config invalid fee recipient."),
+            Error::ConfigInvalidFeeVault => solana_program::msg!("Config invalid fee vault."),
+            Error::TokenAccountInvalidAuthority => solana_program::msg!("Token account invalid authority."),
+            Error::RiskLimitsIsNotInitialized => solana_program::msg!("Risk limits is not initialized."),
+            Error::RiskLimitsInvalidParameters => solana_program::msg!("Risk limits invalid parameters."),
+            Error::RiskLimitsMaximumLamportsPerTradeExceeded => solana_program::msg!("Risk limits maximum lamports per trade exceeded."),
+            Error::RiskLimitsMaximumTreasurySharePerTradeExceeded => solana_program::msg!("Risk limits maximum treasury share per trade exceeded."),
+            Error::RiskLimitsMaximumTradesPerSlotWindowExceeded => solana_program::msg!("Risk limits maximum trades per slot window exceeded."),
+            Error::AllowedMintsIsNotInitialized => solana_program::msg!("Allowed mints is not initialized."),
+            Error::AllowedMintsAlreadyContainsMint => solana_program::msg!("Allowed mints already contains mint."),
+            Error::AllowedMintsDoesNotContainMint => solana_program::msg!("Allowed mints does not contain mint."),
+            Error::IntermediaryInvalidPendingManager => solana_program::msg!("Intermediary invalid pending manager."),
+            Error::IntermediaryInvalidPendingTrader => solana_program::msg!("Intermediary invalid pending trader."),
+            Error::TraderSeatIsNotInitialized => solana_program::msg!("Trader seat is not initialized."),
+            Error::TraderSeatIsAlreadyInitialized => solana_program::msg!("Trader seat is already initialized."),
+            Error::TraderSeatInvalidTrader => solana_program::msg!("Trader seat invalid trader."),
+            Error::TraderSeatAllocationExceeded => solana_program::msg!("Trader seat allocation exceeded."),
+            Error::IntermediaryPaused => solana_program::msg!("Intermediary paused."),
+            Error::IntermediaryIsNotPaused => solana_program::msg!("Intermediary is not paused."),
+            Error::IntermediaryIsNotMigrated => solana_program::msg!("Intermediary is not migrated."),
+            Error::IntermediaryIsAlreadyMigrated => solana_program::msg!("Intermediary is already migrated."),
+            Error::VenueIsNotSupported => solana_program::msg!("Venue is not supported."),
+            Error::RouteInvalidLegsQuantity => solana_program::msg!("Route invalid legs quantity."),
+            Error::IntermediaryInvalidMaximumPriceDeviation => solana_program::msg!("Intermediary invalid maximum price deviation."),
+            Error::PriceDeviationExceeded => solana_program::msg!("Price deviation exceeded."),
+            Error::IntermediaryInvalidInvestorOrManager => solana_program::msg!("Intermediary invalid investor or manager."),
+            Error::IntermediaryHasOpenPositions => solana_program::msg!("Intermediary has open positions."),
+            Error::IntermediaryHasTraderSeats => solana_program::msg!("Intermediary has trader seats."),
         }
     }
 }
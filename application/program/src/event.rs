@@ -0,0 +1,217 @@
+use {
+    crate::{
+        state::Fees,
+        PROGRAM_PUBKEY,
+    },
+    base64::Engine,
+    solana_program::pubkey::Pubkey,
+    std::{
+        io::{
+            Error,
+            ErrorKind,
+        },
+        str::FromStr,
+    },
+};
+#[derive(Debug, Clone, PartialEq, Eq, borsh::BorshDeserialize, borsh::BorshSerialize, borsh::BorshSchema)]
+pub enum VersionedEvent {
+    V1(Event),
+}
+#[derive(Debug, Clone, PartialEq, Eq, borsh::BorshDeserialize, borsh::BorshSerialize, borsh::BorshSchema)]
+pub enum Event {
+    Initialized {
+        intermediary_pubkey: Pubkey,
+        investor_pubkey: Pubkey,
+        manager_pubkey: Pubkey,
+        trader_pubkey: Pubkey,
+        lamports_to_treasury: u64,
+        treasury_amount: u64,
+    },
+    FundsDeposited {
+        intermediary_pubkey: Pubkey,
+        lamports_to_treasury: u64,
+        treasury_amount: u64,
+    },
+    FundsWithdrawn {
+        intermediary_pubkey: Pubkey,
+        lamports_from_treasury: u64,
+        treasury_amount: u64,
+    },
+    Traded {
+        intermediary_pubkey: Pubkey,
+        venue: Venue,
+        side: Side,
+        mint_pubkey: Pubkey,
+        amount_in: u64,
+        amount_out: u64,
+        treasury_amount: u64,
+    },
+    FeeCharged {
+        intermediary_pubkey: Pubkey,
+        payer_pubkey: Pubkey,
+        fee_vault_pubkey: Pubkey,
+        lamports: u64,
+    },
+    RoleProposed {
+        intermediary_pubkey: Pubkey,
+        role: Role,
+        pubkey: Pubkey,
+    },
+    RoleAccepted {
+        intermediary_pubkey: Pubkey,
+        role: Role,
+        pubkey: Pubkey,
+    },
+    RoleProposalCancelled {
+        intermediary_pubkey: Pubkey,
+        role: Role,
+        pubkey: Pubkey,
+    },
+    RiskLimitsSet {
+        intermediary_pubkey: Pubkey,
+        maximum_lamports_per_trade: u64,
+        maximum_treasury_share_per_trade_basis_points: u16,
+        maximum_trades_per_slot_window: u64,
+        slot_window_length: u64,
+    },
+    AllowedMintAdded {
+        intermediary_pubkey: Pubkey,
+        mint_pubkey: Pubkey,
+    },
+    AllowedMintRemoved {
+        intermediary_pubkey: Pubkey,
+        mint_pubkey: Pubkey,
+    },
+    IntermediaryClosed {
+        intermediary_pubkey: Pubkey,
+        lamports_to_investor: u64,
+    },
+    ConfigInitialized {
+        administrator_pubkey: Pubkey,
+        fee_recipient_pubkey: Pubkey,
+        fees: Fees,
+    },
+    ConfigUpdated {
+        fees: Fees,
+    },
+    FeesSwept {
+        fee_recipient_pubkey: Pubkey,
+        lamports: u64,
+    },
+    TraderSeatAdded {
+        intermediary_pubkey: Pubkey,
+        trader_pubkey: Pubkey,
+        allocated_lamports: u64,
+    },
+    TraderSeatRemoved {
+        intermediary_pubkey: Pubkey,
+        trader_pubkey: Pubkey,
+        spent_lamports: u64,
+        received_lamports: u64,
+    },
+    Paused {
+        intermediary_pubkey: Pubkey,
+        authority_pubkey: Pubkey,
+    },
+    Unpaused {
+        intermediary_pubkey: Pubkey,
+        authority_pubkey: Pubkey,
+    },
+    IntermediaryMigrated {
+        intermediary_pubkey: Pubkey,
+        previous_version: u8,
+        version: u8,
+    },
+    TokenWithdrawn {
+        intermediary_pubkey: Pubkey,
+        mint_pubkey: Pubkey,
+        investor_token_account_pubkey: Pubkey,
+        amount: u64,
+    },
+    RoutedTraded {
+        intermediary_pubkey: Pubkey,
+        venues: Vec<Venue>,
+        side: Side,
+        mint_pubkey: Pubkey,
+        intermediate_mint_pubkeys: Vec<Pubkey>,
+        amount_in: u64,
+        amount_out: u64,
+        treasury_amount: u64,
+    },
+    MaximumPriceDeviationSet {
+        intermediary_pubkey: Pubkey,
+        maximum_price_deviation_basis_points: u16,
+    },
+}
+#[derive(Debug, Clone, Copy, PartialEq, Eq, borsh::BorshDeserialize, borsh::BorshSerialize, borsh::BorshSchema)]
+pub enum Venue {
+    Raydium,
+    Pumpswap,
+    RaydiumCpmm,
+    RaydiumClmm,
+    MeteoraDlmm,
+    MeteoraDynamicAmm,
+    PumpfunCurve,
+}
+#[derive(Debug, Clone, Copy, PartialEq, Eq, borsh::BorshDeserialize, borsh::BorshSerialize, borsh::BorshSchema)]
+pub enum Side {
+    Buy,
+    Sell,
+}
+#[derive(Debug, Clone, Copy, PartialEq, Eq, borsh::BorshDeserialize, borsh::BorshSerialize, borsh::BorshSchema)]
+pub enum Role {
+    Manager,
+    Trader,
+}
+impl Event {
+    pub fn emit(self) -> Result<(), Error> {
+        let data = borsh::to_vec(&VersionedEvent::V1(self))?;
+        solana_program::log::sol_log_data([data.as_slice()].as_slice());
+        Ok(())
+    }
+}
+impl VersionedEvent {
+    pub fn decode<'a>(data: &'a [u8]) -> Result<Self, Error> {
+        borsh::from_slice::<Self>(data)
+    }
+    // https://github.com/anza-xyz/agave/blob/v2.1.15/program-runtime/src/stable_log.rs
+    pub fn decode_log_messages<'a>(log_messages: &'a [String]) -> Result<Vec<Self>, Error> {
+        const PROGRAM_DATA_PREFIX: &'static str = "Program data: ";
+        const PROGRAM_PREFIX: &'static str = "Program ";
+        let mut program_pubkey_stack = Vec::<Pubkey>::new();
+        let mut versioned_events = Vec::<Self>::new();
+        for log_message in log_messages.iter() {
+            if let Some(program_data) = log_message.strip_prefix(PROGRAM_DATA_PREFIX) {
+                if program_pubkey_stack.last() != Some(&PROGRAM_PUBKEY) {
+                    continue;
+                }
+                for program_data_ in program_data.split(' ') {
+                    let data = base64::engine::general_purpose::STANDARD
+                        .decode(program_data_)
+                        .map_err(|error| Error::new(ErrorKind::InvalidData, error))?;
+                    versioned_events.push(Self::decode(data.as_slice())?);
+                }
+                continue;
+            }
+            let log_message_ = match log_message.strip_prefix(PROGRAM_PREFIX) {
+                Some(log_message__) => log_message__,
+                None => continue,
+            };
+            let mut log_message_parts = log_message_.split(' ');
+            let (program_pubkey, status) = match (log_message_parts.next(), log_message_parts.next()) {
+                (Some(program_pubkey_), Some(status_)) => (program_pubkey_, status_),
+                _ => continue,
+            };
+            let program_pubkey = match Pubkey::from_str(program_pubkey) {
+                Ok(program_pubkey_) => program_pubkey_,
+                Err(_) => continue,
+            };
+            if status == "invoke" {
+                program_pubkey_stack.push(program_pubkey);
+            } else if status == "success" || status == "failed:" {
+                program_pubkey_stack.pop();
+            }
+        }
+        Ok(versioned_events)
+    }
+}
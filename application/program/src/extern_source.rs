@@ -65,6 +65,339 @@ pub fn raydium_swap_base_in_pack(amount_in: u64, minimum_amount_out: u64) -> Vec
     buffer.extend_from_slice(minimum_amount_out.to_le_bytes().as_slice());
     buffer
 }
+// https://github.com/raydium-io/raydium-amm/blob/d10a8e9fab9f7a3d87b4ae3891e3e4c24b75c041/program/src/instruction.rs
+pub fn create_raydium_swap_base_out_instruction<'a>(
+    amm_program: &'a Pubkey,
+    amm_pool: &'a Pubkey,
+    amm_authority: &'a Pubkey,
+    amm_open_orders: &'a Pubkey,
+    amm_coin_vault: &'a Pubkey,
+    amm_pc_vault: &'a Pubkey,
+    market_program: &'a Pubkey,
+    market: &'a Pubkey,
+    market_bids: &'a Pubkey,
+    market_asks: &'a Pubkey,
+    market_event_queue: &'a Pubkey,
+    market_coin_vault: &'a Pubkey,
+    market_pc_vault: &'a Pubkey,
+    market_vault_signer: &'a Pubkey,
+    user_token_source: &'a Pubkey,
+    user_token_destination: &'a Pubkey,
+    user_source_owner: &'a Pubkey,
+    max_amount_in: u64,
+    amount_out: u64,
+) -> Instruction {
+    Instruction {
+        program_id: *amm_program,
+        accounts: vec![
+            AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new(*amm_pool, false),
+            AccountMeta::new_readonly(*amm_authority, false),
+            AccountMeta::new(*amm_open_orders, false),
+            AccountMeta::new(*amm_coin_vault, false),
+            AccountMeta::new(*amm_pc_vault, false),
+            AccountMeta::new_readonly(*market_program, false),
+            AccountMeta::new(*market, false),
+            AccountMeta::new(*market_bids, false),
+            AccountMeta::new(*market_asks, false),
+            AccountMeta::new(*market_event_queue, false),
+            AccountMeta::new(*market_coin_vault, false),
+            AccountMeta::new(*market_pc_vault, false),
+            AccountMeta::new_readonly(*market_vault_signer, false),
+            AccountMeta::new(*user_token_source, false),
+            AccountMeta::new(*user_token_destination, false),
+            AccountMeta::new_readonly(*user_source_owner, true),
+        ],
+        data: raydium_swap_base_out_pack(
+            max_amount_in,
+            amount_out,
+        ),
+    }
+}
+// https://github.com/raydium-io/raydium-amm/blob/d10a8e9fab9f7a3d87b4ae3891e3e4c24b75c041/program/src/instruction.rs
+pub fn raydium_swap_base_out_pack(max_amount_in: u64, amount_out: u64) -> Vec<u8> {
+    let mut buffer = Vec::<u8>::with_capacity(17);
+    buffer.push(11);
+    buffer.extend_from_slice(max_amount_in.to_le_bytes().as_slice());
+    buffer.extend_from_slice(amount_out.to_le_bytes().as_slice());
+    buffer
+}
+// https://github.com/raydium-io/raydium-cp-swap/blob/master/programs/cp-swap/src/instructions/swap_base_input.rs
+pub fn create_raydium_cpmm_swap_base_input_instruction<'a>(
+    program_id: &'a Pubkey,
+    payer: &'a Pubkey,
+    authority: &'a Pubkey,
+    amm_config: &'a Pubkey,
+    pool_state: &'a Pubkey,
+    input_token_account: &'a Pubkey,
+    output_token_account: &'a Pubkey,
+    input_vault: &'a Pubkey,
+    output_vault: &'a Pubkey,
+    input_token_program: &'a Pubkey,
+    output_token_program: &'a Pubkey,
+    input_token_mint: &'a Pubkey,
+    output_token_mint: &'a Pubkey,
+    observation_state: &'a Pubkey,
+    amount_in: u64,
+    minimum_amount_out: u64,
+) -> Instruction {
+    let mut data = Vec::<u8>::with_capacity(24);
+    // sha256("global:swap_base_input")[..8]
+    const DESCRIMINATOR: [u8; 8] = [143, 190, 90, 218, 196, 30, 51, 222];
+    data.extend(DESCRIMINATOR);
+    data.extend(amount_in.to_le_bytes());
+    data.extend(minimum_amount_out.to_le_bytes());
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(*payer, true),
+            AccountMeta::new_readonly(*authority, false),
+            AccountMeta::new_readonly(*amm_config, false),
+            AccountMeta::new(*pool_state, false),
+            AccountMeta::new(*input_token_account, false),
+            AccountMeta::new(*output_token_account, false),
+            AccountMeta::new(*input_vault, false),
+            AccountMeta::new(*output_vault, false),
+            AccountMeta::new_readonly(*input_token_program, false),
+            AccountMeta::new_readonly(*output_token_program, false),
+            AccountMeta::new_readonly(*input_token_mint, false),
+            AccountMeta::new_readonly(*output_token_mint, false),
+            AccountMeta::new(*observation_state, false),
+        ],
+        data,
+    }
+}
+// https://github.com/raydium-io/raydium-clmm/blob/master/programs/amm/src/instructions/swap_v2.rs
+// The remaining accounts are the optional tick array bitmap extension followed by the tick arrays the swap crosses.
+pub fn create_raydium_clmm_swap_v2_instruction<'a>(
+    program_id: &'a Pubkey,
+    payer: &'a Pubkey,
+    amm_config: &'a Pubkey,
+    pool_state: &'a Pubkey,
+    input_token_account: &'a Pubkey,
+    output_token_account: &'a Pubkey,
+    input_vault: &'a Pubkey,
+    output_vault: &'a Pubkey,
+    observation_state: &'a Pubkey,
+    input_vault_mint: &'a Pubkey,
+    output_vault_mint: &'a Pubkey,
+    remaining_accounts: &'a [Pubkey],
+    amount: u64,
+    other_amount_threshold: u64,
+    sqrt_price_limit_x64: u128,
+    is_base_input: bool,
+) -> Instruction {
+    let mut data = Vec::<u8>::with_capacity(41);
+    // sha256("global:swap_v2")[..8]
+    const DESCRIMINATOR: [u8; 8] = [43, 4, 237, 11, 26, 201, 30, 98];
+    data.extend(DESCRIMINATOR);
+    data.extend(amount.to_le_bytes());
+    data.extend(other_amount_threshold.to_le_bytes());
+    data.extend(sqrt_price_limit_x64.to_le_bytes());
+    data.push(is_base_input as u8);
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*payer, true),
+        AccountMeta::new_readonly(*amm_config, false),
+        AccountMeta::new(*pool_state, false),
+        AccountMeta::new(*input_token_account, false),
+        AccountMeta::new(*output_token_account, false),
+        AccountMeta::new(*input_vault, false),
+        AccountMeta::new(*output_vault, false),
+        AccountMeta::new(*observation_state, false),
+        AccountMeta::new_readonly(spl_token::ID, false),
+        AccountMeta::new_readonly(spl_token_2022::ID, false),
+        AccountMeta::new_readonly(crate::SPL_MEMO_PROGRAM_PUBKEY, false),
+        AccountMeta::new_readonly(*input_vault_mint, false),
+        AccountMeta::new_readonly(*output_vault_mint, false),
+    ];
+    for remaining_account in remaining_accounts.iter() {
+        accounts.push(AccountMeta::new(*remaining_account, false));
+    }
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    }
+}
+// https://github.com/MeteoraAg/dlmm-sdk/blob/main/programs/lb_clmm/src/instructions/swap.rs
+// The host fee account is optional and replaced by the program id. The remaining accounts are the bin arrays the swap crosses.
+pub fn create_meteora_dlmm_swap_instruction<'a>(
+    program_id: &'a Pubkey,
+    lb_pair: &'a Pubkey,
+    bin_array_bitmap_extension: &'a Pubkey,
+    reserve_x: &'a Pubkey,
+    reserve_y: &'a Pubkey,
+    user_token_in: &'a Pubkey,
+    user_token_out: &'a Pubkey,
+    token_x_mint: &'a Pubkey,
+    token_y_mint: &'a Pubkey,
+    oracle: &'a Pubkey,
+    user: &'a Pubkey,
+    token_x_program: &'a Pubkey,
+    token_y_program: &'a Pubkey,
+    event_authority: &'a Pubkey,
+    remaining_accounts: &'a [Pubkey],
+    amount_in: u64,
+    min_amount_out: u64,
+) -> Instruction {
+    let mut data = Vec::<u8>::with_capacity(24);
+    // sha256("global:swap")[..8]
+    const DESCRIMINATOR: [u8; 8] = [248, 198, 158, 145, 225, 117, 135, 200];
+    data.extend(DESCRIMINATOR);
+    data.extend(amount_in.to_le_bytes());
+    data.extend(min_amount_out.to_le_bytes());
+    let mut accounts = vec![
+        AccountMeta::new(*lb_pair, false),
+        AccountMeta::new_readonly(*bin_array_bitmap_extension, false),
+        AccountMeta::new(*reserve_x, false),
+        AccountMeta::new(*reserve_y, false),
+        AccountMeta::new(*user_token_in, false),
+        AccountMeta::new(*user_token_out, false),
+        AccountMeta::new_readonly(*token_x_mint, false),
+        AccountMeta::new_readonly(*token_y_mint, false),
+        AccountMeta::new(*oracle, false),
+        AccountMeta::new_readonly(*program_id, false),
+        AccountMeta::new_readonly(*user, true),
+        AccountMeta::new_readonly(*token_x_program, false),
+        AccountMeta::new_readonly(*token_y_program, false),
+        AccountMeta::new_readonly(*event_authority, false),
+        AccountMeta::new_readonly(*program_id, false),
+    ];
+    for remaining_account in remaining_accounts.iter() {
+        accounts.push(AccountMeta::new(*remaining_account, false));
+    }
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    }
+}
+// https://github.com/MeteoraAg/damm-v1-sdk/blob/main/programs/dynamic-amm/src/lib.rs
+pub fn create_meteora_dynamic_amm_swap_instruction<'a>(
+    program_id: &'a Pubkey,
+    pool: &'a Pubkey,
+    user_source_token: &'a Pubkey,
+    user_destination_token: &'a Pubkey,
+    a_vault: &'a Pubkey,
+    b_vault: &'a Pubkey,
+    a_token_vault: &'a Pubkey,
+    b_token_vault: &'a Pubkey,
+    a_vault_lp_mint: &'a Pubkey,
+    b_vault_lp_mint: &'a Pubkey,
+    a_vault_lp: &'a Pubkey,
+    b_vault_lp: &'a Pubkey,
+    protocol_token_fee: &'a Pubkey,
+    user: &'a Pubkey,
+    vault_program: &'a Pubkey,
+    token_program: &'a Pubkey,
+    in_amount: u64,
+    minimum_out_amount: u64,
+) -> Instruction {
+    let mut data = Vec::<u8>::with_capacity(24);
+    // sha256("global:swap")[..8]
+    const DESCRIMINATOR: [u8; 8] = [248, 198, 158, 145, 225, 117, 135, 200];
+    data.extend(DESCRIMINATOR);
+    data.extend(in_amount.to_le_bytes());
+    data.extend(minimum_out_amount.to_le_bytes());
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(*pool, false),
+            AccountMeta::new(*user_source_token, false),
+            AccountMeta::new(*user_destination_token, false),
+            AccountMeta::new(*a_vault, false),
+            AccountMeta::new(*b_vault, false),
+            AccountMeta::new(*a_token_vault, false),
+            AccountMeta::new(*b_token_vault, false),
+            AccountMeta::new(*a_vault_lp_mint, false),
+            AccountMeta::new(*b_vault_lp_mint, false),
+            AccountMeta::new(*a_vault_lp, false),
+            AccountMeta::new(*b_vault_lp, false),
+            AccountMeta::new(*protocol_token_fee, false),
+            AccountMeta::new_readonly(*user, true),
+            AccountMeta::new_readonly(*vault_program, false),
+            AccountMeta::new_readonly(*token_program, false),
+        ],
+        data,
+    }
+}
+// The bonding curve settles in native SOL: the buyer pays from and the seller is paid into the user system account.
+pub fn create_pumpfun_buy_instruction<'a>(
+    program_id: &'a Pubkey,
+    global: &'a Pubkey,
+    fee_recipient: &'a Pubkey,
+    mint: &'a Pubkey,
+    bonding_curve: &'a Pubkey,
+    associated_bonding_curve: &'a Pubkey,
+    associated_user: &'a Pubkey,
+    user: &'a Pubkey,
+    event_authority: &'a Pubkey,
+    amount: u64,
+    max_sol_cost: u64,
+) -> Instruction {
+    let mut data = Vec::<u8>::with_capacity(24);
+    // sha256("global:buy")[..8]
+    const DESCRIMINATOR: [u8; 8] = [102, 6, 61, 18, 1, 218, 235, 234];
+    data.extend(DESCRIMINATOR);
+    data.extend(amount.to_le_bytes());
+    data.extend(max_sol_cost.to_le_bytes());
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(*global, false),
+            AccountMeta::new(*fee_recipient, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new(*bonding_curve, false),
+            AccountMeta::new(*associated_bonding_curve, false),
+            AccountMeta::new(*associated_user, false),
+            AccountMeta::new(*user, true),
+            AccountMeta::new_readonly(solana_program::system_program::ID, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new_readonly(solana_program::sysvar::rent::ID, false),
+            AccountMeta::new_readonly(*event_authority, false),
+            AccountMeta::new_readonly(*program_id, false),
+        ],
+        data,
+    }
+}
+pub fn create_pumpfun_sell_instruction<'a>(
+    program_id: &'a Pubkey,
+    global: &'a Pubkey,
+    fee_recipient: &'a Pubkey,
+    mint: &'a Pubkey,
+    bonding_curve: &'a Pubkey,
+    associated_bonding_curve: &'a Pubkey,
+    associated_user: &'a Pubkey,
+    user: &'a Pubkey,
+    event_authority: &'a Pubkey,
+    amount: u64,
+    min_sol_output: u64,
+) -> Instruction {
+    let mut data = Vec::<u8>::with_capacity(24);
+    // sha256("global:sell")[..8]
+    const DESCRIMINATOR: [u8; 8] = [51, 230, 133, 164, 1, 127, 131, 173];
+    data.extend(DESCRIMINATOR);
+    data.extend(amount.to_le_bytes());
+    data.extend(min_sol_output.to_le_bytes());
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(*global, false),
+            AccountMeta::new(*fee_recipient, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new(*bonding_curve, false),
+            AccountMeta::new(*associated_bonding_curve, false),
+            AccountMeta::new(*associated_user, false),
+            AccountMeta::new(*user, true),
+            AccountMeta::new_readonly(solana_program::system_program::ID, false),
+            AccountMeta::new_readonly(SPL_ASSOCIATED_TOKEN_ACCOUNT_ID, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new_readonly(*event_authority, false),
+            AccountMeta::new_readonly(*program_id, false),
+        ],
+        data,
+    }
+}
 pub fn create_pumpswap_buy_instruction<'a>(
     program_id: &'a Pubkey,
     pool: &'a Pubkey,
@@ -162,4 +495,43 @@ pub fn create_pumpswap_sell_instruction<'a>(
         ],
         data,
     }
-}
\ No newline at end of file
+}
+// spl-associated-token-account 6.0.0, get_associated_token_address_with_program_id
+pub fn get_associated_token_address<'a>(
+    wallet_address: &'a Pubkey,
+    token_mint_address: &'a Pubkey,
+    token_program_id: &'a Pubkey,
+) -> Pubkey {
+    Pubkey::find_program_address(
+        [
+            wallet_address.as_ref(),
+            token_program_id.as_ref(),
+            token_mint_address.as_ref(),
+        ]
+        .as_slice(),
+        &SPL_ASSOCIATED_TOKEN_ACCOUNT_ID,
+    )
+    .0
+}
+// spl-associated-token-account 6.0.0, instruction::create_associated_token_account_idempotent
+pub fn create_associated_token_account_idempotent_instruction<'a>(
+    funding_address: &'a Pubkey,
+    associated_token_address: &'a Pubkey,
+    wallet_address: &'a Pubkey,
+    token_mint_address: &'a Pubkey,
+    token_program_id: &'a Pubkey,
+) -> Instruction {
+    const DESCRIMINATOR: u8 = 1;
+    Instruction {
+        program_id: SPL_ASSOCIATED_TOKEN_ACCOUNT_ID,
+        accounts: vec![
+            AccountMeta::new(*funding_address, true),
+            AccountMeta::new(*associated_token_address, false),
+            AccountMeta::new_readonly(*wallet_address, false),
+            AccountMeta::new_readonly(*token_mint_address, false),
+            AccountMeta::new_readonly(solana_program::system_program::ID, false),
+            AccountMeta::new_readonly(*token_program_id, false),
+        ],
+        data: vec![DESCRIMINATOR],
+    }
+}
@@ -1,5 +1,5 @@
 use {
-    crate::SPL_ASSOCIATED_TOKEN_ACCOUNT_ID, solana_program::{
+    crate::{event::{Side, Venue}, state::Fees, SPL_ASSOCIATED_TOKEN_ACCOUNT_ID}, solana_program::{
         instruction::{
             AccountMeta,
             Instruction as Instruction_,
@@ -41,15 +41,170 @@ pub enum Instruction {
         base_amount_out: u64,
         maximum_quote_amount_in: u64,
         any_mint_token_account_pubkey_bump_seed: u8,
+        any_mint_token_account_authority_pubkey_bump_seed: u8,
     },
     SellOnPumpswap {
         input_token_amount: u64,
         minimum_output_token_amount: Option<u64>,
         any_mint_token_account_pubkey_bump_seed: u8,
+        any_mint_token_account_authority_pubkey_bump_seed: u8,
+        temporary_any_mint_token_account_pubkey_bump_seed: u8,
+        temporary_any_mint_token_account_authority_pubkey_bump_seed: u8,
+    },
+    ProposeManager,
+    ProposeTrader,
+    InitializeConfig {
+        fees: Fees,
+        config_pubkey_bump_seed: u8,
+        fee_vault_pubkey_bump_seed: u8,
+    },
+    UpdateConfig {
+        fees: Fees,
+    },
+    SweepFees,
+    BuyOnRaydiumExactOut {
+        maximum_amount_in: u64,
+        amount_out: u64,
+        any_mint_token_account_pubkey_bump_seed: u8,
+        any_mint_token_account_authority_pubkey_bump_seed: u8,
+    },
+    SellOnRaydiumExactOut {
+        maximum_amount_in: u64,
+        amount_out: u64,
+        any_mint_token_account_pubkey_bump_seed: u8,
+        any_mint_token_account_authority_pubkey_bump_seed: u8,
+        temporary_any_mint_token_account_pubkey_bump_seed: u8,
+        temporary_any_mint_token_account_authority_pubkey_bump_seed: u8,
+    },
+    SetRiskLimits {
+        maximum_lamports_per_trade: u64,
+        maximum_treasury_share_per_trade_basis_points: u16,
+        maximum_trades_per_slot_window: u64,
+        slot_window_length: u64,
+        risk_limits_pubkey_bump_seed: u8,
+    },
+    AddAllowedMint {
+        allowed_mints_pubkey_bump_seed: u8,
+    },
+    RemoveAllowedMint,
+    // The positions are followed by the trader seats.
+    CloseIntermediary {
+        any_mint_token_accounts_quantity: u8,
+    },
+    AcceptManager,
+    AcceptTrader,
+    CancelManagerProposal,
+    CancelTraderProposal,
+    AddTraderSeat {
+        allocated_lamports: u64,
+        trader_seat_pubkey_bump_seed: u8,
+    },
+    RemoveTraderSeat,
+    Pause,
+    Unpause,
+    MigrateIntermediary,
+    // Each leg is the position accounts followed by the accounts of its venue of `venues`.
+    LiquidateAll {
+        venues: Vec<Venue>,
+        minimum_output_token_amounts: Vec<u64>,
+    },
+    WithdrawToken {
+        amount: u64,
+    },
+    BuyOnRaydiumCpmm {
+        input_token_amount: u64,
+        minimum_output_token_amount: u64,
+        any_mint_token_account_pubkey_bump_seed: u8,
+        any_mint_token_account_authority_pubkey_bump_seed: u8,
+    },
+    SellOnRaydiumCpmm {
+        input_token_amount: u64,
+        minimum_output_token_amount: Option<u64>,
+        any_mint_token_account_pubkey_bump_seed: u8,
+        any_mint_token_account_authority_pubkey_bump_seed: u8,
+        temporary_any_mint_token_account_pubkey_bump_seed: u8,
+        temporary_any_mint_token_account_authority_pubkey_bump_seed: u8,
+    },
+    BuyOnRaydiumClmm {
+        input_token_amount: u64,
+        minimum_output_token_amount: u64,
+        any_mint_token_account_pubkey_bump_seed: u8,
+        any_mint_token_account_authority_pubkey_bump_seed: u8,
+    },
+    SellOnRaydiumClmm {
+        input_token_amount: u64,
+        minimum_output_token_amount: Option<u64>,
+        any_mint_token_account_pubkey_bump_seed: u8,
+        any_mint_token_account_authority_pubkey_bump_seed: u8,
+        temporary_any_mint_token_account_pubkey_bump_seed: u8,
+        temporary_any_mint_token_account_authority_pubkey_bump_seed: u8,
+    },
+    BuyOnMeteoraDlmm {
+        input_token_amount: u64,
+        minimum_output_token_amount: u64,
+        any_mint_token_account_pubkey_bump_seed: u8,
+        any_mint_token_account_authority_pubkey_bump_seed: u8,
+    },
+    SellOnMeteoraDlmm {
+        input_token_amount: u64,
+        minimum_output_token_amount: Option<u64>,
+        any_mint_token_account_pubkey_bump_seed: u8,
+        any_mint_token_account_authority_pubkey_bump_seed: u8,
+        temporary_any_mint_token_account_pubkey_bump_seed: u8,
+        temporary_any_mint_token_account_authority_pubkey_bump_seed: u8,
+    },
+    BuyOnMeteoraDynamicAmm {
+        input_token_amount: u64,
+        minimum_output_token_amount: u64,
+        any_mint_token_account_pubkey_bump_seed: u8,
+        any_mint_token_account_authority_pubkey_bump_seed: u8,
+    },
+    SellOnMeteoraDynamicAmm {
+        input_token_amount: u64,
+        minimum_output_token_amount: Option<u64>,
+        any_mint_token_account_pubkey_bump_seed: u8,
+        any_mint_token_account_authority_pubkey_bump_seed: u8,
+        temporary_any_mint_token_account_pubkey_bump_seed: u8,
+        temporary_any_mint_token_account_authority_pubkey_bump_seed: u8,
+    },
+    BuyOnPumpfunCurve {
+        token_amount_out: u64,
+        maximum_lamports_in: u64,
+        any_mint_token_account_pubkey_bump_seed: u8,
+        any_mint_token_account_authority_pubkey_bump_seed: u8,
+    },
+    SellOnPumpfunCurve {
+        input_token_amount: u64,
+        minimum_output_token_amount: Option<u64>,
+        any_mint_token_account_pubkey_bump_seed: u8,
+        any_mint_token_account_authority_pubkey_bump_seed: u8,
+        temporary_any_mint_token_account_pubkey_bump_seed: u8,
+        temporary_any_mint_token_account_authority_pubkey_bump_seed: u8,
+    },
+    // Venue-agnostic swap: the intermediary accounts are followed by the accounts of `venue`.
+    Swap {
+        venue: Venue,
+        side: Side,
+        amount_in: u64,
+        min_out: u64,
+        any_mint_token_account_pubkey_bump_seed: u8,
+        any_mint_token_account_authority_pubkey_bump_seed: u8,
         temporary_any_mint_token_account_pubkey_bump_seed: u8,
+        temporary_any_mint_token_account_authority_pubkey_bump_seed: u8,
+    },
+    // Routed swap: the intermediary accounts are followed by a mint and a token account per intermediate token, then by the accounts of each venue of `venues`.
+    RoutedSwap {
+        side: Side,
+        venues: Vec<Venue>,
+        amount_in: u64,
+        min_out: u64,
+        any_mint_token_account_pubkey_bump_seed: u8,
+        any_mint_token_account_authority_pubkey_bump_seed: u8,
+        intermediate_token_account_pubkey_bump_seeds: Vec<u8>,
+    },
+    SetMaximumPriceDeviation {
+        maximum_price_deviation_basis_points: u16,
     },
-    ChangeManager,
-    ChangeTrader,
 }
 impl Instruction {
     pub fn initialize<'a>(
@@ -62,6 +217,8 @@ impl Instruction {
         w_sol_token_account_authority_pubkey: &'a Pubkey,
         temporary_w_sol_token_account_pubkey: &'a Pubkey,
         temporary_w_sol_token_account_authority_pubkey: &'a Pubkey,
+        config_pubkey: &'a Pubkey,
+        fee_vault_pubkey: &'a Pubkey,
         lamports_to_treasury: u64,
         w_sol_token_account_pubkey_bump_seed: u8,
         w_sol_token_account_authority_pubkey_bump_seed: u8,
@@ -72,7 +229,7 @@ impl Instruction {
             Instruction_ {
                 program_id: *program_id_pubkey,
                 accounts: vec![
-                    AccountMeta::new_readonly(*intermediary_investor_pubkey, true),
+                    AccountMeta::new(*intermediary_investor_pubkey, true),
                     AccountMeta::new(*intermediary_pubkey, true),
                     AccountMeta::new_readonly(*intermediary_manager_pubkey, false),
                     AccountMeta::new_readonly(*intermediary_trader_pubkey, false),
@@ -84,6 +241,8 @@ impl Instruction {
                     AccountMeta::new_readonly(solana_program::system_program::ID, false),
                     AccountMeta::new_readonly(solana_program::sysvar::rent::ID, false),
                     AccountMeta::new_readonly(spl_token::ID, false),
+                    AccountMeta::new_readonly(*config_pubkey, false),
+                    AccountMeta::new(*fee_vault_pubkey, false),
                 ],
                 data: borsh::to_vec(
                     &Self::Initialize {
@@ -102,6 +261,8 @@ impl Instruction {
         intermediary_investor_pubkey: &'a Pubkey,
         intermediary_pubkey: &'a Pubkey,
         w_sol_token_account_pubkey: &'a Pubkey,
+        config_pubkey: &'a Pubkey,
+        fee_vault_pubkey: &'a Pubkey,
         lamports_to_treasury: u64,
     ) -> Result<Instruction_, Box<dyn Error + 'static>> {
         Ok(
@@ -113,6 +274,8 @@ impl Instruction {
                     AccountMeta::new(*w_sol_token_account_pubkey, false),
                     AccountMeta::new_readonly(solana_program::system_program::ID, false),
                     AccountMeta::new_readonly(spl_token::ID, false),
+                    AccountMeta::new_readonly(*config_pubkey, false),
+                    AccountMeta::new(*fee_vault_pubkey, false),
                 ],
                 data: borsh::to_vec(
                     &Self::DepositFunds {
@@ -130,6 +293,8 @@ impl Instruction {
         w_sol_token_account_authority_pubkey: &'a Pubkey,
         temporary_w_sol_token_account_pubkey: &'a Pubkey,
         temporary_w_sol_token_account_authority_pubkey: &'a Pubkey,
+        config_pubkey: &'a Pubkey,
+        fee_vault_pubkey: &'a Pubkey,
         lamports_from_treasury: u64,
     ) -> Result<Instruction_, Box<dyn Error + 'static>> {
         Ok(
@@ -146,6 +311,8 @@ impl Instruction {
                     AccountMeta::new_readonly(solana_program::system_program::ID, false),
                     AccountMeta::new_readonly(solana_program::sysvar::rent::ID, false),
                     AccountMeta::new_readonly(spl_token::ID, false),
+                    AccountMeta::new_readonly(*config_pubkey, false),
+                    AccountMeta::new(*fee_vault_pubkey, false),
                 ],
                 data: borsh::to_vec(
                     &Self::WithdrawFunds {
@@ -180,6 +347,11 @@ impl Instruction {
         market_coin_vault_pubkey: &'a Pubkey,
         market_pc_vault_pubkey: &'a Pubkey,
         market_vault_signer_pubkey: &'a Pubkey,
+        allowed_mints_pubkey: &'a Pubkey,
+        risk_limits_pubkey: &'a Pubkey,
+        trader_seat_pubkey: &'a Pubkey,
+        config_pubkey: &'a Pubkey,
+        fee_vault_pubkey: &'a Pubkey,
         input_token_amount: u64,
         minimum_output_token_amount: u64,
         any_mint_token_account_pubkey_bump_seed: u8,
@@ -190,7 +362,7 @@ impl Instruction {
                 program_id: *program_id_pubkey,
                 accounts: vec![
                     AccountMeta::new(*intermediary_trader_pubkey, true),
-                    AccountMeta::new_readonly(*intermediary_pubkey, false),
+                    AccountMeta::new(*intermediary_pubkey, false),
                     AccountMeta::new(*w_sol_token_account_pubkey, false),
                     AccountMeta::new_readonly(*w_sol_token_account_authority_pubkey, false),
                     AccountMeta::new(*temporary_w_sol_token_account_pubkey, false),
@@ -216,6 +388,11 @@ impl Instruction {
                     AccountMeta::new(*market_coin_vault_pubkey, false),
                     AccountMeta::new(*market_pc_vault_pubkey, false),
                     AccountMeta::new_readonly(*market_vault_signer_pubkey, false),
+                    AccountMeta::new_readonly(*allowed_mints_pubkey, false),
+                    AccountMeta::new(*risk_limits_pubkey, false),
+                    AccountMeta::new(*trader_seat_pubkey, false),
+                    AccountMeta::new_readonly(*config_pubkey, false),
+                    AccountMeta::new(*fee_vault_pubkey, false),
                 ],
                 data: borsh::to_vec(
                     &Self::BuyOnRaydium {
@@ -252,6 +429,10 @@ impl Instruction {
         market_coin_vault_pubkey: &'a Pubkey,
         market_pc_vault_pubkey: &'a Pubkey,
         market_vault_signer_pubkey: &'a Pubkey,
+        risk_limits_pubkey: &'a Pubkey,
+        trader_seat_pubkey: &'a Pubkey,
+        config_pubkey: &'a Pubkey,
+        fee_vault_pubkey: &'a Pubkey,
         input_token_amount: u64,
         minimum_output_token_amount: Option<u64>,
         any_mint_token_account_pubkey_bump_seed: u8,
@@ -264,7 +445,7 @@ impl Instruction {
                 program_id: *program_id_pubkey,
                 accounts: vec![
                     AccountMeta::new(*intermediary_trader_pubkey, true),
-                    AccountMeta::new_readonly(*intermediary_pubkey, false),
+                    AccountMeta::new(*intermediary_pubkey, false),
                     AccountMeta::new(*w_sol_token_account_pubkey, false),
                     AccountMeta::new(*any_mint_token_account_pubkey, false),
                     AccountMeta::new_readonly(*any_mint_token_account_authority_pubkey, false),
@@ -288,6 +469,10 @@ impl Instruction {
                     AccountMeta::new(*market_coin_vault_pubkey, false),
                     AccountMeta::new(*market_pc_vault_pubkey, false),
                     AccountMeta::new_readonly(*market_vault_signer_pubkey, false),
+                    AccountMeta::new(*risk_limits_pubkey, false),
+                    AccountMeta::new(*trader_seat_pubkey, false),
+                    AccountMeta::new_readonly(*config_pubkey, false),
+                    AccountMeta::new(*fee_vault_pubkey, false),
                 ],
                 data: borsh::to_vec(
                     &Self::SellOnRaydium {
@@ -309,7 +494,9 @@ impl Instruction {
         w_sol_token_account_pubkey: &'a Pubkey,
         w_sol_token_account_authority_pubkey: &'a Pubkey,
         temporary_w_sol_token_account_pubkey: &'a Pubkey,
+        temporary_w_sol_token_account_authority_pubkey: &'a Pubkey,
         any_mint_token_account_pubkey: &'a Pubkey,
+        any_mint_token_account_authority_pubkey: &'a Pubkey,
         any_mint_token_mint_account_pubkey: &'a Pubkey,
         pumpfan_program_id_pubkey: &'a Pubkey,
         pool_pubkey: &'a Pubkey,
@@ -321,20 +508,28 @@ impl Instruction {
         base_token_program_pubkey: &'a Pubkey,
         quote_token_program_pubkey: &'a Pubkey,
         event_authority_pubkey: &'a Pubkey,
+        allowed_mints_pubkey: &'a Pubkey,
+        risk_limits_pubkey: &'a Pubkey,
+        trader_seat_pubkey: &'a Pubkey,
+        config_pubkey: &'a Pubkey,
+        fee_vault_pubkey: &'a Pubkey,
         base_amount_out: u64,
         maximum_quote_amount_in: u64,
         any_mint_token_account_pubkey_bump_seed: u8,
+        any_mint_token_account_authority_pubkey_bump_seed: u8,
     ) -> Result<Instruction_, Box<dyn Error + 'static>> {
         Ok(
             Instruction_ {
                 program_id: *program_id_pubkey,
                 accounts: vec![
                     AccountMeta::new(*intermediary_trader_pubkey, true),
-                    AccountMeta::new_readonly(*intermediary_pubkey, false),
+                    AccountMeta::new(*intermediary_pubkey, false),
                     AccountMeta::new(*w_sol_token_account_pubkey, false),
                     AccountMeta::new_readonly(*w_sol_token_account_authority_pubkey, false),
                     AccountMeta::new(*temporary_w_sol_token_account_pubkey, false),
+                    AccountMeta::new(*temporary_w_sol_token_account_authority_pubkey, false),
                     AccountMeta::new(*any_mint_token_account_pubkey, false),
+                    AccountMeta::new(*any_mint_token_account_authority_pubkey, false),
                     AccountMeta::new_readonly(spl_token::native_mint::ID, false),
                     AccountMeta::new_readonly(*any_mint_token_mint_account_pubkey, false),
                     AccountMeta::new_readonly(solana_program::system_program::ID, false),
@@ -351,12 +546,18 @@ impl Instruction {
                     AccountMeta::new_readonly(*quote_token_program_pubkey, false),
                     AccountMeta::new_readonly(SPL_ASSOCIATED_TOKEN_ACCOUNT_ID, false),
                     AccountMeta::new_readonly(*event_authority_pubkey, false),
+                    AccountMeta::new_readonly(*allowed_mints_pubkey, false),
+                    AccountMeta::new(*risk_limits_pubkey, false),
+                    AccountMeta::new(*trader_seat_pubkey, false),
+                    AccountMeta::new_readonly(*config_pubkey, false),
+                    AccountMeta::new(*fee_vault_pubkey, false),
                 ],
                 data: borsh::to_vec(
                     &Self::BuyOnPumpswap {
                         base_amount_out,
                         maximum_quote_amount_in,
                         any_mint_token_account_pubkey_bump_seed,
+                        any_mint_token_account_authority_pubkey_bump_seed,
                     },
                 )?,
             },
@@ -369,7 +570,9 @@ impl Instruction {
         w_sol_token_account_pubkey: &'a Pubkey,
         temporary_w_sol_token_account_pubkey: &'a Pubkey,
         any_mint_token_account_pubkey: &'a Pubkey,
+        any_mint_token_account_authority_pubkey: &'a Pubkey,
         temporary_any_mint_token_account_pubkey: &'a Pubkey,
+        temporary_any_mint_token_account_authority_pubkey: &'a Pubkey,
         any_mint_token_mint_account_pubkey: &'a Pubkey,
         pumpfan_program_id_pubkey: &'a Pubkey,
         pool_pubkey: &'a Pubkey,
@@ -381,23 +584,31 @@ impl Instruction {
         base_token_program_pubkey: &'a Pubkey,
         quote_token_program_pubkey: &'a Pubkey,
         event_authority_pubkey: &'a Pubkey,
+        risk_limits_pubkey: &'a Pubkey,
+        trader_seat_pubkey: &'a Pubkey,
+        config_pubkey: &'a Pubkey,
+        fee_vault_pubkey: &'a Pubkey,
         input_token_amount: u64,
         minimum_output_token_amount: Option<u64>,
         any_mint_token_account_pubkey_bump_seed: u8,
+        any_mint_token_account_authority_pubkey_bump_seed: u8,
         temporary_any_mint_token_account_pubkey_bump_seed: u8,
+        temporary_any_mint_token_account_authority_pubkey_bump_seed: u8,
     ) -> Result<Instruction_, Box<dyn Error + 'static>> {
         Ok(
             Instruction_ {
                 program_id: *program_id_pubkey,
                 accounts: vec![
                     AccountMeta::new(*intermediary_trader_pubkey, true),
-                    AccountMeta::new_readonly(*intermediary_pubkey, false),
+                    AccountMeta::new(*intermediary_pubkey, false),
                     AccountMeta::new(*w_sol_token_account_pubkey, false),
                     AccountMeta::new(*temporary_w_sol_token_account_pubkey, false),
                     AccountMeta::new(*any_mint_token_account_pubkey, false),
+                    AccountMeta::new(*any_mint_token_account_authority_pubkey, false),
                     AccountMeta::new(*temporary_any_mint_token_account_pubkey, false),
+                    AccountMeta::new(*temporary_any_mint_token_account_authority_pubkey, false),
                     AccountMeta::new_readonly(spl_token::native_mint::ID, false),
-                    AccountMeta::new_readonly(*any_mint_token_mint_account_pubkey, false),
+                    AccountMeta::new(*any_mint_token_mint_account_pubkey, false),
                     AccountMeta::new_readonly(solana_program::system_program::ID, false),
                     AccountMeta::new_readonly(solana_program::sysvar::rent::ID, false),
                     AccountMeta::new_readonly(spl_token::ID, false),
@@ -412,54 +623,1597 @@ impl Instruction {
                     AccountMeta::new_readonly(*quote_token_program_pubkey, false),
                     AccountMeta::new_readonly(SPL_ASSOCIATED_TOKEN_ACCOUNT_ID, false),
                     AccountMeta::new_readonly(*event_authority_pubkey, false),
+                    AccountMeta::new(*risk_limits_pubkey, false),
+                    AccountMeta::new(*trader_seat_pubkey, false),
+                    AccountMeta::new_readonly(*config_pubkey, false),
+                    AccountMeta::new(*fee_vault_pubkey, false),
                 ],
                 data: borsh::to_vec(
                     &Self::SellOnPumpswap {
                         input_token_amount,
                         minimum_output_token_amount,
                         any_mint_token_account_pubkey_bump_seed,
+                        any_mint_token_account_authority_pubkey_bump_seed,
                         temporary_any_mint_token_account_pubkey_bump_seed,
+                        temporary_any_mint_token_account_authority_pubkey_bump_seed,
                     },
                 )?,
             },
         )
     }
-    pub fn change_manager<'a>(
+    pub fn propose_manager<'a>(
         program_id_pubkey: &'a Pubkey,
         intermediary_investor_pubkey: &'a Pubkey,
         intermediary_pubkey: &'a Pubkey,
         intermediary_manager_pubkey: &'a Pubkey,
+        config_pubkey: &'a Pubkey,
+        fee_vault_pubkey: &'a Pubkey,
     ) -> Result<Instruction_, Box<dyn Error + 'static>> {
         Ok(
             Instruction_ {
                 program_id: *program_id_pubkey,
                 accounts: vec![
-                    AccountMeta::new_readonly(*intermediary_investor_pubkey, true),
+                    AccountMeta::new(*intermediary_investor_pubkey, true),
                     AccountMeta::new(*intermediary_pubkey, false),
                     AccountMeta::new_readonly(*intermediary_manager_pubkey, false),
                     AccountMeta::new_readonly(solana_program::system_program::ID, false),
+                    AccountMeta::new_readonly(*config_pubkey, false),
+                    AccountMeta::new(*fee_vault_pubkey, false),
                 ],
-                data: borsh::to_vec(&Self::ChangeManager)?,
+                data: borsh::to_vec(&Self::ProposeManager)?,
             },
         )
     }
-    pub fn change_trader<'a>(
+    pub fn propose_trader<'a>(
         program_id_pubkey: &'a Pubkey,
         intermediary_manager: &'a Pubkey,
         intermediary_pubkey: &'a Pubkey,
         intermediary_trader_pubkey: &'a Pubkey,
+        config_pubkey: &'a Pubkey,
+        fee_vault_pubkey: &'a Pubkey,
     ) -> Result<Instruction_, Box<dyn Error + 'static>> {
         Ok(
             Instruction_ {
                 program_id: *program_id_pubkey,
                 accounts: vec![
-                    AccountMeta::new_readonly(*intermediary_manager, true),
+                    AccountMeta::new(*intermediary_manager, true),
                     AccountMeta::new(*intermediary_pubkey, false),
                     AccountMeta::new_readonly(*intermediary_trader_pubkey, false),
                     AccountMeta::new_readonly(solana_program::system_program::ID, false),
+                    AccountMeta::new_readonly(*config_pubkey, false),
+                    AccountMeta::new(*fee_vault_pubkey, false),
+                ],
+                data: borsh::to_vec(&Self::ProposeTrader)?,
+            },
+        )
+    }
+    pub fn initialize_config<'a>(
+        program_id_pubkey: &'a Pubkey,
+        administrator_pubkey: &'a Pubkey,
+        config_pubkey: &'a Pubkey,
+        fee_vault_pubkey: &'a Pubkey,
+        fee_recipient_pubkey: &'a Pubkey,
+        program_data_pubkey: &'a Pubkey,
+        fees: Fees,
+        config_pubkey_bump_seed: u8,
+        fee_vault_pubkey_bump_seed: u8,
+    ) -> Result<Instruction_, Box<dyn Error + 'static>> {
+        Ok(
+            Instruction_ {
+                program_id: *program_id_pubkey,
+                accounts: vec![
+                    AccountMeta::new(*administrator_pubkey, true),
+                    AccountMeta::new(*config_pubkey, false),
+                    AccountMeta::new(*fee_vault_pubkey, false),
+                    AccountMeta::new_readonly(*fee_recipient_pubkey, false),
+                    AccountMeta::new_readonly(*program_data_pubkey, false),
+                    AccountMeta::new_readonly(solana_program::system_program::ID, false),
+                    AccountMeta::new_readonly(solana_program::sysvar::rent::ID, false),
+                ],
+                data: borsh::to_vec(
+                    &Self::InitializeConfig {
+                        fees,
+                        config_pubkey_bump_seed,
+                        fee_vault_pubkey_bump_seed,
+                    },
+                )?,
+            },
+        )
+    }
+    pub fn update_config<'a>(
+        program_id_pubkey: &'a Pubkey,
+        administrator_pubkey: &'a Pubkey,
+        config_pubkey: &'a Pubkey,
+        fee_recipient_pubkey: &'a Pubkey,
+        fees: Fees,
+    ) -> Result<Instruction_, Box<dyn Error + 'static>> {
+        Ok(
+            Instruction_ {
+                program_id: *program_id_pubkey,
+                accounts: vec![
+                    AccountMeta::new_readonly(*administrator_pubkey, true),
+                    AccountMeta::new(*config_pubkey, false),
+                    AccountMeta::new_readonly(*fee_recipient_pubkey, false),
+                ],
+                data: borsh::to_vec(
+                    &Self::UpdateConfig {
+                        fees,
+                    },
+                )?,
+            },
+        )
+    }
+    pub fn sweep_fees<'a>(
+        program_id_pubkey: &'a Pubkey,
+        administrator_pubkey: &'a Pubkey,
+        config_pubkey: &'a Pubkey,
+        fee_vault_pubkey: &'a Pubkey,
+        fee_recipient_pubkey: &'a Pubkey,
+    ) -> Result<Instruction_, Box<dyn Error + 'static>> {
+        Ok(
+            Instruction_ {
+                program_id: *program_id_pubkey,
+                accounts: vec![
+                    AccountMeta::new_readonly(*administrator_pubkey, true),
+                    AccountMeta::new_readonly(*config_pubkey, false),
+                    AccountMeta::new(*fee_vault_pubkey, false),
+                    AccountMeta::new(*fee_recipient_pubkey, false),
+                    AccountMeta::new_readonly(solana_program::sysvar::rent::ID, false),
+                ],
+                data: borsh::to_vec(&Self::SweepFees)?,
+            },
+        )
+    }
+    pub fn buy_on_raydium_exact_out<'a>(
+        program_id_pubkey: &'a Pubkey,
+        intermediary_trader_pubkey: &'a Pubkey,
+        intermediary_pubkey: &'a Pubkey,
+        w_sol_token_account_pubkey: &'a Pubkey,
+        w_sol_token_account_authority_pubkey: &'a Pubkey,
+        temporary_w_sol_token_account_pubkey: &'a Pubkey,
+        temporary_w_sol_token_account_authority_pubkey: &'a Pubkey,
+        any_mint_token_account_pubkey: &'a Pubkey,
+        any_mint_token_account_authority_pubkey: &'a Pubkey,
+        any_mint_token_mint_account_pubkey: &'a Pubkey,
+        amm_programm_id_pubkey: &'a Pubkey,
+        amm_pool_pubkey: &'a Pubkey,
+        amm_authority_pubkey: &'a Pubkey,
+        amm_open_orders_pubkey: &'a Pubkey,
+        amm_coin_vault_pubkey: &'a Pubkey,
+        amm_pc_vault_pubkey: &'a Pubkey,
+        market_program_id_pubkey: &'a Pubkey,
+        market_pubkey: &'a Pubkey,
+        market_bids_pubkey: &'a Pubkey,
+        market_asks_pubkey: &'a Pubkey,
+        market_event_queue_pubkey: &'a Pubkey,
+        market_coin_vault_pubkey: &'a Pubkey,
+        market_pc_vault_pubkey: &'a Pubkey,
+        market_vault_signer_pubkey: &'a Pubkey,
+        allowed_mints_pubkey: &'a Pubkey,
+        risk_limits_pubkey: &'a Pubkey,
+        trader_seat_pubkey: &'a Pubkey,
+        config_pubkey: &'a Pubkey,
+        fee_vault_pubkey: &'a Pubkey,
+        maximum_amount_in: u64,
+        amount_out: u64,
+        any_mint_token_account_pubkey_bump_seed: u8,
+        any_mint_token_account_authority_pubkey_bump_seed: u8,
+    ) -> Result<Instruction_, Box<dyn Error + 'static>> {
+        Ok(
+            Instruction_ {
+                program_id: *program_id_pubkey,
+                accounts: vec![
+                    AccountMeta::new(*intermediary_trader_pubkey, true),
+                    AccountMeta::new(*intermediary_pubkey, false),
+                    AccountMeta::new(*w_sol_token_account_pubkey, false),
+                    AccountMeta::new_readonly(*w_sol_token_account_authority_pubkey, false),
+                    AccountMeta::new(*temporary_w_sol_token_account_pubkey, false),
+                    AccountMeta::new_readonly(*temporary_w_sol_token_account_authority_pubkey, false),
+                    AccountMeta::new(*any_mint_token_account_pubkey, false),
+                    AccountMeta::new_readonly(*any_mint_token_account_authority_pubkey, false),
+                    AccountMeta::new_readonly(spl_token::native_mint::ID, false),
+                    AccountMeta::new_readonly(*any_mint_token_mint_account_pubkey, false),
+                    AccountMeta::new_readonly(solana_program::system_program::ID, false),
+                    AccountMeta::new_readonly(solana_program::sysvar::rent::ID, false),
+                    AccountMeta::new_readonly(spl_token::ID, false),
+                    AccountMeta::new_readonly(*amm_programm_id_pubkey, false),
+                    AccountMeta::new(*amm_pool_pubkey, false),
+                    AccountMeta::new_readonly(*amm_authority_pubkey, false),
+                    AccountMeta::new(*amm_open_orders_pubkey, false),
+                    AccountMeta::new(*amm_coin_vault_pubkey, false),
+                    AccountMeta::new(*amm_pc_vault_pubkey, false),
+                    AccountMeta::new_readonly(*market_program_id_pubkey, false),
+                    AccountMeta::new(*market_pubkey, false),
+                    AccountMeta::new(*market_bids_pubkey, false),
+                    AccountMeta::new(*market_asks_pubkey, false),
+                    AccountMeta::new(*market_event_queue_pubkey, false),
+                    AccountMeta::new(*market_coin_vault_pubkey, false),
+                    AccountMeta::new(*market_pc_vault_pubkey, false),
+                    AccountMeta::new_readonly(*market_vault_signer_pubkey, false),
+                    AccountMeta::new_readonly(*allowed_mints_pubkey, false),
+                    AccountMeta::new(*risk_limits_pubkey, false),
+                    AccountMeta::new(*trader_seat_pubkey, false),
+                    AccountMeta::new_readonly(*config_pubkey, false),
+                    AccountMeta::new(*fee_vault_pubkey, false),
                 ],
-                data: borsh::to_vec(&Self::ChangeTrader)?,
+                data: borsh::to_vec(
+                    &Self::BuyOnRaydiumExactOut {
+                        maximum_amount_in,
+                        amount_out,
+                        any_mint_token_account_pubkey_bump_seed,
+                        any_mint_token_account_authority_pubkey_bump_seed,
+                    },
+                )?,
             },
         )
     }
+    pub fn sell_on_raydium_exact_out<'a>(
+        program_id_pubkey: &'a Pubkey,
+        intermediary_trader_pubkey: &'a Pubkey,
+        intermediary_pubkey: &'a Pubkey,
+        w_sol_token_account_pubkey: &'a Pubkey,
+        any_mint_token_account_pubkey: &'a Pubkey,
+        any_mint_token_account_authority_pubkey: &'a Pubkey,
+        temporary_any_mint_token_account_pubkey: &'a Pubkey,
+        temporary_any_mint_token_account_authority_pubkey: &'a Pubkey,
+        any_mint_token_mint_account_pubkey: &'a Pubkey,
+        amm_programm_id_pubkey: &'a Pubkey,
+        amm_pool_pubkey: &'a Pubkey,
+        amm_authority_pubkey: &'a Pubkey,
+        amm_open_orders_pubkey: &'a Pubkey,
+        amm_coin_vault_pubkey: &'a Pubkey,
+        amm_pc_vault_pubkey: &'a Pubkey,
+        market_program_id_pubkey: &'a Pubkey,
+        market_pubkey: &'a Pubkey,
+        market_bids_pubkey: &'a Pubkey,
+        market_asks_pubkey: &'a Pubkey,
+        market_event_queue_pubkey: &'a Pubkey,
+        market_coin_vault_pubkey: &'a Pubkey,
+        market_pc_vault_pubkey: &'a Pubkey,
+        market_vault_signer_pubkey: &'a Pubkey,
+        risk_limits_pubkey: &'a Pubkey,
+        trader_seat_pubkey: &'a Pubkey,
+        config_pubkey: &'a Pubkey,
+        fee_vault_pubkey: &'a Pubkey,
+        maximum_amount_in: u64,
+        amount_out: u64,
+        any_mint_token_account_pubkey_bump_seed: u8,
+        any_mint_token_account_authority_pubkey_bump_seed: u8,
+        temporary_any_mint_token_account_pubkey_bump_seed: u8,
+        temporary_any_mint_token_account_authority_pubkey_bump_seed: u8,
+    ) -> Result<Instruction_, Box<dyn Error + 'static>> {
+        Ok(
+            Instruction_ {
+                program_id: *program_id_pubkey,
+                accounts: vec![
+                    AccountMeta::new(*intermediary_trader_pubkey, true),
+                    AccountMeta::new(*intermediary_pubkey, false),
+                    AccountMeta::new(*w_sol_token_account_pubkey, false),
+                    AccountMeta::new(*any_mint_token_account_pubkey, false),
+                    AccountMeta::new_readonly(*any_mint_token_account_authority_pubkey, false),
+                    AccountMeta::new(*temporary_any_mint_token_account_pubkey, false),
+                    AccountMeta::new_readonly(*temporary_any_mint_token_account_authority_pubkey, false),
+                    AccountMeta::new_readonly(*any_mint_token_mint_account_pubkey, false),
+                    AccountMeta::new_readonly(solana_program::system_program::ID, false),
+                    AccountMeta::new_readonly(solana_program::sysvar::rent::ID, false),
+                    AccountMeta::new_readonly(spl_token::ID, false),
+                    AccountMeta::new_readonly(*amm_programm_id_pubkey, false),
+                    AccountMeta::new(*amm_pool_pubkey, false),
+                    AccountMeta::new_readonly(*amm_authority_pubkey, false),
+                    AccountMeta::new(*amm_open_orders_pubkey, false),
+                    AccountMeta::new(*amm_coin_vault_pubkey, false),
+                    AccountMeta::new(*amm_pc_vault_pubkey, false),
+                    AccountMeta::new_readonly(*market_program_id_pubkey, false),
+                    AccountMeta::new(*market_pubkey, false),
+                    AccountMeta::new(*market_bids_pubkey, false),
+                    AccountMeta::new(*market_asks_pubkey, false),
+                    AccountMeta::new(*market_event_queue_pubkey, false),
+                    AccountMeta::new(*market_coin_vault_pubkey, false),
+                    AccountMeta::new(*market_pc_vault_pubkey, false),
+                    AccountMeta::new_readonly(*market_vault_signer_pubkey, false),
+                    AccountMeta::new(*risk_limits_pubkey, false),
+                    AccountMeta::new(*trader_seat_pubkey, false),
+                    AccountMeta::new_readonly(*config_pubkey, false),
+                    AccountMeta::new(*fee_vault_pubkey, false),
+                ],
+                data: borsh::to_vec(
+                    &Self::SellOnRaydiumExactOut {
+                        maximum_amount_in,
+                        amount_out,
+                        any_mint_token_account_pubkey_bump_seed,
+                        any_mint_token_account_authority_pubkey_bump_seed,
+                        temporary_any_mint_token_account_pubkey_bump_seed,
+                        temporary_any_mint_token_account_authority_pubkey_bump_seed,
+                    },
+                )?,
+            },
+        )
+    }
+    pub fn set_risk_limits<'a>(
+        program_id_pubkey: &'a Pubkey,
+        intermediary_manager_pubkey: &'a Pubkey,
+        intermediary_pubkey: &'a Pubkey,
+        risk_limits_pubkey: &'a Pubkey,
+        maximum_lamports_per_trade: u64,
+        maximum_treasury_share_per_trade_basis_points: u16,
+        maximum_trades_per_slot_window: u64,
+        slot_window_length: u64,
+        risk_limits_pubkey_bump_seed: u8,
+    ) -> Result<Instruction_, Box<dyn Error + 'static>> {
+        Ok(
+            Instruction_ {
+                program_id: *program_id_pubkey,
+                accounts: vec![
+                    AccountMeta::new(*intermediary_manager_pubkey, true),
+                    AccountMeta::new_readonly(*intermediary_pubkey, false),
+                    AccountMeta::new(*risk_limits_pubkey, false),
+                    AccountMeta::new_readonly(solana_program::system_program::ID, false),
+                    AccountMeta::new_readonly(solana_program::sysvar::rent::ID, false),
+                ],
+                data: borsh::to_vec(
+                    &Self::SetRiskLimits {
+                        maximum_lamports_per_trade,
+                        maximum_treasury_share_per_trade_basis_points,
+                        maximum_trades_per_slot_window,
+                        slot_window_length,
+                        risk_limits_pubkey_bump_seed,
+                    },
+                )?,
+            },
+        )
+    }
+    pub fn add_allowed_mint<'a>(
+        program_id_pubkey: &'a Pubkey,
+        intermediary_manager_pubkey: &'a Pubkey,
+        intermediary_pubkey: &'a Pubkey,
+        allowed_mints_pubkey: &'a Pubkey,
+        any_mint_token_mint_account_pubkey: &'a Pubkey,
+        allowed_mints_pubkey_bump_seed: u8,
+    ) -> Result<Instruction_, Box<dyn Error + 'static>> {
+        Ok(
+            Instruction_ {
+                program_id: *program_id_pubkey,
+                accounts: vec![
+                    AccountMeta::new(*intermediary_manager_pubkey, true),
+                    AccountMeta::new_readonly(*intermediary_pubkey, false),
+                    AccountMeta::new(*allowed_mints_pubkey, false),
+                    AccountMeta::new_readonly(*any_mint_token_mint_account_pubkey, false),
+                    AccountMeta::new_readonly(solana_program::system_program::ID, false),
+                    AccountMeta::new_readonly(solana_program::sysvar::rent::ID, false),
+                ],
+                data: borsh::to_vec(
+                    &Self::AddAllowedMint {
+                        allowed_mints_pubkey_bump_seed,
+                    },
+                )?,
+            },
+        )
+    }
+    pub fn remove_allowed_mint<'a>(
+        program_id_pubkey: &'a Pubkey,
+        intermediary_manager_pubkey: &'a Pubkey,
+        intermediary_pubkey: &'a Pubkey,
+        allowed_mints_pubkey: &'a Pubkey,
+        any_mint_token_mint_account_pubkey: &'a Pubkey,
+    ) -> Result<Instruction_, Box<dyn Error + 'static>> {
+        Ok(
+            Instruction_ {
+                program_id: *program_id_pubkey,
+                accounts: vec![
+                    AccountMeta::new(*intermediary_manager_pubkey, true),
+                    AccountMeta::new_readonly(*intermediary_pubkey, false),
+                    AccountMeta::new(*allowed_mints_pubkey, false),
+                    AccountMeta::new_readonly(*any_mint_token_mint_account_pubkey, false),
+                    AccountMeta::new_readonly(solana_program::sysvar::rent::ID, false),
+                ],
+                data: borsh::to_vec(&Self::RemoveAllowedMint)?,
+            },
+        )
+    }
+    pub fn close_intermediary<'a>(
+        program_id_pubkey: &'a Pubkey,
+        intermediary_investor_pubkey: &'a Pubkey,
+        intermediary_pubkey: &'a Pubkey,
+        w_sol_token_account_pubkey: &'a Pubkey,
+        w_sol_token_account_authority_pubkey: &'a Pubkey,
+        risk_limits_pubkey: &'a Pubkey,
+        allowed_mints_pubkey: &'a Pubkey,
+        any_mint_token_accounts: &'a [AnyMintTokenAccountPubkeys],
+        trader_seat_pubkeys: &'a [Pubkey],
+    ) -> Result<Instruction_, Box<dyn Error + 'static>> {
+        let mut accounts = vec![
+            AccountMeta::new(*intermediary_investor_pubkey, true),
+            AccountMeta::new(*intermediary_pubkey, false),
+            AccountMeta::new(*w_sol_token_account_pubkey, false),
+            AccountMeta::new_readonly(*w_sol_token_account_authority_pubkey, false),
+            AccountMeta::new(*risk_limits_pubkey, false),
+            AccountMeta::new(*allowed_mints_pubkey, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+        ];
+        for any_mint_token_account in any_mint_token_accounts.iter() {
+            accounts.push(AccountMeta::new(any_mint_token_account.any_mint_token_mint_pubkey, false));
+            accounts.push(AccountMeta::new(any_mint_token_account.any_mint_token_account_pubkey, false));
+            accounts.push(AccountMeta::new_readonly(any_mint_token_account.any_mint_token_account_authority_pubkey, false));
+            accounts.push(AccountMeta::new_readonly(any_mint_token_account.any_mint_token_program_pubkey, false));
+        }
+        for trader_seat_pubkey in trader_seat_pubkeys.iter() {
+            accounts.push(AccountMeta::new(*trader_seat_pubkey, false));
+        }
+        Ok(
+            Instruction_ {
+                program_id: *program_id_pubkey,
+                accounts,
+                data: borsh::to_vec(
+                    &Self::CloseIntermediary {
+                        any_mint_token_accounts_quantity: any_mint_token_accounts.len() as u8,
+                    },
+                )?,
+            },
+        )
+    }
+    pub fn accept_manager<'a>(
+        program_id_pubkey: &'a Pubkey,
+        intermediary_pending_manager_pubkey: &'a Pubkey,
+        intermediary_pubkey: &'a Pubkey,
+    ) -> Result<Instruction_, Box<dyn Error + 'static>> {
+        Ok(
+            Instruction_ {
+                program_id: *program_id_pubkey,
+                accounts: vec![
+                    AccountMeta::new(*intermediary_pending_manager_pubkey, true),
+                    AccountMeta::new(*intermediary_pubkey, false),
+                ],
+                data: borsh::to_vec(&Self::AcceptManager)?,
+            },
+        )
+    }
+    pub fn accept_trader<'a>(
+        program_id_pubkey: &'a Pubkey,
+        intermediary_pending_trader_pubkey: &'a Pubkey,
+        intermediary_pubkey: &'a Pubkey,
+    ) -> Result<Instruction_, Box<dyn Error + 'static>> {
+        Ok(
+            Instruction_ {
+                program_id: *program_id_pubkey,
+                accounts: vec![
+                    AccountMeta::new(*intermediary_pending_trader_pubkey, true),
+                    AccountMeta::new(*intermediary_pubkey, false),
+                ],
+                data: borsh::to_vec(&Self::AcceptTrader)?,
+            },
+        )
+    }
+    pub fn cancel_manager_proposal<'a>(
+        program_id_pubkey: &'a Pubkey,
+        authority_pubkey: &'a Pubkey,
+        intermediary_pubkey: &'a Pubkey,
+    ) -> Result<Instruction_, Box<dyn Error + 'static>> {
+        Ok(
+            Instruction_ {
+                program_id: *program_id_pubkey,
+                accounts: vec![
+                    AccountMeta::new(*authority_pubkey, true),
+                    AccountMeta::new(*intermediary_pubkey, false),
+                ],
+                data: borsh::to_vec(&Self::CancelManagerProposal)?,
+            },
+        )
+    }
+    pub fn cancel_trader_proposal<'a>(
+        program_id_pubkey: &'a Pubkey,
+        authority_pubkey: &'a Pubkey,
+        intermediary_pubkey: &'a Pubkey,
+    ) -> Result<Instruction_, Box<dyn Error + 'static>> {
+        Ok(
+            Instruction_ {
+                program_id: *program_id_pubkey,
+                accounts: vec![
+                    AccountMeta::new(*authority_pubkey, true),
+                    AccountMeta::new(*intermediary_pubkey, false),
+                ],
+                data: borsh::to_vec(&Self::CancelTraderProposal)?,
+            },
+        )
+    }
+    pub fn add_trader_seat<'a>(
+        program_id_pubkey: &'a Pubkey,
+        intermediary_manager_pubkey: &'a Pubkey,
+        intermediary_pubkey: &'a Pubkey,
+        intermediary_trader_pubkey: &'a Pubkey,
+        trader_seat_pubkey: &'a Pubkey,
+        allocated_lamports: u64,
+        trader_seat_pubkey_bump_seed: u8,
+    ) -> Result<Instruction_, Box<dyn Error + 'static>> {
+        Ok(
+            Instruction_ {
+                program_id: *program_id_pubkey,
+                accounts: vec![
+                    AccountMeta::new(*intermediary_manager_pubkey, true),
+                    AccountMeta::new(*intermediary_pubkey, false),
+                    AccountMeta::new_readonly(*intermediary_trader_pubkey, false),
+                    AccountMeta::new(*trader_seat_pubkey, false),
+                    AccountMeta::new_readonly(solana_program::system_program::ID, false),
+                    AccountMeta::new_readonly(solana_program::sysvar::rent::ID, false),
+                ],
+                data: borsh::to_vec(
+                    &Self::AddTraderSeat {
+                        allocated_lamports,
+                        trader_seat_pubkey_bump_seed,
+                    },
+                )?,
+            },
+        )
+    }
+    pub fn remove_trader_seat<'a>(
+        program_id_pubkey: &'a Pubkey,
+        intermediary_manager_pubkey: &'a Pubkey,
+        intermediary_pubkey: &'a Pubkey,
+        trader_seat_pubkey: &'a Pubkey,
+    ) -> Result<Instruction_, Box<dyn Error + 'static>> {
+        Ok(
+            Instruction_ {
+                program_id: *program_id_pubkey,
+                accounts: vec![
+                    AccountMeta::new(*intermediary_manager_pubkey, true),
+                    AccountMeta::new(*intermediary_pubkey, false),
+                    AccountMeta::new(*trader_seat_pubkey, false),
+                ],
+                data: borsh::to_vec(&Self::RemoveTraderSeat)?,
+            },
+        )
+    }
+    pub fn pause<'a>(
+        program_id_pubkey: &'a Pubkey,
+        authority_pubkey: &'a Pubkey,
+        intermediary_pubkey: &'a Pubkey,
+    ) -> Result<Instruction_, Box<dyn Error + 'static>> {
+        Ok(
+            Instruction_ {
+                program_id: *program_id_pubkey,
+                accounts: vec![
+                    AccountMeta::new(*authority_pubkey, true),
+                    AccountMeta::new(*intermediary_pubkey, false),
+                ],
+                data: borsh::to_vec(&Self::Pause)?,
+            },
+        )
+    }
+    pub fn unpause<'a>(
+        program_id_pubkey: &'a Pubkey,
+        intermediary_investor_pubkey: &'a Pubkey,
+        intermediary_pubkey: &'a Pubkey,
+    ) -> Result<Instruction_, Box<dyn Error + 'static>> {
+        Ok(
+            Instruction_ {
+                program_id: *program_id_pubkey,
+                accounts: vec![
+                    AccountMeta::new(*intermediary_investor_pubkey, true),
+                    AccountMeta::new(*intermediary_pubkey, false),
+                ],
+                data: borsh::to_vec(&Self::Unpause)?,
+            },
+        )
+    }
+    pub fn set_maximum_price_deviation<'a>(
+        program_id_pubkey: &'a Pubkey,
+        intermediary_manager_pubkey: &'a Pubkey,
+        intermediary_pubkey: &'a Pubkey,
+        maximum_price_deviation_basis_points: u16,
+    ) -> Result<Instruction_, Box<dyn Error + 'static>> {
+        Ok(
+            Instruction_ {
+                program_id: *program_id_pubkey,
+                accounts: vec![
+                    AccountMeta::new(*intermediary_manager_pubkey, true),
+                    AccountMeta::new(*intermediary_pubkey, false),
+                ],
+                data: borsh::to_vec(
+                    &Self::SetMaximumPriceDeviation {
+                        maximum_price_deviation_basis_points,
+                    },
+                )?,
+            },
+        )
+    }
+    pub fn migrate_intermediary<'a>(
+        program_id_pubkey: &'a Pubkey,
+        authority_pubkey: &'a Pubkey,
+        intermediary_pubkey: &'a Pubkey,
+    ) -> Result<Instruction_, Box<dyn Error + 'static>> {
+        Ok(
+            Instruction_ {
+                program_id: *program_id_pubkey,
+                accounts: vec![
+                    AccountMeta::new(*authority_pubkey, true),
+                    AccountMeta::new(*intermediary_pubkey, false),
+                    AccountMeta::new_readonly(solana_program::system_program::ID, false),
+                    AccountMeta::new_readonly(solana_program::sysvar::rent::ID, false),
+                ],
+                data: borsh::to_vec(&Self::MigrateIntermediary)?,
+            },
+        )
+    }
+    pub fn liquidate_all<'a>(
+        program_id_pubkey: &'a Pubkey,
+        authority_pubkey: &'a Pubkey,
+        intermediary_pubkey: &'a Pubkey,
+        w_sol_token_account_pubkey: &'a Pubkey,
+        temporary_w_sol_token_account_pubkey: &'a Pubkey,
+        liquidation_legs: &'a [LiquidationLeg],
+    ) -> Result<Instruction_, Box<dyn Error + 'static>> {
+        let mut accounts = vec![
+            AccountMeta::new(*authority_pubkey, true),
+            AccountMeta::new(*intermediary_pubkey, false),
+            AccountMeta::new(*w_sol_token_account_pubkey, false),
+            AccountMeta::new(*temporary_w_sol_token_account_pubkey, false),
+            AccountMeta::new_readonly(spl_token::native_mint::ID, false),
+            AccountMeta::new_readonly(solana_program::system_program::ID, false),
+            AccountMeta::new_readonly(solana_program::sysvar::rent::ID, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+        ];
+        let mut venues = Vec::<Venue>::with_capacity(liquidation_legs.len());
+        let mut minimum_output_token_amounts = Vec::<u64>::with_capacity(liquidation_legs.len());
+        for liquidation_leg in liquidation_legs.iter() {
+            accounts.push(AccountMeta::new(liquidation_leg.any_mint_token_mint_pubkey, false));
+            accounts.push(AccountMeta::new(liquidation_leg.any_mint_token_account_pubkey, false));
+            accounts.push(AccountMeta::new(liquidation_leg.any_mint_token_account_authority_pubkey, false));
+            accounts.push(AccountMeta::new_readonly(liquidation_leg.any_mint_token_program_pubkey, false));
+            accounts.append(
+                &mut crate::venue::create_account_metas(
+                    liquidation_leg.venue,
+                    liquidation_leg.venue_account_pubkeys.as_slice(),
+                )?,
+            );
+            venues.push(liquidation_leg.venue);
+            minimum_output_token_amounts.push(liquidation_leg.minimum_output_token_amount);
+        }
+        Ok(
+            Instruction_ {
+                program_id: *program_id_pubkey,
+                accounts,
+                data: borsh::to_vec(
+                    &Self::LiquidateAll {
+                        venues,
+                        minimum_output_token_amounts,
+                    },
+                )?,
+            },
+        )
+    }
+    pub fn withdraw_token<'a>(
+        program_id_pubkey: &'a Pubkey,
+        intermediary_investor_pubkey: &'a Pubkey,
+        intermediary_pubkey: &'a Pubkey,
+        any_mint_token_mint_pubkey: &'a Pubkey,
+        any_mint_token_account_pubkey: &'a Pubkey,
+        any_mint_token_account_authority_pubkey: &'a Pubkey,
+        intermediary_investor_token_account_pubkey: &'a Pubkey,
+        any_mint_token_program_pubkey: &'a Pubkey,
+        amount: u64,
+    ) -> Result<Instruction_, Box<dyn Error + 'static>> {
+        Ok(
+            Instruction_ {
+                program_id: *program_id_pubkey,
+                accounts: vec![
+                    AccountMeta::new(*intermediary_investor_pubkey, true),
+                    AccountMeta::new_readonly(*intermediary_pubkey, false),
+                    AccountMeta::new_readonly(*any_mint_token_mint_pubkey, false),
+                    AccountMeta::new(*any_mint_token_account_pubkey, false),
+                    AccountMeta::new_readonly(*any_mint_token_account_authority_pubkey, false),
+                    AccountMeta::new(*intermediary_investor_token_account_pubkey, false),
+                    AccountMeta::new_readonly(solana_program::system_program::ID, false),
+                    AccountMeta::new_readonly(*any_mint_token_program_pubkey, false),
+                    AccountMeta::new_readonly(SPL_ASSOCIATED_TOKEN_ACCOUNT_ID, false),
+                ],
+                data: borsh::to_vec(
+                    &Self::WithdrawToken {
+                        amount,
+                    },
+                )?,
+            },
+        )
+    }
+    pub fn buy_on_raydium_cpmm<'a>(
+        program_id_pubkey: &'a Pubkey,
+        intermediary_trader_pubkey: &'a Pubkey,
+        intermediary_pubkey: &'a Pubkey,
+        w_sol_token_account_pubkey: &'a Pubkey,
+        w_sol_token_account_authority_pubkey: &'a Pubkey,
+        temporary_w_sol_token_account_pubkey: &'a Pubkey,
+        temporary_w_sol_token_account_authority_pubkey: &'a Pubkey,
+        any_mint_token_account_pubkey: &'a Pubkey,
+        any_mint_token_account_authority_pubkey: &'a Pubkey,
+        any_mint_token_mint_account_pubkey: &'a Pubkey,
+        any_mint_token_program_pubkey: &'a Pubkey,
+        cpmm_authority_pubkey: &'a Pubkey,
+        cpmm_amm_config_pubkey: &'a Pubkey,
+        cpmm_pool_state_pubkey: &'a Pubkey,
+        cpmm_w_sol_vault_pubkey: &'a Pubkey,
+        cpmm_any_mint_vault_pubkey: &'a Pubkey,
+        cpmm_observation_state_pubkey: &'a Pubkey,
+        allowed_mints_pubkey: &'a Pubkey,
+        risk_limits_pubkey: &'a Pubkey,
+        trader_seat_pubkey: &'a Pubkey,
+        config_pubkey: &'a Pubkey,
+        fee_vault_pubkey: &'a Pubkey,
+        input_token_amount: u64,
+        minimum_output_token_amount: u64,
+        any_mint_token_account_pubkey_bump_seed: u8,
+        any_mint_token_account_authority_pubkey_bump_seed: u8,
+    ) -> Result<Instruction_, Box<dyn Error + 'static>> {
+        Ok(
+            Instruction_ {
+                program_id: *program_id_pubkey,
+                accounts: vec![
+                    AccountMeta::new(*intermediary_trader_pubkey, true),
+                    AccountMeta::new(*intermediary_pubkey, false),
+                    AccountMeta::new(*w_sol_token_account_pubkey, false),
+                    AccountMeta::new_readonly(*w_sol_token_account_authority_pubkey, false),
+                    AccountMeta::new(*temporary_w_sol_token_account_pubkey, false),
+                    AccountMeta::new_readonly(*temporary_w_sol_token_account_authority_pubkey, false),
+                    AccountMeta::new(*any_mint_token_account_pubkey, false),
+                    AccountMeta::new_readonly(*any_mint_token_account_authority_pubkey, false),
+                    AccountMeta::new_readonly(spl_token::native_mint::ID, false),
+                    AccountMeta::new_readonly(*any_mint_token_mint_account_pubkey, false),
+                    AccountMeta::new_readonly(solana_program::system_program::ID, false),
+                    AccountMeta::new_readonly(solana_program::sysvar::rent::ID, false),
+                    AccountMeta::new_readonly(spl_token::ID, false),
+                    AccountMeta::new_readonly(*any_mint_token_program_pubkey, false),
+                    AccountMeta::new_readonly(crate::RAYDIUM_CPMM_PROGRAM_PUBKEY, false),
+                    AccountMeta::new_readonly(*cpmm_authority_pubkey, false),
+                    AccountMeta::new_readonly(*cpmm_amm_config_pubkey, false),
+                    AccountMeta::new(*cpmm_pool_state_pubkey, false),
+                    AccountMeta::new(*cpmm_w_sol_vault_pubkey, false),
+                    AccountMeta::new(*cpmm_any_mint_vault_pubkey, false),
+                    AccountMeta::new(*cpmm_observation_state_pubkey, false),
+                    AccountMeta::new_readonly(*allowed_mints_pubkey, false),
+                    AccountMeta::new(*risk_limits_pubkey, false),
+                    AccountMeta::new(*trader_seat_pubkey, false),
+                    AccountMeta::new_readonly(*config_pubkey, false),
+                    AccountMeta::new(*fee_vault_pubkey, false),
+                ],
+                data: borsh::to_vec(
+                    &Self::BuyOnRaydiumCpmm {
+                        input_token_amount,
+                        minimum_output_token_amount,
+                        any_mint_token_account_pubkey_bump_seed,
+                        any_mint_token_account_authority_pubkey_bump_seed,
+                    },
+                )?,
+            },
+        )
+    }
+    pub fn sell_on_raydium_cpmm<'a>(
+        program_id_pubkey: &'a Pubkey,
+        intermediary_trader_pubkey: &'a Pubkey,
+        intermediary_pubkey: &'a Pubkey,
+        w_sol_token_account_pubkey: &'a Pubkey,
+        any_mint_token_account_pubkey: &'a Pubkey,
+        any_mint_token_account_authority_pubkey: &'a Pubkey,
+        temporary_any_mint_token_account_pubkey: &'a Pubkey,
+        temporary_any_mint_token_account_authority_pubkey: &'a Pubkey,
+        any_mint_token_mint_account_pubkey: &'a Pubkey,
+        any_mint_token_program_pubkey: &'a Pubkey,
+        cpmm_authority_pubkey: &'a Pubkey,
+        cpmm_amm_config_pubkey: &'a Pubkey,
+        cpmm_pool_state_pubkey: &'a Pubkey,
+        cpmm_w_sol_vault_pubkey: &'a Pubkey,
+        cpmm_any_mint_vault_pubkey: &'a Pubkey,
+        cpmm_observation_state_pubkey: &'a Pubkey,
+        risk_limits_pubkey: &'a Pubkey,
+        trader_seat_pubkey: &'a Pubkey,
+        config_pubkey: &'a Pubkey,
+        fee_vault_pubkey: &'a Pubkey,
+        input_token_amount: u64,
+        minimum_output_token_amount: Option<u64>,
+        any_mint_token_account_pubkey_bump_seed: u8,
+        any_mint_token_account_authority_pubkey_bump_seed: u8,
+        temporary_any_mint_token_account_pubkey_bump_seed: u8,
+        temporary_any_mint_token_account_authority_pubkey_bump_seed: u8,
+    ) -> Result<Instruction_, Box<dyn Error + 'static>> {
+        Ok(
+            Instruction_ {
+                program_id: *program_id_pubkey,
+                accounts: vec![
+                    AccountMeta::new(*intermediary_trader_pubkey, true),
+                    AccountMeta::new(*intermediary_pubkey, false),
+                    AccountMeta::new(*w_sol_token_account_pubkey, false),
+                    AccountMeta::new(*any_mint_token_account_pubkey, false),
+                    AccountMeta::new_readonly(*any_mint_token_account_authority_pubkey, false),
+                    AccountMeta::new(*temporary_any_mint_token_account_pubkey, false),
+                    AccountMeta::new_readonly(*temporary_any_mint_token_account_authority_pubkey, false),
+                    AccountMeta::new_readonly(spl_token::native_mint::ID, false),
+                    AccountMeta::new(*any_mint_token_mint_account_pubkey, false),
+                    AccountMeta::new_readonly(solana_program::system_program::ID, false),
+                    AccountMeta::new_readonly(solana_program::sysvar::rent::ID, false),
+                    AccountMeta::new_readonly(spl_token::ID, false),
+                    AccountMeta::new_readonly(*any_mint_token_program_pubkey, false),
+                    AccountMeta::new_readonly(crate::RAYDIUM_CPMM_PROGRAM_PUBKEY, false),
+                    AccountMeta::new_readonly(*cpmm_authority_pubkey, false),
+                    AccountMeta::new_readonly(*cpmm_amm_config_pubkey, false),
+                    AccountMeta::new(*cpmm_pool_state_pubkey, false),
+                    AccountMeta::new(*cpmm_w_sol_vault_pubkey, false),
+                    AccountMeta::new(*cpmm_any_mint_vault_pubkey, false),
+                    AccountMeta::new(*cpmm_observation_state_pubkey, false),
+                    AccountMeta::new(*risk_limits_pubkey, false),
+                    AccountMeta::new(*trader_seat_pubkey, false),
+                    AccountMeta::new_readonly(*config_pubkey, false),
+                    AccountMeta::new(*fee_vault_pubkey, false),
+                ],
+                data: borsh::to_vec(
+                    &Self::SellOnRaydiumCpmm {
+                        input_token_amount,
+                        minimum_output_token_amount,
+                        any_mint_token_account_pubkey_bump_seed,
+                        any_mint_token_account_authority_pubkey_bump_seed,
+                        temporary_any_mint_token_account_pubkey_bump_seed,
+                        temporary_any_mint_token_account_authority_pubkey_bump_seed,
+                    },
+                )?,
+            },
+        )
+    }
+    pub fn buy_on_raydium_clmm<'a>(
+        program_id_pubkey: &'a Pubkey,
+        intermediary_trader_pubkey: &'a Pubkey,
+        intermediary_pubkey: &'a Pubkey,
+        w_sol_token_account_pubkey: &'a Pubkey,
+        w_sol_token_account_authority_pubkey: &'a Pubkey,
+        temporary_w_sol_token_account_pubkey: &'a Pubkey,
+        temporary_w_sol_token_account_authority_pubkey: &'a Pubkey,
+        any_mint_token_account_pubkey: &'a Pubkey,
+        any_mint_token_account_authority_pubkey: &'a Pubkey,
+        any_mint_token_mint_account_pubkey: &'a Pubkey,
+        clmm_amm_config_pubkey: &'a Pubkey,
+        clmm_pool_state_pubkey: &'a Pubkey,
+        clmm_w_sol_vault_pubkey: &'a Pubkey,
+        clmm_any_mint_vault_pubkey: &'a Pubkey,
+        clmm_observation_state_pubkey: &'a Pubkey,
+        allowed_mints_pubkey: &'a Pubkey,
+        risk_limits_pubkey: &'a Pubkey,
+        trader_seat_pubkey: &'a Pubkey,
+        config_pubkey: &'a Pubkey,
+        fee_vault_pubkey: &'a Pubkey,
+        clmm_tick_array_pubkeys: &'a [Pubkey],
+        input_token_amount: u64,
+        minimum_output_token_amount: u64,
+        any_mint_token_account_pubkey_bump_seed: u8,
+        any_mint_token_account_authority_pubkey_bump_seed: u8,
+    ) -> Result<Instruction_, Box<dyn Error + 'static>> {
+        let mut accounts = vec![
+            AccountMeta::new(*intermediary_trader_pubkey, true),
+            AccountMeta::new(*intermediary_pubkey, false),
+            AccountMeta::new(*w_sol_token_account_pubkey, false),
+            AccountMeta::new_readonly(*w_sol_token_account_authority_pubkey, false),
+            AccountMeta::new(*temporary_w_sol_token_account_pubkey, false),
+            AccountMeta::new_readonly(*temporary_w_sol_token_account_authority_pubkey, false),
+            AccountMeta::new(*any_mint_token_account_pubkey, false),
+            AccountMeta::new_readonly(*any_mint_token_account_authority_pubkey, false),
+            AccountMeta::new_readonly(spl_token::native_mint::ID, false),
+            AccountMeta::new_readonly(*any_mint_token_mint_account_pubkey, false),
+            AccountMeta::new_readonly(solana_program::system_program::ID, false),
+            AccountMeta::new_readonly(solana_program::sysvar::rent::ID, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new_readonly(spl_token_2022::ID, false),
+            AccountMeta::new_readonly(crate::SPL_MEMO_PROGRAM_PUBKEY, false),
+            AccountMeta::new_readonly(crate::RAYDIUM_CLMM_PROGRAM_PUBKEY, false),
+            AccountMeta::new_readonly(*clmm_amm_config_pubkey, false),
+            AccountMeta::new(*clmm_pool_state_pubkey, false),
+            AccountMeta::new(*clmm_w_sol_vault_pubkey, false),
+            AccountMeta::new(*clmm_any_mint_vault_pubkey, false),
+            AccountMeta::new(*clmm_observation_state_pubkey, false),
+            AccountMeta::new_readonly(*allowed_mints_pubkey, false),
+            AccountMeta::new(*risk_limits_pubkey, false),
+            AccountMeta::new(*trader_seat_pubkey, false),
+            AccountMeta::new_readonly(*config_pubkey, false),
+            AccountMeta::new(*fee_vault_pubkey, false),
+        ];
+        for clmm_tick_array_pubkey in clmm_tick_array_pubkeys.iter() {
+            accounts.push(AccountMeta::new(*clmm_tick_array_pubkey, false));
+        }
+        Ok(
+            Instruction_ {
+                program_id: *program_id_pubkey,
+                accounts,
+                data: borsh::to_vec(
+                    &Self::BuyOnRaydiumClmm {
+                        input_token_amount,
+                        minimum_output_token_amount,
+                        any_mint_token_account_pubkey_bump_seed,
+                        any_mint_token_account_authority_pubkey_bump_seed,
+                    },
+                )?,
+            },
+        )
+    }
+    pub fn sell_on_raydium_clmm<'a>(
+        program_id_pubkey: &'a Pubkey,
+        intermediary_trader_pubkey: &'a Pubkey,
+        intermediary_pubkey: &'a Pubkey,
+        w_sol_token_account_pubkey: &'a Pubkey,
+        any_mint_token_account_pubkey: &'a Pubkey,
+        any_mint_token_account_authority_pubkey: &'a Pubkey,
+        temporary_any_mint_token_account_pubkey: &'a Pubkey,
+        temporary_any_mint_token_account_authority_pubkey: &'a Pubkey,
+        any_mint_token_mint_account_pubkey: &'a Pubkey,
+        clmm_amm_config_pubkey: &'a Pubkey,
+        clmm_pool_state_pubkey: &'a Pubkey,
+        clmm_w_sol_vault_pubkey: &'a Pubkey,
+        clmm_any_mint_vault_pubkey: &'a Pubkey,
+        clmm_observation_state_pubkey: &'a Pubkey,
+        risk_limits_pubkey: &'a Pubkey,
+        trader_seat_pubkey: &'a Pubkey,
+        config_pubkey: &'a Pubkey,
+        fee_vault_pubkey: &'a Pubkey,
+        clmm_tick_array_pubkeys: &'a [Pubkey],
+        input_token_amount: u64,
+        minimum_output_token_amount: Option<u64>,
+        any_mint_token_account_pubkey_bump_seed: u8,
+        any_mint_token_account_authority_pubkey_bump_seed: u8,
+        temporary_any_mint_token_account_pubkey_bump_seed: u8,
+        temporary_any_mint_token_account_authority_pubkey_bump_seed: u8,
+    ) -> Result<Instruction_, Box<dyn Error + 'static>> {
+        let mut accounts = vec![
+            AccountMeta::new(*intermediary_trader_pubkey, true),
+            AccountMeta::new(*intermediary_pubkey, false),
+            AccountMeta::new(*w_sol_token_account_pubkey, false),
+            AccountMeta::new(*any_mint_token_account_pubkey, false),
+            AccountMeta::new_readonly(*any_mint_token_account_authority_pubkey, false),
+            AccountMeta::new(*temporary_any_mint_token_account_pubkey, false),
+            AccountMeta::new_readonly(*temporary_any_mint_token_account_authority_pubkey, false),
+            AccountMeta::new_readonly(spl_token::native_mint::ID, false),
+            AccountMeta::new(*any_mint_token_mint_account_pubkey, false),
+            AccountMeta::new_readonly(solana_program::system_program::ID, false),
+            AccountMeta::new_readonly(solana_program::sysvar::rent::ID, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new_readonly(spl_token_2022::ID, false),
+            AccountMeta::new_readonly(crate::SPL_MEMO_PROGRAM_PUBKEY, false),
+            AccountMeta::new_readonly(crate::RAYDIUM_CLMM_PROGRAM_PUBKEY, false),
+            AccountMeta::new_readonly(*clmm_amm_config_pubkey, false),
+            AccountMeta::new(*clmm_pool_state_pubkey, false),
+            AccountMeta::new(*clmm_w_sol_vault_pubkey, false),
+            AccountMeta::new(*clmm_any_mint_vault_pubkey, false),
+            AccountMeta::new(*clmm_observation_state_pubkey, false),
+            AccountMeta::new(*risk_limits_pubkey, false),
+            AccountMeta::new(*trader_seat_pubkey, false),
+            AccountMeta::new_readonly(*config_pubkey, false),
+            AccountMeta::new(*fee_vault_pubkey, false),
+        ];
+        for clmm_tick_array_pubkey in clmm_tick_array_pubkeys.iter() {
+            accounts.push(AccountMeta::new(*clmm_tick_array_pubkey, false));
+        }
+        Ok(
+            Instruction_ {
+                program_id: *program_id_pubkey,
+                accounts,
+                data: borsh::to_vec(
+                    &Self::SellOnRaydiumClmm {
+                        input_token_amount,
+                        minimum_output_token_amount,
+                        any_mint_token_account_pubkey_bump_seed,
+                        any_mint_token_account_authority_pubkey_bump_seed,
+                        temporary_any_mint_token_account_pubkey_bump_seed,
+                        temporary_any_mint_token_account_authority_pubkey_bump_seed,
+                    },
+                )?,
+            },
+        )
+    }
+    pub fn buy_on_meteora_dlmm<'a>(
+        program_id_pubkey: &'a Pubkey,
+        intermediary_trader_pubkey: &'a Pubkey,
+        intermediary_pubkey: &'a Pubkey,
+        w_sol_token_account_pubkey: &'a Pubkey,
+        w_sol_token_account_authority_pubkey: &'a Pubkey,
+        temporary_w_sol_token_account_pubkey: &'a Pubkey,
+        temporary_w_sol_token_account_authority_pubkey: &'a Pubkey,
+        any_mint_token_account_pubkey: &'a Pubkey,
+        any_mint_token_account_authority_pubkey: &'a Pubkey,
+        any_mint_token_mint_account_pubkey: &'a Pubkey,
+        any_mint_token_program_pubkey: &'a Pubkey,
+        dlmm_lb_pair_pubkey: &'a Pubkey,
+        dlmm_bin_array_bitmap_extension_pubkey: &'a Pubkey,
+        dlmm_w_sol_reserve_pubkey: &'a Pubkey,
+        dlmm_any_mint_reserve_pubkey: &'a Pubkey,
+        dlmm_oracle_pubkey: &'a Pubkey,
+        dlmm_event_authority_pubkey: &'a Pubkey,
+        allowed_mints_pubkey: &'a Pubkey,
+        risk_limits_pubkey: &'a Pubkey,
+        trader_seat_pubkey: &'a Pubkey,
+        config_pubkey: &'a Pubkey,
+        fee_vault_pubkey: &'a Pubkey,
+        dlmm_bin_array_pubkeys: &'a [Pubkey],
+        input_token_amount: u64,
+        minimum_output_token_amount: u64,
+        any_mint_token_account_pubkey_bump_seed: u8,
+        any_mint_token_account_authority_pubkey_bump_seed: u8,
+    ) -> Result<Instruction_, Box<dyn Error + 'static>> {
+        let mut accounts = vec![
+            AccountMeta::new(*intermediary_trader_pubkey, true),
+            AccountMeta::new(*intermediary_pubkey, false),
+            AccountMeta::new(*w_sol_token_account_pubkey, false),
+            AccountMeta::new_readonly(*w_sol_token_account_authority_pubkey, false),
+            AccountMeta::new(*temporary_w_sol_token_account_pubkey, false),
+            AccountMeta::new_readonly(*temporary_w_sol_token_account_authority_pubkey, false),
+            AccountMeta::new(*any_mint_token_account_pubkey, false),
+            AccountMeta::new_readonly(*any_mint_token_account_authority_pubkey, false),
+            AccountMeta::new_readonly(spl_token::native_mint::ID, false),
+            AccountMeta::new_readonly(*any_mint_token_mint_account_pubkey, false),
+            AccountMeta::new_readonly(solana_program::system_program::ID, false),
+            AccountMeta::new_readonly(solana_program::sysvar::rent::ID, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new_readonly(*any_mint_token_program_pubkey, false),
+            AccountMeta::new_readonly(crate::METEORA_DLMM_PROGRAM_PUBKEY, false),
+            AccountMeta::new(*dlmm_lb_pair_pubkey, false),
+            AccountMeta::new_readonly(*dlmm_bin_array_bitmap_extension_pubkey, false),
+            AccountMeta::new(*dlmm_w_sol_reserve_pubkey, false),
+            AccountMeta::new(*dlmm_any_mint_reserve_pubkey, false),
+            AccountMeta::new(*dlmm_oracle_pubkey, false),
+            AccountMeta::new_readonly(*dlmm_event_authority_pubkey, false),
+            AccountMeta::new_readonly(*allowed_mints_pubkey, false),
+            AccountMeta::new(*risk_limits_pubkey, false),
+            AccountMeta::new(*trader_seat_pubkey, false),
+            AccountMeta::new_readonly(*config_pubkey, false),
+            AccountMeta::new(*fee_vault_pubkey, false),
+        ];
+        for dlmm_bin_array_pubkey in dlmm_bin_array_pubkeys.iter() {
+            accounts.push(AccountMeta::new(*dlmm_bin_array_pubkey, false));
+        }
+        Ok(
+            Instruction_ {
+                program_id: *program_id_pubkey,
+                accounts,
+                data: borsh::to_vec(
+                    &Self::BuyOnMeteoraDlmm {
+                        input_token_amount,
+                        minimum_output_token_amount,
+                        any_mint_token_account_pubkey_bump_seed,
+                        any_mint_token_account_authority_pubkey_bump_seed,
+                    },
+                )?,
+            },
+        )
+    }
+    pub fn sell_on_meteora_dlmm<'a>(
+        program_id_pubkey: &'a Pubkey,
+        intermediary_trader_pubkey: &'a Pubkey,
+        intermediary_pubkey: &'a Pubkey,
+        w_sol_token_account_pubkey: &'a Pubkey,
+        any_mint_token_account_pubkey: &'a Pubkey,
+        any_mint_token_account_authority_pubkey: &'a Pubkey,
+        temporary_any_mint_token_account_pubkey: &'a Pubkey,
+        temporary_any_mint_token_account_authority_pubkey: &'a Pubkey,
+        any_mint_token_mint_account_pubkey: &'a Pubkey,
+        any_mint_token_program_pubkey: &'a Pubkey,
+        dlmm_lb_pair_pubkey: &'a Pubkey,
+        dlmm_bin_array_bitmap_extension_pubkey: &'a Pubkey,
+        dlmm_w_sol_reserve_pubkey: &'a Pubkey,
+        dlmm_any_mint_reserve_pubkey: &'a Pubkey,
+        dlmm_oracle_pubkey: &'a Pubkey,
+        dlmm_event_authority_pubkey: &'a Pubkey,
+        risk_limits_pubkey: &'a Pubkey,
+        trader_seat_pubkey: &'a Pubkey,
+        config_pubkey: &'a Pubkey,
+        fee_vault_pubkey: &'a Pubkey,
+        dlmm_bin_array_pubkeys: &'a [Pubkey],
+        input_token_amount: u64,
+        minimum_output_token_amount: Option<u64>,
+        any_mint_token_account_pubkey_bump_seed: u8,
+        any_mint_token_account_authority_pubkey_bump_seed: u8,
+        temporary_any_mint_token_account_pubkey_bump_seed: u8,
+        temporary_any_mint_token_account_authority_pubkey_bump_seed: u8,
+    ) -> Result<Instruction_, Box<dyn Error + 'static>> {
+        let mut accounts = vec![
+            AccountMeta::new(*intermediary_trader_pubkey, true),
+            AccountMeta::new(*intermediary_pubkey, false),
+            AccountMeta::new(*w_sol_token_account_pubkey, false),
+            AccountMeta::new(*any_mint_token_account_pubkey, false),
+            AccountMeta::new_readonly(*any_mint_token_account_authority_pubkey, false),
+            AccountMeta::new(*temporary_any_mint_token_account_pubkey, false),
+            AccountMeta::new_readonly(*temporary_any_mint_token_account_authority_pubkey, false),
+            AccountMeta::new_readonly(spl_token::native_mint::ID, false),
+            AccountMeta::new(*any_mint_token_mint_account_pubkey, false),
+            AccountMeta::new_readonly(solana_program::system_program::ID, false),
+            AccountMeta::new_readonly(solana_program::sysvar::rent::ID, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new_readonly(*any_mint_token_program_pubkey, false),
+            AccountMeta::new_readonly(crate::METEORA_DLMM_PROGRAM_PUBKEY, false),
+            AccountMeta::new(*dlmm_lb_pair_pubkey, false),
+            AccountMeta::new_readonly(*dlmm_bin_array_bitmap_extension_pubkey, false),
+            AccountMeta::new(*dlmm_w_sol_reserve_pubkey, false),
+            AccountMeta::new(*dlmm_any_mint_reserve_pubkey, false),
+            AccountMeta::new(*dlmm_oracle_pubkey, false),
+            AccountMeta::new_readonly(*dlmm_event_authority_pubkey, false),
+            AccountMeta::new(*risk_limits_pubkey, false),
+            AccountMeta::new(*trader_seat_pubkey, false),
+            AccountMeta::new_readonly(*config_pubkey, false),
+            AccountMeta::new(*fee_vault_pubkey, false),
+        ];
+        for dlmm_bin_array_pubkey in dlmm_bin_array_pubkeys.iter() {
+            accounts.push(AccountMeta::new(*dlmm_bin_array_pubkey, false));
+        }
+        Ok(
+            Instruction_ {
+                program_id: *program_id_pubkey,
+                accounts,
+                data: borsh::to_vec(
+                    &Self::SellOnMeteoraDlmm {
+                        input_token_amount,
+                        minimum_output_token_amount,
+                        any_mint_token_account_pubkey_bump_seed,
+                        any_mint_token_account_authority_pubkey_bump_seed,
+                        temporary_any_mint_token_account_pubkey_bump_seed,
+                        temporary_any_mint_token_account_authority_pubkey_bump_seed,
+                    },
+                )?,
+            },
+        )
+    }
+    pub fn buy_on_meteora_dynamic_amm<'a>(
+        program_id_pubkey: &'a Pubkey,
+        intermediary_trader_pubkey: &'a Pubkey,
+        intermediary_pubkey: &'a Pubkey,
+        w_sol_token_account_pubkey: &'a Pubkey,
+        w_sol_token_account_authority_pubkey: &'a Pubkey,
+        temporary_w_sol_token_account_pubkey: &'a Pubkey,
+        temporary_w_sol_token_account_authority_pubkey: &'a Pubkey,
+        any_mint_token_account_pubkey: &'a Pubkey,
+        any_mint_token_account_authority_pubkey: &'a Pubkey,
+        any_mint_token_mint_account_pubkey: &'a Pubkey,
+        dynamic_amm_pool_pubkey: &'a Pubkey,
+        dynamic_amm_w_sol_vault_pubkey: &'a Pubkey,
+        dynamic_amm_any_mint_vault_pubkey: &'a Pubkey,
+        dynamic_amm_w_sol_token_vault_pubkey: &'a Pubkey,
+        dynamic_amm_any_mint_token_vault_pubkey: &'a Pubkey,
+        dynamic_amm_w_sol_vault_lp_mint_pubkey: &'a Pubkey,
+        dynamic_amm_any_mint_vault_lp_mint_pubkey: &'a Pubkey,
+        dynamic_amm_w_sol_vault_lp_pubkey: &'a Pubkey,
+        dynamic_amm_any_mint_vault_lp_pubkey: &'a Pubkey,
+        dynamic_amm_protocol_token_fee_pubkey: &'a Pubkey,
+        allowed_mints_pubkey: &'a Pubkey,
+        risk_limits_pubkey: &'a Pubkey,
+        trader_seat_pubkey: &'a Pubkey,
+        config_pubkey: &'a Pubkey,
+        fee_vault_pubkey: &'a Pubkey,
+        input_token_amount: u64,
+        minimum_output_token_amount: u64,
+        any_mint_token_account_pubkey_bump_seed: u8,
+        any_mint_token_account_authority_pubkey_bump_seed: u8,
+    ) -> Result<Instruction_, Box<dyn Error + 'static>> {
+        Ok(
+            Instruction_ {
+                program_id: *program_id_pubkey,
+                accounts: vec![
+                AccountMeta::new(*intermediary_trader_pubkey, true),
+                AccountMeta::new(*intermediary_pubkey, false),
+                AccountMeta::new(*w_sol_token_account_pubkey, false),
+                AccountMeta::new_readonly(*w_sol_token_account_authority_pubkey, false),
+                AccountMeta::new(*temporary_w_sol_token_account_pubkey, false),
+                AccountMeta::new_readonly(*temporary_w_sol_token_account_authority_pubkey, false),
+                AccountMeta::new(*any_mint_token_account_pubkey, false),
+                AccountMeta::new_readonly(*any_mint_token_account_authority_pubkey, false),
+                AccountMeta::new_readonly(spl_token::native_mint::ID, false),
+                AccountMeta::new_readonly(*any_mint_token_mint_account_pubkey, false),
+                AccountMeta::new_readonly(solana_program::system_program::ID, false),
+                AccountMeta::new_readonly(solana_program::sysvar::rent::ID, false),
+                AccountMeta::new_readonly(spl_token::ID, false),
+                AccountMeta::new_readonly(crate::METEORA_DYNAMIC_AMM_PROGRAM_PUBKEY, false),
+                AccountMeta::new_readonly(crate::METEORA_VAULT_PROGRAM_PUBKEY, false),
+                AccountMeta::new(*dynamic_amm_pool_pubkey, false),
+                AccountMeta::new(*dynamic_amm_w_sol_vault_pubkey, false),
+                AccountMeta::new(*dynamic_amm_any_mint_vault_pubkey, false),
+                AccountMeta::new(*dynamic_amm_w_sol_token_vault_pubkey, false),
+                AccountMeta::new(*dynamic_amm_any_mint_token_vault_pubkey, false),
+                AccountMeta::new(*dynamic_amm_w_sol_vault_lp_mint_pubkey, false),
+                AccountMeta::new(*dynamic_amm_any_mint_vault_lp_mint_pubkey, false),
+                AccountMeta::new(*dynamic_amm_w_sol_vault_lp_pubkey, false),
+                AccountMeta::new(*dynamic_amm_any_mint_vault_lp_pubkey, false),
+                AccountMeta::new(*dynamic_amm_protocol_token_fee_pubkey, false),
+                AccountMeta::new_readonly(*allowed_mints_pubkey, false),
+                AccountMeta::new(*risk_limits_pubkey, false),
+                AccountMeta::new(*trader_seat_pubkey, false),
+                AccountMeta::new_readonly(*config_pubkey, false),
+                AccountMeta::new(*fee_vault_pubkey, false),
+                ],
+                data: borsh::to_vec(
+                    &Self::BuyOnMeteoraDynamicAmm {
+                        input_token_amount,
+                        minimum_output_token_amount,
+                        any_mint_token_account_pubkey_bump_seed,
+                        any_mint_token_account_authority_pubkey_bump_seed,
+                    },
+                )?,
+            },
+        )
+    }
+    pub fn sell_on_meteora_dynamic_amm<'a>(
+        program_id_pubkey: &'a Pubkey,
+        intermediary_trader_pubkey: &'a Pubkey,
+        intermediary_pubkey: &'a Pubkey,
+        w_sol_token_account_pubkey: &'a Pubkey,
+        any_mint_token_account_pubkey: &'a Pubkey,
+        any_mint_token_account_authority_pubkey: &'a Pubkey,
+        temporary_any_mint_token_account_pubkey: &'a Pubkey,
+        temporary_any_mint_token_account_authority_pubkey: &'a Pubkey,
+        any_mint_token_mint_account_pubkey: &'a Pubkey,
+        dynamic_amm_pool_pubkey: &'a Pubkey,
+        dynamic_amm_w_sol_vault_pubkey: &'a Pubkey,
+        dynamic_amm_any_mint_vault_pubkey: &'a Pubkey,
+        dynamic_amm_w_sol_token_vault_pubkey: &'a Pubkey,
+        dynamic_amm_any_mint_token_vault_pubkey: &'a Pubkey,
+        dynamic_amm_w_sol_vault_lp_mint_pubkey: &'a Pubkey,
+        dynamic_amm_any_mint_vault_lp_mint_pubkey: &'a Pubkey,
+        dynamic_amm_w_sol_vault_lp_pubkey: &'a Pubkey,
+        dynamic_amm_any_mint_vault_lp_pubkey: &'a Pubkey,
+        dynamic_amm_protocol_token_fee_pubkey: &'a Pubkey,
+        risk_limits_pubkey: &'a Pubkey,
+        trader_seat_pubkey: &'a Pubkey,
+        config_pubkey: &'a Pubkey,
+        fee_vault_pubkey: &'a Pubkey,
+        input_token_amount: u64,
+        minimum_output_token_amount: Option<u64>,
+        any_mint_token_account_pubkey_bump_seed: u8,
+        any_mint_token_account_authority_pubkey_bump_seed: u8,
+        temporary_any_mint_token_account_pubkey_bump_seed: u8,
+        temporary_any_mint_token_account_authority_pubkey_bump_seed: u8,
+    ) -> Result<Instruction_, Box<dyn Error + 'static>> {
+        Ok(
+            Instruction_ {
+                program_id: *program_id_pubkey,
+                accounts: vec![
+                AccountMeta::new(*intermediary_trader_pubkey, true),
+                AccountMeta::new(*intermediary_pubkey, false),
+                AccountMeta::new(*w_sol_token_account_pubkey, false),
+                AccountMeta::new(*any_mint_token_account_pubkey, false),
+                AccountMeta::new_readonly(*any_mint_token_account_authority_pubkey, false),
+                AccountMeta::new(*temporary_any_mint_token_account_pubkey, false),
+                AccountMeta::new_readonly(*temporary_any_mint_token_account_authority_pubkey, false),
+                AccountMeta::new_readonly(spl_token::native_mint::ID, false),
+                AccountMeta::new(*any_mint_token_mint_account_pubkey, false),
+                AccountMeta::new_readonly(solana_program::system_program::ID, false),
+                AccountMeta::new_readonly(solana_program::sysvar::rent::ID, false),
+                AccountMeta::new_readonly(spl_token::ID, false),
+                AccountMeta::new_readonly(crate::METEORA_DYNAMIC_AMM_PROGRAM_PUBKEY, false),
+                AccountMeta::new_readonly(crate::METEORA_VAULT_PROGRAM_PUBKEY, false),
+                AccountMeta::new(*dynamic_amm_pool_pubkey, false),
+                AccountMeta::new(*dynamic_amm_w_sol_vault_pubkey, false),
+                AccountMeta::new(*dynamic_amm_any_mint_vault_pubkey, false),
+                AccountMeta::new(*dynamic_amm_w_sol_token_vault_pubkey, false),
+                AccountMeta::new(*dynamic_amm_any_mint_token_vault_pubkey, false),
+                AccountMeta::new(*dynamic_amm_w_sol_vault_lp_mint_pubkey, false),
+                AccountMeta::new(*dynamic_amm_any_mint_vault_lp_mint_pubkey, false),
+                AccountMeta::new(*dynamic_amm_w_sol_vault_lp_pubkey, false),
+                AccountMeta::new(*dynamic_amm_any_mint_vault_lp_pubkey, false),
+                AccountMeta::new(*dynamic_amm_protocol_token_fee_pubkey, false),
+                AccountMeta::new(*risk_limits_pubkey, false),
+                AccountMeta::new(*trader_seat_pubkey, false),
+                AccountMeta::new_readonly(*config_pubkey, false),
+                AccountMeta::new(*fee_vault_pubkey, false),
+                ],
+                data: borsh::to_vec(
+                    &Self::SellOnMeteoraDynamicAmm {
+                        input_token_amount,
+                        minimum_output_token_amount,
+                        any_mint_token_account_pubkey_bump_seed,
+                        any_mint_token_account_authority_pubkey_bump_seed,
+                        temporary_any_mint_token_account_pubkey_bump_seed,
+                        temporary_any_mint_token_account_authority_pubkey_bump_seed,
+                    },
+                )?,
+            },
+        )
+    }
+    pub fn buy_on_pumpfun_curve<'a>(
+        program_id_pubkey: &'a Pubkey,
+        intermediary_trader_pubkey: &'a Pubkey,
+        intermediary_pubkey: &'a Pubkey,
+        w_sol_token_account_pubkey: &'a Pubkey,
+        w_sol_token_account_authority_pubkey: &'a Pubkey,
+        temporary_w_sol_token_account_pubkey: &'a Pubkey,
+        temporary_w_sol_token_account_authority_pubkey: &'a Pubkey,
+        any_mint_token_account_pubkey: &'a Pubkey,
+        any_mint_token_account_authority_pubkey: &'a Pubkey,
+        any_mint_token_mint_account_pubkey: &'a Pubkey,
+        global_pubkey: &'a Pubkey,
+        fee_recipient_pubkey: &'a Pubkey,
+        bonding_curve_pubkey: &'a Pubkey,
+        associated_bonding_curve_pubkey: &'a Pubkey,
+        event_authority_pubkey: &'a Pubkey,
+        allowed_mints_pubkey: &'a Pubkey,
+        risk_limits_pubkey: &'a Pubkey,
+        trader_seat_pubkey: &'a Pubkey,
+        config_pubkey: &'a Pubkey,
+        fee_vault_pubkey: &'a Pubkey,
+        token_amount_out: u64,
+        maximum_lamports_in: u64,
+        any_mint_token_account_pubkey_bump_seed: u8,
+        any_mint_token_account_authority_pubkey_bump_seed: u8,
+    ) -> Result<Instruction_, Box<dyn Error + 'static>> {
+        Ok(
+            Instruction_ {
+                program_id: *program_id_pubkey,
+                accounts: vec![
+                    AccountMeta::new(*intermediary_trader_pubkey, true),
+                    AccountMeta::new(*intermediary_pubkey, false),
+                    AccountMeta::new(*w_sol_token_account_pubkey, false),
+                    AccountMeta::new_readonly(*w_sol_token_account_authority_pubkey, false),
+                    AccountMeta::new(*temporary_w_sol_token_account_pubkey, false),
+                    AccountMeta::new(*temporary_w_sol_token_account_authority_pubkey, false),
+                    AccountMeta::new(*any_mint_token_account_pubkey, false),
+                    AccountMeta::new(*any_mint_token_account_authority_pubkey, false),
+                    AccountMeta::new_readonly(spl_token::native_mint::ID, false),
+                    AccountMeta::new_readonly(*any_mint_token_mint_account_pubkey, false),
+                    AccountMeta::new_readonly(solana_program::system_program::ID, false),
+                    AccountMeta::new_readonly(solana_program::sysvar::rent::ID, false),
+                    AccountMeta::new_readonly(spl_token::ID, false),
+                    AccountMeta::new_readonly(crate::PUMPFUN_PROGRAM_PUBKEY, false),
+                    AccountMeta::new_readonly(*global_pubkey, false),
+                    AccountMeta::new(*fee_recipient_pubkey, false),
+                    AccountMeta::new(*bonding_curve_pubkey, false),
+                    AccountMeta::new(*associated_bonding_curve_pubkey, false),
+                    AccountMeta::new_readonly(*event_authority_pubkey, false),
+                    AccountMeta::new_readonly(*allowed_mints_pubkey, false),
+                    AccountMeta::new(*risk_limits_pubkey, false),
+                    AccountMeta::new(*trader_seat_pubkey, false),
+                    AccountMeta::new_readonly(*config_pubkey, false),
+                    AccountMeta::new(*fee_vault_pubkey, false),
+                ],
+                data: borsh::to_vec(
+                    &Self::BuyOnPumpfunCurve {
+                        token_amount_out,
+                        maximum_lamports_in,
+                        any_mint_token_account_pubkey_bump_seed,
+                        any_mint_token_account_authority_pubkey_bump_seed,
+                    },
+                )?,
+            },
+        )
+    }
+    pub fn sell_on_pumpfun_curve<'a>(
+        program_id_pubkey: &'a Pubkey,
+        intermediary_trader_pubkey: &'a Pubkey,
+        intermediary_pubkey: &'a Pubkey,
+        w_sol_token_account_pubkey: &'a Pubkey,
+        any_mint_token_account_pubkey: &'a Pubkey,
+        any_mint_token_account_authority_pubkey: &'a Pubkey,
+        temporary_any_mint_token_account_pubkey: &'a Pubkey,
+        temporary_any_mint_token_account_authority_pubkey: &'a Pubkey,
+        any_mint_token_mint_account_pubkey: &'a Pubkey,
+        global_pubkey: &'a Pubkey,
+        fee_recipient_pubkey: &'a Pubkey,
+        bonding_curve_pubkey: &'a Pubkey,
+        associated_bonding_curve_pubkey: &'a Pubkey,
+        event_authority_pubkey: &'a Pubkey,
+        risk_limits_pubkey: &'a Pubkey,
+        trader_seat_pubkey: &'a Pubkey,
+        config_pubkey: &'a Pubkey,
+        fee_vault_pubkey: &'a Pubkey,
+        input_token_amount: u64,
+        minimum_output_token_amount: Option<u64>,
+        any_mint_token_account_pubkey_bump_seed: u8,
+        any_mint_token_account_authority_pubkey_bump_seed: u8,
+        temporary_any_mint_token_account_pubkey_bump_seed: u8,
+        temporary_any_mint_token_account_authority_pubkey_bump_seed: u8,
+    ) -> Result<Instruction_, Box<dyn Error + 'static>> {
+        Ok(
+            Instruction_ {
+                program_id: *program_id_pubkey,
+                accounts: vec![
+                    AccountMeta::new(*intermediary_trader_pubkey, true),
+                    AccountMeta::new(*intermediary_pubkey, false),
+                    AccountMeta::new(*w_sol_token_account_pubkey, false),
+                    AccountMeta::new(*any_mint_token_account_pubkey, false),
+                    AccountMeta::new(*any_mint_token_account_authority_pubkey, false),
+                    AccountMeta::new(*temporary_any_mint_token_account_pubkey, false),
+                    AccountMeta::new(*temporary_any_mint_token_account_authority_pubkey, false),
+                    AccountMeta::new_readonly(spl_token::native_mint::ID, false),
+                    AccountMeta::new_readonly(*any_mint_token_mint_account_pubkey, false),
+                    AccountMeta::new_readonly(solana_program::system_program::ID, false),
+                    AccountMeta::new_readonly(solana_program::sysvar::rent::ID, false),
+                    AccountMeta::new_readonly(spl_token::ID, false),
+                    AccountMeta::new_readonly(SPL_ASSOCIATED_TOKEN_ACCOUNT_ID, false),
+                    AccountMeta::new_readonly(crate::PUMPFUN_PROGRAM_PUBKEY, false),
+                    AccountMeta::new_readonly(*global_pubkey, false),
+                    AccountMeta::new(*fee_recipient_pubkey, false),
+                    AccountMeta::new(*bonding_curve_pubkey, false),
+                    AccountMeta::new(*associated_bonding_curve_pubkey, false),
+                    AccountMeta::new_readonly(*event_authority_pubkey, false),
+                    AccountMeta::new(*risk_limits_pubkey, false),
+                    AccountMeta::new(*trader_seat_pubkey, false),
+                    AccountMeta::new_readonly(*config_pubkey, false),
+                    AccountMeta::new(*fee_vault_pubkey, false),
+                ],
+                data: borsh::to_vec(
+                    &Self::SellOnPumpfunCurve {
+                        input_token_amount,
+                        minimum_output_token_amount,
+                        any_mint_token_account_pubkey_bump_seed,
+                        any_mint_token_account_authority_pubkey_bump_seed,
+                        temporary_any_mint_token_account_pubkey_bump_seed,
+                        temporary_any_mint_token_account_authority_pubkey_bump_seed,
+                    },
+                )?,
+            },
+        )
+    }
+    pub fn swap<'a>(
+        program_id_pubkey: &'a Pubkey,
+        intermediary_trader_pubkey: &'a Pubkey,
+        intermediary_pubkey: &'a Pubkey,
+        w_sol_token_account_pubkey: &'a Pubkey,
+        w_sol_token_account_authority_pubkey: &'a Pubkey,
+        temporary_w_sol_token_account_pubkey: &'a Pubkey,
+        temporary_w_sol_token_account_authority_pubkey: &'a Pubkey,
+        any_mint_token_account_pubkey: &'a Pubkey,
+        any_mint_token_account_authority_pubkey: &'a Pubkey,
+        temporary_any_mint_token_account_pubkey: &'a Pubkey,
+        temporary_any_mint_token_account_authority_pubkey: &'a Pubkey,
+        any_mint_token_mint_account_pubkey: &'a Pubkey,
+        any_mint_token_program_pubkey: &'a Pubkey,
+        allowed_mints_pubkey: &'a Pubkey,
+        risk_limits_pubkey: &'a Pubkey,
+        trader_seat_pubkey: &'a Pubkey,
+        config_pubkey: &'a Pubkey,
+        fee_vault_pubkey: &'a Pubkey,
+        venue: Venue,
+        venue_account_pubkeys: &'a [Pubkey],
+        side: Side,
+        amount_in: u64,
+        min_out: u64,
+        any_mint_token_account_pubkey_bump_seed: u8,
+        any_mint_token_account_authority_pubkey_bump_seed: u8,
+        temporary_any_mint_token_account_pubkey_bump_seed: u8,
+        temporary_any_mint_token_account_authority_pubkey_bump_seed: u8,
+    ) -> Result<Instruction_, Box<dyn Error + 'static>> {
+        let mut accounts = vec![
+            AccountMeta::new(*intermediary_trader_pubkey, true),
+            AccountMeta::new(*intermediary_pubkey, false),
+            AccountMeta::new(*w_sol_token_account_pubkey, false),
+            AccountMeta::new_readonly(*w_sol_token_account_authority_pubkey, false),
+            AccountMeta::new(*temporary_w_sol_token_account_pubkey, false),
+            // The swap authorities are writable, a venue settled in lamports pays from and into them.
+            AccountMeta::new(*temporary_w_sol_token_account_authority_pubkey, false),
+            AccountMeta::new(*any_mint_token_account_pubkey, false),
+            AccountMeta::new(*any_mint_token_account_authority_pubkey, false),
+            AccountMeta::new(*temporary_any_mint_token_account_pubkey, false),
+            AccountMeta::new(*temporary_any_mint_token_account_authority_pubkey, false),
+            AccountMeta::new_readonly(spl_token::native_mint::ID, false),
+            AccountMeta::new(*any_mint_token_mint_account_pubkey, false),
+            AccountMeta::new_readonly(solana_program::system_program::ID, false),
+            AccountMeta::new_readonly(solana_program::sysvar::rent::ID, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new_readonly(*any_mint_token_program_pubkey, false),
+            AccountMeta::new_readonly(*allowed_mints_pubkey, false),
+            AccountMeta::new(*risk_limits_pubkey, false),
+            AccountMeta::new(*trader_seat_pubkey, false),
+            AccountMeta::new_readonly(*config_pubkey, false),
+            AccountMeta::new(*fee_vault_pubkey, false),
+        ];
+        accounts.append(
+            &mut crate::venue::create_account_metas(
+                venue,
+                venue_account_pubkeys,
+            )?,
+        );
+        Ok(
+            Instruction_ {
+                program_id: *program_id_pubkey,
+                accounts,
+                data: borsh::to_vec(
+                    &Self::Swap {
+                        venue,
+                        side,
+                        amount_in,
+                        min_out,
+                        any_mint_token_account_pubkey_bump_seed,
+                        any_mint_token_account_authority_pubkey_bump_seed,
+                        temporary_any_mint_token_account_pubkey_bump_seed,
+                        temporary_any_mint_token_account_authority_pubkey_bump_seed,
+                    },
+                )?,
+            },
+        )
+    }
+    pub fn routed_swap<'a>(
+        program_id_pubkey: &'a Pubkey,
+        intermediary_trader_pubkey: &'a Pubkey,
+        intermediary_pubkey: &'a Pubkey,
+        w_sol_token_account_pubkey: &'a Pubkey,
+        w_sol_token_account_authority_pubkey: &'a Pubkey,
+        temporary_w_sol_token_account_pubkey: &'a Pubkey,
+        any_mint_token_account_pubkey: &'a Pubkey,
+        any_mint_token_account_authority_pubkey: &'a Pubkey,
+        any_mint_token_mint_account_pubkey: &'a Pubkey,
+        any_mint_token_program_pubkey: &'a Pubkey,
+        allowed_mints_pubkey: &'a Pubkey,
+        risk_limits_pubkey: &'a Pubkey,
+        trader_seat_pubkey: &'a Pubkey,
+        config_pubkey: &'a Pubkey,
+        fee_vault_pubkey: &'a Pubkey,
+        route_hops: &'a [RouteHop],
+        route_legs: &'a [RouteLeg],
+        side: Side,
+        amount_in: u64,
+        min_out: u64,
+        any_mint_token_account_pubkey_bump_seed: u8,
+        any_mint_token_account_authority_pubkey_bump_seed: u8,
+    ) -> Result<Instruction_, Box<dyn Error + 'static>> {
+        let mut accounts = vec![
+            AccountMeta::new(*intermediary_trader_pubkey, true),
+            AccountMeta::new(*intermediary_pubkey, false),
+            AccountMeta::new(*w_sol_token_account_pubkey, false),
+            AccountMeta::new_readonly(*w_sol_token_account_authority_pubkey, false),
+            AccountMeta::new(*temporary_w_sol_token_account_pubkey, false),
+            AccountMeta::new(*any_mint_token_account_pubkey, false),
+            AccountMeta::new_readonly(*any_mint_token_account_authority_pubkey, false),
+            AccountMeta::new_readonly(spl_token::native_mint::ID, false),
+            AccountMeta::new(*any_mint_token_mint_account_pubkey, false),
+            AccountMeta::new_readonly(solana_program::system_program::ID, false),
+            AccountMeta::new_readonly(solana_program::sysvar::rent::ID, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new_readonly(*any_mint_token_program_pubkey, false),
+            AccountMeta::new_readonly(*allowed_mints_pubkey, false),
+            AccountMeta::new(*risk_limits_pubkey, false),
+            AccountMeta::new(*trader_seat_pubkey, false),
+            AccountMeta::new_readonly(*config_pubkey, false),
+            AccountMeta::new(*fee_vault_pubkey, false),
+        ];
+        let mut intermediate_token_account_pubkey_bump_seeds = Vec::<u8>::with_capacity(route_hops.len());
+        for route_hop in route_hops.iter() {
+            accounts.push(AccountMeta::new_readonly(route_hop.intermediate_token_mint_pubkey, false));
+            accounts.push(AccountMeta::new(route_hop.intermediate_token_account_pubkey, false));
+            intermediate_token_account_pubkey_bump_seeds.push(route_hop.intermediate_token_account_pubkey_bump_seed);
+        }
+        let mut venues = Vec::<Venue>::with_capacity(route_legs.len());
+        for route_leg in route_legs.iter() {
+            accounts.append(
+                &mut crate::venue::create_account_metas(
+                    route_leg.venue,
+                    route_leg.venue_account_pubkeys.as_slice(),
+                )?,
+            );
+            venues.push(route_leg.venue);
+        }
+        Ok(
+            Instruction_ {
+                program_id: *program_id_pubkey,
+                accounts,
+                data: borsh::to_vec(
+                    &Self::RoutedSwap {
+                        side,
+                        venues,
+                        amount_in,
+                        min_out,
+                        any_mint_token_account_pubkey_bump_seed,
+                        any_mint_token_account_authority_pubkey_bump_seed,
+                        intermediate_token_account_pubkey_bump_seeds,
+                    },
+                )?,
+            },
+        )
+    }
+}
+pub struct AnyMintTokenAccountPubkeys {
+    pub any_mint_token_mint_pubkey: Pubkey,
+    pub any_mint_token_account_pubkey: Pubkey,
+    pub any_mint_token_account_authority_pubkey: Pubkey,
+    pub any_mint_token_program_pubkey: Pubkey,
+}
+pub struct LiquidationLeg {
+    pub any_mint_token_mint_pubkey: Pubkey,
+    pub any_mint_token_account_pubkey: Pubkey,
+    pub any_mint_token_account_authority_pubkey: Pubkey,
+    pub any_mint_token_program_pubkey: Pubkey,
+    pub venue: Venue,
+    pub venue_account_pubkeys: Vec<Pubkey>,
+    pub minimum_output_token_amount: u64,
+}
+pub struct RouteHop {
+    pub intermediate_token_mint_pubkey: Pubkey,
+    pub intermediate_token_account_pubkey: Pubkey,
+    pub intermediate_token_account_pubkey_bump_seed: u8,
+}
+pub struct RouteLeg {
+    pub venue: Venue,
+    pub venue_account_pubkeys: Vec<Pubkey>,
 }
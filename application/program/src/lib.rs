@@ -1,10 +1,12 @@
 #[cfg(not(feature = "no-entrypoint"))]
 pub mod entrypoint;
 pub mod error;
+pub mod event;
 pub mod extern_source;
 pub mod instruction;
 pub mod processor;
 pub mod state;
+pub mod venue;
 use solana_program::pubkey::{
     Pubkey,
     PubkeyError,
@@ -22,16 +24,39 @@ pub const PUMPSWAP_PROGRAM_PUBKEY: Pubkey = {
     solana_program::declare_id!("pAMMBay6oceH9fJKBRHGP5D4bD4sWpmSwMn52FMfXEA");
     id()
 };
+pub const PUMPFUN_PROGRAM_PUBKEY: Pubkey = {
+    solana_program::declare_id!("6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P");
+    id()
+};
+// https://github.com/raydium-io/raydium-cp-swap
+pub const RAYDIUM_CPMM_PROGRAM_PUBKEY: Pubkey = {
+    solana_program::declare_id!("CPMMoo8L3F4NbTegBCKVNunggL7H1ZpdTHKxQB5qKP1C");
+    id()
+};
+// https://github.com/raydium-io/raydium-clmm
+pub const RAYDIUM_CLMM_PROGRAM_PUBKEY: Pubkey = {
+    solana_program::declare_id!("CAMMCzo5YL8w4VFF8KVHrK22GGUsp5VTaW7grrKgrWqK");
+    id()
+};
+// https://github.com/MeteoraAg/dlmm-sdk
+pub const METEORA_DLMM_PROGRAM_PUBKEY: Pubkey = {
+    solana_program::declare_id!("LBUZKhRxPF3XUpBCjp4YzTKgLccjZhTSDM9YuVaPwxo");
+    id()
+};
+// https://github.com/MeteoraAg/damm-v1-sdk
+pub const METEORA_DYNAMIC_AMM_PROGRAM_PUBKEY: Pubkey = {
+    solana_program::declare_id!("Eo7WjKq67rjJQSZxS6z3YkapzY3eMj6Xy8X5EQVn5UaB");
+    id()
+};
+pub const METEORA_VAULT_PROGRAM_PUBKEY: Pubkey = {
+    solana_program::declare_id!("24Uqj9JCLxUeoC3hGfh5W3s9FM9uCHDS2SG3LYwBpyTi");
+    id()
+};
+pub const SPL_MEMO_PROGRAM_PUBKEY: Pubkey = {
+    solana_program::declare_id!("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr");
+    id()
+};
 pub const SPL_ASSOCIATED_TOKEN_ACCOUNT_ID: Pubkey = Pubkey::new_from_array(spl_associated_token_account::ID.to_bytes());
-pub const FEE_INITIALIZE: u64 = 1010101; // TODO TODO unit works comission
-pub const FEE_DEPOSIT_FUNDS: u64 = 1010101; // TODO TODO unit works comission
-pub const FEE_WITHDRAW_FUNDS: u64 = 1010101; // TODO TODO unit works comission
-pub const FEE_BUY_ON_RAYDIUM: u64 = 1010101; // TODO TODO unit works comission
-pub const FEE_SELL_ON_RAYDIUM: u64 = 1010101; // TODO TODO unit works comission
-pub const FEE_BUY_ON_PUMPSWAP: u64 = 1010101; // TODO TODO unit works comission
-pub const FEE_SELL_ON_PUMPSWAP: u64 = 1010101; // TODO TODO unit works comission
-pub const FEE_CHANGE_MANAGER: u64 = 1010101; // TODO TODO unit works comission
-pub const FEE_CHANGE_TRADER: u64 = 1010101; // TODO TODO unit works comission
 pub trait ProgramDerivedAddress<'a> {
     fn find<'b>(
         &'b self,
@@ -159,3 +184,78 @@ impl<'a> ProgramDerivedAddress<'a> for TemporaryAnyMintTokenAccountAuthorityAddr
         ]
     }
 }
+pub struct IntermediateTokenAccountAddressResolver<'a> {
+    pub intermediate_token_mint_pubkey: &'a Pubkey,
+    pub any_mint_token_account_pubkey: &'a Pubkey,
+    pub intermediary_pubkey: &'a Pubkey,
+}
+impl<'a> ProgramDerivedAddress<'a> for IntermediateTokenAccountAddressResolver<'a> {
+    fn get_seeds<'b>(&'b self) -> Vec<&'a [u8]> {
+        vec![
+            self.intermediate_token_mint_pubkey.as_ref(),
+            self.any_mint_token_account_pubkey.as_ref(),
+            self.intermediary_pubkey.as_ref(),
+            PROGRAM_PUBKEY.as_ref(),
+            b"intermediate_token_account".as_slice(),
+        ]
+    }
+}
+pub struct ConfigAddressResolver;
+impl<'a> ProgramDerivedAddress<'a> for ConfigAddressResolver {
+    fn get_seeds<'b>(&'b self) -> Vec<&'a [u8]> {
+        vec![
+            PROGRAM_PUBKEY.as_ref(),
+            b"config".as_slice(),
+        ]
+    }
+}
+pub struct FeeVaultAddressResolver<'a> {
+    pub config_pubkey: &'a Pubkey,
+}
+impl<'a> ProgramDerivedAddress<'a> for FeeVaultAddressResolver<'a> {
+    fn get_seeds<'b>(&'b self) -> Vec<&'a [u8]> {
+        vec![
+            self.config_pubkey.as_ref(),
+            PROGRAM_PUBKEY.as_ref(),
+            b"fee_vault".as_slice(),
+        ]
+    }
+}
+pub struct RiskLimitsAddressResolver<'a> {
+    pub intermediary_pubkey: &'a Pubkey,
+}
+impl<'a> ProgramDerivedAddress<'a> for RiskLimitsAddressResolver<'a> {
+    fn get_seeds<'b>(&'b self) -> Vec<&'a [u8]> {
+        vec![
+            self.intermediary_pubkey.as_ref(),
+            PROGRAM_PUBKEY.as_ref(),
+            b"risk_limits".as_slice(),
+        ]
+    }
+}
+pub struct AllowedMintsAddressResolver<'a> {
+    pub intermediary_pubkey: &'a Pubkey,
+}
+impl<'a> ProgramDerivedAddress<'a> for AllowedMintsAddressResolver<'a> {
+    fn get_seeds<'b>(&'b self) -> Vec<&'a [u8]> {
+        vec![
+            self.intermediary_pubkey.as_ref(),
+            PROGRAM_PUBKEY.as_ref(),
+            b"allowed_mints".as_slice(),
+        ]
+    }
+}
+pub struct TraderSeatAddressResolver<'a> {
+    pub intermediary_pubkey: &'a Pubkey,
+    pub trader_pubkey: &'a Pubkey,
+}
+impl<'a> ProgramDerivedAddress<'a> for TraderSeatAddressResolver<'a> {
+    fn get_seeds<'b>(&'b self) -> Vec<&'a [u8]> {
+        vec![
+            self.intermediary_pubkey.as_ref(),
+            self.trader_pubkey.as_ref(),
+            PROGRAM_PUBKEY.as_ref(),
+            b"trader_seat".as_slice(),
+        ]
+    }
+}
@@ -1,17 +1,28 @@
 use {
     crate::{
-        error::Error, instruction::Instruction, state::Intermediary, AnyMintTokenAccountAddressResolver, AnyMintTokenAccountAuthorityAddressResolver, ProgramDerivedAddress, TemporaryAnyMintTokenAccountAddressResolver, TemporaryAnyMintTokenAccountAuthorityAddressResolver, TemporaryWSolTokenAccountAddressResolver, TemporaryWSolTokenAccountAuthorityAddressResolver, WSolTokenAccountAddressResolver, WSolTokenAccountAuthorityAddressResolver, FEE_BUY_ON_PUMPSWAP, FEE_BUY_ON_RAYDIUM, FEE_CHANGE_MANAGER, FEE_CHANGE_TRADER, FEE_DEPOSIT_FUNDS, FEE_INITIALIZE, FEE_SELL_ON_PUMPSWAP, FEE_SELL_ON_RAYDIUM, FEE_WITHDRAW_FUNDS, PROGRAM_PUBKEY, PUMPSWAP_PROGRAM_PUBKEY, RAYDIUM_LIQUIDITY_POOL_V4_PROGRAM_PUBKEY, SPL_ASSOCIATED_TOKEN_ACCOUNT_ID
+        error::Error, event::{Event, Role, Side, Venue}, instruction::Instruction, AllowedMintsAddressResolver, state::{AllowedMints, Config, Fees, Intermediary, RiskLimits, TraderSeat}, AnyMintTokenAccountAddressResolver, AnyMintTokenAccountAuthorityAddressResolver, ConfigAddressResolver, FeeVaultAddressResolver, IntermediateTokenAccountAddressResolver, ProgramDerivedAddress, RiskLimitsAddressResolver, TemporaryAnyMintTokenAccountAddressResolver, TemporaryAnyMintTokenAccountAuthorityAddressResolver, TemporaryWSolTokenAccountAddressResolver, TemporaryWSolTokenAccountAuthorityAddressResolver, TraderSeatAddressResolver, venue::{AnyVenueAdapter, MeteoraDlmmAdapter, MeteoraDynamicAmmAdapter, PumpfunCurveAdapter, PumpswapAdapter, RaydiumAdapter, RaydiumClmmAdapter, RaydiumCpmmAdapter, VenueAdapter, VenueSwap}, WSolTokenAccountAddressResolver, WSolTokenAccountAuthorityAddressResolver, PROGRAM_PUBKEY, SPL_ASSOCIATED_TOKEN_ACCOUNT_ID
     },
     borsh::BorshDeserialize,
     solana_program::{
         account_info::AccountInfo,
+        clock::Clock,
         entrypoint::ProgramResult,
+        program_error::ProgramError,
         program_pack::Pack,
         pubkey::Pubkey,
         rent::Rent,
         sysvar::Sysvar,
     },
     spl_token::state::Account,
+    spl_token_2022::extension::{
+        transfer_fee::{
+            TransferFeeAmount,
+            TransferFeeConfig,
+        },
+        BaseStateWithExtensions,
+        ExtensionType,
+        StateWithExtensions,
+    },
     std::collections::HashSet,
 };
 pub struct Processor;
@@ -61,6 +72,7 @@ impl Processor {
                 minimum_output_token_amount,
                 any_mint_token_account_pubkey_bump_seed,
                 any_mint_token_account_authority_pubkey_bump_seed,
+                false,
             ),
             Instruction::SellOnRaydium {
                 input_token_amount,
@@ -78,38 +90,386 @@ impl Processor {
                 any_mint_token_account_authority_pubkey_bump_seed,
                 temporary_any_mint_token_account_pubkey_bump_seed,
                 temporary_any_mint_token_account_authority_pubkey_bump_seed,
+                false,
             ),
             Instruction::BuyOnPumpswap {
                 base_amount_out,
                 maximum_quote_amount_in,
-                any_mint_token_account_pubkey_bump_seed
+                any_mint_token_account_pubkey_bump_seed,
+                any_mint_token_account_authority_pubkey_bump_seed,
             } => Self::buy_on_pumpswap(
                 program_id,
                 accounts,
                 base_amount_out,
                 maximum_quote_amount_in,
                 any_mint_token_account_pubkey_bump_seed,
+                any_mint_token_account_authority_pubkey_bump_seed,
             ),
             Instruction::SellOnPumpswap {
                 input_token_amount,
                 minimum_output_token_amount,
                 any_mint_token_account_pubkey_bump_seed,
+                any_mint_token_account_authority_pubkey_bump_seed,
                 temporary_any_mint_token_account_pubkey_bump_seed,
+                temporary_any_mint_token_account_authority_pubkey_bump_seed,
             } => Self::sell_on_pumpswap(
                 program_id,
                 accounts,
                 input_token_amount,
                 minimum_output_token_amount,
                 any_mint_token_account_pubkey_bump_seed,
+                any_mint_token_account_authority_pubkey_bump_seed,
+                temporary_any_mint_token_account_pubkey_bump_seed,
+                temporary_any_mint_token_account_authority_pubkey_bump_seed,
+            ),
+            Instruction::ProposeManager => Self::propose_manager(
+                program_id,
+                accounts,
+            ),
+            Instruction::ProposeTrader => Self::propose_trader(
+                program_id,
+                accounts,
+            ),
+            Instruction::InitializeConfig {
+                fees,
+                config_pubkey_bump_seed,
+                fee_vault_pubkey_bump_seed,
+            } => Self::initialize_config(
+                program_id,
+                accounts,
+                fees,
+                config_pubkey_bump_seed,
+                fee_vault_pubkey_bump_seed,
+            ),
+            Instruction::UpdateConfig {
+                fees,
+            } => Self::update_config(
+                program_id,
+                accounts,
+                fees,
+            ),
+            Instruction::SweepFees => Self::sweep_fees(
+                program_id,
+                accounts,
+            ),
+            Instruction::BuyOnRaydiumExactOut {
+                maximum_amount_in,
+                amount_out,
+                any_mint_token_account_pubkey_bump_seed,
+                any_mint_token_account_authority_pubkey_bump_seed,
+            } => Self::buy_on_raydium(
+                program_id,
+                accounts,
+                maximum_amount_in,
+                amount_out,
+                any_mint_token_account_pubkey_bump_seed,
+                any_mint_token_account_authority_pubkey_bump_seed,
+                true,
+            ),
+            Instruction::SellOnRaydiumExactOut {
+                maximum_amount_in,
+                amount_out,
+                any_mint_token_account_pubkey_bump_seed,
+                any_mint_token_account_authority_pubkey_bump_seed,
+                temporary_any_mint_token_account_pubkey_bump_seed,
+                temporary_any_mint_token_account_authority_pubkey_bump_seed,
+            } => Self::sell_on_raydium(
+                program_id,
+                accounts,
+                maximum_amount_in,
+                Some(amount_out),
+                any_mint_token_account_pubkey_bump_seed,
+                any_mint_token_account_authority_pubkey_bump_seed,
+                temporary_any_mint_token_account_pubkey_bump_seed,
+                temporary_any_mint_token_account_authority_pubkey_bump_seed,
+                true,
+            ),
+            Instruction::SetRiskLimits {
+                maximum_lamports_per_trade,
+                maximum_treasury_share_per_trade_basis_points,
+                maximum_trades_per_slot_window,
+                slot_window_length,
+                risk_limits_pubkey_bump_seed,
+            } => Self::set_risk_limits(
+                program_id,
+                accounts,
+                maximum_lamports_per_trade,
+                maximum_treasury_share_per_trade_basis_points,
+                maximum_trades_per_slot_window,
+                slot_window_length,
+                risk_limits_pubkey_bump_seed,
+            ),
+            Instruction::AddAllowedMint {
+                allowed_mints_pubkey_bump_seed,
+            } => Self::add_allowed_mint(
+                program_id,
+                accounts,
+                allowed_mints_pubkey_bump_seed,
+            ),
+            Instruction::RemoveAllowedMint => Self::remove_allowed_mint(
+                program_id,
+                accounts,
+            ),
+            Instruction::CloseIntermediary {
+                any_mint_token_accounts_quantity,
+            } => Self::close_intermediary(
+                program_id,
+                accounts,
+                any_mint_token_accounts_quantity,
+            ),
+            Instruction::AcceptManager => Self::accept_manager(
+                program_id,
+                accounts,
+            ),
+            Instruction::AcceptTrader => Self::accept_trader(
+                program_id,
+                accounts,
+            ),
+            Instruction::CancelManagerProposal => Self::cancel_manager_proposal(
+                program_id,
+                accounts,
+            ),
+            Instruction::CancelTraderProposal => Self::cancel_trader_proposal(
+                program_id,
+                accounts,
+            ),
+            Instruction::AddTraderSeat {
+                allocated_lamports,
+                trader_seat_pubkey_bump_seed,
+            } => Self::add_trader_seat(
+                program_id,
+                accounts,
+                allocated_lamports,
+                trader_seat_pubkey_bump_seed,
+            ),
+            Instruction::RemoveTraderSeat => Self::remove_trader_seat(
+                program_id,
+                accounts,
+            ),
+            Instruction::Pause => Self::pause(
+                program_id,
+                accounts,
+            ),
+            Instruction::Unpause => Self::unpause(
+                program_id,
+                accounts,
+            ),
+            Instruction::MigrateIntermediary => Self::migrate_intermediary(
+                program_id,
+                accounts,
+            ),
+            Instruction::LiquidateAll {
+                venues,
+                minimum_output_token_amounts,
+            } => Self::liquidate_all(
+                program_id,
+                accounts,
+                venues,
+                minimum_output_token_amounts,
+            ),
+            Instruction::WithdrawToken {
+                amount,
+            } => Self::withdraw_token(
+                program_id,
+                accounts,
+                amount,
+            ),
+            Instruction::BuyOnRaydiumCpmm {
+                input_token_amount,
+                minimum_output_token_amount,
+                any_mint_token_account_pubkey_bump_seed,
+                any_mint_token_account_authority_pubkey_bump_seed,
+            } => Self::buy_on_raydium_cpmm(
+                program_id,
+                accounts,
+                input_token_amount,
+                minimum_output_token_amount,
+                any_mint_token_account_pubkey_bump_seed,
+                any_mint_token_account_authority_pubkey_bump_seed,
+            ),
+            Instruction::SellOnRaydiumCpmm {
+                input_token_amount,
+                minimum_output_token_amount,
+                any_mint_token_account_pubkey_bump_seed,
+                any_mint_token_account_authority_pubkey_bump_seed,
+                temporary_any_mint_token_account_pubkey_bump_seed,
+                temporary_any_mint_token_account_authority_pubkey_bump_seed,
+            } => Self::sell_on_raydium_cpmm(
+                program_id,
+                accounts,
+                input_token_amount,
+                minimum_output_token_amount,
+                any_mint_token_account_pubkey_bump_seed,
+                any_mint_token_account_authority_pubkey_bump_seed,
+                temporary_any_mint_token_account_pubkey_bump_seed,
+                temporary_any_mint_token_account_authority_pubkey_bump_seed,
+            ),
+            Instruction::BuyOnRaydiumClmm {
+                input_token_amount,
+                minimum_output_token_amount,
+                any_mint_token_account_pubkey_bump_seed,
+                any_mint_token_account_authority_pubkey_bump_seed,
+            } => Self::buy_on_raydium_clmm(
+                program_id,
+                accounts,
+                input_token_amount,
+                minimum_output_token_amount,
+                any_mint_token_account_pubkey_bump_seed,
+                any_mint_token_account_authority_pubkey_bump_seed,
+            ),
+            Instruction::SellOnRaydiumClmm {
+                input_token_amount,
+                minimum_output_token_amount,
+                any_mint_token_account_pubkey_bump_seed,
+                any_mint_token_account_authority_pubkey_bump_seed,
+                temporary_any_mint_token_account_pubkey_bump_seed,
+                temporary_any_mint_token_account_authority_pubkey_bump_seed,
+            } => Self::sell_on_raydium_clmm(
+                program_id,
+                accounts,
+                input_token_amount,
+                minimum_output_token_amount,
+                any_mint_token_account_pubkey_bump_seed,
+                any_mint_token_account_authority_pubkey_bump_seed,
+                temporary_any_mint_token_account_pubkey_bump_seed,
+                temporary_any_mint_token_account_authority_pubkey_bump_seed,
+            ),
+            Instruction::BuyOnMeteoraDlmm {
+                input_token_amount,
+                minimum_output_token_amount,
+                any_mint_token_account_pubkey_bump_seed,
+                any_mint_token_account_authority_pubkey_bump_seed,
+            } => Self::buy_on_meteora_dlmm(
+                program_id,
+                accounts,
+                input_token_amount,
+                minimum_output_token_amount,
+                any_mint_token_account_pubkey_bump_seed,
+                any_mint_token_account_authority_pubkey_bump_seed,
+            ),
+            Instruction::SellOnMeteoraDlmm {
+                input_token_amount,
+                minimum_output_token_amount,
+                any_mint_token_account_pubkey_bump_seed,
+                any_mint_token_account_authority_pubkey_bump_seed,
+                temporary_any_mint_token_account_pubkey_bump_seed,
+                temporary_any_mint_token_account_authority_pubkey_bump_seed,
+            } => Self::sell_on_meteora_dlmm(
+                program_id,
+                accounts,
+                input_token_amount,
+                minimum_output_token_amount,
+                any_mint_token_account_pubkey_bump_seed,
+                any_mint_token_account_authority_pubkey_bump_seed,
+                temporary_any_mint_token_account_pubkey_bump_seed,
+                temporary_any_mint_token_account_authority_pubkey_bump_seed,
+            ),
+            Instruction::BuyOnMeteoraDynamicAmm {
+                input_token_amount,
+                minimum_output_token_amount,
+                any_mint_token_account_pubkey_bump_seed,
+                any_mint_token_account_authority_pubkey_bump_seed,
+            } => Self::buy_on_meteora_dynamic_amm(
+                program_id,
+                accounts,
+                input_token_amount,
+                minimum_output_token_amount,
+                any_mint_token_account_pubkey_bump_seed,
+                any_mint_token_account_authority_pubkey_bump_seed,
+            ),
+            Instruction::SellOnMeteoraDynamicAmm {
+                input_token_amount,
+                minimum_output_token_amount,
+                any_mint_token_account_pubkey_bump_seed,
+                any_mint_token_account_authority_pubkey_bump_seed,
+                temporary_any_mint_token_account_pubkey_bump_seed,
+                temporary_any_mint_token_account_authority_pubkey_bump_seed,
+            } => Self::sell_on_meteora_dynamic_amm(
+                program_id,
+                accounts,
+                input_token_amount,
+                minimum_output_token_amount,
+                any_mint_token_account_pubkey_bump_seed,
+                any_mint_token_account_authority_pubkey_bump_seed,
+                temporary_any_mint_token_account_pubkey_bump_seed,
+                temporary_any_mint_token_account_authority_pubkey_bump_seed,
+            ),
+            Instruction::BuyOnPumpfunCurve {
+                token_amount_out,
+                maximum_lamports_in,
+                any_mint_token_account_pubkey_bump_seed,
+                any_mint_token_account_authority_pubkey_bump_seed,
+            } => Self::buy_on_pumpfun_curve(
+                program_id,
+                accounts,
+                token_amount_out,
+                maximum_lamports_in,
+                any_mint_token_account_pubkey_bump_seed,
+                any_mint_token_account_authority_pubkey_bump_seed,
+            ),
+            Instruction::SellOnPumpfunCurve {
+                input_token_amount,
+                minimum_output_token_amount,
+                any_mint_token_account_pubkey_bump_seed,
+                any_mint_token_account_authority_pubkey_bump_seed,
+                temporary_any_mint_token_account_pubkey_bump_seed,
+                temporary_any_mint_token_account_authority_pubkey_bump_seed,
+            } => Self::sell_on_pumpfun_curve(
+                program_id,
+                accounts,
+                input_token_amount,
+                minimum_output_token_amount,
+                any_mint_token_account_pubkey_bump_seed,
+                any_mint_token_account_authority_pubkey_bump_seed,
+                temporary_any_mint_token_account_pubkey_bump_seed,
+                temporary_any_mint_token_account_authority_pubkey_bump_seed,
+            ),
+            Instruction::Swap {
+                venue,
+                side,
+                amount_in,
+                min_out,
+                any_mint_token_account_pubkey_bump_seed,
+                any_mint_token_account_authority_pubkey_bump_seed,
+                temporary_any_mint_token_account_pubkey_bump_seed,
+                temporary_any_mint_token_account_authority_pubkey_bump_seed,
+            } => Self::swap(
+                program_id,
+                accounts,
+                venue,
+                side,
+                amount_in,
+                min_out,
+                any_mint_token_account_pubkey_bump_seed,
+                any_mint_token_account_authority_pubkey_bump_seed,
                 temporary_any_mint_token_account_pubkey_bump_seed,
+                temporary_any_mint_token_account_authority_pubkey_bump_seed,
             ),
-            Instruction::ChangeManager => Self::change_manager(
+            Instruction::RoutedSwap {
+                side,
+                venues,
+                amount_in,
+                min_out,
+                any_mint_token_account_pubkey_bump_seed,
+                any_mint_token_account_authority_pubkey_bump_seed,
+                intermediate_token_account_pubkey_bump_seeds,
+            } => Self::routed_swap(
                 program_id,
                 accounts,
+                side,
+                venues,
+                amount_in,
+                min_out,
+                any_mint_token_account_pubkey_bump_seed,
+                any_mint_token_account_authority_pubkey_bump_seed,
+                intermediate_token_account_pubkey_bump_seeds,
             ),
-            Instruction::ChangeTrader => Self::change_trader(
+            Instruction::SetMaximumPriceDeviation {
+                maximum_price_deviation_basis_points,
+            } => Self::set_maximum_price_deviation(
                 program_id,
                 accounts,
+                maximum_price_deviation_basis_points,
             ),
         }
     }
@@ -135,6 +495,8 @@ impl Processor {
         let system_program_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
         let rent_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
         let token_program_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let config_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let fee_vault_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
         let w_sol_token_account_address_resolver = WSolTokenAccountAddressResolver {
             intermediary_pubkey: intermediary_account_info.key,
         };
@@ -149,7 +511,7 @@ impl Processor {
             temporary_w_sol_token_account_pubkey: temporary_w_sol_token_account_account_info.key,
             intermediary_pubkey: intermediary_account_info.key,
         };
-        let mut account_differentiator = HashSet::<&'_ Pubkey>::with_capacity(12);
+        let mut account_differentiator = HashSet::<&'_ Pubkey>::with_capacity(14);
         if !account_differentiator.insert(intermediary_investor_account_info.key)
             || !account_differentiator.insert(intermediary_account_info.key)
             || !account_differentiator.insert(intermediary_manager_account_info.key)
@@ -162,6 +524,8 @@ impl Processor {
             || !account_differentiator.insert(system_program_account_info.key)
             || !account_differentiator.insert(rent_account_info.key)
             || !account_differentiator.insert(token_program_account_info.key)
+            || !account_differentiator.insert(config_account_info.key)
+            || !account_differentiator.insert(fee_vault_account_info.key)
             || *w_sol_token_account_account_info.key != w_sol_token_account_address_resolver.create(w_sol_token_account_pubkey_bump_seed)?
             || *w_sol_token_account_authority_info.key != w_sol_token_account_authority_address_resolver.create(w_sol_token_account_authority_pubkey_bump_seed)?
             || *temporary_w_sol_token_account_account_info.key != temporary_w_sol_token_account_address_resolver.create(temporary_w_sol_token_account_pubkey_bump_seed)?
@@ -179,6 +543,7 @@ impl Processor {
             || !intermediary_account_info.is_writable
             || !intermediary_account_info.is_signer
             || !w_sol_token_account_account_info.is_writable
+            || !fee_vault_account_info.is_writable
         {
             return Err(Error::InvalidAccountConfigurationFlags.into());
         }
@@ -197,6 +562,7 @@ impl Processor {
             return Err(Error::InvalidAccountData.into());
         }
         let intermediary = Intermediary {
+            version: Intermediary::VERSION,
             is_initialized: true,
             investor_pubkey: *intermediary_investor_account_info.key,
             manager_pubkey: *intermediary_manager_account_info.key,
@@ -208,17 +574,56 @@ impl Processor {
             w_sol_token_account_authority_pubkey_bump_seed,
             temporary_w_sol_token_account_pubkey_bump_seed,
             temporary_w_sol_token_account_authority_pubkey_bump_seed,
+            pending_manager_pubkey: Pubkey::default(),
+            pending_trader_pubkey: Pubkey::default(),
+            is_paused: false,
+            maximum_price_deviation_basis_points: 0,
+            open_positions_quantity: 0,
+            trader_seats_quantity: 0,
+            padding: [0; Intermediary::PADDING_LENGTH],
         };
         let intermediary_object_length = borsh::object_length(&intermediary)?;
         let rent = Rent::from_account_info(rent_account_info)?;
         let intermediary_rent_exemption_balance = rent.minimum_balance(intermediary_object_length);
         let token_account_rent_exemption_balance = rent.minimum_balance(<Account as Pack>::LEN);
-        if intermediary_investor_account_info.lamports() < (intermediary_rent_exemption_balance + token_account_rent_exemption_balance + lamports_to_treasury + FEE_INITIALIZE)       // TODO все просчитать
+        if *config_account_info.owner != PROGRAM_PUBKEY {
+            return Err(Error::InvalidAccountOwner.into());
+        }
+        let config = borsh::from_slice::<Config>(&config_account_info.data.borrow())?;
+        if !config.is_initialized {
+            return Err(Error::ConfigIsNotInitialized.into());
+        }
+        if *config_account_info.key != ConfigAddressResolver.create(config.config_pubkey_bump_seed)? {
+            return Err(Error::InvalidAccountPubkey.into());
+        }
+        if *fee_vault_account_info.key != config.fee_vault_pubkey {
+            return Err(Error::ConfigInvalidFeeVault.into());
+        }
+        if intermediary_investor_account_info.lamports() < (intermediary_rent_exemption_balance + token_account_rent_exemption_balance + lamports_to_treasury + config.fees.initialize)       // TODO все просчитать
         || intermediary_manager_account_info.lamports() == 0
         || intermediary_trader_account_info.lamports() == 0
         {
             return Err(Error::InvalidAccountLamports.into());
         }
+        solana_program::program::invoke(
+            &solana_program::system_instruction::transfer(
+                intermediary_investor_account_info.key,
+                fee_vault_account_info.key,
+                config.fees.initialize,
+            ),
+            vec![
+                intermediary_investor_account_info.clone(),
+                fee_vault_account_info.clone(),
+            ]
+            .as_slice(),
+        )?;
+        Event::FeeCharged {
+            intermediary_pubkey: *intermediary_account_info.key,
+            payer_pubkey: *intermediary_investor_account_info.key,
+            fee_vault_pubkey: *fee_vault_account_info.key,
+            lamports: config.fees.initialize,
+        }
+        .emit()?;
         solana_program::program::invoke(
             &solana_program::system_instruction::create_account(
                 intermediary_investor_account_info.key,
@@ -277,6 +682,15 @@ impl Processor {
         if w_sol_token_account.amount != lamports_to_treasury {
             return Err(Error::TokenAccountInvalidAmount.into());
         }
+        Event::Initialized {
+            intermediary_pubkey: *intermediary_account_info.key,
+            investor_pubkey: *intermediary_investor_account_info.key,
+            manager_pubkey: *intermediary_manager_account_info.key,
+            trader_pubkey: *intermediary_trader_account_info.key,
+            lamports_to_treasury,
+            treasury_amount: w_sol_token_account.amount,
+        }
+        .emit()?;
         Ok(())
     }
     fn deposit_funds<'a>(_program_id: &'a Pubkey, accounts: &'a [AccountInfo], lamports_to_treasury: u64) -> ProgramResult {
@@ -286,24 +700,64 @@ impl Processor {
         let w_sol_token_account_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
         let system_program_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
         let token_program_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
-        let mut account_differentiator = HashSet::<&'_ Pubkey>::with_capacity(5);
+        let config_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let fee_vault_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let mut account_differentiator = HashSet::<&'_ Pubkey>::with_capacity(7);
         if !account_differentiator.insert(intermediary_investor_account_info.key)
             || !account_differentiator.insert(intermediary_account_info.key)
             || !account_differentiator.insert(w_sol_token_account_account_info.key)
             || !account_differentiator.insert(system_program_account_info.key)
             || !account_differentiator.insert(token_program_account_info.key)
+            || !account_differentiator.insert(config_account_info.key)
+            || !account_differentiator.insert(fee_vault_account_info.key)
             || *system_program_account_info.key != solana_program::system_program::ID
             || *token_program_account_info.key != spl_token::ID
         {
             return Err(Error::InvalidAccountPubkey.into());
         }
-        if !intermediary_investor_account_info.is_writable || !intermediary_investor_account_info.is_signer || !w_sol_token_account_account_info.is_writable {
+        if !intermediary_investor_account_info.is_writable
+            || !intermediary_investor_account_info.is_signer
+            || !w_sol_token_account_account_info.is_writable
+            || !fee_vault_account_info.is_writable
+        {
             return Err(Error::InvalidAccountConfigurationFlags.into());
         }
-        if intermediary_investor_account_info.lamports() < (lamports_to_treasury + FEE_DEPOSIT_FUNDS) {
-            return Err(Error::InvalidAccountLamports.into());
+        if *config_account_info.owner != PROGRAM_PUBKEY {
+            return Err(Error::InvalidAccountOwner.into());
+        }
+        let config = borsh::from_slice::<Config>(&config_account_info.data.borrow())?;
+        if !config.is_initialized {
+            return Err(Error::ConfigIsNotInitialized.into());
+        }
+        if *config_account_info.key != ConfigAddressResolver.create(config.config_pubkey_bump_seed)? {
+            return Err(Error::InvalidAccountPubkey.into());
+        }
+        if *fee_vault_account_info.key != config.fee_vault_pubkey {
+            return Err(Error::ConfigInvalidFeeVault.into());
+        }
+        if intermediary_investor_account_info.lamports() < (lamports_to_treasury + config.fees.deposit_funds) {
+            return Err(Error::InvalidAccountLamports.into());
+        }
+        solana_program::program::invoke(
+            &solana_program::system_instruction::transfer(
+                intermediary_investor_account_info.key,
+                fee_vault_account_info.key,
+                config.fees.deposit_funds,
+            ),
+            vec![
+                intermediary_investor_account_info.clone(),
+                fee_vault_account_info.clone(),
+            ]
+            .as_slice(),
+        )?;
+        Event::FeeCharged {
+            intermediary_pubkey: *intermediary_account_info.key,
+            payer_pubkey: *intermediary_investor_account_info.key,
+            fee_vault_pubkey: *fee_vault_account_info.key,
+            lamports: config.fees.deposit_funds,
         }
-        let intermediary = borsh::from_slice::<Intermediary>(&intermediary_account_info.data.borrow())?;
+        .emit()?;
+        let intermediary = Intermediary::load(&intermediary_account_info.data.borrow())?;
         if !intermediary.is_initialized {
             return Err(Error::IntermediaryIsNotInitialized.into());
         }
@@ -335,6 +789,12 @@ impl Processor {
             ]
             .as_slice(),
         )?;
+        Event::FundsDeposited {
+            intermediary_pubkey: *intermediary_account_info.key,
+            lamports_to_treasury,
+            treasury_amount: Account::unpack_unchecked(&w_sol_token_account_account_info.data.borrow())?.amount,
+        }
+        .emit()?;
         Ok(())
     }
     fn withdraw_funds<'a>(_program_id: &'a Pubkey, accounts: &'a [AccountInfo], lamports_from_treasury: u64) -> ProgramResult {
@@ -349,7 +809,9 @@ impl Processor {
         let system_program_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
         let rent_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
         let token_program_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
-        let mut account_differentiator = HashSet::<&'_ Pubkey>::with_capacity(10);
+        let config_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let fee_vault_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let mut account_differentiator = HashSet::<&'_ Pubkey>::with_capacity(12);
         if !account_differentiator.insert(intermediary_investor_account_info.key)
             || !account_differentiator.insert(intermediary_account_info.key)
             || !account_differentiator.insert(w_sol_token_account_account_info.key)
@@ -360,6 +822,8 @@ impl Processor {
             || !account_differentiator.insert(system_program_account_info.key)
             || !account_differentiator.insert(rent_account_info.key)
             || !account_differentiator.insert(token_program_account_info.key)
+            || !account_differentiator.insert(config_account_info.key)
+            || !account_differentiator.insert(fee_vault_account_info.key)
             || *w_sol_token_mint_account_info.key != spl_token::native_mint::ID
             || *system_program_account_info.key != solana_program::system_program::ID
             || *rent_account_info.key != solana_program::sysvar::rent::ID
@@ -371,10 +835,11 @@ impl Processor {
             || !intermediary_investor_account_info.is_signer
             || !w_sol_token_account_account_info.is_writable
             || !temporary_w_sol_token_account_account_info.is_writable
+            || !fee_vault_account_info.is_writable
         {
             return Err(Error::InvalidAccountConfigurationFlags.into());
         }
-        let intermediary = borsh::from_slice::<Intermediary>(&intermediary_account_info.data.borrow())?;
+        let intermediary = Intermediary::load(&intermediary_account_info.data.borrow())?;
         if !intermediary.is_initialized {
             return Err(Error::IntermediaryIsNotInitialized.into());
         }
@@ -399,9 +864,41 @@ impl Processor {
         }
         let rent = Rent::from_account_info(rent_account_info)?;
         let token_account_rent_exemption_balance = rent.minimum_balance(<Account as Pack>::LEN);
-        if intermediary_investor_account_info.lamports() < (token_account_rent_exemption_balance + FEE_WITHDRAW_FUNDS) {
+        if *config_account_info.owner != PROGRAM_PUBKEY {
+            return Err(Error::InvalidAccountOwner.into());
+        }
+        let config = borsh::from_slice::<Config>(&config_account_info.data.borrow())?;
+        if !config.is_initialized {
+            return Err(Error::ConfigIsNotInitialized.into());
+        }
+        if *config_account_info.key != ConfigAddressResolver.create(config.config_pubkey_bump_seed)? {
+            return Err(Error::InvalidAccountPubkey.into());
+        }
+        if *fee_vault_account_info.key != config.fee_vault_pubkey {
+            return Err(Error::ConfigInvalidFeeVault.into());
+        }
+        if intermediary_investor_account_info.lamports() < (token_account_rent_exemption_balance + config.fees.withdraw_funds) {
             return Err(Error::InvalidAccountLamports.into());
         }
+        solana_program::program::invoke(
+            &solana_program::system_instruction::transfer(
+                intermediary_investor_account_info.key,
+                fee_vault_account_info.key,
+                config.fees.withdraw_funds,
+            ),
+            vec![
+                intermediary_investor_account_info.clone(),
+                fee_vault_account_info.clone(),
+            ]
+            .as_slice(),
+        )?;
+        Event::FeeCharged {
+            intermediary_pubkey: *intermediary_account_info.key,
+            payer_pubkey: *intermediary_investor_account_info.key,
+            fee_vault_pubkey: *fee_vault_account_info.key,
+            lamports: config.fees.withdraw_funds,
+        }
+        .emit()?;
         let temporary_w_sol_token_account_address_resolver = TemporaryWSolTokenAccountAddressResolver {
             w_sol_token_account_pubkey: w_sol_token_account_account_info.key,
         };
@@ -485,15 +982,22 @@ impl Processor {
             .as_slice(),
             [temporary_w_sol_token_account_authority_pubkey_seeds.as_slice()].as_slice(),
         )?;
+        Event::FundsWithdrawn {
+            intermediary_pubkey: *intermediary_account_info.key,
+            lamports_from_treasury,
+            treasury_amount: Account::unpack_unchecked(&w_sol_token_account_account_info.data.borrow())?.amount,
+        }
+        .emit()?;
         Ok(())
     }
-    fn buy_on_raydium<'a>(
+    fn buy_on_raydium<'a, 'b>(
         _program_id: &'a Pubkey,
-        accounts: &'a [AccountInfo],
+        accounts: &'a [AccountInfo<'b>],
         input_token_amount: u64,
         minimum_output_token_amount: u64,
         any_mint_token_account_pubkey_bump_seed: u8,
         any_mint_token_account_authority_pubkey_bump_seed: u8,
+        is_exact_out: bool,
     ) -> ProgramResult {
         let account_info_iter = &mut accounts.iter();
         let intermediary_trader_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
@@ -523,258 +1027,70 @@ impl Processor {
         let market_coin_vault_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
         let market_pc_vault_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
         let market_vault_signer_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
-        let any_mint_token_account_address_resolver = AnyMintTokenAccountAddressResolver {
-            any_mint_token_mint_pubkey: any_mint_token_mint_account_info.key,
-            intermediary_pubkey: intermediary_account_info.key,
-        };
-        let any_mint_token_account_authority_address_resolver = AnyMintTokenAccountAuthorityAddressResolver {
-            any_mint_token_account_pubkey: any_mint_token_account_account_info.key,
-            intermediary_pubkey: intermediary_account_info.key,
-        };
-        if *any_mint_token_account_account_info.key != any_mint_token_account_address_resolver.create(any_mint_token_account_pubkey_bump_seed)?
-            || *any_mint_token_account_authority_account_info.key != any_mint_token_account_authority_address_resolver.create(any_mint_token_account_authority_pubkey_bump_seed)?
-            || w_sol_token_mint_account_info.key == any_mint_token_mint_account_info.key
-            || *w_sol_token_mint_account_info.key != spl_token::native_mint::ID
-            || *system_program_account_info.key != solana_program::system_program::ID
-            || *rent_account_info.key != solana_program::sysvar::rent::ID
-            || *token_program_account_info.key != spl_token::ID
-            || *amm_program_id.key != RAYDIUM_LIQUIDITY_POOL_V4_PROGRAM_PUBKEY
-        {
-            return Err(Error::InvalidAccountPubkey.into());
-        }
-        if !intermediary_trader_account_info.is_signer
-            || !intermediary_trader_account_info.is_writable
-            || !w_sol_token_account_account_info.is_writable
-            || !temporary_w_sol_token_account_account_info.is_writable
-            || !any_mint_token_account_account_info.is_writable
-            || !amm_pool_account_info.is_writable
-            || !amm_open_orders_account_info.is_writable
-            || !amm_coin_vault_account_info.is_writable
-            || !amm_pc_vault_account_info.is_writable
-            || !market_account_info.is_writable
-            || !market_bids_account_info.is_writable
-            || !market_asks_account_info.is_writable
-            || !market_event_queue_account_info.is_writable
-            || !market_coin_vault_account_info.is_writable
-            || !market_pc_vault_account_info.is_writable
-        {
-            return Err(Error::InvalidAccountConfigurationFlags.into());
-        }
-        let rent = Rent::from_account_info(rent_account_info)?;
-        let token_account_rent_exemption_balance = rent.minimum_balance(<Account as Pack>::LEN);
-        if intermediary_trader_account_info.lamports() < (2 * token_account_rent_exemption_balance + FEE_BUY_ON_RAYDIUM) {
-            return Err(Error::InvalidAccountLamports.into());
-        }
-        let intermediary = borsh::from_slice::<Intermediary>(&intermediary_account_info.data.borrow())?;
-        if !intermediary.is_initialized {
-            return Err(Error::IntermediaryIsNotInitialized.into());
-        }
-        if *intermediary_trader_account_info.key != intermediary.trader_pubkey {
-            return Err(Error::IntermediaryInvalidInvestor.into());
-        }
-        if *w_sol_token_account_account_info.key != intermediary.w_sol_token_account_pubkey {
-            return Err(Error::IntermediaryInvalidWSolTokenAccount.into());
-        }
-        if *w_sol_token_account_authority_account_info.key != intermediary.w_sol_token_account_authority_pubkey {
-            return Err(Error::IntermediaryInvalidWSolTokenAccountAuthority.into());
-        }
-        if *temporary_w_sol_token_account_account_info.key != intermediary.temporary_w_sol_token_account_pubkey {
-            return Err(Error::IntermediaryInvalidTemporaryWSolTokenAccount.into());
-        }
-        if *temporary_w_sol_token_account_authority_account_info.key != intermediary.temporary_w_sol_token_account_authority_pubkey {
-            return Err(Error::IntermediaryInvalidTemporaryWSolTokenAccountAuthority.into());
-        }
-        let w_sol_token_account = Account::unpack_unchecked(&w_sol_token_account_account_info.data.borrow())?;
-        if input_token_amount > w_sol_token_account.amount {
-            return Err(Error::TokenAccountInsufficientAmount.into());
-        }
-        let temporary_w_sol_token_account_address_resolver = TemporaryWSolTokenAccountAddressResolver {
-            w_sol_token_account_pubkey: w_sol_token_account_account_info.key,
-        };
-        let mut temporary_w_sol_token_account_pubkey_seeds = temporary_w_sol_token_account_address_resolver.get_seeds();
-        let temporary_w_sol_token_account_pubkey_bump_seed_ = [intermediary.temporary_w_sol_token_account_pubkey_bump_seed];
-        temporary_w_sol_token_account_pubkey_seeds.push(temporary_w_sol_token_account_pubkey_bump_seed_.as_slice());
-        solana_program::program::invoke_signed(
-            &solana_program::system_instruction::create_account(
-                intermediary_trader_account_info.key,
-                temporary_w_sol_token_account_account_info.key,
-                token_account_rent_exemption_balance,
-                <Account as Pack>::LEN as u64,
-                token_program_account_info.key,
-            ),
-            vec![
-                intermediary_trader_account_info.clone(),
-                temporary_w_sol_token_account_account_info.clone(),
-            ]
-            .as_slice(),
-            [temporary_w_sol_token_account_pubkey_seeds.as_slice()].as_slice(),
-        )?;
-        solana_program::program::invoke(
-            &spl_token::instruction::initialize_account(
-                token_program_account_info.key,
-                temporary_w_sol_token_account_account_info.key,
-                w_sol_token_mint_account_info.key,
-                temporary_w_sol_token_account_authority_account_info.key,
-            )?,
-            vec![
-                temporary_w_sol_token_account_account_info.clone(),
-                w_sol_token_mint_account_info.clone(),
-                temporary_w_sol_token_account_authority_account_info.clone(),
-                rent_account_info.clone(),
-            ]
-            .as_slice(),
-        )?;
-        let w_sol_token_account_authority_address_resolver = WSolTokenAccountAuthorityAddressResolver {
-            w_sol_token_account_pubkey: w_sol_token_account_account_info.key,
-            intermediary_pubkey: intermediary_account_info.key,
-        };
-        let mut w_sol_token_account_authority_pubkey_seeds = w_sol_token_account_authority_address_resolver.get_seeds();
-        let w_sol_token_account_authority_pubkey_bump_seed_ = [intermediary.w_sol_token_account_authority_pubkey_bump_seed];
-        w_sol_token_account_authority_pubkey_seeds.push(w_sol_token_account_authority_pubkey_bump_seed_.as_slice());
-        solana_program::program::invoke_signed(
-            &spl_token::instruction::transfer(
-                token_program_account_info.key,
-                w_sol_token_account_account_info.key,
-                temporary_w_sol_token_account_account_info.key,
-                w_sol_token_account_authority_account_info.key,
-                [].as_slice(),
-                input_token_amount,
-            )?,
-            vec![
-                w_sol_token_account_account_info.clone(),
-                temporary_w_sol_token_account_account_info.clone(),
-                w_sol_token_account_authority_account_info.clone(),
-            ]
-            .as_slice(),
-            [w_sol_token_account_authority_pubkey_seeds.as_slice()].as_slice(),
-        )?;
-        let mut any_mint_token_account_pubkey_seeds = any_mint_token_account_address_resolver.get_seeds();
-        let any_mint_token_account_pubkey_bump_seed_ = [any_mint_token_account_pubkey_bump_seed];
-        any_mint_token_account_pubkey_seeds.push(any_mint_token_account_pubkey_bump_seed_.as_slice());
-        solana_program::program::invoke_signed(
-            &solana_program::system_instruction::create_account(
-                intermediary_trader_account_info.key,
-                any_mint_token_account_account_info.key,
-                token_account_rent_exemption_balance,
-                <Account as Pack>::LEN as u64,
-                token_program_account_info.key,
-            ),
-            vec![
-                intermediary_trader_account_info.clone(),
-                any_mint_token_account_account_info.clone(),
-            ]
-            .as_slice(),
-            [any_mint_token_account_pubkey_seeds.as_slice()].as_slice(),
-        )?;
-        solana_program::program::invoke(
-            &spl_token::instruction::initialize_account(
-                token_program_account_info.key,
-                any_mint_token_account_account_info.key,
-                any_mint_token_mint_account_info.key,
-                any_mint_token_account_authority_account_info.key,
-            )?,
-            vec![
-                any_mint_token_account_account_info.clone(),
-                any_mint_token_mint_account_info.clone(),
-                any_mint_token_account_authority_account_info.clone(),
-                rent_account_info.clone(),
-            ]
-            .as_slice(),
-        )?;
-        let temporary_w_sol_token_account_authority_address_resolver = TemporaryWSolTokenAccountAuthorityAddressResolver {
-            temporary_w_sol_token_account_pubkey: temporary_w_sol_token_account_account_info.key,
-            intermediary_pubkey: intermediary_account_info.key,
-        };
-        let mut temporary_w_sol_token_account_authority_pubkey_seeds = temporary_w_sol_token_account_authority_address_resolver.get_seeds();
-        let temporary_w_sol_token_account_authority_pubkey_bump_seed_ = [intermediary.temporary_w_sol_token_account_authority_pubkey_bump_seed];
-        temporary_w_sol_token_account_authority_pubkey_seeds.push(temporary_w_sol_token_account_authority_pubkey_bump_seed_.as_slice());
-        solana_program::program::invoke_signed(
-            &crate::extern_source::create_raydium_swap_base_in_instruction(
-                amm_program_id.key,
-                amm_pool_account_info.key,
-                amm_authority_account_info.key,
-                amm_open_orders_account_info.key,
-                amm_coin_vault_account_info.key,
-                amm_pc_vault_account_info.key,
-                market_program_id_account_info.key,
-                market_account_info.key,
-                market_bids_account_info.key,
-                market_asks_account_info.key,
-                market_event_queue_account_info.key,
-                market_coin_vault_account_info.key,
-                market_pc_vault_account_info.key,
-                market_vault_signer_account_info.key,
-                temporary_w_sol_token_account_account_info.key,
-                any_mint_token_account_account_info.key,
-                temporary_w_sol_token_account_authority_account_info.key,
-                input_token_amount,
-                minimum_output_token_amount,
-            ),
-            vec![
-                token_program_account_info.clone(),
-                amm_pool_account_info.clone(),
-                amm_authority_account_info.clone(),
-                amm_open_orders_account_info.clone(),
-                amm_coin_vault_account_info.clone(),
-                amm_pc_vault_account_info.clone(),
-                market_program_id_account_info.clone(),
-                market_account_info.clone(),
-                market_bids_account_info.clone(),
-                market_asks_account_info.clone(),
-                market_event_queue_account_info.clone(),
-                market_coin_vault_account_info.clone(),
-                market_pc_vault_account_info.clone(),
-                market_vault_signer_account_info.clone(),
-                temporary_w_sol_token_account_account_info.clone(),
-                any_mint_token_account_account_info.clone(),
-                temporary_w_sol_token_account_authority_account_info.clone(),
-            ]
-            .as_slice(),
-            [temporary_w_sol_token_account_authority_pubkey_seeds.as_slice()].as_slice(),
-        )?;
-        if Account::unpack_unchecked(&any_mint_token_account_account_info.data.borrow())?.amount < minimum_output_token_amount {
-            return Err(Error::TokenAccountInvalidAmount.into());
-        }
-        if Account::unpack_unchecked(&temporary_w_sol_token_account_account_info.data.borrow())?.amount != 0 {
-            return Err(Error::TokenAccountInvalidAmount.into());
-        };
-        solana_program::program::invoke_signed(
-            &spl_token::instruction::close_account(
-                token_program_account_info.key,
-                temporary_w_sol_token_account_account_info.key,
-                w_sol_token_account_account_info.key,
-                temporary_w_sol_token_account_authority_account_info.key,
-                [].as_slice(),
-            )?,
-            vec![
-                temporary_w_sol_token_account_account_info.clone(),
-                w_sol_token_account_account_info.clone(),
-                temporary_w_sol_token_account_authority_account_info.clone(),
-            ]
-            .as_slice(),
-            [temporary_w_sol_token_account_authority_pubkey_seeds.as_slice()].as_slice(),
-        )?;
-        solana_program::program::invoke(
-            &spl_token::instruction::sync_native(
-                token_program_account_info.key,
-                w_sol_token_account_account_info.key,
-            )?,
-            vec![
-                w_sol_token_account_account_info.clone(),
+        let allowed_mints_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let risk_limits_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let trader_seat_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let config_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let fee_vault_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let raydium_adapter = RaydiumAdapter::load(
+            [
+                amm_program_id,
+                amm_pool_account_info,
+                amm_authority_account_info,
+                amm_open_orders_account_info,
+                amm_coin_vault_account_info,
+                amm_pc_vault_account_info,
+                market_program_id_account_info,
+                market_account_info,
+                market_bids_account_info,
+                market_asks_account_info,
+                market_event_queue_account_info,
+                market_coin_vault_account_info,
+                market_pc_vault_account_info,
+                market_vault_signer_account_info,
             ]
             .as_slice(),
         )?;
-        Ok(())
+        Self::buy_on_venue(
+            &VenueBuyAccounts {
+                intermediary_trader_account_info,
+                intermediary_account_info,
+                w_sol_token_account_account_info,
+                w_sol_token_account_authority_account_info,
+                temporary_w_sol_token_account_account_info,
+                temporary_w_sol_token_account_authority_account_info,
+                any_mint_token_account_account_info,
+                any_mint_token_account_authority_account_info,
+                w_sol_token_mint_account_info,
+                any_mint_token_mint_account_info,
+                system_program_account_info,
+                rent_account_info,
+                token_program_account_info,
+                any_mint_token_program_account_info: token_program_account_info,
+                allowed_mints_account_info,
+                risk_limits_account_info,
+                trader_seat_account_info,
+                config_account_info,
+                fee_vault_account_info,
+            },
+            &raydium_adapter,
+            input_token_amount,
+            minimum_output_token_amount,
+            any_mint_token_account_pubkey_bump_seed,
+            any_mint_token_account_authority_pubkey_bump_seed,
+            is_exact_out,
+        )
     }
-    fn sell_on_raydium<'a>(
+    fn sell_on_raydium<'a, 'b>(
         _program_id: &'a Pubkey,
-        accounts: &'a [AccountInfo],
+        accounts: &'a [AccountInfo<'b>],
         input_token_amount: u64,
         minimum_output_token_amount: Option<u64>,
         any_mint_token_account_pubkey_bump_seed: u8,
         any_mint_token_account_authority_pubkey_bump_seed: u8,
         temporary_any_mint_token_account_pubkey_bump_seed: u8,
         temporary_any_mint_token_account_authority_pubkey_bump_seed: u8,
+        is_exact_out: bool,
     ) -> ProgramResult {
         let account_info_iter = &mut accounts.iter();
         let intermediary_trader_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
@@ -802,227 +1118,3552 @@ impl Processor {
         let market_coin_vault_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
         let market_pc_vault_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
         let market_vault_signer_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
-        let any_mint_token_account_address_resolver = AnyMintTokenAccountAddressResolver {
-            any_mint_token_mint_pubkey: any_mint_token_mint_account_info.key,
-            intermediary_pubkey: intermediary_account_info.key,
-        };
-        let any_mint_token_account_authority_address_resolver = AnyMintTokenAccountAuthorityAddressResolver {
-            any_mint_token_account_pubkey: any_mint_token_account_account_info.key,
-            intermediary_pubkey: intermediary_account_info.key,
-        };
-        let temporary_any_mint_token_account_address_resolver = TemporaryAnyMintTokenAccountAddressResolver {
-            any_mint_token_account_pubkey: any_mint_token_account_account_info.key,
-            intermediary_pubkey: intermediary_account_info.key,
-        };
-        let temporary_any_mint_token_account_authority_address_resolver = TemporaryAnyMintTokenAccountAuthorityAddressResolver {
-            temporary_any_mint_token_account_pubkey: temporary_any_mint_token_account_account_info.key,
-            intermediary_pubkey: intermediary_account_info.key,
-        };
-        if *any_mint_token_account_account_info.key != any_mint_token_account_address_resolver.create(any_mint_token_account_pubkey_bump_seed)?
-            || *any_mint_token_account_authority_account_info.key != any_mint_token_account_authority_address_resolver.create(any_mint_token_account_authority_pubkey_bump_seed)?
-            || *temporary_any_mint_token_account_account_info.key != temporary_any_mint_token_account_address_resolver.create(temporary_any_mint_token_account_pubkey_bump_seed)?
-            || *temporary_any_mint_token_account_authority_account_info.key
-                != temporary_any_mint_token_account_authority_address_resolver.create(temporary_any_mint_token_account_authority_pubkey_bump_seed)?
-            || *any_mint_token_mint_account_info.key == spl_token::native_mint::ID
-            || *system_program_account_info.key != solana_program::system_program::ID
-            || *rent_account_info.key != solana_program::sysvar::rent::ID
-            || *token_program_account_info.key != spl_token::ID
-            || *amm_program_id.key != RAYDIUM_LIQUIDITY_POOL_V4_PROGRAM_PUBKEY
-        {
-            return Err(Error::InvalidAccountPubkey.into());
+        let risk_limits_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let trader_seat_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let config_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let fee_vault_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let raydium_adapter = RaydiumAdapter::load(
+            [
+                amm_program_id,
+                amm_pool_account_info,
+                amm_authority_account_info,
+                amm_open_orders_account_info,
+                amm_coin_vault_account_info,
+                amm_pc_vault_account_info,
+                market_program_id_account_info,
+                market_account_info,
+                market_bids_account_info,
+                market_asks_account_info,
+                market_event_queue_account_info,
+                market_coin_vault_account_info,
+                market_pc_vault_account_info,
+                market_vault_signer_account_info,
+            ]
+            .as_slice(),
+        )?;
+        Self::sell_on_venue(
+            &VenueSellAccounts {
+                intermediary_trader_account_info,
+                intermediary_account_info,
+                w_sol_token_account_account_info,
+                temporary_w_sol_token_account_account_info: None,
+                any_mint_token_account_account_info,
+                any_mint_token_account_authority_account_info,
+                temporary_any_mint_token_account_account_info,
+                temporary_any_mint_token_account_authority_account_info,
+                w_sol_token_mint_account_info: None,
+                any_mint_token_mint_account_info,
+                system_program_account_info,
+                rent_account_info,
+                token_program_account_info,
+                any_mint_token_program_account_info: token_program_account_info,
+                risk_limits_account_info,
+                trader_seat_account_info,
+                config_account_info,
+                fee_vault_account_info,
+            },
+            &raydium_adapter,
+            input_token_amount,
+            minimum_output_token_amount,
+            any_mint_token_account_pubkey_bump_seed,
+            temporary_any_mint_token_account_pubkey_bump_seed,
+            any_mint_token_account_authority_pubkey_bump_seed,
+            temporary_any_mint_token_account_authority_pubkey_bump_seed,
+            is_exact_out,
+        )
+    }
+    fn buy_on_pumpswap<'a, 'b>(
+        _program_id: &'a Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+        base_amount_out: u64,
+        maximum_quote_amount_in: u64,
+        any_mint_token_account_pubkey_bump_seed: u8,
+        any_mint_token_account_authority_pubkey_bump_seed: u8,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let intermediary_trader_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let intermediary_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let w_sol_token_account_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let w_sol_token_account_authority_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let temporary_w_sol_token_account_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let temporary_w_sol_token_account_authority_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let any_mint_token_account_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let any_mint_token_account_authority_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let w_sol_token_mint_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let any_mint_token_mint_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let system_program_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let rent_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let token_program_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let pumpfan_program_id_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let pool_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let global_config_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let pool_base_token_account_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let pool_quote_token_account_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let protocol_fee_recipient_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let protocol_fee_recipient_token_account_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let base_token_program_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let quote_token_program_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let associated_token_account_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let event_authority_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let allowed_mints_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let risk_limits_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let trader_seat_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let config_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let fee_vault_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let pumpswap_adapter = PumpswapAdapter::load(
+            [
+                pumpfan_program_id_account_info,
+                pool_account_info,
+                global_config_account_info,
+                pool_base_token_account_account_info,
+                pool_quote_token_account_account_info,
+                protocol_fee_recipient_account_info,
+                protocol_fee_recipient_token_account_account_info,
+                quote_token_program_account_info,
+                associated_token_account_account_info,
+                event_authority_account_info,
+            ]
+            .as_slice(),
+        )?;
+        Self::buy_on_venue(
+            &VenueBuyAccounts {
+                intermediary_trader_account_info,
+                intermediary_account_info,
+                w_sol_token_account_account_info,
+                w_sol_token_account_authority_account_info,
+                temporary_w_sol_token_account_account_info,
+                temporary_w_sol_token_account_authority_account_info,
+                any_mint_token_account_account_info,
+                any_mint_token_account_authority_account_info,
+                w_sol_token_mint_account_info,
+                any_mint_token_mint_account_info,
+                system_program_account_info,
+                rent_account_info,
+                token_program_account_info,
+                any_mint_token_program_account_info: base_token_program_account_info,
+                allowed_mints_account_info,
+                risk_limits_account_info,
+                trader_seat_account_info,
+                config_account_info,
+                fee_vault_account_info,
+            },
+            &pumpswap_adapter,
+            maximum_quote_amount_in,
+            base_amount_out,
+            any_mint_token_account_pubkey_bump_seed,
+            any_mint_token_account_authority_pubkey_bump_seed,
+            false,
+        )
+    }
+    fn sell_on_pumpswap<'a, 'b>(
+        _program_id: &'a Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+        input_token_amount: u64,
+        minimum_output_token_amount: Option<u64>,
+        any_mint_token_account_pubkey_bump_seed: u8,
+        any_mint_token_account_authority_pubkey_bump_seed: u8,
+        temporary_any_mint_token_account_pubkey_bump_seed: u8,
+        temporary_any_mint_token_account_authority_pubkey_bump_seed: u8,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let intermediary_trader_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let intermediary_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let w_sol_token_account_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let temporary_w_sol_token_account_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let any_mint_token_account_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let any_mint_token_account_authority_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let temporary_any_mint_token_account_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let temporary_any_mint_token_account_authority_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let w_sol_token_mint_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let any_mint_token_mint_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let system_program_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let rent_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let token_program_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let pumpfan_program_id_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let pool_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let global_config_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let pool_base_token_account_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let pool_quote_token_account_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let protocol_fee_recipient_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let protocol_fee_recipient_token_account_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let base_token_program_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let quote_token_program_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let associated_token_account_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let event_authority_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let risk_limits_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let trader_seat_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let config_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let fee_vault_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let pumpswap_adapter = PumpswapAdapter::load(
+            [
+                pumpfan_program_id_account_info,
+                pool_account_info,
+                global_config_account_info,
+                pool_base_token_account_account_info,
+                pool_quote_token_account_account_info,
+                protocol_fee_recipient_account_info,
+                protocol_fee_recipient_token_account_account_info,
+                quote_token_program_account_info,
+                associated_token_account_account_info,
+                event_authority_account_info,
+            ]
+            .as_slice(),
+        )?;
+        Self::sell_on_venue(
+            &VenueSellAccounts {
+                intermediary_trader_account_info,
+                intermediary_account_info,
+                w_sol_token_account_account_info,
+                temporary_w_sol_token_account_account_info: Some(temporary_w_sol_token_account_account_info),
+                any_mint_token_account_account_info,
+                any_mint_token_account_authority_account_info,
+                temporary_any_mint_token_account_account_info,
+                temporary_any_mint_token_account_authority_account_info,
+                w_sol_token_mint_account_info: Some(w_sol_token_mint_account_info),
+                any_mint_token_mint_account_info,
+                system_program_account_info,
+                rent_account_info,
+                token_program_account_info,
+                any_mint_token_program_account_info: base_token_program_account_info,
+                risk_limits_account_info,
+                trader_seat_account_info,
+                config_account_info,
+                fee_vault_account_info,
+            },
+            &pumpswap_adapter,
+            input_token_amount,
+            minimum_output_token_amount,
+            any_mint_token_account_pubkey_bump_seed,
+            temporary_any_mint_token_account_pubkey_bump_seed,
+            any_mint_token_account_authority_pubkey_bump_seed,
+            temporary_any_mint_token_account_authority_pubkey_bump_seed,
+            false,
+        )
+    }
+    fn propose_manager<'a>(_program_id: &'a Pubkey, accounts: &'a [AccountInfo]) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let intermediary_investor_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let intermediary_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let intermediary_manager_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let system_program_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let config_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let fee_vault_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let mut account_differentiator = HashSet::<&'_ Pubkey>::with_capacity(6);
+        if !account_differentiator.insert(intermediary_investor_account_info.key)
+            || !account_differentiator.insert(intermediary_account_info.key)
+            || !account_differentiator.insert(intermediary_manager_account_info.key)
+            || !account_differentiator.insert(system_program_account_info.key)
+            || !account_differentiator.insert(config_account_info.key)
+            || !account_differentiator.insert(fee_vault_account_info.key)
+            || *system_program_account_info.key != solana_program::system_program::ID
+        {
+            return Err(Error::InvalidAccountPubkey.into());
+        }
+        if !intermediary_investor_account_info.is_signer
+            || !intermediary_investor_account_info.is_writable
+            || !intermediary_account_info.is_writable
+            || !fee_vault_account_info.is_writable
+        {
+            return Err(Error::InvalidAccountConfigurationFlags.into());
+        }
+        if intermediary_manager_account_info.owner != system_program_account_info.key {
+            return Err(Error::InvalidAccountOwner.into());
+        }
+        if !intermediary_manager_account_info.data_is_empty() {
+            return Err(Error::InvalidAccountData.into());
+        }
+        if *config_account_info.owner != PROGRAM_PUBKEY {
+            return Err(Error::InvalidAccountOwner.into());
+        }
+        let config = borsh::from_slice::<Config>(&config_account_info.data.borrow())?;
+        if !config.is_initialized {
+            return Err(Error::ConfigIsNotInitialized.into());
+        }
+        if *config_account_info.key != ConfigAddressResolver.create(config.config_pubkey_bump_seed)? {
+            return Err(Error::InvalidAccountPubkey.into());
+        }
+        if *fee_vault_account_info.key != config.fee_vault_pubkey {
+            return Err(Error::ConfigInvalidFeeVault.into());
+        }
+        if intermediary_investor_account_info.lamports() < config.fees.change_manager || intermediary_manager_account_info.lamports() == 0 {
+            return Err(Error::InvalidAccountLamports.into());
+        }
+        solana_program::program::invoke(
+            &solana_program::system_instruction::transfer(
+                intermediary_investor_account_info.key,
+                fee_vault_account_info.key,
+                config.fees.change_manager,
+            ),
+            vec![
+                intermediary_investor_account_info.clone(),
+                fee_vault_account_info.clone(),
+            ]
+            .as_slice(),
+        )?;
+        Event::FeeCharged {
+            intermediary_pubkey: *intermediary_account_info.key,
+            payer_pubkey: *intermediary_investor_account_info.key,
+            fee_vault_pubkey: *fee_vault_account_info.key,
+            lamports: config.fees.change_manager,
+        }
+        .emit()?;
+        let mut intermediary = Intermediary::load(&intermediary_account_info.data.borrow())?;
+        if !intermediary.is_initialized {
+            return Err(Error::IntermediaryIsNotInitialized.into());
+        }
+        if intermediary.version != Intermediary::VERSION {
+            return Err(Error::IntermediaryIsNotMigrated.into());
+        }
+        if *intermediary_investor_account_info.key != intermediary.investor_pubkey {
+            return Err(Error::IntermediaryInvalidInvestor.into());
+        }
+        if *intermediary_manager_account_info.key == intermediary.investor_pubkey
+            || *intermediary_manager_account_info.key == intermediary.manager_pubkey
+            || *intermediary_manager_account_info.key == intermediary.trader_pubkey
+        {
+            return Err(Error::IntermediaryInvalidManager.into());
+        }
+        intermediary.pending_manager_pubkey = *intermediary_manager_account_info.key;
+        borsh::to_writer(
+            &mut intermediary_account_info.data.borrow_mut()[..],
+            &intermediary,
+        )?;
+        Event::RoleProposed {
+            intermediary_pubkey: *intermediary_account_info.key,
+            role: Role::Manager,
+            pubkey: intermediary.pending_manager_pubkey,
+        }
+        .emit()?;
+        Ok(())
+    }
+    fn propose_trader<'a>(_program_id: &'a Pubkey, accounts: &'a [AccountInfo]) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let intermediary_manager_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let intermediary_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let intermediary_trader_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let system_program_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let config_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let fee_vault_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let mut account_differentiator = HashSet::<&'_ Pubkey>::with_capacity(6);
+        if !account_differentiator.insert(intermediary_manager_account_info.key)
+            || !account_differentiator.insert(intermediary_account_info.key)
+            || !account_differentiator.insert(intermediary_trader_account_info.key)
+            || !account_differentiator.insert(system_program_account_info.key)
+            || !account_differentiator.insert(config_account_info.key)
+            || !account_differentiator.insert(fee_vault_account_info.key)
+            || *system_program_account_info.key != solana_program::system_program::ID
+        {
+            return Err(Error::InvalidAccountPubkey.into());
+        }
+        if !intermediary_manager_account_info.is_signer
+            || !intermediary_manager_account_info.is_writable
+            || !intermediary_account_info.is_writable
+            || !fee_vault_account_info.is_writable
+        {
+            return Err(Error::InvalidAccountConfigurationFlags.into());
+        }
+        if intermediary_trader_account_info.owner != system_program_account_info.key {
+            return Err(Error::InvalidAccountOwner.into());
+        }
+        if !intermediary_trader_account_info.data_is_empty() {
+            return Err(Error::InvalidAccountData.into());
+        }
+        if *config_account_info.owner != PROGRAM_PUBKEY {
+            return Err(Error::InvalidAccountOwner.into());
+        }
+        let config = borsh::from_slice::<Config>(&config_account_info.data.borrow())?;
+        if !config.is_initialized {
+            return Err(Error::ConfigIsNotInitialized.into());
+        }
+        if *config_account_info.key != ConfigAddressResolver.create(config.config_pubkey_bump_seed)? {
+            return Err(Error::InvalidAccountPubkey.into());
+        }
+        if *fee_vault_account_info.key != config.fee_vault_pubkey {
+            return Err(Error::ConfigInvalidFeeVault.into());
+        }
+        if intermediary_manager_account_info.lamports() < config.fees.change_trader || intermediary_trader_account_info.lamports() == 0 {
+            return Err(Error::InvalidAccountLamports.into());
+        }
+        solana_program::program::invoke(
+            &solana_program::system_instruction::transfer(
+                intermediary_manager_account_info.key,
+                fee_vault_account_info.key,
+                config.fees.change_trader,
+            ),
+            vec![
+                intermediary_manager_account_info.clone(),
+                fee_vault_account_info.clone(),
+            ]
+            .as_slice(),
+        )?;
+        Event::FeeCharged {
+            intermediary_pubkey: *intermediary_account_info.key,
+            payer_pubkey: *intermediary_manager_account_info.key,
+            fee_vault_pubkey: *fee_vault_account_info.key,
+            lamports: config.fees.change_trader,
+        }
+        .emit()?;
+        let mut intermediary = Intermediary::load(&intermediary_account_info.data.borrow())?;
+        if !intermediary.is_initialized {
+            return Err(Error::IntermediaryIsNotInitialized.into());
+        }
+        if intermediary.version != Intermediary::VERSION {
+            return Err(Error::IntermediaryIsNotMigrated.into());
+        }
+        if *intermediary_manager_account_info.key != intermediary.manager_pubkey {
+            return Err(Error::IntermediaryInvalidManager.into());
+        }
+        if *intermediary_trader_account_info.key == intermediary.investor_pubkey
+            || *intermediary_trader_account_info.key == intermediary.manager_pubkey
+            || *intermediary_trader_account_info.key == intermediary.trader_pubkey
+        {
+            return Err(Error::IntermediaryInvalidTrader.into());
+        }
+        intermediary.pending_trader_pubkey = *intermediary_trader_account_info.key;
+        borsh::to_writer(
+            &mut intermediary_account_info.data.borrow_mut()[..],
+            &intermediary,
+        )?;
+        Event::RoleProposed {
+            intermediary_pubkey: *intermediary_account_info.key,
+            role: Role::Trader,
+            pubkey: intermediary.pending_trader_pubkey,
+        }
+        .emit()?;
+        Ok(())
+    }
+    fn initialize_config<'a>(
+        _program_id: &'a Pubkey,
+        accounts: &'a [AccountInfo],
+        fees: Fees,
+        config_pubkey_bump_seed: u8,
+        fee_vault_pubkey_bump_seed: u8,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let administrator_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let config_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let fee_vault_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let fee_recipient_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let program_data_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let system_program_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let rent_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let config_address_resolver = ConfigAddressResolver;
+        let fee_vault_address_resolver = FeeVaultAddressResolver {
+            config_pubkey: config_account_info.key,
+        };
+        let mut account_differentiator = HashSet::<&'_ Pubkey>::with_capacity(7);
+        if !account_differentiator.insert(administrator_account_info.key)
+            || !account_differentiator.insert(config_account_info.key)
+            || !account_differentiator.insert(fee_vault_account_info.key)
+            || !account_differentiator.insert(fee_recipient_account_info.key)
+            || !account_differentiator.insert(program_data_account_info.key)
+            || !account_differentiator.insert(system_program_account_info.key)
+            || !account_differentiator.insert(rent_account_info.key)
+            || *config_account_info.key != config_address_resolver.create(config_pubkey_bump_seed)?
+            || *fee_vault_account_info.key != fee_vault_address_resolver.create(fee_vault_pubkey_bump_seed)?
+            || *program_data_account_info.key != solana_program::bpf_loader_upgradeable::get_program_data_address(&PROGRAM_PUBKEY)
+            || *system_program_account_info.key != solana_program::system_program::ID
+            || *rent_account_info.key != solana_program::sysvar::rent::ID
+        {
+            return Err(Error::InvalidAccountPubkey.into());
+        }
+        if !administrator_account_info.is_signer || !administrator_account_info.is_writable || !config_account_info.is_writable || !fee_vault_account_info.is_writable {
+            return Err(Error::InvalidAccountConfigurationFlags.into());
+        }
+        if *program_data_account_info.owner != solana_program::bpf_loader_upgradeable::ID {
+            return Err(Error::InvalidAccountOwner.into());
+        }
+        if !config_account_info.data_is_empty() || !fee_vault_account_info.data_is_empty() {
+            return Err(Error::InvalidAccountData.into());
+        }
+        // https://github.com/anza-xyz/agave/blob/v2.1.15/sdk/program/src/bpf_loader_upgradeable.rs#L29
+        // ProgramData { slot: u64, upgrade_authority_address: Option<Pubkey> } with the u32 enum tag 3.
+        {
+            let program_data = program_data_account_info.data.borrow();
+            if program_data.len() < solana_program::bpf_loader_upgradeable::UpgradeableLoaderState::size_of_programdata_metadata()
+                || program_data[0..4] != 3_u32.to_le_bytes()
+            {
+                return Err(Error::InvalidAccountData.into());
+            }
+            if program_data[12] != 1 || program_data[13..45] != administrator_account_info.key.to_bytes() {
+                return Err(Error::ConfigInvalidAdministrator.into());
+            }
+        }
+        let config = Config {
+            is_initialized: true,
+            administrator_pubkey: *administrator_account_info.key,
+            fee_recipient_pubkey: *fee_recipient_account_info.key,
+            fee_vault_pubkey: *fee_vault_account_info.key,
+            config_pubkey_bump_seed,
+            fee_vault_pubkey_bump_seed,
+            fees,
+        };
+        let config_object_length = borsh::object_length(&config)?;
+        let rent = Rent::from_account_info(rent_account_info)?;
+        let config_rent_exemption_balance = rent.minimum_balance(config_object_length);
+        let fee_vault_rent_exemption_balance = rent.minimum_balance(0);
+        if administrator_account_info.lamports() < (config_rent_exemption_balance + fee_vault_rent_exemption_balance) {
+            return Err(Error::InvalidAccountLamports.into());
+        }
+        let mut config_pubkey_seeds = config_address_resolver.get_seeds();
+        let config_pubkey_bump_seed_ = [config_pubkey_bump_seed];
+        config_pubkey_seeds.push(config_pubkey_bump_seed_.as_slice());
+        solana_program::program::invoke_signed(
+            &solana_program::system_instruction::create_account(
+                administrator_account_info.key,
+                config_account_info.key,
+                config_rent_exemption_balance,
+                config_object_length as u64,
+                &PROGRAM_PUBKEY,
+            ),
+            vec![
+                administrator_account_info.clone(),
+                config_account_info.clone(),
+            ]
+            .as_slice(),
+            [config_pubkey_seeds.as_slice()].as_slice(),
+        )?;
+        borsh::to_writer(
+            &mut config_account_info.data.borrow_mut()[..],
+            &config,
+        )?;
+        let mut fee_vault_pubkey_seeds = fee_vault_address_resolver.get_seeds();
+        let fee_vault_pubkey_bump_seed_ = [fee_vault_pubkey_bump_seed];
+        fee_vault_pubkey_seeds.push(fee_vault_pubkey_bump_seed_.as_slice());
+        solana_program::program::invoke_signed(
+            &solana_program::system_instruction::create_account(
+                administrator_account_info.key,
+                fee_vault_account_info.key,
+                fee_vault_rent_exemption_balance,
+                0,
+                &PROGRAM_PUBKEY,
+            ),
+            vec![
+                administrator_account_info.clone(),
+                fee_vault_account_info.clone(),
+            ]
+            .as_slice(),
+            [fee_vault_pubkey_seeds.as_slice()].as_slice(),
+        )?;
+        Event::ConfigInitialized {
+            administrator_pubkey: *administrator_account_info.key,
+            fee_recipient_pubkey: *fee_recipient_account_info.key,
+            fees,
+        }
+        .emit()?;
+        Ok(())
+    }
+    fn update_config<'a>(_program_id: &'a Pubkey, accounts: &'a [AccountInfo], fees: Fees) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let administrator_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let config_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let fee_recipient_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let mut account_differentiator = HashSet::<&'_ Pubkey>::with_capacity(3);
+        if !account_differentiator.insert(administrator_account_info.key)
+            || !account_differentiator.insert(config_account_info.key)
+            || !account_differentiator.insert(fee_recipient_account_info.key)
+        {
+            return Err(Error::InvalidAccountPubkey.into());
+        }
+        if !administrator_account_info.is_signer || !config_account_info.is_writable {
+            return Err(Error::InvalidAccountConfigurationFlags.into());
+        }
+        if *config_account_info.owner != PROGRAM_PUBKEY {
+            return Err(Error::InvalidAccountOwner.into());
+        }
+        let mut config = borsh::from_slice::<Config>(&config_account_info.data.borrow())?;
+        if !config.is_initialized {
+            return Err(Error::ConfigIsNotInitialized.into());
+        }
+        if *config_account_info.key != ConfigAddressResolver.create(config.config_pubkey_bump_seed)? {
+            return Err(Error::InvalidAccountPubkey.into());
+        }
+        if *administrator_account_info.key != config.administrator_pubkey {
+            return Err(Error::ConfigInvalidAdministrator.into());
+        }
+        if *fee_recipient_account_info.key == config.fee_vault_pubkey {
+            return Err(Error::ConfigInvalidFeeRecipient.into());
+        }
+        config.fee_recipient_pubkey = *fee_recipient_account_info.key;
+        config.fees = fees;
+        borsh::to_writer(
+            &mut config_account_info.data.borrow_mut()[..],
+            &config,
+        )?;
+        Event::ConfigUpdated {
+            fees,
+        }
+        .emit()?;
+        Ok(())
+    }
+    fn sweep_fees<'a>(_program_id: &'a Pubkey, accounts: &'a [AccountInfo]) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let administrator_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let config_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let fee_vault_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let fee_recipient_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let rent_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let mut account_differentiator = HashSet::<&'_ Pubkey>::with_capacity(5);
+        if !account_differentiator.insert(administrator_account_info.key)
+            || !account_differentiator.insert(config_account_info.key)
+            || !account_differentiator.insert(fee_vault_account_info.key)
+            || !account_differentiator.insert(fee_recipient_account_info.key)
+            || !account_differentiator.insert(rent_account_info.key)
+            || *rent_account_info.key != solana_program::sysvar::rent::ID
+        {
+            return Err(Error::InvalidAccountPubkey.into());
+        }
+        if !administrator_account_info.is_signer || !fee_vault_account_info.is_writable || !fee_recipient_account_info.is_writable {
+            return Err(Error::InvalidAccountConfigurationFlags.into());
+        }
+        if *config_account_info.owner != PROGRAM_PUBKEY || *fee_vault_account_info.owner != PROGRAM_PUBKEY {
+            return Err(Error::InvalidAccountOwner.into());
+        }
+        let config = borsh::from_slice::<Config>(&config_account_info.data.borrow())?;
+        if !config.is_initialized {
+            return Err(Error::ConfigIsNotInitialized.into());
+        }
+        if *config_account_info.key != ConfigAddressResolver.create(config.config_pubkey_bump_seed)? {
+            return Err(Error::InvalidAccountPubkey.into());
+        }
+        if *administrator_account_info.key != config.administrator_pubkey {
+            return Err(Error::ConfigInvalidAdministrator.into());
+        }
+        if *fee_vault_account_info.key != config.fee_vault_pubkey {
+            return Err(Error::ConfigInvalidFeeVault.into());
+        }
+        if *fee_recipient_account_info.key != config.fee_recipient_pubkey {
+            return Err(Error::ConfigInvalidFeeRecipient.into());
+        }
+        let rent = Rent::from_account_info(rent_account_info)?;
+        let fee_vault_rent_exemption_balance = rent.minimum_balance(fee_vault_account_info.data_len());
+        let lamports_to_fee_recipient = fee_vault_account_info.lamports().saturating_sub(fee_vault_rent_exemption_balance);
+        if lamports_to_fee_recipient == 0 {
+            return Ok(());
+        }
+        **fee_vault_account_info.try_borrow_mut_lamports()? -= lamports_to_fee_recipient;
+        **fee_recipient_account_info.try_borrow_mut_lamports()? += lamports_to_fee_recipient;
+        Event::FeesSwept {
+            fee_recipient_pubkey: *fee_recipient_account_info.key,
+            lamports: lamports_to_fee_recipient,
+        }
+        .emit()?;
+        Ok(())
+    }
+    fn set_risk_limits<'a>(
+        _program_id: &'a Pubkey,
+        accounts: &'a [AccountInfo],
+        maximum_lamports_per_trade: u64,
+        maximum_treasury_share_per_trade_basis_points: u16,
+        maximum_trades_per_slot_window: u64,
+        slot_window_length: u64,
+        risk_limits_pubkey_bump_seed: u8,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let intermediary_manager_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let intermediary_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let risk_limits_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let system_program_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let rent_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let risk_limits_address_resolver = RiskLimitsAddressResolver {
+            intermediary_pubkey: intermediary_account_info.key,
+        };
+        let mut account_differentiator = HashSet::<&'_ Pubkey>::with_capacity(5);
+        if !account_differentiator.insert(intermediary_manager_account_info.key)
+            || !account_differentiator.insert(intermediary_account_info.key)
+            || !account_differentiator.insert(risk_limits_account_info.key)
+            || !account_differentiator.insert(system_program_account_info.key)
+            || !account_differentiator.insert(rent_account_info.key)
+            || *risk_limits_account_info.key != risk_limits_address_resolver.create(risk_limits_pubkey_bump_seed)?
+            || *system_program_account_info.key != solana_program::system_program::ID
+            || *rent_account_info.key != solana_program::sysvar::rent::ID
+        {
+            return Err(Error::InvalidAccountPubkey.into());
+        }
+        if !intermediary_manager_account_info.is_signer || !intermediary_manager_account_info.is_writable || !risk_limits_account_info.is_writable {
+            return Err(Error::InvalidAccountConfigurationFlags.into());
+        }
+        if maximum_treasury_share_per_trade_basis_points > 10_000 || slot_window_length == 0 {
+            return Err(Error::RiskLimitsInvalidParameters.into());
+        }
+        let intermediary = Intermediary::load(&intermediary_account_info.data.borrow())?;
+        if !intermediary.is_initialized {
+            return Err(Error::IntermediaryIsNotInitialized.into());
+        }
+        if *intermediary_manager_account_info.key != intermediary.manager_pubkey {
+            return Err(Error::IntermediaryInvalidManager.into());
+        }
+        if risk_limits_account_info.data_is_empty() {
+            let risk_limits = RiskLimits {
+                is_initialized: true,
+                intermediary_pubkey: *intermediary_account_info.key,
+                maximum_lamports_per_trade,
+                maximum_treasury_share_per_trade_basis_points,
+                maximum_trades_per_slot_window,
+                slot_window_length,
+                slot_window_start_slot: 0,
+                slot_window_trades_quantity: 0,
+                risk_limits_pubkey_bump_seed,
+            };
+            let risk_limits_object_length = borsh::object_length(&risk_limits)?;
+            let rent = Rent::from_account_info(rent_account_info)?;
+            let risk_limits_rent_exemption_balance = rent.minimum_balance(risk_limits_object_length);
+            if intermediary_manager_account_info.lamports() < risk_limits_rent_exemption_balance {
+                return Err(Error::InvalidAccountLamports.into());
+            }
+            let mut risk_limits_pubkey_seeds = risk_limits_address_resolver.get_seeds();
+            let risk_limits_pubkey_bump_seed_ = [risk_limits_pubkey_bump_seed];
+            risk_limits_pubkey_seeds.push(risk_limits_pubkey_bump_seed_.as_slice());
+            solana_program::program::invoke_signed(
+                &solana_program::system_instruction::create_account(
+                    intermediary_manager_account_info.key,
+                    risk_limits_account_info.key,
+                    risk_limits_rent_exemption_balance,
+                    risk_limits_object_length as u64,
+                    &PROGRAM_PUBKEY,
+                ),
+                vec![
+                    intermediary_manager_account_info.clone(),
+                    risk_limits_account_info.clone(),
+                ]
+                .as_slice(),
+                [risk_limits_pubkey_seeds.as_slice()].as_slice(),
+            )?;
+            borsh::to_writer(
+                &mut risk_limits_account_info.data.borrow_mut()[..],
+                &risk_limits,
+            )?;
+        } else {
+            if *risk_limits_account_info.owner != PROGRAM_PUBKEY {
+                return Err(Error::InvalidAccountOwner.into());
+            }
+            let mut risk_limits = borsh::from_slice::<RiskLimits>(&risk_limits_account_info.data.borrow())?;
+            if !risk_limits.is_initialized {
+                return Err(Error::RiskLimitsIsNotInitialized.into());
+            }
+            risk_limits.maximum_lamports_per_trade = maximum_lamports_per_trade;
+            risk_limits.maximum_treasury_share_per_trade_basis_points = maximum_treasury_share_per_trade_basis_points;
+            risk_limits.maximum_trades_per_slot_window = maximum_trades_per_slot_window;
+            risk_limits.slot_window_length = slot_window_length;
+            borsh::to_writer(
+                &mut risk_limits_account_info.data.borrow_mut()[..],
+                &risk_limits,
+            )?;
+        }
+        Event::RiskLimitsSet {
+            intermediary_pubkey: *intermediary_account_info.key,
+            maximum_lamports_per_trade,
+            maximum_treasury_share_per_trade_basis_points,
+            maximum_trades_per_slot_window,
+            slot_window_length,
+        }
+        .emit()?;
+        Ok(())
+    }
+    fn add_allowed_mint<'a>(_program_id: &'a Pubkey, accounts: &'a [AccountInfo], allowed_mints_pubkey_bump_seed: u8) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let intermediary_manager_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let intermediary_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let allowed_mints_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let any_mint_token_mint_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let system_program_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let rent_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let allowed_mints_address_resolver = AllowedMintsAddressResolver {
+            intermediary_pubkey: intermediary_account_info.key,
+        };
+        let mut account_differentiator = HashSet::<&'_ Pubkey>::with_capacity(6);
+        if !account_differentiator.insert(intermediary_manager_account_info.key)
+            || !account_differentiator.insert(intermediary_account_info.key)
+            || !account_differentiator.insert(allowed_mints_account_info.key)
+            || !account_differentiator.insert(any_mint_token_mint_account_info.key)
+            || !account_differentiator.insert(system_program_account_info.key)
+            || !account_differentiator.insert(rent_account_info.key)
+            || *allowed_mints_account_info.key != allowed_mints_address_resolver.create(allowed_mints_pubkey_bump_seed)?
+            || *any_mint_token_mint_account_info.key == spl_token::native_mint::ID
+            || *system_program_account_info.key != solana_program::system_program::ID
+            || *rent_account_info.key != solana_program::sysvar::rent::ID
+        {
+            return Err(Error::InvalidAccountPubkey.into());
+        }
+        if !intermediary_manager_account_info.is_signer || !intermediary_manager_account_info.is_writable || !allowed_mints_account_info.is_writable {
+            return Err(Error::InvalidAccountConfigurationFlags.into());
+        }
+        if *any_mint_token_mint_account_info.owner != spl_token::ID && *any_mint_token_mint_account_info.owner != spl_token_2022::ID {
+            return Err(Error::InvalidAccountOwner.into());
+        }
+        if StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&any_mint_token_mint_account_info.data.borrow()).is_err() {
+            return Err(Error::InvalidAccountData.into());
+        }
+        let intermediary = Intermediary::load(&intermediary_account_info.data.borrow())?;
+        if !intermediary.is_initialized {
+            return Err(Error::IntermediaryIsNotInitialized.into());
+        }
+        if *intermediary_manager_account_info.key != intermediary.manager_pubkey {
+            return Err(Error::IntermediaryInvalidManager.into());
+        }
+        let rent = Rent::from_account_info(rent_account_info)?;
+        if allowed_mints_account_info.data_is_empty() {
+            let allowed_mints = AllowedMints {
+                is_initialized: true,
+                intermediary_pubkey: *intermediary_account_info.key,
+                allowed_mints_pubkey_bump_seed,
+                mint_pubkeys: vec![*any_mint_token_mint_account_info.key],
+            };
+            let allowed_mints_object_length = borsh::object_length(&allowed_mints)?;
+            let allowed_mints_rent_exemption_balance = rent.minimum_balance(allowed_mints_object_length);
+            if intermediary_manager_account_info.lamports() < allowed_mints_rent_exemption_balance {
+                return Err(Error::InvalidAccountLamports.into());
+            }
+            let mut allowed_mints_pubkey_seeds = allowed_mints_address_resolver.get_seeds();
+            let allowed_mints_pubkey_bump_seed_ = [allowed_mints_pubkey_bump_seed];
+            allowed_mints_pubkey_seeds.push(allowed_mints_pubkey_bump_seed_.as_slice());
+            solana_program::program::invoke_signed(
+                &solana_program::system_instruction::create_account(
+                    intermediary_manager_account_info.key,
+                    allowed_mints_account_info.key,
+                    allowed_mints_rent_exemption_balance,
+                    allowed_mints_object_length as u64,
+                    &PROGRAM_PUBKEY,
+                ),
+                vec![
+                    intermediary_manager_account_info.clone(),
+                    allowed_mints_account_info.clone(),
+                ]
+                .as_slice(),
+                [allowed_mints_pubkey_seeds.as_slice()].as_slice(),
+            )?;
+            borsh::to_writer(
+                &mut allowed_mints_account_info.data.borrow_mut()[..],
+                &allowed_mints,
+            )?;
+            Event::AllowedMintAdded {
+                intermediary_pubkey: *intermediary_account_info.key,
+                mint_pubkey: *any_mint_token_mint_account_info.key,
+            }
+            .emit()?;
+            return Ok(());
+        }
+        if *allowed_mints_account_info.owner != PROGRAM_PUBKEY {
+            return Err(Error::InvalidAccountOwner.into());
+        }
+        let mut allowed_mints = borsh::from_slice::<AllowedMints>(&allowed_mints_account_info.data.borrow())?;
+        if !allowed_mints.is_initialized {
+            return Err(Error::AllowedMintsIsNotInitialized.into());
+        }
+        if allowed_mints.mint_pubkeys.contains(any_mint_token_mint_account_info.key) {
+            return Err(Error::AllowedMintsAlreadyContainsMint.into());
+        }
+        allowed_mints.mint_pubkeys.push(*any_mint_token_mint_account_info.key);
+        let allowed_mints_object_length = borsh::object_length(&allowed_mints)?;
+        let allowed_mints_rent_exemption_balance = rent.minimum_balance(allowed_mints_object_length);
+        if allowed_mints_rent_exemption_balance > allowed_mints_account_info.lamports() {
+            let lamports_to_allowed_mints = allowed_mints_rent_exemption_balance - allowed_mints_account_info.lamports();
+            if intermediary_manager_account_info.lamports() < lamports_to_allowed_mints {
+                return Err(Error::InvalidAccountLamports.into());
+            }
+            solana_program::program::invoke(
+                &solana_program::system_instruction::transfer(
+                    intermediary_manager_account_info.key,
+                    allowed_mints_account_info.key,
+                    lamports_to_allowed_mints,
+                ),
+                vec![
+                    intermediary_manager_account_info.clone(),
+                    allowed_mints_account_info.clone(),
+                ]
+                .as_slice(),
+            )?;
+        }
+        allowed_mints_account_info.realloc(allowed_mints_object_length, false)?;
+        borsh::to_writer(
+            &mut allowed_mints_account_info.data.borrow_mut()[..],
+            &allowed_mints,
+        )?;
+        Event::AllowedMintAdded {
+            intermediary_pubkey: *intermediary_account_info.key,
+            mint_pubkey: *any_mint_token_mint_account_info.key,
+        }
+        .emit()?;
+        Ok(())
+    }
+    fn remove_allowed_mint<'a>(_program_id: &'a Pubkey, accounts: &'a [AccountInfo]) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let intermediary_manager_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let intermediary_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let allowed_mints_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let any_mint_token_mint_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let rent_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let mut account_differentiator = HashSet::<&'_ Pubkey>::with_capacity(5);
+        if !account_differentiator.insert(intermediary_manager_account_info.key)
+            || !account_differentiator.insert(intermediary_account_info.key)
+            || !account_differentiator.insert(allowed_mints_account_info.key)
+            || !account_differentiator.insert(any_mint_token_mint_account_info.key)
+            || !account_differentiator.insert(rent_account_info.key)
+            || *rent_account_info.key != solana_program::sysvar::rent::ID
+        {
+            return Err(Error::InvalidAccountPubkey.into());
+        }
+        if !intermediary_manager_account_info.is_signer || !intermediary_manager_account_info.is_writable || !allowed_mints_account_info.is_writable {
+            return Err(Error::InvalidAccountConfigurationFlags.into());
+        }
+        if *allowed_mints_account_info.owner != PROGRAM_PUBKEY {
+            return Err(Error::InvalidAccountOwner.into());
+        }
+        let intermediary = Intermediary::load(&intermediary_account_info.data.borrow())?;
+        if !intermediary.is_initialized {
+            return Err(Error::IntermediaryIsNotInitialized.into());
+        }
+        if *intermediary_manager_account_info.key != intermediary.manager_pubkey {
+            return Err(Error::IntermediaryInvalidManager.into());
+        }
+        let mut allowed_mints = borsh::from_slice::<AllowedMints>(&allowed_mints_account_info.data.borrow())?;
+        if !allowed_mints.is_initialized {
+            return Err(Error::AllowedMintsIsNotInitialized.into());
+        }
+        let allowed_mints_address_resolver = AllowedMintsAddressResolver {
+            intermediary_pubkey: intermediary_account_info.key,
+        };
+        if *allowed_mints_account_info.key != allowed_mints_address_resolver.create(allowed_mints.allowed_mints_pubkey_bump_seed)? {
+            return Err(Error::InvalidAccountPubkey.into());
+        }
+        let mint_pubkey_index = match allowed_mints.mint_pubkeys.iter().position(|mint_pubkey| *mint_pubkey == *any_mint_token_mint_account_info.key) {
+            Some(mint_pubkey_index_) => mint_pubkey_index_,
+            None => return Err(Error::AllowedMintsDoesNotContainMint.into()),
+        };
+        allowed_mints.mint_pubkeys.swap_remove(mint_pubkey_index);
+        let allowed_mints_object_length = borsh::object_length(&allowed_mints)?;
+        allowed_mints_account_info.realloc(allowed_mints_object_length, false)?;
+        borsh::to_writer(
+            &mut allowed_mints_account_info.data.borrow_mut()[..],
+            &allowed_mints,
+        )?;
+        let rent = Rent::from_account_info(rent_account_info)?;
+        let allowed_mints_rent_exemption_balance = rent.minimum_balance(allowed_mints_object_length);
+        if allowed_mints_account_info.lamports() > allowed_mints_rent_exemption_balance {
+            let lamports_from_allowed_mints = allowed_mints_account_info.lamports() - allowed_mints_rent_exemption_balance;
+            **allowed_mints_account_info.try_borrow_mut_lamports()? -= lamports_from_allowed_mints;
+            **intermediary_manager_account_info.try_borrow_mut_lamports()? += lamports_from_allowed_mints;
+        }
+        Event::AllowedMintRemoved {
+            intermediary_pubkey: *intermediary_account_info.key,
+            mint_pubkey: *any_mint_token_mint_account_info.key,
+        }
+        .emit()?;
+        Ok(())
+    }
+    fn close_intermediary<'a>(_program_id: &'a Pubkey, accounts: &'a [AccountInfo], any_mint_token_accounts_quantity: u8) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let intermediary_investor_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let intermediary_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let w_sol_token_account_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let w_sol_token_account_authority_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let risk_limits_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let allowed_mints_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let token_program_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let remaining_account_infos = account_info_iter.as_slice();
+        if remaining_account_infos.len() < 4 * (any_mint_token_accounts_quantity as usize) {
+            return Err(Error::InvalidAccountPubkey.into());
+        }
+        let (any_mint_token_account_account_infos, trader_seat_account_infos) = remaining_account_infos.split_at(4 * (any_mint_token_accounts_quantity as usize));
+        let mut account_differentiator = HashSet::<&'_ Pubkey>::with_capacity(7 + remaining_account_infos.len());
+        if !account_differentiator.insert(intermediary_investor_account_info.key)
+            || !account_differentiator.insert(intermediary_account_info.key)
+            || !account_differentiator.insert(w_sol_token_account_account_info.key)
+            || !account_differentiator.insert(w_sol_token_account_authority_account_info.key)
+            || !account_differentiator.insert(risk_limits_account_info.key)
+            || !account_differentiator.insert(allowed_mints_account_info.key)
+            || !account_differentiator.insert(token_program_account_info.key)
+            || *token_program_account_info.key != spl_token::ID
+        {
+            return Err(Error::InvalidAccountPubkey.into());
+        }
+        // The token program of a position is shared between positions, so it is left out of the differentiator.
+        for account_infos in any_mint_token_account_account_infos.chunks(4) {
+            if !account_differentiator.insert(account_infos[0].key) || !account_differentiator.insert(account_infos[1].key) || !account_differentiator.insert(account_infos[2].key) {
+                return Err(Error::InvalidAccountPubkey.into());
+            }
+        }
+        for trader_seat_account_info in trader_seat_account_infos.iter() {
+            if !account_differentiator.insert(trader_seat_account_info.key) {
+                return Err(Error::InvalidAccountPubkey.into());
+            }
+        }
+        if !intermediary_investor_account_info.is_writable
+            || !intermediary_investor_account_info.is_signer
+            || !intermediary_account_info.is_writable
+            || !w_sol_token_account_account_info.is_writable
+            || !risk_limits_account_info.is_writable
+            || !allowed_mints_account_info.is_writable
+            || any_mint_token_account_account_infos.chunks(4).any(|account_infos| !account_infos[1].is_writable)
+            || trader_seat_account_infos.iter().any(|trader_seat_account_info| !trader_seat_account_info.is_writable)
+        {
+            return Err(Error::InvalidAccountConfigurationFlags.into());
+        }
+        let intermediary_investor_lamports_before_closing = intermediary_investor_account_info.lamports();
+        if *intermediary_account_info.owner != PROGRAM_PUBKEY {
+            return Err(Error::InvalidAccountOwner.into());
+        }
+        let intermediary = Intermediary::load(&intermediary_account_info.data.borrow())?;
+        if !intermediary.is_initialized {
+            return Err(Error::IntermediaryIsNotInitialized.into());
+        }
+        if *intermediary_investor_account_info.key != intermediary.investor_pubkey {
+            return Err(Error::IntermediaryInvalidInvestor.into());
+        }
+        if intermediary.open_positions_quantity > any_mint_token_accounts_quantity as u32 {
+            return Err(Error::IntermediaryHasOpenPositions.into());
+        }
+        if intermediary.trader_seats_quantity > trader_seat_account_infos.len() as u32 {
+            return Err(Error::IntermediaryHasTraderSeats.into());
+        }
+        if *w_sol_token_account_account_info.key != intermediary.w_sol_token_account_pubkey {
+            return Err(Error::IntermediaryInvalidWSolTokenAccount.into());
+        }
+        if *w_sol_token_account_authority_account_info.key != intermediary.w_sol_token_account_authority_pubkey {
+            return Err(Error::IntermediaryInvalidWSolTokenAccountAuthority.into());
+        }
+        let risk_limits_address_resolver = RiskLimitsAddressResolver {
+            intermediary_pubkey: intermediary_account_info.key,
+        };
+        if *risk_limits_account_info.key != risk_limits_address_resolver.find().0 {
+            return Err(Error::InvalidAccountPubkey.into());
+        }
+        let allowed_mints_address_resolver = AllowedMintsAddressResolver {
+            intermediary_pubkey: intermediary_account_info.key,
+        };
+        if *allowed_mints_account_info.key != allowed_mints_address_resolver.find().0 {
+            return Err(Error::InvalidAccountPubkey.into());
+        }
+        for account_infos in any_mint_token_account_account_infos.chunks(4) {
+            let any_mint_token_mint_account_info = &account_infos[0];
+            let any_mint_token_account_account_info = &account_infos[1];
+            let any_mint_token_account_authority_account_info = &account_infos[2];
+            let any_mint_token_program_account_info = &account_infos[3];
+            Self::enforce_any_mint_token_program(
+                any_mint_token_mint_account_info,
+                any_mint_token_program_account_info,
+            )?;
+            if any_mint_token_account_account_info.owner != any_mint_token_program_account_info.key {
+                return Err(Error::InvalidAccountOwner.into());
+            }
+            let any_mint_token_account = Self::unpack_any_mint_token_account(any_mint_token_account_account_info)?;
+            if any_mint_token_account.mint != *any_mint_token_mint_account_info.key {
+                return Err(Error::TokenAccountInvalidMint.into());
+            }
+            let any_mint_token_account_address_resolver = AnyMintTokenAccountAddressResolver {
+                any_mint_token_mint_pubkey: any_mint_token_mint_account_info.key,
+                intermediary_pubkey: intermediary_account_info.key,
+            };
+            if *any_mint_token_account_account_info.key != any_mint_token_account_address_resolver.find().0 {
+                return Err(Error::InvalidAccountPubkey.into());
+            }
+            if any_mint_token_account.amount != 0 {
+                return Err(Error::TokenAccountInvalidAmount.into());
+            }
+            let any_mint_token_account_authority_address_resolver = AnyMintTokenAccountAuthorityAddressResolver {
+                any_mint_token_account_pubkey: any_mint_token_account_account_info.key,
+                intermediary_pubkey: intermediary_account_info.key,
+            };
+            let (any_mint_token_account_authority_pubkey, any_mint_token_account_authority_pubkey_bump_seed) = any_mint_token_account_authority_address_resolver.find();
+            if *any_mint_token_account_authority_account_info.key != any_mint_token_account_authority_pubkey {
+                return Err(Error::InvalidAccountPubkey.into());
+            }
+            if any_mint_token_account.owner != any_mint_token_account_authority_pubkey {
+                return Err(Error::TokenAccountInvalidAuthority.into());
+            }
+            let mut any_mint_token_account_authority_pubkey_seeds = any_mint_token_account_authority_address_resolver.get_seeds();
+            let any_mint_token_account_authority_pubkey_bump_seed_ = [any_mint_token_account_authority_pubkey_bump_seed];
+            any_mint_token_account_authority_pubkey_seeds.push(any_mint_token_account_authority_pubkey_bump_seed_.as_slice());
+            Self::harvest_any_mint_withheld_tokens(
+                any_mint_token_program_account_info,
+                any_mint_token_mint_account_info,
+                any_mint_token_account_account_info,
+            )?;
+            solana_program::program::invoke_signed(
+                &spl_token_2022::instruction::close_account(
+                    any_mint_token_program_account_info.key,
+                    any_mint_token_account_account_info.key,
+                    intermediary_investor_account_info.key,
+                    any_mint_token_account_authority_account_info.key,
+                    [].as_slice(),
+                )?,
+                vec![
+                    any_mint_token_account_account_info.clone(),
+                    intermediary_investor_account_info.clone(),
+                    any_mint_token_account_authority_account_info.clone(),
+                ]
+                .as_slice(),
+                [any_mint_token_account_authority_pubkey_seeds.as_slice()].as_slice(),
+            )?;
+        }
+        let w_sol_token_account_authority_address_resolver = WSolTokenAccountAuthorityAddressResolver {
+            w_sol_token_account_pubkey: w_sol_token_account_account_info.key,
+            intermediary_pubkey: intermediary_account_info.key,
+        };
+        let mut w_sol_token_account_authority_pubkey_seeds = w_sol_token_account_authority_address_resolver.get_seeds();
+        let w_sol_token_account_authority_pubkey_bump_seed_ = [intermediary.w_sol_token_account_authority_pubkey_bump_seed];
+        w_sol_token_account_authority_pubkey_seeds.push(w_sol_token_account_authority_pubkey_bump_seed_.as_slice());
+        solana_program::program::invoke_signed(
+            &spl_token::instruction::close_account(
+                token_program_account_info.key,
+                w_sol_token_account_account_info.key,
+                intermediary_investor_account_info.key,
+                w_sol_token_account_authority_account_info.key,
+                [].as_slice(),
+            )?,
+            vec![
+                w_sol_token_account_account_info.clone(),
+                intermediary_investor_account_info.clone(),
+                w_sol_token_account_authority_account_info.clone(),
+            ]
+            .as_slice(),
+            [w_sol_token_account_authority_pubkey_seeds.as_slice()].as_slice(),
+        )?;
+        for trader_seat_account_info in trader_seat_account_infos.iter() {
+            if *trader_seat_account_info.owner != PROGRAM_PUBKEY {
+                return Err(Error::InvalidAccountOwner.into());
+            }
+            let trader_seat = borsh::from_slice::<TraderSeat>(&trader_seat_account_info.data.borrow())?;
+            if !trader_seat.is_initialized {
+                return Err(Error::TraderSeatIsNotInitialized.into());
+            }
+            let trader_seat_address_resolver = TraderSeatAddressResolver {
+                intermediary_pubkey: intermediary_account_info.key,
+                trader_pubkey: &trader_seat.trader_pubkey,
+            };
+            if trader_seat.intermediary_pubkey != *intermediary_account_info.key
+                || *trader_seat_account_info.key != trader_seat_address_resolver.create(trader_seat.trader_seat_pubkey_bump_seed)?
+            {
+                return Err(Error::InvalidAccountPubkey.into());
+            }
+            let lamports_to_investor = trader_seat_account_info.lamports();
+            **trader_seat_account_info.try_borrow_mut_lamports()? -= lamports_to_investor;
+            **intermediary_investor_account_info.try_borrow_mut_lamports()? += lamports_to_investor;
+            trader_seat_account_info.data.borrow_mut().fill(0);
+            trader_seat_account_info.realloc(0, false)?;
+            Event::TraderSeatRemoved {
+                intermediary_pubkey: *intermediary_account_info.key,
+                trader_pubkey: trader_seat.trader_pubkey,
+                spent_lamports: trader_seat.spent_lamports,
+                received_lamports: trader_seat.received_lamports,
+            }
+            .emit()?;
+        }
+        for account_info in [risk_limits_account_info, allowed_mints_account_info, intermediary_account_info] {
+            if account_info.data_is_empty() && account_info.lamports() == 0 {
+                continue;
+            }
+            if *account_info.owner != PROGRAM_PUBKEY {
+                return Err(Error::InvalidAccountOwner.into());
+            }
+            let lamports_to_investor = account_info.lamports();
+            **account_info.try_borrow_mut_lamports()? -= lamports_to_investor;
+            **intermediary_investor_account_info.try_borrow_mut_lamports()? += lamports_to_investor;
+            account_info.data.borrow_mut().fill(0);
+            account_info.realloc(0, false)?;
+        }
+        Event::IntermediaryClosed {
+            intermediary_pubkey: *intermediary_account_info.key,
+            lamports_to_investor: intermediary_investor_account_info.lamports() - intermediary_investor_lamports_before_closing,
+        }
+        .emit()?;
+        Ok(())
+    }
+    fn accept_manager<'a>(_program_id: &'a Pubkey, accounts: &'a [AccountInfo]) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let intermediary_pending_manager_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let intermediary_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        if intermediary_pending_manager_account_info.key == intermediary_account_info.key {
+            return Err(Error::InvalidAccountPubkey.into());
+        }
+        if !intermediary_pending_manager_account_info.is_signer || !intermediary_account_info.is_writable {
+            return Err(Error::InvalidAccountConfigurationFlags.into());
+        }
+        if *intermediary_account_info.owner != PROGRAM_PUBKEY {
+            return Err(Error::InvalidAccountOwner.into());
+        }
+        let mut intermediary = Intermediary::load(&intermediary_account_info.data.borrow())?;
+        if !intermediary.is_initialized {
+            return Err(Error::IntermediaryIsNotInitialized.into());
+        }
+        if intermediary.version != Intermediary::VERSION {
+            return Err(Error::IntermediaryIsNotMigrated.into());
+        }
+        if intermediary.pending_manager_pubkey == Pubkey::default() || *intermediary_pending_manager_account_info.key != intermediary.pending_manager_pubkey {
+            return Err(Error::IntermediaryInvalidPendingManager.into());
+        }
+        if *intermediary_pending_manager_account_info.key == intermediary.investor_pubkey
+            || *intermediary_pending_manager_account_info.key == intermediary.manager_pubkey
+            || *intermediary_pending_manager_account_info.key == intermediary.trader_pubkey
+        {
+            return Err(Error::IntermediaryInvalidManager.into());
+        }
+        intermediary.manager_pubkey = intermediary.pending_manager_pubkey;
+        intermediary.pending_manager_pubkey = Pubkey::default();
+        borsh::to_writer(
+            &mut intermediary_account_info.data.borrow_mut()[..],
+            &intermediary,
+        )?;
+        Event::RoleAccepted {
+            intermediary_pubkey: *intermediary_account_info.key,
+            role: Role::Manager,
+            pubkey: intermediary.manager_pubkey,
+        }
+        .emit()?;
+        Ok(())
+    }
+    fn accept_trader<'a>(_program_id: &'a Pubkey, accounts: &'a [AccountInfo]) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let intermediary_pending_trader_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let intermediary_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        if intermediary_pending_trader_account_info.key == intermediary_account_info.key {
+            return Err(Error::InvalidAccountPubkey.into());
+        }
+        if !intermediary_pending_trader_account_info.is_signer || !intermediary_account_info.is_writable {
+            return Err(Error::InvalidAccountConfigurationFlags.into());
+        }
+        if *intermediary_account_info.owner != PROGRAM_PUBKEY {
+            return Err(Error::InvalidAccountOwner.into());
+        }
+        let mut intermediary = Intermediary::load(&intermediary_account_info.data.borrow())?;
+        if !intermediary.is_initialized {
+            return Err(Error::IntermediaryIsNotInitialized.into());
+        }
+        if intermediary.version != Intermediary::VERSION {
+            return Err(Error::IntermediaryIsNotMigrated.into());
+        }
+        if intermediary.pending_trader_pubkey == Pubkey::default() || *intermediary_pending_trader_account_info.key != intermediary.pending_trader_pubkey {
+            return Err(Error::IntermediaryInvalidPendingTrader.into());
+        }
+        if *intermediary_pending_trader_account_info.key == intermediary.investor_pubkey
+            || *intermediary_pending_trader_account_info.key == intermediary.manager_pubkey
+            || *intermediary_pending_trader_account_info.key == intermediary.trader_pubkey
+        {
+            return Err(Error::IntermediaryInvalidTrader.into());
+        }
+        intermediary.trader_pubkey = intermediary.pending_trader_pubkey;
+        intermediary.pending_trader_pubkey = Pubkey::default();
+        borsh::to_writer(
+            &mut intermediary_account_info.data.borrow_mut()[..],
+            &intermediary,
+        )?;
+        Event::RoleAccepted {
+            intermediary_pubkey: *intermediary_account_info.key,
+            role: Role::Trader,
+            pubkey: intermediary.trader_pubkey,
+        }
+        .emit()?;
+        Ok(())
+    }
+    fn cancel_manager_proposal<'a>(_program_id: &'a Pubkey, accounts: &'a [AccountInfo]) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let authority_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let intermediary_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        if authority_account_info.key == intermediary_account_info.key {
+            return Err(Error::InvalidAccountPubkey.into());
+        }
+        if !authority_account_info.is_signer || !intermediary_account_info.is_writable {
+            return Err(Error::InvalidAccountConfigurationFlags.into());
+        }
+        if *intermediary_account_info.owner != PROGRAM_PUBKEY {
+            return Err(Error::InvalidAccountOwner.into());
+        }
+        let mut intermediary = Intermediary::load(&intermediary_account_info.data.borrow())?;
+        if !intermediary.is_initialized {
+            return Err(Error::IntermediaryIsNotInitialized.into());
+        }
+        if intermediary.version != Intermediary::VERSION {
+            return Err(Error::IntermediaryIsNotMigrated.into());
+        }
+        if *authority_account_info.key != intermediary.investor_pubkey && *authority_account_info.key != intermediary.manager_pubkey {
+            return Err(Error::IntermediaryInvalidInvestor.into());
+        }
+        if intermediary.pending_manager_pubkey == Pubkey::default() {
+            return Err(Error::IntermediaryInvalidPendingManager.into());
+        }
+        let intermediary_pending_manager_pubkey = intermediary.pending_manager_pubkey;
+        intermediary.pending_manager_pubkey = Pubkey::default();
+        borsh::to_writer(
+            &mut intermediary_account_info.data.borrow_mut()[..],
+            &intermediary,
+        )?;
+        Event::RoleProposalCancelled {
+            intermediary_pubkey: *intermediary_account_info.key,
+            role: Role::Manager,
+            pubkey: intermediary_pending_manager_pubkey,
+        }
+        .emit()?;
+        Ok(())
+    }
+    fn cancel_trader_proposal<'a>(_program_id: &'a Pubkey, accounts: &'a [AccountInfo]) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let authority_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let intermediary_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        if authority_account_info.key == intermediary_account_info.key {
+            return Err(Error::InvalidAccountPubkey.into());
+        }
+        if !authority_account_info.is_signer || !intermediary_account_info.is_writable {
+            return Err(Error::InvalidAccountConfigurationFlags.into());
+        }
+        if *intermediary_account_info.owner != PROGRAM_PUBKEY {
+            return Err(Error::InvalidAccountOwner.into());
+        }
+        let mut intermediary = Intermediary::load(&intermediary_account_info.data.borrow())?;
+        if !intermediary.is_initialized {
+            return Err(Error::IntermediaryIsNotInitialized.into());
+        }
+        if intermediary.version != Intermediary::VERSION {
+            return Err(Error::IntermediaryIsNotMigrated.into());
+        }
+        if *authority_account_info.key != intermediary.manager_pubkey && *authority_account_info.key != intermediary.trader_pubkey {
+            return Err(Error::IntermediaryInvalidManager.into());
+        }
+        if intermediary.pending_trader_pubkey == Pubkey::default() {
+            return Err(Error::IntermediaryInvalidPendingTrader.into());
+        }
+        let intermediary_pending_trader_pubkey = intermediary.pending_trader_pubkey;
+        intermediary.pending_trader_pubkey = Pubkey::default();
+        borsh::to_writer(
+            &mut intermediary_account_info.data.borrow_mut()[..],
+            &intermediary,
+        )?;
+        Event::RoleProposalCancelled {
+            intermediary_pubkey: *intermediary_account_info.key,
+            role: Role::Trader,
+            pubkey: intermediary_pending_trader_pubkey,
+        }
+        .emit()?;
+        Ok(())
+    }
+    fn add_trader_seat<'a>(_program_id: &'a Pubkey, accounts: &'a [AccountInfo], allocated_lamports: u64, trader_seat_pubkey_bump_seed: u8) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let intermediary_manager_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let intermediary_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let intermediary_trader_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let trader_seat_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let system_program_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let rent_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let trader_seat_address_resolver = TraderSeatAddressResolver {
+            intermediary_pubkey: intermediary_account_info.key,
+            trader_pubkey: intermediary_trader_account_info.key,
+        };
+        let mut account_differentiator = HashSet::<&'_ Pubkey>::with_capacity(6);
+        if !account_differentiator.insert(intermediary_manager_account_info.key)
+            || !account_differentiator.insert(intermediary_account_info.key)
+            || !account_differentiator.insert(intermediary_trader_account_info.key)
+            || !account_differentiator.insert(trader_seat_account_info.key)
+            || !account_differentiator.insert(system_program_account_info.key)
+            || !account_differentiator.insert(rent_account_info.key)
+            || *trader_seat_account_info.key != trader_seat_address_resolver.create(trader_seat_pubkey_bump_seed)?
+            || *system_program_account_info.key != solana_program::system_program::ID
+            || *rent_account_info.key != solana_program::sysvar::rent::ID
+        {
+            return Err(Error::InvalidAccountPubkey.into());
+        }
+        if !intermediary_manager_account_info.is_signer || !intermediary_manager_account_info.is_writable || !intermediary_account_info.is_writable || !trader_seat_account_info.is_writable {
+            return Err(Error::InvalidAccountConfigurationFlags.into());
+        }
+        if *intermediary_trader_account_info.owner != solana_program::system_program::ID {
+            return Err(Error::InvalidAccountOwner.into());
+        }
+        if !trader_seat_account_info.data_is_empty() {
+            return Err(Error::TraderSeatIsAlreadyInitialized.into());
+        }
+        if *intermediary_account_info.owner != PROGRAM_PUBKEY {
+            return Err(Error::InvalidAccountOwner.into());
+        }
+        let mut intermediary = Intermediary::load(&intermediary_account_info.data.borrow())?;
+        if !intermediary.is_initialized {
+            return Err(Error::IntermediaryIsNotInitialized.into());
+        }
+        if intermediary.version != Intermediary::VERSION {
+            return Err(Error::IntermediaryIsNotMigrated.into());
+        }
+        if *intermediary_manager_account_info.key != intermediary.manager_pubkey {
+            return Err(Error::IntermediaryInvalidManager.into());
+        }
+        let trader_seat = TraderSeat {
+            is_initialized: true,
+            intermediary_pubkey: *intermediary_account_info.key,
+            trader_pubkey: *intermediary_trader_account_info.key,
+            allocated_lamports,
+            spent_lamports: 0,
+            received_lamports: 0,
+            trades_quantity: 0,
+            trader_seat_pubkey_bump_seed,
+        };
+        let trader_seat_object_length = borsh::object_length(&trader_seat)?;
+        let rent = Rent::from_account_info(rent_account_info)?;
+        let trader_seat_rent_exemption_balance = rent.minimum_balance(trader_seat_object_length);
+        if intermediary_manager_account_info.lamports() < trader_seat_rent_exemption_balance {
+            return Err(Error::InvalidAccountLamports.into());
+        }
+        let mut trader_seat_pubkey_seeds = trader_seat_address_resolver.get_seeds();
+        let trader_seat_pubkey_bump_seed_ = [trader_seat_pubkey_bump_seed];
+        trader_seat_pubkey_seeds.push(trader_seat_pubkey_bump_seed_.as_slice());
+        solana_program::program::invoke_signed(
+            &solana_program::system_instruction::create_account(
+                intermediary_manager_account_info.key,
+                trader_seat_account_info.key,
+                trader_seat_rent_exemption_balance,
+                trader_seat_object_length as u64,
+                &PROGRAM_PUBKEY,
+            ),
+            vec![
+                intermediary_manager_account_info.clone(),
+                trader_seat_account_info.clone(),
+            ]
+            .as_slice(),
+            [trader_seat_pubkey_seeds.as_slice()].as_slice(),
+        )?;
+        borsh::to_writer(
+            &mut trader_seat_account_info.data.borrow_mut()[..],
+            &trader_seat,
+        )?;
+        intermediary.trader_seats_quantity = intermediary.trader_seats_quantity.saturating_add(1);
+        borsh::to_writer(
+            &mut intermediary_account_info.data.borrow_mut()[..],
+            &intermediary,
+        )?;
+        Event::TraderSeatAdded {
+            intermediary_pubkey: *intermediary_account_info.key,
+            trader_pubkey: *intermediary_trader_account_info.key,
+            allocated_lamports,
+        }
+        .emit()?;
+        Ok(())
+    }
+    fn remove_trader_seat<'a>(_program_id: &'a Pubkey, accounts: &'a [AccountInfo]) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let intermediary_manager_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let intermediary_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let trader_seat_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let mut account_differentiator = HashSet::<&'_ Pubkey>::with_capacity(3);
+        if !account_differentiator.insert(intermediary_manager_account_info.key)
+            || !account_differentiator.insert(intermediary_account_info.key)
+            || !account_differentiator.insert(trader_seat_account_info.key)
+        {
+            return Err(Error::InvalidAccountPubkey.into());
+        }
+        if !intermediary_manager_account_info.is_signer || !intermediary_manager_account_info.is_writable || !intermediary_account_info.is_writable || !trader_seat_account_info.is_writable {
+            return Err(Error::InvalidAccountConfigurationFlags.into());
+        }
+        if trader_seat_account_info.data_is_empty() {
+            return Err(Error::TraderSeatIsNotInitialized.into());
+        }
+        if *trader_seat_account_info.owner != PROGRAM_PUBKEY {
+            return Err(Error::InvalidAccountOwner.into());
+        }
+        if *intermediary_account_info.owner != PROGRAM_PUBKEY {
+            return Err(Error::InvalidAccountOwner.into());
+        }
+        let mut intermediary = Intermediary::load(&intermediary_account_info.data.borrow())?;
+        if !intermediary.is_initialized {
+            return Err(Error::IntermediaryIsNotInitialized.into());
+        }
+        if intermediary.version != Intermediary::VERSION {
+            return Err(Error::IntermediaryIsNotMigrated.into());
+        }
+        if *intermediary_manager_account_info.key != intermediary.manager_pubkey {
+            return Err(Error::IntermediaryInvalidManager.into());
+        }
+        let trader_seat = borsh::from_slice::<TraderSeat>(&trader_seat_account_info.data.borrow())?;
+        if !trader_seat.is_initialized {
+            return Err(Error::TraderSeatIsNotInitialized.into());
+        }
+        let trader_seat_address_resolver = TraderSeatAddressResolver {
+            intermediary_pubkey: intermediary_account_info.key,
+            trader_pubkey: &trader_seat.trader_pubkey,
+        };
+        if trader_seat.intermediary_pubkey != *intermediary_account_info.key
+            || *trader_seat_account_info.key != trader_seat_address_resolver.create(trader_seat.trader_seat_pubkey_bump_seed)?
+        {
+            return Err(Error::InvalidAccountPubkey.into());
+        }
+        let lamports_to_manager = trader_seat_account_info.lamports();
+        **trader_seat_account_info.try_borrow_mut_lamports()? -= lamports_to_manager;
+        **intermediary_manager_account_info.try_borrow_mut_lamports()? += lamports_to_manager;
+        trader_seat_account_info.data.borrow_mut().fill(0);
+        trader_seat_account_info.realloc(0, false)?;
+        intermediary.trader_seats_quantity = intermediary.trader_seats_quantity.saturating_sub(1);
+        borsh::to_writer(
+            &mut intermediary_account_info.data.borrow_mut()[..],
+            &intermediary,
+        )?;
+        Event::TraderSeatRemoved {
+            intermediary_pubkey: *intermediary_account_info.key,
+            trader_pubkey: trader_seat.trader_pubkey,
+            spent_lamports: trader_seat.spent_lamports,
+            received_lamports: trader_seat.received_lamports,
+        }
+        .emit()?;
+        Ok(())
+    }
+    fn pause<'a>(_program_id: &'a Pubkey, accounts: &'a [AccountInfo]) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let authority_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let intermediary_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        if authority_account_info.key == intermediary_account_info.key {
+            return Err(Error::InvalidAccountPubkey.into());
+        }
+        if !authority_account_info.is_signer || !intermediary_account_info.is_writable {
+            return Err(Error::InvalidAccountConfigurationFlags.into());
+        }
+        if *intermediary_account_info.owner != PROGRAM_PUBKEY {
+            return Err(Error::InvalidAccountOwner.into());
+        }
+        let mut intermediary = Intermediary::load(&intermediary_account_info.data.borrow())?;
+        if !intermediary.is_initialized {
+            return Err(Error::IntermediaryIsNotInitialized.into());
+        }
+        if intermediary.version != Intermediary::VERSION {
+            return Err(Error::IntermediaryIsNotMigrated.into());
+        }
+        if *authority_account_info.key != intermediary.investor_pubkey && *authority_account_info.key != intermediary.manager_pubkey {
+            return Err(Error::IntermediaryInvalidInvestor.into());
+        }
+        if intermediary.is_paused {
+            return Err(Error::IntermediaryPaused.into());
+        }
+        intermediary.is_paused = true;
+        borsh::to_writer(
+            &mut intermediary_account_info.data.borrow_mut()[..],
+            &intermediary,
+        )?;
+        Event::Paused {
+            intermediary_pubkey: *intermediary_account_info.key,
+            authority_pubkey: *authority_account_info.key,
+        }
+        .emit()?;
+        Ok(())
+    }
+    fn unpause<'a>(_program_id: &'a Pubkey, accounts: &'a [AccountInfo]) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let intermediary_investor_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let intermediary_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        if intermediary_investor_account_info.key == intermediary_account_info.key {
+            return Err(Error::InvalidAccountPubkey.into());
+        }
+        if !intermediary_investor_account_info.is_signer || !intermediary_account_info.is_writable {
+            return Err(Error::InvalidAccountConfigurationFlags.into());
+        }
+        if *intermediary_account_info.owner != PROGRAM_PUBKEY {
+            return Err(Error::InvalidAccountOwner.into());
+        }
+        let mut intermediary = Intermediary::load(&intermediary_account_info.data.borrow())?;
+        if !intermediary.is_initialized {
+            return Err(Error::IntermediaryIsNotInitialized.into());
+        }
+        if intermediary.version != Intermediary::VERSION {
+            return Err(Error::IntermediaryIsNotMigrated.into());
+        }
+        if *intermediary_investor_account_info.key != intermediary.investor_pubkey {
+            return Err(Error::IntermediaryInvalidInvestor.into());
+        }
+        if !intermediary.is_paused {
+            return Err(Error::IntermediaryIsNotPaused.into());
+        }
+        intermediary.is_paused = false;
+        borsh::to_writer(
+            &mut intermediary_account_info.data.borrow_mut()[..],
+            &intermediary,
+        )?;
+        Event::Unpaused {
+            intermediary_pubkey: *intermediary_account_info.key,
+            authority_pubkey: *intermediary_investor_account_info.key,
+        }
+        .emit()?;
+        Ok(())
+    }
+    fn set_maximum_price_deviation<'a>(_program_id: &'a Pubkey, accounts: &'a [AccountInfo], maximum_price_deviation_basis_points: u16) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let intermediary_manager_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let intermediary_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        if intermediary_manager_account_info.key == intermediary_account_info.key {
+            return Err(Error::InvalidAccountPubkey.into());
+        }
+        if !intermediary_manager_account_info.is_signer || !intermediary_account_info.is_writable {
+            return Err(Error::InvalidAccountConfigurationFlags.into());
+        }
+        if maximum_price_deviation_basis_points > 10_000 {
+            return Err(Error::IntermediaryInvalidMaximumPriceDeviation.into());
+        }
+        if *intermediary_account_info.owner != PROGRAM_PUBKEY {
+            return Err(Error::InvalidAccountOwner.into());
+        }
+        let mut intermediary = Intermediary::load(&intermediary_account_info.data.borrow())?;
+        if !intermediary.is_initialized {
+            return Err(Error::IntermediaryIsNotInitialized.into());
+        }
+        if intermediary.version != Intermediary::VERSION {
+            return Err(Error::IntermediaryIsNotMigrated.into());
+        }
+        if *intermediary_manager_account_info.key != intermediary.manager_pubkey {
+            return Err(Error::IntermediaryInvalidManager.into());
+        }
+        intermediary.maximum_price_deviation_basis_points = maximum_price_deviation_basis_points;
+        borsh::to_writer(
+            &mut intermediary_account_info.data.borrow_mut()[..],
+            &intermediary,
+        )?;
+        Event::MaximumPriceDeviationSet {
+            intermediary_pubkey: *intermediary_account_info.key,
+            maximum_price_deviation_basis_points,
+        }
+        .emit()?;
+        Ok(())
+    }
+    fn migrate_intermediary<'a>(_program_id: &'a Pubkey, accounts: &'a [AccountInfo]) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let authority_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let intermediary_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let system_program_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let rent_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let mut account_differentiator = HashSet::<&'_ Pubkey>::with_capacity(4);
+        if !account_differentiator.insert(authority_account_info.key)
+            || !account_differentiator.insert(intermediary_account_info.key)
+            || !account_differentiator.insert(system_program_account_info.key)
+            || !account_differentiator.insert(rent_account_info.key)
+            || *system_program_account_info.key != solana_program::system_program::ID
+            || *rent_account_info.key != solana_program::sysvar::rent::ID
+        {
+            return Err(Error::InvalidAccountPubkey.into());
+        }
+        if !authority_account_info.is_signer || !authority_account_info.is_writable || !intermediary_account_info.is_writable {
+            return Err(Error::InvalidAccountConfigurationFlags.into());
+        }
+        if *intermediary_account_info.owner != PROGRAM_PUBKEY {
+            return Err(Error::InvalidAccountOwner.into());
+        }
+        let mut intermediary = Intermediary::load(&intermediary_account_info.data.borrow())?;
+        if !intermediary.is_initialized {
+            return Err(Error::IntermediaryIsNotInitialized.into());
+        }
+        if *authority_account_info.key != intermediary.investor_pubkey && *authority_account_info.key != intermediary.manager_pubkey {
+            return Err(Error::IntermediaryInvalidInvestor.into());
+        }
+        if intermediary.version == Intermediary::VERSION {
+            return Err(Error::IntermediaryIsAlreadyMigrated.into());
+        }
+        let previous_version = intermediary.version;
+        intermediary.version = Intermediary::VERSION;
+        let intermediary_object_length = borsh::object_length(&intermediary)?;
+        let rent = Rent::from_account_info(rent_account_info)?;
+        let intermediary_rent_exemption_balance = rent.minimum_balance(intermediary_object_length);
+        if intermediary_rent_exemption_balance > intermediary_account_info.lamports() {
+            let lamports_to_intermediary = intermediary_rent_exemption_balance - intermediary_account_info.lamports();
+            if authority_account_info.lamports() < lamports_to_intermediary {
+                return Err(Error::InvalidAccountLamports.into());
+            }
+            solana_program::program::invoke(
+                &solana_program::system_instruction::transfer(
+                    authority_account_info.key,
+                    intermediary_account_info.key,
+                    lamports_to_intermediary,
+                ),
+                vec![
+                    authority_account_info.clone(),
+                    intermediary_account_info.clone(),
+                ]
+                .as_slice(),
+            )?;
+        }
+        intermediary_account_info.realloc(intermediary_object_length, true)?;
+        borsh::to_writer(
+            &mut intermediary_account_info.data.borrow_mut()[..],
+            &intermediary,
+        )?;
+        Event::IntermediaryMigrated {
+            intermediary_pubkey: *intermediary_account_info.key,
+            previous_version,
+            version: intermediary.version,
+        }
+        .emit()?;
+        Ok(())
+    }
+    fn liquidate_all<'a, 'b>(_program_id: &'a Pubkey, accounts: &'a [AccountInfo<'b>], venues: Vec<Venue>, minimum_output_token_amounts: Vec<u64>) -> ProgramResult {
+        const LIQUIDATION_LEG_FIXED_ACCOUNTS_QUANTITY: usize = 4;
+        if venues.is_empty() || venues.len() != minimum_output_token_amounts.len() {
+            return Err(Error::InvalidAccountPubkey.into());
+        }
+        let account_info_iter = &mut accounts.iter();
+        let authority_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let intermediary_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let w_sol_token_account_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let temporary_w_sol_token_account_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let w_sol_token_mint_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let system_program_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let rent_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let token_program_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        // Each leg is the position accounts followed by the accounts of its venue.
+        let mut liquidation_leg_account_infos = Vec::<(&'a [AccountInfo<'b>], AnyVenueAdapter<'a, 'b>)>::with_capacity(venues.len());
+        for venue in venues.iter() {
+            let position_account_infos = account_info_iter.as_slice();
+            if position_account_infos.len() < LIQUIDATION_LEG_FIXED_ACCOUNTS_QUANTITY {
+                return Err(Error::InvalidAccountPubkey.into());
+            }
+            for _ in 0..LIQUIDATION_LEG_FIXED_ACCOUNTS_QUANTITY {
+                account_info_iter.next();
+            }
+            let venue_accounts_quantity = crate::venue::get_accounts_writability(*venue)?.len();
+            let venue_account_infos = account_info_iter.take(venue_accounts_quantity).collect::<Vec<&'a AccountInfo<'b>>>();
+            if venue_account_infos.len() != venue_accounts_quantity {
+                return Err(Error::InvalidAccountPubkey.into());
+            }
+            liquidation_leg_account_infos.push(
+                (
+                    &position_account_infos[..LIQUIDATION_LEG_FIXED_ACCOUNTS_QUANTITY],
+                    AnyVenueAdapter::load(
+                        *venue,
+                        venue_account_infos.as_slice(),
+                    )?,
+                ),
+            );
+        }
+        if account_info_iter.next().is_some() {
+            return Err(Error::InvalidAccountPubkey.into());
+        }
+        let mut account_differentiator = HashSet::<&'_ Pubkey>::with_capacity(8 + 3 * venues.len());
+        if !account_differentiator.insert(authority_account_info.key)
+            || !account_differentiator.insert(intermediary_account_info.key)
+            || !account_differentiator.insert(w_sol_token_account_account_info.key)
+            || !account_differentiator.insert(temporary_w_sol_token_account_account_info.key)
+            || !account_differentiator.insert(w_sol_token_mint_account_info.key)
+            || !account_differentiator.insert(system_program_account_info.key)
+            || !account_differentiator.insert(rent_account_info.key)
+            || !account_differentiator.insert(token_program_account_info.key)
+            || *w_sol_token_mint_account_info.key != spl_token::native_mint::ID
+            || *system_program_account_info.key != solana_program::system_program::ID
+            || *rent_account_info.key != solana_program::sysvar::rent::ID
+            || *token_program_account_info.key != spl_token::ID
+        {
+            return Err(Error::InvalidAccountPubkey.into());
+        }
+        for (position_account_infos, _) in liquidation_leg_account_infos.iter() {
+            if !account_differentiator.insert(position_account_infos[0].key)
+                || !account_differentiator.insert(position_account_infos[1].key)
+                || !account_differentiator.insert(position_account_infos[2].key)
+            {
+                return Err(Error::InvalidAccountPubkey.into());
+            }
+        }
+        if !authority_account_info.is_signer
+            || !authority_account_info.is_writable
+            || !intermediary_account_info.is_writable
+            || !w_sol_token_account_account_info.is_writable
+            || !temporary_w_sol_token_account_account_info.is_writable
+            || liquidation_leg_account_infos.iter().any(|(position_account_infos, _)| !position_account_infos[1].is_writable)
+        {
+            return Err(Error::InvalidAccountConfigurationFlags.into());
+        }
+        if *intermediary_account_info.owner != PROGRAM_PUBKEY {
+            return Err(Error::InvalidAccountOwner.into());
+        }
+        let intermediary = Intermediary::load(&intermediary_account_info.data.borrow())?;
+        if !intermediary.is_initialized {
+            return Err(Error::IntermediaryIsNotInitialized.into());
+        }
+        if *authority_account_info.key != intermediary.investor_pubkey && *authority_account_info.key != intermediary.manager_pubkey {
+            return Err(Error::IntermediaryInvalidInvestorOrManager.into());
+        }
+        if *w_sol_token_account_account_info.key != intermediary.w_sol_token_account_pubkey {
+            return Err(Error::IntermediaryInvalidWSolTokenAccount.into());
+        }
+        if *temporary_w_sol_token_account_account_info.key != intermediary.temporary_w_sol_token_account_pubkey {
+            return Err(Error::IntermediaryInvalidTemporaryWSolTokenAccount.into());
+        }
+        let rent = Rent::from_account_info(rent_account_info)?;
+        let token_account_rent_exemption_balance = rent.minimum_balance(<Account as Pack>::LEN);
+        let temporary_w_sol_token_account_address_resolver = TemporaryWSolTokenAccountAddressResolver {
+            w_sol_token_account_pubkey: w_sol_token_account_account_info.key,
+        };
+        let mut temporary_w_sol_token_account_pubkey_seeds = temporary_w_sol_token_account_address_resolver.get_seeds();
+        let temporary_w_sol_token_account_pubkey_bump_seed_ = [intermediary.temporary_w_sol_token_account_pubkey_bump_seed];
+        temporary_w_sol_token_account_pubkey_seeds.push(temporary_w_sol_token_account_pubkey_bump_seed_.as_slice());
+        for ((position_account_infos, any_venue_adapter), (venue, minimum_output_token_amount)) in liquidation_leg_account_infos.iter().zip(venues.iter().zip(minimum_output_token_amounts)) {
+            let any_mint_token_mint_account_info = &position_account_infos[0];
+            let any_mint_token_account_account_info = &position_account_infos[1];
+            let any_mint_token_account_authority_account_info = &position_account_infos[2];
+            let any_mint_token_program_account_info = &position_account_infos[3];
+            Self::enforce_any_mint_token_program(
+                any_mint_token_mint_account_info,
+                any_mint_token_program_account_info,
+            )?;
+            let any_mint_token_account_address_resolver = AnyMintTokenAccountAddressResolver {
+                any_mint_token_mint_pubkey: any_mint_token_mint_account_info.key,
+                intermediary_pubkey: intermediary_account_info.key,
+            };
+            if *any_mint_token_mint_account_info.key == spl_token::native_mint::ID || *any_mint_token_account_account_info.key != any_mint_token_account_address_resolver.find().0 {
+                return Err(Error::InvalidAccountPubkey.into());
+            }
+            let any_mint_token_account_authority_address_resolver = AnyMintTokenAccountAuthorityAddressResolver {
+                any_mint_token_account_pubkey: any_mint_token_account_account_info.key,
+                intermediary_pubkey: intermediary_account_info.key,
+            };
+            let (any_mint_token_account_authority_pubkey, any_mint_token_account_authority_pubkey_bump_seed) = any_mint_token_account_authority_address_resolver.find();
+            if *any_mint_token_account_authority_account_info.key != any_mint_token_account_authority_pubkey {
+                return Err(Error::InvalidAccountPubkey.into());
+            }
+            if any_mint_token_account_account_info.owner != any_mint_token_program_account_info.key {
+                return Err(Error::InvalidAccountOwner.into());
+            }
+            let any_mint_token_account = Self::unpack_any_mint_token_account(any_mint_token_account_account_info)?;
+            if any_mint_token_account.mint != *any_mint_token_mint_account_info.key {
+                return Err(Error::TokenAccountInvalidMint.into());
+            }
+            if any_mint_token_account.owner != any_mint_token_account_authority_pubkey {
+                return Err(Error::TokenAccountInvalidAuthority.into());
+            }
+            let mut any_mint_token_account_authority_pubkey_seeds = any_mint_token_account_authority_address_resolver.get_seeds();
+            let any_mint_token_account_authority_pubkey_bump_seed_ = [any_mint_token_account_authority_pubkey_bump_seed];
+            any_mint_token_account_authority_pubkey_seeds.push(any_mint_token_account_authority_pubkey_bump_seed_.as_slice());
+            let amount_out = if any_mint_token_account.amount != 0 {
+                // The venue pays into a temporary wSOL account owned by the swap authority, which is emptied into the treasury
+                // and closed back into the liquidation authority that funded it.
+                solana_program::program::invoke_signed(
+                    &solana_program::system_instruction::create_account(
+                        authority_account_info.key,
+                        temporary_w_sol_token_account_account_info.key,
+                        token_account_rent_exemption_balance,
+                        <Account as Pack>::LEN as u64,
+                        token_program_account_info.key,
+                    ),
+                    vec![
+                        authority_account_info.clone(),
+                        temporary_w_sol_token_account_account_info.clone(),
+                    ]
+                    .as_slice(),
+                    [temporary_w_sol_token_account_pubkey_seeds.as_slice()].as_slice(),
+                )?;
+                solana_program::program::invoke(
+                    &spl_token::instruction::initialize_account(
+                        token_program_account_info.key,
+                        temporary_w_sol_token_account_account_info.key,
+                        w_sol_token_mint_account_info.key,
+                        any_mint_token_account_authority_account_info.key,
+                    )?,
+                    vec![
+                        temporary_w_sol_token_account_account_info.clone(),
+                        w_sol_token_mint_account_info.clone(),
+                        any_mint_token_account_authority_account_info.clone(),
+                        rent_account_info.clone(),
+                    ]
+                    .as_slice(),
+                )?;
+                let venue_swap = VenueSwap {
+                    side: Side::Sell,
+                    source_token_account_account_info: any_mint_token_account_account_info,
+                    destination_token_account_account_info: temporary_w_sol_token_account_account_info,
+                    authority_account_info: any_mint_token_account_authority_account_info,
+                    quote_token_mint_account_info: Some(w_sol_token_mint_account_info),
+                    base_token_mint_account_info: any_mint_token_mint_account_info,
+                    system_program_account_info,
+                    token_program_account_info,
+                    base_token_program_account_info: any_mint_token_program_account_info,
+                    amount_in: any_mint_token_account.amount,
+                    minimum_amount_out: minimum_output_token_amount,
+                };
+                let reserves = any_venue_adapter.get_reserves(&venue_swap)?;
+                let (swap_instruction, swap_account_infos) = any_venue_adapter.create_swap_instruction(&venue_swap)?;
+                solana_program::program::invoke_signed(
+                    &swap_instruction,
+                    swap_account_infos.as_slice(),
+                    [any_mint_token_account_authority_pubkey_seeds.as_slice()].as_slice(),
+                )?;
+                let temporary_w_sol_token_account_amount = Account::unpack(&temporary_w_sol_token_account_account_info.data.borrow())?.amount;
+                if temporary_w_sol_token_account_amount < minimum_output_token_amount {
+                    return Err(Error::TokenAccountInvalidAmount.into());
+                }
+                Self::enforce_price_deviation(
+                    intermediary.maximum_price_deviation_basis_points,
+                    reserves,
+                    any_mint_token_account.amount,
+                    temporary_w_sol_token_account_amount,
+                )?;
+                solana_program::program::invoke_signed(
+                    &spl_token::instruction::transfer(
+                        token_program_account_info.key,
+                        temporary_w_sol_token_account_account_info.key,
+                        w_sol_token_account_account_info.key,
+                        any_mint_token_account_authority_account_info.key,
+                        [].as_slice(),
+                        temporary_w_sol_token_account_amount,
+                    )?,
+                    vec![
+                        temporary_w_sol_token_account_account_info.clone(),
+                        w_sol_token_account_account_info.clone(),
+                        any_mint_token_account_authority_account_info.clone(),
+                    ]
+                    .as_slice(),
+                    [any_mint_token_account_authority_pubkey_seeds.as_slice()].as_slice(),
+                )?;
+                solana_program::program::invoke_signed(
+                    &spl_token::instruction::close_account(
+                        token_program_account_info.key,
+                        temporary_w_sol_token_account_account_info.key,
+                        authority_account_info.key,
+                        any_mint_token_account_authority_account_info.key,
+                        [].as_slice(),
+                    )?,
+                    vec![
+                        temporary_w_sol_token_account_account_info.clone(),
+                        authority_account_info.clone(),
+                        any_mint_token_account_authority_account_info.clone(),
+                    ]
+                    .as_slice(),
+                    [any_mint_token_account_authority_pubkey_seeds.as_slice()].as_slice(),
+                )?;
+                temporary_w_sol_token_account_amount
+            } else {
+                if minimum_output_token_amount != 0 {
+                    return Err(Error::TokenAccountInvalidAmount.into());
+                }
+                0
+            };
+            if Self::unpack_any_mint_token_account(any_mint_token_account_account_info)?.amount != 0 {
+                return Err(Error::TokenAccountInvalidAmount.into());
+            }
+            Self::harvest_any_mint_withheld_tokens(
+                any_mint_token_program_account_info,
+                any_mint_token_mint_account_info,
+                any_mint_token_account_account_info,
+            )?;
+            solana_program::program::invoke_signed(
+                &spl_token_2022::instruction::close_account(
+                    any_mint_token_program_account_info.key,
+                    any_mint_token_account_account_info.key,
+                    w_sol_token_account_account_info.key,
+                    any_mint_token_account_authority_account_info.key,
+                    [].as_slice(),
+                )?,
+                vec![
+                    any_mint_token_account_account_info.clone(),
+                    w_sol_token_account_account_info.clone(),
+                    any_mint_token_account_authority_account_info.clone(),
+                ]
+                .as_slice(),
+                [any_mint_token_account_authority_pubkey_seeds.as_slice()].as_slice(),
+            )?;
+            solana_program::program::invoke(
+                &spl_token::instruction::sync_native(
+                    token_program_account_info.key,
+                    w_sol_token_account_account_info.key,
+                )?,
+                vec![
+                    w_sol_token_account_account_info.clone(),
+                ]
+                .as_slice(),
+            )?;
+            Event::Traded {
+                intermediary_pubkey: *intermediary_account_info.key,
+                venue: *venue,
+                side: Side::Sell,
+                mint_pubkey: *any_mint_token_mint_account_info.key,
+                amount_in: any_mint_token_account.amount,
+                amount_out,
+                treasury_amount: Account::unpack_unchecked(&w_sol_token_account_account_info.data.borrow())?.amount,
+            }
+            .emit()?;
+        }
+        Self::record_open_positions(
+            intermediary_account_info,
+            0,
+            liquidation_leg_account_infos.len() as u32,
+        )?;
+        Ok(())
+    }
+    fn withdraw_token<'a>(_program_id: &'a Pubkey, accounts: &'a [AccountInfo], amount: u64) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let intermediary_investor_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let intermediary_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let any_mint_token_mint_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let any_mint_token_account_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let any_mint_token_account_authority_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let intermediary_investor_token_account_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let system_program_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let token_program_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let associated_token_account_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let mut account_differentiator = HashSet::<&'_ Pubkey>::with_capacity(9);
+        if !account_differentiator.insert(intermediary_investor_account_info.key)
+            || !account_differentiator.insert(intermediary_account_info.key)
+            || !account_differentiator.insert(any_mint_token_mint_account_info.key)
+            || !account_differentiator.insert(any_mint_token_account_account_info.key)
+            || !account_differentiator.insert(any_mint_token_account_authority_account_info.key)
+            || !account_differentiator.insert(intermediary_investor_token_account_account_info.key)
+            || !account_differentiator.insert(system_program_account_info.key)
+            || !account_differentiator.insert(token_program_account_info.key)
+            || !account_differentiator.insert(associated_token_account_account_info.key)
+            || *any_mint_token_mint_account_info.key == spl_token::native_mint::ID
+            || *system_program_account_info.key != solana_program::system_program::ID
+            || *associated_token_account_account_info.key != SPL_ASSOCIATED_TOKEN_ACCOUNT_ID
+        {
+            return Err(Error::InvalidAccountPubkey.into());
+        }
+        Self::enforce_any_mint_token_program(
+            any_mint_token_mint_account_info,
+            token_program_account_info,
+        )?;
+        let any_mint_token_account_address_resolver = AnyMintTokenAccountAddressResolver {
+            any_mint_token_mint_pubkey: any_mint_token_mint_account_info.key,
+            intermediary_pubkey: intermediary_account_info.key,
+        };
+        if *any_mint_token_account_account_info.key != any_mint_token_account_address_resolver.find().0 {
+            return Err(Error::InvalidAccountPubkey.into());
+        }
+        let any_mint_token_account_authority_address_resolver = AnyMintTokenAccountAuthorityAddressResolver {
+            any_mint_token_account_pubkey: any_mint_token_account_account_info.key,
+            intermediary_pubkey: intermediary_account_info.key,
+        };
+        let (any_mint_token_account_authority_pubkey, any_mint_token_account_authority_pubkey_bump_seed) = any_mint_token_account_authority_address_resolver.find();
+        if *any_mint_token_account_authority_account_info.key != any_mint_token_account_authority_pubkey {
+            return Err(Error::InvalidAccountPubkey.into());
+        }
+        if *intermediary_investor_token_account_account_info.key
+            != crate::extern_source::get_associated_token_address(
+                intermediary_investor_account_info.key,
+                any_mint_token_mint_account_info.key,
+                token_program_account_info.key,
+            )
+        {
+            return Err(Error::InvalidAccountPubkey.into());
+        }
+        if !intermediary_investor_account_info.is_writable
+            || !intermediary_investor_account_info.is_signer
+            || !any_mint_token_account_account_info.is_writable
+            || !intermediary_investor_token_account_account_info.is_writable
+        {
+            return Err(Error::InvalidAccountConfigurationFlags.into());
+        }
+        if *intermediary_account_info.owner != PROGRAM_PUBKEY || any_mint_token_account_account_info.owner != token_program_account_info.key {
+            return Err(Error::InvalidAccountOwner.into());
+        }
+        let intermediary = Intermediary::load(&intermediary_account_info.data.borrow())?;
+        if !intermediary.is_initialized {
+            return Err(Error::IntermediaryIsNotInitialized.into());
+        }
+        if *intermediary_investor_account_info.key != intermediary.investor_pubkey {
+            return Err(Error::IntermediaryInvalidInvestor.into());
+        }
+        let any_mint_token_account = Self::unpack_any_mint_token_account(any_mint_token_account_account_info)?;
+        if any_mint_token_account.mint != *any_mint_token_mint_account_info.key {
+            return Err(Error::TokenAccountInvalidMint.into());
+        }
+        if any_mint_token_account.owner != any_mint_token_account_authority_pubkey {
+            return Err(Error::TokenAccountInvalidAuthority.into());
+        }
+        if amount > any_mint_token_account.amount {
+            return Err(Error::TokenAccountInsufficientAmount.into());
+        }
+        solana_program::program::invoke(
+            &crate::extern_source::create_associated_token_account_idempotent_instruction(
+                intermediary_investor_account_info.key,
+                intermediary_investor_token_account_account_info.key,
+                intermediary_investor_account_info.key,
+                any_mint_token_mint_account_info.key,
+                token_program_account_info.key,
+            ),
+            vec![
+                intermediary_investor_account_info.clone(),
+                intermediary_investor_token_account_account_info.clone(),
+                any_mint_token_mint_account_info.clone(),
+                system_program_account_info.clone(),
+                token_program_account_info.clone(),
+                associated_token_account_account_info.clone(),
+            ]
+            .as_slice(),
+        )?;
+        let mut any_mint_token_account_authority_pubkey_seeds = any_mint_token_account_authority_address_resolver.get_seeds();
+        let any_mint_token_account_authority_pubkey_bump_seed_ = [any_mint_token_account_authority_pubkey_bump_seed];
+        any_mint_token_account_authority_pubkey_seeds.push(any_mint_token_account_authority_pubkey_bump_seed_.as_slice());
+        solana_program::program::invoke_signed(
+            &spl_token_2022::instruction::transfer_checked(
+                token_program_account_info.key,
+                any_mint_token_account_account_info.key,
+                any_mint_token_mint_account_info.key,
+                intermediary_investor_token_account_account_info.key,
+                any_mint_token_account_authority_account_info.key,
+                [].as_slice(),
+                amount,
+                Self::get_any_mint_token_mint_decimals(any_mint_token_mint_account_info)?,
+            )?,
+            vec![
+                any_mint_token_account_account_info.clone(),
+                any_mint_token_mint_account_info.clone(),
+                intermediary_investor_token_account_account_info.clone(),
+                any_mint_token_account_authority_account_info.clone(),
+            ]
+            .as_slice(),
+            [any_mint_token_account_authority_pubkey_seeds.as_slice()].as_slice(),
+        )?;
+        Event::TokenWithdrawn {
+            intermediary_pubkey: *intermediary_account_info.key,
+            mint_pubkey: *any_mint_token_mint_account_info.key,
+            investor_token_account_pubkey: *intermediary_investor_token_account_account_info.key,
+            amount,
+        }
+        .emit()?;
+        Ok(())
+    }
+    fn buy_on_raydium_cpmm<'a, 'b>(
+        _program_id: &'a Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+        input_token_amount: u64,
+        minimum_output_token_amount: u64,
+        any_mint_token_account_pubkey_bump_seed: u8,
+        any_mint_token_account_authority_pubkey_bump_seed: u8,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let intermediary_trader_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let intermediary_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let w_sol_token_account_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let w_sol_token_account_authority_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let temporary_w_sol_token_account_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let temporary_w_sol_token_account_authority_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let any_mint_token_account_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let any_mint_token_account_authority_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let w_sol_token_mint_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let any_mint_token_mint_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let system_program_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let rent_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let token_program_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let any_mint_token_program_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let cpmm_program_id_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let cpmm_authority_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let cpmm_amm_config_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let cpmm_pool_state_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let cpmm_w_sol_vault_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let cpmm_any_mint_vault_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let cpmm_observation_state_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let allowed_mints_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let risk_limits_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let trader_seat_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let config_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let fee_vault_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let raydium_cpmm_adapter = RaydiumCpmmAdapter::load(
+            [
+                cpmm_program_id_account_info,
+                cpmm_authority_account_info,
+                cpmm_amm_config_account_info,
+                cpmm_pool_state_account_info,
+                cpmm_w_sol_vault_account_info,
+                cpmm_any_mint_vault_account_info,
+                cpmm_observation_state_account_info,
+            ]
+            .as_slice(),
+        )?;
+        Self::buy_on_venue(
+            &VenueBuyAccounts {
+                intermediary_trader_account_info,
+                intermediary_account_info,
+                w_sol_token_account_account_info,
+                w_sol_token_account_authority_account_info,
+                temporary_w_sol_token_account_account_info,
+                temporary_w_sol_token_account_authority_account_info,
+                any_mint_token_account_account_info,
+                any_mint_token_account_authority_account_info,
+                w_sol_token_mint_account_info,
+                any_mint_token_mint_account_info,
+                system_program_account_info,
+                rent_account_info,
+                token_program_account_info,
+                any_mint_token_program_account_info,
+                allowed_mints_account_info,
+                risk_limits_account_info,
+                trader_seat_account_info,
+                config_account_info,
+                fee_vault_account_info,
+            },
+            &raydium_cpmm_adapter,
+            input_token_amount,
+            minimum_output_token_amount,
+            any_mint_token_account_pubkey_bump_seed,
+            any_mint_token_account_authority_pubkey_bump_seed,
+            false,
+        )
+    }
+    fn sell_on_raydium_cpmm<'a, 'b>(
+        _program_id: &'a Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+        input_token_amount: u64,
+        minimum_output_token_amount: Option<u64>,
+        any_mint_token_account_pubkey_bump_seed: u8,
+        any_mint_token_account_authority_pubkey_bump_seed: u8,
+        temporary_any_mint_token_account_pubkey_bump_seed: u8,
+        temporary_any_mint_token_account_authority_pubkey_bump_seed: u8,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let intermediary_trader_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let intermediary_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let w_sol_token_account_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let any_mint_token_account_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let any_mint_token_account_authority_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let temporary_any_mint_token_account_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let temporary_any_mint_token_account_authority_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let w_sol_token_mint_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let any_mint_token_mint_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let system_program_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let rent_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let token_program_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let any_mint_token_program_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let cpmm_program_id_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let cpmm_authority_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let cpmm_amm_config_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let cpmm_pool_state_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let cpmm_w_sol_vault_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let cpmm_any_mint_vault_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let cpmm_observation_state_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let risk_limits_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let trader_seat_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let config_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let fee_vault_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let raydium_cpmm_adapter = RaydiumCpmmAdapter::load(
+            [
+                cpmm_program_id_account_info,
+                cpmm_authority_account_info,
+                cpmm_amm_config_account_info,
+                cpmm_pool_state_account_info,
+                cpmm_w_sol_vault_account_info,
+                cpmm_any_mint_vault_account_info,
+                cpmm_observation_state_account_info,
+            ]
+            .as_slice(),
+        )?;
+        Self::sell_on_venue(
+            &VenueSellAccounts {
+                intermediary_trader_account_info,
+                intermediary_account_info,
+                w_sol_token_account_account_info,
+                temporary_w_sol_token_account_account_info: None,
+                any_mint_token_account_account_info,
+                any_mint_token_account_authority_account_info,
+                temporary_any_mint_token_account_account_info,
+                temporary_any_mint_token_account_authority_account_info,
+                w_sol_token_mint_account_info: Some(w_sol_token_mint_account_info),
+                any_mint_token_mint_account_info,
+                system_program_account_info,
+                rent_account_info,
+                token_program_account_info,
+                any_mint_token_program_account_info,
+                risk_limits_account_info,
+                trader_seat_account_info,
+                config_account_info,
+                fee_vault_account_info,
+            },
+            &raydium_cpmm_adapter,
+            input_token_amount,
+            minimum_output_token_amount,
+            any_mint_token_account_pubkey_bump_seed,
+            temporary_any_mint_token_account_pubkey_bump_seed,
+            any_mint_token_account_authority_pubkey_bump_seed,
+            temporary_any_mint_token_account_authority_pubkey_bump_seed,
+            false,
+        )
+    }
+    fn buy_on_raydium_clmm<'a, 'b>(
+        _program_id: &'a Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+        input_token_amount: u64,
+        minimum_output_token_amount: u64,
+        any_mint_token_account_pubkey_bump_seed: u8,
+        any_mint_token_account_authority_pubkey_bump_seed: u8,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let intermediary_trader_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let intermediary_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let w_sol_token_account_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let w_sol_token_account_authority_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let temporary_w_sol_token_account_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let temporary_w_sol_token_account_authority_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let any_mint_token_account_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let any_mint_token_account_authority_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let w_sol_token_mint_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let any_mint_token_mint_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let system_program_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let rent_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let token_program_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let token_2022_program_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let memo_program_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let clmm_program_id_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let clmm_amm_config_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let clmm_pool_state_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let clmm_w_sol_vault_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let clmm_any_mint_vault_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let clmm_observation_state_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let allowed_mints_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let risk_limits_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let trader_seat_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let config_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let fee_vault_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let clmm_tick_array_account_infos = account_info_iter.as_slice();
+        let any_mint_token_program_account_info = if *any_mint_token_mint_account_info.owner == spl_token_2022::ID {
+            token_2022_program_account_info
+        } else {
+            token_program_account_info
+        };
+        let mut venue_account_infos = vec![
+            clmm_program_id_account_info,
+            clmm_amm_config_account_info,
+            clmm_pool_state_account_info,
+            clmm_w_sol_vault_account_info,
+            clmm_any_mint_vault_account_info,
+            clmm_observation_state_account_info,
+            token_2022_program_account_info,
+            memo_program_account_info,
+        ];
+        venue_account_infos.extend(clmm_tick_array_account_infos.iter());
+        let raydium_clmm_adapter = RaydiumClmmAdapter::load(venue_account_infos.as_slice())?;
+        Self::buy_on_venue(
+            &VenueBuyAccounts {
+                intermediary_trader_account_info,
+                intermediary_account_info,
+                w_sol_token_account_account_info,
+                w_sol_token_account_authority_account_info,
+                temporary_w_sol_token_account_account_info,
+                temporary_w_sol_token_account_authority_account_info,
+                any_mint_token_account_account_info,
+                any_mint_token_account_authority_account_info,
+                w_sol_token_mint_account_info,
+                any_mint_token_mint_account_info,
+                system_program_account_info,
+                rent_account_info,
+                token_program_account_info,
+                any_mint_token_program_account_info,
+                allowed_mints_account_info,
+                risk_limits_account_info,
+                trader_seat_account_info,
+                config_account_info,
+                fee_vault_account_info,
+            },
+            &raydium_clmm_adapter,
+            input_token_amount,
+            minimum_output_token_amount,
+            any_mint_token_account_pubkey_bump_seed,
+            any_mint_token_account_authority_pubkey_bump_seed,
+            false,
+        )
+    }
+    fn sell_on_raydium_clmm<'a, 'b>(
+        _program_id: &'a Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+        input_token_amount: u64,
+        minimum_output_token_amount: Option<u64>,
+        any_mint_token_account_pubkey_bump_seed: u8,
+        any_mint_token_account_authority_pubkey_bump_seed: u8,
+        temporary_any_mint_token_account_pubkey_bump_seed: u8,
+        temporary_any_mint_token_account_authority_pubkey_bump_seed: u8,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let intermediary_trader_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let intermediary_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let w_sol_token_account_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let any_mint_token_account_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let any_mint_token_account_authority_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let temporary_any_mint_token_account_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let temporary_any_mint_token_account_authority_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let w_sol_token_mint_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let any_mint_token_mint_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let system_program_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let rent_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let token_program_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let token_2022_program_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let memo_program_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let clmm_program_id_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let clmm_amm_config_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let clmm_pool_state_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let clmm_w_sol_vault_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let clmm_any_mint_vault_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let clmm_observation_state_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let risk_limits_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let trader_seat_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let config_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let fee_vault_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let clmm_tick_array_account_infos = account_info_iter.as_slice();
+        let any_mint_token_program_account_info = if *any_mint_token_mint_account_info.owner == spl_token_2022::ID {
+            token_2022_program_account_info
+        } else {
+            token_program_account_info
+        };
+        let mut venue_account_infos = vec![
+            clmm_program_id_account_info,
+            clmm_amm_config_account_info,
+            clmm_pool_state_account_info,
+            clmm_w_sol_vault_account_info,
+            clmm_any_mint_vault_account_info,
+            clmm_observation_state_account_info,
+            token_2022_program_account_info,
+            memo_program_account_info,
+        ];
+        venue_account_infos.extend(clmm_tick_array_account_infos.iter());
+        let raydium_clmm_adapter = RaydiumClmmAdapter::load(venue_account_infos.as_slice())?;
+        Self::sell_on_venue(
+            &VenueSellAccounts {
+                intermediary_trader_account_info,
+                intermediary_account_info,
+                w_sol_token_account_account_info,
+                temporary_w_sol_token_account_account_info: None,
+                any_mint_token_account_account_info,
+                any_mint_token_account_authority_account_info,
+                temporary_any_mint_token_account_account_info,
+                temporary_any_mint_token_account_authority_account_info,
+                w_sol_token_mint_account_info: Some(w_sol_token_mint_account_info),
+                any_mint_token_mint_account_info,
+                system_program_account_info,
+                rent_account_info,
+                token_program_account_info,
+                any_mint_token_program_account_info,
+                risk_limits_account_info,
+                trader_seat_account_info,
+                config_account_info,
+                fee_vault_account_info,
+            },
+            &raydium_clmm_adapter,
+            input_token_amount,
+            minimum_output_token_amount,
+            any_mint_token_account_pubkey_bump_seed,
+            temporary_any_mint_token_account_pubkey_bump_seed,
+            any_mint_token_account_authority_pubkey_bump_seed,
+            temporary_any_mint_token_account_authority_pubkey_bump_seed,
+            false,
+        )
+    }
+    fn buy_on_meteora_dlmm<'a, 'b>(
+        _program_id: &'a Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+        input_token_amount: u64,
+        minimum_output_token_amount: u64,
+        any_mint_token_account_pubkey_bump_seed: u8,
+        any_mint_token_account_authority_pubkey_bump_seed: u8,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let intermediary_trader_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let intermediary_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let w_sol_token_account_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let w_sol_token_account_authority_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let temporary_w_sol_token_account_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let temporary_w_sol_token_account_authority_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let any_mint_token_account_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let any_mint_token_account_authority_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let w_sol_token_mint_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let any_mint_token_mint_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let system_program_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let rent_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let token_program_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let any_mint_token_program_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let dlmm_program_id_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let dlmm_lb_pair_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let dlmm_bin_array_bitmap_extension_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let dlmm_w_sol_reserve_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let dlmm_any_mint_reserve_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let dlmm_oracle_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let dlmm_event_authority_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let allowed_mints_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let risk_limits_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let trader_seat_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let config_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let fee_vault_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let dlmm_bin_array_account_infos = account_info_iter.as_slice();
+        let mut venue_account_infos = vec![
+            dlmm_program_id_account_info,
+            dlmm_lb_pair_account_info,
+            dlmm_bin_array_bitmap_extension_account_info,
+            dlmm_w_sol_reserve_account_info,
+            dlmm_any_mint_reserve_account_info,
+            dlmm_oracle_account_info,
+            dlmm_event_authority_account_info,
+        ];
+        venue_account_infos.extend(dlmm_bin_array_account_infos.iter());
+        let meteora_dlmm_adapter = MeteoraDlmmAdapter::load(venue_account_infos.as_slice())?;
+        Self::buy_on_venue(
+            &VenueBuyAccounts {
+                intermediary_trader_account_info,
+                intermediary_account_info,
+                w_sol_token_account_account_info,
+                w_sol_token_account_authority_account_info,
+                temporary_w_sol_token_account_account_info,
+                temporary_w_sol_token_account_authority_account_info,
+                any_mint_token_account_account_info,
+                any_mint_token_account_authority_account_info,
+                w_sol_token_mint_account_info,
+                any_mint_token_mint_account_info,
+                system_program_account_info,
+                rent_account_info,
+                token_program_account_info,
+                any_mint_token_program_account_info,
+                allowed_mints_account_info,
+                risk_limits_account_info,
+                trader_seat_account_info,
+                config_account_info,
+                fee_vault_account_info,
+            },
+            &meteora_dlmm_adapter,
+            input_token_amount,
+            minimum_output_token_amount,
+            any_mint_token_account_pubkey_bump_seed,
+            any_mint_token_account_authority_pubkey_bump_seed,
+            false,
+        )
+    }
+    fn sell_on_meteora_dlmm<'a, 'b>(
+        _program_id: &'a Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+        input_token_amount: u64,
+        minimum_output_token_amount: Option<u64>,
+        any_mint_token_account_pubkey_bump_seed: u8,
+        any_mint_token_account_authority_pubkey_bump_seed: u8,
+        temporary_any_mint_token_account_pubkey_bump_seed: u8,
+        temporary_any_mint_token_account_authority_pubkey_bump_seed: u8,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let intermediary_trader_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let intermediary_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let w_sol_token_account_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let any_mint_token_account_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let any_mint_token_account_authority_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let temporary_any_mint_token_account_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let temporary_any_mint_token_account_authority_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let w_sol_token_mint_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let any_mint_token_mint_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let system_program_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let rent_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let token_program_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let any_mint_token_program_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let dlmm_program_id_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let dlmm_lb_pair_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let dlmm_bin_array_bitmap_extension_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let dlmm_w_sol_reserve_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let dlmm_any_mint_reserve_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let dlmm_oracle_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let dlmm_event_authority_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let risk_limits_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let trader_seat_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let config_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let fee_vault_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let dlmm_bin_array_account_infos = account_info_iter.as_slice();
+        let mut venue_account_infos = vec![
+            dlmm_program_id_account_info,
+            dlmm_lb_pair_account_info,
+            dlmm_bin_array_bitmap_extension_account_info,
+            dlmm_w_sol_reserve_account_info,
+            dlmm_any_mint_reserve_account_info,
+            dlmm_oracle_account_info,
+            dlmm_event_authority_account_info,
+        ];
+        venue_account_infos.extend(dlmm_bin_array_account_infos.iter());
+        let meteora_dlmm_adapter = MeteoraDlmmAdapter::load(venue_account_infos.as_slice())?;
+        Self::sell_on_venue(
+            &VenueSellAccounts {
+                intermediary_trader_account_info,
+                intermediary_account_info,
+                w_sol_token_account_account_info,
+                temporary_w_sol_token_account_account_info: None,
+                any_mint_token_account_account_info,
+                any_mint_token_account_authority_account_info,
+                temporary_any_mint_token_account_account_info,
+                temporary_any_mint_token_account_authority_account_info,
+                w_sol_token_mint_account_info: Some(w_sol_token_mint_account_info),
+                any_mint_token_mint_account_info,
+                system_program_account_info,
+                rent_account_info,
+                token_program_account_info,
+                any_mint_token_program_account_info,
+                risk_limits_account_info,
+                trader_seat_account_info,
+                config_account_info,
+                fee_vault_account_info,
+            },
+            &meteora_dlmm_adapter,
+            input_token_amount,
+            minimum_output_token_amount,
+            any_mint_token_account_pubkey_bump_seed,
+            temporary_any_mint_token_account_pubkey_bump_seed,
+            any_mint_token_account_authority_pubkey_bump_seed,
+            temporary_any_mint_token_account_authority_pubkey_bump_seed,
+            false,
+        )
+    }
+    fn buy_on_meteora_dynamic_amm<'a, 'b>(
+        _program_id: &'a Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+        input_token_amount: u64,
+        minimum_output_token_amount: u64,
+        any_mint_token_account_pubkey_bump_seed: u8,
+        any_mint_token_account_authority_pubkey_bump_seed: u8,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let intermediary_trader_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let intermediary_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let w_sol_token_account_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let w_sol_token_account_authority_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let temporary_w_sol_token_account_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let temporary_w_sol_token_account_authority_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let any_mint_token_account_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let any_mint_token_account_authority_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let w_sol_token_mint_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let any_mint_token_mint_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let system_program_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let rent_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let token_program_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let dynamic_amm_program_id_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let vault_program_id_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let dynamic_amm_pool_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let dynamic_amm_w_sol_vault_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let dynamic_amm_any_mint_vault_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let dynamic_amm_w_sol_token_vault_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let dynamic_amm_any_mint_token_vault_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let dynamic_amm_w_sol_vault_lp_mint_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let dynamic_amm_any_mint_vault_lp_mint_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let dynamic_amm_w_sol_vault_lp_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let dynamic_amm_any_mint_vault_lp_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let dynamic_amm_protocol_token_fee_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let allowed_mints_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let risk_limits_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let trader_seat_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let config_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let fee_vault_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let meteora_dynamic_amm_adapter = MeteoraDynamicAmmAdapter::load(
+            [
+                dynamic_amm_program_id_account_info,
+                vault_program_id_account_info,
+                dynamic_amm_pool_account_info,
+                dynamic_amm_w_sol_vault_account_info,
+                dynamic_amm_any_mint_vault_account_info,
+                dynamic_amm_w_sol_token_vault_account_info,
+                dynamic_amm_any_mint_token_vault_account_info,
+                dynamic_amm_w_sol_vault_lp_mint_account_info,
+                dynamic_amm_any_mint_vault_lp_mint_account_info,
+                dynamic_amm_w_sol_vault_lp_account_info,
+                dynamic_amm_any_mint_vault_lp_account_info,
+                dynamic_amm_protocol_token_fee_account_info,
+            ]
+            .as_slice(),
+        )?;
+        Self::buy_on_venue(
+            &VenueBuyAccounts {
+                intermediary_trader_account_info,
+                intermediary_account_info,
+                w_sol_token_account_account_info,
+                w_sol_token_account_authority_account_info,
+                temporary_w_sol_token_account_account_info,
+                temporary_w_sol_token_account_authority_account_info,
+                any_mint_token_account_account_info,
+                any_mint_token_account_authority_account_info,
+                w_sol_token_mint_account_info,
+                any_mint_token_mint_account_info,
+                system_program_account_info,
+                rent_account_info,
+                token_program_account_info,
+                any_mint_token_program_account_info: token_program_account_info,
+                allowed_mints_account_info,
+                risk_limits_account_info,
+                trader_seat_account_info,
+                config_account_info,
+                fee_vault_account_info,
+            },
+            &meteora_dynamic_amm_adapter,
+            input_token_amount,
+            minimum_output_token_amount,
+            any_mint_token_account_pubkey_bump_seed,
+            any_mint_token_account_authority_pubkey_bump_seed,
+            false,
+        )
+    }
+    fn sell_on_meteora_dynamic_amm<'a, 'b>(
+        _program_id: &'a Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+        input_token_amount: u64,
+        minimum_output_token_amount: Option<u64>,
+        any_mint_token_account_pubkey_bump_seed: u8,
+        any_mint_token_account_authority_pubkey_bump_seed: u8,
+        temporary_any_mint_token_account_pubkey_bump_seed: u8,
+        temporary_any_mint_token_account_authority_pubkey_bump_seed: u8,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let intermediary_trader_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let intermediary_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let w_sol_token_account_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let any_mint_token_account_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let any_mint_token_account_authority_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let temporary_any_mint_token_account_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let temporary_any_mint_token_account_authority_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let w_sol_token_mint_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let any_mint_token_mint_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let system_program_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let rent_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let token_program_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let dynamic_amm_program_id_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let vault_program_id_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let dynamic_amm_pool_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let dynamic_amm_w_sol_vault_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let dynamic_amm_any_mint_vault_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let dynamic_amm_w_sol_token_vault_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let dynamic_amm_any_mint_token_vault_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let dynamic_amm_w_sol_vault_lp_mint_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let dynamic_amm_any_mint_vault_lp_mint_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let dynamic_amm_w_sol_vault_lp_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let dynamic_amm_any_mint_vault_lp_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let dynamic_amm_protocol_token_fee_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let risk_limits_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let trader_seat_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let config_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let fee_vault_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let meteora_dynamic_amm_adapter = MeteoraDynamicAmmAdapter::load(
+            [
+                dynamic_amm_program_id_account_info,
+                vault_program_id_account_info,
+                dynamic_amm_pool_account_info,
+                dynamic_amm_w_sol_vault_account_info,
+                dynamic_amm_any_mint_vault_account_info,
+                dynamic_amm_w_sol_token_vault_account_info,
+                dynamic_amm_any_mint_token_vault_account_info,
+                dynamic_amm_w_sol_vault_lp_mint_account_info,
+                dynamic_amm_any_mint_vault_lp_mint_account_info,
+                dynamic_amm_w_sol_vault_lp_account_info,
+                dynamic_amm_any_mint_vault_lp_account_info,
+                dynamic_amm_protocol_token_fee_account_info,
+            ]
+            .as_slice(),
+        )?;
+        Self::sell_on_venue(
+            &VenueSellAccounts {
+                intermediary_trader_account_info,
+                intermediary_account_info,
+                w_sol_token_account_account_info,
+                temporary_w_sol_token_account_account_info: None,
+                any_mint_token_account_account_info,
+                any_mint_token_account_authority_account_info,
+                temporary_any_mint_token_account_account_info,
+                temporary_any_mint_token_account_authority_account_info,
+                w_sol_token_mint_account_info: Some(w_sol_token_mint_account_info),
+                any_mint_token_mint_account_info,
+                system_program_account_info,
+                rent_account_info,
+                token_program_account_info,
+                any_mint_token_program_account_info: token_program_account_info,
+                risk_limits_account_info,
+                trader_seat_account_info,
+                config_account_info,
+                fee_vault_account_info,
+            },
+            &meteora_dynamic_amm_adapter,
+            input_token_amount,
+            minimum_output_token_amount,
+            any_mint_token_account_pubkey_bump_seed,
+            temporary_any_mint_token_account_pubkey_bump_seed,
+            any_mint_token_account_authority_pubkey_bump_seed,
+            temporary_any_mint_token_account_authority_pubkey_bump_seed,
+            false,
+        )
+    }
+    fn buy_on_pumpfun_curve<'a, 'b>(
+        _program_id: &'a Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+        token_amount_out: u64,
+        maximum_lamports_in: u64,
+        any_mint_token_account_pubkey_bump_seed: u8,
+        any_mint_token_account_authority_pubkey_bump_seed: u8,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let intermediary_trader_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let intermediary_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let w_sol_token_account_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let w_sol_token_account_authority_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let temporary_w_sol_token_account_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let temporary_w_sol_token_account_authority_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let any_mint_token_account_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let any_mint_token_account_authority_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let w_sol_token_mint_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let any_mint_token_mint_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let system_program_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let rent_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let token_program_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let pumpfun_program_id_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let global_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let fee_recipient_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let bonding_curve_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let associated_bonding_curve_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let event_authority_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let allowed_mints_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let risk_limits_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let trader_seat_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let config_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let fee_vault_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let pumpfun_curve_adapter = PumpfunCurveAdapter::load(
+            [
+                pumpfun_program_id_account_info,
+                global_account_info,
+                fee_recipient_account_info,
+                bonding_curve_account_info,
+                associated_bonding_curve_account_info,
+                event_authority_account_info,
+                rent_account_info,
+            ]
+            .as_slice(),
+        )?;
+        Self::buy_on_venue(
+            &VenueBuyAccounts {
+                intermediary_trader_account_info,
+                intermediary_account_info,
+                w_sol_token_account_account_info,
+                w_sol_token_account_authority_account_info,
+                temporary_w_sol_token_account_account_info,
+                temporary_w_sol_token_account_authority_account_info,
+                any_mint_token_account_account_info,
+                any_mint_token_account_authority_account_info,
+                w_sol_token_mint_account_info,
+                any_mint_token_mint_account_info,
+                system_program_account_info,
+                rent_account_info,
+                token_program_account_info,
+                any_mint_token_program_account_info: token_program_account_info,
+                allowed_mints_account_info,
+                risk_limits_account_info,
+                trader_seat_account_info,
+                config_account_info,
+                fee_vault_account_info,
+            },
+            &pumpfun_curve_adapter,
+            maximum_lamports_in,
+            token_amount_out,
+            any_mint_token_account_pubkey_bump_seed,
+            any_mint_token_account_authority_pubkey_bump_seed,
+            false,
+        )
+    }
+    fn sell_on_pumpfun_curve<'a, 'b>(
+        _program_id: &'a Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+        input_token_amount: u64,
+        minimum_output_token_amount: Option<u64>,
+        any_mint_token_account_pubkey_bump_seed: u8,
+        any_mint_token_account_authority_pubkey_bump_seed: u8,
+        temporary_any_mint_token_account_pubkey_bump_seed: u8,
+        temporary_any_mint_token_account_authority_pubkey_bump_seed: u8,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let intermediary_trader_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let intermediary_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let w_sol_token_account_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let any_mint_token_account_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let any_mint_token_account_authority_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let temporary_any_mint_token_account_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let temporary_any_mint_token_account_authority_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let w_sol_token_mint_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let any_mint_token_mint_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let system_program_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let rent_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let token_program_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let associated_token_account_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let pumpfun_program_id_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let global_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let fee_recipient_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let bonding_curve_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let associated_bonding_curve_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let event_authority_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let risk_limits_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let trader_seat_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let config_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let fee_vault_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let pumpfun_curve_adapter = PumpfunCurveAdapter::load(
+            [
+                pumpfun_program_id_account_info,
+                global_account_info,
+                fee_recipient_account_info,
+                bonding_curve_account_info,
+                associated_bonding_curve_account_info,
+                event_authority_account_info,
+                associated_token_account_account_info,
+            ]
+            .as_slice(),
+        )?;
+        Self::sell_on_venue(
+            &VenueSellAccounts {
+                intermediary_trader_account_info,
+                intermediary_account_info,
+                w_sol_token_account_account_info,
+                temporary_w_sol_token_account_account_info: None,
+                any_mint_token_account_account_info,
+                any_mint_token_account_authority_account_info,
+                temporary_any_mint_token_account_account_info,
+                temporary_any_mint_token_account_authority_account_info,
+                w_sol_token_mint_account_info: Some(w_sol_token_mint_account_info),
+                any_mint_token_mint_account_info,
+                system_program_account_info,
+                rent_account_info,
+                token_program_account_info,
+                any_mint_token_program_account_info: token_program_account_info,
+                risk_limits_account_info,
+                trader_seat_account_info,
+                config_account_info,
+                fee_vault_account_info,
+            },
+            &pumpfun_curve_adapter,
+            input_token_amount,
+            minimum_output_token_amount,
+            any_mint_token_account_pubkey_bump_seed,
+            temporary_any_mint_token_account_pubkey_bump_seed,
+            any_mint_token_account_authority_pubkey_bump_seed,
+            temporary_any_mint_token_account_authority_pubkey_bump_seed,
+            false,
+        )
+    }
+    fn swap<'a, 'b>(
+        _program_id: &'a Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+        venue: Venue,
+        side: Side,
+        amount_in: u64,
+        min_out: u64,
+        any_mint_token_account_pubkey_bump_seed: u8,
+        any_mint_token_account_authority_pubkey_bump_seed: u8,
+        temporary_any_mint_token_account_pubkey_bump_seed: u8,
+        temporary_any_mint_token_account_authority_pubkey_bump_seed: u8,
+    ) -> ProgramResult {
+        const INTERMEDIARY_ACCOUNTS_QUANTITY: usize = 21;
+        if accounts.len() < INTERMEDIARY_ACCOUNTS_QUANTITY {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        }
+        let venue_account_infos = accounts[INTERMEDIARY_ACCOUNTS_QUANTITY..].iter().collect::<Vec<&'a AccountInfo<'b>>>();
+        match venue {
+            Venue::Raydium => {
+                Self::swap_on_venue(
+                    accounts,
+                    &RaydiumAdapter::load(venue_account_infos.as_slice())?,
+                    side,
+                    amount_in,
+                    min_out,
+                    any_mint_token_account_pubkey_bump_seed,
+                    any_mint_token_account_authority_pubkey_bump_seed,
+                    temporary_any_mint_token_account_pubkey_bump_seed,
+                    temporary_any_mint_token_account_authority_pubkey_bump_seed,
+                )
+            }
+            Venue::Pumpswap => {
+                Self::swap_on_venue(
+                    accounts,
+                    &PumpswapAdapter::load(venue_account_infos.as_slice())?,
+                    side,
+                    amount_in,
+                    min_out,
+                    any_mint_token_account_pubkey_bump_seed,
+                    any_mint_token_account_authority_pubkey_bump_seed,
+                    temporary_any_mint_token_account_pubkey_bump_seed,
+                    temporary_any_mint_token_account_authority_pubkey_bump_seed,
+                )
+            }
+            Venue::RaydiumCpmm => {
+                Self::swap_on_venue(
+                    accounts,
+                    &RaydiumCpmmAdapter::load(venue_account_infos.as_slice())?,
+                    side,
+                    amount_in,
+                    min_out,
+                    any_mint_token_account_pubkey_bump_seed,
+                    any_mint_token_account_authority_pubkey_bump_seed,
+                    temporary_any_mint_token_account_pubkey_bump_seed,
+                    temporary_any_mint_token_account_authority_pubkey_bump_seed,
+                )
+            }
+            Venue::RaydiumClmm => {
+                Self::swap_on_venue(
+                    accounts,
+                    &RaydiumClmmAdapter::load(venue_account_infos.as_slice())?,
+                    side,
+                    amount_in,
+                    min_out,
+                    any_mint_token_account_pubkey_bump_seed,
+                    any_mint_token_account_authority_pubkey_bump_seed,
+                    temporary_any_mint_token_account_pubkey_bump_seed,
+                    temporary_any_mint_token_account_authority_pubkey_bump_seed,
+                )
+            }
+            Venue::MeteoraDlmm => {
+                Self::swap_on_venue(
+                    accounts,
+                    &MeteoraDlmmAdapter::load(venue_account_infos.as_slice())?,
+                    side,
+                    amount_in,
+                    min_out,
+                    any_mint_token_account_pubkey_bump_seed,
+                    any_mint_token_account_authority_pubkey_bump_seed,
+                    temporary_any_mint_token_account_pubkey_bump_seed,
+                    temporary_any_mint_token_account_authority_pubkey_bump_seed,
+                )
+            }
+            Venue::MeteoraDynamicAmm => {
+                Self::swap_on_venue(
+                    accounts,
+                    &MeteoraDynamicAmmAdapter::load(venue_account_infos.as_slice())?,
+                    side,
+                    amount_in,
+                    min_out,
+                    any_mint_token_account_pubkey_bump_seed,
+                    any_mint_token_account_authority_pubkey_bump_seed,
+                    temporary_any_mint_token_account_pubkey_bump_seed,
+                    temporary_any_mint_token_account_authority_pubkey_bump_seed,
+                )
+            }
+            Venue::PumpfunCurve => {
+                Self::swap_on_venue(
+                    accounts,
+                    &PumpfunCurveAdapter::load(venue_account_infos.as_slice())?,
+                    side,
+                    amount_in,
+                    min_out,
+                    any_mint_token_account_pubkey_bump_seed,
+                    any_mint_token_account_authority_pubkey_bump_seed,
+                    temporary_any_mint_token_account_pubkey_bump_seed,
+                    temporary_any_mint_token_account_authority_pubkey_bump_seed,
+                )
+            }
+        }
+    }
+    // Chains two or three venue legs through program derived intermediate token accounts, only the last leg is held to `min_out`.
+    // PumpSwap buys an exact amount and would leave the rest of an intermediate token behind, so it can not take a leg of a buy route.
+    fn routed_swap<'a, 'b>(
+        _program_id: &'a Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+        side: Side,
+        venues: Vec<Venue>,
+        amount_in: u64,
+        min_out: u64,
+        any_mint_token_account_pubkey_bump_seed: u8,
+        any_mint_token_account_authority_pubkey_bump_seed: u8,
+        intermediate_token_account_pubkey_bump_seeds: Vec<u8>,
+    ) -> ProgramResult {
+        const MINIMUM_LEGS_QUANTITY: usize = 2;
+        const MAXIMUM_LEGS_QUANTITY: usize = 3;
+        if venues.len() < MINIMUM_LEGS_QUANTITY || venues.len() > MAXIMUM_LEGS_QUANTITY || intermediate_token_account_pubkey_bump_seeds.len() != venues.len() - 1 {
+            return Err(Error::RouteInvalidLegsQuantity.into());
+        }
+        if side == Side::Buy && venues.contains(&Venue::Pumpswap) {
+            return Err(Error::VenueIsNotSupported.into());
         }
+        let account_info_iter = &mut accounts.iter();
+        let intermediary_trader_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let intermediary_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let w_sol_token_account_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let w_sol_token_account_authority_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let temporary_w_sol_token_account_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let any_mint_token_account_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let any_mint_token_account_authority_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let w_sol_token_mint_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let any_mint_token_mint_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let system_program_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let rent_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let token_program_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let any_mint_token_program_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let allowed_mints_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let risk_limits_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let trader_seat_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let config_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let fee_vault_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let mut intermediate_token_mint_account_infos = Vec::<&'a AccountInfo<'b>>::with_capacity(intermediate_token_account_pubkey_bump_seeds.len());
+        let mut intermediate_token_account_account_infos = Vec::<&'a AccountInfo<'b>>::with_capacity(intermediate_token_account_pubkey_bump_seeds.len());
+        for _ in intermediate_token_account_pubkey_bump_seeds.iter() {
+            intermediate_token_mint_account_infos.push(solana_program::account_info::next_account_info(account_info_iter)?);
+            intermediate_token_account_account_infos.push(solana_program::account_info::next_account_info(account_info_iter)?);
+        }
+        let mut any_venue_adapters = Vec::<AnyVenueAdapter<'a, 'b>>::with_capacity(venues.len());
+        for venue in venues.iter() {
+            let venue_account_infos = account_info_iter.take(crate::venue::get_accounts_writability(*venue)?.len()).collect::<Vec<&'a AccountInfo<'b>>>();
+            any_venue_adapters.push(
+                AnyVenueAdapter::load(
+                    *venue,
+                    venue_account_infos.as_slice(),
+                )?,
+            );
+        }
+        let any_mint_token_account_address_resolver = AnyMintTokenAccountAddressResolver {
+            any_mint_token_mint_pubkey: any_mint_token_mint_account_info.key,
+            intermediary_pubkey: intermediary_account_info.key,
+        };
+        let any_mint_token_account_authority_address_resolver = AnyMintTokenAccountAuthorityAddressResolver {
+            any_mint_token_account_pubkey: any_mint_token_account_account_info.key,
+            intermediary_pubkey: intermediary_account_info.key,
+        };
+        if *any_mint_token_account_account_info.key != any_mint_token_account_address_resolver.create(any_mint_token_account_pubkey_bump_seed)?
+            || *any_mint_token_account_authority_account_info.key != any_mint_token_account_authority_address_resolver.create(any_mint_token_account_authority_pubkey_bump_seed)?
+            || w_sol_token_mint_account_info.key == any_mint_token_mint_account_info.key
+            || *w_sol_token_mint_account_info.key != spl_token::native_mint::ID
+            || *system_program_account_info.key != solana_program::system_program::ID
+            || *rent_account_info.key != solana_program::sysvar::rent::ID
+            || *token_program_account_info.key != spl_token::ID
+        {
+            return Err(Error::InvalidAccountPubkey.into());
+        }
+        for (index, (intermediate_token_mint_account_info, intermediate_token_account_account_info)) in
+            intermediate_token_mint_account_infos.iter().zip(intermediate_token_account_account_infos.iter()).enumerate()
+        {
+            let intermediate_token_account_address_resolver = IntermediateTokenAccountAddressResolver {
+                intermediate_token_mint_pubkey: intermediate_token_mint_account_info.key,
+                any_mint_token_account_pubkey: any_mint_token_account_account_info.key,
+                intermediary_pubkey: intermediary_account_info.key,
+            };
+            if *intermediate_token_account_account_info.key != intermediate_token_account_address_resolver.create(intermediate_token_account_pubkey_bump_seeds[index])?
+                || intermediate_token_mint_account_info.key == w_sol_token_mint_account_info.key
+                || intermediate_token_mint_account_info.key == any_mint_token_mint_account_info.key
+                || intermediate_token_mint_account_infos[..index]
+                    .iter()
+                    .any(|intermediate_token_mint_account_info_| intermediate_token_mint_account_info_.key == intermediate_token_mint_account_info.key)
+            {
+                return Err(Error::InvalidAccountPubkey.into());
+            }
+            if *intermediate_token_mint_account_info.owner != spl_token::ID {
+                return Err(Error::InvalidAccountOwner.into());
+            }
+        }
+        Self::enforce_any_mint_token_program(
+            any_mint_token_mint_account_info,
+            any_mint_token_program_account_info,
+        )?;
         if !intermediary_trader_account_info.is_signer
             || !intermediary_trader_account_info.is_writable
+            || !intermediary_account_info.is_writable
             || !w_sol_token_account_account_info.is_writable
+            || !temporary_w_sol_token_account_account_info.is_writable
             || !any_mint_token_account_account_info.is_writable
-            || !temporary_any_mint_token_account_account_info.is_writable
-            || !amm_pool_account_info.is_writable
-            || !amm_open_orders_account_info.is_writable
-            || !amm_coin_vault_account_info.is_writable
-            || !amm_pc_vault_account_info.is_writable
-            || !market_account_info.is_writable
-            || !market_bids_account_info.is_writable
-            || !market_asks_account_info.is_writable
-            || !market_event_queue_account_info.is_writable
-            || !market_coin_vault_account_info.is_writable
-            || !market_pc_vault_account_info.is_writable
+            || (side == Side::Sell && *any_mint_token_program_account_info.key == spl_token_2022::ID && !any_mint_token_mint_account_info.is_writable)
+            || intermediate_token_account_account_infos.iter().any(|intermediate_token_account_account_info| !intermediate_token_account_account_info.is_writable)
+            || !risk_limits_account_info.is_writable
+            || !trader_seat_account_info.is_writable
+            || !fee_vault_account_info.is_writable
         {
             return Err(Error::InvalidAccountConfigurationFlags.into());
         }
-        if any_mint_token_account_account_info.data_len() != <Account as Pack>::LEN {
-            return Err(Error::InvalidAccountData.into());
-        }
         let rent = Rent::from_account_info(rent_account_info)?;
         let token_account_rent_exemption_balance = rent.minimum_balance(<Account as Pack>::LEN);
-        if intermediary_trader_account_info.lamports() < (token_account_rent_exemption_balance + FEE_SELL_ON_RAYDIUM) {
+        if *config_account_info.owner != PROGRAM_PUBKEY {
+            return Err(Error::InvalidAccountOwner.into());
+        }
+        let config = borsh::from_slice::<Config>(&config_account_info.data.borrow())?;
+        if !config.is_initialized {
+            return Err(Error::ConfigIsNotInitialized.into());
+        }
+        if *config_account_info.key != ConfigAddressResolver.create(config.config_pubkey_bump_seed)? {
+            return Err(Error::InvalidAccountPubkey.into());
+        }
+        if *fee_vault_account_info.key != config.fee_vault_pubkey {
+            return Err(Error::ConfigInvalidFeeVault.into());
+        }
+        let fee = any_venue_adapters
+            .iter()
+            .map(
+                |any_venue_adapter| {
+                    any_venue_adapter.get_fee(
+                        &config.fees,
+                        side,
+                    )
+                },
+            )
+            .sum::<u64>();
+        let is_any_mint_token_account_initialized = !any_mint_token_account_account_info.data_is_empty();
+        let any_mint_token_account_length = Self::get_any_mint_token_account_length(any_mint_token_mint_account_info)?;
+        let any_mint_token_account_rent_exemption_balance = if is_any_mint_token_account_initialized {
+            0
+        } else {
+            rent.minimum_balance(any_mint_token_account_length)
+        };
+        let token_accounts_to_create_quantity = 1 + intermediate_token_account_account_infos
+            .iter()
+            .filter(|intermediate_token_account_account_info| intermediate_token_account_account_info.data_is_empty())
+            .count() as u64;
+        if intermediary_trader_account_info.lamports() < (token_accounts_to_create_quantity * token_account_rent_exemption_balance + any_mint_token_account_rent_exemption_balance + fee) {
             return Err(Error::InvalidAccountLamports.into());
         }
-        let intermediary = borsh::from_slice::<Intermediary>(&intermediary_account_info.data.borrow())?;
+        solana_program::program::invoke(
+            &solana_program::system_instruction::transfer(
+                intermediary_trader_account_info.key,
+                fee_vault_account_info.key,
+                fee,
+            ),
+            vec![
+                intermediary_trader_account_info.clone(),
+                fee_vault_account_info.clone(),
+            ]
+            .as_slice(),
+        )?;
+        Event::FeeCharged {
+            intermediary_pubkey: *intermediary_account_info.key,
+            payer_pubkey: *intermediary_trader_account_info.key,
+            fee_vault_pubkey: *fee_vault_account_info.key,
+            lamports: fee,
+        }
+        .emit()?;
+        let intermediary = Intermediary::load(&intermediary_account_info.data.borrow())?;
         if !intermediary.is_initialized {
             return Err(Error::IntermediaryIsNotInitialized.into());
         }
-        if *intermediary_trader_account_info.key != intermediary.trader_pubkey {
-            return Err(Error::IntermediaryInvalidInvestor.into());
+        if intermediary.is_paused {
+            return Err(Error::IntermediaryPaused.into());
         }
+        let lamports_from_treasury = match side {
+            Side::Buy => amount_in,
+            Side::Sell => 0,
+        };
+        Self::enforce_trader_seat(
+            intermediary_account_info,
+            trader_seat_account_info,
+            intermediary_trader_account_info.key,
+            lamports_from_treasury,
+        )?;
         if *w_sol_token_account_account_info.key != intermediary.w_sol_token_account_pubkey {
             return Err(Error::IntermediaryInvalidWSolTokenAccount.into());
         }
-        let any_mint_token_account = Account::unpack_unchecked(&any_mint_token_account_account_info.data.borrow())?;
-        if input_token_amount > any_mint_token_account.amount {
-            return Err(Error::TokenAccountInsufficientAmount.into());
+        if *w_sol_token_account_authority_account_info.key != intermediary.w_sol_token_account_authority_pubkey {
+            return Err(Error::IntermediaryInvalidWSolTokenAccountAuthority.into());
         }
-        let temporary_any_mint_token_account_authority_pubkey_bump_seed_ = [temporary_any_mint_token_account_authority_pubkey_bump_seed];
-        let mut any_mint_token_account_authority_pubkey_seeds = any_mint_token_account_authority_address_resolver.get_seeds();
-        let any_mint_token_account_authority_pubkey_bump_seed_ = [any_mint_token_account_authority_pubkey_bump_seed];
-        any_mint_token_account_authority_pubkey_seeds.push(any_mint_token_account_authority_pubkey_bump_seed_.as_slice());
-        let (choosed_any_mint_token_account_account_info, choosed_any_mint_token_account_authority_account_info, choosed_any_mint_token_account_authority_pubkey_seeds) =
-            if input_token_amount == any_mint_token_account.amount {
-                (
-                    any_mint_token_account_account_info,
-                    any_mint_token_account_authority_account_info,
-                    any_mint_token_account_authority_pubkey_seeds,
-                )
-            } else {
-                let mut temporary_any_mint_token_account_pubkey_seeds = temporary_any_mint_token_account_address_resolver.get_seeds();
-                let temporary_any_mint_token_account_pubkey_bump_seed_ = [temporary_any_mint_token_account_pubkey_bump_seed];
-                temporary_any_mint_token_account_pubkey_seeds.push(temporary_any_mint_token_account_pubkey_bump_seed_.as_slice());
+        if *temporary_w_sol_token_account_account_info.key != intermediary.temporary_w_sol_token_account_pubkey {
+            return Err(Error::IntermediaryInvalidTemporaryWSolTokenAccount.into());
+        }
+        let any_mint_token_account_amount_before_swap = if is_any_mint_token_account_initialized {
+            if any_mint_token_account_account_info.owner != any_mint_token_program_account_info.key {
+                return Err(Error::InvalidAccountOwner.into());
+            }
+            let any_mint_token_account = Self::unpack_any_mint_token_account(any_mint_token_account_account_info)?;
+            if any_mint_token_account.mint != *any_mint_token_mint_account_info.key {
+                return Err(Error::TokenAccountInvalidMint.into());
+            }
+            if any_mint_token_account.owner != *any_mint_token_account_authority_account_info.key {
+                return Err(Error::TokenAccountInvalidAuthority.into());
+            }
+            any_mint_token_account.amount
+        } else {
+            0
+        };
+        for (intermediate_token_mint_account_info, intermediate_token_account_account_info) in intermediate_token_mint_account_infos.iter().zip(intermediate_token_account_account_infos.iter()) {
+            if intermediate_token_account_account_info.data_is_empty() {
+                continue;
+            }
+            if *intermediate_token_account_account_info.owner != spl_token::ID {
+                return Err(Error::InvalidAccountOwner.into());
+            }
+            let intermediate_token_account = Account::unpack(&intermediate_token_account_account_info.data.borrow())?;
+            if intermediate_token_account.mint != *intermediate_token_mint_account_info.key {
+                return Err(Error::TokenAccountInvalidMint.into());
+            }
+            if intermediate_token_account.owner != *any_mint_token_account_authority_account_info.key {
+                return Err(Error::TokenAccountInvalidAuthority.into());
+            }
+        }
+        let w_sol_token_account = Account::unpack_unchecked(&w_sol_token_account_account_info.data.borrow())?;
+        match side {
+            Side::Buy => {
+                if amount_in > w_sol_token_account.amount {
+                    return Err(Error::TokenAccountInsufficientAmount.into());
+                }
+                Self::enforce_allowed_mint(
+                    intermediary_account_info,
+                    allowed_mints_account_info,
+                    any_mint_token_mint_account_info.key,
+                )?;
+                for intermediate_token_mint_account_info in intermediate_token_mint_account_infos.iter() {
+                    Self::enforce_allowed_mint(
+                        intermediary_account_info,
+                        allowed_mints_account_info,
+                        intermediate_token_mint_account_info.key,
+                    )?;
+                }
+            }
+            Side::Sell => {
+                if amount_in > any_mint_token_account_amount_before_swap {
+                    return Err(Error::TokenAccountInsufficientAmount.into());
+                }
+            }
+        }
+        Self::enforce_risk_limits(
+            intermediary_account_info,
+            risk_limits_account_info,
+            lamports_from_treasury,
+            match side {
+                Side::Buy => w_sol_token_account.amount,
+                Side::Sell => 0,
+            },
+        )?;
+        let temporary_w_sol_token_account_address_resolver = TemporaryWSolTokenAccountAddressResolver {
+            w_sol_token_account_pubkey: w_sol_token_account_account_info.key,
+        };
+        let mut temporary_w_sol_token_account_pubkey_seeds = temporary_w_sol_token_account_address_resolver.get_seeds();
+        let temporary_w_sol_token_account_pubkey_bump_seed_ = [intermediary.temporary_w_sol_token_account_pubkey_bump_seed];
+        temporary_w_sol_token_account_pubkey_seeds.push(temporary_w_sol_token_account_pubkey_bump_seed_.as_slice());
+        solana_program::program::invoke_signed(
+            &solana_program::system_instruction::create_account(
+                intermediary_trader_account_info.key,
+                temporary_w_sol_token_account_account_info.key,
+                token_account_rent_exemption_balance,
+                <Account as Pack>::LEN as u64,
+                token_program_account_info.key,
+            ),
+            vec![
+                intermediary_trader_account_info.clone(),
+                temporary_w_sol_token_account_account_info.clone(),
+            ]
+            .as_slice(),
+            [temporary_w_sol_token_account_pubkey_seeds.as_slice()].as_slice(),
+        )?;
+        solana_program::program::invoke(
+            &spl_token::instruction::initialize_account(
+                token_program_account_info.key,
+                temporary_w_sol_token_account_account_info.key,
+                w_sol_token_mint_account_info.key,
+                any_mint_token_account_authority_account_info.key,
+            )?,
+            vec![
+                temporary_w_sol_token_account_account_info.clone(),
+                w_sol_token_mint_account_info.clone(),
+                any_mint_token_account_authority_account_info.clone(),
+                rent_account_info.clone(),
+            ]
+            .as_slice(),
+        )?;
+        if side == Side::Buy {
+            let w_sol_token_account_authority_address_resolver = WSolTokenAccountAuthorityAddressResolver {
+                w_sol_token_account_pubkey: w_sol_token_account_account_info.key,
+                intermediary_pubkey: intermediary_account_info.key,
+            };
+            let mut w_sol_token_account_authority_pubkey_seeds = w_sol_token_account_authority_address_resolver.get_seeds();
+            let w_sol_token_account_authority_pubkey_bump_seed_ = [intermediary.w_sol_token_account_authority_pubkey_bump_seed];
+            w_sol_token_account_authority_pubkey_seeds.push(w_sol_token_account_authority_pubkey_bump_seed_.as_slice());
+            solana_program::program::invoke_signed(
+                &spl_token::instruction::transfer(
+                    token_program_account_info.key,
+                    w_sol_token_account_account_info.key,
+                    temporary_w_sol_token_account_account_info.key,
+                    w_sol_token_account_authority_account_info.key,
+                    [].as_slice(),
+                    amount_in,
+                )?,
+                vec![
+                    w_sol_token_account_account_info.clone(),
+                    temporary_w_sol_token_account_account_info.clone(),
+                    w_sol_token_account_authority_account_info.clone(),
+                ]
+                .as_slice(),
+                [w_sol_token_account_authority_pubkey_seeds.as_slice()].as_slice(),
+            )?;
+            if !is_any_mint_token_account_initialized {
+                let mut any_mint_token_account_pubkey_seeds = any_mint_token_account_address_resolver.get_seeds();
+                let any_mint_token_account_pubkey_bump_seed_ = [any_mint_token_account_pubkey_bump_seed];
+                any_mint_token_account_pubkey_seeds.push(any_mint_token_account_pubkey_bump_seed_.as_slice());
                 solana_program::program::invoke_signed(
                     &solana_program::system_instruction::create_account(
                         intermediary_trader_account_info.key,
-                        temporary_any_mint_token_account_account_info.key,
-                        token_account_rent_exemption_balance,
-                        <Account as Pack>::LEN as u64,
-                        token_program_account_info.key,
+                        any_mint_token_account_account_info.key,
+                        any_mint_token_account_rent_exemption_balance,
+                        any_mint_token_account_length as u64,
+                        any_mint_token_program_account_info.key,
                     ),
                     vec![
                         intermediary_trader_account_info.clone(),
-                        temporary_any_mint_token_account_account_info.clone(),
+                        any_mint_token_account_account_info.clone(),
                     ]
                     .as_slice(),
-                    [temporary_any_mint_token_account_pubkey_seeds.as_slice()].as_slice(),
+                    [any_mint_token_account_pubkey_seeds.as_slice()].as_slice(),
                 )?;
                 solana_program::program::invoke(
-                    &spl_token::instruction::initialize_account(
-                        token_program_account_info.key,
-                        temporary_any_mint_token_account_account_info.key,
-                        any_mint_token_mint_account_info.key,
-                        temporary_any_mint_token_account_authority_account_info.key,
-                    )?,
-                    vec![
-                    temporary_any_mint_token_account_account_info.clone(),
-                    any_mint_token_mint_account_info.clone(),
-                    temporary_any_mint_token_account_authority_account_info.clone(),
-                    rent_account_info.clone(),
-                ]
-                    .as_slice(),
-                )?;
-                solana_program::program::invoke_signed(
-                    &spl_token::instruction::transfer(
-                        token_program_account_info.key,
+                    &spl_token_2022::instruction::initialize_account(
+                        any_mint_token_program_account_info.key,
                         any_mint_token_account_account_info.key,
-                        temporary_any_mint_token_account_account_info.key,
+                        any_mint_token_mint_account_info.key,
                         any_mint_token_account_authority_account_info.key,
-                        [].as_slice(),
-                        input_token_amount,
                     )?,
                     vec![
                         any_mint_token_account_account_info.clone(),
-                        temporary_any_mint_token_account_account_info.clone(),
+                        any_mint_token_mint_account_info.clone(),
                         any_mint_token_account_authority_account_info.clone(),
+                        rent_account_info.clone(),
                     ]
                     .as_slice(),
-                    [any_mint_token_account_authority_pubkey_seeds.as_slice()].as_slice(),
                 )?;
-                let mut temporary_any_mint_token_account_authority_pubkey_seeds = temporary_any_mint_token_account_authority_address_resolver.get_seeds();
-                temporary_any_mint_token_account_authority_pubkey_seeds.push(temporary_any_mint_token_account_authority_pubkey_bump_seed_.as_slice());
-                (
-                    temporary_any_mint_token_account_account_info,
-                    temporary_any_mint_token_account_authority_account_info,
-                    temporary_any_mint_token_account_authority_pubkey_seeds,
-                )
-            };
-        let (minimum_output_token_amount_, current_w_sol_token_account_amount) = match minimum_output_token_amount {
-            Some(minimum_output_token_amount__) => {
-                (
-                    minimum_output_token_amount__,
-                    Some(Account::unpack_unchecked(&w_sol_token_account_account_info.data.borrow())?.amount),
-                )
-            }
-            None => {
-                (
+                Self::record_open_positions(
+                    intermediary_account_info,
+                    1,
                     0,
-                    None,
-                )
+                )?;
             }
-        };
-        solana_program::program::invoke_signed(
-            &crate::extern_source::create_raydium_swap_base_in_instruction(
-                amm_program_id.key,
-                amm_pool_account_info.key,
-                amm_authority_account_info.key,
-                amm_open_orders_account_info.key,
-                amm_coin_vault_account_info.key,
-                amm_pc_vault_account_info.key,
-                market_program_id_account_info.key,
-                market_account_info.key,
-                market_bids_account_info.key,
-                market_asks_account_info.key,
-                market_event_queue_account_info.key,
-                market_coin_vault_account_info.key,
-                market_pc_vault_account_info.key,
-                market_vault_signer_account_info.key,
-                choosed_any_mint_token_account_account_info.key,
-                w_sol_token_account_account_info.key,
-                choosed_any_mint_token_account_authority_account_info.key,
-                input_token_amount,
-                minimum_output_token_amount_,
-            ),
-            vec![
-                token_program_account_info.clone(),
-                amm_pool_account_info.clone(),
-                amm_authority_account_info.clone(),
-                amm_open_orders_account_info.clone(),
-                amm_coin_vault_account_info.clone(),
-                amm_pc_vault_account_info.clone(),
-                market_program_id_account_info.clone(),
-                market_account_info.clone(),
-                market_bids_account_info.clone(),
-                market_asks_account_info.clone(),
-                market_event_queue_account_info.clone(),
-                market_coin_vault_account_info.clone(),
-                market_pc_vault_account_info.clone(),
-                market_vault_signer_account_info.clone(),
-                choosed_any_mint_token_account_account_info.clone(),
-                w_sol_token_account_account_info.clone(),
-                choosed_any_mint_token_account_authority_account_info.clone(),
-            ]
-            .as_slice(),
-            [choosed_any_mint_token_account_authority_pubkey_seeds.as_slice()].as_slice(),
-        )?;
-        if let Some(current_w_sol_token_account_amount_) = current_w_sol_token_account_amount {
-            if (Account::unpack_unchecked(&w_sol_token_account_account_info.data.borrow())?.amount - current_w_sol_token_account_amount_) < minimum_output_token_amount_ {
-                return Err(Error::TokenAccountInvalidAmount.into());
+        }
+        for (index, (intermediate_token_mint_account_info, intermediate_token_account_account_info)) in
+            intermediate_token_mint_account_infos.iter().zip(intermediate_token_account_account_infos.iter()).enumerate()
+        {
+            if !intermediate_token_account_account_info.data_is_empty() {
+                continue;
             }
+            let intermediate_token_account_address_resolver = IntermediateTokenAccountAddressResolver {
+                intermediate_token_mint_pubkey: intermediate_token_mint_account_info.key,
+                any_mint_token_account_pubkey: any_mint_token_account_account_info.key,
+                intermediary_pubkey: intermediary_account_info.key,
+            };
+            let mut intermediate_token_account_pubkey_seeds = intermediate_token_account_address_resolver.get_seeds();
+            let intermediate_token_account_pubkey_bump_seed_ = [intermediate_token_account_pubkey_bump_seeds[index]];
+            intermediate_token_account_pubkey_seeds.push(intermediate_token_account_pubkey_bump_seed_.as_slice());
+            solana_program::program::invoke_signed(
+                &solana_program::system_instruction::create_account(
+                    intermediary_trader_account_info.key,
+                    intermediate_token_account_account_info.key,
+                    token_account_rent_exemption_balance,
+                    <Account as Pack>::LEN as u64,
+                    token_program_account_info.key,
+                ),
+                vec![
+                    intermediary_trader_account_info.clone(),
+                    (*intermediate_token_account_account_info).clone(),
+                ]
+                .as_slice(),
+                [intermediate_token_account_pubkey_seeds.as_slice()].as_slice(),
+            )?;
+            solana_program::program::invoke(
+                &spl_token::instruction::initialize_account(
+                    token_program_account_info.key,
+                    intermediate_token_account_account_info.key,
+                    intermediate_token_mint_account_info.key,
+                    any_mint_token_account_authority_account_info.key,
+                )?,
+                vec![
+                    (*intermediate_token_account_account_info).clone(),
+                    (*intermediate_token_mint_account_info).clone(),
+                    any_mint_token_account_authority_account_info.clone(),
+                    rent_account_info.clone(),
+                ]
+                .as_slice(),
+            )?;
+        }
+        // The route runs from the first token account to the last one, each leg swapping into the next.
+        // Every token account of the route is owned by the any mint token account authority, which signs each leg.
+        let mut any_mint_token_account_authority_pubkey_seeds = any_mint_token_account_authority_address_resolver.get_seeds();
+        let any_mint_token_account_authority_pubkey_bump_seed_ = [any_mint_token_account_authority_pubkey_bump_seed];
+        any_mint_token_account_authority_pubkey_seeds.push(any_mint_token_account_authority_pubkey_bump_seed_.as_slice());
+        let (first_token_account_account_info, first_token_mint_account_info, last_token_account_account_info, last_token_mint_account_info) = match side {
+            Side::Buy => (temporary_w_sol_token_account_account_info, w_sol_token_mint_account_info, any_mint_token_account_account_info, any_mint_token_mint_account_info),
+            Side::Sell => (any_mint_token_account_account_info, any_mint_token_mint_account_info, temporary_w_sol_token_account_account_info, w_sol_token_mint_account_info),
+        };
+        let mut route_token_account_account_infos = vec![first_token_account_account_info];
+        route_token_account_account_infos.extend(intermediate_token_account_account_infos.iter());
+        route_token_account_account_infos.push(last_token_account_account_info);
+        let mut route_token_mint_account_infos = vec![first_token_mint_account_info];
+        route_token_mint_account_infos.extend(intermediate_token_mint_account_infos.iter());
+        route_token_mint_account_infos.push(last_token_mint_account_info);
+        for (index, any_venue_adapter) in any_venue_adapters.iter().enumerate() {
+            let leg_amount_in = if index == 0 {
+                amount_in
+            } else {
+                Account::unpack(&route_token_account_account_infos[index].data.borrow())?.amount
+            };
+            let (quote_token_mint_account_info, base_token_mint_account_info) = match side {
+                Side::Buy => (route_token_mint_account_infos[index], route_token_mint_account_infos[index + 1]),
+                Side::Sell => (route_token_mint_account_infos[index + 1], route_token_mint_account_infos[index]),
+            };
+            let base_token_program_account_info = if base_token_mint_account_info.key == any_mint_token_mint_account_info.key {
+                any_mint_token_program_account_info
+            } else {
+                token_program_account_info
+            };
+            let venue_swap = VenueSwap {
+                side,
+                source_token_account_account_info: route_token_account_account_infos[index],
+                destination_token_account_account_info: route_token_account_account_infos[index + 1],
+                authority_account_info: any_mint_token_account_authority_account_info,
+                quote_token_mint_account_info: Some(quote_token_mint_account_info),
+                base_token_mint_account_info,
+                system_program_account_info,
+                token_program_account_info,
+                base_token_program_account_info,
+                amount_in: leg_amount_in,
+                minimum_amount_out: if index == any_venue_adapters.len() - 1 {
+                    min_out
+                } else {
+                    0
+                },
+            };
+            let reserves = any_venue_adapter.get_reserves(&venue_swap)?;
+            let source_token_account_amount_before_leg = Self::unpack_any_mint_token_account(route_token_account_account_infos[index])?.amount;
+            let destination_token_account_amount_before_leg = Self::unpack_any_mint_token_account(route_token_account_account_infos[index + 1])?.amount;
+            let (swap_instruction, swap_account_infos) = any_venue_adapter.create_swap_instruction(&venue_swap)?;
+            solana_program::program::invoke_signed(
+                &swap_instruction,
+                swap_account_infos.as_slice(),
+                [any_mint_token_account_authority_pubkey_seeds.as_slice()].as_slice(),
+            )?;
+            Self::enforce_price_deviation(
+                intermediary.maximum_price_deviation_basis_points,
+                reserves,
+                source_token_account_amount_before_leg - Self::unpack_any_mint_token_account(route_token_account_account_infos[index])?.amount,
+                Self::unpack_any_mint_token_account(route_token_account_account_infos[index + 1])?.amount - destination_token_account_amount_before_leg,
+            )?;
         }
-        if Account::unpack_unchecked(&choosed_any_mint_token_account_account_info.data.borrow())?.amount != 0 {
+        let any_mint_token_account_amount_after_swap = Self::unpack_any_mint_token_account(any_mint_token_account_account_info)?.amount;
+        let route_amount_out = match side {
+            Side::Buy => any_mint_token_account_amount_after_swap - any_mint_token_account_amount_before_swap,
+            // Measured before any account is closed into the treasury, so rent is not counted as proceeds.
+            Side::Sell => Account::unpack(&temporary_w_sol_token_account_account_info.data.borrow())?.amount,
+        };
+        if route_amount_out < min_out {
             return Err(Error::TokenAccountInvalidAmount.into());
         }
         solana_program::program::invoke_signed(
             &spl_token::instruction::close_account(
                 token_program_account_info.key,
-                choosed_any_mint_token_account_account_info.key,
+                temporary_w_sol_token_account_account_info.key,
                 w_sol_token_account_account_info.key,
-                choosed_any_mint_token_account_authority_account_info.key,
+                any_mint_token_account_authority_account_info.key,
                 [].as_slice(),
             )?,
             vec![
-                choosed_any_mint_token_account_account_info.clone(),
+                temporary_w_sol_token_account_account_info.clone(),
                 w_sol_token_account_account_info.clone(),
-                choosed_any_mint_token_account_authority_account_info.clone(),
+                any_mint_token_account_authority_account_info.clone(),
             ]
             .as_slice(),
-            [choosed_any_mint_token_account_authority_pubkey_seeds.as_slice()].as_slice(),
+            [any_mint_token_account_authority_pubkey_seeds.as_slice()].as_slice(),
         )?;
+        // Every leg spends the whole intermediate amount, so no intermediate token account outlives the route.
+        for intermediate_token_account_account_info in intermediate_token_account_account_infos.iter() {
+            if Account::unpack(&intermediate_token_account_account_info.data.borrow())?.amount != 0 {
+                return Err(Error::TokenAccountInvalidAmount.into());
+            }
+            solana_program::program::invoke_signed(
+                &spl_token::instruction::close_account(
+                    token_program_account_info.key,
+                    intermediate_token_account_account_info.key,
+                    w_sol_token_account_account_info.key,
+                    any_mint_token_account_authority_account_info.key,
+                    [].as_slice(),
+                )?,
+                vec![
+                    (*intermediate_token_account_account_info).clone(),
+                    w_sol_token_account_account_info.clone(),
+                    any_mint_token_account_authority_account_info.clone(),
+                ]
+                .as_slice(),
+                [any_mint_token_account_authority_pubkey_seeds.as_slice()].as_slice(),
+            )?;
+        }
+        if side == Side::Sell && any_mint_token_account_amount_after_swap == 0 {
+            Self::harvest_any_mint_withheld_tokens(
+                any_mint_token_program_account_info,
+                any_mint_token_mint_account_info,
+                any_mint_token_account_account_info,
+            )?;
+            solana_program::program::invoke_signed(
+                &spl_token_2022::instruction::close_account(
+                    any_mint_token_program_account_info.key,
+                    any_mint_token_account_account_info.key,
+                    w_sol_token_account_account_info.key,
+                    any_mint_token_account_authority_account_info.key,
+                    [].as_slice(),
+                )?,
+                vec![
+                    any_mint_token_account_account_info.clone(),
+                    w_sol_token_account_account_info.clone(),
+                    any_mint_token_account_authority_account_info.clone(),
+                ]
+                .as_slice(),
+                [any_mint_token_account_authority_pubkey_seeds.as_slice()].as_slice(),
+            )?;
+            Self::record_open_positions(
+                intermediary_account_info,
+                0,
+                1,
+            )?;
+        }
         solana_program::program::invoke(
             &spl_token::instruction::sync_native(
                 token_program_account_info.key,
@@ -1033,14 +4674,54 @@ impl Processor {
             ]
             .as_slice(),
         )?;
+        let w_sol_token_account_amount_after_swap = Account::unpack_unchecked(&w_sol_token_account_account_info.data.borrow())?.amount;
+        let (route_amount_in, spent_lamports, received_lamports) = match side {
+            Side::Buy => {
+                let spent_lamports = w_sol_token_account.amount.saturating_sub(w_sol_token_account_amount_after_swap);
+                (
+                    spent_lamports,
+                    spent_lamports,
+                    0,
+                )
+            }
+            Side::Sell => (
+                amount_in,
+                0,
+                route_amount_out,
+            ),
+        };
+        Event::RoutedTraded {
+            intermediary_pubkey: *intermediary_account_info.key,
+            venues,
+            side,
+            mint_pubkey: *any_mint_token_mint_account_info.key,
+            intermediate_mint_pubkeys: intermediate_token_mint_account_infos
+                .iter()
+                .map(|intermediate_token_mint_account_info| *intermediate_token_mint_account_info.key)
+                .collect(),
+            amount_in: route_amount_in,
+            amount_out: route_amount_out,
+            treasury_amount: w_sol_token_account_amount_after_swap,
+        }
+        .emit()?;
+        Self::record_trader_seat_trade(
+            trader_seat_account_info,
+            spent_lamports,
+            received_lamports,
+        )?;
         Ok(())
     }
-    fn buy_on_pumpswap<'a>(
-        _program_id: &'a Pubkey,
-        accounts: &'a [AccountInfo],
-        base_amount_out: u64,
-        maximum_quote_amount_in: u64,
+    // The any mint, temporary any mint and temporary WSol token accounts are owned by their program derived authorities.
+    fn swap_on_venue<'a, 'b, V: VenueAdapter<'a, 'b>>(
+        accounts: &'a [AccountInfo<'b>],
+        venue_adapter: &V,
+        side: Side,
+        amount_in: u64,
+        min_out: u64,
         any_mint_token_account_pubkey_bump_seed: u8,
+        any_mint_token_account_authority_pubkey_bump_seed: u8,
+        temporary_any_mint_token_account_pubkey_bump_seed: u8,
+        temporary_any_mint_token_account_authority_pubkey_bump_seed: u8,
     ) -> ProgramResult {
         let account_info_iter = &mut accounts.iter();
         let intermediary_trader_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
@@ -1048,61 +4729,214 @@ impl Processor {
         let w_sol_token_account_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
         let w_sol_token_account_authority_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
         let temporary_w_sol_token_account_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let temporary_w_sol_token_account_authority_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
         let any_mint_token_account_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let any_mint_token_account_authority_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let temporary_any_mint_token_account_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let temporary_any_mint_token_account_authority_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
         let w_sol_token_mint_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
         let any_mint_token_mint_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
         let system_program_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
         let rent_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
         let token_program_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
-        let pumpfan_program_id_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
-        let pool_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
-        let global_config_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
-        let pool_base_token_account_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
-        let pool_quote_token_account_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
-        let protocol_fee_recipient_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
-        let protocol_fee_recipient_token_account_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
-        let base_token_program_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
-        let quote_token_program_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
-        let associated_token_account_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
-        let event_authority_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let any_mint_token_program_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let allowed_mints_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let risk_limits_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let trader_seat_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let config_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let fee_vault_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        match side {
+            Side::Buy => {
+                Self::buy_on_venue(
+                    &VenueBuyAccounts {
+                        intermediary_trader_account_info,
+                        intermediary_account_info,
+                        w_sol_token_account_account_info,
+                        w_sol_token_account_authority_account_info,
+                        temporary_w_sol_token_account_account_info,
+                        temporary_w_sol_token_account_authority_account_info,
+                        any_mint_token_account_account_info,
+                        any_mint_token_account_authority_account_info,
+                        w_sol_token_mint_account_info,
+                        any_mint_token_mint_account_info,
+                        system_program_account_info,
+                        rent_account_info,
+                        token_program_account_info,
+                        any_mint_token_program_account_info,
+                        allowed_mints_account_info,
+                        risk_limits_account_info,
+                        trader_seat_account_info,
+                        config_account_info,
+                        fee_vault_account_info,
+                    },
+                    venue_adapter,
+                    amount_in,
+                    min_out,
+                    any_mint_token_account_pubkey_bump_seed,
+                    any_mint_token_account_authority_pubkey_bump_seed,
+                    false,
+                )
+            }
+            Side::Sell => {
+                Self::sell_on_venue(
+                    &VenueSellAccounts {
+                        intermediary_trader_account_info,
+                        intermediary_account_info,
+                        w_sol_token_account_account_info,
+                        temporary_w_sol_token_account_account_info: Some(temporary_w_sol_token_account_account_info),
+                        any_mint_token_account_account_info,
+                        any_mint_token_account_authority_account_info,
+                        temporary_any_mint_token_account_account_info,
+                        temporary_any_mint_token_account_authority_account_info,
+                        w_sol_token_mint_account_info: Some(w_sol_token_mint_account_info),
+                        any_mint_token_mint_account_info,
+                        system_program_account_info,
+                        rent_account_info,
+                        token_program_account_info,
+                        any_mint_token_program_account_info,
+                        risk_limits_account_info,
+                        trader_seat_account_info,
+                        config_account_info,
+                        fee_vault_account_info,
+                    },
+                    venue_adapter,
+                    amount_in,
+                    Some(min_out),
+                    any_mint_token_account_pubkey_bump_seed,
+                    temporary_any_mint_token_account_pubkey_bump_seed,
+                    any_mint_token_account_authority_pubkey_bump_seed,
+                    temporary_any_mint_token_account_authority_pubkey_bump_seed,
+                    false,
+                )
+            }
+        }
+    }
+    // Treasury -> temporary WSol token account -> venue -> any mint token account, the leftover goes back to the treasury.
+    fn buy_on_venue<'a, 'b, V: VenueAdapter<'a, 'b>>(
+        venue_buy_accounts: &VenueBuyAccounts<'a, 'b>,
+        venue_adapter: &V,
+        input_token_amount: u64,
+        minimum_output_token_amount: u64,
+        any_mint_token_account_pubkey_bump_seed: u8,
+        any_mint_token_account_authority_pubkey_bump_seed: u8,
+        is_exact_out: bool,
+    ) -> ProgramResult {
+        let VenueBuyAccounts {
+            intermediary_trader_account_info,
+            intermediary_account_info,
+            w_sol_token_account_account_info,
+            w_sol_token_account_authority_account_info,
+            temporary_w_sol_token_account_account_info,
+            temporary_w_sol_token_account_authority_account_info,
+            any_mint_token_account_account_info,
+            any_mint_token_account_authority_account_info,
+            w_sol_token_mint_account_info,
+            any_mint_token_mint_account_info,
+            system_program_account_info,
+            rent_account_info,
+            token_program_account_info,
+            any_mint_token_program_account_info,
+            allowed_mints_account_info,
+            risk_limits_account_info,
+            trader_seat_account_info,
+            config_account_info,
+            fee_vault_account_info,
+        } = *venue_buy_accounts;
         let any_mint_token_account_address_resolver = AnyMintTokenAccountAddressResolver {
             any_mint_token_mint_pubkey: any_mint_token_mint_account_info.key,
             intermediary_pubkey: intermediary_account_info.key,
         };
+        let any_mint_token_account_authority_address_resolver = AnyMintTokenAccountAuthorityAddressResolver {
+            any_mint_token_account_pubkey: any_mint_token_account_account_info.key,
+            intermediary_pubkey: intermediary_account_info.key,
+        };
         if *any_mint_token_account_account_info.key != any_mint_token_account_address_resolver.create(any_mint_token_account_pubkey_bump_seed)?
+            || *any_mint_token_account_authority_account_info.key != any_mint_token_account_authority_address_resolver.create(any_mint_token_account_authority_pubkey_bump_seed)?
             || w_sol_token_mint_account_info.key == any_mint_token_mint_account_info.key
             || *w_sol_token_mint_account_info.key != spl_token::native_mint::ID
             || *system_program_account_info.key != solana_program::system_program::ID
             || *rent_account_info.key != solana_program::sysvar::rent::ID
             || *token_program_account_info.key != spl_token::ID
-            || *pumpfan_program_id_account_info.key != PUMPSWAP_PROGRAM_PUBKEY
-            || *associated_token_account_account_info.key != SPL_ASSOCIATED_TOKEN_ACCOUNT_ID
         {
             return Err(Error::InvalidAccountPubkey.into());
         }
+        Self::enforce_any_mint_token_program(
+            any_mint_token_mint_account_info,
+            any_mint_token_program_account_info,
+        )?;
         if !intermediary_trader_account_info.is_signer
             || !intermediary_trader_account_info.is_writable
+            || !intermediary_account_info.is_writable
             || !w_sol_token_account_account_info.is_writable
             || !temporary_w_sol_token_account_account_info.is_writable
             || !any_mint_token_account_account_info.is_writable
-            || !pool_base_token_account_account_info.is_writable
-            || !pool_quote_token_account_account_info.is_writable
-            || !protocol_fee_recipient_token_account_account_info.is_writable
+            || !risk_limits_account_info.is_writable
+            || !trader_seat_account_info.is_writable
+            || !fee_vault_account_info.is_writable
         {
             return Err(Error::InvalidAccountConfigurationFlags.into());
         }
         let rent = Rent::from_account_info(rent_account_info)?;
         let token_account_rent_exemption_balance = rent.minimum_balance(<Account as Pack>::LEN);
-        if intermediary_trader_account_info.lamports() < (2 * token_account_rent_exemption_balance + FEE_BUY_ON_PUMPSWAP) {
+        if *config_account_info.owner != PROGRAM_PUBKEY {
+            return Err(Error::InvalidAccountOwner.into());
+        }
+        let config = borsh::from_slice::<Config>(&config_account_info.data.borrow())?;
+        if !config.is_initialized {
+            return Err(Error::ConfigIsNotInitialized.into());
+        }
+        if *config_account_info.key != ConfigAddressResolver.create(config.config_pubkey_bump_seed)? {
+            return Err(Error::InvalidAccountPubkey.into());
+        }
+        if *fee_vault_account_info.key != config.fee_vault_pubkey {
+            return Err(Error::ConfigInvalidFeeVault.into());
+        }
+        let fee = V::get_fee(
+            &config.fees,
+            Side::Buy,
+        );
+        let is_any_mint_token_account_initialized = !any_mint_token_account_account_info.data_is_empty();
+        let any_mint_token_account_length = Self::get_any_mint_token_account_length(any_mint_token_mint_account_info)?;
+        let any_mint_token_account_rent_exemption_balance = if is_any_mint_token_account_initialized {
+            0
+        } else {
+            rent.minimum_balance(any_mint_token_account_length)
+        };
+        if intermediary_trader_account_info.lamports() < (token_account_rent_exemption_balance + any_mint_token_account_rent_exemption_balance + fee) {
             return Err(Error::InvalidAccountLamports.into());
         }
-        let intermediary = borsh::from_slice::<Intermediary>(&intermediary_account_info.data.borrow())?;
+        solana_program::program::invoke(
+            &solana_program::system_instruction::transfer(
+                intermediary_trader_account_info.key,
+                fee_vault_account_info.key,
+                fee,
+            ),
+            vec![
+                intermediary_trader_account_info.clone(),
+                fee_vault_account_info.clone(),
+            ]
+            .as_slice(),
+        )?;
+        Event::FeeCharged {
+            intermediary_pubkey: *intermediary_account_info.key,
+            payer_pubkey: *intermediary_trader_account_info.key,
+            fee_vault_pubkey: *fee_vault_account_info.key,
+            lamports: fee,
+        }
+        .emit()?;
+        let intermediary = Intermediary::load(&intermediary_account_info.data.borrow())?;
         if !intermediary.is_initialized {
             return Err(Error::IntermediaryIsNotInitialized.into());
         }
-        if *intermediary_trader_account_info.key != intermediary.trader_pubkey {
-            return Err(Error::IntermediaryInvalidInvestor.into());
+        if intermediary.is_paused {
+            return Err(Error::IntermediaryPaused.into());
         }
+        Self::enforce_trader_seat(
+            intermediary_account_info,
+            trader_seat_account_info,
+            intermediary_trader_account_info.key,
+            input_token_amount,
+        )?;
         if *w_sol_token_account_account_info.key != intermediary.w_sol_token_account_pubkey {
             return Err(Error::IntermediaryInvalidWSolTokenAccount.into());
         }
@@ -1112,10 +4946,39 @@ impl Processor {
         if *temporary_w_sol_token_account_account_info.key != intermediary.temporary_w_sol_token_account_pubkey {
             return Err(Error::IntermediaryInvalidTemporaryWSolTokenAccount.into());
         }
+        if *temporary_w_sol_token_account_authority_account_info.key != intermediary.temporary_w_sol_token_account_authority_pubkey {
+            return Err(Error::IntermediaryInvalidTemporaryWSolTokenAccountAuthority.into());
+        }
+        let any_mint_token_account_amount_before_swap = if is_any_mint_token_account_initialized {
+            if any_mint_token_account_account_info.owner != any_mint_token_program_account_info.key {
+                return Err(Error::InvalidAccountOwner.into());
+            }
+            let any_mint_token_account = Self::unpack_any_mint_token_account(any_mint_token_account_account_info)?;
+            if any_mint_token_account.mint != *any_mint_token_mint_account_info.key {
+                return Err(Error::TokenAccountInvalidMint.into());
+            }
+            if any_mint_token_account.owner != *any_mint_token_account_authority_account_info.key {
+                return Err(Error::TokenAccountInvalidAuthority.into());
+            }
+            any_mint_token_account.amount
+        } else {
+            0
+        };
         let w_sol_token_account = Account::unpack_unchecked(&w_sol_token_account_account_info.data.borrow())?;
-        if maximum_quote_amount_in > w_sol_token_account.amount {
+        if input_token_amount > w_sol_token_account.amount {
             return Err(Error::TokenAccountInsufficientAmount.into());
         }
+        Self::enforce_allowed_mint(
+            intermediary_account_info,
+            allowed_mints_account_info,
+            any_mint_token_mint_account_info.key,
+        )?;
+        Self::enforce_risk_limits(
+            intermediary_account_info,
+            risk_limits_account_info,
+            input_token_amount,
+            w_sol_token_account.amount,
+        )?;
         let temporary_w_sol_token_account_address_resolver = TemporaryWSolTokenAccountAddressResolver {
             w_sol_token_account_pubkey: w_sol_token_account_account_info.key,
         };
@@ -1128,7 +4991,7 @@ impl Processor {
                 temporary_w_sol_token_account_account_info.key,
                 token_account_rent_exemption_balance,
                 <Account as Pack>::LEN as u64,
-                quote_token_program_account_info.key,
+                token_program_account_info.key,
             ),
             vec![
                 intermediary_trader_account_info.clone(),
@@ -1139,15 +5002,15 @@ impl Processor {
         )?;
         solana_program::program::invoke(
             &spl_token::instruction::initialize_account(
-                quote_token_program_account_info.key,
+                token_program_account_info.key,
                 temporary_w_sol_token_account_account_info.key,
                 w_sol_token_mint_account_info.key,
-                intermediary_trader_account_info.key,
+                temporary_w_sol_token_account_authority_account_info.key,
             )?,
             vec![
                 temporary_w_sol_token_account_account_info.clone(),
                 w_sol_token_mint_account_info.clone(),
-                intermediary_trader_account_info.clone(),
+                temporary_w_sol_token_account_authority_account_info.clone(),
                 rent_account_info.clone(),
             ]
             .as_slice(),
@@ -1166,7 +5029,7 @@ impl Processor {
                 temporary_w_sol_token_account_account_info.key,
                 w_sol_token_account_authority_account_info.key,
                 [].as_slice(),
-                maximum_quote_amount_in,
+                input_token_amount,
             )?,
             vec![
                 w_sol_token_account_account_info.clone(),
@@ -1176,98 +5039,158 @@ impl Processor {
             .as_slice(),
             [w_sol_token_account_authority_pubkey_seeds.as_slice()].as_slice(),
         )?;
-        let mut any_mint_token_account_pubkey_seeds = any_mint_token_account_address_resolver.get_seeds();
-        let any_mint_token_account_pubkey_bump_seed_ = [any_mint_token_account_pubkey_bump_seed];
-        any_mint_token_account_pubkey_seeds.push(any_mint_token_account_pubkey_bump_seed_.as_slice());
+        if !is_any_mint_token_account_initialized {
+            let mut any_mint_token_account_pubkey_seeds = any_mint_token_account_address_resolver.get_seeds();
+            let any_mint_token_account_pubkey_bump_seed_ = [any_mint_token_account_pubkey_bump_seed];
+            any_mint_token_account_pubkey_seeds.push(any_mint_token_account_pubkey_bump_seed_.as_slice());
+            solana_program::program::invoke_signed(
+                &solana_program::system_instruction::create_account(
+                    intermediary_trader_account_info.key,
+                    any_mint_token_account_account_info.key,
+                    any_mint_token_account_rent_exemption_balance,
+                    any_mint_token_account_length as u64,
+                    any_mint_token_program_account_info.key,
+                ),
+                vec![
+                    intermediary_trader_account_info.clone(),
+                    any_mint_token_account_account_info.clone(),
+                ]
+                .as_slice(),
+                [any_mint_token_account_pubkey_seeds.as_slice()].as_slice(),
+            )?;
+            solana_program::program::invoke(
+                &spl_token_2022::instruction::initialize_account(
+                    any_mint_token_program_account_info.key,
+                    any_mint_token_account_account_info.key,
+                    any_mint_token_mint_account_info.key,
+                    any_mint_token_account_authority_account_info.key,
+                )?,
+                vec![
+                    any_mint_token_account_account_info.clone(),
+                    any_mint_token_mint_account_info.clone(),
+                    any_mint_token_account_authority_account_info.clone(),
+                    rent_account_info.clone(),
+                ]
+                .as_slice(),
+            )?;
+            Self::record_open_positions(
+                intermediary_account_info,
+                1,
+                0,
+            )?;
+        }
+        let temporary_w_sol_token_account_authority_address_resolver = TemporaryWSolTokenAccountAuthorityAddressResolver {
+            temporary_w_sol_token_account_pubkey: temporary_w_sol_token_account_account_info.key,
+            intermediary_pubkey: intermediary_account_info.key,
+        };
+        let mut temporary_w_sol_token_account_authority_pubkey_seeds = temporary_w_sol_token_account_authority_address_resolver.get_seeds();
+        let temporary_w_sol_token_account_authority_pubkey_bump_seed_ = [intermediary.temporary_w_sol_token_account_authority_pubkey_bump_seed];
+        temporary_w_sol_token_account_authority_pubkey_seeds.push(temporary_w_sol_token_account_authority_pubkey_bump_seed_.as_slice());
+        let temporary_w_sol_token_account_authority_signers_seeds = vec![temporary_w_sol_token_account_authority_pubkey_seeds.as_slice()];
+        let venue_swap = VenueSwap {
+            side: Side::Buy,
+            source_token_account_account_info: temporary_w_sol_token_account_account_info,
+            destination_token_account_account_info: any_mint_token_account_account_info,
+            authority_account_info: temporary_w_sol_token_account_authority_account_info,
+            quote_token_mint_account_info: Some(w_sol_token_mint_account_info),
+            base_token_mint_account_info: any_mint_token_mint_account_info,
+            system_program_account_info,
+            token_program_account_info,
+            base_token_program_account_info: any_mint_token_program_account_info,
+            amount_in: input_token_amount,
+            minimum_amount_out: minimum_output_token_amount,
+        };
+        let reserves = venue_adapter.get_reserves(&venue_swap)?;
+        let (swap_instruction, swap_account_infos) = if is_exact_out {
+            venue_adapter.create_exact_out_swap_instruction(&venue_swap)?
+        } else {
+            venue_adapter.create_swap_instruction(&venue_swap)?
+        };
+        // A venue paid in native SOL gets the treasury share unwrapped into the swap authority.
+        let temporary_w_sol_token_account_lamports = temporary_w_sol_token_account_account_info.lamports();
+        let temporary_w_sol_token_account_authority_lamports_before_unwrap = temporary_w_sol_token_account_authority_account_info.lamports();
+        if V::IS_SETTLED_IN_LAMPORTS {
+            solana_program::program::invoke_signed(
+                &spl_token::instruction::close_account(
+                    token_program_account_info.key,
+                    temporary_w_sol_token_account_account_info.key,
+                    temporary_w_sol_token_account_authority_account_info.key,
+                    temporary_w_sol_token_account_authority_account_info.key,
+                    [].as_slice(),
+                )?,
+                vec![
+                    temporary_w_sol_token_account_account_info.clone(),
+                    temporary_w_sol_token_account_authority_account_info.clone(),
+                ]
+                .as_slice(),
+                temporary_w_sol_token_account_authority_signers_seeds.as_slice(),
+            )?;
+        }
         solana_program::program::invoke_signed(
-            &solana_program::system_instruction::create_account(
-                intermediary_trader_account_info.key,
-                any_mint_token_account_account_info.key,
-                token_account_rent_exemption_balance,
-                <Account as Pack>::LEN as u64,
-                base_token_program_account_info.key,
-            ),
-            vec![
-                intermediary_trader_account_info.clone(),
-                any_mint_token_account_account_info.clone(),
-            ]
-            .as_slice(),
-            [any_mint_token_account_pubkey_seeds.as_slice()].as_slice(),
-        )?;
-        solana_program::program::invoke(
-            &spl_token::instruction::initialize_account(
-                quote_token_program_account_info.key,
-                any_mint_token_account_account_info.key,
-                any_mint_token_mint_account_info.key,
-                intermediary_trader_account_info.key,
-            )?,
-            vec![
-                any_mint_token_account_account_info.clone(),
-                any_mint_token_mint_account_info.clone(),
-                intermediary_trader_account_info.clone(),
-                rent_account_info.clone(),
-            ]
-            .as_slice(),
+            &swap_instruction,
+            swap_account_infos.as_slice(),
+            temporary_w_sol_token_account_authority_signers_seeds.as_slice(),
         )?;
-        solana_program::program::invoke(
-            &crate::extern_source::create_pumpswap_buy_instruction(
-                pumpfan_program_id_account_info.key,
-                pool_account_info.key,
-                intermediary_trader_account_info.key,
-                global_config_account_info.key,
-                any_mint_token_mint_account_info.key,
-                w_sol_token_mint_account_info.key,
-                any_mint_token_account_account_info.key,
-                temporary_w_sol_token_account_account_info.key,
-                pool_base_token_account_account_info.key,
-                pool_quote_token_account_account_info.key,
-                protocol_fee_recipient_account_info.key,
-                protocol_fee_recipient_token_account_account_info.key,
-                base_token_program_account_info.key,
-                quote_token_program_account_info.key,
-                event_authority_account_info.key,
-                base_amount_out,
-                maximum_quote_amount_in,
-            ),
-            vec![
-                pool_account_info.clone(),
-                intermediary_trader_account_info.clone(),
-                global_config_account_info.clone(),
-                any_mint_token_mint_account_info.clone(),
-                w_sol_token_mint_account_info.clone(),
-                any_mint_token_account_account_info.clone(),
-                temporary_w_sol_token_account_account_info.clone(),
-                pool_base_token_account_account_info.clone(),
-                pool_quote_token_account_account_info.clone(),
-                protocol_fee_recipient_account_info.clone(),
-                protocol_fee_recipient_token_account_account_info.clone(),
-                base_token_program_account_info.clone(),
-                quote_token_program_account_info.clone(),
-                system_program_account_info.clone(),
-                associated_token_account_account_info.clone(),
-                event_authority_account_info.clone(),
-                pumpfan_program_id_account_info.clone(),
-            ]
-            .as_slice(),
+        let spent_input_token_amount = if V::IS_SETTLED_IN_LAMPORTS {
+            // Lamports the venue did not take, together with the temporary account rent, are wrapped back into the treasury.
+            let unspent_lamports = temporary_w_sol_token_account_authority_account_info
+                .lamports()
+                .checked_sub(temporary_w_sol_token_account_authority_lamports_before_unwrap)
+                .ok_or(Error::InvalidAccountLamports)?;
+            solana_program::program::invoke_signed(
+                &solana_program::system_instruction::transfer(
+                    temporary_w_sol_token_account_authority_account_info.key,
+                    w_sol_token_account_account_info.key,
+                    unspent_lamports,
+                ),
+                vec![
+                    temporary_w_sol_token_account_authority_account_info.clone(),
+                    w_sol_token_account_account_info.clone(),
+                ]
+                .as_slice(),
+                temporary_w_sol_token_account_authority_signers_seeds.as_slice(),
+            )?;
+            temporary_w_sol_token_account_lamports.checked_sub(unspent_lamports).ok_or(Error::InvalidAccountLamports)?
+        } else {
+            input_token_amount - Account::unpack(&temporary_w_sol_token_account_account_info.data.borrow())?.amount
+        };
+        let any_mint_token_account_amount_after_swap = Self::unpack_any_mint_token_account(any_mint_token_account_account_info)?.amount;
+        Self::enforce_price_deviation(
+            intermediary.maximum_price_deviation_basis_points,
+            reserves,
+            spent_input_token_amount,
+            any_mint_token_account_amount_after_swap - any_mint_token_account_amount_before_swap,
         )?;
-        if Account::unpack_unchecked(&any_mint_token_account_account_info.data.borrow())?.amount < base_amount_out {
+        // An exact output is paid before the transfer fee is withheld, any other output is held to the bound as received.
+        let minimum_output_token_amount_after_transfer_fee = if V::IS_BUYING_EXACT_OUT || is_exact_out {
+            minimum_output_token_amount - Self::calculate_any_mint_transfer_fee(
+                any_mint_token_mint_account_info,
+                minimum_output_token_amount,
+            )?
+        } else {
+            minimum_output_token_amount
+        };
+        if (any_mint_token_account_amount_after_swap - any_mint_token_account_amount_before_swap) < minimum_output_token_amount_after_transfer_fee {
             return Err(Error::TokenAccountInvalidAmount.into());
         }
-        solana_program::program::invoke(
-            &spl_token::instruction::close_account(
-                quote_token_program_account_info.key,
-                temporary_w_sol_token_account_account_info.key,
-                w_sol_token_account_account_info.key,
-                intermediary_trader_account_info.key,
-                [].as_slice(),
-            )?,
-            vec![
-                temporary_w_sol_token_account_account_info.clone(),
-                w_sol_token_account_account_info.clone(),
-                intermediary_trader_account_info.clone(),
-            ]
-            .as_slice(),
-        )?;
+        if !V::IS_SETTLED_IN_LAMPORTS {
+            solana_program::program::invoke_signed(
+                &spl_token::instruction::close_account(
+                    token_program_account_info.key,
+                    temporary_w_sol_token_account_account_info.key,
+                    w_sol_token_account_account_info.key,
+                    temporary_w_sol_token_account_authority_account_info.key,
+                    [].as_slice(),
+                )?,
+                vec![
+                    temporary_w_sol_token_account_account_info.clone(),
+                    w_sol_token_account_account_info.clone(),
+                    temporary_w_sol_token_account_authority_account_info.clone(),
+                ]
+                .as_slice(),
+                temporary_w_sol_token_account_authority_signers_seeds.as_slice(),
+            )?;
+        }
         solana_program::program::invoke(
             &spl_token::instruction::sync_native(
                 token_program_account_info.key,
@@ -1278,135 +5201,244 @@ impl Processor {
             ]
             .as_slice(),
         )?;
+        let w_sol_token_account_amount_after_swap = Account::unpack_unchecked(&w_sol_token_account_account_info.data.borrow())?.amount;
+        Event::Traded {
+            intermediary_pubkey: *intermediary_account_info.key,
+            venue: V::VENUE,
+            side: Side::Buy,
+            mint_pubkey: *any_mint_token_mint_account_info.key,
+            amount_in: w_sol_token_account.amount.saturating_sub(w_sol_token_account_amount_after_swap),
+            amount_out: any_mint_token_account_amount_after_swap - any_mint_token_account_amount_before_swap,
+            treasury_amount: w_sol_token_account_amount_after_swap,
+        }
+        .emit()?;
+        Self::record_trader_seat_trade(
+            trader_seat_account_info,
+            w_sol_token_account.amount.saturating_sub(w_sol_token_account_amount_after_swap),
+            0,
+        )?;
         Ok(())
     }
-    fn sell_on_pumpswap<'a>(
-        _program_id: &'a Pubkey,
-        accounts: &'a [AccountInfo],
+    // Any mint token account (or a temporary one for a partial sell) -> venue -> treasury, directly or through the temporary WSol token account.
+    fn sell_on_venue<'a, 'b, V: VenueAdapter<'a, 'b>>(
+        venue_sell_accounts: &VenueSellAccounts<'a, 'b>,
+        venue_adapter: &V,
         input_token_amount: u64,
         minimum_output_token_amount: Option<u64>,
         any_mint_token_account_pubkey_bump_seed: u8,
         temporary_any_mint_token_account_pubkey_bump_seed: u8,
+        any_mint_token_account_authority_pubkey_bump_seed: u8,
+        temporary_any_mint_token_account_authority_pubkey_bump_seed: u8,
+        is_exact_out: bool,
     ) -> ProgramResult {
-        let account_info_iter = &mut accounts.iter();
-        let intermediary_trader_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
-        let intermediary_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
-        let w_sol_token_account_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
-        let temporary_w_sol_token_account_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
-        let any_mint_token_account_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
-        let temporary_any_mint_token_account_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
-        let w_sol_token_mint_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
-        let any_mint_token_mint_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
-        let system_program_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
-        let rent_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
-        let token_program_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
-        let pumpfan_program_id_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
-        let pool_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
-        let global_config_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
-        let pool_base_token_account_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
-        let pool_quote_token_account_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
-        let protocol_fee_recipient_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
-        let protocol_fee_recipient_token_account_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
-        let base_token_program_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
-        let quote_token_program_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
-        let associated_token_account_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
-        let event_authority_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
+        let VenueSellAccounts {
+            intermediary_trader_account_info,
+            intermediary_account_info,
+            w_sol_token_account_account_info,
+            temporary_w_sol_token_account_account_info,
+            any_mint_token_account_account_info,
+            any_mint_token_account_authority_account_info,
+            temporary_any_mint_token_account_account_info,
+            temporary_any_mint_token_account_authority_account_info,
+            w_sol_token_mint_account_info,
+            any_mint_token_mint_account_info,
+            system_program_account_info,
+            rent_account_info,
+            token_program_account_info,
+            any_mint_token_program_account_info,
+            risk_limits_account_info,
+            trader_seat_account_info,
+            config_account_info,
+            fee_vault_account_info,
+        } = *venue_sell_accounts;
         let any_mint_token_account_address_resolver = AnyMintTokenAccountAddressResolver {
             any_mint_token_mint_pubkey: any_mint_token_mint_account_info.key,
             intermediary_pubkey: intermediary_account_info.key,
         };
+        let any_mint_token_account_authority_address_resolver = AnyMintTokenAccountAuthorityAddressResolver {
+            any_mint_token_account_pubkey: any_mint_token_account_account_info.key,
+            intermediary_pubkey: intermediary_account_info.key,
+        };
         let temporary_any_mint_token_account_address_resolver = TemporaryAnyMintTokenAccountAddressResolver {
             any_mint_token_account_pubkey: any_mint_token_account_account_info.key,
             intermediary_pubkey: intermediary_account_info.key,
         };
+        let temporary_any_mint_token_account_authority_address_resolver = TemporaryAnyMintTokenAccountAuthorityAddressResolver {
+            temporary_any_mint_token_account_pubkey: temporary_any_mint_token_account_account_info.key,
+            intermediary_pubkey: intermediary_account_info.key,
+        };
+        let is_w_sol_token_mint_valid = match w_sol_token_mint_account_info {
+            Some(w_sol_token_mint_account_info_) => *w_sol_token_mint_account_info_.key == spl_token::native_mint::ID,
+            None => true,
+        };
         if *any_mint_token_account_account_info.key != any_mint_token_account_address_resolver.create(any_mint_token_account_pubkey_bump_seed)?
+            || *any_mint_token_account_authority_account_info.key != any_mint_token_account_authority_address_resolver.create(any_mint_token_account_authority_pubkey_bump_seed)?
             || *temporary_any_mint_token_account_account_info.key != temporary_any_mint_token_account_address_resolver.create(temporary_any_mint_token_account_pubkey_bump_seed)?
-            || *any_mint_token_mint_account_info.key == *w_sol_token_mint_account_info.key
-            || *w_sol_token_mint_account_info.key != spl_token::native_mint::ID
+            || *temporary_any_mint_token_account_authority_account_info.key
+                != temporary_any_mint_token_account_authority_address_resolver.create(temporary_any_mint_token_account_authority_pubkey_bump_seed)?
+            || *any_mint_token_mint_account_info.key == spl_token::native_mint::ID
+            || !is_w_sol_token_mint_valid
             || *system_program_account_info.key != solana_program::system_program::ID
             || *rent_account_info.key != solana_program::sysvar::rent::ID
             || *token_program_account_info.key != spl_token::ID
-            || *pumpfan_program_id_account_info.key != PUMPSWAP_PROGRAM_PUBKEY
-            || *associated_token_account_account_info.key != SPL_ASSOCIATED_TOKEN_ACCOUNT_ID
         {
             return Err(Error::InvalidAccountPubkey.into());
         }
+        Self::enforce_any_mint_token_program(
+            any_mint_token_mint_account_info,
+            any_mint_token_program_account_info,
+        )?;
         if !intermediary_trader_account_info.is_signer
             || !intermediary_trader_account_info.is_writable
+            || !intermediary_account_info.is_writable
             || !w_sol_token_account_account_info.is_writable
-            || !temporary_w_sol_token_account_account_info.is_writable
+            || !temporary_w_sol_token_account_account_info.map_or(true, |temporary_w_sol_token_account_account_info_| temporary_w_sol_token_account_account_info_.is_writable)
             || !any_mint_token_account_account_info.is_writable
             || !temporary_any_mint_token_account_account_info.is_writable
-            || !pool_base_token_account_account_info.is_writable
-            || !pool_quote_token_account_account_info.is_writable
-            || !protocol_fee_recipient_token_account_account_info.is_writable
+            || (*any_mint_token_program_account_info.key == spl_token_2022::ID && !any_mint_token_mint_account_info.is_writable)
+            || !risk_limits_account_info.is_writable
+            || !trader_seat_account_info.is_writable
+            || !fee_vault_account_info.is_writable
         {
             return Err(Error::InvalidAccountConfigurationFlags.into());
         }
-        if any_mint_token_account_account_info.data_len() != <Account as Pack>::LEN {
-            return Err(Error::InvalidAccountData.into());
+        if any_mint_token_account_account_info.owner != any_mint_token_program_account_info.key {
+            return Err(Error::InvalidAccountOwner.into());
         }
         let rent = Rent::from_account_info(rent_account_info)?;
         let token_account_rent_exemption_balance = rent.minimum_balance(<Account as Pack>::LEN);
-        if intermediary_trader_account_info.lamports() < (token_account_rent_exemption_balance + FEE_SELL_ON_PUMPSWAP) {
-            return Err(Error::InvalidAccountLamports.into());
-        }
-        let intermediary = borsh::from_slice::<Intermediary>(&intermediary_account_info.data.borrow())?;
-        if !intermediary.is_initialized {
-            return Err(Error::IntermediaryIsNotInitialized.into());
+        let any_mint_token_account_length = Self::get_any_mint_token_account_length(any_mint_token_mint_account_info)?;
+        let any_mint_token_account_rent_exemption_balance = rent.minimum_balance(any_mint_token_account_length);
+        if *config_account_info.owner != PROGRAM_PUBKEY {
+            return Err(Error::InvalidAccountOwner.into());
         }
-        if *intermediary_trader_account_info.key != intermediary.trader_pubkey {
-            return Err(Error::IntermediaryInvalidInvestor.into());
+        let config = borsh::from_slice::<Config>(&config_account_info.data.borrow())?;
+        if !config.is_initialized {
+            return Err(Error::ConfigIsNotInitialized.into());
         }
-        if *w_sol_token_account_account_info.key != intermediary.w_sol_token_account_pubkey {
-            return Err(Error::IntermediaryInvalidWSolTokenAccount.into());
+        if *config_account_info.key != ConfigAddressResolver.create(config.config_pubkey_bump_seed)? {
+            return Err(Error::InvalidAccountPubkey.into());
         }
-        if *temporary_w_sol_token_account_account_info.key != intermediary.temporary_w_sol_token_account_pubkey {
-            return Err(Error::IntermediaryInvalidTemporaryWSolTokenAccount.into());
+        if *fee_vault_account_info.key != config.fee_vault_pubkey {
+            return Err(Error::ConfigInvalidFeeVault.into());
         }
-        let any_mint_token_account = Account::unpack_unchecked(&any_mint_token_account_account_info.data.borrow())?;
-        if input_token_amount > any_mint_token_account.amount {
-            return Err(Error::TokenAccountInsufficientAmount.into());
+        let fee = V::get_fee(
+            &config.fees,
+            Side::Sell,
+        );
+        if intermediary_trader_account_info.lamports() < (token_account_rent_exemption_balance + fee) {
+            return Err(Error::InvalidAccountLamports.into());
         }
-        let temporary_w_sol_token_account_address_resolver = TemporaryWSolTokenAccountAddressResolver {
-            w_sol_token_account_pubkey: w_sol_token_account_account_info.key,
-        };
-        let mut temporary_w_sol_token_account_pubkey_seeds = temporary_w_sol_token_account_address_resolver.get_seeds();
-        let temporary_w_sol_token_account_pubkey_bump_seed_ = [intermediary.temporary_w_sol_token_account_pubkey_bump_seed];
-        temporary_w_sol_token_account_pubkey_seeds.push(temporary_w_sol_token_account_pubkey_bump_seed_.as_slice());
-        solana_program::program::invoke_signed(
-            &solana_program::system_instruction::create_account(
+        solana_program::program::invoke(
+            &solana_program::system_instruction::transfer(
                 intermediary_trader_account_info.key,
-                temporary_w_sol_token_account_account_info.key,
-                token_account_rent_exemption_balance,
-                <Account as Pack>::LEN as u64,
-                quote_token_program_account_info.key,
+                fee_vault_account_info.key,
+                fee,
             ),
             vec![
                 intermediary_trader_account_info.clone(),
-                temporary_w_sol_token_account_account_info.clone(),
+                fee_vault_account_info.clone(),
             ]
             .as_slice(),
-            [temporary_w_sol_token_account_pubkey_seeds.as_slice()].as_slice(),
         )?;
-        solana_program::program::invoke(
-            &spl_token::instruction::initialize_account(
-                quote_token_program_account_info.key,
-                temporary_w_sol_token_account_account_info.key,
-                w_sol_token_mint_account_info.key,
-                intermediary_trader_account_info.key,
-            )?,
-            vec![
-                temporary_w_sol_token_account_account_info.clone(),
-                w_sol_token_mint_account_info.clone(),
-                intermediary_trader_account_info.clone(),
-                rent_account_info.clone(),
-            ]
-            .as_slice(),
+        Event::FeeCharged {
+            intermediary_pubkey: *intermediary_account_info.key,
+            payer_pubkey: *intermediary_trader_account_info.key,
+            fee_vault_pubkey: *fee_vault_account_info.key,
+            lamports: fee,
+        }
+        .emit()?;
+        let intermediary = Intermediary::load(&intermediary_account_info.data.borrow())?;
+        if !intermediary.is_initialized {
+            return Err(Error::IntermediaryIsNotInitialized.into());
+        }
+        if intermediary.is_paused {
+            return Err(Error::IntermediaryPaused.into());
+        }
+        Self::enforce_trader_seat(
+            intermediary_account_info,
+            trader_seat_account_info,
+            intermediary_trader_account_info.key,
+            0,
+        )?;
+        if *w_sol_token_account_account_info.key != intermediary.w_sol_token_account_pubkey {
+            return Err(Error::IntermediaryInvalidWSolTokenAccount.into());
+        }
+        if let Some(temporary_w_sol_token_account_account_info_) = temporary_w_sol_token_account_account_info {
+            if *temporary_w_sol_token_account_account_info_.key != intermediary.temporary_w_sol_token_account_pubkey {
+                return Err(Error::IntermediaryInvalidTemporaryWSolTokenAccount.into());
+            }
+        }
+        Self::enforce_risk_limits(
+            intermediary_account_info,
+            risk_limits_account_info,
+            0,
+            0,
         )?;
-        let choosed_any_mint_token_account_account_info =
+        let any_mint_token_account = Self::unpack_any_mint_token_account(any_mint_token_account_account_info)?;
+        if input_token_amount > any_mint_token_account.amount {
+            return Err(Error::TokenAccountInsufficientAmount.into());
+        }
+        let mut any_mint_token_account_authority_pubkey_seeds = any_mint_token_account_authority_address_resolver.get_seeds();
+        let any_mint_token_account_authority_pubkey_bump_seed_ = [any_mint_token_account_authority_pubkey_bump_seed];
+        any_mint_token_account_authority_pubkey_seeds.push(any_mint_token_account_authority_pubkey_bump_seed_.as_slice());
+        let any_mint_token_account_authority_signers_seeds = vec![any_mint_token_account_authority_pubkey_seeds.as_slice()];
+        let mut temporary_any_mint_token_account_authority_pubkey_seeds = temporary_any_mint_token_account_authority_address_resolver.get_seeds();
+        let temporary_any_mint_token_account_authority_pubkey_bump_seed_ = [temporary_any_mint_token_account_authority_pubkey_bump_seed];
+        temporary_any_mint_token_account_authority_pubkey_seeds.push(temporary_any_mint_token_account_authority_pubkey_bump_seed_.as_slice());
+        let temporary_any_mint_token_account_authority_signers_seeds = vec![temporary_any_mint_token_account_authority_pubkey_seeds.as_slice()];
+        // The venue pays into a temporary WSol token account when it needs the swap authority to own the destination.
+        let destination_token_account_account_info = match temporary_w_sol_token_account_account_info {
+            Some(temporary_w_sol_token_account_account_info_) => {
+                let w_sol_token_mint_account_info_ = w_sol_token_mint_account_info.ok_or(Error::InvalidAccountPubkey)?;
+                let temporary_w_sol_token_account_address_resolver = TemporaryWSolTokenAccountAddressResolver {
+                    w_sol_token_account_pubkey: w_sol_token_account_account_info.key,
+                };
+                let mut temporary_w_sol_token_account_pubkey_seeds = temporary_w_sol_token_account_address_resolver.get_seeds();
+                let temporary_w_sol_token_account_pubkey_bump_seed_ = [intermediary.temporary_w_sol_token_account_pubkey_bump_seed];
+                temporary_w_sol_token_account_pubkey_seeds.push(temporary_w_sol_token_account_pubkey_bump_seed_.as_slice());
+                solana_program::program::invoke_signed(
+                    &solana_program::system_instruction::create_account(
+                        intermediary_trader_account_info.key,
+                        temporary_w_sol_token_account_account_info_.key,
+                        token_account_rent_exemption_balance,
+                        <Account as Pack>::LEN as u64,
+                        token_program_account_info.key,
+                    ),
+                    vec![
+                        intermediary_trader_account_info.clone(),
+                        temporary_w_sol_token_account_account_info_.clone(),
+                    ]
+                    .as_slice(),
+                    [temporary_w_sol_token_account_pubkey_seeds.as_slice()].as_slice(),
+                )?;
+                solana_program::program::invoke(
+                    &spl_token::instruction::initialize_account(
+                        token_program_account_info.key,
+                        temporary_w_sol_token_account_account_info_.key,
+                        w_sol_token_mint_account_info_.key,
+                        any_mint_token_account_authority_account_info.key,
+                    )?,
+                    vec![
+                        temporary_w_sol_token_account_account_info_.clone(),
+                        w_sol_token_mint_account_info_.clone(),
+                        any_mint_token_account_authority_account_info.clone(),
+                        rent_account_info.clone(),
+                    ]
+                    .as_slice(),
+                )?;
+                temporary_w_sol_token_account_account_info_
+            }
+            None => w_sol_token_account_account_info,
+        };
+        let (choosed_any_mint_token_account_account_info, choosed_any_mint_token_account_authority_account_info, choosed_any_mint_token_account_authority_signers_seeds, base_amount_in) =
             if input_token_amount == any_mint_token_account.amount {
-                any_mint_token_account_account_info
+                (
+                    any_mint_token_account_account_info,
+                    any_mint_token_account_authority_account_info,
+                    &any_mint_token_account_authority_signers_seeds,
+                    input_token_amount,
+                )
             } else {
                 let mut temporary_any_mint_token_account_pubkey_seeds = temporary_any_mint_token_account_address_resolver.get_seeds();
                 let temporary_any_mint_token_account_pubkey_bump_seed_ = [temporary_any_mint_token_account_pubkey_bump_seed];
@@ -1415,143 +5447,225 @@ impl Processor {
                     &solana_program::system_instruction::create_account(
                         intermediary_trader_account_info.key,
                         temporary_any_mint_token_account_account_info.key,
-                        token_account_rent_exemption_balance,
-                        <Account as Pack>::LEN as u64,
-                        base_token_program_account_info.key,
+                        any_mint_token_account_rent_exemption_balance,
+                        any_mint_token_account_length as u64,
+                        any_mint_token_program_account_info.key,
                     ),
                     vec![
-                    intermediary_trader_account_info.clone(),
-                    temporary_any_mint_token_account_account_info.clone(),
-                ]
+                        intermediary_trader_account_info.clone(),
+                        temporary_any_mint_token_account_account_info.clone(),
+                    ]
                     .as_slice(),
                     [temporary_any_mint_token_account_pubkey_seeds.as_slice()].as_slice(),
                 )?;
                 solana_program::program::invoke(
-                    &spl_token::instruction::initialize_account(
-                        base_token_program_account_info.key,
+                    &spl_token_2022::instruction::initialize_account(
+                        any_mint_token_program_account_info.key,
                         temporary_any_mint_token_account_account_info.key,
                         any_mint_token_mint_account_info.key,
-                        intermediary_trader_account_info.key,
+                        temporary_any_mint_token_account_authority_account_info.key,
                     )?,
                     vec![
                         temporary_any_mint_token_account_account_info.clone(),
                         any_mint_token_mint_account_info.clone(),
-                        intermediary_trader_account_info.clone(),
+                        temporary_any_mint_token_account_authority_account_info.clone(),
                         rent_account_info.clone(),
                     ]
                     .as_slice(),
                 )?;
-                solana_program::program::invoke(
-                    &spl_token::instruction::transfer(
-                        base_token_program_account_info.key,
+                solana_program::program::invoke_signed(
+                    &spl_token_2022::instruction::transfer_checked(
+                        any_mint_token_program_account_info.key,
                         any_mint_token_account_account_info.key,
+                        any_mint_token_mint_account_info.key,
                         temporary_any_mint_token_account_account_info.key,
-                        intermediary_trader_account_info.key,
+                        any_mint_token_account_authority_account_info.key,
                         [].as_slice(),
                         input_token_amount,
+                        Self::get_any_mint_token_mint_decimals(any_mint_token_mint_account_info)?,
                     )?,
                     vec![
                         any_mint_token_account_account_info.clone(),
+                        any_mint_token_mint_account_info.clone(),
                         temporary_any_mint_token_account_account_info.clone(),
-                        intermediary_trader_account_info.clone(),
+                        any_mint_token_account_authority_account_info.clone(),
                     ]
                     .as_slice(),
+                    any_mint_token_account_authority_signers_seeds.as_slice(),
                 )?;
-                temporary_any_mint_token_account_account_info
-            };
-        let (minimum_output_token_amount_, is_need_to_check_minimum_otput_token_amount) = match minimum_output_token_amount {
-            Some(minimum_output_token_amount__) => {
-                (
-                    minimum_output_token_amount__,
-                    true,
-                )
-            }
-            None => {
                 (
-                    0,
-                    false,
+                    temporary_any_mint_token_account_account_info,
+                    temporary_any_mint_token_account_authority_account_info,
+                    &temporary_any_mint_token_account_authority_signers_seeds,
+                    Self::unpack_any_mint_token_account(temporary_any_mint_token_account_account_info)?.amount,
                 )
-            }
+            };
+        let destination_token_account_amount_before_swap = Account::unpack_unchecked(&destination_token_account_account_info.data.borrow())?.amount;
+        let venue_swap = VenueSwap {
+            side: Side::Sell,
+            source_token_account_account_info: choosed_any_mint_token_account_account_info,
+            destination_token_account_account_info,
+            authority_account_info: choosed_any_mint_token_account_authority_account_info,
+            quote_token_mint_account_info: w_sol_token_mint_account_info,
+            base_token_mint_account_info: any_mint_token_mint_account_info,
+            system_program_account_info,
+            token_program_account_info,
+            base_token_program_account_info: any_mint_token_program_account_info,
+            amount_in: base_amount_in,
+            minimum_amount_out: minimum_output_token_amount.unwrap_or(0),
         };
-        solana_program::program::invoke(
-            &crate::extern_source::create_pumpswap_sell_instruction(
-                pumpfan_program_id_account_info.key,
-                pool_account_info.key,
-                intermediary_trader_account_info.key,
-                global_config_account_info.key,
-                any_mint_token_mint_account_info.key,
-                w_sol_token_mint_account_info.key,
-                choosed_any_mint_token_account_account_info.key,
-                temporary_w_sol_token_account_account_info.key,
-                pool_base_token_account_account_info.key,
-                pool_quote_token_account_account_info.key,
-                protocol_fee_recipient_account_info.key,
-                protocol_fee_recipient_token_account_account_info.key,
-                base_token_program_account_info.key,
-                quote_token_program_account_info.key,
-                event_authority_account_info.key,
-                input_token_amount,
-                minimum_output_token_amount_,
-            ),
-            vec![
-                pool_account_info.clone(),
-                intermediary_trader_account_info.clone(),
-                global_config_account_info.clone(),
-                any_mint_token_mint_account_info.clone(),
-                w_sol_token_mint_account_info.clone(),
-                choosed_any_mint_token_account_account_info.clone(),
-                temporary_w_sol_token_account_account_info.clone(),
-                pool_base_token_account_account_info.clone(),
-                pool_quote_token_account_account_info.clone(),
-                protocol_fee_recipient_account_info.clone(),
-                protocol_fee_recipient_token_account_account_info.clone(),
-                base_token_program_account_info.clone(),
-                quote_token_program_account_info.clone(),
-                system_program_account_info.clone(),
-                associated_token_account_account_info.clone(),
-                event_authority_account_info.clone(),
-                pumpfan_program_id_account_info.clone(),
-            ]
-            .as_slice(),
+        let reserves = venue_adapter.get_reserves(&venue_swap)?;
+        let (swap_instruction, swap_account_infos) = if is_exact_out {
+            venue_adapter.create_exact_out_swap_instruction(&venue_swap)?
+        } else {
+            venue_adapter.create_swap_instruction(&venue_swap)?
+        };
+        let choosed_any_mint_token_account_authority_lamports_before_swap = choosed_any_mint_token_account_authority_account_info.lamports();
+        solana_program::program::invoke_signed(
+            &swap_instruction,
+            swap_account_infos.as_slice(),
+            choosed_any_mint_token_account_authority_signers_seeds.as_slice(),
         )?;
-        if is_need_to_check_minimum_otput_token_amount {
-            if Account::unpack_unchecked(&temporary_w_sol_token_account_account_info.data.borrow())?.amount < minimum_output_token_amount_ {
-                return Err(Error::TokenAccountInvalidAmount.into());
-            }
+        if V::IS_SETTLED_IN_LAMPORTS {
+            // The venue pays native SOL into the swap authority, which is wrapped into the destination right away.
+            let received_lamports = choosed_any_mint_token_account_authority_account_info
+                .lamports()
+                .checked_sub(choosed_any_mint_token_account_authority_lamports_before_swap)
+                .ok_or(Error::InvalidAccountLamports)?;
+            solana_program::program::invoke_signed(
+                &solana_program::system_instruction::transfer(
+                    choosed_any_mint_token_account_authority_account_info.key,
+                    destination_token_account_account_info.key,
+                    received_lamports,
+                ),
+                vec![
+                    choosed_any_mint_token_account_authority_account_info.clone(),
+                    destination_token_account_account_info.clone(),
+                ]
+                .as_slice(),
+                choosed_any_mint_token_account_authority_signers_seeds.as_slice(),
+            )?;
+            solana_program::program::invoke(
+                &spl_token::instruction::sync_native(
+                    token_program_account_info.key,
+                    destination_token_account_account_info.key,
+                )?,
+                vec![
+                    destination_token_account_account_info.clone(),
+                ]
+                .as_slice(),
+            )?;
         }
-        if Account::unpack_unchecked(&choosed_any_mint_token_account_account_info.data.borrow())?.amount != 0 {
+        // Measured before any account is closed into the treasury, so rent is not counted as proceeds.
+        let received_token_amount = Account::unpack_unchecked(&destination_token_account_account_info.data.borrow())?.amount - destination_token_account_amount_before_swap;
+        // An exact output sell may leave part of the input behind, which stays with the position.
+        let unspent_input_token_amount = Self::unpack_any_mint_token_account(choosed_any_mint_token_account_account_info)?.amount;
+        if unspent_input_token_amount != 0 && !is_exact_out {
             return Err(Error::TokenAccountInvalidAmount.into());
         }
-        solana_program::program::invoke(
-            &spl_token::instruction::close_account(
-                quote_token_program_account_info.key,
-                temporary_w_sol_token_account_account_info.key,
-                w_sol_token_account_account_info.key,
-                intermediary_trader_account_info.key,
-                [].as_slice(),
-            )?,
-            vec![
-                temporary_w_sol_token_account_account_info.clone(),
-                w_sol_token_account_account_info.clone(),
-                intermediary_trader_account_info.clone(),
-            ]
-            .as_slice(),
-        )?;
-        solana_program::program::invoke(
-            &spl_token::instruction::close_account(
-                base_token_program_account_info.key,
-                choosed_any_mint_token_account_account_info.key,
-                w_sol_token_account_account_info.key,
-                intermediary_trader_account_info.key,
-                [].as_slice(),
-            )?,
-            vec![
-                choosed_any_mint_token_account_account_info.clone(),
-                w_sol_token_account_account_info.clone(),
-                intermediary_trader_account_info.clone(),
-            ]
-            .as_slice(),
+        Self::enforce_price_deviation(
+            intermediary.maximum_price_deviation_basis_points,
+            reserves,
+            base_amount_in - unspent_input_token_amount,
+            received_token_amount,
         )?;
+        if let Some(minimum_output_token_amount_) = minimum_output_token_amount {
+            if received_token_amount < minimum_output_token_amount_ {
+                return Err(Error::TokenAccountInvalidAmount.into());
+            }
+        }
+        if let Some(temporary_w_sol_token_account_account_info_) = temporary_w_sol_token_account_account_info {
+            solana_program::program::invoke_signed(
+                &spl_token::instruction::close_account(
+                    token_program_account_info.key,
+                    temporary_w_sol_token_account_account_info_.key,
+                    w_sol_token_account_account_info.key,
+                    any_mint_token_account_authority_account_info.key,
+                    [].as_slice(),
+                )?,
+                vec![
+                    temporary_w_sol_token_account_account_info_.clone(),
+                    w_sol_token_account_account_info.clone(),
+                    any_mint_token_account_authority_account_info.clone(),
+                ]
+                .as_slice(),
+                any_mint_token_account_authority_signers_seeds.as_slice(),
+            )?;
+        }
+        if input_token_amount == any_mint_token_account.amount {
+            if unspent_input_token_amount == 0 {
+                Self::harvest_any_mint_withheld_tokens(
+                    any_mint_token_program_account_info,
+                    any_mint_token_mint_account_info,
+                    any_mint_token_account_account_info,
+                )?;
+                solana_program::program::invoke_signed(
+                    &spl_token_2022::instruction::close_account(
+                        any_mint_token_program_account_info.key,
+                        any_mint_token_account_account_info.key,
+                        w_sol_token_account_account_info.key,
+                        any_mint_token_account_authority_account_info.key,
+                        [].as_slice(),
+                    )?,
+                    vec![
+                        any_mint_token_account_account_info.clone(),
+                        w_sol_token_account_account_info.clone(),
+                        any_mint_token_account_authority_account_info.clone(),
+                    ]
+                    .as_slice(),
+                    any_mint_token_account_authority_signers_seeds.as_slice(),
+                )?;
+                Self::record_open_positions(
+                    intermediary_account_info,
+                    0,
+                    1,
+                )?;
+            }
+        } else {
+            if unspent_input_token_amount != 0 {
+                solana_program::program::invoke_signed(
+                    &spl_token_2022::instruction::transfer_checked(
+                        any_mint_token_program_account_info.key,
+                        temporary_any_mint_token_account_account_info.key,
+                        any_mint_token_mint_account_info.key,
+                        any_mint_token_account_account_info.key,
+                        temporary_any_mint_token_account_authority_account_info.key,
+                        [].as_slice(),
+                        unspent_input_token_amount,
+                        Self::get_any_mint_token_mint_decimals(any_mint_token_mint_account_info)?,
+                    )?,
+                    vec![
+                        temporary_any_mint_token_account_account_info.clone(),
+                        any_mint_token_mint_account_info.clone(),
+                        any_mint_token_account_account_info.clone(),
+                        temporary_any_mint_token_account_authority_account_info.clone(),
+                    ]
+                    .as_slice(),
+                    temporary_any_mint_token_account_authority_signers_seeds.as_slice(),
+                )?;
+            }
+            Self::harvest_any_mint_withheld_tokens(
+                any_mint_token_program_account_info,
+                any_mint_token_mint_account_info,
+                temporary_any_mint_token_account_account_info,
+            )?;
+            solana_program::program::invoke_signed(
+                &spl_token_2022::instruction::close_account(
+                    any_mint_token_program_account_info.key,
+                    temporary_any_mint_token_account_account_info.key,
+                    w_sol_token_account_account_info.key,
+                    temporary_any_mint_token_account_authority_account_info.key,
+                    [].as_slice(),
+                )?,
+                vec![
+                    temporary_any_mint_token_account_account_info.clone(),
+                    w_sol_token_account_account_info.clone(),
+                    temporary_any_mint_token_account_authority_account_info.clone(),
+                ]
+                .as_slice(),
+                temporary_any_mint_token_account_authority_signers_seeds.as_slice(),
+            )?;
+        }
         solana_program::program::invoke(
             &spl_token::instruction::sync_native(
                 token_program_account_info.key,
@@ -1562,100 +5676,283 @@ impl Processor {
             ]
             .as_slice(),
         )?;
+        let w_sol_token_account_amount_after_swap = Account::unpack_unchecked(&w_sol_token_account_account_info.data.borrow())?.amount;
+        Event::Traded {
+            intermediary_pubkey: *intermediary_account_info.key,
+            venue: V::VENUE,
+            side: Side::Sell,
+            mint_pubkey: *any_mint_token_mint_account_info.key,
+            amount_in: input_token_amount - unspent_input_token_amount,
+            amount_out: received_token_amount,
+            treasury_amount: w_sol_token_account_amount_after_swap,
+        }
+        .emit()?;
+        Self::record_trader_seat_trade(
+            trader_seat_account_info,
+            0,
+            received_token_amount,
+        )?;
         Ok(())
     }
-    fn change_manager<'a>(_program_id: &'a Pubkey, accounts: &'a [AccountInfo]) -> ProgramResult {
-        let account_info_iter = &mut accounts.iter();
-        let intermediary_investor_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
-        let intermediary_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
-        let intermediary_manager_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
-        let system_program_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
-        let mut account_differentiator = HashSet::<&'_ Pubkey>::with_capacity(4);
-        if !account_differentiator.insert(intermediary_investor_account_info.key)
-            || !account_differentiator.insert(intermediary_account_info.key)
-            || !account_differentiator.insert(intermediary_manager_account_info.key)
-            || !account_differentiator.insert(system_program_account_info.key)
-            || *system_program_account_info.key != solana_program::system_program::ID
-        {
+    // The realized output may fall short of the constant product output of the reserves read before the swap by the tolerance at most, venue fees included.
+    fn enforce_price_deviation(maximum_price_deviation_basis_points: u16, reserves: (u64, u64), amount_in: u64, amount_out: u64) -> ProgramResult {
+        if maximum_price_deviation_basis_points == 0 || amount_in == 0 {
+            return Ok(());
+        }
+        let (input_reserve, output_reserve) = reserves;
+        let expected_amount_out = (output_reserve as u128) * (amount_in as u128) / ((input_reserve as u128) + (amount_in as u128));
+        if (amount_out as u128) * 10_000 < expected_amount_out * ((10_000 - maximum_price_deviation_basis_points) as u128) {
+            return Err(Error::PriceDeviationExceeded.into());
+        }
+        Ok(())
+    }
+    fn enforce_allowed_mint<'a>(
+        intermediary_account_info: &'a AccountInfo,
+        allowed_mints_account_info: &'a AccountInfo,
+        any_mint_token_mint_pubkey: &'a Pubkey,
+    ) -> ProgramResult {
+        if allowed_mints_account_info.data_is_empty() {
+            return Err(Error::AllowedMintsDoesNotContainMint.into());
+        }
+        if *allowed_mints_account_info.owner != PROGRAM_PUBKEY {
+            return Err(Error::InvalidAccountOwner.into());
+        }
+        let allowed_mints = borsh::from_slice::<AllowedMints>(&allowed_mints_account_info.data.borrow())?;
+        if !allowed_mints.is_initialized {
+            return Err(Error::AllowedMintsIsNotInitialized.into());
+        }
+        let allowed_mints_address_resolver = AllowedMintsAddressResolver {
+            intermediary_pubkey: intermediary_account_info.key,
+        };
+        if *allowed_mints_account_info.key != allowed_mints_address_resolver.create(allowed_mints.allowed_mints_pubkey_bump_seed)? {
             return Err(Error::InvalidAccountPubkey.into());
         }
-        if !intermediary_investor_account_info.is_signer || !intermediary_account_info.is_writable {
-            return Err(Error::InvalidAccountConfigurationFlags.into());
+        if !allowed_mints.mint_pubkeys.contains(any_mint_token_mint_pubkey) {
+            return Err(Error::AllowedMintsDoesNotContainMint.into());
         }
-        if intermediary_manager_account_info.owner != system_program_account_info.key {
+        Ok(())
+    }
+    fn enforce_risk_limits<'a>(
+        intermediary_account_info: &'a AccountInfo,
+        risk_limits_account_info: &'a AccountInfo,
+        lamports_from_treasury: u64,
+        treasury_lamports: u64,
+    ) -> ProgramResult {
+        let risk_limits_address_resolver = RiskLimitsAddressResolver {
+            intermediary_pubkey: intermediary_account_info.key,
+        };
+        // Until the manager sets risk limits, the trades are not limited.
+        if risk_limits_account_info.data_is_empty() {
+            if *risk_limits_account_info.key != risk_limits_address_resolver.find().0 {
+                return Err(Error::InvalidAccountPubkey.into());
+            }
+            return Ok(());
+        }
+        if *risk_limits_account_info.owner != PROGRAM_PUBKEY {
             return Err(Error::InvalidAccountOwner.into());
         }
-        if !intermediary_manager_account_info.data_is_empty() {
-            return Err(Error::InvalidAccountData.into());
+        let mut risk_limits = borsh::from_slice::<RiskLimits>(&risk_limits_account_info.data.borrow())?;
+        if !risk_limits.is_initialized {
+            return Err(Error::RiskLimitsIsNotInitialized.into());
         }
-        if intermediary_investor_account_info.lamports() < FEE_CHANGE_MANAGER || intermediary_manager_account_info.lamports() == 0 {
-            return Err(Error::InvalidAccountLamports.into());
+        if *risk_limits_account_info.key != risk_limits_address_resolver.create(risk_limits.risk_limits_pubkey_bump_seed)? {
+            return Err(Error::InvalidAccountPubkey.into());
         }
-        let mut intermediary = borsh::from_slice::<Intermediary>(&intermediary_account_info.data.borrow())?;
-        if !intermediary.is_initialized {
-            return Err(Error::IntermediaryIsNotInitialized.into());
+        if lamports_from_treasury > risk_limits.maximum_lamports_per_trade {
+            return Err(Error::RiskLimitsMaximumLamportsPerTradeExceeded.into());
         }
-        if *intermediary_investor_account_info.key != intermediary.investor_pubkey {
-            return Err(Error::IntermediaryInvalidInvestor.into());
+        if (lamports_from_treasury as u128) * 10_000 > (treasury_lamports as u128) * (risk_limits.maximum_treasury_share_per_trade_basis_points as u128) {
+            return Err(Error::RiskLimitsMaximumTreasurySharePerTradeExceeded.into());
         }
-        if *intermediary_manager_account_info.key == intermediary.investor_pubkey
-            || *intermediary_manager_account_info.key == intermediary.manager_pubkey
-            || *intermediary_manager_account_info.key == intermediary.trader_pubkey
-        {
-            return Err(Error::IntermediaryInvalidManager.into());
+        let current_slot = Clock::get()?.slot;
+        if current_slot >= risk_limits.slot_window_start_slot.saturating_add(risk_limits.slot_window_length) {
+            risk_limits.slot_window_start_slot = current_slot;
+            risk_limits.slot_window_trades_quantity = 0;
+        }
+        if risk_limits.slot_window_trades_quantity >= risk_limits.maximum_trades_per_slot_window {
+            return Err(Error::RiskLimitsMaximumTradesPerSlotWindowExceeded.into());
         }
-        intermediary.manager_pubkey = *intermediary_manager_account_info.key;
+        risk_limits.slot_window_trades_quantity += 1;
         borsh::to_writer(
-            &mut intermediary_account_info.data.borrow_mut()[..],
-            &intermediary,
+            &mut risk_limits_account_info.data.borrow_mut()[..],
+            &risk_limits,
         )?;
         Ok(())
     }
-    fn change_trader<'a>(_program_id: &'a Pubkey, accounts: &'a [AccountInfo]) -> ProgramResult {
-        let account_info_iter = &mut accounts.iter();
-        let intermediary_manager_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
-        let intermediary_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
-        let intermediary_trader_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
-        let system_program_account_info = solana_program::account_info::next_account_info(account_info_iter)?;
-        let mut account_differentiator = HashSet::<&'_ Pubkey>::with_capacity(4);
-        if !account_differentiator.insert(intermediary_manager_account_info.key)
-            || !account_differentiator.insert(intermediary_account_info.key)
-            || !account_differentiator.insert(intermediary_trader_account_info.key)
-            || !account_differentiator.insert(system_program_account_info.key)
-            || *system_program_account_info.key != solana_program::system_program::ID
-        {
-            return Err(Error::InvalidAccountPubkey.into());
-        }
-        if !intermediary_manager_account_info.is_signer || !intermediary_account_info.is_writable {
-            return Err(Error::InvalidAccountConfigurationFlags.into());
+    fn enforce_trader_seat<'a>(
+        intermediary_account_info: &'a AccountInfo,
+        trader_seat_account_info: &'a AccountInfo,
+        trader_pubkey: &'a Pubkey,
+        lamports_from_treasury: u64,
+    ) -> ProgramResult {
+        if trader_seat_account_info.data_is_empty() {
+            return Err(Error::TraderSeatIsNotInitialized.into());
         }
-        if intermediary_trader_account_info.owner != system_program_account_info.key {
+        if *trader_seat_account_info.owner != PROGRAM_PUBKEY {
             return Err(Error::InvalidAccountOwner.into());
         }
-        if !intermediary_trader_account_info.data_is_empty() {
-            return Err(Error::InvalidAccountData.into());
+        let trader_seat = borsh::from_slice::<TraderSeat>(&trader_seat_account_info.data.borrow())?;
+        if !trader_seat.is_initialized {
+            return Err(Error::TraderSeatIsNotInitialized.into());
         }
-        if intermediary_manager_account_info.lamports() < FEE_CHANGE_TRADER || intermediary_trader_account_info.lamports() == 0 {
-            return Err(Error::InvalidAccountLamports.into());
+        let trader_seat_address_resolver = TraderSeatAddressResolver {
+            intermediary_pubkey: intermediary_account_info.key,
+            trader_pubkey,
+        };
+        if *trader_seat_account_info.key != trader_seat_address_resolver.create(trader_seat.trader_seat_pubkey_bump_seed)? {
+            return Err(Error::InvalidAccountPubkey.into());
         }
-        let mut intermediary = borsh::from_slice::<Intermediary>(&intermediary_account_info.data.borrow())?;
-        if !intermediary.is_initialized {
-            return Err(Error::IntermediaryIsNotInitialized.into());
+        if trader_seat.intermediary_pubkey != *intermediary_account_info.key || trader_seat.trader_pubkey != *trader_pubkey {
+            return Err(Error::TraderSeatInvalidTrader.into());
         }
-        if *intermediary_manager_account_info.key != intermediary.manager_pubkey {
-            return Err(Error::IntermediaryInvalidManager.into());
+        if (trader_seat.spent_lamports as u128) + (lamports_from_treasury as u128) > (trader_seat.allocated_lamports as u128) + (trader_seat.received_lamports as u128) {
+            return Err(Error::TraderSeatAllocationExceeded.into());
         }
-        if *intermediary_trader_account_info.key == intermediary.investor_pubkey
-            || *intermediary_trader_account_info.key == intermediary.manager_pubkey
-            || *intermediary_trader_account_info.key == intermediary.trader_pubkey
-        {
-            return Err(Error::IntermediaryInvalidTrader.into());
+        Ok(())
+    }
+    fn record_trader_seat_trade<'a>(trader_seat_account_info: &'a AccountInfo, spent_lamports: u64, received_lamports: u64) -> ProgramResult {
+        let mut trader_seat = borsh::from_slice::<TraderSeat>(&trader_seat_account_info.data.borrow())?;
+        trader_seat.spent_lamports = trader_seat.spent_lamports.saturating_add(spent_lamports);
+        trader_seat.received_lamports = trader_seat.received_lamports.saturating_add(received_lamports);
+        trader_seat.trades_quantity += 1;
+        borsh::to_writer(
+            &mut trader_seat_account_info.data.borrow_mut()[..],
+            &trader_seat,
+        )?;
+        Ok(())
+    }
+    // Positions opened before the counter existed may be closed after it, hence the saturation.
+    fn record_open_positions<'a>(intermediary_account_info: &'a AccountInfo, opened_positions_quantity: u32, closed_positions_quantity: u32) -> ProgramResult {
+        if opened_positions_quantity == 0 && closed_positions_quantity == 0 {
+            return Ok(());
+        }
+        let mut intermediary = Intermediary::load(&intermediary_account_info.data.borrow())?;
+        if intermediary.version != Intermediary::VERSION {
+            return Err(Error::IntermediaryIsNotMigrated.into());
         }
-        intermediary.trader_pubkey = *intermediary_trader_account_info.key;
+        intermediary.open_positions_quantity = intermediary.open_positions_quantity.saturating_add(opened_positions_quantity).saturating_sub(closed_positions_quantity);
         borsh::to_writer(
             &mut intermediary_account_info.data.borrow_mut()[..],
             &intermediary,
         )?;
         Ok(())
     }
+    fn enforce_any_mint_token_program<'a>(any_mint_token_mint_account_info: &'a AccountInfo, any_mint_token_program_account_info: &'a AccountInfo) -> ProgramResult {
+        if *any_mint_token_program_account_info.key != spl_token::ID && *any_mint_token_program_account_info.key != spl_token_2022::ID {
+            return Err(Error::InvalidAccountPubkey.into());
+        }
+        if any_mint_token_mint_account_info.owner != any_mint_token_program_account_info.key {
+            return Err(Error::InvalidAccountOwner.into());
+        }
+        Ok(())
+    }
+    fn get_any_mint_token_account_length<'a>(any_mint_token_mint_account_info: &'a AccountInfo) -> Result<usize, ProgramError> {
+        if *any_mint_token_mint_account_info.owner != spl_token_2022::ID {
+            return Ok(<Account as Pack>::LEN);
+        }
+        let any_mint_token_mint_data = any_mint_token_mint_account_info.data.borrow();
+        let any_mint_token_mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&any_mint_token_mint_data)?;
+        ExtensionType::try_calculate_account_len::<spl_token_2022::state::Account>(
+            ExtensionType::get_required_init_account_extensions(any_mint_token_mint.get_extension_types()?.as_slice()).as_slice(),
+        )
+    }
+    fn get_any_mint_token_mint_decimals<'a>(any_mint_token_mint_account_info: &'a AccountInfo) -> Result<u8, ProgramError> {
+        Ok(StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&any_mint_token_mint_account_info.data.borrow())?.base.decimals)
+    }
+    fn unpack_any_mint_token_account<'a>(any_mint_token_account_account_info: &'a AccountInfo) -> Result<spl_token_2022::state::Account, ProgramError> {
+        Ok(StateWithExtensions::<spl_token_2022::state::Account>::unpack(&any_mint_token_account_account_info.data.borrow())?.base)
+    }
+    // The fee the token program withholds in the destination account on a transfer of `amount`.
+    fn calculate_any_mint_transfer_fee<'a>(any_mint_token_mint_account_info: &'a AccountInfo, amount: u64) -> Result<u64, ProgramError> {
+        if *any_mint_token_mint_account_info.owner != spl_token_2022::ID {
+            return Ok(0);
+        }
+        let any_mint_token_mint_data = any_mint_token_mint_account_info.data.borrow();
+        let any_mint_token_mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&any_mint_token_mint_data)?;
+        match any_mint_token_mint.get_extension::<TransferFeeConfig>() {
+            Ok(transfer_fee_config) => {
+                match transfer_fee_config.calculate_epoch_fee(
+                    Clock::get()?.epoch,
+                    amount,
+                ) {
+                    Some(transfer_fee) => Ok(transfer_fee),
+                    None => Err(Error::TokenAccountInvalidAmount.into()),
+                }
+            }
+            Err(_) => Ok(0),
+        }
+    }
+    // Token-2022 refuses to close an account that still holds withheld transfer fees.
+    fn harvest_any_mint_withheld_tokens<'a, 'b>(
+        any_mint_token_program_account_info: &'a AccountInfo<'b>,
+        any_mint_token_mint_account_info: &'a AccountInfo<'b>,
+        token_account_account_info: &'a AccountInfo<'b>,
+    ) -> ProgramResult {
+        if *any_mint_token_program_account_info.key != spl_token_2022::ID {
+            return Ok(());
+        }
+        let withheld_amount = match StateWithExtensions::<spl_token_2022::state::Account>::unpack(&token_account_account_info.data.borrow())?.get_extension::<TransferFeeAmount>() {
+            Ok(transfer_fee_amount) => u64::from(transfer_fee_amount.withheld_amount),
+            Err(_) => 0,
+        };
+        if withheld_amount == 0 {
+            return Ok(());
+        }
+        solana_program::program::invoke(
+            &spl_token_2022::extension::transfer_fee::instruction::harvest_withheld_tokens_to_mint(
+                any_mint_token_program_account_info.key,
+                any_mint_token_mint_account_info.key,
+                [token_account_account_info.key].as_slice(),
+            )?,
+            vec![
+                any_mint_token_mint_account_info.clone(),
+                token_account_account_info.clone(),
+            ]
+            .as_slice(),
+        )?;
+        Ok(())
+    }
+}
+#[derive(Clone, Copy)]
+struct VenueBuyAccounts<'a, 'b> {
+    intermediary_trader_account_info: &'a AccountInfo<'b>,
+    intermediary_account_info: &'a AccountInfo<'b>,
+    w_sol_token_account_account_info: &'a AccountInfo<'b>,
+    w_sol_token_account_authority_account_info: &'a AccountInfo<'b>,
+    temporary_w_sol_token_account_account_info: &'a AccountInfo<'b>,
+    temporary_w_sol_token_account_authority_account_info: &'a AccountInfo<'b>,
+    any_mint_token_account_account_info: &'a AccountInfo<'b>,
+    any_mint_token_account_authority_account_info: &'a AccountInfo<'b>,
+    w_sol_token_mint_account_info: &'a AccountInfo<'b>,
+    any_mint_token_mint_account_info: &'a AccountInfo<'b>,
+    system_program_account_info: &'a AccountInfo<'b>,
+    rent_account_info: &'a AccountInfo<'b>,
+    token_program_account_info: &'a AccountInfo<'b>,
+    any_mint_token_program_account_info: &'a AccountInfo<'b>,
+    allowed_mints_account_info: &'a AccountInfo<'b>,
+    risk_limits_account_info: &'a AccountInfo<'b>,
+    trader_seat_account_info: &'a AccountInfo<'b>,
+    config_account_info: &'a AccountInfo<'b>,
+    fee_vault_account_info: &'a AccountInfo<'b>,
+}
+#[derive(Clone, Copy)]
+struct VenueSellAccounts<'a, 'b> {
+    intermediary_trader_account_info: &'a AccountInfo<'b>,
+    intermediary_account_info: &'a AccountInfo<'b>,
+    w_sol_token_account_account_info: &'a AccountInfo<'b>,
+    temporary_w_sol_token_account_account_info: Option<&'a AccountInfo<'b>>,
+    any_mint_token_account_account_info: &'a AccountInfo<'b>,
+    any_mint_token_account_authority_account_info: &'a AccountInfo<'b>,
+    temporary_any_mint_token_account_account_info: &'a AccountInfo<'b>,
+    temporary_any_mint_token_account_authority_account_info: &'a AccountInfo<'b>,
+    w_sol_token_mint_account_info: Option<&'a AccountInfo<'b>>,
+    any_mint_token_mint_account_info: &'a AccountInfo<'b>,
+    system_program_account_info: &'a AccountInfo<'b>,
+    rent_account_info: &'a AccountInfo<'b>,
+    token_program_account_info: &'a AccountInfo<'b>,
+    any_mint_token_program_account_info: &'a AccountInfo<'b>,
+    risk_limits_account_info: &'a AccountInfo<'b>,
+    trader_seat_account_info: &'a AccountInfo<'b>,
+    config_account_info: &'a AccountInfo<'b>,
+    fee_vault_account_info: &'a AccountInfo<'b>,
 }
@@ -2,6 +2,7 @@ use solana_program::pubkey::Pubkey;
 #[repr(C)]
 #[derive(borsh::BorshDeserialize, borsh::BorshSerialize, borsh::BorshSchema)]
 pub struct Intermediary {
+    pub version: u8,
     pub is_initialized: bool,
     pub investor_pubkey: Pubkey,
     pub manager_pubkey: Pubkey,
@@ -13,4 +14,139 @@ pub struct Intermediary {
     pub w_sol_token_account_authority_pubkey_bump_seed: u8,
     pub temporary_w_sol_token_account_pubkey_bump_seed: u8,
     pub temporary_w_sol_token_account_authority_pubkey_bump_seed: u8,
+    pub pending_manager_pubkey: Pubkey,
+    pub pending_trader_pubkey: Pubkey,
+    pub is_paused: bool,
+    // Carved out of the padding, zero disables the price sanity check.
+    pub maximum_price_deviation_basis_points: u16,
+    // Carved out of the padding, counts the any mint token accounts owned by the intermediary and the trader seats,
+    // so the intermediary can not be closed while any of them is open. Those opened before the counters existed are not counted.
+    pub open_positions_quantity: u32,
+    pub trader_seats_quantity: u32,
+    pub padding: [u8; Intermediary::PADDING_LENGTH],
+}
+impl Intermediary {
+    pub const VERSION: u8 = 1;
+    pub const PADDING_LENGTH: usize = 118;
+    // Reads the current layout and the unversioned one that preceded it. The unversioned
+    // layout has no padding, so the account data length alone tells them apart.
+    pub fn load<'a>(data: &'a [u8]) -> Result<Self, std::io::Error> {
+        match borsh::from_slice::<Self>(data) {
+            Ok(intermediary) => Ok(intermediary),
+            Err(error) => {
+                if let Ok(intermediary_v0) = borsh::from_slice::<IntermediaryV0>(data) {
+                    return Ok(Self::from(intermediary_v0));
+                }
+                Err(error)
+            }
+        }
+    }
+}
+// The original layout.
+#[repr(C)]
+#[derive(borsh::BorshDeserialize, borsh::BorshSerialize, borsh::BorshSchema)]
+pub struct IntermediaryV0 {
+    pub is_initialized: bool,
+    pub investor_pubkey: Pubkey,
+    pub manager_pubkey: Pubkey,
+    pub trader_pubkey: Pubkey,
+    pub w_sol_token_account_pubkey: Pubkey,
+    pub w_sol_token_account_authority_pubkey: Pubkey,
+    pub temporary_w_sol_token_account_pubkey: Pubkey,
+    pub temporary_w_sol_token_account_authority_pubkey: Pubkey,
+    pub w_sol_token_account_authority_pubkey_bump_seed: u8,
+    pub temporary_w_sol_token_account_pubkey_bump_seed: u8,
+    pub temporary_w_sol_token_account_authority_pubkey_bump_seed: u8,
+}
+impl From<IntermediaryV0> for Intermediary {
+    fn from(intermediary_v0: IntermediaryV0) -> Self {
+        Self {
+            version: 0,
+            is_initialized: intermediary_v0.is_initialized,
+            investor_pubkey: intermediary_v0.investor_pubkey,
+            manager_pubkey: intermediary_v0.manager_pubkey,
+            trader_pubkey: intermediary_v0.trader_pubkey,
+            w_sol_token_account_pubkey: intermediary_v0.w_sol_token_account_pubkey,
+            w_sol_token_account_authority_pubkey: intermediary_v0.w_sol_token_account_authority_pubkey,
+            temporary_w_sol_token_account_pubkey: intermediary_v0.temporary_w_sol_token_account_pubkey,
+            temporary_w_sol_token_account_authority_pubkey: intermediary_v0.temporary_w_sol_token_account_authority_pubkey,
+            w_sol_token_account_authority_pubkey_bump_seed: intermediary_v0.w_sol_token_account_authority_pubkey_bump_seed,
+            temporary_w_sol_token_account_pubkey_bump_seed: intermediary_v0.temporary_w_sol_token_account_pubkey_bump_seed,
+            temporary_w_sol_token_account_authority_pubkey_bump_seed: intermediary_v0.temporary_w_sol_token_account_authority_pubkey_bump_seed,
+            pending_manager_pubkey: Pubkey::default(),
+            pending_trader_pubkey: Pubkey::default(),
+            is_paused: false,
+            maximum_price_deviation_basis_points: 0,
+            open_positions_quantity: 0,
+            trader_seats_quantity: 0,
+            padding: [0; Intermediary::PADDING_LENGTH],
+        }
+    }
+}
+#[repr(C)]
+#[derive(borsh::BorshDeserialize, borsh::BorshSerialize, borsh::BorshSchema)]
+pub struct Config {
+    pub is_initialized: bool,
+    pub administrator_pubkey: Pubkey,
+    pub fee_recipient_pubkey: Pubkey,
+    pub fee_vault_pubkey: Pubkey,
+    pub config_pubkey_bump_seed: u8,
+    pub fee_vault_pubkey_bump_seed: u8,
+    pub fees: Fees,
+}
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, borsh::BorshDeserialize, borsh::BorshSerialize, borsh::BorshSchema)]
+pub struct Fees {
+    pub initialize: u64,
+    pub deposit_funds: u64,
+    pub withdraw_funds: u64,
+    pub buy_on_raydium: u64,
+    pub sell_on_raydium: u64,
+    pub buy_on_pumpswap: u64,
+    pub sell_on_pumpswap: u64,
+    pub change_manager: u64,
+    pub change_trader: u64,
+    pub buy_on_raydium_cpmm: u64,
+    pub sell_on_raydium_cpmm: u64,
+    pub buy_on_raydium_clmm: u64,
+    pub sell_on_raydium_clmm: u64,
+    pub buy_on_meteora_dlmm: u64,
+    pub sell_on_meteora_dlmm: u64,
+    pub buy_on_meteora_dynamic_amm: u64,
+    pub sell_on_meteora_dynamic_amm: u64,
+    pub buy_on_pumpfun_curve: u64,
+    pub sell_on_pumpfun_curve: u64,
+}
+#[repr(C)]
+#[derive(borsh::BorshDeserialize, borsh::BorshSerialize, borsh::BorshSchema)]
+pub struct RiskLimits {
+    pub is_initialized: bool,
+    pub intermediary_pubkey: Pubkey,
+    pub maximum_lamports_per_trade: u64,
+    pub maximum_treasury_share_per_trade_basis_points: u16,
+    pub maximum_trades_per_slot_window: u64,
+    pub slot_window_length: u64,
+    pub slot_window_start_slot: u64,
+    pub slot_window_trades_quantity: u64,
+    pub risk_limits_pubkey_bump_seed: u8,
+}
+#[repr(C)]
+#[derive(borsh::BorshDeserialize, borsh::BorshSerialize, borsh::BorshSchema)]
+pub struct AllowedMints {
+    pub is_initialized: bool,
+    pub intermediary_pubkey: Pubkey,
+    pub allowed_mints_pubkey_bump_seed: u8,
+    pub mint_pubkeys: Vec<Pubkey>,
+}
+#[repr(C)]
+#[derive(borsh::BorshDeserialize, borsh::BorshSerialize, borsh::BorshSchema)]
+pub struct TraderSeat {
+    pub is_initialized: bool,
+    pub intermediary_pubkey: Pubkey,
+    pub trader_pubkey: Pubkey,
+    pub allocated_lamports: u64,
+    pub spent_lamports: u64,
+    pub received_lamports: u64,
+    pub trades_quantity: u64,
+    pub trader_seat_pubkey_bump_seed: u8,
 }
@@ -0,0 +1,1439 @@
+use {
+    crate::{
+        error::Error,
+        event::{
+            Side,
+            Venue,
+        },
+        state::Fees,
+        METEORA_DLMM_PROGRAM_PUBKEY,
+        METEORA_DYNAMIC_AMM_PROGRAM_PUBKEY,
+        METEORA_VAULT_PROGRAM_PUBKEY,
+        PUMPFUN_PROGRAM_PUBKEY,
+        PUMPSWAP_PROGRAM_PUBKEY,
+        RAYDIUM_CLMM_PROGRAM_PUBKEY,
+        RAYDIUM_CPMM_PROGRAM_PUBKEY,
+        RAYDIUM_LIQUIDITY_POOL_V4_PROGRAM_PUBKEY,
+        SPL_ASSOCIATED_TOKEN_ACCOUNT_ID,
+        SPL_MEMO_PROGRAM_PUBKEY,
+    },
+    solana_program::{
+        account_info::AccountInfo,
+        instruction::{
+            AccountMeta,
+            Instruction,
+        },
+        program_error::ProgramError,
+        program_pack::Pack,
+        pubkey::Pubkey,
+    },
+    spl_token_2022::extension::StateWithExtensions,
+};
+// The intermediary side of a single venue swap: what to swap, from which token account, into which one.
+// Buy swaps the quote token (WSol on a direct trade) into the base token, sell swaps the base token back.
+pub struct VenueSwap<'a, 'b> {
+    pub side: Side,
+    pub source_token_account_account_info: &'a AccountInfo<'b>,
+    pub destination_token_account_account_info: &'a AccountInfo<'b>,
+    pub authority_account_info: &'a AccountInfo<'b>,
+    pub quote_token_mint_account_info: Option<&'a AccountInfo<'b>>,
+    pub base_token_mint_account_info: &'a AccountInfo<'b>,
+    pub system_program_account_info: &'a AccountInfo<'b>,
+    pub token_program_account_info: &'a AccountInfo<'b>,
+    pub base_token_program_account_info: &'a AccountInfo<'b>,
+    pub amount_in: u64,
+    pub minimum_amount_out: u64,
+}
+pub trait VenueAdapter<'a, 'b>: Sized {
+    const VENUE: Venue;
+    // Writability of the venue accounts, in the order they follow the intermediary accounts.
+    const ACCOUNTS_WRITABILITY: &'static [bool];
+    // The venue takes and pays native SOL through the swap authority instead of WSol token accounts.
+    const IS_SETTLED_IN_LAMPORTS: bool = false;
+    // The venue buys exactly `minimum_amount_out`, so a transfer fee on the base token comes out of that amount.
+    const IS_BUYING_EXACT_OUT: bool = false;
+    fn load(venue_account_infos: &[&'a AccountInfo<'b>]) -> Result<Self, ProgramError>;
+    fn get_fee(fees: &Fees, side: Side) -> u64;
+    // On buy the venue may take less than `amount_in` but must deliver at least `minimum_amount_out`.
+    fn create_swap_instruction(&self, venue_swap: &VenueSwap<'a, 'b>) -> Result<(Instruction, Vec<AccountInfo<'b>>), ProgramError>;
+    // The venue takes at most `amount_in` and delivers exactly `minimum_amount_out`.
+    fn create_exact_out_swap_instruction(&self, _venue_swap: &VenueSwap<'a, 'b>) -> Result<(Instruction, Vec<AccountInfo<'b>>), ProgramError> {
+        Err(Error::VenueIsNotSupported.into())
+    }
+    // Input and output reserves of the pool, read before the swap. The venue itself rejects foreign pool token accounts, so a swap that goes through was priced against these.
+    fn get_reserves(&self, venue_swap: &VenueSwap<'a, 'b>) -> Result<(u64, u64), ProgramError>;
+}
+pub fn get_accounts_writability(venue: Venue) -> Result<&'static [bool], Error> {
+    match venue {
+        Venue::Raydium => Ok(<RaydiumAdapter as VenueAdapter>::ACCOUNTS_WRITABILITY),
+        Venue::Pumpswap => Ok(<PumpswapAdapter as VenueAdapter>::ACCOUNTS_WRITABILITY),
+        Venue::RaydiumCpmm => Ok(<RaydiumCpmmAdapter as VenueAdapter>::ACCOUNTS_WRITABILITY),
+        Venue::RaydiumClmm => Ok(<RaydiumClmmAdapter as VenueAdapter>::ACCOUNTS_WRITABILITY),
+        Venue::MeteoraDlmm => Ok(<MeteoraDlmmAdapter as VenueAdapter>::ACCOUNTS_WRITABILITY),
+        Venue::MeteoraDynamicAmm => Ok(<MeteoraDynamicAmmAdapter as VenueAdapter>::ACCOUNTS_WRITABILITY),
+        Venue::PumpfunCurve => Ok(<PumpfunCurveAdapter as VenueAdapter>::ACCOUNTS_WRITABILITY),
+    }
+}
+pub fn create_account_metas<'a>(venue: Venue, venue_account_pubkeys: &'a [Pubkey]) -> Result<Vec<AccountMeta>, Error> {
+    let accounts_writability = get_accounts_writability(venue)?;
+    if venue_account_pubkeys.len() != accounts_writability.len() {
+        return Err(Error::InvalidAccountPubkey);
+    }
+    Ok(
+        venue_account_pubkeys
+            .iter()
+            .zip(accounts_writability.iter())
+            .map(
+                |(venue_account_pubkey, is_writable)| {
+                    if *is_writable {
+                        AccountMeta::new(*venue_account_pubkey, false)
+                    } else {
+                        AccountMeta::new_readonly(*venue_account_pubkey, false)
+                    }
+                },
+            )
+            .collect(),
+    )
+}
+fn unpack_pool_token_account<'a, 'b>(pool_token_account_account_info: &'a AccountInfo<'b>) -> Result<spl_token_2022::state::Account, ProgramError> {
+    if *pool_token_account_account_info.owner != spl_token::ID && *pool_token_account_account_info.owner != spl_token_2022::ID {
+        return Err(Error::InvalidAccountOwner.into());
+    }
+    Ok(StateWithExtensions::<spl_token_2022::state::Account>::unpack(&pool_token_account_account_info.data.borrow())?.base)
+}
+fn order_reserves<T>(side: Side, base_reserve: T, quote_reserve: T) -> (T, T) {
+    match side {
+        Side::Buy => (quote_reserve, base_reserve),
+        Side::Sell => (base_reserve, quote_reserve),
+    }
+}
+// Reads `N` bytes at `offset` of an account owned by `pool_program_pubkey`.
+fn read_pool_data<'a, 'b, const N: usize>(pool_account_info: &'a AccountInfo<'b>, pool_program_pubkey: &'a Pubkey, offset: usize) -> Result<[u8; N], ProgramError> {
+    if pool_account_info.owner != pool_program_pubkey {
+        return Err(Error::InvalidAccountOwner.into());
+    }
+    let pool_data = pool_account_info.data.borrow();
+    let bytes = pool_data.get(offset..offset + N).ok_or(Error::InvalidAccountData)?;
+    Ok(<[u8; N]>::try_from(bytes).map_err(|_| Error::InvalidAccountData)?)
+}
+// Tells whether the quote mint is the first of the two consecutive mints stored at `mints_offset` in the pool account.
+fn is_quote_first_pool_mint<'a, 'b>(pool_account_info: &'a AccountInfo<'b>, pool_program_pubkey: &'a Pubkey, mints_offset: usize, venue_swap: &VenueSwap<'a, 'b>) -> Result<bool, ProgramError> {
+    let quote_token_mint_account_info = venue_swap.quote_token_mint_account_info.ok_or(Error::InvalidAccountPubkey)?;
+    let pool_mint_pubkeys = read_pool_data::<64>(
+        pool_account_info,
+        pool_program_pubkey,
+        mints_offset,
+    )?;
+    let (first_mint_pubkey, second_mint_pubkey) = pool_mint_pubkeys.split_at(32);
+    if first_mint_pubkey == quote_token_mint_account_info.key.as_ref() && second_mint_pubkey == venue_swap.base_token_mint_account_info.key.as_ref() {
+        return Ok(true);
+    }
+    if first_mint_pubkey == venue_swap.base_token_mint_account_info.key.as_ref() && second_mint_pubkey == quote_token_mint_account_info.key.as_ref() {
+        return Ok(false);
+    }
+    Err(Error::InvalidAccountData.into())
+}
+// Reserves of a concentrated liquidity venue are derived from its price, so they are scaled down until both fit into u64 keeping the ratio.
+fn fit_reserves(input_reserve: f64, output_reserve: f64) -> (u64, u64) {
+    const MAXIMUM_RESERVE: f64 = (1u64 << 62) as f64;
+    let scale = (input_reserve.max(output_reserve) / MAXIMUM_RESERVE).max(1.0);
+    (
+        (input_reserve / scale) as u64,
+        (output_reserve / scale) as u64,
+    )
+}
+fn enforce_accounts_writability<'a, 'b>(venue_account_infos: &[&'a AccountInfo<'b>], accounts_writability: &'static [bool]) -> Result<(), ProgramError> {
+    if venue_account_infos
+        .iter()
+        .zip(accounts_writability.iter())
+        .any(|(venue_account_info, is_writable)| *is_writable && !venue_account_info.is_writable)
+    {
+        return Err(Error::InvalidAccountConfigurationFlags.into());
+    }
+    Ok(())
+}
+// https://github.com/raydium-io/raydium-amm/blob/d10a8e9fab9f7a3d87b4ae3891e3e4c24b75c041/program/src/instruction.rs#L1045
+pub struct RaydiumAdapter<'a, 'b> {
+    amm_program_id_account_info: &'a AccountInfo<'b>,
+    amm_pool_account_info: &'a AccountInfo<'b>,
+    amm_authority_account_info: &'a AccountInfo<'b>,
+    amm_open_orders_account_info: &'a AccountInfo<'b>,
+    amm_coin_vault_account_info: &'a AccountInfo<'b>,
+    amm_pc_vault_account_info: &'a AccountInfo<'b>,
+    market_program_id_account_info: &'a AccountInfo<'b>,
+    market_account_info: &'a AccountInfo<'b>,
+    market_bids_account_info: &'a AccountInfo<'b>,
+    market_asks_account_info: &'a AccountInfo<'b>,
+    market_event_queue_account_info: &'a AccountInfo<'b>,
+    market_coin_vault_account_info: &'a AccountInfo<'b>,
+    market_pc_vault_account_info: &'a AccountInfo<'b>,
+    market_vault_signer_account_info: &'a AccountInfo<'b>,
+}
+impl<'a, 'b> RaydiumAdapter<'a, 'b> {
+    fn create_raydium_swap_instruction(&self, venue_swap: &VenueSwap<'a, 'b>, is_exact_out: bool) -> Result<(Instruction, Vec<AccountInfo<'b>>), ProgramError> {
+        let create_instruction = if is_exact_out {
+            crate::extern_source::create_raydium_swap_base_out_instruction
+        } else {
+            crate::extern_source::create_raydium_swap_base_in_instruction
+        };
+        Ok(
+            (
+                create_instruction(
+                    self.amm_program_id_account_info.key,
+                    self.amm_pool_account_info.key,
+                    self.amm_authority_account_info.key,
+                    self.amm_open_orders_account_info.key,
+                    self.amm_coin_vault_account_info.key,
+                    self.amm_pc_vault_account_info.key,
+                    self.market_program_id_account_info.key,
+                    self.market_account_info.key,
+                    self.market_bids_account_info.key,
+                    self.market_asks_account_info.key,
+                    self.market_event_queue_account_info.key,
+                    self.market_coin_vault_account_info.key,
+                    self.market_pc_vault_account_info.key,
+                    self.market_vault_signer_account_info.key,
+                    venue_swap.source_token_account_account_info.key,
+                    venue_swap.destination_token_account_account_info.key,
+                    venue_swap.authority_account_info.key,
+                    venue_swap.amount_in,
+                    venue_swap.minimum_amount_out,
+                ),
+                vec![
+                    venue_swap.token_program_account_info.clone(),
+                    self.amm_pool_account_info.clone(),
+                    self.amm_authority_account_info.clone(),
+                    self.amm_open_orders_account_info.clone(),
+                    self.amm_coin_vault_account_info.clone(),
+                    self.amm_pc_vault_account_info.clone(),
+                    self.market_program_id_account_info.clone(),
+                    self.market_account_info.clone(),
+                    self.market_bids_account_info.clone(),
+                    self.market_asks_account_info.clone(),
+                    self.market_event_queue_account_info.clone(),
+                    self.market_coin_vault_account_info.clone(),
+                    self.market_pc_vault_account_info.clone(),
+                    self.market_vault_signer_account_info.clone(),
+                    venue_swap.source_token_account_account_info.clone(),
+                    venue_swap.destination_token_account_account_info.clone(),
+                    venue_swap.authority_account_info.clone(),
+                ],
+            ),
+        )
+    }
+}
+impl<'a, 'b> VenueAdapter<'a, 'b> for RaydiumAdapter<'a, 'b> {
+    const VENUE: Venue = Venue::Raydium;
+    const ACCOUNTS_WRITABILITY: &'static [bool] = &[
+        false,
+        true,
+        false,
+        true,
+        true,
+        true,
+        false,
+        true,
+        true,
+        true,
+        true,
+        true,
+        true,
+        false,
+    ];
+    fn load(venue_account_infos: &[&'a AccountInfo<'b>]) -> Result<Self, ProgramError> {
+        let [
+            amm_program_id_account_info,
+            amm_pool_account_info,
+            amm_authority_account_info,
+            amm_open_orders_account_info,
+            amm_coin_vault_account_info,
+            amm_pc_vault_account_info,
+            market_program_id_account_info,
+            market_account_info,
+            market_bids_account_info,
+            market_asks_account_info,
+            market_event_queue_account_info,
+            market_coin_vault_account_info,
+            market_pc_vault_account_info,
+            market_vault_signer_account_info,
+        ] = *venue_account_infos
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        if *amm_program_id_account_info.key != RAYDIUM_LIQUIDITY_POOL_V4_PROGRAM_PUBKEY {
+            return Err(Error::InvalidAccountPubkey.into());
+        }
+        enforce_accounts_writability(
+            venue_account_infos,
+            Self::ACCOUNTS_WRITABILITY,
+        )?;
+        Ok(
+            Self {
+                amm_program_id_account_info,
+                amm_pool_account_info,
+                amm_authority_account_info,
+                amm_open_orders_account_info,
+                amm_coin_vault_account_info,
+                amm_pc_vault_account_info,
+                market_program_id_account_info,
+                market_account_info,
+                market_bids_account_info,
+                market_asks_account_info,
+                market_event_queue_account_info,
+                market_coin_vault_account_info,
+                market_pc_vault_account_info,
+                market_vault_signer_account_info,
+            },
+        )
+    }
+    fn get_fee(fees: &Fees, side: Side) -> u64 {
+        match side {
+            Side::Buy => fees.buy_on_raydium,
+            Side::Sell => fees.sell_on_raydium,
+        }
+    }
+    fn create_swap_instruction(&self, venue_swap: &VenueSwap<'a, 'b>) -> Result<(Instruction, Vec<AccountInfo<'b>>), ProgramError> {
+        self.create_raydium_swap_instruction(
+            venue_swap,
+            false,
+        )
+    }
+    fn create_exact_out_swap_instruction(&self, venue_swap: &VenueSwap<'a, 'b>) -> Result<(Instruction, Vec<AccountInfo<'b>>), ProgramError> {
+        self.create_raydium_swap_instruction(
+            venue_swap,
+            true,
+        )
+    }
+    fn get_reserves(&self, venue_swap: &VenueSwap<'a, 'b>) -> Result<(u64, u64), ProgramError> {
+        let amm_coin_vault = unpack_pool_token_account(self.amm_coin_vault_account_info)?;
+        let amm_pc_vault = unpack_pool_token_account(self.amm_pc_vault_account_info)?;
+        let (base_reserve, quote_reserve) = if amm_coin_vault.mint == *venue_swap.base_token_mint_account_info.key {
+            (amm_coin_vault.amount, amm_pc_vault.amount)
+        } else {
+            (amm_pc_vault.amount, amm_coin_vault.amount)
+        };
+        Ok(
+            order_reserves(
+                venue_swap.side,
+                base_reserve,
+                quote_reserve,
+            ),
+        )
+    }
+}
+// From IDL (https://www.npmjs.com/package/@pump-fun/pump-swap-sdk)
+pub struct PumpswapAdapter<'a, 'b> {
+    pumpswap_program_id_account_info: &'a AccountInfo<'b>,
+    pool_account_info: &'a AccountInfo<'b>,
+    global_config_account_info: &'a AccountInfo<'b>,
+    pool_base_token_account_account_info: &'a AccountInfo<'b>,
+    pool_quote_token_account_account_info: &'a AccountInfo<'b>,
+    protocol_fee_recipient_account_info: &'a AccountInfo<'b>,
+    protocol_fee_recipient_token_account_account_info: &'a AccountInfo<'b>,
+    quote_token_program_account_info: &'a AccountInfo<'b>,
+    associated_token_account_account_info: &'a AccountInfo<'b>,
+    event_authority_account_info: &'a AccountInfo<'b>,
+}
+impl<'a, 'b> VenueAdapter<'a, 'b> for PumpswapAdapter<'a, 'b> {
+    const VENUE: Venue = Venue::Pumpswap;
+    const ACCOUNTS_WRITABILITY: &'static [bool] = &[
+        false,
+        false,
+        false,
+        true,
+        true,
+        false,
+        true,
+        false,
+        false,
+        false,
+    ];
+    const IS_BUYING_EXACT_OUT: bool = true;
+    fn load(venue_account_infos: &[&'a AccountInfo<'b>]) -> Result<Self, ProgramError> {
+        let [
+            pumpswap_program_id_account_info,
+            pool_account_info,
+            global_config_account_info,
+            pool_base_token_account_account_info,
+            pool_quote_token_account_account_info,
+            protocol_fee_recipient_account_info,
+            protocol_fee_recipient_token_account_account_info,
+            quote_token_program_account_info,
+            associated_token_account_account_info,
+            event_authority_account_info,
+        ] = *venue_account_infos
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        if *pumpswap_program_id_account_info.key != PUMPSWAP_PROGRAM_PUBKEY
+            || *quote_token_program_account_info.key != spl_token::ID
+            || *associated_token_account_account_info.key != SPL_ASSOCIATED_TOKEN_ACCOUNT_ID
+        {
+            return Err(Error::InvalidAccountPubkey.into());
+        }
+        enforce_accounts_writability(
+            venue_account_infos,
+            Self::ACCOUNTS_WRITABILITY,
+        )?;
+        Ok(
+            Self {
+                pumpswap_program_id_account_info,
+                pool_account_info,
+                global_config_account_info,
+                pool_base_token_account_account_info,
+                pool_quote_token_account_account_info,
+                protocol_fee_recipient_account_info,
+                protocol_fee_recipient_token_account_account_info,
+                quote_token_program_account_info,
+                associated_token_account_account_info,
+                event_authority_account_info,
+            },
+        )
+    }
+    fn get_fee(fees: &Fees, side: Side) -> u64 {
+        match side {
+            Side::Buy => fees.buy_on_pumpswap,
+            Side::Sell => fees.sell_on_pumpswap,
+        }
+    }
+    fn create_swap_instruction(&self, venue_swap: &VenueSwap<'a, 'b>) -> Result<(Instruction, Vec<AccountInfo<'b>>), ProgramError> {
+        let quote_token_mint_account_info = venue_swap.quote_token_mint_account_info.ok_or(Error::InvalidAccountPubkey)?;
+        // PumpSwap buys an exact base amount, so the minimum output is what is asked for and the input is the ceiling.
+        let (instruction, user_base_token_account_account_info, user_quote_token_account_account_info) = match venue_swap.side {
+            Side::Buy => {
+                (
+                    crate::extern_source::create_pumpswap_buy_instruction(
+                        self.pumpswap_program_id_account_info.key,
+                        self.pool_account_info.key,
+                        venue_swap.authority_account_info.key,
+                        self.global_config_account_info.key,
+                        venue_swap.base_token_mint_account_info.key,
+                        quote_token_mint_account_info.key,
+                        venue_swap.destination_token_account_account_info.key,
+                        venue_swap.source_token_account_account_info.key,
+                        self.pool_base_token_account_account_info.key,
+                        self.pool_quote_token_account_account_info.key,
+                        self.protocol_fee_recipient_account_info.key,
+                        self.protocol_fee_recipient_token_account_account_info.key,
+                        venue_swap.base_token_program_account_info.key,
+                        self.quote_token_program_account_info.key,
+                        self.event_authority_account_info.key,
+                        venue_swap.minimum_amount_out,
+                        venue_swap.amount_in,
+                    ),
+                    venue_swap.destination_token_account_account_info,
+                    venue_swap.source_token_account_account_info,
+                )
+            }
+            Side::Sell => {
+                (
+                    crate::extern_source::create_pumpswap_sell_instruction(
+                        self.pumpswap_program_id_account_info.key,
+                        self.pool_account_info.key,
+                        venue_swap.authority_account_info.key,
+                        self.global_config_account_info.key,
+                        venue_swap.base_token_mint_account_info.key,
+                        quote_token_mint_account_info.key,
+                        venue_swap.source_token_account_account_info.key,
+                        venue_swap.destination_token_account_account_info.key,
+                        self.pool_base_token_account_account_info.key,
+                        self.pool_quote_token_account_account_info.key,
+                        self.protocol_fee_recipient_account_info.key,
+                        self.protocol_fee_recipient_token_account_account_info.key,
+                        venue_swap.base_token_program_account_info.key,
+                        self.quote_token_program_account_info.key,
+                        self.event_authority_account_info.key,
+                        venue_swap.amount_in,
+                        venue_swap.minimum_amount_out,
+                    ),
+                    venue_swap.source_token_account_account_info,
+                    venue_swap.destination_token_account_account_info,
+                )
+            }
+        };
+        Ok(
+            (
+                instruction,
+                vec![
+                    self.pool_account_info.clone(),
+                    venue_swap.authority_account_info.clone(),
+                    self.global_config_account_info.clone(),
+                    venue_swap.base_token_mint_account_info.clone(),
+                    quote_token_mint_account_info.clone(),
+                    user_base_token_account_account_info.clone(),
+                    user_quote_token_account_account_info.clone(),
+                    self.pool_base_token_account_account_info.clone(),
+                    self.pool_quote_token_account_account_info.clone(),
+                    self.protocol_fee_recipient_account_info.clone(),
+                    self.protocol_fee_recipient_token_account_account_info.clone(),
+                    venue_swap.base_token_program_account_info.clone(),
+                    self.quote_token_program_account_info.clone(),
+                    venue_swap.system_program_account_info.clone(),
+                    self.associated_token_account_account_info.clone(),
+                    self.event_authority_account_info.clone(),
+                    self.pumpswap_program_id_account_info.clone(),
+                ],
+            ),
+        )
+    }
+    fn get_reserves(&self, venue_swap: &VenueSwap<'a, 'b>) -> Result<(u64, u64), ProgramError> {
+        Ok(
+            order_reserves(
+                venue_swap.side,
+                unpack_pool_token_account(self.pool_base_token_account_account_info)?.amount,
+                unpack_pool_token_account(self.pool_quote_token_account_account_info)?.amount,
+            ),
+        )
+    }
+}
+// https://github.com/raydium-io/raydium-cp-swap/blob/master/programs/cp-swap/src/instructions/swap_base_input.rs
+pub struct RaydiumCpmmAdapter<'a, 'b> {
+    cpmm_program_id_account_info: &'a AccountInfo<'b>,
+    cpmm_authority_account_info: &'a AccountInfo<'b>,
+    cpmm_amm_config_account_info: &'a AccountInfo<'b>,
+    cpmm_pool_state_account_info: &'a AccountInfo<'b>,
+    cpmm_quote_vault_account_info: &'a AccountInfo<'b>,
+    cpmm_base_vault_account_info: &'a AccountInfo<'b>,
+    cpmm_observation_state_account_info: &'a AccountInfo<'b>,
+}
+impl<'a, 'b> VenueAdapter<'a, 'b> for RaydiumCpmmAdapter<'a, 'b> {
+    const VENUE: Venue = Venue::RaydiumCpmm;
+    const ACCOUNTS_WRITABILITY: &'static [bool] = &[
+        false,
+        false,
+        false,
+        true,
+        true,
+        true,
+        true,
+    ];
+    fn load(venue_account_infos: &[&'a AccountInfo<'b>]) -> Result<Self, ProgramError> {
+        let [
+            cpmm_program_id_account_info,
+            cpmm_authority_account_info,
+            cpmm_amm_config_account_info,
+            cpmm_pool_state_account_info,
+            cpmm_quote_vault_account_info,
+            cpmm_base_vault_account_info,
+            cpmm_observation_state_account_info,
+        ] = *venue_account_infos
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        if *cpmm_program_id_account_info.key != RAYDIUM_CPMM_PROGRAM_PUBKEY {
+            return Err(Error::InvalidAccountPubkey.into());
+        }
+        enforce_accounts_writability(
+            venue_account_infos,
+            Self::ACCOUNTS_WRITABILITY,
+        )?;
+        Ok(
+            Self {
+                cpmm_program_id_account_info,
+                cpmm_authority_account_info,
+                cpmm_amm_config_account_info,
+                cpmm_pool_state_account_info,
+                cpmm_quote_vault_account_info,
+                cpmm_base_vault_account_info,
+                cpmm_observation_state_account_info,
+            },
+        )
+    }
+    fn get_fee(fees: &Fees, side: Side) -> u64 {
+        match side {
+            Side::Buy => fees.buy_on_raydium_cpmm,
+            Side::Sell => fees.sell_on_raydium_cpmm,
+        }
+    }
+    fn create_swap_instruction(&self, venue_swap: &VenueSwap<'a, 'b>) -> Result<(Instruction, Vec<AccountInfo<'b>>), ProgramError> {
+        let quote_token_mint_account_info = venue_swap.quote_token_mint_account_info.ok_or(Error::InvalidAccountPubkey)?;
+        let (input_vault_account_info, output_vault_account_info, input_token_program_account_info, output_token_program_account_info, input_token_mint_account_info, output_token_mint_account_info) =
+            match venue_swap.side {
+                Side::Buy => (
+                    self.cpmm_quote_vault_account_info,
+                    self.cpmm_base_vault_account_info,
+                    venue_swap.token_program_account_info,
+                    venue_swap.base_token_program_account_info,
+                    quote_token_mint_account_info,
+                    venue_swap.base_token_mint_account_info,
+                ),
+                Side::Sell => (
+                    self.cpmm_base_vault_account_info,
+                    self.cpmm_quote_vault_account_info,
+                    venue_swap.base_token_program_account_info,
+                    venue_swap.token_program_account_info,
+                    venue_swap.base_token_mint_account_info,
+                    quote_token_mint_account_info,
+                ),
+            };
+        Ok(
+            (
+                crate::extern_source::create_raydium_cpmm_swap_base_input_instruction(
+                    self.cpmm_program_id_account_info.key,
+                    venue_swap.authority_account_info.key,
+                    self.cpmm_authority_account_info.key,
+                    self.cpmm_amm_config_account_info.key,
+                    self.cpmm_pool_state_account_info.key,
+                    venue_swap.source_token_account_account_info.key,
+                    venue_swap.destination_token_account_account_info.key,
+                    input_vault_account_info.key,
+                    output_vault_account_info.key,
+                    input_token_program_account_info.key,
+                    output_token_program_account_info.key,
+                    input_token_mint_account_info.key,
+                    output_token_mint_account_info.key,
+                    self.cpmm_observation_state_account_info.key,
+                    venue_swap.amount_in,
+                    venue_swap.minimum_amount_out,
+                ),
+                vec![
+                    venue_swap.authority_account_info.clone(),
+                    self.cpmm_authority_account_info.clone(),
+                    self.cpmm_amm_config_account_info.clone(),
+                    self.cpmm_pool_state_account_info.clone(),
+                    venue_swap.source_token_account_account_info.clone(),
+                    venue_swap.destination_token_account_account_info.clone(),
+                    input_vault_account_info.clone(),
+                    output_vault_account_info.clone(),
+                    input_token_program_account_info.clone(),
+                    output_token_program_account_info.clone(),
+                    input_token_mint_account_info.clone(),
+                    output_token_mint_account_info.clone(),
+                    self.cpmm_observation_state_account_info.clone(),
+                    self.cpmm_program_id_account_info.clone(),
+                ],
+            ),
+        )
+    }
+    fn get_reserves(&self, venue_swap: &VenueSwap<'a, 'b>) -> Result<(u64, u64), ProgramError> {
+        Ok(
+            order_reserves(
+                venue_swap.side,
+                unpack_pool_token_account(self.cpmm_base_vault_account_info)?.amount,
+                unpack_pool_token_account(self.cpmm_quote_vault_account_info)?.amount,
+            ),
+        )
+    }
+}
+// https://github.com/raydium-io/raydium-clmm/blob/master/programs/amm/src/instructions/swap_v2.rs
+pub struct RaydiumClmmAdapter<'a, 'b> {
+    clmm_program_id_account_info: &'a AccountInfo<'b>,
+    clmm_amm_config_account_info: &'a AccountInfo<'b>,
+    clmm_pool_state_account_info: &'a AccountInfo<'b>,
+    clmm_quote_vault_account_info: &'a AccountInfo<'b>,
+    clmm_base_vault_account_info: &'a AccountInfo<'b>,
+    clmm_observation_state_account_info: &'a AccountInfo<'b>,
+    token_2022_program_account_info: &'a AccountInfo<'b>,
+    memo_program_account_info: &'a AccountInfo<'b>,
+    clmm_tick_array_account_infos: Vec<&'a AccountInfo<'b>>,
+}
+impl<'a, 'b> RaydiumClmmAdapter<'a, 'b> {
+    const FIXED_ACCOUNTS_QUANTITY: usize = 8;
+}
+impl<'a, 'b> VenueAdapter<'a, 'b> for RaydiumClmmAdapter<'a, 'b> {
+    const VENUE: Venue = Venue::RaydiumClmm;
+    // Ends with three tick arrays. The venue specific instructions take any non-zero number of them.
+    const ACCOUNTS_WRITABILITY: &'static [bool] = &[
+        false,
+        false,
+        true,
+        true,
+        true,
+        true,
+        false,
+        false,
+        true,
+        true,
+        true,
+    ];
+    fn load(venue_account_infos: &[&'a AccountInfo<'b>]) -> Result<Self, ProgramError> {
+        if venue_account_infos.len() < Self::FIXED_ACCOUNTS_QUANTITY {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        }
+        let (fixed_account_infos, clmm_tick_array_account_infos) = venue_account_infos.split_at(Self::FIXED_ACCOUNTS_QUANTITY);
+        let [
+            clmm_program_id_account_info,
+            clmm_amm_config_account_info,
+            clmm_pool_state_account_info,
+            clmm_quote_vault_account_info,
+            clmm_base_vault_account_info,
+            clmm_observation_state_account_info,
+            token_2022_program_account_info,
+            memo_program_account_info,
+        ] = *fixed_account_infos
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        if *clmm_program_id_account_info.key != RAYDIUM_CLMM_PROGRAM_PUBKEY
+            || *token_2022_program_account_info.key != spl_token_2022::ID
+            || *memo_program_account_info.key != SPL_MEMO_PROGRAM_PUBKEY
+            || clmm_tick_array_account_infos.is_empty()
+        {
+            return Err(Error::InvalidAccountPubkey.into());
+        }
+        enforce_accounts_writability(
+            venue_account_infos,
+            Self::ACCOUNTS_WRITABILITY,
+        )?;
+        if clmm_tick_array_account_infos.iter().any(|clmm_tick_array_account_info| !clmm_tick_array_account_info.is_writable) {
+            return Err(Error::InvalidAccountConfigurationFlags.into());
+        }
+        Ok(
+            Self {
+                clmm_program_id_account_info,
+                clmm_amm_config_account_info,
+                clmm_pool_state_account_info,
+                clmm_quote_vault_account_info,
+                clmm_base_vault_account_info,
+                clmm_observation_state_account_info,
+                token_2022_program_account_info,
+                memo_program_account_info,
+                clmm_tick_array_account_infos: clmm_tick_array_account_infos.to_vec(),
+            },
+        )
+    }
+    fn get_fee(fees: &Fees, side: Side) -> u64 {
+        match side {
+            Side::Buy => fees.buy_on_raydium_clmm,
+            Side::Sell => fees.sell_on_raydium_clmm,
+        }
+    }
+    fn create_swap_instruction(&self, venue_swap: &VenueSwap<'a, 'b>) -> Result<(Instruction, Vec<AccountInfo<'b>>), ProgramError> {
+        let quote_token_mint_account_info = venue_swap.quote_token_mint_account_info.ok_or(Error::InvalidAccountPubkey)?;
+        let (input_vault_account_info, output_vault_account_info, input_token_mint_account_info, output_token_mint_account_info) = match venue_swap.side {
+            Side::Buy => (
+                self.clmm_quote_vault_account_info,
+                self.clmm_base_vault_account_info,
+                quote_token_mint_account_info,
+                venue_swap.base_token_mint_account_info,
+            ),
+            Side::Sell => (
+                self.clmm_base_vault_account_info,
+                self.clmm_quote_vault_account_info,
+                venue_swap.base_token_mint_account_info,
+                quote_token_mint_account_info,
+            ),
+        };
+        let clmm_tick_array_pubkeys = self
+            .clmm_tick_array_account_infos
+            .iter()
+            .map(|clmm_tick_array_account_info| *clmm_tick_array_account_info.key)
+            .collect::<Vec<Pubkey>>();
+        let mut account_infos = vec![
+            venue_swap.authority_account_info.clone(),
+            self.clmm_amm_config_account_info.clone(),
+            self.clmm_pool_state_account_info.clone(),
+            venue_swap.source_token_account_account_info.clone(),
+            venue_swap.destination_token_account_account_info.clone(),
+            input_vault_account_info.clone(),
+            output_vault_account_info.clone(),
+            self.clmm_observation_state_account_info.clone(),
+            venue_swap.token_program_account_info.clone(),
+            self.token_2022_program_account_info.clone(),
+            self.memo_program_account_info.clone(),
+            input_token_mint_account_info.clone(),
+            output_token_mint_account_info.clone(),
+        ];
+        account_infos.extend(
+            self.clmm_tick_array_account_infos
+                .iter()
+                .map(|clmm_tick_array_account_info| (*clmm_tick_array_account_info).clone()),
+        );
+        account_infos.push(self.clmm_program_id_account_info.clone());
+        Ok(
+            (
+                crate::extern_source::create_raydium_clmm_swap_v2_instruction(
+                    self.clmm_program_id_account_info.key,
+                    venue_swap.authority_account_info.key,
+                    self.clmm_amm_config_account_info.key,
+                    self.clmm_pool_state_account_info.key,
+                    venue_swap.source_token_account_account_info.key,
+                    venue_swap.destination_token_account_account_info.key,
+                    input_vault_account_info.key,
+                    output_vault_account_info.key,
+                    self.clmm_observation_state_account_info.key,
+                    input_token_mint_account_info.key,
+                    output_token_mint_account_info.key,
+                    clmm_tick_array_pubkeys.as_slice(),
+                    venue_swap.amount_in,
+                    venue_swap.minimum_amount_out,
+                    0,
+                    true,
+                ),
+                account_infos,
+            ),
+        )
+    }
+    // Virtual reserves of the current price range: L / sqrt(P) of token 0 and L * sqrt(P) of token 1.
+    fn get_reserves(&self, venue_swap: &VenueSwap<'a, 'b>) -> Result<(u64, u64), ProgramError> {
+        const MINTS_OFFSET: usize = 73;
+        const LIQUIDITY_OFFSET: usize = 237;
+        const SQRT_PRICE_X64_OFFSET: usize = 253;
+        let is_quote_token_0 = is_quote_first_pool_mint(
+            self.clmm_pool_state_account_info,
+            &RAYDIUM_CLMM_PROGRAM_PUBKEY,
+            MINTS_OFFSET,
+            venue_swap,
+        )?;
+        let liquidity = u128::from_le_bytes(
+            read_pool_data::<16>(
+                self.clmm_pool_state_account_info,
+                &RAYDIUM_CLMM_PROGRAM_PUBKEY,
+                LIQUIDITY_OFFSET,
+            )?,
+        ) as f64;
+        let sqrt_price = u128::from_le_bytes(
+            read_pool_data::<16>(
+                self.clmm_pool_state_account_info,
+                &RAYDIUM_CLMM_PROGRAM_PUBKEY,
+                SQRT_PRICE_X64_OFFSET,
+            )?,
+        ) as f64
+            / (u64::MAX as f64 + 1.0);
+        if sqrt_price == 0.0 {
+            return Err(Error::InvalidAccountData.into());
+        }
+        let (token_0_reserve, token_1_reserve) = (liquidity / sqrt_price, liquidity * sqrt_price);
+        let (base_reserve, quote_reserve) = if is_quote_token_0 {
+            (token_1_reserve, token_0_reserve)
+        } else {
+            (token_0_reserve, token_1_reserve)
+        };
+        let (input_reserve, output_reserve) = order_reserves(
+            venue_swap.side,
+            base_reserve,
+            quote_reserve,
+        );
+        Ok(
+            fit_reserves(
+                input_reserve,
+                output_reserve,
+            ),
+        )
+    }
+}
+// https://github.com/MeteoraAg/dlmm-sdk/blob/main/programs/lb_clmm/src/instructions/swap.rs
+pub struct MeteoraDlmmAdapter<'a, 'b> {
+    dlmm_program_id_account_info: &'a AccountInfo<'b>,
+    dlmm_lb_pair_account_info: &'a AccountInfo<'b>,
+    dlmm_bin_array_bitmap_extension_account_info: &'a AccountInfo<'b>,
+    dlmm_quote_reserve_account_info: &'a AccountInfo<'b>,
+    dlmm_base_reserve_account_info: &'a AccountInfo<'b>,
+    dlmm_oracle_account_info: &'a AccountInfo<'b>,
+    dlmm_event_authority_account_info: &'a AccountInfo<'b>,
+    dlmm_bin_array_account_infos: Vec<&'a AccountInfo<'b>>,
+}
+impl<'a, 'b> MeteoraDlmmAdapter<'a, 'b> {
+    const FIXED_ACCOUNTS_QUANTITY: usize = 7;
+    const MINTS_OFFSET: usize = 88;
+}
+impl<'a, 'b> VenueAdapter<'a, 'b> for MeteoraDlmmAdapter<'a, 'b> {
+    const VENUE: Venue = Venue::MeteoraDlmm;
+    // Ends with three bin arrays. The venue specific instructions take any non-zero number of them.
+    const ACCOUNTS_WRITABILITY: &'static [bool] = &[
+        false,
+        true,
+        false,
+        true,
+        true,
+        true,
+        false,
+        true,
+        true,
+        true,
+    ];
+    fn load(venue_account_infos: &[&'a AccountInfo<'b>]) -> Result<Self, ProgramError> {
+        if venue_account_infos.len() < Self::FIXED_ACCOUNTS_QUANTITY {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        }
+        let (fixed_account_infos, dlmm_bin_array_account_infos) = venue_account_infos.split_at(Self::FIXED_ACCOUNTS_QUANTITY);
+        let [
+            dlmm_program_id_account_info,
+            dlmm_lb_pair_account_info,
+            dlmm_bin_array_bitmap_extension_account_info,
+            dlmm_quote_reserve_account_info,
+            dlmm_base_reserve_account_info,
+            dlmm_oracle_account_info,
+            dlmm_event_authority_account_info,
+        ] = *fixed_account_infos
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        if *dlmm_program_id_account_info.key != METEORA_DLMM_PROGRAM_PUBKEY || dlmm_bin_array_account_infos.is_empty() {
+            return Err(Error::InvalidAccountPubkey.into());
+        }
+        enforce_accounts_writability(
+            venue_account_infos,
+            Self::ACCOUNTS_WRITABILITY,
+        )?;
+        if dlmm_bin_array_account_infos.iter().any(|dlmm_bin_array_account_info| !dlmm_bin_array_account_info.is_writable) {
+            return Err(Error::InvalidAccountConfigurationFlags.into());
+        }
+        Ok(
+            Self {
+                dlmm_program_id_account_info,
+                dlmm_lb_pair_account_info,
+                dlmm_bin_array_bitmap_extension_account_info,
+                dlmm_quote_reserve_account_info,
+                dlmm_base_reserve_account_info,
+                dlmm_oracle_account_info,
+                dlmm_event_authority_account_info,
+                dlmm_bin_array_account_infos: dlmm_bin_array_account_infos.to_vec(),
+            },
+        )
+    }
+    fn get_fee(fees: &Fees, side: Side) -> u64 {
+        match side {
+            Side::Buy => fees.buy_on_meteora_dlmm,
+            Side::Sell => fees.sell_on_meteora_dlmm,
+        }
+    }
+    fn create_swap_instruction(&self, venue_swap: &VenueSwap<'a, 'b>) -> Result<(Instruction, Vec<AccountInfo<'b>>), ProgramError> {
+        let quote_token_mint_account_info = venue_swap.quote_token_mint_account_info.ok_or(Error::InvalidAccountPubkey)?;
+        let (dlmm_reserve_x_account_info, dlmm_reserve_y_account_info, token_x_mint_account_info, token_y_mint_account_info, token_x_program_account_info, token_y_program_account_info) =
+            if is_quote_first_pool_mint(
+                self.dlmm_lb_pair_account_info,
+                &METEORA_DLMM_PROGRAM_PUBKEY,
+                Self::MINTS_OFFSET,
+                venue_swap,
+            )? {
+                (
+                    self.dlmm_quote_reserve_account_info,
+                    self.dlmm_base_reserve_account_info,
+                    quote_token_mint_account_info,
+                    venue_swap.base_token_mint_account_info,
+                    venue_swap.token_program_account_info,
+                    venue_swap.base_token_program_account_info,
+                )
+            } else {
+                (
+                    self.dlmm_base_reserve_account_info,
+                    self.dlmm_quote_reserve_account_info,
+                    venue_swap.base_token_mint_account_info,
+                    quote_token_mint_account_info,
+                    venue_swap.base_token_program_account_info,
+                    venue_swap.token_program_account_info,
+                )
+            };
+        let dlmm_bin_array_pubkeys = self
+            .dlmm_bin_array_account_infos
+            .iter()
+            .map(|dlmm_bin_array_account_info| *dlmm_bin_array_account_info.key)
+            .collect::<Vec<Pubkey>>();
+        let mut account_infos = vec![
+            self.dlmm_lb_pair_account_info.clone(),
+            self.dlmm_bin_array_bitmap_extension_account_info.clone(),
+            dlmm_reserve_x_account_info.clone(),
+            dlmm_reserve_y_account_info.clone(),
+            venue_swap.source_token_account_account_info.clone(),
+            venue_swap.destination_token_account_account_info.clone(),
+            token_x_mint_account_info.clone(),
+            token_y_mint_account_info.clone(),
+            self.dlmm_oracle_account_info.clone(),
+            venue_swap.authority_account_info.clone(),
+            token_x_program_account_info.clone(),
+            token_y_program_account_info.clone(),
+            self.dlmm_event_authority_account_info.clone(),
+            self.dlmm_program_id_account_info.clone(),
+        ];
+        account_infos.extend(
+            self.dlmm_bin_array_account_infos
+                .iter()
+                .map(|dlmm_bin_array_account_info| (*dlmm_bin_array_account_info).clone()),
+        );
+        Ok(
+            (
+                crate::extern_source::create_meteora_dlmm_swap_instruction(
+                    self.dlmm_program_id_account_info.key,
+                    self.dlmm_lb_pair_account_info.key,
+                    self.dlmm_bin_array_bitmap_extension_account_info.key,
+                    dlmm_reserve_x_account_info.key,
+                    dlmm_reserve_y_account_info.key,
+                    venue_swap.source_token_account_account_info.key,
+                    venue_swap.destination_token_account_account_info.key,
+                    token_x_mint_account_info.key,
+                    token_y_mint_account_info.key,
+                    self.dlmm_oracle_account_info.key,
+                    venue_swap.authority_account_info.key,
+                    token_x_program_account_info.key,
+                    token_y_program_account_info.key,
+                    self.dlmm_event_authority_account_info.key,
+                    dlmm_bin_array_pubkeys.as_slice(),
+                    venue_swap.amount_in,
+                    venue_swap.minimum_amount_out,
+                ),
+                account_infos,
+            ),
+        )
+    }
+    // The active bin fills at (1 + bin_step / 10_000) ^ active_id token Y per token X with no slippage, which deep reserves at that price stand for.
+    fn get_reserves(&self, venue_swap: &VenueSwap<'a, 'b>) -> Result<(u64, u64), ProgramError> {
+        const ACTIVE_ID_OFFSET: usize = 76;
+        const BIN_STEP_OFFSET: usize = 80;
+        let is_quote_token_x = is_quote_first_pool_mint(
+            self.dlmm_lb_pair_account_info,
+            &METEORA_DLMM_PROGRAM_PUBKEY,
+            Self::MINTS_OFFSET,
+            venue_swap,
+        )?;
+        let active_id = i32::from_le_bytes(
+            read_pool_data::<4>(
+                self.dlmm_lb_pair_account_info,
+                &METEORA_DLMM_PROGRAM_PUBKEY,
+                ACTIVE_ID_OFFSET,
+            )?,
+        );
+        let bin_step = u16::from_le_bytes(
+            read_pool_data::<2>(
+                self.dlmm_lb_pair_account_info,
+                &METEORA_DLMM_PROGRAM_PUBKEY,
+                BIN_STEP_OFFSET,
+            )?,
+        );
+        let price = (1.0 + (bin_step as f64) / 10_000.0).powi(active_id);
+        let output_per_input_price = if is_quote_token_x == (venue_swap.side == Side::Buy) {
+            price
+        } else {
+            1.0 / price
+        };
+        Ok(
+            fit_reserves(
+                u64::MAX as f64,
+                (u64::MAX as f64) * output_per_input_price,
+            ),
+        )
+    }
+}
+// https://github.com/MeteoraAg/damm-v1-sdk/blob/main/programs/dynamic-amm/src/lib.rs
+pub struct MeteoraDynamicAmmAdapter<'a, 'b> {
+    dynamic_amm_program_id_account_info: &'a AccountInfo<'b>,
+    vault_program_id_account_info: &'a AccountInfo<'b>,
+    dynamic_amm_pool_account_info: &'a AccountInfo<'b>,
+    dynamic_amm_quote_vault_account_info: &'a AccountInfo<'b>,
+    dynamic_amm_base_vault_account_info: &'a AccountInfo<'b>,
+    dynamic_amm_quote_token_vault_account_info: &'a AccountInfo<'b>,
+    dynamic_amm_base_token_vault_account_info: &'a AccountInfo<'b>,
+    dynamic_amm_quote_vault_lp_mint_account_info: &'a AccountInfo<'b>,
+    dynamic_amm_base_vault_lp_mint_account_info: &'a AccountInfo<'b>,
+    dynamic_amm_quote_vault_lp_account_info: &'a AccountInfo<'b>,
+    dynamic_amm_base_vault_lp_account_info: &'a AccountInfo<'b>,
+    dynamic_amm_protocol_token_fee_account_info: &'a AccountInfo<'b>,
+}
+impl<'a, 'b> MeteoraDynamicAmmAdapter<'a, 'b> {
+    // The share of a Meteora vault held by the pool through its vault LP tokens.
+    fn get_vault_reserve(vault_account_info: &'a AccountInfo<'b>, vault_lp_mint_account_info: &'a AccountInfo<'b>, vault_lp_account_info: &'a AccountInfo<'b>) -> Result<u64, ProgramError> {
+        const TOTAL_AMOUNT_OFFSET: usize = 11;
+        let vault_total_amount = u64::from_le_bytes(
+            read_pool_data::<8>(
+                vault_account_info,
+                &METEORA_VAULT_PROGRAM_PUBKEY,
+                TOTAL_AMOUNT_OFFSET,
+            )?,
+        );
+        if *vault_lp_mint_account_info.owner != spl_token::ID {
+            return Err(Error::InvalidAccountOwner.into());
+        }
+        let vault_lp_mint_supply = spl_token::state::Mint::unpack(&vault_lp_mint_account_info.data.borrow())?.supply;
+        if vault_lp_mint_supply == 0 {
+            return Ok(0);
+        }
+        Ok(((vault_total_amount as u128) * (unpack_pool_token_account(vault_lp_account_info)?.amount as u128) / (vault_lp_mint_supply as u128)) as u64)
+    }
+}
+impl<'a, 'b> VenueAdapter<'a, 'b> for MeteoraDynamicAmmAdapter<'a, 'b> {
+    const VENUE: Venue = Venue::MeteoraDynamicAmm;
+    const ACCOUNTS_WRITABILITY: &'static [bool] = &[
+        false,
+        false,
+        true,
+        true,
+        true,
+        true,
+        true,
+        true,
+        true,
+        true,
+        true,
+        true,
+    ];
+    fn load(venue_account_infos: &[&'a AccountInfo<'b>]) -> Result<Self, ProgramError> {
+        let [
+            dynamic_amm_program_id_account_info,
+            vault_program_id_account_info,
+            dynamic_amm_pool_account_info,
+            dynamic_amm_quote_vault_account_info,
+            dynamic_amm_base_vault_account_info,
+            dynamic_amm_quote_token_vault_account_info,
+            dynamic_amm_base_token_vault_account_info,
+            dynamic_amm_quote_vault_lp_mint_account_info,
+            dynamic_amm_base_vault_lp_mint_account_info,
+            dynamic_amm_quote_vault_lp_account_info,
+            dynamic_amm_base_vault_lp_account_info,
+            dynamic_amm_protocol_token_fee_account_info,
+        ] = *venue_account_infos
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        if *dynamic_amm_program_id_account_info.key != METEORA_DYNAMIC_AMM_PROGRAM_PUBKEY || *vault_program_id_account_info.key != METEORA_VAULT_PROGRAM_PUBKEY {
+            return Err(Error::InvalidAccountPubkey.into());
+        }
+        enforce_accounts_writability(
+            venue_account_infos,
+            Self::ACCOUNTS_WRITABILITY,
+        )?;
+        Ok(
+            Self {
+                dynamic_amm_program_id_account_info,
+                vault_program_id_account_info,
+                dynamic_amm_pool_account_info,
+                dynamic_amm_quote_vault_account_info,
+                dynamic_amm_base_vault_account_info,
+                dynamic_amm_quote_token_vault_account_info,
+                dynamic_amm_base_token_vault_account_info,
+                dynamic_amm_quote_vault_lp_mint_account_info,
+                dynamic_amm_base_vault_lp_mint_account_info,
+                dynamic_amm_quote_vault_lp_account_info,
+                dynamic_amm_base_vault_lp_account_info,
+                dynamic_amm_protocol_token_fee_account_info,
+            },
+        )
+    }
+    fn get_fee(fees: &Fees, side: Side) -> u64 {
+        match side {
+            Side::Buy => fees.buy_on_meteora_dynamic_amm,
+            Side::Sell => fees.sell_on_meteora_dynamic_amm,
+        }
+    }
+    // The pool takes a single token program, so both of its tokens have to be classic ones.
+    fn create_swap_instruction(&self, venue_swap: &VenueSwap<'a, 'b>) -> Result<(Instruction, Vec<AccountInfo<'b>>), ProgramError> {
+        const MINTS_OFFSET: usize = 40;
+        if *venue_swap.base_token_program_account_info.key != spl_token::ID {
+            return Err(Error::InvalidAccountPubkey.into());
+        }
+        let (a_vault, b_vault, a_token_vault, b_token_vault, a_vault_lp_mint, b_vault_lp_mint, a_vault_lp, b_vault_lp) = if is_quote_first_pool_mint(
+            self.dynamic_amm_pool_account_info,
+            &METEORA_DYNAMIC_AMM_PROGRAM_PUBKEY,
+            MINTS_OFFSET,
+            venue_swap,
+        )? {
+            (
+                self.dynamic_amm_quote_vault_account_info,
+                self.dynamic_amm_base_vault_account_info,
+                self.dynamic_amm_quote_token_vault_account_info,
+                self.dynamic_amm_base_token_vault_account_info,
+                self.dynamic_amm_quote_vault_lp_mint_account_info,
+                self.dynamic_amm_base_vault_lp_mint_account_info,
+                self.dynamic_amm_quote_vault_lp_account_info,
+                self.dynamic_amm_base_vault_lp_account_info,
+            )
+        } else {
+            (
+                self.dynamic_amm_base_vault_account_info,
+                self.dynamic_amm_quote_vault_account_info,
+                self.dynamic_amm_base_token_vault_account_info,
+                self.dynamic_amm_quote_token_vault_account_info,
+                self.dynamic_amm_base_vault_lp_mint_account_info,
+                self.dynamic_amm_quote_vault_lp_mint_account_info,
+                self.dynamic_amm_base_vault_lp_account_info,
+                self.dynamic_amm_quote_vault_lp_account_info,
+            )
+        };
+        Ok(
+            (
+                crate::extern_source::create_meteora_dynamic_amm_swap_instruction(
+                    self.dynamic_amm_program_id_account_info.key,
+                    self.dynamic_amm_pool_account_info.key,
+                    venue_swap.source_token_account_account_info.key,
+                    venue_swap.destination_token_account_account_info.key,
+                    a_vault.key,
+                    b_vault.key,
+                    a_token_vault.key,
+                    b_token_vault.key,
+                    a_vault_lp_mint.key,
+                    b_vault_lp_mint.key,
+                    a_vault_lp.key,
+                    b_vault_lp.key,
+                    self.dynamic_amm_protocol_token_fee_account_info.key,
+                    venue_swap.authority_account_info.key,
+                    self.vault_program_id_account_info.key,
+                    venue_swap.token_program_account_info.key,
+                    venue_swap.amount_in,
+                    venue_swap.minimum_amount_out,
+                ),
+                vec![
+                    self.dynamic_amm_pool_account_info.clone(),
+                    venue_swap.source_token_account_account_info.clone(),
+                    venue_swap.destination_token_account_account_info.clone(),
+                    a_vault.clone(),
+                    b_vault.clone(),
+                    a_token_vault.clone(),
+                    b_token_vault.clone(),
+                    a_vault_lp_mint.clone(),
+                    b_vault_lp_mint.clone(),
+                    a_vault_lp.clone(),
+                    b_vault_lp.clone(),
+                    self.dynamic_amm_protocol_token_fee_account_info.clone(),
+                    venue_swap.authority_account_info.clone(),
+                    self.vault_program_id_account_info.clone(),
+                    venue_swap.token_program_account_info.clone(),
+                    self.dynamic_amm_program_id_account_info.clone(),
+                ],
+            ),
+        )
+    }
+    fn get_reserves(&self, venue_swap: &VenueSwap<'a, 'b>) -> Result<(u64, u64), ProgramError> {
+        Ok(
+            order_reserves(
+                venue_swap.side,
+                Self::get_vault_reserve(
+                    self.dynamic_amm_base_vault_account_info,
+                    self.dynamic_amm_base_vault_lp_mint_account_info,
+                    self.dynamic_amm_base_vault_lp_account_info,
+                )?,
+                Self::get_vault_reserve(
+                    self.dynamic_amm_quote_vault_account_info,
+                    self.dynamic_amm_quote_vault_lp_mint_account_info,
+                    self.dynamic_amm_quote_vault_lp_account_info,
+                )?,
+            ),
+        )
+    }
+}
+pub struct PumpfunCurveAdapter<'a, 'b> {
+    pumpfun_program_id_account_info: &'a AccountInfo<'b>,
+    global_account_info: &'a AccountInfo<'b>,
+    fee_recipient_account_info: &'a AccountInfo<'b>,
+    bonding_curve_account_info: &'a AccountInfo<'b>,
+    associated_bonding_curve_account_info: &'a AccountInfo<'b>,
+    event_authority_account_info: &'a AccountInfo<'b>,
+    side_account_info: &'a AccountInfo<'b>,
+}
+impl<'a, 'b> VenueAdapter<'a, 'b> for PumpfunCurveAdapter<'a, 'b> {
+    const VENUE: Venue = Venue::PumpfunCurve;
+    // Ends with the account the side needs: the rent sysvar for a buy, the associated token account program for a sell.
+    const ACCOUNTS_WRITABILITY: &'static [bool] = &[
+        false,
+        false,
+        true,
+        true,
+        true,
+        false,
+        false,
+    ];
+    const IS_SETTLED_IN_LAMPORTS: bool = true;
+    const IS_BUYING_EXACT_OUT: bool = true;
+    fn load(venue_account_infos: &[&'a AccountInfo<'b>]) -> Result<Self, ProgramError> {
+        let [
+            pumpfun_program_id_account_info,
+            global_account_info,
+            fee_recipient_account_info,
+            bonding_curve_account_info,
+            associated_bonding_curve_account_info,
+            event_authority_account_info,
+            side_account_info,
+        ] = *venue_account_infos
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        if *pumpfun_program_id_account_info.key != PUMPFUN_PROGRAM_PUBKEY
+            || (*side_account_info.key != solana_program::sysvar::rent::ID && *side_account_info.key != SPL_ASSOCIATED_TOKEN_ACCOUNT_ID)
+        {
+            return Err(Error::InvalidAccountPubkey.into());
+        }
+        enforce_accounts_writability(
+            venue_account_infos,
+            Self::ACCOUNTS_WRITABILITY,
+        )?;
+        Ok(
+            Self {
+                pumpfun_program_id_account_info,
+                global_account_info,
+                fee_recipient_account_info,
+                bonding_curve_account_info,
+                associated_bonding_curve_account_info,
+                event_authority_account_info,
+                side_account_info,
+            },
+        )
+    }
+    fn get_fee(fees: &Fees, side: Side) -> u64 {
+        match side {
+            Side::Buy => fees.buy_on_pumpfun_curve,
+            Side::Sell => fees.sell_on_pumpfun_curve,
+        }
+    }
+    // The curve buys an exact token amount, so the minimum output is what is asked for and the input is the lamports ceiling.
+    fn create_swap_instruction(&self, venue_swap: &VenueSwap<'a, 'b>) -> Result<(Instruction, Vec<AccountInfo<'b>>), ProgramError> {
+        if *venue_swap.base_token_program_account_info.key != spl_token::ID {
+            return Err(Error::InvalidAccountPubkey.into());
+        }
+        match venue_swap.side {
+            Side::Buy => {
+                if *self.side_account_info.key != solana_program::sysvar::rent::ID {
+                    return Err(Error::InvalidAccountPubkey.into());
+                }
+                Ok(
+                    (
+                        crate::extern_source::create_pumpfun_buy_instruction(
+                            self.pumpfun_program_id_account_info.key,
+                            self.global_account_info.key,
+                            self.fee_recipient_account_info.key,
+                            venue_swap.base_token_mint_account_info.key,
+                            self.bonding_curve_account_info.key,
+                            self.associated_bonding_curve_account_info.key,
+                            venue_swap.destination_token_account_account_info.key,
+                            venue_swap.authority_account_info.key,
+                            self.event_authority_account_info.key,
+                            venue_swap.minimum_amount_out,
+                            venue_swap.amount_in,
+                        ),
+                        vec![
+                            self.global_account_info.clone(),
+                            self.fee_recipient_account_info.clone(),
+                            venue_swap.base_token_mint_account_info.clone(),
+                            self.bonding_curve_account_info.clone(),
+                            self.associated_bonding_curve_account_info.clone(),
+                            venue_swap.destination_token_account_account_info.clone(),
+                            venue_swap.authority_account_info.clone(),
+                            venue_swap.system_program_account_info.clone(),
+                            venue_swap.token_program_account_info.clone(),
+                            self.side_account_info.clone(),
+                            self.event_authority_account_info.clone(),
+                            self.pumpfun_program_id_account_info.clone(),
+                        ],
+                    ),
+                )
+            }
+            Side::Sell => {
+                if *self.side_account_info.key != SPL_ASSOCIATED_TOKEN_ACCOUNT_ID {
+                    return Err(Error::InvalidAccountPubkey.into());
+                }
+                Ok(
+                    (
+                        crate::extern_source::create_pumpfun_sell_instruction(
+                            self.pumpfun_program_id_account_info.key,
+                            self.global_account_info.key,
+                            self.fee_recipient_account_info.key,
+                            venue_swap.base_token_mint_account_info.key,
+                            self.bonding_curve_account_info.key,
+                            self.associated_bonding_curve_account_info.key,
+                            venue_swap.source_token_account_account_info.key,
+                            venue_swap.authority_account_info.key,
+                            self.event_authority_account_info.key,
+                            venue_swap.amount_in,
+                            venue_swap.minimum_amount_out,
+                        ),
+                        vec![
+                            self.global_account_info.clone(),
+                            self.fee_recipient_account_info.clone(),
+                            venue_swap.base_token_mint_account_info.clone(),
+                            self.bonding_curve_account_info.clone(),
+                            self.associated_bonding_curve_account_info.clone(),
+                            venue_swap.source_token_account_account_info.clone(),
+                            venue_swap.authority_account_info.clone(),
+                            venue_swap.system_program_account_info.clone(),
+                            self.side_account_info.clone(),
+                            venue_swap.token_program_account_info.clone(),
+                            self.event_authority_account_info.clone(),
+                            self.pumpfun_program_id_account_info.clone(),
+                        ],
+                    ),
+                )
+            }
+        }
+    }
+    fn get_reserves(&self, venue_swap: &VenueSwap<'a, 'b>) -> Result<(u64, u64), ProgramError> {
+        const VIRTUAL_TOKEN_RESERVES_OFFSET: usize = 8;
+        const VIRTUAL_SOL_RESERVES_OFFSET: usize = 16;
+        Ok(
+            order_reserves(
+                venue_swap.side,
+                u64::from_le_bytes(
+                    read_pool_data::<8>(
+                        self.bonding_curve_account_info,
+                        &PUMPFUN_PROGRAM_PUBKEY,
+                        VIRTUAL_TOKEN_RESERVES_OFFSET,
+                    )?,
+                ),
+                u64::from_le_bytes(
+                    read_pool_data::<8>(
+                        self.bonding_curve_account_info,
+                        &PUMPFUN_PROGRAM_PUBKEY,
+                        VIRTUAL_SOL_RESERVES_OFFSET,
+                    )?,
+                ),
+            ),
+        )
+    }
+}
+// A venue adapter picked at runtime, for instructions that take the venue per leg.
+// The Pump.fun curve settles in lamports, which a leg between two token accounts cannot do.
+pub enum AnyVenueAdapter<'a, 'b> {
+    Raydium(RaydiumAdapter<'a, 'b>),
+    Pumpswap(PumpswapAdapter<'a, 'b>),
+    RaydiumCpmm(RaydiumCpmmAdapter<'a, 'b>),
+    RaydiumClmm(RaydiumClmmAdapter<'a, 'b>),
+    MeteoraDlmm(MeteoraDlmmAdapter<'a, 'b>),
+    MeteoraDynamicAmm(MeteoraDynamicAmmAdapter<'a, 'b>),
+}
+impl<'a, 'b> AnyVenueAdapter<'a, 'b> {
+    pub fn load(venue: Venue, venue_account_infos: &[&'a AccountInfo<'b>]) -> Result<Self, ProgramError> {
+        match venue {
+            Venue::Raydium => Ok(Self::Raydium(RaydiumAdapter::load(venue_account_infos)?)),
+            Venue::Pumpswap => Ok(Self::Pumpswap(PumpswapAdapter::load(venue_account_infos)?)),
+            Venue::RaydiumCpmm => Ok(Self::RaydiumCpmm(RaydiumCpmmAdapter::load(venue_account_infos)?)),
+            Venue::RaydiumClmm => Ok(Self::RaydiumClmm(RaydiumClmmAdapter::load(venue_account_infos)?)),
+            Venue::MeteoraDlmm => Ok(Self::MeteoraDlmm(MeteoraDlmmAdapter::load(venue_account_infos)?)),
+            Venue::MeteoraDynamicAmm => Ok(Self::MeteoraDynamicAmm(MeteoraDynamicAmmAdapter::load(venue_account_infos)?)),
+            Venue::PumpfunCurve => Err(Error::VenueIsNotSupported.into()),
+        }
+    }
+    pub fn get_fee(&self, fees: &Fees, side: Side) -> u64 {
+        match self {
+            Self::Raydium(_) => RaydiumAdapter::get_fee(
+                fees,
+                side,
+            ),
+            Self::Pumpswap(_) => PumpswapAdapter::get_fee(
+                fees,
+                side,
+            ),
+            Self::RaydiumCpmm(_) => RaydiumCpmmAdapter::get_fee(
+                fees,
+                side,
+            ),
+            Self::RaydiumClmm(_) => RaydiumClmmAdapter::get_fee(
+                fees,
+                side,
+            ),
+            Self::MeteoraDlmm(_) => MeteoraDlmmAdapter::get_fee(
+                fees,
+                side,
+            ),
+            Self::MeteoraDynamicAmm(_) => MeteoraDynamicAmmAdapter::get_fee(
+                fees,
+                side,
+            ),
+        }
+    }
+    pub fn create_swap_instruction(&self, venue_swap: &VenueSwap<'a, 'b>) -> Result<(Instruction, Vec<AccountInfo<'b>>), ProgramError> {
+        match self {
+            Self::Raydium(raydium_adapter) => raydium_adapter.create_swap_instruction(venue_swap),
+            Self::Pumpswap(pumpswap_adapter) => pumpswap_adapter.create_swap_instruction(venue_swap),
+            Self::RaydiumCpmm(raydium_cpmm_adapter) => raydium_cpmm_adapter.create_swap_instruction(venue_swap),
+            Self::RaydiumClmm(raydium_clmm_adapter) => raydium_clmm_adapter.create_swap_instruction(venue_swap),
+            Self::MeteoraDlmm(meteora_dlmm_adapter) => meteora_dlmm_adapter.create_swap_instruction(venue_swap),
+            Self::MeteoraDynamicAmm(meteora_dynamic_amm_adapter) => meteora_dynamic_amm_adapter.create_swap_instruction(venue_swap),
+        }
+    }
+    pub fn get_reserves(&self, venue_swap: &VenueSwap<'a, 'b>) -> Result<(u64, u64), ProgramError> {
+        match self {
+            Self::Raydium(raydium_adapter) => raydium_adapter.get_reserves(venue_swap),
+            Self::Pumpswap(pumpswap_adapter) => pumpswap_adapter.get_reserves(venue_swap),
+            Self::RaydiumCpmm(raydium_cpmm_adapter) => raydium_cpmm_adapter.get_reserves(venue_swap),
+            Self::RaydiumClmm(raydium_clmm_adapter) => raydium_clmm_adapter.get_reserves(venue_swap),
+            Self::MeteoraDlmm(meteora_dlmm_adapter) => meteora_dlmm_adapter.get_reserves(venue_swap),
+            Self::MeteoraDynamicAmm(meteora_dynamic_amm_adapter) => meteora_dynamic_amm_adapter.get_reserves(venue_swap),
+        }
+    }
+}
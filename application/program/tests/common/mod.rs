@@ -0,0 +1,2224 @@
+#![allow(dead_code)]
+use {
+    base64::Engine,
+    simo_intermediary::{
+        error::Error,
+        event::VersionedEvent,
+        instruction::Instruction,
+        processor::Processor,
+        state::{
+            Fees,
+            Intermediary,
+            TraderSeat,
+        },
+        AllowedMintsAddressResolver,
+        AnyMintTokenAccountAddressResolver,
+        AnyMintTokenAccountAuthorityAddressResolver,
+        ConfigAddressResolver,
+        FeeVaultAddressResolver,
+        ProgramDerivedAddress,
+        RiskLimitsAddressResolver,
+        TemporaryWSolTokenAccountAddressResolver,
+        TemporaryWSolTokenAccountAuthorityAddressResolver,
+        TraderSeatAddressResolver,
+        WSolTokenAccountAddressResolver,
+        WSolTokenAccountAuthorityAddressResolver,
+        METEORA_DLMM_PROGRAM_PUBKEY,
+        METEORA_DYNAMIC_AMM_PROGRAM_PUBKEY,
+        METEORA_VAULT_PROGRAM_PUBKEY,
+        PROGRAM_PUBKEY,
+        PUMPFUN_PROGRAM_PUBKEY,
+        PUMPSWAP_PROGRAM_PUBKEY,
+        RAYDIUM_CLMM_PROGRAM_PUBKEY,
+        RAYDIUM_CPMM_PROGRAM_PUBKEY,
+        RAYDIUM_LIQUIDITY_POOL_V4_PROGRAM_PUBKEY,
+        SPL_ASSOCIATED_TOKEN_ACCOUNT_ID,
+        SPL_MEMO_PROGRAM_PUBKEY,
+    },
+    solana_program_test::{
+        processor,
+        BanksClientError,
+        ProgramTest,
+        ProgramTestContext,
+    },
+    solana_sdk::{
+        account::Account,
+        account_info::AccountInfo,
+        bpf_loader_upgradeable,
+        entrypoint::ProgramResult,
+        instruction::{
+            AccountMeta,
+            Instruction as Instruction_,
+            InstructionError,
+        },
+        native_token::LAMPORTS_PER_SOL,
+        program::{
+            invoke,
+            invoke_signed,
+        },
+        program_error::ProgramError,
+        program_option::COption,
+        program_pack::Pack,
+        program_stubs::{
+            self,
+            SyscallStubs,
+        },
+        pubkey::Pubkey,
+        rent::Rent,
+        signature::Keypair,
+        signer::Signer,
+        system_program,
+        transaction::{
+            Transaction,
+            TransactionError,
+        },
+    },
+    spl_token::state::{
+        Account as TokenAccount,
+        Mint,
+    },
+    spl_token_2022::extension::{
+        transfer_fee::{
+            TransferFee,
+            TransferFeeAmount,
+            TransferFeeConfig,
+        },
+        BaseStateWithExtensionsMut,
+        ExtensionType,
+        StateWithExtensions,
+        StateWithExtensionsMut,
+    },
+    std::sync::Once,
+};
+pub const FEE: u64 = 10_000;
+pub const FEES: Fees = Fees {
+    initialize: FEE,
+    deposit_funds: FEE,
+    withdraw_funds: FEE,
+    buy_on_raydium: FEE,
+    sell_on_raydium: FEE,
+    buy_on_pumpswap: FEE,
+    sell_on_pumpswap: FEE,
+    change_manager: FEE,
+    change_trader: FEE,
+    buy_on_raydium_cpmm: FEE,
+    sell_on_raydium_cpmm: FEE,
+    buy_on_raydium_clmm: FEE,
+    sell_on_raydium_clmm: FEE,
+    buy_on_meteora_dlmm: FEE,
+    sell_on_meteora_dlmm: FEE,
+    buy_on_meteora_dynamic_amm: FEE,
+    sell_on_meteora_dynamic_amm: FEE,
+    buy_on_pumpfun_curve: FEE,
+    sell_on_pumpfun_curve: FEE,
+};
+pub const FUNDED_ACCOUNT_LAMPORTS: u64 = 100 * LAMPORTS_PER_SOL;
+const LOG_DATA_PREFIX: &'static str = "Program log: data: ";
+const PROGRAM_DATA_PREFIX: &'static str = "Program data: ";
+pub struct Environment {
+    pub context: ProgramTestContext,
+    pub administrator: Keypair,
+    pub fee_recipient: Keypair,
+    pub investor: Keypair,
+    pub manager: Keypair,
+    pub trader: Keypair,
+    pub intermediary: Keypair,
+    pub config_pubkey: Pubkey,
+    pub fee_vault_pubkey: Pubkey,
+}
+impl Environment {
+    pub async fn start(program_test_configurator: impl FnOnce(&mut ProgramTest, &Pubkey)) -> Self {
+        let intermediary = Keypair::new();
+        let administrator = Keypair::new();
+        let fee_recipient = Keypair::new();
+        let investor = Keypair::new();
+        let manager = Keypair::new();
+        let trader = Keypair::new();
+        let mut program_test = ProgramTest::new(
+            "simo_intermediary",
+            PROGRAM_PUBKEY,
+            processor!(Processor::process),
+        );
+        program_test.prefer_bpf(false);
+        program_test.add_account(
+            spl_token::native_mint::ID,
+            create_mint_account(9),
+        );
+        program_test.add_account(
+            bpf_loader_upgradeable::get_program_data_address(&PROGRAM_PUBKEY),
+            create_program_data_account(&administrator.pubkey()),
+        );
+        for pubkey in [
+            administrator.pubkey(),
+            fee_recipient.pubkey(),
+            investor.pubkey(),
+            manager.pubkey(),
+            trader.pubkey(),
+        ] {
+            add_funded_account(
+                &mut program_test,
+                &pubkey,
+            );
+        }
+        program_test_configurator(
+            &mut program_test,
+            &intermediary.pubkey(),
+        );
+        let context = program_test.start_with_context().await;
+        install_log_data_syscall_stubs();
+        let (config_pubkey, _) = ConfigAddressResolver.find();
+        let (fee_vault_pubkey, _) = FeeVaultAddressResolver {
+            config_pubkey: &config_pubkey,
+        }
+        .find();
+        Self {
+            context,
+            administrator,
+            fee_recipient,
+            investor,
+            manager,
+            trader,
+            intermediary,
+            config_pubkey,
+            fee_vault_pubkey,
+        }
+    }
+    pub async fn process<'a>(&'a mut self, instructions: &'a [Instruction_], signers: &'a [&'a Keypair]) -> Result<Vec<VersionedEvent>, BanksClientError> {
+        let recent_blockhash = self.context.banks_client.get_latest_blockhash().await?;
+        let mut signers_ = vec![&self.context.payer];
+        signers_.extend_from_slice(signers);
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&self.context.payer.pubkey()),
+            signers_.as_slice(),
+            recent_blockhash,
+        );
+        let banks_transaction_result_with_metadata = self.context.banks_client.process_transaction_with_metadata(transaction).await?;
+        banks_transaction_result_with_metadata.result?;
+        let log_messages = match banks_transaction_result_with_metadata.metadata {
+            Some(transaction_metadata) => transaction_metadata.log_messages,
+            None => vec![],
+        };
+        let log_messages = log_messages
+            .into_iter()
+            .map(
+                |log_message| match log_message.strip_prefix(LOG_DATA_PREFIX) {
+                    Some(program_data) => format!("{}{}", PROGRAM_DATA_PREFIX, program_data),
+                    None => log_message,
+                },
+            )
+            .collect::<Vec<String>>();
+        Ok(VersionedEvent::decode_log_messages(log_messages.as_slice()).unwrap())
+    }
+    pub async fn get_account<'a>(&'a mut self, pubkey: &'a Pubkey) -> Option<Account> {
+        self.context.banks_client.get_account(*pubkey).await.unwrap()
+    }
+    pub async fn get_lamports<'a>(&'a mut self, pubkey: &'a Pubkey) -> u64 {
+        match self.get_account(pubkey).await {
+            Some(account) => account.lamports,
+            None => 0,
+        }
+    }
+    pub async fn get_token_account_amount<'a>(&'a mut self, pubkey: &'a Pubkey) -> u64 {
+        StateWithExtensions::<spl_token_2022::state::Account>::unpack(self.get_account(pubkey).await.unwrap().data.as_slice()).unwrap().base.amount
+    }
+    pub async fn get_intermediary<'a>(&'a mut self) -> Intermediary {
+        let intermediary_pubkey = self.intermediary.pubkey();
+        Intermediary::load(self.get_account(&intermediary_pubkey).await.unwrap().data.as_slice()).unwrap()
+    }
+    pub fn intermediary_pubkeys<'a>(&'a self) -> IntermediaryPubkeys {
+        IntermediaryPubkeys::new(&self.intermediary.pubkey())
+    }
+    pub fn trader_seat_pubkey<'a>(&'a self, trader_pubkey: &'a Pubkey) -> (Pubkey, u8) {
+        TraderSeatAddressResolver {
+            intermediary_pubkey: &self.intermediary.pubkey(),
+            trader_pubkey,
+        }
+        .find()
+    }
+    pub async fn get_trader_seat<'a>(&'a mut self, trader_pubkey: &'a Pubkey) -> TraderSeat {
+        let (trader_seat_pubkey, _) = self.trader_seat_pubkey(trader_pubkey);
+        borsh::from_slice::<TraderSeat>(self.get_account(&trader_seat_pubkey).await.unwrap().data.as_slice()).unwrap()
+    }
+    pub async fn initialize_config<'a>(&'a mut self) -> Vec<VersionedEvent> {
+        let (config_pubkey, config_pubkey_bump_seed) = ConfigAddressResolver.find();
+        let (fee_vault_pubkey, fee_vault_pubkey_bump_seed) = FeeVaultAddressResolver {
+            config_pubkey: &config_pubkey,
+        }
+        .find();
+        let instruction = Instruction::initialize_config(
+            &PROGRAM_PUBKEY,
+            &self.administrator.pubkey(),
+            &config_pubkey,
+            &fee_vault_pubkey,
+            &self.fee_recipient.pubkey(),
+            &bpf_loader_upgradeable::get_program_data_address(&PROGRAM_PUBKEY),
+            FEES,
+            config_pubkey_bump_seed,
+            fee_vault_pubkey_bump_seed,
+        )
+        .unwrap();
+        let administrator = self.administrator.insecure_clone();
+        self.process(
+            [instruction].as_slice(),
+            [&administrator].as_slice(),
+        )
+        .await
+        .unwrap()
+    }
+    pub async fn initialize_intermediary<'a>(&'a mut self, lamports_to_treasury: u64) -> Vec<VersionedEvent> {
+        let intermediary_pubkeys = self.intermediary_pubkeys();
+        let instruction = Instruction::initialize(
+            &PROGRAM_PUBKEY,
+            &self.investor.pubkey(),
+            &self.intermediary.pubkey(),
+            &self.manager.pubkey(),
+            &self.trader.pubkey(),
+            &intermediary_pubkeys.w_sol_token_account_pubkey,
+            &intermediary_pubkeys.w_sol_token_account_authority_pubkey,
+            &intermediary_pubkeys.temporary_w_sol_token_account_pubkey,
+            &intermediary_pubkeys.temporary_w_sol_token_account_authority_pubkey,
+            &self.config_pubkey,
+            &self.fee_vault_pubkey,
+            lamports_to_treasury,
+            intermediary_pubkeys.w_sol_token_account_pubkey_bump_seed,
+            intermediary_pubkeys.w_sol_token_account_authority_pubkey_bump_seed,
+            intermediary_pubkeys.temporary_w_sol_token_account_pubkey_bump_seed,
+            intermediary_pubkeys.temporary_w_sol_token_account_authority_pubkey_bump_seed,
+        )
+        .unwrap();
+        let investor = self.investor.insecure_clone();
+        let intermediary = self.intermediary.insecure_clone();
+        self.process(
+            [instruction].as_slice(),
+            [&investor, &intermediary].as_slice(),
+        )
+        .await
+        .unwrap()
+    }
+    pub async fn set_risk_limits<'a>(
+        &'a mut self,
+        maximum_lamports_per_trade: u64,
+        maximum_treasury_share_per_trade_basis_points: u16,
+        maximum_trades_per_slot_window: u64,
+        slot_window_length: u64,
+    ) -> Result<Vec<VersionedEvent>, BanksClientError> {
+        let intermediary_pubkeys = self.intermediary_pubkeys();
+        let instruction = Instruction::set_risk_limits(
+            &PROGRAM_PUBKEY,
+            &self.manager.pubkey(),
+            &self.intermediary.pubkey(),
+            &intermediary_pubkeys.risk_limits_pubkey,
+            maximum_lamports_per_trade,
+            maximum_treasury_share_per_trade_basis_points,
+            maximum_trades_per_slot_window,
+            slot_window_length,
+            intermediary_pubkeys.risk_limits_pubkey_bump_seed,
+        )
+        .unwrap();
+        let manager = self.manager.insecure_clone();
+        self.process(
+            [instruction].as_slice(),
+            [&manager].as_slice(),
+        )
+        .await
+    }
+    pub async fn add_allowed_mint<'a>(&'a mut self, any_mint_token_mint_pubkey: &'a Pubkey) -> Result<Vec<VersionedEvent>, BanksClientError> {
+        let intermediary_pubkeys = self.intermediary_pubkeys();
+        let instruction = Instruction::add_allowed_mint(
+            &PROGRAM_PUBKEY,
+            &self.manager.pubkey(),
+            &self.intermediary.pubkey(),
+            &intermediary_pubkeys.allowed_mints_pubkey,
+            any_mint_token_mint_pubkey,
+            intermediary_pubkeys.allowed_mints_pubkey_bump_seed,
+        )
+        .unwrap();
+        let manager = self.manager.insecure_clone();
+        self.process(
+            [instruction].as_slice(),
+            [&manager].as_slice(),
+        )
+        .await
+    }
+    pub async fn add_trader_seat<'a>(&'a mut self, trader_pubkey: &'a Pubkey, allocated_lamports: u64) -> Result<Vec<VersionedEvent>, BanksClientError> {
+        let (trader_seat_pubkey, trader_seat_pubkey_bump_seed) = self.trader_seat_pubkey(trader_pubkey);
+        let instruction = Instruction::add_trader_seat(
+            &PROGRAM_PUBKEY,
+            &self.manager.pubkey(),
+            &self.intermediary.pubkey(),
+            trader_pubkey,
+            &trader_seat_pubkey,
+            allocated_lamports,
+            trader_seat_pubkey_bump_seed,
+        )
+        .unwrap();
+        let manager = self.manager.insecure_clone();
+        self.process(
+            [instruction].as_slice(),
+            [&manager].as_slice(),
+        )
+        .await
+    }
+    pub async fn withdraw_funds<'a>(&'a mut self, lamports_from_treasury: u64) -> Result<Vec<VersionedEvent>, BanksClientError> {
+        let intermediary_pubkeys = self.intermediary_pubkeys();
+        let instruction = Instruction::withdraw_funds(
+            &PROGRAM_PUBKEY,
+            &self.investor.pubkey(),
+            &self.intermediary.pubkey(),
+            &intermediary_pubkeys.w_sol_token_account_pubkey,
+            &intermediary_pubkeys.w_sol_token_account_authority_pubkey,
+            &intermediary_pubkeys.temporary_w_sol_token_account_pubkey,
+            &intermediary_pubkeys.temporary_w_sol_token_account_authority_pubkey,
+            &self.config_pubkey,
+            &self.fee_vault_pubkey,
+            lamports_from_treasury,
+        )
+        .unwrap();
+        let investor = self.investor.insecure_clone();
+        self.process(
+            [instruction].as_slice(),
+            [&investor].as_slice(),
+        )
+        .await
+    }
+    // Opens a position of `any_mint_token_mint_pubkey` on the mock PumpSwap pool with the environment trader.
+    pub async fn buy_on_mock_pumpswap<'a>(
+        &'a mut self,
+        mock_pumpswap_pool: &'a MockPumpswapPool,
+        any_mint_token_mint_pubkey: &'a Pubkey,
+        base_amount_out: u64,
+        maximum_quote_amount_in: u64,
+    ) -> Result<Vec<VersionedEvent>, BanksClientError> {
+        let intermediary_pubkey = self.intermediary.pubkey();
+        let intermediary_pubkeys = self.intermediary_pubkeys();
+        let (any_mint_token_account_pubkey, any_mint_token_account_pubkey_bump_seed) = AnyMintTokenAccountAddressResolver {
+            any_mint_token_mint_pubkey,
+            intermediary_pubkey: &intermediary_pubkey,
+        }
+        .find();
+        let (any_mint_token_account_authority_pubkey, any_mint_token_account_authority_pubkey_bump_seed) = AnyMintTokenAccountAuthorityAddressResolver {
+            any_mint_token_account_pubkey: &any_mint_token_account_pubkey,
+            intermediary_pubkey: &intermediary_pubkey,
+        }
+        .find();
+        let (trader_seat_pubkey, _) = self.trader_seat_pubkey(&self.trader.pubkey());
+        let instruction = Instruction::buy_on_pumpswap(
+            &PROGRAM_PUBKEY,
+            &self.trader.pubkey(),
+            &intermediary_pubkey,
+            &intermediary_pubkeys.w_sol_token_account_pubkey,
+            &intermediary_pubkeys.w_sol_token_account_authority_pubkey,
+            &intermediary_pubkeys.temporary_w_sol_token_account_pubkey,
+            &intermediary_pubkeys.temporary_w_sol_token_account_authority_pubkey,
+            &any_mint_token_account_pubkey,
+            &any_mint_token_account_authority_pubkey,
+            any_mint_token_mint_pubkey,
+            &PUMPSWAP_PROGRAM_PUBKEY,
+            &mock_pumpswap_pool.pool_pubkey,
+            &mock_pumpswap_pool.filler_pubkey,
+            &mock_pumpswap_pool.pool_base_token_account_pubkey,
+            &mock_pumpswap_pool.pool_quote_token_account_pubkey,
+            &mock_pumpswap_pool.filler_pubkey,
+            &mock_pumpswap_pool.protocol_fee_recipient_token_account_pubkey,
+            &spl_token::ID,
+            &spl_token::ID,
+            &mock_pumpswap_pool.filler_pubkey,
+            &intermediary_pubkeys.allowed_mints_pubkey,
+            &intermediary_pubkeys.risk_limits_pubkey,
+            &trader_seat_pubkey,
+            &self.config_pubkey,
+            &self.fee_vault_pubkey,
+            base_amount_out,
+            maximum_quote_amount_in,
+            any_mint_token_account_pubkey_bump_seed,
+            any_mint_token_account_authority_pubkey_bump_seed,
+        )
+        .unwrap();
+        let trader = self.trader.insecure_clone();
+        self.process(
+            [instruction].as_slice(),
+            [&trader].as_slice(),
+        )
+        .await
+    }
+}
+pub struct IntermediaryPubkeys {
+    pub w_sol_token_account_pubkey: Pubkey,
+    pub w_sol_token_account_pubkey_bump_seed: u8,
+    pub w_sol_token_account_authority_pubkey: Pubkey,
+    pub w_sol_token_account_authority_pubkey_bump_seed: u8,
+    pub temporary_w_sol_token_account_pubkey: Pubkey,
+    pub temporary_w_sol_token_account_pubkey_bump_seed: u8,
+    pub temporary_w_sol_token_account_authority_pubkey: Pubkey,
+    pub temporary_w_sol_token_account_authority_pubkey_bump_seed: u8,
+    pub risk_limits_pubkey: Pubkey,
+    pub risk_limits_pubkey_bump_seed: u8,
+    pub allowed_mints_pubkey: Pubkey,
+    pub allowed_mints_pubkey_bump_seed: u8,
+}
+impl IntermediaryPubkeys {
+    pub fn new<'a>(intermediary_pubkey: &'a Pubkey) -> Self {
+        let (w_sol_token_account_pubkey, w_sol_token_account_pubkey_bump_seed) = WSolTokenAccountAddressResolver {
+            intermediary_pubkey,
+        }
+        .find();
+        let (w_sol_token_account_authority_pubkey, w_sol_token_account_authority_pubkey_bump_seed) = WSolTokenAccountAuthorityAddressResolver {
+            w_sol_token_account_pubkey: &w_sol_token_account_pubkey,
+            intermediary_pubkey,
+        }
+        .find();
+        let (temporary_w_sol_token_account_pubkey, temporary_w_sol_token_account_pubkey_bump_seed) = TemporaryWSolTokenAccountAddressResolver {
+            w_sol_token_account_pubkey: &w_sol_token_account_pubkey,
+        }
+        .find();
+        let (temporary_w_sol_token_account_authority_pubkey, temporary_w_sol_token_account_authority_pubkey_bump_seed) = TemporaryWSolTokenAccountAuthorityAddressResolver {
+            temporary_w_sol_token_account_pubkey: &temporary_w_sol_token_account_pubkey,
+            intermediary_pubkey,
+        }
+        .find();
+        let (risk_limits_pubkey, risk_limits_pubkey_bump_seed) = RiskLimitsAddressResolver {
+            intermediary_pubkey,
+        }
+        .find();
+        let (allowed_mints_pubkey, allowed_mints_pubkey_bump_seed) = AllowedMintsAddressResolver {
+            intermediary_pubkey,
+        }
+        .find();
+        Self {
+            w_sol_token_account_pubkey,
+            w_sol_token_account_pubkey_bump_seed,
+            w_sol_token_account_authority_pubkey,
+            w_sol_token_account_authority_pubkey_bump_seed,
+            temporary_w_sol_token_account_pubkey,
+            temporary_w_sol_token_account_pubkey_bump_seed,
+            temporary_w_sol_token_account_authority_pubkey,
+            temporary_w_sol_token_account_authority_pubkey_bump_seed,
+            risk_limits_pubkey,
+            risk_limits_pubkey_bump_seed,
+            allowed_mints_pubkey,
+            allowed_mints_pubkey_bump_seed,
+        }
+    }
+}
+// The processor runs as a builtin, where `sol_log_data` prints to stdout instead of the transaction log.
+fn install_log_data_syscall_stubs() {
+    static ONCE: Once = Once::new();
+    ONCE.call_once(
+        || {
+            let syscall_stubs = program_stubs::set_syscall_stubs(Box::new(LogDataSyscallStubs::default()));
+            program_stubs::set_syscall_stubs(
+                Box::new(
+                    LogDataSyscallStubs {
+                        syscall_stubs: Some(syscall_stubs),
+                    },
+                ),
+            );
+        },
+    );
+}
+#[derive(Default)]
+struct LogDataSyscallStubs {
+    syscall_stubs: Option<Box<dyn SyscallStubs>>,
+}
+impl LogDataSyscallStubs {
+    fn get<'a>(&'a self) -> &'a dyn SyscallStubs {
+        self.syscall_stubs.as_deref().unwrap()
+    }
+}
+impl SyscallStubs for LogDataSyscallStubs {
+    fn sol_log(&self, message: &str) {
+        self.get().sol_log(message)
+    }
+    fn sol_log_compute_units(&self) {
+        self.get().sol_log_compute_units()
+    }
+    fn sol_remaining_compute_units(&self) -> u64 {
+        self.get().sol_remaining_compute_units()
+    }
+    fn sol_invoke_signed(&self, instruction: &Instruction_, account_infos: &[AccountInfo], signers_seeds: &[&[&[u8]]]) -> ProgramResult {
+        self.get().sol_invoke_signed(
+            instruction,
+            account_infos,
+            signers_seeds,
+        )
+    }
+    fn sol_get_sysvar(&self, sysvar_id_addr: *const u8, var_addr: *mut u8, offset: u64, length: u64) -> u64 {
+        self.get().sol_get_sysvar(
+            sysvar_id_addr,
+            var_addr,
+            offset,
+            length,
+        )
+    }
+    fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+        self.get().sol_get_clock_sysvar(var_addr)
+    }
+    fn sol_get_epoch_schedule_sysvar(&self, var_addr: *mut u8) -> u64 {
+        self.get().sol_get_epoch_schedule_sysvar(var_addr)
+    }
+    fn sol_get_fees_sysvar(&self, var_addr: *mut u8) -> u64 {
+        self.get().sol_get_fees_sysvar(var_addr)
+    }
+    fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+        self.get().sol_get_rent_sysvar(var_addr)
+    }
+    fn sol_get_epoch_rewards_sysvar(&self, var_addr: *mut u8) -> u64 {
+        self.get().sol_get_epoch_rewards_sysvar(var_addr)
+    }
+    fn sol_get_last_restart_slot(&self, var_addr: *mut u8) -> u64 {
+        self.get().sol_get_last_restart_slot(var_addr)
+    }
+    fn sol_get_epoch_stake(&self, vote_address: *const u8) -> u64 {
+        self.get().sol_get_epoch_stake(vote_address)
+    }
+    unsafe fn sol_memcpy(&self, dst: *mut u8, src: *const u8, n: usize) {
+        self.get().sol_memcpy(
+            dst,
+            src,
+            n,
+        )
+    }
+    unsafe fn sol_memmove(&self, dst: *mut u8, src: *const u8, n: usize) {
+        self.get().sol_memmove(
+            dst,
+            src,
+            n,
+        )
+    }
+    unsafe fn sol_memcmp(&self, s1: *const u8, s2: *const u8, n: usize, result: *mut i32) {
+        self.get().sol_memcmp(
+            s1,
+            s2,
+            n,
+            result,
+        )
+    }
+    unsafe fn sol_memset(&self, s: *mut u8, c: u8, n: usize) {
+        self.get().sol_memset(
+            s,
+            c,
+            n,
+        )
+    }
+    fn sol_get_return_data(&self) -> Option<(Pubkey, Vec<u8>)> {
+        self.get().sol_get_return_data()
+    }
+    fn sol_set_return_data(&self, data: &[u8]) {
+        self.get().sol_set_return_data(data)
+    }
+    fn sol_log_data(&self, fields: &[&[u8]]) {
+        let program_data = fields
+            .iter()
+            .map(|field| base64::engine::general_purpose::STANDARD.encode(field))
+            .collect::<Vec<String>>()
+            .join(" ");
+        self.get().sol_log(format!("data: {}", program_data).as_str())
+    }
+    fn sol_get_processed_sibling_instruction(&self, index: usize) -> Option<Instruction_> {
+        self.get().sol_get_processed_sibling_instruction(index)
+    }
+    fn sol_get_stack_height(&self) -> u64 {
+        self.get().sol_get_stack_height()
+    }
+}
+pub fn create_token_account<'a>(mint_pubkey: &'a Pubkey, owner_pubkey: &'a Pubkey, amount: u64) -> Account {
+    let token_account = TokenAccount {
+        mint: *mint_pubkey,
+        owner: *owner_pubkey,
+        amount,
+        delegate: COption::None,
+        state: spl_token::state::AccountState::Initialized,
+        is_native: COption::None,
+        delegated_amount: 0,
+        close_authority: COption::None,
+    };
+    let mut account = Account::new(
+        LAMPORTS_PER_SOL / 100,
+        TokenAccount::LEN,
+        &spl_token::ID,
+    );
+    TokenAccount::pack(
+        token_account,
+        account.data.as_mut_slice(),
+    )
+    .unwrap();
+    account
+}
+// A wrapped SOL account backs its amount with lamports, as the wSOL vaults of the venues do.
+pub fn create_w_sol_token_account<'a>(owner_pubkey: &'a Pubkey, amount: u64) -> Account {
+    let rent_exemption_balance = Rent::default().minimum_balance(TokenAccount::LEN);
+    let token_account = TokenAccount {
+        mint: spl_token::native_mint::ID,
+        owner: *owner_pubkey,
+        amount,
+        delegate: COption::None,
+        state: spl_token::state::AccountState::Initialized,
+        is_native: COption::Some(rent_exemption_balance),
+        delegated_amount: 0,
+        close_authority: COption::None,
+    };
+    let mut account = Account::new(
+        rent_exemption_balance + amount,
+        TokenAccount::LEN,
+        &spl_token::ID,
+    );
+    TokenAccount::pack(
+        token_account,
+        account.data.as_mut_slice(),
+    )
+    .unwrap();
+    account
+}
+pub fn create_mint_account(decimals: u8) -> Account {
+    let mint = Mint {
+        mint_authority: COption::None,
+        supply: 0,
+        decimals,
+        is_initialized: true,
+        freeze_authority: COption::None,
+    };
+    let mut account = Account::new(
+        LAMPORTS_PER_SOL,
+        Mint::LEN,
+        &spl_token::ID,
+    );
+    Mint::pack(
+        mint,
+        account.data.as_mut_slice(),
+    )
+    .unwrap();
+    account
+}
+pub fn create_token_2022_account<'a>(mint_pubkey: &'a Pubkey, owner_pubkey: &'a Pubkey, amount: u64) -> Account {
+    let mut account = Account::new(
+        LAMPORTS_PER_SOL / 100,
+        ExtensionType::try_calculate_account_len::<spl_token_2022::state::Account>([ExtensionType::TransferFeeAmount].as_slice()).unwrap(),
+        &spl_token_2022::ID,
+    );
+    let mut state = StateWithExtensionsMut::<spl_token_2022::state::Account>::unpack_uninitialized(account.data.as_mut_slice()).unwrap();
+    state.init_extension::<TransferFeeAmount>(true).unwrap();
+    state.base = spl_token_2022::state::Account {
+        mint: *mint_pubkey,
+        owner: *owner_pubkey,
+        amount,
+        delegate: COption::None,
+        state: spl_token_2022::state::AccountState::Initialized,
+        is_native: COption::None,
+        delegated_amount: 0,
+        close_authority: COption::None,
+    };
+    state.pack_base();
+    state.init_account_type().unwrap();
+    account
+}
+pub fn create_token_2022_mint_account_with_transfer_fee(decimals: u8, transfer_fee_basis_points: u16) -> Account {
+    let mut account = Account::new(
+        LAMPORTS_PER_SOL,
+        ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>([ExtensionType::TransferFeeConfig].as_slice()).unwrap(),
+        &spl_token_2022::ID,
+    );
+    let mut state = StateWithExtensionsMut::<spl_token_2022::state::Mint>::unpack_uninitialized(account.data.as_mut_slice()).unwrap();
+    let transfer_fee = TransferFee {
+        epoch: 0.into(),
+        maximum_fee: u64::MAX.into(),
+        transfer_fee_basis_points: transfer_fee_basis_points.into(),
+    };
+    let transfer_fee_config = state.init_extension::<TransferFeeConfig>(true).unwrap();
+    transfer_fee_config.older_transfer_fee = transfer_fee;
+    transfer_fee_config.newer_transfer_fee = transfer_fee;
+    state.base = spl_token_2022::state::Mint {
+        mint_authority: COption::None,
+        supply: 0,
+        decimals,
+        is_initialized: true,
+        freeze_authority: COption::None,
+    };
+    state.pack_base();
+    state.init_account_type().unwrap();
+    account
+}
+// https://github.com/anza-xyz/agave/blob/v2.1.15/sdk/program/src/bpf_loader_upgradeable.rs#L29
+fn create_program_data_account<'a>(upgrade_authority_pubkey: &'a Pubkey) -> Account {
+    let mut data = Vec::<u8>::with_capacity(45);
+    data.extend_from_slice(3_u32.to_le_bytes().as_slice());
+    data.extend_from_slice(0_u64.to_le_bytes().as_slice());
+    data.push(1);
+    data.extend_from_slice(upgrade_authority_pubkey.as_ref());
+    Account {
+        lamports: LAMPORTS_PER_SOL,
+        data,
+        owner: bpf_loader_upgradeable::ID,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+pub fn add_funded_account<'a>(program_test: &'a mut ProgramTest, pubkey: &'a Pubkey) {
+    program_test.add_account(
+        *pubkey,
+        Account::new(
+            FUNDED_ACCOUNT_LAMPORTS,
+            0,
+            &system_program::ID,
+        ),
+    );
+}
+pub fn assert_error<T>(result: Result<T, BanksClientError>, error: Error) {
+    match result {
+        Err(BanksClientError::TransactionError(TransactionError::InstructionError(_, InstructionError::Custom(code)))) => assert_eq!(code, error as u32),
+        Err(banks_client_error) => panic!("Unexpected error: {:?}.", banks_client_error),
+        Ok(_) => panic!("The transaction was expected to fail."),
+    }
+}
+// The venues run as the mock programs below rather than as the venue programs cloned from mainnet: the harness runs without
+// network, and neither the program binaries nor the pools can be fetched offline. The mocks take the accounts and data of the real swap
+// instructions, so the intermediary is exercised through the same CPIs, while the pool layouts are covered by the quoter tests.
+// Stands in for Raydium AMM v4 offline. `swap_base_in` and `swap_base_out` are constant product swaps between the two vaults of the pool,
+// owned by the amm authority. Only the fee set through `MockRaydiumPool::add_fee` is read from the pool, the open orders and market accounts are not read.
+pub fn process_mock_raydium_swap<'a>(program_id: &'a Pubkey, accounts: &'a [AccountInfo], input: &'a [u8]) -> ProgramResult {
+    let [
+        token_program_account_info,
+        amm_pool_account_info,
+        amm_authority_account_info,
+        _,
+        amm_coin_vault_account_info,
+        amm_pc_vault_account_info,
+        _,
+        _,
+        _,
+        _,
+        _,
+        _,
+        _,
+        _,
+        user_token_source_account_info,
+        user_token_destination_account_info,
+        user_source_owner_account_info,
+    ] = accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    if input.len() != 17 {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    let first_amount = u64::from_le_bytes(input[1..9].try_into().unwrap());
+    let second_amount = u64::from_le_bytes(input[9..17].try_into().unwrap());
+    let user_token_source = TokenAccount::unpack(&user_token_source_account_info.data.borrow())?;
+    let (input_vault_account_info, output_vault_account_info) = if TokenAccount::unpack(&amm_coin_vault_account_info.data.borrow())?.mint == user_token_source.mint {
+        (amm_coin_vault_account_info, amm_pc_vault_account_info)
+    } else {
+        (amm_pc_vault_account_info, amm_coin_vault_account_info)
+    };
+    let input_reserve = TokenAccount::unpack(&input_vault_account_info.data.borrow())?.amount;
+    let output_reserve = TokenAccount::unpack(&output_vault_account_info.data.borrow())?.amount;
+    let fee_basis_points = get_mock_raydium_pool_fee_basis_points(amm_pool_account_info) as u128;
+    let (amount_in, amount_out) = match input[0] {
+        // swap_base_in { amount_in, minimum_amount_out }
+        9 => {
+            let amount_in_after_fee = (first_amount as u128) * (10_000 - fee_basis_points) / 10_000;
+            let amount_out = ((output_reserve as u128) * amount_in_after_fee / ((input_reserve as u128) + amount_in_after_fee)) as u64;
+            if amount_out < second_amount {
+                return Err(ProgramError::InsufficientFunds);
+            }
+            (
+                first_amount,
+                amount_out,
+            )
+        }
+        // swap_base_out { maximum_amount_in, amount_out }
+        11 => {
+            if second_amount >= output_reserve {
+                return Err(ProgramError::InsufficientFunds);
+            }
+            let amount_in_after_fee = ((input_reserve as u128) * (second_amount as u128)).div_ceil((output_reserve - second_amount) as u128);
+            let amount_in = (amount_in_after_fee * 10_000).div_ceil(10_000 - fee_basis_points) as u64;
+            if amount_in > first_amount {
+                return Err(ProgramError::InsufficientFunds);
+            }
+            (
+                amount_in,
+                second_amount,
+            )
+        }
+        _ => return Err(ProgramError::InvalidInstructionData),
+    };
+    invoke(
+        &spl_token::instruction::transfer(
+            token_program_account_info.key,
+            user_token_source_account_info.key,
+            input_vault_account_info.key,
+            user_source_owner_account_info.key,
+            [].as_slice(),
+            amount_in,
+        )?,
+        [
+            user_token_source_account_info.clone(),
+            input_vault_account_info.clone(),
+            user_source_owner_account_info.clone(),
+        ]
+        .as_slice(),
+    )?;
+    let (_, amm_authority_nonce) = Pubkey::find_program_address(
+        [MOCK_RAYDIUM_AMM_AUTHORITY_SEED].as_slice(),
+        program_id,
+    );
+    invoke_signed(
+        &spl_token::instruction::transfer(
+            token_program_account_info.key,
+            output_vault_account_info.key,
+            user_token_destination_account_info.key,
+            amm_authority_account_info.key,
+            [].as_slice(),
+            amount_out,
+        )?,
+        [
+            output_vault_account_info.clone(),
+            user_token_destination_account_info.clone(),
+            amm_authority_account_info.clone(),
+        ]
+        .as_slice(),
+        [
+            [
+                MOCK_RAYDIUM_AMM_AUTHORITY_SEED,
+                [amm_authority_nonce].as_slice(),
+            ]
+            .as_slice(),
+        ]
+        .as_slice(),
+    )
+}
+const MOCK_RAYDIUM_AMM_AUTHORITY_SEED: &'static [u8] = b"amm authority";
+// A pool without an account of its own swaps without a fee.
+fn get_mock_raydium_pool_fee_basis_points<'a>(amm_pool_account_info: &'a AccountInfo) -> u16 {
+    match amm_pool_account_info.data.borrow().get(..2) {
+        Some(fee_basis_points) => u16::from_le_bytes(fee_basis_points.try_into().unwrap()),
+        None => 0,
+    }
+}
+pub fn add_mock_raydium_program<'a>(program_test: &'a mut ProgramTest) {
+    program_test.add_program(
+        "mock_raydium_amm_v4",
+        RAYDIUM_LIQUIDITY_POOL_V4_PROGRAM_PUBKEY,
+        processor!(process_mock_raydium_swap),
+    );
+}
+pub struct MockRaydiumPool {
+    pub amm_authority_pubkey: Pubkey,
+    pub amm_coin_vault_pubkey: Pubkey,
+    pub amm_pc_vault_pubkey: Pubkey,
+    // One pubkey stands for the pool, open orders and market accounts, so a route fits the static accounts of a transaction.
+    pub filler_pubkey: Pubkey,
+}
+impl MockRaydiumPool {
+    pub fn add<'a>(program_test: &'a mut ProgramTest, coin_mint_pubkey: &'a Pubkey, pc_mint_pubkey: &'a Pubkey, coin_reserve: u64, pc_reserve: u64) -> Self {
+        let (amm_authority_pubkey, _) = Pubkey::find_program_address(
+            [MOCK_RAYDIUM_AMM_AUTHORITY_SEED].as_slice(),
+            &RAYDIUM_LIQUIDITY_POOL_V4_PROGRAM_PUBKEY,
+        );
+        let amm_coin_vault_pubkey = Pubkey::new_unique();
+        let amm_pc_vault_pubkey = Pubkey::new_unique();
+        for (amm_vault_pubkey, mint_pubkey, reserve) in [
+            (
+                amm_coin_vault_pubkey,
+                coin_mint_pubkey,
+                coin_reserve,
+            ),
+            (
+                amm_pc_vault_pubkey,
+                pc_mint_pubkey,
+                pc_reserve,
+            ),
+        ] {
+            program_test.add_account(
+                amm_vault_pubkey,
+                if *mint_pubkey == spl_token::native_mint::ID {
+                    create_w_sol_token_account(
+                        &amm_authority_pubkey,
+                        reserve,
+                    )
+                } else {
+                    create_token_account(
+                        mint_pubkey,
+                        &amm_authority_pubkey,
+                        reserve,
+                    )
+                },
+            );
+        }
+        Self {
+            amm_authority_pubkey,
+            amm_coin_vault_pubkey,
+            amm_pc_vault_pubkey,
+            filler_pubkey: Pubkey::new_unique(),
+        }
+    }
+    // The pool keeps `fee_basis_points` of the input, so its output falls short of the constant product output.
+    pub fn add_fee<'a>(&'a self, program_test: &'a mut ProgramTest, fee_basis_points: u16) {
+        program_test.add_account(
+            self.filler_pubkey,
+            Account {
+                lamports: LAMPORTS_PER_SOL,
+                data: fee_basis_points.to_le_bytes().to_vec(),
+                owner: RAYDIUM_LIQUIDITY_POOL_V4_PROGRAM_PUBKEY,
+                executable: false,
+                rent_epoch: 0,
+            },
+        );
+    }
+    // In the order of `RaydiumAdapter`.
+    pub fn get_venue_account_pubkeys<'a>(&'a self) -> Vec<Pubkey> {
+        vec![
+            RAYDIUM_LIQUIDITY_POOL_V4_PROGRAM_PUBKEY,
+            self.filler_pubkey,
+            self.amm_authority_pubkey,
+            self.filler_pubkey,
+            self.amm_coin_vault_pubkey,
+            self.amm_pc_vault_pubkey,
+            self.filler_pubkey,
+            self.filler_pubkey,
+            self.filler_pubkey,
+            self.filler_pubkey,
+            self.filler_pubkey,
+            self.filler_pubkey,
+            self.filler_pubkey,
+            self.filler_pubkey,
+        ]
+    }
+}
+// Stands in for PumpSwap offline. `buy` and `sell` are a fee-less constant product swap between the two token accounts of the pool,
+// owned by the pool. The global config, protocol fee recipient and event authority accounts are not read.
+pub fn process_mock_pumpswap_swap<'a>(program_id: &'a Pubkey, accounts: &'a [AccountInfo], input: &'a [u8]) -> ProgramResult {
+    let [
+        pool_account_info,
+        user_account_info,
+        _,
+        _,
+        _,
+        user_base_token_account_account_info,
+        user_quote_token_account_account_info,
+        pool_base_token_account_account_info,
+        pool_quote_token_account_account_info,
+        _,
+        _,
+        base_token_program_account_info,
+        quote_token_program_account_info,
+        _,
+        _,
+        _,
+        _,
+    ] = accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    if input.len() != 24 {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    let base_amount = u64::from_le_bytes(input[8..16].try_into().unwrap());
+    let quote_amount_limit = u64::from_le_bytes(input[16..24].try_into().unwrap());
+    let base_reserve = TokenAccount::unpack(&pool_base_token_account_account_info.data.borrow())?.amount;
+    let quote_reserve = TokenAccount::unpack(&pool_quote_token_account_account_info.data.borrow())?.amount;
+    // A buy takes the exact base amount out for at most the quote limit, a sell puts the exact base amount in for at least the quote limit.
+    let (
+        (input_token_program_account_info, user_input_token_account_account_info, pool_input_token_account_account_info, amount_in),
+        (output_token_program_account_info, pool_output_token_account_account_info, user_output_token_account_account_info, amount_out),
+    ) = if input[..8] == MOCK_PUMPSWAP_BUY_DISCRIMINATOR {
+        if base_amount >= base_reserve {
+            return Err(ProgramError::InsufficientFunds);
+        }
+        let quote_amount_in = ((quote_reserve as u128) * (base_amount as u128)).div_ceil((base_reserve - base_amount) as u128) as u64;
+        if quote_amount_in > quote_amount_limit {
+            return Err(ProgramError::InsufficientFunds);
+        }
+        (
+            (quote_token_program_account_info, user_quote_token_account_account_info, pool_quote_token_account_account_info, quote_amount_in),
+            (base_token_program_account_info, pool_base_token_account_account_info, user_base_token_account_account_info, base_amount),
+        )
+    } else if input[..8] == MOCK_PUMPSWAP_SELL_DISCRIMINATOR {
+        let quote_amount_out = ((quote_reserve as u128) * (base_amount as u128) / ((base_reserve as u128) + (base_amount as u128))) as u64;
+        if quote_amount_out < quote_amount_limit {
+            return Err(ProgramError::InsufficientFunds);
+        }
+        (
+            (base_token_program_account_info, user_base_token_account_account_info, pool_base_token_account_account_info, base_amount),
+            (quote_token_program_account_info, pool_quote_token_account_account_info, user_quote_token_account_account_info, quote_amount_out),
+        )
+    } else {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+    invoke(
+        &spl_token::instruction::transfer(
+            input_token_program_account_info.key,
+            user_input_token_account_account_info.key,
+            pool_input_token_account_account_info.key,
+            user_account_info.key,
+            [].as_slice(),
+            amount_in,
+        )?,
+        [
+            user_input_token_account_account_info.clone(),
+            pool_input_token_account_account_info.clone(),
+            user_account_info.clone(),
+        ]
+        .as_slice(),
+    )?;
+    let (_, pool_bump_seed) = Pubkey::find_program_address(
+        [MOCK_PUMPSWAP_POOL_SEED].as_slice(),
+        program_id,
+    );
+    invoke_signed(
+        &spl_token::instruction::transfer(
+            output_token_program_account_info.key,
+            pool_output_token_account_account_info.key,
+            user_output_token_account_account_info.key,
+            pool_account_info.key,
+            [].as_slice(),
+            amount_out,
+        )?,
+        [
+            pool_output_token_account_account_info.clone(),
+            user_output_token_account_account_info.clone(),
+            pool_account_info.clone(),
+        ]
+        .as_slice(),
+        [
+            [
+                MOCK_PUMPSWAP_POOL_SEED,
+                [pool_bump_seed].as_slice(),
+            ]
+            .as_slice(),
+        ]
+        .as_slice(),
+    )
+}
+const MOCK_PUMPSWAP_POOL_SEED: &'static [u8] = b"pool";
+const MOCK_PUMPSWAP_BUY_DISCRIMINATOR: [u8; 8] = [102, 6, 61, 18, 1, 218, 235, 234];
+const MOCK_PUMPSWAP_SELL_DISCRIMINATOR: [u8; 8] = [51, 230, 133, 164, 1, 127, 131, 173];
+pub fn add_mock_pumpswap_program<'a>(program_test: &'a mut ProgramTest) {
+    program_test.add_program(
+        "mock_pumpswap",
+        PUMPSWAP_PROGRAM_PUBKEY,
+        processor!(process_mock_pumpswap_swap),
+    );
+}
+pub struct MockPumpswapPool {
+    pub pool_pubkey: Pubkey,
+    pub pool_base_token_account_pubkey: Pubkey,
+    pub pool_quote_token_account_pubkey: Pubkey,
+    // One pubkey stands for the global config, protocol fee recipient and event authority accounts.
+    pub filler_pubkey: Pubkey,
+    pub protocol_fee_recipient_token_account_pubkey: Pubkey,
+}
+impl MockPumpswapPool {
+    pub fn add<'a>(program_test: &'a mut ProgramTest, base_mint_pubkey: &'a Pubkey, base_reserve: u64, quote_reserve: u64) -> Self {
+        let (pool_pubkey, _) = Pubkey::find_program_address(
+            [MOCK_PUMPSWAP_POOL_SEED].as_slice(),
+            &PUMPSWAP_PROGRAM_PUBKEY,
+        );
+        let pool_base_token_account_pubkey = Pubkey::new_unique();
+        let pool_quote_token_account_pubkey = Pubkey::new_unique();
+        program_test.add_account(
+            pool_base_token_account_pubkey,
+            create_token_account(
+                base_mint_pubkey,
+                &pool_pubkey,
+                base_reserve,
+            ),
+        );
+        program_test.add_account(
+            pool_quote_token_account_pubkey,
+            create_w_sol_token_account(
+                &pool_pubkey,
+                quote_reserve,
+            ),
+        );
+        Self {
+            pool_pubkey,
+            pool_base_token_account_pubkey,
+            pool_quote_token_account_pubkey,
+            filler_pubkey: Pubkey::new_unique(),
+            protocol_fee_recipient_token_account_pubkey: Pubkey::new_unique(),
+        }
+    }
+    // In the order of `PumpswapAdapter`.
+    pub fn get_venue_account_pubkeys<'a>(&'a self) -> Vec<Pubkey> {
+        vec![
+            PUMPSWAP_PROGRAM_PUBKEY,
+            self.pool_pubkey,
+            self.filler_pubkey,
+            self.pool_base_token_account_pubkey,
+            self.pool_quote_token_account_pubkey,
+            self.filler_pubkey,
+            self.protocol_fee_recipient_token_account_pubkey,
+            spl_token::ID,
+            SPL_ASSOCIATED_TOKEN_ACCOUNT_ID,
+            self.filler_pubkey,
+        ]
+    }
+}
+// Moves `amount_in` from the user into the input vault and `amount_out` from the output vault to the user, the output signed by the vault owner.
+fn transfer_through_mock_vaults<'a, 'b>(
+    token_program_account_info: &'a AccountInfo<'b>,
+    user_source_token_account_account_info: &'a AccountInfo<'b>,
+    user_destination_token_account_account_info: &'a AccountInfo<'b>,
+    user_account_info: &'a AccountInfo<'b>,
+    input_vault_account_info: &'a AccountInfo<'b>,
+    output_vault_account_info: &'a AccountInfo<'b>,
+    vault_owner_account_info: &'a AccountInfo<'b>,
+    vault_owner_seeds: &'a [&'a [u8]],
+    amount_in: u64,
+    amount_out: u64,
+) -> ProgramResult {
+    invoke(
+        &spl_token::instruction::transfer(
+            token_program_account_info.key,
+            user_source_token_account_account_info.key,
+            input_vault_account_info.key,
+            user_account_info.key,
+            [].as_slice(),
+            amount_in,
+        )?,
+        [
+            user_source_token_account_account_info.clone(),
+            input_vault_account_info.clone(),
+            user_account_info.clone(),
+        ]
+        .as_slice(),
+    )?;
+    invoke_signed(
+        &spl_token::instruction::transfer(
+            token_program_account_info.key,
+            output_vault_account_info.key,
+            user_destination_token_account_account_info.key,
+            vault_owner_account_info.key,
+            [].as_slice(),
+            amount_out,
+        )?,
+        [
+            output_vault_account_info.clone(),
+            user_destination_token_account_account_info.clone(),
+            vault_owner_account_info.clone(),
+        ]
+        .as_slice(),
+        [vault_owner_seeds].as_slice(),
+    )
+}
+// Fails unless the vault and the user token account both hold `mint_account_info` and `token_program_account_info` owns the mint,
+// so a swap instruction built with its accounts out of order is rejected as the venue would reject it.
+fn enforce_mock_swap_leg<'a, 'b>(
+    vault_account_info: &'a AccountInfo<'b>,
+    user_token_account_account_info: &'a AccountInfo<'b>,
+    mint_account_info: &'a AccountInfo<'b>,
+    token_program_account_info: &'a AccountInfo<'b>,
+) -> Result<u64, ProgramError> {
+    let vault = TokenAccount::unpack(&vault_account_info.data.borrow())?;
+    if vault.mint != *mint_account_info.key
+        || TokenAccount::unpack(&user_token_account_account_info.data.borrow())?.mint != *mint_account_info.key
+        || token_program_account_info.key != mint_account_info.owner
+    {
+        return Err(ProgramError::InvalidArgument);
+    }
+    Ok(vault.amount)
+}
+fn get_constant_product_amount_out(input_reserve: u64, output_reserve: u64, amount_in: u64) -> u64 {
+    ((output_reserve as u128) * (amount_in as u128) / ((input_reserve as u128) + (amount_in as u128))) as u64
+}
+// Stands in for Raydium CPMM offline. `swap_base_input` is a fee-less constant product swap between the two vaults of the pool,
+// owned by the vault authority. The amm config, pool state and observation state accounts are not read.
+pub fn process_mock_raydium_cpmm_swap<'a>(program_id: &'a Pubkey, accounts: &'a [AccountInfo], input: &'a [u8]) -> ProgramResult {
+    let [
+        payer_account_info,
+        authority_account_info,
+        _,
+        _,
+        input_token_account_account_info,
+        output_token_account_account_info,
+        input_vault_account_info,
+        output_vault_account_info,
+        input_token_program_account_info,
+        output_token_program_account_info,
+        input_token_mint_account_info,
+        output_token_mint_account_info,
+        _,
+    ] = accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    if input.len() != 24 || input[..8] != MOCK_RAYDIUM_CPMM_SWAP_BASE_INPUT_DISCRIMINATOR {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    if !payer_account_info.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    let amount_in = u64::from_le_bytes(input[8..16].try_into().unwrap());
+    let minimum_amount_out = u64::from_le_bytes(input[16..24].try_into().unwrap());
+    let (authority_pubkey, authority_bump_seed) = Pubkey::find_program_address(
+        [MOCK_RAYDIUM_CPMM_AUTHORITY_SEED].as_slice(),
+        program_id,
+    );
+    if *authority_account_info.key != authority_pubkey {
+        return Err(ProgramError::InvalidArgument);
+    }
+    let input_reserve = enforce_mock_swap_leg(
+        input_vault_account_info,
+        input_token_account_account_info,
+        input_token_mint_account_info,
+        input_token_program_account_info,
+    )?;
+    let output_reserve = enforce_mock_swap_leg(
+        output_vault_account_info,
+        output_token_account_account_info,
+        output_token_mint_account_info,
+        output_token_program_account_info,
+    )?;
+    let amount_out = get_constant_product_amount_out(
+        input_reserve,
+        output_reserve,
+        amount_in,
+    );
+    if amount_out < minimum_amount_out {
+        return Err(ProgramError::InsufficientFunds);
+    }
+    transfer_through_mock_vaults(
+        input_token_program_account_info,
+        input_token_account_account_info,
+        output_token_account_account_info,
+        payer_account_info,
+        input_vault_account_info,
+        output_vault_account_info,
+        authority_account_info,
+        [
+            MOCK_RAYDIUM_CPMM_AUTHORITY_SEED,
+            [authority_bump_seed].as_slice(),
+        ]
+        .as_slice(),
+        amount_in,
+        amount_out,
+    )
+}
+const MOCK_RAYDIUM_CPMM_AUTHORITY_SEED: &'static [u8] = b"vault_and_lp_mint_auth_seed";
+const MOCK_RAYDIUM_CPMM_SWAP_BASE_INPUT_DISCRIMINATOR: [u8; 8] = [143, 190, 90, 218, 196, 30, 51, 222];
+pub fn add_mock_raydium_cpmm_program<'a>(program_test: &'a mut ProgramTest) {
+    program_test.add_program(
+        "mock_raydium_cpmm",
+        RAYDIUM_CPMM_PROGRAM_PUBKEY,
+        processor!(process_mock_raydium_cpmm_swap),
+    );
+}
+pub struct MockRaydiumCpmmPool {
+    pub authority_pubkey: Pubkey,
+    pub w_sol_vault_pubkey: Pubkey,
+    pub any_mint_vault_pubkey: Pubkey,
+    // One pubkey stands for the amm config, pool state and observation state accounts.
+    pub filler_pubkey: Pubkey,
+}
+impl MockRaydiumCpmmPool {
+    pub fn add<'a>(program_test: &'a mut ProgramTest, any_mint_token_mint_pubkey: &'a Pubkey, any_mint_reserve: u64, w_sol_reserve: u64) -> Self {
+        let (authority_pubkey, _) = Pubkey::find_program_address(
+            [MOCK_RAYDIUM_CPMM_AUTHORITY_SEED].as_slice(),
+            &RAYDIUM_CPMM_PROGRAM_PUBKEY,
+        );
+        let w_sol_vault_pubkey = Pubkey::new_unique();
+        let any_mint_vault_pubkey = Pubkey::new_unique();
+        program_test.add_account(
+            w_sol_vault_pubkey,
+            create_w_sol_token_account(
+                &authority_pubkey,
+                w_sol_reserve,
+            ),
+        );
+        program_test.add_account(
+            any_mint_vault_pubkey,
+            create_token_account(
+                any_mint_token_mint_pubkey,
+                &authority_pubkey,
+                any_mint_reserve,
+            ),
+        );
+        Self {
+            authority_pubkey,
+            w_sol_vault_pubkey,
+            any_mint_vault_pubkey,
+            filler_pubkey: Pubkey::new_unique(),
+        }
+    }
+}
+// Stands in for Raydium CLMM offline. `swap_v2` is a fee-less constant product swap between the two vaults of the pool, owned by the
+// pool state. The pool state keeps the mints, liquidity and square root price the adapter reads, recomputed from the vaults after each swap.
+// The amm config, observation state and tick array accounts are not read.
+pub fn process_mock_raydium_clmm_swap<'a>(program_id: &'a Pubkey, accounts: &'a [AccountInfo], input: &'a [u8]) -> ProgramResult {
+    if accounts.len() < 14 {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+    let [
+        payer_account_info,
+        _,
+        pool_state_account_info,
+        input_token_account_account_info,
+        output_token_account_account_info,
+        input_vault_account_info,
+        output_vault_account_info,
+        _,
+        token_program_account_info,
+        token_2022_program_account_info,
+        memo_program_account_info,
+        input_vault_mint_account_info,
+        output_vault_mint_account_info,
+    ] = &accounts[..13]
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    if input.len() != 41 || input[..8] != MOCK_RAYDIUM_CLMM_SWAP_V2_DISCRIMINATOR || input[40] != 1 {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    if !payer_account_info.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    if *token_2022_program_account_info.key != spl_token_2022::ID || *memo_program_account_info.key != SPL_MEMO_PROGRAM_PUBKEY {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    let amount_in = u64::from_le_bytes(input[8..16].try_into().unwrap());
+    let minimum_amount_out = u64::from_le_bytes(input[16..24].try_into().unwrap());
+    let (pool_state_pubkey, pool_state_bump_seed) = Pubkey::find_program_address(
+        [MOCK_RAYDIUM_CLMM_POOL_STATE_SEED].as_slice(),
+        program_id,
+    );
+    if *pool_state_account_info.key != pool_state_pubkey {
+        return Err(ProgramError::InvalidArgument);
+    }
+    let input_reserve = enforce_mock_swap_leg(
+        input_vault_account_info,
+        input_token_account_account_info,
+        input_vault_mint_account_info,
+        token_program_account_info,
+    )?;
+    let output_reserve = enforce_mock_swap_leg(
+        output_vault_account_info,
+        output_token_account_account_info,
+        output_vault_mint_account_info,
+        token_program_account_info,
+    )?;
+    let amount_out = get_constant_product_amount_out(
+        input_reserve,
+        output_reserve,
+        amount_in,
+    );
+    if amount_out < minimum_amount_out {
+        return Err(ProgramError::InsufficientFunds);
+    }
+    transfer_through_mock_vaults(
+        token_program_account_info,
+        input_token_account_account_info,
+        output_token_account_account_info,
+        payer_account_info,
+        input_vault_account_info,
+        output_vault_account_info,
+        pool_state_account_info,
+        [
+            MOCK_RAYDIUM_CLMM_POOL_STATE_SEED,
+            [pool_state_bump_seed].as_slice(),
+        ]
+        .as_slice(),
+        amount_in,
+        amount_out,
+    )?;
+    let is_input_token_0 = pool_state_account_info.data.borrow()[MOCK_RAYDIUM_CLMM_MINTS_OFFSET..MOCK_RAYDIUM_CLMM_MINTS_OFFSET + 32] == *input_vault_mint_account_info.key.as_ref();
+    let (token_0_reserve, token_1_reserve) = if is_input_token_0 {
+        (input_reserve + amount_in, output_reserve - amount_out)
+    } else {
+        (output_reserve - amount_out, input_reserve + amount_in)
+    };
+    write_mock_raydium_clmm_price(
+        &mut pool_state_account_info.data.borrow_mut(),
+        token_0_reserve,
+        token_1_reserve,
+    );
+    Ok(())
+}
+const MOCK_RAYDIUM_CLMM_POOL_STATE_SEED: &'static [u8] = b"pool";
+const MOCK_RAYDIUM_CLMM_SWAP_V2_DISCRIMINATOR: [u8; 8] = [43, 4, 237, 11, 26, 201, 30, 98];
+// https://github.com/raydium-io/raydium-clmm/blob/master/programs/amm/src/states/pool.rs
+const MOCK_RAYDIUM_CLMM_MINTS_OFFSET: usize = 73;
+const MOCK_RAYDIUM_CLMM_LIQUIDITY_OFFSET: usize = 237;
+const MOCK_RAYDIUM_CLMM_SQRT_PRICE_X64_OFFSET: usize = 253;
+// L = sqrt(x * y) and sqrt(P) = sqrt(y / x), so that L / sqrt(P) and L * sqrt(P) give back the reserves.
+fn write_mock_raydium_clmm_price(pool_state_data: &mut [u8], token_0_reserve: u64, token_1_reserve: u64) {
+    let liquidity = ((token_0_reserve as f64) * (token_1_reserve as f64)).sqrt() as u128;
+    let sqrt_price_x64 = ((token_1_reserve as f64) / (token_0_reserve as f64)).sqrt() * (u64::MAX as f64 + 1.0);
+    pool_state_data[MOCK_RAYDIUM_CLMM_LIQUIDITY_OFFSET..MOCK_RAYDIUM_CLMM_LIQUIDITY_OFFSET + 16].copy_from_slice(liquidity.to_le_bytes().as_slice());
+    pool_state_data[MOCK_RAYDIUM_CLMM_SQRT_PRICE_X64_OFFSET..MOCK_RAYDIUM_CLMM_SQRT_PRICE_X64_OFFSET + 16].copy_from_slice((sqrt_price_x64 as u128).to_le_bytes().as_slice());
+}
+pub fn add_mock_raydium_clmm_program<'a>(program_test: &'a mut ProgramTest) {
+    program_test.add_program(
+        "mock_raydium_clmm",
+        RAYDIUM_CLMM_PROGRAM_PUBKEY,
+        processor!(process_mock_raydium_clmm_swap),
+    );
+}
+pub struct MockRaydiumClmmPool {
+    pub pool_state_pubkey: Pubkey,
+    pub w_sol_vault_pubkey: Pubkey,
+    pub any_mint_vault_pubkey: Pubkey,
+    // One pubkey stands for the amm config and observation state accounts.
+    pub filler_pubkey: Pubkey,
+    pub tick_array_pubkeys: Vec<Pubkey>,
+}
+impl MockRaydiumClmmPool {
+    pub fn add<'a>(program_test: &'a mut ProgramTest, any_mint_token_mint_pubkey: &'a Pubkey, any_mint_reserve: u64, w_sol_reserve: u64) -> Self {
+        let (pool_state_pubkey, _) = Pubkey::find_program_address(
+            [MOCK_RAYDIUM_CLMM_POOL_STATE_SEED].as_slice(),
+            &RAYDIUM_CLMM_PROGRAM_PUBKEY,
+        );
+        let w_sol_vault_pubkey = Pubkey::new_unique();
+        let any_mint_vault_pubkey = Pubkey::new_unique();
+        program_test.add_account(
+            w_sol_vault_pubkey,
+            create_w_sol_token_account(
+                &pool_state_pubkey,
+                w_sol_reserve,
+            ),
+        );
+        program_test.add_account(
+            any_mint_vault_pubkey,
+            create_token_account(
+                any_mint_token_mint_pubkey,
+                &pool_state_pubkey,
+                any_mint_reserve,
+            ),
+        );
+        // As on the venue, token 0 is the mint with the lower pubkey.
+        let ((token_0_mint_pubkey, token_0_reserve), (token_1_mint_pubkey, token_1_reserve)) = if *any_mint_token_mint_pubkey < spl_token::native_mint::ID {
+            (
+                (*any_mint_token_mint_pubkey, any_mint_reserve),
+                (spl_token::native_mint::ID, w_sol_reserve),
+            )
+        } else {
+            (
+                (spl_token::native_mint::ID, w_sol_reserve),
+                (*any_mint_token_mint_pubkey, any_mint_reserve),
+            )
+        };
+        let mut pool_state_data = vec![0; MOCK_RAYDIUM_CLMM_SQRT_PRICE_X64_OFFSET + 16];
+        pool_state_data[MOCK_RAYDIUM_CLMM_MINTS_OFFSET..MOCK_RAYDIUM_CLMM_MINTS_OFFSET + 32].copy_from_slice(token_0_mint_pubkey.as_ref());
+        pool_state_data[MOCK_RAYDIUM_CLMM_MINTS_OFFSET + 32..MOCK_RAYDIUM_CLMM_MINTS_OFFSET + 64].copy_from_slice(token_1_mint_pubkey.as_ref());
+        write_mock_raydium_clmm_price(
+            pool_state_data.as_mut_slice(),
+            token_0_reserve,
+            token_1_reserve,
+        );
+        program_test.add_account(
+            pool_state_pubkey,
+            Account {
+                lamports: LAMPORTS_PER_SOL,
+                data: pool_state_data,
+                owner: RAYDIUM_CLMM_PROGRAM_PUBKEY,
+                executable: false,
+                rent_epoch: 0,
+            },
+        );
+        Self {
+            pool_state_pubkey,
+            w_sol_vault_pubkey,
+            any_mint_vault_pubkey,
+            filler_pubkey: Pubkey::new_unique(),
+            tick_array_pubkeys: vec![
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+            ],
+        }
+    }
+}
+// Stands in for Meteora DLMM offline. `swap` fills at the price of the active bin of the lb pair, (1 + bin_step / 10_000) ^ active_id
+// token Y per token X, from the two reserves of the pair, owned by the lb pair. The bitmap extension, oracle, event authority and bin array accounts are not read.
+pub fn process_mock_meteora_dlmm_swap<'a>(program_id: &'a Pubkey, accounts: &'a [AccountInfo], input: &'a [u8]) -> ProgramResult {
+    if accounts.len() < 16 {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+    let [
+        lb_pair_account_info,
+        _,
+        reserve_x_account_info,
+        reserve_y_account_info,
+        user_token_in_account_info,
+        user_token_out_account_info,
+        token_x_mint_account_info,
+        token_y_mint_account_info,
+        _,
+        host_fee_in_account_info,
+        user_account_info,
+        token_x_program_account_info,
+        token_y_program_account_info,
+        _,
+        _,
+    ] = &accounts[..15]
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    if input.len() != 24 || input[..8] != MOCK_METEORA_SWAP_DISCRIMINATOR {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    if !user_account_info.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    let amount_in = u64::from_le_bytes(input[8..16].try_into().unwrap());
+    let minimum_amount_out = u64::from_le_bytes(input[16..24].try_into().unwrap());
+    let (lb_pair_pubkey, lb_pair_bump_seed) = Pubkey::find_program_address(
+        [MOCK_METEORA_DLMM_LB_PAIR_SEED].as_slice(),
+        program_id,
+    );
+    // The optional host fee account is left out by passing the program id.
+    if *lb_pair_account_info.key != lb_pair_pubkey || host_fee_in_account_info.key != program_id {
+        return Err(ProgramError::InvalidArgument);
+    }
+    let lb_pair_data = lb_pair_account_info.data.borrow();
+    if lb_pair_data[MOCK_METEORA_DLMM_MINTS_OFFSET..MOCK_METEORA_DLMM_MINTS_OFFSET + 32] != *token_x_mint_account_info.key.as_ref()
+        || lb_pair_data[MOCK_METEORA_DLMM_MINTS_OFFSET + 32..MOCK_METEORA_DLMM_MINTS_OFFSET + 64] != *token_y_mint_account_info.key.as_ref()
+    {
+        return Err(ProgramError::InvalidArgument);
+    }
+    let active_id = i32::from_le_bytes(lb_pair_data[MOCK_METEORA_DLMM_ACTIVE_ID_OFFSET..MOCK_METEORA_DLMM_ACTIVE_ID_OFFSET + 4].try_into().unwrap());
+    let bin_step = u16::from_le_bytes(lb_pair_data[MOCK_METEORA_DLMM_BIN_STEP_OFFSET..MOCK_METEORA_DLMM_BIN_STEP_OFFSET + 2].try_into().unwrap());
+    drop(lb_pair_data);
+    let price = (1.0 + (bin_step as f64) / 10_000.0).powi(active_id);
+    let is_swap_for_y = TokenAccount::unpack(&user_token_in_account_info.data.borrow())?.mint == *token_x_mint_account_info.key;
+    let (
+        (input_reserve_account_info, input_token_mint_account_info, input_token_program_account_info),
+        (output_reserve_account_info, output_token_mint_account_info, output_token_program_account_info),
+    ) = if is_swap_for_y {
+        (
+            (reserve_x_account_info, token_x_mint_account_info, token_x_program_account_info),
+            (reserve_y_account_info, token_y_mint_account_info, token_y_program_account_info),
+        )
+    } else {
+        (
+            (reserve_y_account_info, token_y_mint_account_info, token_y_program_account_info),
+            (reserve_x_account_info, token_x_mint_account_info, token_x_program_account_info),
+        )
+    };
+    enforce_mock_swap_leg(
+        input_reserve_account_info,
+        user_token_in_account_info,
+        input_token_mint_account_info,
+        input_token_program_account_info,
+    )?;
+    let output_reserve = enforce_mock_swap_leg(
+        output_reserve_account_info,
+        user_token_out_account_info,
+        output_token_mint_account_info,
+        output_token_program_account_info,
+    )?;
+    let amount_out = if is_swap_for_y {
+        (amount_in as f64) * price
+    } else {
+        (amount_in as f64) / price
+    } as u64;
+    if amount_out > output_reserve || amount_out < minimum_amount_out {
+        return Err(ProgramError::InsufficientFunds);
+    }
+    transfer_through_mock_vaults(
+        input_token_program_account_info,
+        user_token_in_account_info,
+        user_token_out_account_info,
+        user_account_info,
+        input_reserve_account_info,
+        output_reserve_account_info,
+        lb_pair_account_info,
+        [
+            MOCK_METEORA_DLMM_LB_PAIR_SEED,
+            [lb_pair_bump_seed].as_slice(),
+        ]
+        .as_slice(),
+        amount_in,
+        amount_out,
+    )
+}
+const MOCK_METEORA_DLMM_LB_PAIR_SEED: &'static [u8] = b"lb_pair";
+const MOCK_METEORA_SWAP_DISCRIMINATOR: [u8; 8] = [248, 198, 158, 145, 225, 117, 135, 200];
+// https://github.com/MeteoraAg/dlmm-sdk/blob/main/programs/lb_clmm/src/state/lb_pair.rs
+const MOCK_METEORA_DLMM_ACTIVE_ID_OFFSET: usize = 76;
+const MOCK_METEORA_DLMM_BIN_STEP_OFFSET: usize = 80;
+const MOCK_METEORA_DLMM_MINTS_OFFSET: usize = 88;
+pub fn add_mock_meteora_dlmm_program<'a>(program_test: &'a mut ProgramTest) {
+    program_test.add_program(
+        "mock_meteora_dlmm",
+        METEORA_DLMM_PROGRAM_PUBKEY,
+        processor!(process_mock_meteora_dlmm_swap),
+    );
+}
+pub struct MockMeteoraDlmmPool {
+    pub lb_pair_pubkey: Pubkey,
+    pub w_sol_reserve_pubkey: Pubkey,
+    pub any_mint_reserve_pubkey: Pubkey,
+    // One pubkey stands for the bitmap extension, oracle and event authority accounts.
+    pub filler_pubkey: Pubkey,
+    pub bin_array_pubkeys: Vec<Pubkey>,
+}
+impl MockMeteoraDlmmPool {
+    // wSOL is token X, so the active bin prices the any mint in wSOL.
+    pub fn add<'a>(program_test: &'a mut ProgramTest, any_mint_token_mint_pubkey: &'a Pubkey, any_mint_reserve: u64, w_sol_reserve: u64, bin_step: u16, active_id: i32) -> Self {
+        let (lb_pair_pubkey, _) = Pubkey::find_program_address(
+            [MOCK_METEORA_DLMM_LB_PAIR_SEED].as_slice(),
+            &METEORA_DLMM_PROGRAM_PUBKEY,
+        );
+        let w_sol_reserve_pubkey = Pubkey::new_unique();
+        let any_mint_reserve_pubkey = Pubkey::new_unique();
+        program_test.add_account(
+            w_sol_reserve_pubkey,
+            create_w_sol_token_account(
+                &lb_pair_pubkey,
+                w_sol_reserve,
+            ),
+        );
+        program_test.add_account(
+            any_mint_reserve_pubkey,
+            create_token_account(
+                any_mint_token_mint_pubkey,
+                &lb_pair_pubkey,
+                any_mint_reserve,
+            ),
+        );
+        let mut lb_pair_data = vec![0; MOCK_METEORA_DLMM_MINTS_OFFSET + 64];
+        lb_pair_data[MOCK_METEORA_DLMM_ACTIVE_ID_OFFSET..MOCK_METEORA_DLMM_ACTIVE_ID_OFFSET + 4].copy_from_slice(active_id.to_le_bytes().as_slice());
+        lb_pair_data[MOCK_METEORA_DLMM_BIN_STEP_OFFSET..MOCK_METEORA_DLMM_BIN_STEP_OFFSET + 2].copy_from_slice(bin_step.to_le_bytes().as_slice());
+        lb_pair_data[MOCK_METEORA_DLMM_MINTS_OFFSET..MOCK_METEORA_DLMM_MINTS_OFFSET + 32].copy_from_slice(spl_token::native_mint::ID.as_ref());
+        lb_pair_data[MOCK_METEORA_DLMM_MINTS_OFFSET + 32..MOCK_METEORA_DLMM_MINTS_OFFSET + 64].copy_from_slice(any_mint_token_mint_pubkey.as_ref());
+        program_test.add_account(
+            lb_pair_pubkey,
+            Account {
+                lamports: LAMPORTS_PER_SOL,
+                data: lb_pair_data,
+                owner: METEORA_DLMM_PROGRAM_PUBKEY,
+                executable: false,
+                rent_epoch: 0,
+            },
+        );
+        Self {
+            lb_pair_pubkey,
+            w_sol_reserve_pubkey,
+            any_mint_reserve_pubkey,
+            filler_pubkey: Pubkey::new_unique(),
+            bin_array_pubkeys: vec![
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+            ],
+        }
+    }
+}
+// Stands in for Meteora dynamic AMM offline. `swap` is a fee-less constant product swap between the token vaults of the two Meteora vaults
+// of the pool, whose whole LP supply the pool holds. The token vaults are owned by the pool rather than by the vaults, and the pool has the
+// vault program below bring the total amounts of the vaults in line with the token vaults after the swap. The protocol fee account is not read.
+pub fn process_mock_meteora_dynamic_amm_swap<'a>(program_id: &'a Pubkey, accounts: &'a [AccountInfo], input: &'a [u8]) -> ProgramResult {
+    let [
+        pool_account_info,
+        user_source_token_account_info,
+        user_destination_token_account_info,
+        a_vault_account_info,
+        b_vault_account_info,
+        a_token_vault_account_info,
+        b_token_vault_account_info,
+        a_vault_lp_mint_account_info,
+        b_vault_lp_mint_account_info,
+        a_vault_lp_account_info,
+        b_vault_lp_account_info,
+        _,
+        user_account_info,
+        vault_program_account_info,
+        token_program_account_info,
+    ] = accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    if input.len() != 24 || input[..8] != MOCK_METEORA_SWAP_DISCRIMINATOR {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    if !user_account_info.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    if *vault_program_account_info.key != METEORA_VAULT_PROGRAM_PUBKEY || *token_program_account_info.key != spl_token::ID {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    let amount_in = u64::from_le_bytes(input[8..16].try_into().unwrap());
+    let minimum_amount_out = u64::from_le_bytes(input[16..24].try_into().unwrap());
+    let (pool_pubkey, pool_bump_seed) = Pubkey::find_program_address(
+        [MOCK_METEORA_DYNAMIC_AMM_POOL_SEED].as_slice(),
+        program_id,
+    );
+    if *pool_account_info.key != pool_pubkey {
+        return Err(ProgramError::InvalidArgument);
+    }
+    let pool_data = pool_account_info.data.borrow();
+    let a_mint_pubkey = Pubkey::try_from(&pool_data[MOCK_METEORA_DYNAMIC_AMM_MINTS_OFFSET..MOCK_METEORA_DYNAMIC_AMM_MINTS_OFFSET + 32]).unwrap();
+    let b_mint_pubkey = Pubkey::try_from(&pool_data[MOCK_METEORA_DYNAMIC_AMM_MINTS_OFFSET + 32..MOCK_METEORA_DYNAMIC_AMM_MINTS_OFFSET + 64]).unwrap();
+    drop(pool_data);
+    let a_reserve = enforce_mock_meteora_vault(
+        a_vault_account_info,
+        a_token_vault_account_info,
+        a_vault_lp_mint_account_info,
+        a_vault_lp_account_info,
+        &a_mint_pubkey,
+    )?;
+    let b_reserve = enforce_mock_meteora_vault(
+        b_vault_account_info,
+        b_token_vault_account_info,
+        b_vault_lp_mint_account_info,
+        b_vault_lp_account_info,
+        &b_mint_pubkey,
+    )?;
+    let user_source_token_mint_pubkey = TokenAccount::unpack(&user_source_token_account_info.data.borrow())?.mint;
+    let user_destination_token_mint_pubkey = TokenAccount::unpack(&user_destination_token_account_info.data.borrow())?.mint;
+    let (input_token_vault_account_info, output_token_vault_account_info, input_reserve, output_reserve) =
+        if user_source_token_mint_pubkey == a_mint_pubkey && user_destination_token_mint_pubkey == b_mint_pubkey {
+            (a_token_vault_account_info, b_token_vault_account_info, a_reserve, b_reserve)
+        } else if user_source_token_mint_pubkey == b_mint_pubkey && user_destination_token_mint_pubkey == a_mint_pubkey {
+            (b_token_vault_account_info, a_token_vault_account_info, b_reserve, a_reserve)
+        } else {
+            return Err(ProgramError::InvalidArgument);
+        };
+    let amount_out = get_constant_product_amount_out(
+        input_reserve,
+        output_reserve,
+        amount_in,
+    );
+    if amount_out < minimum_amount_out {
+        return Err(ProgramError::InsufficientFunds);
+    }
+    transfer_through_mock_vaults(
+        token_program_account_info,
+        user_source_token_account_info,
+        user_destination_token_account_info,
+        user_account_info,
+        input_token_vault_account_info,
+        output_token_vault_account_info,
+        pool_account_info,
+        [
+            MOCK_METEORA_DYNAMIC_AMM_POOL_SEED,
+            [pool_bump_seed].as_slice(),
+        ]
+        .as_slice(),
+        amount_in,
+        amount_out,
+    )?;
+    for (vault_account_info, token_vault_account_info) in [
+        (
+            a_vault_account_info,
+            a_token_vault_account_info,
+        ),
+        (
+            b_vault_account_info,
+            b_token_vault_account_info,
+        ),
+    ] {
+        invoke(
+            &Instruction_ {
+                program_id: METEORA_VAULT_PROGRAM_PUBKEY,
+                accounts: vec![
+                    AccountMeta::new(*vault_account_info.key, false),
+                    AccountMeta::new_readonly(*token_vault_account_info.key, false),
+                ],
+                data: vec![],
+            },
+            [
+                vault_account_info.clone(),
+                token_vault_account_info.clone(),
+                vault_program_account_info.clone(),
+            ]
+            .as_slice(),
+        )?;
+    }
+    Ok(())
+}
+// Fails unless the vault keeps `token_vault_account_info` and `vault_lp_mint_account_info`, the token vault holds `mint_pubkey` and the
+// vault LP account holds the vault LP mint. Returns the amount of the token vault.
+fn enforce_mock_meteora_vault<'a, 'b>(
+    vault_account_info: &'a AccountInfo<'b>,
+    token_vault_account_info: &'a AccountInfo<'b>,
+    vault_lp_mint_account_info: &'a AccountInfo<'b>,
+    vault_lp_account_info: &'a AccountInfo<'b>,
+    mint_pubkey: &'a Pubkey,
+) -> Result<u64, ProgramError> {
+    if *vault_account_info.owner != METEORA_VAULT_PROGRAM_PUBKEY {
+        return Err(ProgramError::IllegalOwner);
+    }
+    let vault_data = vault_account_info.data.borrow();
+    let token_vault = TokenAccount::unpack(&token_vault_account_info.data.borrow())?;
+    if vault_data[MOCK_METEORA_VAULT_TOKEN_VAULT_OFFSET..MOCK_METEORA_VAULT_TOKEN_VAULT_OFFSET + 32] != *token_vault_account_info.key.as_ref()
+        || vault_data[MOCK_METEORA_VAULT_LP_MINT_OFFSET..MOCK_METEORA_VAULT_LP_MINT_OFFSET + 32] != *vault_lp_mint_account_info.key.as_ref()
+        || token_vault.mint != *mint_pubkey
+        || TokenAccount::unpack(&vault_lp_account_info.data.borrow())?.mint != *vault_lp_mint_account_info.key
+    {
+        return Err(ProgramError::InvalidArgument);
+    }
+    Ok(token_vault.amount)
+}
+const MOCK_METEORA_DYNAMIC_AMM_POOL_SEED: &'static [u8] = b"pool";
+// https://github.com/MeteoraAg/damm-v1-sdk/blob/main/programs/dynamic-amm/src/state.rs
+const MOCK_METEORA_DYNAMIC_AMM_MINTS_OFFSET: usize = 40;
+// https://github.com/MeteoraAg/vault-sdk/blob/main/programs/vault/src/state.rs
+const MOCK_METEORA_VAULT_TOTAL_AMOUNT_OFFSET: usize = 11;
+const MOCK_METEORA_VAULT_TOKEN_VAULT_OFFSET: usize = 19;
+const MOCK_METEORA_VAULT_LP_MINT_OFFSET: usize = 115;
+const MOCK_METEORA_VAULT_LP_SUPPLY: u64 = 1_000_000_000;
+// Stands in for the Meteora vault program: sets the total amount of the vault to the amount of its token vault.
+pub fn process_mock_meteora_vault<'a>(_program_id: &'a Pubkey, accounts: &'a [AccountInfo], _input: &'a [u8]) -> ProgramResult {
+    let [
+        vault_account_info,
+        token_vault_account_info,
+    ] = accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    let mut vault_data = vault_account_info.data.borrow_mut();
+    if vault_data[MOCK_METEORA_VAULT_TOKEN_VAULT_OFFSET..MOCK_METEORA_VAULT_TOKEN_VAULT_OFFSET + 32] != *token_vault_account_info.key.as_ref() {
+        return Err(ProgramError::InvalidArgument);
+    }
+    let token_vault_amount = TokenAccount::unpack(&token_vault_account_info.data.borrow())?.amount;
+    vault_data[MOCK_METEORA_VAULT_TOTAL_AMOUNT_OFFSET..MOCK_METEORA_VAULT_TOTAL_AMOUNT_OFFSET + 8].copy_from_slice(token_vault_amount.to_le_bytes().as_slice());
+    Ok(())
+}
+pub fn add_mock_meteora_dynamic_amm_program<'a>(program_test: &'a mut ProgramTest) {
+    program_test.add_program(
+        "mock_meteora_dynamic_amm",
+        METEORA_DYNAMIC_AMM_PROGRAM_PUBKEY,
+        processor!(process_mock_meteora_dynamic_amm_swap),
+    );
+    program_test.add_program(
+        "mock_meteora_vault",
+        METEORA_VAULT_PROGRAM_PUBKEY,
+        processor!(process_mock_meteora_vault),
+    );
+}
+pub struct MockMeteoraDynamicAmmPool {
+    pub pool_pubkey: Pubkey,
+    pub w_sol_vault_pubkey: Pubkey,
+    pub any_mint_vault_pubkey: Pubkey,
+    pub w_sol_token_vault_pubkey: Pubkey,
+    pub any_mint_token_vault_pubkey: Pubkey,
+    pub w_sol_vault_lp_mint_pubkey: Pubkey,
+    pub any_mint_vault_lp_mint_pubkey: Pubkey,
+    pub w_sol_vault_lp_pubkey: Pubkey,
+    pub any_mint_vault_lp_pubkey: Pubkey,
+    pub protocol_token_fee_pubkey: Pubkey,
+}
+impl MockMeteoraDynamicAmmPool {
+    // The any mint is token A and wSOL is token B.
+    pub fn add<'a>(program_test: &'a mut ProgramTest, any_mint_token_mint_pubkey: &'a Pubkey, any_mint_reserve: u64, w_sol_reserve: u64) -> Self {
+        let (pool_pubkey, _) = Pubkey::find_program_address(
+            [MOCK_METEORA_DYNAMIC_AMM_POOL_SEED].as_slice(),
+            &METEORA_DYNAMIC_AMM_PROGRAM_PUBKEY,
+        );
+        let mut pool_data = vec![0; MOCK_METEORA_DYNAMIC_AMM_MINTS_OFFSET + 64];
+        pool_data[MOCK_METEORA_DYNAMIC_AMM_MINTS_OFFSET..MOCK_METEORA_DYNAMIC_AMM_MINTS_OFFSET + 32].copy_from_slice(any_mint_token_mint_pubkey.as_ref());
+        pool_data[MOCK_METEORA_DYNAMIC_AMM_MINTS_OFFSET + 32..MOCK_METEORA_DYNAMIC_AMM_MINTS_OFFSET + 64].copy_from_slice(spl_token::native_mint::ID.as_ref());
+        program_test.add_account(
+            pool_pubkey,
+            Account {
+                lamports: LAMPORTS_PER_SOL,
+                data: pool_data,
+                owner: METEORA_DYNAMIC_AMM_PROGRAM_PUBKEY,
+                executable: false,
+                rent_epoch: 0,
+            },
+        );
+        let w_sol_token_vault_pubkey = Pubkey::new_unique();
+        let any_mint_token_vault_pubkey = Pubkey::new_unique();
+        program_test.add_account(
+            w_sol_token_vault_pubkey,
+            create_w_sol_token_account(
+                &pool_pubkey,
+                w_sol_reserve,
+            ),
+        );
+        program_test.add_account(
+            any_mint_token_vault_pubkey,
+            create_token_account(
+                any_mint_token_mint_pubkey,
+                &pool_pubkey,
+                any_mint_reserve,
+            ),
+        );
+        let mut add_vault = |token_vault_pubkey: Pubkey, reserve: u64| {
+            let vault_pubkey = Pubkey::new_unique();
+            let vault_lp_mint_pubkey = Pubkey::new_unique();
+            let vault_lp_pubkey = Pubkey::new_unique();
+            let mut vault_data = vec![0; MOCK_METEORA_VAULT_LP_MINT_OFFSET + 32];
+            vault_data[MOCK_METEORA_VAULT_TOTAL_AMOUNT_OFFSET..MOCK_METEORA_VAULT_TOTAL_AMOUNT_OFFSET + 8].copy_from_slice(reserve.to_le_bytes().as_slice());
+            vault_data[MOCK_METEORA_VAULT_TOKEN_VAULT_OFFSET..MOCK_METEORA_VAULT_TOKEN_VAULT_OFFSET + 32].copy_from_slice(token_vault_pubkey.as_ref());
+            vault_data[MOCK_METEORA_VAULT_LP_MINT_OFFSET..MOCK_METEORA_VAULT_LP_MINT_OFFSET + 32].copy_from_slice(vault_lp_mint_pubkey.as_ref());
+            program_test.add_account(
+                vault_pubkey,
+                Account {
+                    lamports: LAMPORTS_PER_SOL,
+                    data: vault_data,
+                    owner: METEORA_VAULT_PROGRAM_PUBKEY,
+                    executable: false,
+                    rent_epoch: 0,
+                },
+            );
+            let mut vault_lp_mint_account = create_mint_account(9);
+            let mut vault_lp_mint = Mint::unpack(vault_lp_mint_account.data.as_slice()).unwrap();
+            vault_lp_mint.supply = MOCK_METEORA_VAULT_LP_SUPPLY;
+            Mint::pack(
+                vault_lp_mint,
+                vault_lp_mint_account.data.as_mut_slice(),
+            )
+            .unwrap();
+            program_test.add_account(
+                vault_lp_mint_pubkey,
+                vault_lp_mint_account,
+            );
+            program_test.add_account(
+                vault_lp_pubkey,
+                create_token_account(
+                    &vault_lp_mint_pubkey,
+                    &pool_pubkey,
+                    MOCK_METEORA_VAULT_LP_SUPPLY,
+                ),
+            );
+            (
+                vault_pubkey,
+                vault_lp_mint_pubkey,
+                vault_lp_pubkey,
+            )
+        };
+        let (w_sol_vault_pubkey, w_sol_vault_lp_mint_pubkey, w_sol_vault_lp_pubkey) = add_vault(
+            w_sol_token_vault_pubkey,
+            w_sol_reserve,
+        );
+        let (any_mint_vault_pubkey, any_mint_vault_lp_mint_pubkey, any_mint_vault_lp_pubkey) = add_vault(
+            any_mint_token_vault_pubkey,
+            any_mint_reserve,
+        );
+        Self {
+            pool_pubkey,
+            w_sol_vault_pubkey,
+            any_mint_vault_pubkey,
+            w_sol_token_vault_pubkey,
+            any_mint_token_vault_pubkey,
+            w_sol_vault_lp_mint_pubkey,
+            any_mint_vault_lp_mint_pubkey,
+            w_sol_vault_lp_pubkey,
+            any_mint_vault_lp_pubkey,
+            protocol_token_fee_pubkey: Pubkey::new_unique(),
+        }
+    }
+}
+// Stands in for the Pump.fun bonding curve offline. `buy` and `sell` are a fee-less constant product swap on the virtual reserves of the
+// bonding curve, settled in lamports: the buyer pays into the bonding curve through the system program and the seller is paid from it.
+// The tokens move between the user token account and the token account of the bonding curve. The global, fee recipient and event authority accounts are not read.
+pub fn process_mock_pumpfun_swap<'a>(program_id: &'a Pubkey, accounts: &'a [AccountInfo], input: &'a [u8]) -> ProgramResult {
+    let [
+        _,
+        _,
+        mint_account_info,
+        bonding_curve_account_info,
+        associated_bonding_curve_account_info,
+        associated_user_account_info,
+        user_account_info,
+        system_program_account_info,
+        side_program_account_info,
+        side_account_info,
+        _,
+        _,
+    ] = accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    if input.len() != 24 {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    if !user_account_info.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    let token_amount = u64::from_le_bytes(input[8..16].try_into().unwrap());
+    let lamports_limit = u64::from_le_bytes(input[16..24].try_into().unwrap());
+    let (bonding_curve_pubkey, bonding_curve_bump_seed) = Pubkey::find_program_address(
+        [
+            MOCK_PUMPFUN_BONDING_CURVE_SEED,
+            mint_account_info.key.as_ref(),
+        ]
+        .as_slice(),
+        program_id,
+    );
+    let associated_bonding_curve = TokenAccount::unpack(&associated_bonding_curve_account_info.data.borrow())?;
+    if *bonding_curve_account_info.key != bonding_curve_pubkey
+        || associated_bonding_curve.owner != bonding_curve_pubkey
+        || associated_bonding_curve.mint != *mint_account_info.key
+        || TokenAccount::unpack(&associated_user_account_info.data.borrow())?.mint != *mint_account_info.key
+    {
+        return Err(ProgramError::InvalidArgument);
+    }
+    if *system_program_account_info.key != system_program::ID {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    let virtual_token_reserve = u64::from_le_bytes(
+        bonding_curve_account_info.data.borrow()[MOCK_PUMPFUN_VIRTUAL_TOKEN_RESERVES_OFFSET..MOCK_PUMPFUN_VIRTUAL_TOKEN_RESERVES_OFFSET + 8]
+            .try_into()
+            .unwrap(),
+    );
+    let virtual_sol_reserve = u64::from_le_bytes(
+        bonding_curve_account_info.data.borrow()[MOCK_PUMPFUN_VIRTUAL_SOL_RESERVES_OFFSET..MOCK_PUMPFUN_VIRTUAL_SOL_RESERVES_OFFSET + 8]
+            .try_into()
+            .unwrap(),
+    );
+    let bonding_curve_bump_seed_ = [bonding_curve_bump_seed];
+    let bonding_curve_seeds = [
+        MOCK_PUMPFUN_BONDING_CURVE_SEED,
+        mint_account_info.key.as_ref(),
+        bonding_curve_bump_seed_.as_slice(),
+    ];
+    // A buy takes the exact token amount out for at most the lamports limit, a sell puts the exact token amount in for at least the lamports limit.
+    let (virtual_token_reserve_after_swap, virtual_sol_reserve_after_swap) = if input[..8] == MOCK_PUMPFUN_BUY_DISCRIMINATOR {
+        if *side_program_account_info.key != spl_token::ID || *side_account_info.key != solana_sdk::sysvar::rent::ID {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        if token_amount >= virtual_token_reserve {
+            return Err(ProgramError::InsufficientFunds);
+        }
+        let lamports_in = ((virtual_sol_reserve as u128) * (token_amount as u128)).div_ceil((virtual_token_reserve - token_amount) as u128) as u64;
+        if lamports_in > lamports_limit {
+            return Err(ProgramError::InsufficientFunds);
+        }
+        invoke(
+            &solana_sdk::system_instruction::transfer(
+                user_account_info.key,
+                bonding_curve_account_info.key,
+                lamports_in,
+            ),
+            [
+                user_account_info.clone(),
+                bonding_curve_account_info.clone(),
+            ]
+            .as_slice(),
+        )?;
+        invoke_signed(
+            &spl_token::instruction::transfer(
+                side_program_account_info.key,
+                associated_bonding_curve_account_info.key,
+                associated_user_account_info.key,
+                bonding_curve_account_info.key,
+                [].as_slice(),
+                token_amount,
+            )?,
+            [
+                associated_bonding_curve_account_info.clone(),
+                associated_user_account_info.clone(),
+                bonding_curve_account_info.clone(),
+            ]
+            .as_slice(),
+            [bonding_curve_seeds.as_slice()].as_slice(),
+        )?;
+        (
+            virtual_token_reserve - token_amount,
+            virtual_sol_reserve + lamports_in,
+        )
+    } else if input[..8] == MOCK_PUMPFUN_SELL_DISCRIMINATOR {
+        if *side_program_account_info.key != SPL_ASSOCIATED_TOKEN_ACCOUNT_ID || *side_account_info.key != spl_token::ID {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        let lamports_out = get_constant_product_amount_out(
+            virtual_token_reserve,
+            virtual_sol_reserve,
+            token_amount,
+        );
+        if lamports_out < lamports_limit {
+            return Err(ProgramError::InsufficientFunds);
+        }
+        invoke(
+            &spl_token::instruction::transfer(
+                side_account_info.key,
+                associated_user_account_info.key,
+                associated_bonding_curve_account_info.key,
+                user_account_info.key,
+                [].as_slice(),
+                token_amount,
+            )?,
+            [
+                associated_user_account_info.clone(),
+                associated_bonding_curve_account_info.clone(),
+                user_account_info.clone(),
+            ]
+            .as_slice(),
+        )?;
+        **bonding_curve_account_info.try_borrow_mut_lamports()? -= lamports_out;
+        **user_account_info.try_borrow_mut_lamports()? += lamports_out;
+        (
+            virtual_token_reserve + token_amount,
+            virtual_sol_reserve - lamports_out,
+        )
+    } else {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+    write_mock_pumpfun_virtual_reserves(
+        &mut bonding_curve_account_info.data.borrow_mut(),
+        virtual_token_reserve_after_swap,
+        virtual_sol_reserve_after_swap,
+    );
+    Ok(())
+}
+const MOCK_PUMPFUN_BONDING_CURVE_SEED: &'static [u8] = b"bonding-curve";
+const MOCK_PUMPFUN_BUY_DISCRIMINATOR: [u8; 8] = [102, 6, 61, 18, 1, 218, 235, 234];
+const MOCK_PUMPFUN_SELL_DISCRIMINATOR: [u8; 8] = [51, 230, 133, 164, 1, 127, 131, 173];
+// https://github.com/pump-fun/pump-public-docs/blob/main/docs/PUMP_PROGRAM_README.md
+const MOCK_PUMPFUN_VIRTUAL_TOKEN_RESERVES_OFFSET: usize = 8;
+const MOCK_PUMPFUN_VIRTUAL_SOL_RESERVES_OFFSET: usize = 16;
+fn write_mock_pumpfun_virtual_reserves(bonding_curve_data: &mut [u8], virtual_token_reserve: u64, virtual_sol_reserve: u64) {
+    bonding_curve_data[MOCK_PUMPFUN_VIRTUAL_TOKEN_RESERVES_OFFSET..MOCK_PUMPFUN_VIRTUAL_TOKEN_RESERVES_OFFSET + 8].copy_from_slice(virtual_token_reserve.to_le_bytes().as_slice());
+    bonding_curve_data[MOCK_PUMPFUN_VIRTUAL_SOL_RESERVES_OFFSET..MOCK_PUMPFUN_VIRTUAL_SOL_RESERVES_OFFSET + 8].copy_from_slice(virtual_sol_reserve.to_le_bytes().as_slice());
+}
+pub fn add_mock_pumpfun_program<'a>(program_test: &'a mut ProgramTest) {
+    program_test.add_program(
+        "mock_pumpfun",
+        PUMPFUN_PROGRAM_PUBKEY,
+        processor!(process_mock_pumpfun_swap),
+    );
+}
+pub struct MockPumpfunCurve {
+    pub bonding_curve_pubkey: Pubkey,
+    pub associated_bonding_curve_pubkey: Pubkey,
+    // One pubkey stands for the global, fee recipient and event authority accounts.
+    pub filler_pubkey: Pubkey,
+}
+impl MockPumpfunCurve {
+    // The bonding curve holds the virtual SOL reserve as lamports above its rent, so a sell can be paid out of it.
+    pub fn add<'a>(program_test: &'a mut ProgramTest, mint_pubkey: &'a Pubkey, virtual_token_reserve: u64, virtual_sol_reserve: u64) -> Self {
+        let (bonding_curve_pubkey, _) = Pubkey::find_program_address(
+            [
+                MOCK_PUMPFUN_BONDING_CURVE_SEED,
+                mint_pubkey.as_ref(),
+            ]
+            .as_slice(),
+            &PUMPFUN_PROGRAM_PUBKEY,
+        );
+        let mut bonding_curve_data = vec![0; MOCK_PUMPFUN_VIRTUAL_SOL_RESERVES_OFFSET + 8];
+        write_mock_pumpfun_virtual_reserves(
+            bonding_curve_data.as_mut_slice(),
+            virtual_token_reserve,
+            virtual_sol_reserve,
+        );
+        program_test.add_account(
+            bonding_curve_pubkey,
+            Account {
+                lamports: Rent::default().minimum_balance(bonding_curve_data.len()) + virtual_sol_reserve,
+                data: bonding_curve_data,
+                owner: PUMPFUN_PROGRAM_PUBKEY,
+                executable: false,
+                rent_epoch: 0,
+            },
+        );
+        let associated_bonding_curve_pubkey = Pubkey::new_unique();
+        program_test.add_account(
+            associated_bonding_curve_pubkey,
+            create_token_account(
+                mint_pubkey,
+                &bonding_curve_pubkey,
+                virtual_token_reserve,
+            ),
+        );
+        Self {
+            bonding_curve_pubkey,
+            associated_bonding_curve_pubkey,
+            filler_pubkey: Pubkey::new_unique(),
+        }
+    }
+}
@@ -0,0 +1,2404 @@
+mod common;
+use {
+    common::{
+        add_funded_account,
+        add_mock_raydium_program,
+        assert_error,
+        create_mint_account,
+        create_token_2022_account,
+        create_token_2022_mint_account_with_transfer_fee,
+        create_token_account,
+        Environment,
+        MockRaydiumPool,
+        FEE,
+        FEES,
+    },
+    simo_intermediary::{
+        error::Error,
+        extern_source::get_associated_token_address,
+        event::{
+            Event,
+            Role,
+            Side,
+            Venue,
+            VersionedEvent,
+        },
+        instruction::{
+            AnyMintTokenAccountPubkeys,
+            Instruction,
+            LiquidationLeg,
+            RouteHop,
+            RouteLeg,
+        },
+        state::{
+            AllowedMints,
+            Intermediary,
+            IntermediaryV0,
+            RiskLimits,
+        },
+        AnyMintTokenAccountAddressResolver,
+        AnyMintTokenAccountAuthorityAddressResolver,
+        IntermediateTokenAccountAddressResolver,
+        ProgramDerivedAddress,
+        METEORA_DLMM_PROGRAM_PUBKEY,
+        PROGRAM_PUBKEY,
+        PUMPFUN_PROGRAM_PUBKEY,
+        PUMPSWAP_PROGRAM_PUBKEY,
+        RAYDIUM_LIQUIDITY_POOL_V4_PROGRAM_PUBKEY,
+    },
+    solana_sdk::{
+        account::Account,
+        native_token::LAMPORTS_PER_SOL,
+        program_pack::Pack,
+        pubkey::Pubkey,
+        signature::Keypair,
+        signer::Signer,
+    },
+};
+#[tokio::test]
+async fn initialize_deposit_withdraw_close() {
+    let mut environment = Environment::start(|_, _| {}).await;
+    let intermediary_pubkey = environment.intermediary.pubkey();
+    let intermediary_pubkeys = environment.intermediary_pubkeys();
+    let investor = environment.investor.insecure_clone();
+    let versioned_events = environment.initialize_config().await;
+    assert_eq!(
+        versioned_events,
+        vec![
+            VersionedEvent::V1(
+                Event::ConfigInitialized {
+                    administrator_pubkey: environment.administrator.pubkey(),
+                    fee_recipient_pubkey: environment.fee_recipient.pubkey(),
+                    fees: FEES,
+                },
+            ),
+        ],
+    );
+    let versioned_events = environment.initialize_intermediary(LAMPORTS_PER_SOL).await;
+    assert_eq!(
+        versioned_events,
+        vec![
+            VersionedEvent::V1(
+                Event::FeeCharged {
+                    intermediary_pubkey,
+                    payer_pubkey: investor.pubkey(),
+                    fee_vault_pubkey: environment.fee_vault_pubkey,
+                    lamports: FEE,
+                },
+            ),
+            VersionedEvent::V1(
+                Event::Initialized {
+                    intermediary_pubkey,
+                    investor_pubkey: investor.pubkey(),
+                    manager_pubkey: environment.manager.pubkey(),
+                    trader_pubkey: environment.trader.pubkey(),
+                    lamports_to_treasury: LAMPORTS_PER_SOL,
+                    treasury_amount: LAMPORTS_PER_SOL,
+                },
+            ),
+        ],
+    );
+    let intermediary = environment.get_intermediary().await;
+    assert!(intermediary.is_initialized);
+    assert_eq!(intermediary.investor_pubkey, investor.pubkey());
+    assert_eq!(intermediary.w_sol_token_account_pubkey, intermediary_pubkeys.w_sol_token_account_pubkey);
+    assert_eq!(intermediary.pending_manager_pubkey, Pubkey::default());
+    assert_eq!(intermediary.pending_trader_pubkey, Pubkey::default());
+    assert_eq!(environment.get_token_account_amount(&intermediary_pubkeys.w_sol_token_account_pubkey).await, LAMPORTS_PER_SOL);
+    let deposit_funds_instruction = Instruction::deposit_funds(
+        &PROGRAM_PUBKEY,
+        &investor.pubkey(),
+        &intermediary_pubkey,
+        &intermediary_pubkeys.w_sol_token_account_pubkey,
+        &environment.config_pubkey,
+        &environment.fee_vault_pubkey,
+        LAMPORTS_PER_SOL / 2,
+    )
+    .unwrap();
+    let versioned_events = environment
+        .process(
+            [deposit_funds_instruction].as_slice(),
+            [&investor].as_slice(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        versioned_events.last(),
+        Some(
+            &VersionedEvent::V1(
+                Event::FundsDeposited {
+                    intermediary_pubkey,
+                    lamports_to_treasury: LAMPORTS_PER_SOL / 2,
+                    treasury_amount: LAMPORTS_PER_SOL + LAMPORTS_PER_SOL / 2,
+                },
+            ),
+        ),
+    );
+    let investor_lamports_before_withdrawal = environment.get_lamports(&investor.pubkey()).await;
+    let versioned_events = environment.withdraw_funds(LAMPORTS_PER_SOL / 4).await.unwrap();
+    assert_eq!(
+        versioned_events.last(),
+        Some(
+            &VersionedEvent::V1(
+                Event::FundsWithdrawn {
+                    intermediary_pubkey,
+                    lamports_from_treasury: LAMPORTS_PER_SOL / 4,
+                    treasury_amount: LAMPORTS_PER_SOL + LAMPORTS_PER_SOL / 4,
+                },
+            ),
+        ),
+    );
+    assert_eq!(environment.get_lamports(&investor.pubkey()).await, investor_lamports_before_withdrawal + LAMPORTS_PER_SOL / 4 - FEE);
+    assert_eq!(environment.get_token_account_amount(&intermediary_pubkeys.w_sol_token_account_pubkey).await, LAMPORTS_PER_SOL + LAMPORTS_PER_SOL / 4);
+    assert!(environment.get_account(&intermediary_pubkeys.temporary_w_sol_token_account_pubkey).await.is_none());
+    assert_error(
+        environment.withdraw_funds(2 * LAMPORTS_PER_SOL).await,
+        Error::TokenAccountInsufficientAmount,
+    );
+    let investor_lamports_before_closing = environment.get_lamports(&investor.pubkey()).await;
+    let lamports_to_investor =
+        environment.get_lamports(&intermediary_pubkey).await + environment.get_lamports(&intermediary_pubkeys.w_sol_token_account_pubkey).await;
+    let close_intermediary_instruction = Instruction::close_intermediary(
+        &PROGRAM_PUBKEY,
+        &investor.pubkey(),
+        &intermediary_pubkey,
+        &intermediary_pubkeys.w_sol_token_account_pubkey,
+        &intermediary_pubkeys.w_sol_token_account_authority_pubkey,
+        &intermediary_pubkeys.risk_limits_pubkey,
+        &intermediary_pubkeys.allowed_mints_pubkey,
+        [].as_slice(),
+        [].as_slice(),
+    )
+    .unwrap();
+    let versioned_events = environment
+        .process(
+            [close_intermediary_instruction].as_slice(),
+            [&investor].as_slice(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        versioned_events,
+        vec![
+            VersionedEvent::V1(
+                Event::IntermediaryClosed {
+                    intermediary_pubkey,
+                    lamports_to_investor,
+                },
+            ),
+        ],
+    );
+    assert_eq!(environment.get_lamports(&investor.pubkey()).await, investor_lamports_before_closing + lamports_to_investor);
+    assert!(environment.get_account(&intermediary_pubkey).await.is_none());
+    assert!(environment.get_account(&intermediary_pubkeys.w_sol_token_account_pubkey).await.is_none());
+    let fee_recipient_lamports_before_sweeping = environment.get_lamports(&environment.fee_recipient.pubkey()).await;
+    let sweep_fees_instruction = Instruction::sweep_fees(
+        &PROGRAM_PUBKEY,
+        &environment.administrator.pubkey(),
+        &environment.config_pubkey,
+        &environment.fee_vault_pubkey,
+        &environment.fee_recipient.pubkey(),
+    )
+    .unwrap();
+    let administrator = environment.administrator.insecure_clone();
+    let versioned_events = environment
+        .process(
+            [sweep_fees_instruction].as_slice(),
+            [&administrator].as_slice(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        versioned_events,
+        vec![
+            VersionedEvent::V1(
+                Event::FeesSwept {
+                    fee_recipient_pubkey: environment.fee_recipient.pubkey(),
+                    lamports: 3 * FEE,
+                },
+            ),
+        ],
+    );
+    assert_eq!(environment.get_lamports(&environment.fee_recipient.pubkey()).await, fee_recipient_lamports_before_sweeping + 3 * FEE);
+}
+#[tokio::test]
+async fn role_handover_requires_acceptance() {
+    let new_manager = Keypair::new();
+    let new_trader = Keypair::new();
+    let stranger = Keypair::new();
+    let mut environment = Environment::start(
+        |program_test, _| {
+            for pubkey in [
+                new_manager.pubkey(),
+                new_trader.pubkey(),
+                stranger.pubkey(),
+            ] {
+                add_funded_account(
+                    program_test,
+                    &pubkey,
+                );
+            }
+        },
+    )
+    .await;
+    let intermediary_pubkey = environment.intermediary.pubkey();
+    let investor = environment.investor.insecure_clone();
+    let manager = environment.manager.insecure_clone();
+    let trader = environment.trader.insecure_clone();
+    environment.initialize_config().await;
+    environment.initialize_intermediary(LAMPORTS_PER_SOL).await;
+    let propose_manager_instruction = Instruction::propose_manager(
+        &PROGRAM_PUBKEY,
+        &investor.pubkey(),
+        &intermediary_pubkey,
+        &new_manager.pubkey(),
+        &environment.config_pubkey,
+        &environment.fee_vault_pubkey,
+    )
+    .unwrap();
+    let versioned_events = environment
+        .process(
+            [propose_manager_instruction].as_slice(),
+            [&investor].as_slice(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        versioned_events.last(),
+        Some(
+            &VersionedEvent::V1(
+                Event::RoleProposed {
+                    intermediary_pubkey,
+                    role: Role::Manager,
+                    pubkey: new_manager.pubkey(),
+                },
+            ),
+        ),
+    );
+    let intermediary = environment.get_intermediary().await;
+    assert_eq!(intermediary.manager_pubkey, manager.pubkey());
+    assert_eq!(intermediary.pending_manager_pubkey, new_manager.pubkey());
+    let accept_manager_instruction = Instruction::accept_manager(
+        &PROGRAM_PUBKEY,
+        &stranger.pubkey(),
+        &intermediary_pubkey,
+    )
+    .unwrap();
+    assert_error(
+        environment
+            .process(
+                [accept_manager_instruction].as_slice(),
+                [&stranger].as_slice(),
+            )
+            .await,
+        Error::IntermediaryInvalidPendingManager,
+    );
+    let accept_manager_instruction = Instruction::accept_manager(
+        &PROGRAM_PUBKEY,
+        &new_manager.pubkey(),
+        &intermediary_pubkey,
+    )
+    .unwrap();
+    let versioned_events = environment
+        .process(
+            [accept_manager_instruction].as_slice(),
+            [&new_manager].as_slice(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        versioned_events,
+        vec![
+            VersionedEvent::V1(
+                Event::RoleAccepted {
+                    intermediary_pubkey,
+                    role: Role::Manager,
+                    pubkey: new_manager.pubkey(),
+                },
+            ),
+        ],
+    );
+    let intermediary = environment.get_intermediary().await;
+    assert_eq!(intermediary.manager_pubkey, new_manager.pubkey());
+    assert_eq!(intermediary.pending_manager_pubkey, Pubkey::default());
+    let propose_trader_instruction = Instruction::propose_trader(
+        &PROGRAM_PUBKEY,
+        &manager.pubkey(),
+        &intermediary_pubkey,
+        &new_trader.pubkey(),
+        &environment.config_pubkey,
+        &environment.fee_vault_pubkey,
+    )
+    .unwrap();
+    assert_error(
+        environment
+            .process(
+                [propose_trader_instruction].as_slice(),
+                [&manager].as_slice(),
+            )
+            .await,
+        Error::IntermediaryInvalidManager,
+    );
+    let propose_trader_instruction = Instruction::propose_trader(
+        &PROGRAM_PUBKEY,
+        &new_manager.pubkey(),
+        &intermediary_pubkey,
+        &new_trader.pubkey(),
+        &environment.config_pubkey,
+        &environment.fee_vault_pubkey,
+    )
+    .unwrap();
+    environment
+        .process(
+            [propose_trader_instruction.clone()].as_slice(),
+            [&new_manager].as_slice(),
+        )
+        .await
+        .unwrap();
+    let cancel_trader_proposal_instruction = Instruction::cancel_trader_proposal(
+        &PROGRAM_PUBKEY,
+        &trader.pubkey(),
+        &intermediary_pubkey,
+    )
+    .unwrap();
+    let versioned_events = environment
+        .process(
+            [cancel_trader_proposal_instruction.clone()].as_slice(),
+            [&trader].as_slice(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        versioned_events,
+        vec![
+            VersionedEvent::V1(
+                Event::RoleProposalCancelled {
+                    intermediary_pubkey,
+                    role: Role::Trader,
+                    pubkey: new_trader.pubkey(),
+                },
+            ),
+        ],
+    );
+    assert_eq!(environment.get_intermediary().await.pending_trader_pubkey, Pubkey::default());
+    environment.context.warp_forward_force_reward_interval_end().unwrap();
+    assert_error(
+        environment
+            .process(
+                [cancel_trader_proposal_instruction].as_slice(),
+                [&trader].as_slice(),
+            )
+            .await,
+        Error::IntermediaryInvalidPendingTrader,
+    );
+    environment
+        .process(
+            [propose_trader_instruction].as_slice(),
+            [&new_manager].as_slice(),
+        )
+        .await
+        .unwrap();
+    let accept_trader_instruction = Instruction::accept_trader(
+        &PROGRAM_PUBKEY,
+        &new_trader.pubkey(),
+        &intermediary_pubkey,
+    )
+    .unwrap();
+    environment
+        .process(
+            [accept_trader_instruction].as_slice(),
+            [&new_trader].as_slice(),
+        )
+        .await
+        .unwrap();
+    let intermediary = environment.get_intermediary().await;
+    assert_eq!(intermediary.trader_pubkey, new_trader.pubkey());
+    assert_eq!(intermediary.pending_trader_pubkey, Pubkey::default());
+}
+#[tokio::test]
+async fn risk_limits_and_allowed_mints() {
+    let any_mint_token_mint_pubkey = Pubkey::new_unique();
+    let mut environment = Environment::start(
+        |program_test, _| {
+            program_test.add_account(
+                any_mint_token_mint_pubkey,
+                create_mint_account(6),
+            );
+        },
+    )
+    .await;
+    let intermediary_pubkey = environment.intermediary.pubkey();
+    let intermediary_pubkeys = environment.intermediary_pubkeys();
+    environment.initialize_config().await;
+    environment.initialize_intermediary(LAMPORTS_PER_SOL).await;
+    assert_error(
+        environment.set_risk_limits(LAMPORTS_PER_SOL, 10_001, 10, 100).await,
+        Error::RiskLimitsInvalidParameters,
+    );
+    assert_error(
+        environment.set_risk_limits(LAMPORTS_PER_SOL, 5_000, 10, 0).await,
+        Error::RiskLimitsInvalidParameters,
+    );
+    let versioned_events = environment.set_risk_limits(LAMPORTS_PER_SOL, 5_000, 10, 100).await.unwrap();
+    assert_eq!(
+        versioned_events,
+        vec![
+            VersionedEvent::V1(
+                Event::RiskLimitsSet {
+                    intermediary_pubkey,
+                    maximum_lamports_per_trade: LAMPORTS_PER_SOL,
+                    maximum_treasury_share_per_trade_basis_points: 5_000,
+                    maximum_trades_per_slot_window: 10,
+                    slot_window_length: 100,
+                },
+            ),
+        ],
+    );
+    let risk_limits = borsh::from_slice::<RiskLimits>(
+        environment.get_account(&intermediary_pubkeys.risk_limits_pubkey).await.unwrap().data.as_slice(),
+    )
+    .unwrap();
+    assert!(risk_limits.is_initialized);
+    assert_eq!(risk_limits.intermediary_pubkey, intermediary_pubkey);
+    assert_eq!(risk_limits.maximum_lamports_per_trade, LAMPORTS_PER_SOL);
+    assert_eq!(risk_limits.slot_window_length, 100);
+    let versioned_events = environment.add_allowed_mint(&any_mint_token_mint_pubkey).await.unwrap();
+    assert_eq!(
+        versioned_events,
+        vec![
+            VersionedEvent::V1(
+                Event::AllowedMintAdded {
+                    intermediary_pubkey,
+                    mint_pubkey: any_mint_token_mint_pubkey,
+                },
+            ),
+        ],
+    );
+    let allowed_mints = borsh::from_slice::<AllowedMints>(
+        environment.get_account(&intermediary_pubkeys.allowed_mints_pubkey).await.unwrap().data.as_slice(),
+    )
+    .unwrap();
+    assert_eq!(allowed_mints.mint_pubkeys, vec![any_mint_token_mint_pubkey]);
+    environment.context.warp_forward_force_reward_interval_end().unwrap();
+    assert_error(
+        environment.add_allowed_mint(&any_mint_token_mint_pubkey).await,
+        Error::AllowedMintsAlreadyContainsMint,
+    );
+    let manager = environment.manager.insecure_clone();
+    let remove_allowed_mint_instruction = Instruction::remove_allowed_mint(
+        &PROGRAM_PUBKEY,
+        &manager.pubkey(),
+        &intermediary_pubkey,
+        &intermediary_pubkeys.allowed_mints_pubkey,
+        &any_mint_token_mint_pubkey,
+    )
+    .unwrap();
+    let versioned_events = environment
+        .process(
+            [remove_allowed_mint_instruction].as_slice(),
+            [&manager].as_slice(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        versioned_events,
+        vec![
+            VersionedEvent::V1(
+                Event::AllowedMintRemoved {
+                    intermediary_pubkey,
+                    mint_pubkey: any_mint_token_mint_pubkey,
+                },
+            ),
+        ],
+    );
+    let allowed_mints = borsh::from_slice::<AllowedMints>(
+        environment.get_account(&intermediary_pubkeys.allowed_mints_pubkey).await.unwrap().data.as_slice(),
+    )
+    .unwrap();
+    assert!(allowed_mints.mint_pubkeys.is_empty());
+}
+#[tokio::test]
+async fn close_requires_empty_token_positions() {
+    let any_mint_token_mint_pubkey = Pubkey::new_unique();
+    let any_mint_token_2022_mint_pubkey = Pubkey::new_unique();
+    let mut any_mint_token_accounts = Vec::<AnyMintTokenAccountPubkeys>::with_capacity(2);
+    let mut environment = Environment::start(
+        |program_test, intermediary_pubkey| {
+            for (any_mint_token_mint_pubkey_, any_mint_token_program_pubkey) in [
+                (
+                    any_mint_token_mint_pubkey,
+                    spl_token::ID,
+                ),
+                (
+                    any_mint_token_2022_mint_pubkey,
+                    spl_token_2022::ID,
+                ),
+            ] {
+                let (any_mint_token_account_pubkey, _) = AnyMintTokenAccountAddressResolver {
+                    any_mint_token_mint_pubkey: &any_mint_token_mint_pubkey_,
+                    intermediary_pubkey,
+                }
+                .find();
+                let (any_mint_token_account_authority_pubkey, _) = AnyMintTokenAccountAuthorityAddressResolver {
+                    any_mint_token_account_pubkey: &any_mint_token_account_pubkey,
+                    intermediary_pubkey,
+                }
+                .find();
+                if any_mint_token_program_pubkey == spl_token::ID {
+                    program_test.add_account(
+                        any_mint_token_mint_pubkey_,
+                        create_mint_account(6),
+                    );
+                    program_test.add_account(
+                        any_mint_token_account_pubkey,
+                        create_token_account(
+                            &any_mint_token_mint_pubkey_,
+                            &any_mint_token_account_authority_pubkey,
+                            1,
+                        ),
+                    );
+                } else {
+                    program_test.add_account(
+                        any_mint_token_mint_pubkey_,
+                        create_token_2022_mint_account_with_transfer_fee(6, 100),
+                    );
+                    program_test.add_account(
+                        any_mint_token_account_pubkey,
+                        create_token_2022_account(
+                            &any_mint_token_mint_pubkey_,
+                            &any_mint_token_account_authority_pubkey,
+                            0,
+                        ),
+                    );
+                }
+                any_mint_token_accounts.push(
+                    AnyMintTokenAccountPubkeys {
+                        any_mint_token_mint_pubkey: any_mint_token_mint_pubkey_,
+                        any_mint_token_account_pubkey,
+                        any_mint_token_account_authority_pubkey,
+                        any_mint_token_program_pubkey,
+                    },
+                );
+            }
+        },
+    )
+    .await;
+    let intermediary_pubkey = environment.intermediary.pubkey();
+    let intermediary_pubkeys = environment.intermediary_pubkeys();
+    let investor = environment.investor.insecure_clone();
+    environment.initialize_config().await;
+    environment.initialize_intermediary(LAMPORTS_PER_SOL).await;
+    let trader_pubkey = environment.trader.pubkey();
+    environment.add_trader_seat(&trader_pubkey, LAMPORTS_PER_SOL).await.unwrap();
+    let (trader_seat_pubkey, _) = environment.trader_seat_pubkey(&trader_pubkey);
+    let close_intermediary_instruction = |trader_seat_pubkeys: &[Pubkey]| {
+        Instruction::close_intermediary(
+            &PROGRAM_PUBKEY,
+            &investor.pubkey(),
+            &intermediary_pubkey,
+            &intermediary_pubkeys.w_sol_token_account_pubkey,
+            &intermediary_pubkeys.w_sol_token_account_authority_pubkey,
+            &intermediary_pubkeys.risk_limits_pubkey,
+            &intermediary_pubkeys.allowed_mints_pubkey,
+            any_mint_token_accounts.as_slice(),
+            trader_seat_pubkeys,
+        )
+        .unwrap()
+    };
+    assert_error(
+        environment
+            .process(
+                [close_intermediary_instruction([].as_slice())].as_slice(),
+                [&investor].as_slice(),
+            )
+            .await,
+        Error::IntermediaryHasTraderSeats,
+    );
+    assert_error(
+        environment
+            .process(
+                [close_intermediary_instruction([trader_seat_pubkey].as_slice())].as_slice(),
+                [&investor].as_slice(),
+            )
+            .await,
+        Error::TokenAccountInvalidAmount,
+    );
+    let mut any_mint_token_account = environment.get_account(&any_mint_token_accounts[0].any_mint_token_account_pubkey).await.unwrap();
+    any_mint_token_account.data = create_token_account(
+        &any_mint_token_mint_pubkey,
+        &any_mint_token_accounts[0].any_mint_token_account_authority_pubkey,
+        0,
+    )
+    .data;
+    environment.context.set_account(
+        &any_mint_token_accounts[0].any_mint_token_account_pubkey,
+        &any_mint_token_account.into(),
+    );
+    environment.set_risk_limits(LAMPORTS_PER_SOL, 5_000, 10, 100).await.unwrap();
+    let investor_lamports_before_closing = environment.get_lamports(&investor.pubkey()).await;
+    let lamports_to_investor = environment.get_lamports(&intermediary_pubkey).await
+        + environment.get_lamports(&intermediary_pubkeys.w_sol_token_account_pubkey).await
+        + environment.get_lamports(&intermediary_pubkeys.risk_limits_pubkey).await
+        + environment.get_lamports(&any_mint_token_accounts[0].any_mint_token_account_pubkey).await
+        + environment.get_lamports(&any_mint_token_accounts[1].any_mint_token_account_pubkey).await
+        + environment.get_lamports(&trader_seat_pubkey).await;
+    let versioned_events = environment
+        .process(
+            [close_intermediary_instruction([trader_seat_pubkey].as_slice())].as_slice(),
+            [&investor].as_slice(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        versioned_events[0],
+        VersionedEvent::V1(
+            Event::TraderSeatRemoved {
+                intermediary_pubkey,
+                trader_pubkey,
+                spent_lamports: 0,
+                received_lamports: 0,
+            },
+        ),
+    );
+    assert_eq!(environment.get_lamports(&investor.pubkey()).await, investor_lamports_before_closing + lamports_to_investor);
+    for any_mint_token_account in any_mint_token_accounts.iter() {
+        assert!(environment.get_account(&any_mint_token_account.any_mint_token_account_pubkey).await.is_none());
+    }
+    assert!(environment.get_account(&intermediary_pubkeys.risk_limits_pubkey).await.is_none());
+    assert!(environment.get_account(&trader_seat_pubkey).await.is_none());
+}
+#[tokio::test]
+async fn trader_seats_are_managed_per_trader() {
+    let mut environment = Environment::start(|_, _| {}).await;
+    let intermediary_pubkey = environment.intermediary.pubkey();
+    let manager = environment.manager.insecure_clone();
+    let trader_pubkey = environment.trader.pubkey();
+    let second_trader = Keypair::new();
+    environment.initialize_config().await;
+    environment.initialize_intermediary(LAMPORTS_PER_SOL).await;
+    environment
+        .process(
+            [
+                solana_sdk::system_instruction::transfer(
+                    &manager.pubkey(),
+                    &second_trader.pubkey(),
+                    LAMPORTS_PER_SOL,
+                ),
+            ]
+            .as_slice(),
+            [&manager].as_slice(),
+        )
+        .await
+        .unwrap();
+    let versioned_events = environment.add_trader_seat(&trader_pubkey, LAMPORTS_PER_SOL / 2).await.unwrap();
+    assert_eq!(
+        versioned_events,
+        vec![
+            VersionedEvent::V1(
+                Event::TraderSeatAdded {
+                    intermediary_pubkey,
+                    trader_pubkey,
+                    allocated_lamports: LAMPORTS_PER_SOL / 2,
+                },
+            ),
+        ],
+    );
+    environment.add_trader_seat(&second_trader.pubkey(), LAMPORTS_PER_SOL / 4).await.unwrap();
+    let trader_seat = environment.get_trader_seat(&trader_pubkey).await;
+    assert!(trader_seat.is_initialized);
+    assert_eq!(trader_seat.intermediary_pubkey, intermediary_pubkey);
+    assert_eq!(trader_seat.trader_pubkey, trader_pubkey);
+    assert_eq!(trader_seat.allocated_lamports, LAMPORTS_PER_SOL / 2);
+    assert_eq!(trader_seat.spent_lamports, 0);
+    assert_eq!(trader_seat.received_lamports, 0);
+    assert_eq!(trader_seat.trades_quantity, 0);
+    assert_eq!(environment.get_trader_seat(&second_trader.pubkey()).await.allocated_lamports, LAMPORTS_PER_SOL / 4);
+    assert_eq!(environment.get_intermediary().await.trader_seats_quantity, 2);
+    environment.context.warp_forward_force_reward_interval_end().unwrap();
+    assert_error(
+        environment.add_trader_seat(&trader_pubkey, LAMPORTS_PER_SOL).await,
+        Error::TraderSeatIsAlreadyInitialized,
+    );
+    let (trader_seat_pubkey, _) = environment.trader_seat_pubkey(&trader_pubkey);
+    let remove_trader_seat_instruction = Instruction::remove_trader_seat(
+        &PROGRAM_PUBKEY,
+        &trader_pubkey,
+        &intermediary_pubkey,
+        &trader_seat_pubkey,
+    )
+    .unwrap();
+    let trader = environment.trader.insecure_clone();
+    assert_error(
+        environment
+            .process(
+                [remove_trader_seat_instruction].as_slice(),
+                [&trader].as_slice(),
+            )
+            .await,
+        Error::IntermediaryInvalidManager,
+    );
+    let manager_lamports_before_removing = environment.get_lamports(&manager.pubkey()).await;
+    let trader_seat_lamports = environment.get_lamports(&trader_seat_pubkey).await;
+    let remove_trader_seat_instruction = Instruction::remove_trader_seat(
+        &PROGRAM_PUBKEY,
+        &manager.pubkey(),
+        &intermediary_pubkey,
+        &trader_seat_pubkey,
+    )
+    .unwrap();
+    let versioned_events = environment
+        .process(
+            [remove_trader_seat_instruction].as_slice(),
+            [&manager].as_slice(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        versioned_events,
+        vec![
+            VersionedEvent::V1(
+                Event::TraderSeatRemoved {
+                    intermediary_pubkey,
+                    trader_pubkey,
+                    spent_lamports: 0,
+                    received_lamports: 0,
+                },
+            ),
+        ],
+    );
+    assert!(environment.get_account(&trader_seat_pubkey).await.is_none());
+    assert_eq!(environment.get_lamports(&manager.pubkey()).await, manager_lamports_before_removing + trader_seat_lamports);
+    assert_eq!(environment.get_intermediary().await.trader_seats_quantity, 1);
+}
+#[tokio::test]
+async fn pause_blocks_trading_but_not_withdrawals() {
+    let any_mint_token_mint_pubkey = Pubkey::new_unique();
+    let mut environment = Environment::start(
+        |program_test, _| {
+            program_test.add_account(
+                any_mint_token_mint_pubkey,
+                create_mint_account(6),
+            );
+        },
+    )
+    .await;
+    let intermediary_pubkey = environment.intermediary.pubkey();
+    let intermediary_pubkeys = environment.intermediary_pubkeys();
+    let investor = environment.investor.insecure_clone();
+    let manager = environment.manager.insecure_clone();
+    let trader = environment.trader.insecure_clone();
+    environment.initialize_config().await;
+    environment.initialize_intermediary(LAMPORTS_PER_SOL).await;
+    let pause_instruction = Instruction::pause(
+        &PROGRAM_PUBKEY,
+        &manager.pubkey(),
+        &intermediary_pubkey,
+    )
+    .unwrap();
+    let versioned_events = environment
+        .process(
+            [pause_instruction].as_slice(),
+            [&manager].as_slice(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        versioned_events,
+        vec![
+            VersionedEvent::V1(
+                Event::Paused {
+                    intermediary_pubkey,
+                    authority_pubkey: manager.pubkey(),
+                },
+            ),
+        ],
+    );
+    assert!(environment.get_intermediary().await.is_paused);
+    let (any_mint_token_account_pubkey, any_mint_token_account_pubkey_bump_seed) = AnyMintTokenAccountAddressResolver {
+        any_mint_token_mint_pubkey: &any_mint_token_mint_pubkey,
+        intermediary_pubkey: &intermediary_pubkey,
+    }
+    .find();
+    let (any_mint_token_account_authority_pubkey, any_mint_token_account_authority_pubkey_bump_seed) = AnyMintTokenAccountAuthorityAddressResolver {
+        any_mint_token_account_pubkey: &any_mint_token_account_pubkey,
+        intermediary_pubkey: &intermediary_pubkey,
+    }
+    .find();
+    let (trader_seat_pubkey, _) = environment.trader_seat_pubkey(&trader.pubkey());
+    let buy_on_raydium_instruction = Instruction::buy_on_raydium(
+        &PROGRAM_PUBKEY,
+        &trader.pubkey(),
+        &intermediary_pubkey,
+        &intermediary_pubkeys.w_sol_token_account_pubkey,
+        &intermediary_pubkeys.w_sol_token_account_authority_pubkey,
+        &intermediary_pubkeys.temporary_w_sol_token_account_pubkey,
+        &intermediary_pubkeys.temporary_w_sol_token_account_authority_pubkey,
+        &any_mint_token_account_pubkey,
+        &any_mint_token_account_authority_pubkey,
+        &any_mint_token_mint_pubkey,
+        &RAYDIUM_LIQUIDITY_POOL_V4_PROGRAM_PUBKEY,
+        &Pubkey::new_unique(),
+        &Pubkey::new_unique(),
+        &Pubkey::new_unique(),
+        &Pubkey::new_unique(),
+        &Pubkey::new_unique(),
+        &Pubkey::new_unique(),
+        &Pubkey::new_unique(),
+        &Pubkey::new_unique(),
+        &Pubkey::new_unique(),
+        &Pubkey::new_unique(),
+        &Pubkey::new_unique(),
+        &Pubkey::new_unique(),
+        &Pubkey::new_unique(),
+        &intermediary_pubkeys.allowed_mints_pubkey,
+        &intermediary_pubkeys.risk_limits_pubkey,
+        &trader_seat_pubkey,
+        &environment.config_pubkey,
+        &environment.fee_vault_pubkey,
+        LAMPORTS_PER_SOL / 10,
+        0,
+        any_mint_token_account_pubkey_bump_seed,
+        any_mint_token_account_authority_pubkey_bump_seed,
+    )
+    .unwrap();
+    assert_error(
+        environment
+            .process(
+                [buy_on_raydium_instruction].as_slice(),
+                [&trader].as_slice(),
+            )
+            .await,
+        Error::IntermediaryPaused,
+    );
+    let unpause_instruction = Instruction::unpause(
+        &PROGRAM_PUBKEY,
+        &manager.pubkey(),
+        &intermediary_pubkey,
+    )
+    .unwrap();
+    assert_error(
+        environment
+            .process(
+                [unpause_instruction].as_slice(),
+                [&manager].as_slice(),
+            )
+            .await,
+        Error::IntermediaryInvalidInvestor,
+    );
+    environment.withdraw_funds(LAMPORTS_PER_SOL / 4).await.unwrap();
+    assert_eq!(environment.get_token_account_amount(&intermediary_pubkeys.w_sol_token_account_pubkey).await, LAMPORTS_PER_SOL - LAMPORTS_PER_SOL / 4);
+    let unpause_instruction = Instruction::unpause(
+        &PROGRAM_PUBKEY,
+        &investor.pubkey(),
+        &intermediary_pubkey,
+    )
+    .unwrap();
+    let versioned_events = environment
+        .process(
+            [unpause_instruction.clone()].as_slice(),
+            [&investor].as_slice(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        versioned_events,
+        vec![
+            VersionedEvent::V1(
+                Event::Unpaused {
+                    intermediary_pubkey,
+                    authority_pubkey: investor.pubkey(),
+                },
+            ),
+        ],
+    );
+    assert!(!environment.get_intermediary().await.is_paused);
+    environment.context.warp_forward_force_reward_interval_end().unwrap();
+    assert_error(
+        environment
+            .process(
+                [unpause_instruction].as_slice(),
+                [&investor].as_slice(),
+            )
+            .await,
+        Error::IntermediaryIsNotPaused,
+    );
+}
+#[tokio::test]
+async fn migrate_unversioned_intermediary() {
+    let mut environment = Environment::start(|_, _| {}).await;
+    let intermediary_pubkey = environment.intermediary.pubkey();
+    let manager = environment.manager.insecure_clone();
+    environment.initialize_config().await;
+    environment.initialize_intermediary(LAMPORTS_PER_SOL).await;
+    let intermediary = environment.get_intermediary().await;
+    assert_eq!(intermediary.version, Intermediary::VERSION);
+    // The original layout, assembled byte by byte rather than through borsh.
+    let mut intermediary_v0_data = vec![intermediary.is_initialized as u8];
+    for pubkey in [
+        intermediary.investor_pubkey,
+        intermediary.manager_pubkey,
+        intermediary.trader_pubkey,
+        intermediary.w_sol_token_account_pubkey,
+        intermediary.w_sol_token_account_authority_pubkey,
+        intermediary.temporary_w_sol_token_account_pubkey,
+        intermediary.temporary_w_sol_token_account_authority_pubkey,
+    ] {
+        intermediary_v0_data.extend_from_slice(pubkey.as_ref());
+    }
+    intermediary_v0_data.extend_from_slice(
+        [
+            intermediary.w_sol_token_account_authority_pubkey_bump_seed,
+            intermediary.temporary_w_sol_token_account_pubkey_bump_seed,
+            intermediary.temporary_w_sol_token_account_authority_pubkey_bump_seed,
+        ]
+        .as_slice(),
+    );
+    assert_eq!(intermediary_v0_data.len(), 228);
+    let intermediary_v0 = IntermediaryV0 {
+        is_initialized: intermediary.is_initialized,
+        investor_pubkey: intermediary.investor_pubkey,
+        manager_pubkey: intermediary.manager_pubkey,
+        trader_pubkey: intermediary.trader_pubkey,
+        w_sol_token_account_pubkey: intermediary.w_sol_token_account_pubkey,
+        w_sol_token_account_authority_pubkey: intermediary.w_sol_token_account_authority_pubkey,
+        temporary_w_sol_token_account_pubkey: intermediary.temporary_w_sol_token_account_pubkey,
+        temporary_w_sol_token_account_authority_pubkey: intermediary.temporary_w_sol_token_account_authority_pubkey,
+        w_sol_token_account_authority_pubkey_bump_seed: intermediary.w_sol_token_account_authority_pubkey_bump_seed,
+        temporary_w_sol_token_account_pubkey_bump_seed: intermediary.temporary_w_sol_token_account_pubkey_bump_seed,
+        temporary_w_sol_token_account_authority_pubkey_bump_seed: intermediary.temporary_w_sol_token_account_authority_pubkey_bump_seed,
+    };
+    assert_eq!(borsh::to_vec(&intermediary_v0).unwrap(), intermediary_v0_data);
+    let rent = environment.context.banks_client.get_rent().await.unwrap();
+    let mut intermediary_account = environment.get_account(&intermediary_pubkey).await.unwrap();
+    let intermediary_data_length = intermediary_account.data.len();
+    intermediary_account.lamports = rent.minimum_balance(intermediary_v0_data.len());
+    intermediary_account.data = intermediary_v0_data;
+    environment.context.set_account(
+        &intermediary_pubkey,
+        &intermediary_account.into(),
+    );
+    assert_eq!(environment.get_intermediary().await.version, 0);
+    environment.withdraw_funds(LAMPORTS_PER_SOL / 4).await.unwrap();
+    let pause_instruction = Instruction::pause(
+        &PROGRAM_PUBKEY,
+        &manager.pubkey(),
+        &intermediary_pubkey,
+    )
+    .unwrap();
+    assert_error(
+        environment
+            .process(
+                [pause_instruction.clone()].as_slice(),
+                [&manager].as_slice(),
+            )
+            .await,
+        Error::IntermediaryIsNotMigrated,
+    );
+    let migrate_intermediary_instruction = Instruction::migrate_intermediary(
+        &PROGRAM_PUBKEY,
+        &manager.pubkey(),
+        &intermediary_pubkey,
+    )
+    .unwrap();
+    let versioned_events = environment
+        .process(
+            [migrate_intermediary_instruction.clone()].as_slice(),
+            [&manager].as_slice(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        versioned_events,
+        vec![
+            VersionedEvent::V1(
+                Event::IntermediaryMigrated {
+                    intermediary_pubkey,
+                    previous_version: 0,
+                    version: Intermediary::VERSION,
+                },
+            ),
+        ],
+    );
+    let intermediary_account = environment.get_account(&intermediary_pubkey).await.unwrap();
+    assert_eq!(intermediary_account.data.len(), intermediary_data_length);
+    assert!(rent.is_exempt(intermediary_account.lamports, intermediary_data_length));
+    let intermediary = environment.get_intermediary().await;
+    assert_eq!(intermediary.version, Intermediary::VERSION);
+    assert_eq!(intermediary.manager_pubkey, manager.pubkey());
+    assert_eq!(intermediary.pending_manager_pubkey, Pubkey::default());
+    assert!(!intermediary.is_paused);
+    environment.context.warp_forward_force_reward_interval_end().unwrap();
+    assert_error(
+        environment
+            .process(
+                [migrate_intermediary_instruction].as_slice(),
+                [&manager].as_slice(),
+            )
+            .await,
+        Error::IntermediaryIsAlreadyMigrated,
+    );
+    environment
+        .process(
+            [pause_instruction].as_slice(),
+            [&manager].as_slice(),
+        )
+        .await
+        .unwrap();
+}
+#[tokio::test]
+async fn liquidate_all_closes_positions_into_treasury() {
+    let any_mint_token_mint_pubkeys = [
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    ];
+    let mut any_mint_token_accounts = Vec::<AnyMintTokenAccountPubkeys>::with_capacity(2);
+    let mut mock_raydium_pools = Vec::<MockRaydiumPool>::with_capacity(2);
+    let mut environment = Environment::start(
+        |program_test, intermediary_pubkey| {
+            add_mock_raydium_program(program_test);
+            for (any_mint_token_mint_pubkey, any_mint_token_account_amount) in any_mint_token_mint_pubkeys.iter().zip([1_000_000, 0]) {
+                let (any_mint_token_account_pubkey, _) = AnyMintTokenAccountAddressResolver {
+                    any_mint_token_mint_pubkey,
+                    intermediary_pubkey,
+                }
+                .find();
+                let (any_mint_token_account_authority_pubkey, _) = AnyMintTokenAccountAuthorityAddressResolver {
+                    any_mint_token_account_pubkey: &any_mint_token_account_pubkey,
+                    intermediary_pubkey,
+                }
+                .find();
+                program_test.add_account(
+                    *any_mint_token_mint_pubkey,
+                    create_mint_account(6),
+                );
+                program_test.add_account(
+                    any_mint_token_account_pubkey,
+                    create_token_account(
+                        any_mint_token_mint_pubkey,
+                        &any_mint_token_account_authority_pubkey,
+                        any_mint_token_account_amount,
+                    ),
+                );
+                any_mint_token_accounts.push(
+                    AnyMintTokenAccountPubkeys {
+                        any_mint_token_mint_pubkey: *any_mint_token_mint_pubkey,
+                        any_mint_token_account_pubkey,
+                        any_mint_token_account_authority_pubkey,
+                        any_mint_token_program_pubkey: spl_token::ID,
+                    },
+                );
+                mock_raydium_pools.push(
+                    MockRaydiumPool::add(
+                        program_test,
+                        any_mint_token_mint_pubkey,
+                        &spl_token::native_mint::ID,
+                        1_000_000_000_000,
+                        100 * LAMPORTS_PER_SOL,
+                    ),
+                );
+            }
+        },
+    )
+    .await;
+    let intermediary_pubkey = environment.intermediary.pubkey();
+    let intermediary_pubkeys = environment.intermediary_pubkeys();
+    let manager = environment.manager.insecure_clone();
+    let trader = environment.trader.insecure_clone();
+    environment.initialize_config().await;
+    environment.initialize_intermediary(LAMPORTS_PER_SOL).await;
+    let liquidation_legs = any_mint_token_accounts
+        .iter()
+        .zip(mock_raydium_pools.iter())
+        .map(
+            |(any_mint_token_account, mock_raydium_pool)| LiquidationLeg {
+                any_mint_token_mint_pubkey: any_mint_token_account.any_mint_token_mint_pubkey,
+                any_mint_token_account_pubkey: any_mint_token_account.any_mint_token_account_pubkey,
+                any_mint_token_account_authority_pubkey: any_mint_token_account.any_mint_token_account_authority_pubkey,
+                any_mint_token_program_pubkey: any_mint_token_account.any_mint_token_program_pubkey,
+                venue: Venue::Raydium,
+                venue_account_pubkeys: mock_raydium_pool.get_venue_account_pubkeys(),
+                minimum_output_token_amount: 0,
+            },
+        )
+        .collect::<Vec<LiquidationLeg>>();
+    let liquidate_all_instruction = |authority_pubkey: &Pubkey| {
+        Instruction::liquidate_all(
+            &PROGRAM_PUBKEY,
+            authority_pubkey,
+            &intermediary_pubkey,
+            &intermediary_pubkeys.w_sol_token_account_pubkey,
+            &intermediary_pubkeys.temporary_w_sol_token_account_pubkey,
+            liquidation_legs.as_slice(),
+        )
+        .unwrap()
+    };
+    assert_error(
+        environment
+            .process(
+                [liquidate_all_instruction(&trader.pubkey())].as_slice(),
+                [&trader].as_slice(),
+            )
+            .await,
+        Error::IntermediaryInvalidInvestorOrManager,
+    );
+    let mut truncated_liquidate_all_instruction = liquidate_all_instruction(&manager.pubkey());
+    truncated_liquidate_all_instruction.accounts.pop();
+    assert_error(
+        environment
+            .process(
+                [truncated_liquidate_all_instruction].as_slice(),
+                [&manager].as_slice(),
+            )
+            .await,
+        Error::InvalidAccountPubkey,
+    );
+    let pumpfun_curve_liquidation_leg = LiquidationLeg {
+        any_mint_token_mint_pubkey: any_mint_token_accounts[0].any_mint_token_mint_pubkey,
+        any_mint_token_account_pubkey: any_mint_token_accounts[0].any_mint_token_account_pubkey,
+        any_mint_token_account_authority_pubkey: any_mint_token_accounts[0].any_mint_token_account_authority_pubkey,
+        any_mint_token_program_pubkey: spl_token::ID,
+        venue: Venue::PumpfunCurve,
+        venue_account_pubkeys: [PUMPFUN_PROGRAM_PUBKEY]
+            .into_iter()
+            .chain((0..6).map(|_| Pubkey::new_unique()))
+            .collect::<Vec<Pubkey>>(),
+        minimum_output_token_amount: 0,
+    };
+    assert_error(
+        environment
+            .process(
+                [
+                    Instruction::liquidate_all(
+                        &PROGRAM_PUBKEY,
+                        &manager.pubkey(),
+                        &intermediary_pubkey,
+                        &intermediary_pubkeys.w_sol_token_account_pubkey,
+                        &intermediary_pubkeys.temporary_w_sol_token_account_pubkey,
+                        [pumpfun_curve_liquidation_leg].as_slice(),
+                    )
+                    .unwrap(),
+                ]
+                .as_slice(),
+                [&manager].as_slice(),
+            )
+            .await,
+        Error::VenueIsNotSupported,
+    );
+    // The positions were put in place directly, so they are counted by hand.
+    let mut intermediary = environment.get_intermediary().await;
+    intermediary.open_positions_quantity = 2;
+    let mut intermediary_account = environment.get_account(&intermediary_pubkey).await.unwrap();
+    intermediary_account.data = borsh::to_vec(&intermediary).unwrap();
+    environment.context.set_account(
+        &intermediary_pubkey,
+        &intermediary_account.into(),
+    );
+    let w_sol_token_account_amount = environment.get_token_account_amount(&intermediary_pubkeys.w_sol_token_account_pubkey).await;
+    let any_mint_token_account_lamports = [
+        environment.get_lamports(&any_mint_token_accounts[0].any_mint_token_account_pubkey).await,
+        environment.get_lamports(&any_mint_token_accounts[1].any_mint_token_account_pubkey).await,
+    ];
+    let manager_lamports_before_liquidation = environment.get_lamports(&manager.pubkey()).await;
+    let versioned_events = environment
+        .process(
+            [liquidate_all_instruction(&manager.pubkey())].as_slice(),
+            [&manager].as_slice(),
+        )
+        .await
+        .unwrap();
+    // The constant product output of the mock pool for the whole position.
+    let amount_out = (100 * LAMPORTS_PER_SOL as u128 * 1_000_000 / (1_000_000_000_000 + 1_000_000)) as u64;
+    // Closed positions refund their rent into the treasury.
+    let treasury_amounts = [
+        w_sol_token_account_amount + amount_out + any_mint_token_account_lamports[0],
+        w_sol_token_account_amount + amount_out + any_mint_token_account_lamports[0] + any_mint_token_account_lamports[1],
+    ];
+    assert_eq!(
+        versioned_events,
+        vec![
+            VersionedEvent::V1(
+                Event::Traded {
+                    intermediary_pubkey,
+                    venue: Venue::Raydium,
+                    side: Side::Sell,
+                    mint_pubkey: any_mint_token_mint_pubkeys[0],
+                    amount_in: 1_000_000,
+                    amount_out,
+                    treasury_amount: treasury_amounts[0],
+                },
+            ),
+            VersionedEvent::V1(
+                Event::Traded {
+                    intermediary_pubkey,
+                    venue: Venue::Raydium,
+                    side: Side::Sell,
+                    mint_pubkey: any_mint_token_mint_pubkeys[1],
+                    amount_in: 0,
+                    amount_out: 0,
+                    treasury_amount: treasury_amounts[1],
+                },
+            ),
+        ],
+    );
+    for any_mint_token_account in any_mint_token_accounts.iter() {
+        assert!(environment.get_account(&any_mint_token_account.any_mint_token_account_pubkey).await.is_none());
+    }
+    assert!(environment.get_account(&intermediary_pubkeys.temporary_w_sol_token_account_pubkey).await.is_none());
+    assert_eq!(environment.get_token_account_amount(&intermediary_pubkeys.w_sol_token_account_pubkey).await, treasury_amounts[1]);
+    assert_eq!(environment.get_intermediary().await.open_positions_quantity, 0);
+    // The temporary wSOL account rent is refunded to the liquidation authority.
+    assert_eq!(environment.get_lamports(&manager.pubkey()).await, manager_lamports_before_liquidation);
+}
+#[tokio::test]
+async fn investor_withdraws_token_position() {
+    let any_mint_token_mint_pubkey = Pubkey::new_unique();
+    let mut any_mint_token_account_pubkeys = (Pubkey::default(), Pubkey::default());
+    let mut environment = Environment::start(
+        |program_test, intermediary_pubkey| {
+            let (any_mint_token_account_pubkey, _) = AnyMintTokenAccountAddressResolver {
+                any_mint_token_mint_pubkey: &any_mint_token_mint_pubkey,
+                intermediary_pubkey,
+            }
+            .find();
+            let (any_mint_token_account_authority_pubkey, _) = AnyMintTokenAccountAuthorityAddressResolver {
+                any_mint_token_account_pubkey: &any_mint_token_account_pubkey,
+                intermediary_pubkey,
+            }
+            .find();
+            program_test.add_account(
+                any_mint_token_mint_pubkey,
+                create_mint_account(6),
+            );
+            program_test.add_account(
+                any_mint_token_account_pubkey,
+                create_token_account(
+                    &any_mint_token_mint_pubkey,
+                    &any_mint_token_account_authority_pubkey,
+                    100,
+                ),
+            );
+            any_mint_token_account_pubkeys = (any_mint_token_account_pubkey, any_mint_token_account_authority_pubkey);
+        },
+    )
+    .await;
+    let intermediary_pubkey = environment.intermediary.pubkey();
+    let investor = environment.investor.insecure_clone();
+    let trader = environment.trader.insecure_clone();
+    environment.initialize_config().await;
+    environment.initialize_intermediary(LAMPORTS_PER_SOL).await;
+    let withdraw_token_instruction = |intermediary_investor_pubkey: &Pubkey, amount: u64| {
+        Instruction::withdraw_token(
+            &PROGRAM_PUBKEY,
+            intermediary_investor_pubkey,
+            &intermediary_pubkey,
+            &any_mint_token_mint_pubkey,
+            &any_mint_token_account_pubkeys.0,
+            &any_mint_token_account_pubkeys.1,
+            &get_associated_token_address(
+                intermediary_investor_pubkey,
+                &any_mint_token_mint_pubkey,
+                &spl_token::ID,
+            ),
+            &spl_token::ID,
+            amount,
+        )
+        .unwrap()
+    };
+    assert_error(
+        environment
+            .process(
+                [withdraw_token_instruction(&trader.pubkey(), 60)].as_slice(),
+                [&trader].as_slice(),
+            )
+            .await,
+        Error::IntermediaryInvalidInvestor,
+    );
+    assert_error(
+        environment
+            .process(
+                [withdraw_token_instruction(&investor.pubkey(), 101)].as_slice(),
+                [&investor].as_slice(),
+            )
+            .await,
+        Error::TokenAccountInsufficientAmount,
+    );
+    let intermediary_investor_token_account_pubkey = get_associated_token_address(
+        &investor.pubkey(),
+        &any_mint_token_mint_pubkey,
+        &spl_token::ID,
+    );
+    let versioned_events = environment
+        .process(
+            [withdraw_token_instruction(&investor.pubkey(), 60)].as_slice(),
+            [&investor].as_slice(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        versioned_events,
+        vec![
+            VersionedEvent::V1(
+                Event::TokenWithdrawn {
+                    intermediary_pubkey,
+                    mint_pubkey: any_mint_token_mint_pubkey,
+                    investor_token_account_pubkey: intermediary_investor_token_account_pubkey,
+                    amount: 60,
+                },
+            ),
+        ],
+    );
+    assert_eq!(environment.get_token_account_amount(&any_mint_token_account_pubkeys.0).await, 40);
+    assert_eq!(environment.get_token_account_amount(&intermediary_investor_token_account_pubkey).await, 60);
+}
+#[tokio::test]
+async fn investor_withdraws_token_2022_position_net_of_transfer_fee() {
+    let any_mint_token_mint_pubkey = Pubkey::new_unique();
+    let mut any_mint_token_account_pubkeys = (Pubkey::default(), Pubkey::default());
+    let mut environment = Environment::start(
+        |program_test, intermediary_pubkey| {
+            let (any_mint_token_account_pubkey, _) = AnyMintTokenAccountAddressResolver {
+                any_mint_token_mint_pubkey: &any_mint_token_mint_pubkey,
+                intermediary_pubkey,
+            }
+            .find();
+            let (any_mint_token_account_authority_pubkey, _) = AnyMintTokenAccountAuthorityAddressResolver {
+                any_mint_token_account_pubkey: &any_mint_token_account_pubkey,
+                intermediary_pubkey,
+            }
+            .find();
+            program_test.add_account(
+                any_mint_token_mint_pubkey,
+                create_token_2022_mint_account_with_transfer_fee(6, 100),
+            );
+            program_test.add_account(
+                any_mint_token_account_pubkey,
+                create_token_2022_account(
+                    &any_mint_token_mint_pubkey,
+                    &any_mint_token_account_authority_pubkey,
+                    1_000,
+                ),
+            );
+            any_mint_token_account_pubkeys = (any_mint_token_account_pubkey, any_mint_token_account_authority_pubkey);
+        },
+    )
+    .await;
+    let intermediary_pubkey = environment.intermediary.pubkey();
+    let investor = environment.investor.insecure_clone();
+    environment.initialize_config().await;
+    environment.initialize_intermediary(LAMPORTS_PER_SOL).await;
+    environment.add_allowed_mint(&any_mint_token_mint_pubkey).await.unwrap();
+    let intermediary_investor_token_account_pubkey = get_associated_token_address(
+        &investor.pubkey(),
+        &any_mint_token_mint_pubkey,
+        &spl_token_2022::ID,
+    );
+    let withdraw_token_instruction = |any_mint_token_program_pubkey: &Pubkey, amount: u64| {
+        Instruction::withdraw_token(
+            &PROGRAM_PUBKEY,
+            &investor.pubkey(),
+            &intermediary_pubkey,
+            &any_mint_token_mint_pubkey,
+            &any_mint_token_account_pubkeys.0,
+            &any_mint_token_account_pubkeys.1,
+            &intermediary_investor_token_account_pubkey,
+            any_mint_token_program_pubkey,
+            amount,
+        )
+        .unwrap()
+    };
+    assert_error(
+        environment
+            .process(
+                [withdraw_token_instruction(&spl_token::ID, 500)].as_slice(),
+                [&investor].as_slice(),
+            )
+            .await,
+        Error::InvalidAccountOwner,
+    );
+    let versioned_events = environment
+        .process(
+            [withdraw_token_instruction(&spl_token_2022::ID, 500)].as_slice(),
+            [&investor].as_slice(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        versioned_events,
+        vec![
+            VersionedEvent::V1(
+                Event::TokenWithdrawn {
+                    intermediary_pubkey,
+                    mint_pubkey: any_mint_token_mint_pubkey,
+                    investor_token_account_pubkey: intermediary_investor_token_account_pubkey,
+                    amount: 500,
+                },
+            ),
+        ],
+    );
+    assert_eq!(environment.get_token_account_amount(&any_mint_token_account_pubkeys.0).await, 500);
+    assert_eq!(environment.get_token_account_amount(&intermediary_investor_token_account_pubkey).await, 495);
+}
+#[tokio::test]
+async fn raydium_cpmm_and_clmm_validate_venue_accounts() {
+    let any_mint_token_mint_pubkey = Pubkey::new_unique();
+    let mut environment = Environment::start(
+        |program_test, _| {
+            program_test.add_account(
+                any_mint_token_mint_pubkey,
+                create_mint_account(6),
+            );
+        },
+    )
+    .await;
+    let intermediary_pubkey = environment.intermediary.pubkey();
+    let intermediary_pubkeys = environment.intermediary_pubkeys();
+    let trader = environment.trader.insecure_clone();
+    environment.initialize_config().await;
+    environment.initialize_intermediary(LAMPORTS_PER_SOL).await;
+    let (any_mint_token_account_pubkey, any_mint_token_account_pubkey_bump_seed) = AnyMintTokenAccountAddressResolver {
+        any_mint_token_mint_pubkey: &any_mint_token_mint_pubkey,
+        intermediary_pubkey: &intermediary_pubkey,
+    }
+    .find();
+    let (any_mint_token_account_authority_pubkey, any_mint_token_account_authority_pubkey_bump_seed) = AnyMintTokenAccountAuthorityAddressResolver {
+        any_mint_token_account_pubkey: &any_mint_token_account_pubkey,
+        intermediary_pubkey: &intermediary_pubkey,
+    }
+    .find();
+    let (trader_seat_pubkey, _) = environment.trader_seat_pubkey(&trader.pubkey());
+    let buy_on_raydium_cpmm_instruction = Instruction::buy_on_raydium_cpmm(
+        &PROGRAM_PUBKEY,
+        &trader.pubkey(),
+        &intermediary_pubkey,
+        &intermediary_pubkeys.w_sol_token_account_pubkey,
+        &intermediary_pubkeys.w_sol_token_account_authority_pubkey,
+        &intermediary_pubkeys.temporary_w_sol_token_account_pubkey,
+        &intermediary_pubkeys.temporary_w_sol_token_account_authority_pubkey,
+        &any_mint_token_account_pubkey,
+        &any_mint_token_account_authority_pubkey,
+        &any_mint_token_mint_pubkey,
+        &spl_token_2022::ID,
+        &Pubkey::new_unique(),
+        &Pubkey::new_unique(),
+        &Pubkey::new_unique(),
+        &Pubkey::new_unique(),
+        &Pubkey::new_unique(),
+        &Pubkey::new_unique(),
+        &intermediary_pubkeys.allowed_mints_pubkey,
+        &intermediary_pubkeys.risk_limits_pubkey,
+        &trader_seat_pubkey,
+        &environment.config_pubkey,
+        &environment.fee_vault_pubkey,
+        LAMPORTS_PER_SOL / 10,
+        0,
+        any_mint_token_account_pubkey_bump_seed,
+        any_mint_token_account_authority_pubkey_bump_seed,
+    )
+    .unwrap();
+    assert_error(
+        environment
+            .process(
+                [buy_on_raydium_cpmm_instruction].as_slice(),
+                [&trader].as_slice(),
+            )
+            .await,
+        Error::InvalidAccountOwner,
+    );
+    let buy_on_raydium_clmm_instruction = Instruction::buy_on_raydium_clmm(
+        &PROGRAM_PUBKEY,
+        &trader.pubkey(),
+        &intermediary_pubkey,
+        &intermediary_pubkeys.w_sol_token_account_pubkey,
+        &intermediary_pubkeys.w_sol_token_account_authority_pubkey,
+        &intermediary_pubkeys.temporary_w_sol_token_account_pubkey,
+        &intermediary_pubkeys.temporary_w_sol_token_account_authority_pubkey,
+        &any_mint_token_account_pubkey,
+        &any_mint_token_account_authority_pubkey,
+        &any_mint_token_mint_pubkey,
+        &Pubkey::new_unique(),
+        &Pubkey::new_unique(),
+        &Pubkey::new_unique(),
+        &Pubkey::new_unique(),
+        &Pubkey::new_unique(),
+        &intermediary_pubkeys.allowed_mints_pubkey,
+        &intermediary_pubkeys.risk_limits_pubkey,
+        &trader_seat_pubkey,
+        &environment.config_pubkey,
+        &environment.fee_vault_pubkey,
+        [].as_slice(),
+        LAMPORTS_PER_SOL / 10,
+        0,
+        any_mint_token_account_pubkey_bump_seed,
+        any_mint_token_account_authority_pubkey_bump_seed,
+    )
+    .unwrap();
+    assert_error(
+        environment
+            .process(
+                [buy_on_raydium_clmm_instruction].as_slice(),
+                [&trader].as_slice(),
+            )
+            .await,
+        Error::InvalidAccountPubkey,
+    );
+}
+#[tokio::test]
+async fn meteora_venues_validate_pool_accounts() {
+    let any_mint_token_mint_pubkey = Pubkey::new_unique();
+    let token_2022_mint_pubkey = Pubkey::new_unique();
+    let lb_pair_pubkey = Pubkey::new_unique();
+    let mut environment = Environment::start(
+        |program_test, _| {
+            program_test.add_account(
+                any_mint_token_mint_pubkey,
+                create_mint_account(6),
+            );
+            program_test.add_account(
+                token_2022_mint_pubkey,
+                create_token_2022_mint_account_with_transfer_fee(6, 100),
+            );
+            let mut lb_pair_account = Account::new(
+                LAMPORTS_PER_SOL,
+                904,
+                &METEORA_DLMM_PROGRAM_PUBKEY,
+            );
+            lb_pair_account.data[88..120].copy_from_slice(spl_token::native_mint::ID.as_ref());
+            lb_pair_account.data[120..152].copy_from_slice(Pubkey::new_unique().as_ref());
+            program_test.add_account(
+                lb_pair_pubkey,
+                lb_pair_account,
+            );
+        },
+    )
+    .await;
+    let intermediary_pubkey = environment.intermediary.pubkey();
+    let intermediary_pubkeys = environment.intermediary_pubkeys();
+    let trader = environment.trader.insecure_clone();
+    environment.initialize_config().await;
+    environment.initialize_intermediary(LAMPORTS_PER_SOL).await;
+    environment.set_risk_limits(LAMPORTS_PER_SOL, 10_000, 10, 100).await.unwrap();
+    environment.add_allowed_mint(&any_mint_token_mint_pubkey).await.unwrap();
+    environment.add_trader_seat(&trader.pubkey(), LAMPORTS_PER_SOL).await.unwrap();
+    let (trader_seat_pubkey, _) = environment.trader_seat_pubkey(&trader.pubkey());
+    let (any_mint_token_account_pubkey, any_mint_token_account_pubkey_bump_seed) = AnyMintTokenAccountAddressResolver {
+        any_mint_token_mint_pubkey: &any_mint_token_mint_pubkey,
+        intermediary_pubkey: &intermediary_pubkey,
+    }
+    .find();
+    let (any_mint_token_account_authority_pubkey, any_mint_token_account_authority_pubkey_bump_seed) = AnyMintTokenAccountAuthorityAddressResolver {
+        any_mint_token_account_pubkey: &any_mint_token_account_pubkey,
+        intermediary_pubkey: &intermediary_pubkey,
+    }
+    .find();
+    let buy_on_meteora_dlmm_instruction = Instruction::buy_on_meteora_dlmm(
+        &PROGRAM_PUBKEY,
+        &trader.pubkey(),
+        &intermediary_pubkey,
+        &intermediary_pubkeys.w_sol_token_account_pubkey,
+        &intermediary_pubkeys.w_sol_token_account_authority_pubkey,
+        &intermediary_pubkeys.temporary_w_sol_token_account_pubkey,
+        &intermediary_pubkeys.temporary_w_sol_token_account_authority_pubkey,
+        &any_mint_token_account_pubkey,
+        &any_mint_token_account_authority_pubkey,
+        &any_mint_token_mint_pubkey,
+        &spl_token::ID,
+        &lb_pair_pubkey,
+        &METEORA_DLMM_PROGRAM_PUBKEY,
+        &Pubkey::new_unique(),
+        &Pubkey::new_unique(),
+        &Pubkey::new_unique(),
+        &Pubkey::new_unique(),
+        &intermediary_pubkeys.allowed_mints_pubkey,
+        &intermediary_pubkeys.risk_limits_pubkey,
+        &trader_seat_pubkey,
+        &environment.config_pubkey,
+        &environment.fee_vault_pubkey,
+        [Pubkey::new_unique()].as_slice(),
+        LAMPORTS_PER_SOL / 10,
+        0,
+        any_mint_token_account_pubkey_bump_seed,
+        any_mint_token_account_authority_pubkey_bump_seed,
+    )
+    .unwrap();
+    assert_error(
+        environment
+            .process(
+                [buy_on_meteora_dlmm_instruction].as_slice(),
+                [&trader].as_slice(),
+            )
+            .await,
+        Error::InvalidAccountData,
+    );
+    let (any_mint_token_account_pubkey, any_mint_token_account_pubkey_bump_seed) = AnyMintTokenAccountAddressResolver {
+        any_mint_token_mint_pubkey: &token_2022_mint_pubkey,
+        intermediary_pubkey: &intermediary_pubkey,
+    }
+    .find();
+    let (any_mint_token_account_authority_pubkey, any_mint_token_account_authority_pubkey_bump_seed) = AnyMintTokenAccountAuthorityAddressResolver {
+        any_mint_token_account_pubkey: &any_mint_token_account_pubkey,
+        intermediary_pubkey: &intermediary_pubkey,
+    }
+    .find();
+    let buy_on_meteora_dynamic_amm_instruction = Instruction::buy_on_meteora_dynamic_amm(
+        &PROGRAM_PUBKEY,
+        &trader.pubkey(),
+        &intermediary_pubkey,
+        &intermediary_pubkeys.w_sol_token_account_pubkey,
+        &intermediary_pubkeys.w_sol_token_account_authority_pubkey,
+        &intermediary_pubkeys.temporary_w_sol_token_account_pubkey,
+        &intermediary_pubkeys.temporary_w_sol_token_account_authority_pubkey,
+        &any_mint_token_account_pubkey,
+        &any_mint_token_account_authority_pubkey,
+        &token_2022_mint_pubkey,
+        &Pubkey::new_unique(),
+        &Pubkey::new_unique(),
+        &Pubkey::new_unique(),
+        &Pubkey::new_unique(),
+        &Pubkey::new_unique(),
+        &Pubkey::new_unique(),
+        &Pubkey::new_unique(),
+        &Pubkey::new_unique(),
+        &Pubkey::new_unique(),
+        &Pubkey::new_unique(),
+        &intermediary_pubkeys.allowed_mints_pubkey,
+        &intermediary_pubkeys.risk_limits_pubkey,
+        &trader_seat_pubkey,
+        &environment.config_pubkey,
+        &environment.fee_vault_pubkey,
+        LAMPORTS_PER_SOL / 10,
+        0,
+        any_mint_token_account_pubkey_bump_seed,
+        any_mint_token_account_authority_pubkey_bump_seed,
+    )
+    .unwrap();
+    assert_error(
+        environment
+            .process(
+                [buy_on_meteora_dynamic_amm_instruction].as_slice(),
+                [&trader].as_slice(),
+            )
+            .await,
+        Error::InvalidAccountOwner,
+    );
+}
+#[tokio::test]
+async fn pumpfun_curve_rejects_token_2022_mints() {
+    let token_2022_mint_pubkey = Pubkey::new_unique();
+    let mut environment = Environment::start(
+        |program_test, _| {
+            program_test.add_account(
+                token_2022_mint_pubkey,
+                create_token_2022_mint_account_with_transfer_fee(6, 100),
+            );
+        },
+    )
+    .await;
+    let intermediary_pubkey = environment.intermediary.pubkey();
+    let intermediary_pubkeys = environment.intermediary_pubkeys();
+    let trader = environment.trader.insecure_clone();
+    environment.initialize_config().await;
+    environment.initialize_intermediary(LAMPORTS_PER_SOL).await;
+    let (trader_seat_pubkey, _) = environment.trader_seat_pubkey(&trader.pubkey());
+    let (any_mint_token_account_pubkey, any_mint_token_account_pubkey_bump_seed) = AnyMintTokenAccountAddressResolver {
+        any_mint_token_mint_pubkey: &token_2022_mint_pubkey,
+        intermediary_pubkey: &intermediary_pubkey,
+    }
+    .find();
+    let (any_mint_token_account_authority_pubkey, any_mint_token_account_authority_pubkey_bump_seed) = AnyMintTokenAccountAuthorityAddressResolver {
+        any_mint_token_account_pubkey: &any_mint_token_account_pubkey,
+        intermediary_pubkey: &intermediary_pubkey,
+    }
+    .find();
+    let buy_on_pumpfun_curve_instruction = Instruction::buy_on_pumpfun_curve(
+        &PROGRAM_PUBKEY,
+        &trader.pubkey(),
+        &intermediary_pubkey,
+        &intermediary_pubkeys.w_sol_token_account_pubkey,
+        &intermediary_pubkeys.w_sol_token_account_authority_pubkey,
+        &intermediary_pubkeys.temporary_w_sol_token_account_pubkey,
+        &intermediary_pubkeys.temporary_w_sol_token_account_authority_pubkey,
+        &any_mint_token_account_pubkey,
+        &any_mint_token_account_authority_pubkey,
+        &token_2022_mint_pubkey,
+        &Pubkey::new_unique(),
+        &Pubkey::new_unique(),
+        &Pubkey::new_unique(),
+        &Pubkey::new_unique(),
+        &Pubkey::new_unique(),
+        &intermediary_pubkeys.allowed_mints_pubkey,
+        &intermediary_pubkeys.risk_limits_pubkey,
+        &trader_seat_pubkey,
+        &environment.config_pubkey,
+        &environment.fee_vault_pubkey,
+        1_000_000,
+        LAMPORTS_PER_SOL / 10,
+        any_mint_token_account_pubkey_bump_seed,        any_mint_token_account_authority_pubkey_bump_seed,
+    )
+    .unwrap();
+    assert_error(
+        environment
+            .process(
+                [buy_on_pumpfun_curve_instruction].as_slice(),
+                [&trader].as_slice(),
+            )
+            .await,
+        Error::InvalidAccountOwner,
+    );
+}
+#[tokio::test]
+async fn swap_validates_venue() {
+    let any_mint_token_mint_pubkey = Pubkey::new_unique();
+    let mut environment = Environment::start(
+        |program_test, _| {
+            program_test.add_account(
+                any_mint_token_mint_pubkey,
+                create_mint_account(6),
+            );
+        },
+    )
+    .await;
+    let intermediary_pubkey = environment.intermediary.pubkey();
+    let intermediary_pubkeys = environment.intermediary_pubkeys();
+    let trader = environment.trader.insecure_clone();
+    environment.initialize_config().await;
+    environment.initialize_intermediary(LAMPORTS_PER_SOL).await;
+    let (trader_seat_pubkey, _) = environment.trader_seat_pubkey(&trader.pubkey());
+    let (any_mint_token_account_pubkey, any_mint_token_account_pubkey_bump_seed) = AnyMintTokenAccountAddressResolver {
+        any_mint_token_mint_pubkey: &any_mint_token_mint_pubkey,
+        intermediary_pubkey: &intermediary_pubkey,
+    }
+    .find();
+    let (any_mint_token_account_authority_pubkey, any_mint_token_account_authority_pubkey_bump_seed) = AnyMintTokenAccountAuthorityAddressResolver {
+        any_mint_token_account_pubkey: &any_mint_token_account_pubkey,
+        intermediary_pubkey: &intermediary_pubkey,
+    }
+    .find();
+    let create_swap_instruction = |venue: Venue, venue_account_pubkeys: &[Pubkey]| {
+        Instruction::swap(
+            &PROGRAM_PUBKEY,
+            &trader.pubkey(),
+            &intermediary_pubkey,
+            &intermediary_pubkeys.w_sol_token_account_pubkey,
+            &intermediary_pubkeys.w_sol_token_account_authority_pubkey,
+            &intermediary_pubkeys.temporary_w_sol_token_account_pubkey,
+            &intermediary_pubkeys.temporary_w_sol_token_account_authority_pubkey,
+            &any_mint_token_account_pubkey,
+            &any_mint_token_account_authority_pubkey,
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &any_mint_token_mint_pubkey,
+            &spl_token::ID,
+            &intermediary_pubkeys.allowed_mints_pubkey,
+            &intermediary_pubkeys.risk_limits_pubkey,
+            &trader_seat_pubkey,
+            &environment.config_pubkey,
+            &environment.fee_vault_pubkey,
+            venue,
+            venue_account_pubkeys,
+            Side::Buy,
+            LAMPORTS_PER_SOL / 10,
+            0,
+            any_mint_token_account_pubkey_bump_seed,
+            any_mint_token_account_authority_pubkey_bump_seed,
+            0,
+            0,
+        )
+        .unwrap()
+    };
+    // Venue accounts led by a program that is not the venue one.
+    let swap_instructions = [
+        (Venue::Raydium, 14),
+        (Venue::RaydiumCpmm, 7),
+        (Venue::MeteoraDynamicAmm, 12),
+        (Venue::PumpfunCurve, 7),
+    ]
+    .into_iter()
+    .map(
+        |(venue, venue_accounts_quantity)| {
+            create_swap_instruction(
+                venue,
+                (0..venue_accounts_quantity).map(|_| Pubkey::new_unique()).collect::<Vec<Pubkey>>().as_slice(),
+            )
+        },
+    )
+    .collect::<Vec<_>>();
+    for swap_instruction in swap_instructions {
+        assert_error(
+            environment
+                .process(
+                    [swap_instruction].as_slice(),
+                    [&trader].as_slice(),
+                )
+                .await,
+            Error::InvalidAccountPubkey,
+        );
+    }
+}
+#[tokio::test]
+async fn routed_swap_validates_route() {
+    let any_mint_token_mint_pubkey = Pubkey::new_unique();
+    let mut environment = Environment::start(
+        |program_test, _| {
+            program_test.add_account(
+                any_mint_token_mint_pubkey,
+                create_mint_account(6),
+            );
+        },
+    )
+    .await;
+    let intermediary_pubkey = environment.intermediary.pubkey();
+    let intermediary_pubkeys = environment.intermediary_pubkeys();
+    let trader = environment.trader.insecure_clone();
+    environment.initialize_config().await;
+    environment.initialize_intermediary(LAMPORTS_PER_SOL).await;
+    let (trader_seat_pubkey, _) = environment.trader_seat_pubkey(&trader.pubkey());
+    let (any_mint_token_account_pubkey, any_mint_token_account_pubkey_bump_seed) = AnyMintTokenAccountAddressResolver {
+        any_mint_token_mint_pubkey: &any_mint_token_mint_pubkey,
+        intermediary_pubkey: &intermediary_pubkey,
+    }
+    .find();
+    let (any_mint_token_account_authority_pubkey, any_mint_token_account_authority_pubkey_bump_seed) = AnyMintTokenAccountAuthorityAddressResolver {
+        any_mint_token_account_pubkey: &any_mint_token_account_pubkey,
+        intermediary_pubkey: &intermediary_pubkey,
+    }
+    .find();
+    // An intermediate mint that does not exist, so it is not owned by the token program.
+    let intermediate_token_mint_pubkey = Pubkey::new_unique();
+    let (intermediate_token_account_pubkey, intermediate_token_account_pubkey_bump_seed) = IntermediateTokenAccountAddressResolver {
+        intermediate_token_mint_pubkey: &intermediate_token_mint_pubkey,
+        any_mint_token_account_pubkey: &any_mint_token_account_pubkey,
+        intermediary_pubkey: &intermediary_pubkey,
+    }
+    .find();
+    // One filler pubkey stands for the venue accounts so the route fits the static accounts of a transaction.
+    let venue_filler_pubkey = Pubkey::new_unique();
+    let create_route_leg = |venue: Venue| {
+        let mut venue_account_pubkeys = match venue {
+            Venue::Raydium => vec![venue_filler_pubkey; 14],
+            Venue::PumpfunCurve => vec![venue_filler_pubkey; 7],
+            _ => vec![venue_filler_pubkey; 10],
+        };
+        venue_account_pubkeys[0] = match venue {
+            Venue::Raydium => RAYDIUM_LIQUIDITY_POOL_V4_PROGRAM_PUBKEY,
+            Venue::PumpfunCurve => PUMPFUN_PROGRAM_PUBKEY,
+            _ => PUMPSWAP_PROGRAM_PUBKEY,
+        };
+        RouteLeg {
+            venue,
+            venue_account_pubkeys,
+        }
+    };
+    let create_routed_swap_instruction = |route_hops: &[RouteHop], route_legs: &[RouteLeg]| {
+        Instruction::routed_swap(
+            &PROGRAM_PUBKEY,
+            &trader.pubkey(),
+            &intermediary_pubkey,
+            &intermediary_pubkeys.w_sol_token_account_pubkey,
+            &intermediary_pubkeys.w_sol_token_account_authority_pubkey,
+            &intermediary_pubkeys.temporary_w_sol_token_account_pubkey,
+            &any_mint_token_account_pubkey,
+            &any_mint_token_account_authority_pubkey,
+            &any_mint_token_mint_pubkey,
+            &spl_token::ID,
+            &intermediary_pubkeys.allowed_mints_pubkey,
+            &intermediary_pubkeys.risk_limits_pubkey,
+            &trader_seat_pubkey,
+            &environment.config_pubkey,
+            &environment.fee_vault_pubkey,
+            route_hops,
+            route_legs,
+            Side::Buy,
+            LAMPORTS_PER_SOL / 10,
+            0,
+            any_mint_token_account_pubkey_bump_seed,
+            any_mint_token_account_authority_pubkey_bump_seed,
+        )
+        .unwrap()
+    };
+    let route_hops = [
+        RouteHop {
+            intermediate_token_mint_pubkey,
+            intermediate_token_account_pubkey,
+            intermediate_token_account_pubkey_bump_seed,
+        },
+    ];
+    let single_leg_routed_swap_instruction = create_routed_swap_instruction(
+        [].as_slice(),
+        [create_route_leg(Venue::Raydium)].as_slice(),
+    );
+    let exact_output_last_leg_routed_swap_instruction = create_routed_swap_instruction(
+        route_hops.as_slice(),
+        [
+            create_route_leg(Venue::Raydium),
+            create_route_leg(Venue::Pumpswap),
+        ]
+        .as_slice(),
+    );
+    let lamports_settled_leg_routed_swap_instruction = create_routed_swap_instruction(
+        route_hops.as_slice(),
+        [
+            create_route_leg(Venue::Raydium),
+            create_route_leg(Venue::PumpfunCurve),
+        ]
+        .as_slice(),
+    );
+    let foreign_intermediate_mint_routed_swap_instruction = create_routed_swap_instruction(
+        route_hops.as_slice(),
+        [
+            create_route_leg(Venue::Raydium),
+            create_route_leg(Venue::Raydium),
+        ]
+        .as_slice(),
+    );
+    assert_error(
+        environment
+            .process(
+                [single_leg_routed_swap_instruction].as_slice(),
+                [&trader].as_slice(),
+            )
+            .await,
+        Error::RouteInvalidLegsQuantity,
+    );
+    assert_error(
+        environment
+            .process(
+                [exact_output_last_leg_routed_swap_instruction].as_slice(),
+                [&trader].as_slice(),
+            )
+            .await,
+        Error::VenueIsNotSupported,
+    );
+    assert_error(
+        environment
+            .process(
+                [lamports_settled_leg_routed_swap_instruction].as_slice(),
+                [&trader].as_slice(),
+            )
+            .await,
+        Error::VenueIsNotSupported,
+    );
+    assert_error(
+        environment
+            .process(
+                [foreign_intermediate_mint_routed_swap_instruction].as_slice(),
+                [&trader].as_slice(),
+            )
+            .await,
+        Error::InvalidAccountOwner,
+    );
+}
+#[tokio::test]
+async fn routed_swap_token_accounts_are_owned_by_any_mint_token_account_authority() {
+    let intermediate_token_mint_pubkey = Pubkey::new_unique();
+    let any_mint_token_mint_pubkey = Pubkey::new_unique();
+    let mut mock_raydium_pools = Vec::<MockRaydiumPool>::new();
+    let mut environment = Environment::start(
+        |program_test, _| {
+            add_mock_raydium_program(program_test);
+            for token_mint_pubkey in [
+                intermediate_token_mint_pubkey,
+                any_mint_token_mint_pubkey,
+            ] {
+                program_test.add_account(
+                    token_mint_pubkey,
+                    create_mint_account(6),
+                );
+            }
+            mock_raydium_pools.push(
+                MockRaydiumPool::add(
+                    program_test,
+                    &intermediate_token_mint_pubkey,
+                    &spl_token::native_mint::ID,
+                    1_000_000_000_000,
+                    100 * LAMPORTS_PER_SOL,
+                ),
+            );
+            mock_raydium_pools.push(
+                MockRaydiumPool::add(
+                    program_test,
+                    &any_mint_token_mint_pubkey,
+                    &intermediate_token_mint_pubkey,
+                    1_000_000_000_000,
+                    1_000_000_000_000,
+                ),
+            );
+        },
+    )
+    .await;
+    let intermediary_pubkey = environment.intermediary.pubkey();
+    let intermediary_pubkeys = environment.intermediary_pubkeys();
+    let trader = environment.trader.insecure_clone();
+    environment.initialize_config().await;
+    environment.initialize_intermediary(LAMPORTS_PER_SOL).await;
+    environment.set_risk_limits(LAMPORTS_PER_SOL, 10_000, 10, 100).await.unwrap();
+    environment.add_allowed_mint(&intermediate_token_mint_pubkey).await.unwrap();
+    environment.add_allowed_mint(&any_mint_token_mint_pubkey).await.unwrap();
+    environment.add_trader_seat(&trader.pubkey(), LAMPORTS_PER_SOL).await.unwrap();
+    let (trader_seat_pubkey, _) = environment.trader_seat_pubkey(&trader.pubkey());
+    let (any_mint_token_account_pubkey, any_mint_token_account_pubkey_bump_seed) = AnyMintTokenAccountAddressResolver {
+        any_mint_token_mint_pubkey: &any_mint_token_mint_pubkey,
+        intermediary_pubkey: &intermediary_pubkey,
+    }
+    .find();
+    let (any_mint_token_account_authority_pubkey, any_mint_token_account_authority_pubkey_bump_seed) = AnyMintTokenAccountAuthorityAddressResolver {
+        any_mint_token_account_pubkey: &any_mint_token_account_pubkey,
+        intermediary_pubkey: &intermediary_pubkey,
+    }
+    .find();
+    let (intermediate_token_account_pubkey, intermediate_token_account_pubkey_bump_seed) = IntermediateTokenAccountAddressResolver {
+        intermediate_token_mint_pubkey: &intermediate_token_mint_pubkey,
+        any_mint_token_account_pubkey: &any_mint_token_account_pubkey,
+        intermediary_pubkey: &intermediary_pubkey,
+    }
+    .find();
+    let route_hops = [
+        RouteHop {
+            intermediate_token_mint_pubkey,
+            intermediate_token_account_pubkey,
+            intermediate_token_account_pubkey_bump_seed,
+        },
+    ];
+    let route_legs = mock_raydium_pools
+        .iter()
+        .map(
+            |mock_raydium_pool| RouteLeg {
+                venue: Venue::Raydium,
+                venue_account_pubkeys: mock_raydium_pool.get_venue_account_pubkeys(),
+            },
+        )
+        .collect::<Vec<RouteLeg>>();
+    let routed_swap_instruction = Instruction::routed_swap(
+        &PROGRAM_PUBKEY,
+        &trader.pubkey(),
+        &intermediary_pubkey,
+        &intermediary_pubkeys.w_sol_token_account_pubkey,
+        &intermediary_pubkeys.w_sol_token_account_authority_pubkey,
+        &intermediary_pubkeys.temporary_w_sol_token_account_pubkey,
+        &any_mint_token_account_pubkey,
+        &any_mint_token_account_authority_pubkey,
+        &any_mint_token_mint_pubkey,
+        &spl_token::ID,
+        &intermediary_pubkeys.allowed_mints_pubkey,
+        &intermediary_pubkeys.risk_limits_pubkey,
+        &trader_seat_pubkey,
+        &environment.config_pubkey,
+        &environment.fee_vault_pubkey,
+        route_hops.as_slice(),
+        route_legs.as_slice(),
+        Side::Buy,
+        LAMPORTS_PER_SOL / 10,
+        1,
+        any_mint_token_account_pubkey_bump_seed,
+        any_mint_token_account_authority_pubkey_bump_seed,
+    )
+    .unwrap();
+    environment
+        .process(
+            [routed_swap_instruction.clone()].as_slice(),
+            [&trader].as_slice(),
+        )
+        .await
+        .unwrap();
+    let any_mint_token_account = environment.get_account(&any_mint_token_account_pubkey).await.unwrap();
+    let any_mint_token_account = spl_token::state::Account::unpack(any_mint_token_account.data.as_slice()).unwrap();
+    assert_eq!(any_mint_token_account.owner, any_mint_token_account_authority_pubkey);
+    assert!(any_mint_token_account.amount > 0);
+    assert_eq!(environment.get_intermediary().await.open_positions_quantity, 1);
+    // The position was not handed over, so closing would orphan it.
+    let investor = environment.investor.insecure_clone();
+    assert_error(
+        environment
+            .process(
+                [
+                    Instruction::close_intermediary(
+                        &PROGRAM_PUBKEY,
+                        &investor.pubkey(),
+                        &intermediary_pubkey,
+                        &intermediary_pubkeys.w_sol_token_account_pubkey,
+                        &intermediary_pubkeys.w_sol_token_account_authority_pubkey,
+                        &intermediary_pubkeys.risk_limits_pubkey,
+                        &intermediary_pubkeys.allowed_mints_pubkey,
+                        [].as_slice(),
+                        [trader_seat_pubkey].as_slice(),
+                    )
+                    .unwrap(),
+                ]
+                .as_slice(),
+                [&investor].as_slice(),
+            )
+            .await,
+        Error::IntermediaryHasOpenPositions,
+    );
+    // The whole intermediate amount is swapped by the second leg, so its token account is closed.
+    assert!(environment.get_account(&intermediate_token_account_pubkey).await.is_none());
+    // An intermediate token account that already holds a balance is owned by the authority, which spends it on the route.
+    environment.context.set_account(
+        &intermediate_token_account_pubkey,
+        &create_token_account(
+            &intermediate_token_mint_pubkey,
+            &any_mint_token_account_authority_pubkey,
+            1_000,
+        )
+        .into(),
+    );
+    environment.context.get_new_latest_blockhash().await.unwrap();
+    environment
+        .process(
+            [routed_swap_instruction.clone()].as_slice(),
+            [&trader].as_slice(),
+        )
+        .await
+        .unwrap();
+    assert!(environment.get_account(&intermediate_token_account_pubkey).await.is_none());
+    assert_eq!(environment.get_intermediary().await.open_positions_quantity, 1);
+    environment.context.set_account(
+        &intermediate_token_account_pubkey,
+        &create_token_account(
+            &intermediate_token_mint_pubkey,
+            &trader.pubkey(),
+            1_000,
+        )
+        .into(),
+    );
+    environment.context.get_new_latest_blockhash().await.unwrap();
+    assert_error(
+        environment
+            .process(
+                [routed_swap_instruction].as_slice(),
+                [&trader].as_slice(),
+            )
+            .await,
+        Error::TokenAccountInvalidAuthority,
+    );
+}
+#[tokio::test]
+async fn routed_swap_buys_and_sells_through_two_legs() {
+    let intermediate_token_mint_pubkey = Pubkey::new_unique();
+    let any_mint_token_mint_pubkey = Pubkey::new_unique();
+    let mut mock_raydium_pools = Vec::<MockRaydiumPool>::new();
+    let mut environment = Environment::start(
+        |program_test, _| {
+            add_mock_raydium_program(program_test);
+            for token_mint_pubkey in [
+                intermediate_token_mint_pubkey,
+                any_mint_token_mint_pubkey,
+            ] {
+                program_test.add_account(
+                    token_mint_pubkey,
+                    create_mint_account(6),
+                );
+            }
+            mock_raydium_pools.push(
+                MockRaydiumPool::add(
+                    program_test,
+                    &intermediate_token_mint_pubkey,
+                    &spl_token::native_mint::ID,
+                    1_000_000_000_000,
+                    100 * LAMPORTS_PER_SOL,
+                ),
+            );
+            mock_raydium_pools.push(
+                MockRaydiumPool::add(
+                    program_test,
+                    &any_mint_token_mint_pubkey,
+                    &intermediate_token_mint_pubkey,
+                    1_000_000_000_000,
+                    1_000_000_000_000,
+                ),
+            );
+        },
+    )
+    .await;
+    let intermediary_pubkey = environment.intermediary.pubkey();
+    let intermediary_pubkeys = environment.intermediary_pubkeys();
+    let trader = environment.trader.insecure_clone();
+    environment.initialize_config().await;
+    environment.initialize_intermediary(LAMPORTS_PER_SOL).await;
+    environment.set_risk_limits(LAMPORTS_PER_SOL, 10_000, 10, 100).await.unwrap();
+    environment.add_allowed_mint(&intermediate_token_mint_pubkey).await.unwrap();
+    environment.add_allowed_mint(&any_mint_token_mint_pubkey).await.unwrap();
+    environment.add_trader_seat(&trader.pubkey(), LAMPORTS_PER_SOL).await.unwrap();
+    let (trader_seat_pubkey, _) = environment.trader_seat_pubkey(&trader.pubkey());
+    let (any_mint_token_account_pubkey, any_mint_token_account_pubkey_bump_seed) = AnyMintTokenAccountAddressResolver {
+        any_mint_token_mint_pubkey: &any_mint_token_mint_pubkey,
+        intermediary_pubkey: &intermediary_pubkey,
+    }
+    .find();
+    let (any_mint_token_account_authority_pubkey, any_mint_token_account_authority_pubkey_bump_seed) = AnyMintTokenAccountAuthorityAddressResolver {
+        any_mint_token_account_pubkey: &any_mint_token_account_pubkey,
+        intermediary_pubkey: &intermediary_pubkey,
+    }
+    .find();
+    let (intermediate_token_account_pubkey, intermediate_token_account_pubkey_bump_seed) = IntermediateTokenAccountAddressResolver {
+        intermediate_token_mint_pubkey: &intermediate_token_mint_pubkey,
+        any_mint_token_account_pubkey: &any_mint_token_account_pubkey,
+        intermediary_pubkey: &intermediary_pubkey,
+    }
+    .find();
+    let route_hops = [
+        RouteHop {
+            intermediate_token_mint_pubkey,
+            intermediate_token_account_pubkey,
+            intermediate_token_account_pubkey_bump_seed,
+        },
+    ];
+    let mut route_legs = mock_raydium_pools
+        .iter()
+        .map(
+            |mock_raydium_pool| RouteLeg {
+                venue: Venue::Raydium,
+                venue_account_pubkeys: mock_raydium_pool.get_venue_account_pubkeys(),
+            },
+        )
+        .collect::<Vec<RouteLeg>>();
+    let w_sol_amount_before_buy = environment.get_token_account_amount(&intermediary_pubkeys.w_sol_token_account_pubkey).await;
+    environment
+        .process(
+            [
+                Instruction::routed_swap(
+                    &PROGRAM_PUBKEY,
+                    &trader.pubkey(),
+                    &intermediary_pubkey,
+                    &intermediary_pubkeys.w_sol_token_account_pubkey,
+                    &intermediary_pubkeys.w_sol_token_account_authority_pubkey,
+                    &intermediary_pubkeys.temporary_w_sol_token_account_pubkey,
+                    &any_mint_token_account_pubkey,
+                    &any_mint_token_account_authority_pubkey,
+                    &any_mint_token_mint_pubkey,
+                    &spl_token::ID,
+                    &intermediary_pubkeys.allowed_mints_pubkey,
+                    &intermediary_pubkeys.risk_limits_pubkey,
+                    &trader_seat_pubkey,
+                    &environment.config_pubkey,
+                    &environment.fee_vault_pubkey,
+                    route_hops.as_slice(),
+                    route_legs.as_slice(),
+                    Side::Buy,
+                    LAMPORTS_PER_SOL / 10,
+                    1,
+                    any_mint_token_account_pubkey_bump_seed,
+                    any_mint_token_account_authority_pubkey_bump_seed,
+                )
+                .unwrap(),
+            ]
+            .as_slice(),
+            [&trader].as_slice(),
+        )
+        .await
+        .unwrap();
+    let w_sol_amount_after_buy = environment.get_token_account_amount(&intermediary_pubkeys.w_sol_token_account_pubkey).await;
+    assert!(w_sol_amount_after_buy < w_sol_amount_before_buy);
+    let any_mint_token_amount = environment.get_token_account_amount(&any_mint_token_account_pubkey).await;
+    assert!(any_mint_token_amount > 0);
+    assert!(environment.get_account(&intermediate_token_account_pubkey).await.is_none());
+    assert_eq!(environment.get_intermediary().await.open_positions_quantity, 1);
+    // The sell route walks the same pools backwards and closes the position.
+    route_legs.reverse();
+    environment.context.get_new_latest_blockhash().await.unwrap();
+    environment
+        .process(
+            [
+                Instruction::routed_swap(
+                    &PROGRAM_PUBKEY,
+                    &trader.pubkey(),
+                    &intermediary_pubkey,
+                    &intermediary_pubkeys.w_sol_token_account_pubkey,
+                    &intermediary_pubkeys.w_sol_token_account_authority_pubkey,
+                    &intermediary_pubkeys.temporary_w_sol_token_account_pubkey,
+                    &any_mint_token_account_pubkey,
+                    &any_mint_token_account_authority_pubkey,
+                    &any_mint_token_mint_pubkey,
+                    &spl_token::ID,
+                    &intermediary_pubkeys.allowed_mints_pubkey,
+                    &intermediary_pubkeys.risk_limits_pubkey,
+                    &trader_seat_pubkey,
+                    &environment.config_pubkey,
+                    &environment.fee_vault_pubkey,
+                    route_hops.as_slice(),
+                    route_legs.as_slice(),
+                    Side::Sell,
+                    any_mint_token_amount,
+                    1,
+                    any_mint_token_account_pubkey_bump_seed,
+                    any_mint_token_account_authority_pubkey_bump_seed,
+                )
+                .unwrap(),
+            ]
+            .as_slice(),
+            [&trader].as_slice(),
+        )
+        .await
+        .unwrap();
+    assert!(environment.get_token_account_amount(&intermediary_pubkeys.w_sol_token_account_pubkey).await > w_sol_amount_after_buy);
+    assert!(environment.get_account(&any_mint_token_account_pubkey).await.is_none());
+    assert!(environment.get_account(&intermediate_token_account_pubkey).await.is_none());
+    assert_eq!(environment.get_intermediary().await.open_positions_quantity, 0);
+}
+#[tokio::test]
+async fn maximum_price_deviation_is_set_by_manager() {
+    let mut environment = Environment::start(|_, _| {}).await;
+    let intermediary_pubkey = environment.intermediary.pubkey();
+    let investor = environment.investor.insecure_clone();
+    let manager = environment.manager.insecure_clone();
+    environment.initialize_config().await;
+    environment.initialize_intermediary(LAMPORTS_PER_SOL).await;
+    assert_eq!(environment.get_intermediary().await.maximum_price_deviation_basis_points, 0);
+    let investor_set_maximum_price_deviation_instruction = Instruction::set_maximum_price_deviation(
+        &PROGRAM_PUBKEY,
+        &investor.pubkey(),
+        &intermediary_pubkey,
+        300,
+    )
+    .unwrap();
+    assert_error(
+        environment
+            .process(
+                [investor_set_maximum_price_deviation_instruction].as_slice(),
+                [&investor].as_slice(),
+            )
+            .await,
+        Error::IntermediaryInvalidManager,
+    );
+    let excessive_set_maximum_price_deviation_instruction = Instruction::set_maximum_price_deviation(
+        &PROGRAM_PUBKEY,
+        &manager.pubkey(),
+        &intermediary_pubkey,
+        10_001,
+    )
+    .unwrap();
+    assert_error(
+        environment
+            .process(
+                [excessive_set_maximum_price_deviation_instruction].as_slice(),
+                [&manager].as_slice(),
+            )
+            .await,
+        Error::IntermediaryInvalidMaximumPriceDeviation,
+    );
+    let set_maximum_price_deviation_instruction = Instruction::set_maximum_price_deviation(
+        &PROGRAM_PUBKEY,
+        &manager.pubkey(),
+        &intermediary_pubkey,
+        300,
+    )
+    .unwrap();
+    let versioned_events = environment
+        .process(
+            [set_maximum_price_deviation_instruction].as_slice(),
+            [&manager].as_slice(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        versioned_events,
+        vec![
+            VersionedEvent::V1(
+                Event::MaximumPriceDeviationSet {
+                    intermediary_pubkey,
+                    maximum_price_deviation_basis_points: 300,
+                },
+            ),
+        ],
+    );
+    assert_eq!(environment.get_intermediary().await.maximum_price_deviation_basis_points, 300);
+}
@@ -0,0 +1,2086 @@
+mod common;
+use {
+    common::{
+        add_mock_meteora_dlmm_program,
+        add_mock_meteora_dynamic_amm_program,
+        add_mock_pumpfun_program,
+        add_mock_pumpswap_program,
+        add_mock_raydium_clmm_program,
+        add_mock_raydium_cpmm_program,
+        add_mock_raydium_program,
+        assert_error,
+        create_mint_account,
+        Environment,
+        MockMeteoraDlmmPool,
+        MockMeteoraDynamicAmmPool,
+        MockPumpfunCurve,
+        MockPumpswapPool,
+        MockRaydiumClmmPool,
+        MockRaydiumCpmmPool,
+        MockRaydiumPool,
+    },
+    simo_intermediary::{
+        error::Error,
+        event::{
+            Event,
+            Side,
+            Venue,
+            VersionedEvent,
+        },
+        extern_source::get_associated_token_address,
+        instruction::{
+            AnyMintTokenAccountPubkeys,
+            Instruction,
+        },
+        AnyMintTokenAccountAddressResolver,
+        AnyMintTokenAccountAuthorityAddressResolver,
+        ProgramDerivedAddress,
+        TemporaryAnyMintTokenAccountAddressResolver,
+        TemporaryAnyMintTokenAccountAuthorityAddressResolver,
+        PROGRAM_PUBKEY,
+        PUMPSWAP_PROGRAM_PUBKEY,
+        RAYDIUM_LIQUIDITY_POOL_V4_PROGRAM_PUBKEY,
+    },
+    solana_program_test::BanksClientError,
+    solana_sdk::{
+        instruction::InstructionError,
+        native_token::LAMPORTS_PER_SOL,
+        program_pack::Pack,
+        pubkey::Pubkey,
+        rent::Rent,
+        signer::Signer,
+        transaction::TransactionError,
+    },
+    spl_token::state::Account as TokenAccount,
+};
+#[tokio::test]
+async fn buy_and_sell_on_raydium() {
+    let any_mint_token_mint_pubkey = Pubkey::new_unique();
+    let mut mock_raydium_pools = Vec::<MockRaydiumPool>::with_capacity(1);
+    let mut environment = Environment::start(
+        |program_test, _| {
+            add_mock_raydium_program(program_test);
+            program_test.add_account(
+                any_mint_token_mint_pubkey,
+                create_mint_account(6),
+            );
+            mock_raydium_pools.push(
+                MockRaydiumPool::add(
+                    program_test,
+                    &any_mint_token_mint_pubkey,
+                    &spl_token::native_mint::ID,
+                    1_000_000_000_000,
+                    100 * LAMPORTS_PER_SOL,
+                ),
+            );
+        },
+    )
+    .await;
+    let mock_raydium_pool = mock_raydium_pools.pop().unwrap();
+    let intermediary_pubkey = environment.intermediary.pubkey();
+    let intermediary_pubkeys = environment.intermediary_pubkeys();
+    let trader = environment.trader.insecure_clone();
+    let (any_mint_token_account_pubkey, any_mint_token_account_pubkey_bump_seed) = AnyMintTokenAccountAddressResolver {
+        any_mint_token_mint_pubkey: &any_mint_token_mint_pubkey,
+        intermediary_pubkey: &intermediary_pubkey,
+    }
+    .find();
+    let (any_mint_token_account_authority_pubkey, any_mint_token_account_authority_pubkey_bump_seed) = AnyMintTokenAccountAuthorityAddressResolver {
+        any_mint_token_account_pubkey: &any_mint_token_account_pubkey,
+        intermediary_pubkey: &intermediary_pubkey,
+    }
+    .find();
+    let (temporary_any_mint_token_account_pubkey, temporary_any_mint_token_account_pubkey_bump_seed) = TemporaryAnyMintTokenAccountAddressResolver {
+        any_mint_token_account_pubkey: &any_mint_token_account_pubkey,
+        intermediary_pubkey: &intermediary_pubkey,
+    }
+    .find();
+    let (temporary_any_mint_token_account_authority_pubkey, temporary_any_mint_token_account_authority_pubkey_bump_seed) =
+        TemporaryAnyMintTokenAccountAuthorityAddressResolver {
+            temporary_any_mint_token_account_pubkey: &temporary_any_mint_token_account_pubkey,
+            intermediary_pubkey: &intermediary_pubkey,
+        }
+        .find();
+    environment.initialize_config().await;
+    environment.initialize_intermediary(10 * LAMPORTS_PER_SOL).await;
+    environment.set_risk_limits(LAMPORTS_PER_SOL, 10_000, 10, 100).await.unwrap();
+    environment.add_allowed_mint(&any_mint_token_mint_pubkey).await.unwrap();
+    let trader_pubkey = environment.trader.pubkey();
+    environment.add_trader_seat(&trader_pubkey, LAMPORTS_PER_SOL).await.unwrap();
+    let (trader_seat_pubkey, _) = environment.trader_seat_pubkey(&trader_pubkey);
+    let buy_on_raydium_instruction = Instruction::buy_on_raydium(
+        &PROGRAM_PUBKEY,
+        &trader.pubkey(),
+        &intermediary_pubkey,
+        &intermediary_pubkeys.w_sol_token_account_pubkey,
+        &intermediary_pubkeys.w_sol_token_account_authority_pubkey,
+        &intermediary_pubkeys.temporary_w_sol_token_account_pubkey,
+        &intermediary_pubkeys.temporary_w_sol_token_account_authority_pubkey,
+        &any_mint_token_account_pubkey,
+        &any_mint_token_account_authority_pubkey,
+        &any_mint_token_mint_pubkey,
+        &RAYDIUM_LIQUIDITY_POOL_V4_PROGRAM_PUBKEY,
+        &mock_raydium_pool.filler_pubkey,
+        &mock_raydium_pool.amm_authority_pubkey,
+        &mock_raydium_pool.filler_pubkey,
+        &mock_raydium_pool.amm_coin_vault_pubkey,
+        &mock_raydium_pool.amm_pc_vault_pubkey,
+        &mock_raydium_pool.filler_pubkey,
+        &mock_raydium_pool.filler_pubkey,
+        &mock_raydium_pool.filler_pubkey,
+        &mock_raydium_pool.filler_pubkey,
+        &mock_raydium_pool.filler_pubkey,
+        &mock_raydium_pool.filler_pubkey,
+        &mock_raydium_pool.filler_pubkey,
+        &mock_raydium_pool.filler_pubkey,
+        &intermediary_pubkeys.allowed_mints_pubkey,
+        &intermediary_pubkeys.risk_limits_pubkey,
+        &trader_seat_pubkey,
+        &environment.config_pubkey,
+        &environment.fee_vault_pubkey,
+        LAMPORTS_PER_SOL / 10,
+        1,
+        any_mint_token_account_pubkey_bump_seed,
+        any_mint_token_account_authority_pubkey_bump_seed,
+    )
+    .unwrap();
+    let versioned_events = environment
+        .process(
+            [buy_on_raydium_instruction].as_slice(),
+            [&trader].as_slice(),
+        )
+        .await
+        .unwrap();
+    let any_mint_token_account_amount = environment.get_token_account_amount(&any_mint_token_account_pubkey).await;
+    assert!(any_mint_token_account_amount > 0);
+    // The temporary wSOL token account is funded by the trader and closed into the treasury.
+    let w_sol_token_account_amount_after_buying = 10 * LAMPORTS_PER_SOL - LAMPORTS_PER_SOL / 10 + Rent::default().minimum_balance(TokenAccount::LEN);
+    assert_eq!(environment.get_token_account_amount(&intermediary_pubkeys.w_sol_token_account_pubkey).await, w_sol_token_account_amount_after_buying);
+    assert_eq!(
+        versioned_events.last(),
+        Some(
+            &VersionedEvent::V1(
+                Event::Traded {
+                    intermediary_pubkey,
+                    venue: Venue::Raydium,
+                    side: Side::Buy,
+                    mint_pubkey: any_mint_token_mint_pubkey,
+                    amount_in: 10 * LAMPORTS_PER_SOL - w_sol_token_account_amount_after_buying,
+                    amount_out: any_mint_token_account_amount,
+                    treasury_amount: w_sol_token_account_amount_after_buying,
+                },
+            ),
+        ),
+    );
+    let sell_on_raydium_instruction = Instruction::sell_on_raydium(
+        &PROGRAM_PUBKEY,
+        &trader.pubkey(),
+        &intermediary_pubkey,
+        &intermediary_pubkeys.w_sol_token_account_pubkey,
+        &any_mint_token_account_pubkey,
+        &any_mint_token_account_authority_pubkey,
+        &temporary_any_mint_token_account_pubkey,
+        &temporary_any_mint_token_account_authority_pubkey,
+        &any_mint_token_mint_pubkey,
+        &RAYDIUM_LIQUIDITY_POOL_V4_PROGRAM_PUBKEY,
+        &mock_raydium_pool.filler_pubkey,
+        &mock_raydium_pool.amm_authority_pubkey,
+        &mock_raydium_pool.filler_pubkey,
+        &mock_raydium_pool.amm_coin_vault_pubkey,
+        &mock_raydium_pool.amm_pc_vault_pubkey,
+        &mock_raydium_pool.filler_pubkey,
+        &mock_raydium_pool.filler_pubkey,
+        &mock_raydium_pool.filler_pubkey,
+        &mock_raydium_pool.filler_pubkey,
+        &mock_raydium_pool.filler_pubkey,
+        &mock_raydium_pool.filler_pubkey,
+        &mock_raydium_pool.filler_pubkey,
+        &mock_raydium_pool.filler_pubkey,
+        &intermediary_pubkeys.risk_limits_pubkey,
+        &trader_seat_pubkey,
+        &environment.config_pubkey,
+        &environment.fee_vault_pubkey,
+        any_mint_token_account_amount,
+        None,
+        any_mint_token_account_pubkey_bump_seed,
+        any_mint_token_account_authority_pubkey_bump_seed,
+        temporary_any_mint_token_account_pubkey_bump_seed,
+        temporary_any_mint_token_account_authority_pubkey_bump_seed,
+    )
+    .unwrap();
+    let versioned_events = environment
+        .process(
+            [sell_on_raydium_instruction].as_slice(),
+            [&trader].as_slice(),
+        )
+        .await
+        .unwrap();
+    let w_sol_token_account_amount = environment.get_token_account_amount(&intermediary_pubkeys.w_sol_token_account_pubkey).await;
+    assert!(environment.get_account(&any_mint_token_account_pubkey).await.is_none());
+    // The rent of the closed position reaches the treasury too, but is not part of the proceeds.
+    let rent_exemption_balance = Rent::default().minimum_balance(TokenAccount::LEN);
+    assert_eq!(
+        versioned_events.last(),
+        Some(
+            &VersionedEvent::V1(
+                Event::Traded {
+                    intermediary_pubkey,
+                    venue: Venue::Raydium,
+                    side: Side::Sell,
+                    mint_pubkey: any_mint_token_mint_pubkey,
+                    amount_in: any_mint_token_account_amount,
+                    amount_out: w_sol_token_account_amount - w_sol_token_account_amount_after_buying - rent_exemption_balance,
+                    treasury_amount: w_sol_token_account_amount,
+                },
+            ),
+        ),
+    );
+    let trader_seat = environment.get_trader_seat(&trader_pubkey).await;
+    assert_eq!(trader_seat.spent_lamports, 10 * LAMPORTS_PER_SOL - w_sol_token_account_amount_after_buying);
+    assert_eq!(trader_seat.received_lamports, w_sol_token_account_amount - w_sol_token_account_amount_after_buying - rent_exemption_balance);
+    assert_eq!(trader_seat.trades_quantity, 2);
+    environment.withdraw_funds(w_sol_token_account_amount).await.unwrap();
+    assert_eq!(environment.get_token_account_amount(&intermediary_pubkeys.w_sol_token_account_pubkey).await, 0);
+}
+#[tokio::test]
+async fn buy_and_sell_on_pumpswap() {
+    let any_mint_token_mint_pubkey = Pubkey::new_unique();
+    let mut mock_pumpswap_pools = Vec::<MockPumpswapPool>::with_capacity(1);
+    let mut environment = Environment::start(
+        |program_test, _| {
+            add_mock_pumpswap_program(program_test);
+            program_test.add_account(
+                any_mint_token_mint_pubkey,
+                create_mint_account(6),
+            );
+            mock_pumpswap_pools.push(
+                MockPumpswapPool::add(
+                    program_test,
+                    &any_mint_token_mint_pubkey,
+                    1_000_000_000_000,
+                    100 * LAMPORTS_PER_SOL,
+                ),
+            );
+        },
+    )
+    .await;
+    let mock_pumpswap_pool = mock_pumpswap_pools.pop().unwrap();
+    let intermediary_pubkey = environment.intermediary.pubkey();
+    let intermediary_pubkeys = environment.intermediary_pubkeys();
+    let trader = environment.trader.insecure_clone();
+    let (any_mint_token_account_pubkey, any_mint_token_account_pubkey_bump_seed) = AnyMintTokenAccountAddressResolver {
+        any_mint_token_mint_pubkey: &any_mint_token_mint_pubkey,
+        intermediary_pubkey: &intermediary_pubkey,
+    }
+    .find();
+    let (any_mint_token_account_authority_pubkey, any_mint_token_account_authority_pubkey_bump_seed) = AnyMintTokenAccountAuthorityAddressResolver {
+        any_mint_token_account_pubkey: &any_mint_token_account_pubkey,
+        intermediary_pubkey: &intermediary_pubkey,
+    }
+    .find();
+    let (temporary_any_mint_token_account_pubkey, temporary_any_mint_token_account_pubkey_bump_seed) = TemporaryAnyMintTokenAccountAddressResolver {
+        any_mint_token_account_pubkey: &any_mint_token_account_pubkey,
+        intermediary_pubkey: &intermediary_pubkey,
+    }
+    .find();
+    let (temporary_any_mint_token_account_authority_pubkey, temporary_any_mint_token_account_authority_pubkey_bump_seed) = TemporaryAnyMintTokenAccountAuthorityAddressResolver {
+        temporary_any_mint_token_account_pubkey: &temporary_any_mint_token_account_pubkey,
+        intermediary_pubkey: &intermediary_pubkey,
+    }
+    .find();
+    environment.initialize_config().await;
+    environment.initialize_intermediary(10 * LAMPORTS_PER_SOL).await;
+    environment.set_risk_limits(LAMPORTS_PER_SOL, 10_000, 10, 100).await.unwrap();
+    environment.add_allowed_mint(&any_mint_token_mint_pubkey).await.unwrap();
+    let trader_pubkey = environment.trader.pubkey();
+    environment.add_trader_seat(&trader_pubkey, LAMPORTS_PER_SOL).await.unwrap();
+    let (trader_seat_pubkey, _) = environment.trader_seat_pubkey(&trader_pubkey);
+    let maximum_quote_amount_in = LAMPORTS_PER_SOL / 10;
+    let pool_base_token_account_amount = environment.get_token_account_amount(&mock_pumpswap_pool.pool_base_token_account_pubkey).await;
+    let pool_quote_token_account_amount = environment.get_token_account_amount(&mock_pumpswap_pool.pool_quote_token_account_pubkey).await;
+    let base_amount_out = ((pool_base_token_account_amount as u128 * maximum_quote_amount_in as u128)
+        / (pool_quote_token_account_amount as u128 + maximum_quote_amount_in as u128)
+        / 2) as u64;
+    let buy_on_pumpswap_instruction = Instruction::buy_on_pumpswap(
+        &PROGRAM_PUBKEY,
+        &trader.pubkey(),
+        &intermediary_pubkey,
+        &intermediary_pubkeys.w_sol_token_account_pubkey,
+        &intermediary_pubkeys.w_sol_token_account_authority_pubkey,
+        &intermediary_pubkeys.temporary_w_sol_token_account_pubkey,
+        &intermediary_pubkeys.temporary_w_sol_token_account_authority_pubkey,
+        &any_mint_token_account_pubkey,
+        &any_mint_token_account_authority_pubkey,
+        &any_mint_token_mint_pubkey,
+        &PUMPSWAP_PROGRAM_PUBKEY,
+        &mock_pumpswap_pool.pool_pubkey,
+        &mock_pumpswap_pool.filler_pubkey,
+        &mock_pumpswap_pool.pool_base_token_account_pubkey,
+        &mock_pumpswap_pool.pool_quote_token_account_pubkey,
+        &mock_pumpswap_pool.filler_pubkey,
+        &mock_pumpswap_pool.protocol_fee_recipient_token_account_pubkey,
+        &spl_token::ID,
+        &spl_token::ID,
+        &mock_pumpswap_pool.filler_pubkey,
+        &intermediary_pubkeys.allowed_mints_pubkey,
+        &intermediary_pubkeys.risk_limits_pubkey,
+        &trader_seat_pubkey,
+        &environment.config_pubkey,
+        &environment.fee_vault_pubkey,
+        base_amount_out,
+        maximum_quote_amount_in,
+        any_mint_token_account_pubkey_bump_seed,
+        any_mint_token_account_authority_pubkey_bump_seed,
+    )
+    .unwrap();
+    let versioned_events = environment
+        .process(
+            [buy_on_pumpswap_instruction].as_slice(),
+            [&trader].as_slice(),
+        )
+        .await
+        .unwrap();
+    let w_sol_token_account_amount_after_buying = environment.get_token_account_amount(&intermediary_pubkeys.w_sol_token_account_pubkey).await;
+    assert_eq!(environment.get_token_account_amount(&any_mint_token_account_pubkey).await, base_amount_out);
+    assert_eq!(environment.get_intermediary().await.open_positions_quantity, 1);
+    assert_eq!(
+        versioned_events.last(),
+        Some(
+            &VersionedEvent::V1(
+                Event::Traded {
+                    intermediary_pubkey,
+                    venue: Venue::Pumpswap,
+                    side: Side::Buy,
+                    mint_pubkey: any_mint_token_mint_pubkey,
+                    amount_in: 10 * LAMPORTS_PER_SOL - w_sol_token_account_amount_after_buying,
+                    amount_out: base_amount_out,
+                    treasury_amount: w_sol_token_account_amount_after_buying,
+                },
+            ),
+        ),
+    );
+    let sell_on_pumpswap_instruction = Instruction::sell_on_pumpswap(
+        &PROGRAM_PUBKEY,
+        &trader.pubkey(),
+        &intermediary_pubkey,
+        &intermediary_pubkeys.w_sol_token_account_pubkey,
+        &intermediary_pubkeys.temporary_w_sol_token_account_pubkey,
+        &any_mint_token_account_pubkey,
+        &any_mint_token_account_authority_pubkey,
+        &temporary_any_mint_token_account_pubkey,
+        &temporary_any_mint_token_account_authority_pubkey,
+        &any_mint_token_mint_pubkey,
+        &PUMPSWAP_PROGRAM_PUBKEY,
+        &mock_pumpswap_pool.pool_pubkey,
+        &mock_pumpswap_pool.filler_pubkey,
+        &mock_pumpswap_pool.pool_base_token_account_pubkey,
+        &mock_pumpswap_pool.pool_quote_token_account_pubkey,
+        &mock_pumpswap_pool.filler_pubkey,
+        &mock_pumpswap_pool.protocol_fee_recipient_token_account_pubkey,
+        &spl_token::ID,
+        &spl_token::ID,
+        &mock_pumpswap_pool.filler_pubkey,
+        &intermediary_pubkeys.risk_limits_pubkey,
+        &trader_seat_pubkey,
+        &environment.config_pubkey,
+        &environment.fee_vault_pubkey,
+        base_amount_out,
+        None,
+        any_mint_token_account_pubkey_bump_seed,
+        any_mint_token_account_authority_pubkey_bump_seed,
+        temporary_any_mint_token_account_pubkey_bump_seed,
+        temporary_any_mint_token_account_authority_pubkey_bump_seed,
+    )
+    .unwrap();
+    let versioned_events = environment
+        .process(
+            [sell_on_pumpswap_instruction].as_slice(),
+            [&trader].as_slice(),
+        )
+        .await
+        .unwrap();
+    let w_sol_token_account_amount_after_selling = environment.get_token_account_amount(&intermediary_pubkeys.w_sol_token_account_pubkey).await;
+    assert!(environment.get_account(&any_mint_token_account_pubkey).await.is_none());
+    assert_eq!(environment.get_intermediary().await.open_positions_quantity, 0);
+    // The rent of the closed position and temporary wSOL token account reaches the treasury too, but is not part of the proceeds.
+    let rent_exemption_balance = Rent::default().minimum_balance(TokenAccount::LEN);
+    assert_eq!(
+        versioned_events.last(),
+        Some(
+            &VersionedEvent::V1(
+                Event::Traded {
+                    intermediary_pubkey,
+                    venue: Venue::Pumpswap,
+                    side: Side::Sell,
+                    mint_pubkey: any_mint_token_mint_pubkey,
+                    amount_in: base_amount_out,
+                    amount_out: w_sol_token_account_amount_after_selling - w_sol_token_account_amount_after_buying - 2 * rent_exemption_balance,
+                    treasury_amount: w_sol_token_account_amount_after_selling,
+                },
+            ),
+        ),
+    );
+    environment.withdraw_funds(w_sol_token_account_amount_after_selling).await.unwrap();
+    assert_eq!(environment.get_token_account_amount(&intermediary_pubkeys.w_sol_token_account_pubkey).await, 0);
+}
+#[tokio::test]
+async fn withdraw_position_opened_on_pumpswap() {
+    let any_mint_token_mint_pubkey = Pubkey::new_unique();
+    let mut mock_pumpswap_pools = Vec::<MockPumpswapPool>::with_capacity(1);
+    let mut environment = Environment::start(
+        |program_test, _| {
+            add_mock_pumpswap_program(program_test);
+            program_test.add_account(
+                any_mint_token_mint_pubkey,
+                create_mint_account(6),
+            );
+            mock_pumpswap_pools.push(
+                MockPumpswapPool::add(
+                    program_test,
+                    &any_mint_token_mint_pubkey,
+                    1_000_000_000_000,
+                    100 * LAMPORTS_PER_SOL,
+                ),
+            );
+        },
+    )
+    .await;
+    let mock_pumpswap_pool = mock_pumpswap_pools.pop().unwrap();
+    let intermediary_pubkey = environment.intermediary.pubkey();
+    let investor = environment.investor.insecure_clone();
+    let (any_mint_token_account_pubkey, _) = AnyMintTokenAccountAddressResolver {
+        any_mint_token_mint_pubkey: &any_mint_token_mint_pubkey,
+        intermediary_pubkey: &intermediary_pubkey,
+    }
+    .find();
+    let (any_mint_token_account_authority_pubkey, _) = AnyMintTokenAccountAuthorityAddressResolver {
+        any_mint_token_account_pubkey: &any_mint_token_account_pubkey,
+        intermediary_pubkey: &intermediary_pubkey,
+    }
+    .find();
+    environment.initialize_config().await;
+    environment.initialize_intermediary(10 * LAMPORTS_PER_SOL).await;
+    environment.set_risk_limits(LAMPORTS_PER_SOL, 10_000, 10, 100).await.unwrap();
+    environment.add_allowed_mint(&any_mint_token_mint_pubkey).await.unwrap();
+    let trader_pubkey = environment.trader.pubkey();
+    environment.add_trader_seat(&trader_pubkey, LAMPORTS_PER_SOL).await.unwrap();
+    environment
+        .buy_on_mock_pumpswap(
+            &mock_pumpswap_pool,
+            &any_mint_token_mint_pubkey,
+            400_000_000,
+            LAMPORTS_PER_SOL / 10,
+        )
+        .await
+        .unwrap();
+    let intermediary_investor_token_account_pubkey = get_associated_token_address(
+        &investor.pubkey(),
+        &any_mint_token_mint_pubkey,
+        &spl_token::ID,
+    );
+    let withdraw_token_instruction = Instruction::withdraw_token(
+        &PROGRAM_PUBKEY,
+        &investor.pubkey(),
+        &intermediary_pubkey,
+        &any_mint_token_mint_pubkey,
+        &any_mint_token_account_pubkey,
+        &any_mint_token_account_authority_pubkey,
+        &intermediary_investor_token_account_pubkey,
+        &spl_token::ID,
+        400_000_000,
+    )
+    .unwrap();
+    environment
+        .process(
+            [withdraw_token_instruction].as_slice(),
+            [&investor].as_slice(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(environment.get_token_account_amount(&any_mint_token_account_pubkey).await, 0);
+    assert_eq!(environment.get_token_account_amount(&intermediary_investor_token_account_pubkey).await, 400_000_000);
+}
+#[tokio::test]
+async fn close_after_pumpswap_buy_requires_empty_token_positions() {
+    let any_mint_token_mint_pubkey = Pubkey::new_unique();
+    let mut mock_pumpswap_pools = Vec::<MockPumpswapPool>::with_capacity(1);
+    let mut environment = Environment::start(
+        |program_test, _| {
+            add_mock_pumpswap_program(program_test);
+            program_test.add_account(
+                any_mint_token_mint_pubkey,
+                create_mint_account(6),
+            );
+            mock_pumpswap_pools.push(
+                MockPumpswapPool::add(
+                    program_test,
+                    &any_mint_token_mint_pubkey,
+                    1_000_000_000_000,
+                    100 * LAMPORTS_PER_SOL,
+                ),
+            );
+        },
+    )
+    .await;
+    let mock_pumpswap_pool = mock_pumpswap_pools.pop().unwrap();
+    let intermediary_pubkey = environment.intermediary.pubkey();
+    let intermediary_pubkeys = environment.intermediary_pubkeys();
+    let investor = environment.investor.insecure_clone();
+    let (any_mint_token_account_pubkey, _) = AnyMintTokenAccountAddressResolver {
+        any_mint_token_mint_pubkey: &any_mint_token_mint_pubkey,
+        intermediary_pubkey: &intermediary_pubkey,
+    }
+    .find();
+    let (any_mint_token_account_authority_pubkey, _) = AnyMintTokenAccountAuthorityAddressResolver {
+        any_mint_token_account_pubkey: &any_mint_token_account_pubkey,
+        intermediary_pubkey: &intermediary_pubkey,
+    }
+    .find();
+    environment.initialize_config().await;
+    environment.initialize_intermediary(10 * LAMPORTS_PER_SOL).await;
+    environment.set_risk_limits(LAMPORTS_PER_SOL, 10_000, 10, 100).await.unwrap();
+    environment.add_allowed_mint(&any_mint_token_mint_pubkey).await.unwrap();
+    let trader_pubkey = environment.trader.pubkey();
+    environment.add_trader_seat(&trader_pubkey, LAMPORTS_PER_SOL).await.unwrap();
+    let (trader_seat_pubkey, _) = environment.trader_seat_pubkey(&trader_pubkey);
+    environment
+        .buy_on_mock_pumpswap(
+            &mock_pumpswap_pool,
+            &any_mint_token_mint_pubkey,
+            400_000_000,
+            LAMPORTS_PER_SOL / 10,
+        )
+        .await
+        .unwrap();
+    assert_eq!(environment.get_intermediary().await.open_positions_quantity, 1);
+    let any_mint_token_accounts = [
+        AnyMintTokenAccountPubkeys {
+            any_mint_token_mint_pubkey,
+            any_mint_token_account_pubkey,
+            any_mint_token_account_authority_pubkey,
+            any_mint_token_program_pubkey: spl_token::ID,
+        },
+    ];
+    let close_intermediary_instruction = |any_mint_token_accounts: &[AnyMintTokenAccountPubkeys]| {
+        Instruction::close_intermediary(
+            &PROGRAM_PUBKEY,
+            &investor.pubkey(),
+            &intermediary_pubkey,
+            &intermediary_pubkeys.w_sol_token_account_pubkey,
+            &intermediary_pubkeys.w_sol_token_account_authority_pubkey,
+            &intermediary_pubkeys.risk_limits_pubkey,
+            &intermediary_pubkeys.allowed_mints_pubkey,
+            any_mint_token_accounts,
+            [trader_seat_pubkey].as_slice(),
+        )
+        .unwrap()
+    };
+    assert_error(
+        environment
+            .process(
+                [close_intermediary_instruction([].as_slice())].as_slice(),
+                [&investor].as_slice(),
+            )
+            .await,
+        Error::IntermediaryHasOpenPositions,
+    );
+    let intermediary_investor_token_account_pubkey = get_associated_token_address(
+        &investor.pubkey(),
+        &any_mint_token_mint_pubkey,
+        &spl_token::ID,
+    );
+    environment
+        .process(
+            [
+                Instruction::withdraw_token(
+                    &PROGRAM_PUBKEY,
+                    &investor.pubkey(),
+                    &intermediary_pubkey,
+                    &any_mint_token_mint_pubkey,
+                    &any_mint_token_account_pubkey,
+                    &any_mint_token_account_authority_pubkey,
+                    &intermediary_investor_token_account_pubkey,
+                    &spl_token::ID,
+                    400_000_000,
+                )
+                .unwrap(),
+                close_intermediary_instruction(any_mint_token_accounts.as_slice()),
+            ]
+            .as_slice(),
+            [&investor].as_slice(),
+        )
+        .await
+        .unwrap();
+    assert!(environment.get_account(&intermediary_pubkey).await.is_none());
+    assert!(environment.get_account(&any_mint_token_account_pubkey).await.is_none());
+}
+#[tokio::test]
+async fn buy_on_raydium_is_held_to_maximum_price_deviation() {
+    let any_mint_token_mint_pubkey = Pubkey::new_unique();
+    let mut mock_raydium_pools = Vec::<MockRaydiumPool>::with_capacity(1);
+    let mut environment = Environment::start(
+        |program_test, _| {
+            add_mock_raydium_program(program_test);
+            program_test.add_account(
+                any_mint_token_mint_pubkey,
+                create_mint_account(6),
+            );
+            let mock_raydium_pool = MockRaydiumPool::add(
+                program_test,
+                &any_mint_token_mint_pubkey,
+                &spl_token::native_mint::ID,
+                1_000_000_000_000,
+                100 * LAMPORTS_PER_SOL,
+            );
+            mock_raydium_pool.add_fee(
+                program_test,
+                100,
+            );
+            mock_raydium_pools.push(mock_raydium_pool);
+        },
+    )
+    .await;
+    let mock_raydium_pool = mock_raydium_pools.pop().unwrap();
+    let intermediary_pubkey = environment.intermediary.pubkey();
+    let intermediary_pubkeys = environment.intermediary_pubkeys();
+    let trader = environment.trader.insecure_clone();
+    let manager = environment.manager.insecure_clone();
+    let (any_mint_token_account_pubkey, any_mint_token_account_pubkey_bump_seed) = AnyMintTokenAccountAddressResolver {
+        any_mint_token_mint_pubkey: &any_mint_token_mint_pubkey,
+        intermediary_pubkey: &intermediary_pubkey,
+    }
+    .find();
+    let (any_mint_token_account_authority_pubkey, any_mint_token_account_authority_pubkey_bump_seed) = AnyMintTokenAccountAuthorityAddressResolver {
+        any_mint_token_account_pubkey: &any_mint_token_account_pubkey,
+        intermediary_pubkey: &intermediary_pubkey,
+    }
+    .find();
+    environment.initialize_config().await;
+    environment.initialize_intermediary(10 * LAMPORTS_PER_SOL).await;
+    environment.set_risk_limits(LAMPORTS_PER_SOL, 10_000, 10, 100).await.unwrap();
+    environment.add_allowed_mint(&any_mint_token_mint_pubkey).await.unwrap();
+    let trader_pubkey = environment.trader.pubkey();
+    environment.add_trader_seat(&trader_pubkey, LAMPORTS_PER_SOL).await.unwrap();
+    let (trader_seat_pubkey, _) = environment.trader_seat_pubkey(&trader_pubkey);
+    let buy_on_raydium_instruction = Instruction::buy_on_raydium(
+        &PROGRAM_PUBKEY,
+        &trader.pubkey(),
+        &intermediary_pubkey,
+        &intermediary_pubkeys.w_sol_token_account_pubkey,
+        &intermediary_pubkeys.w_sol_token_account_authority_pubkey,
+        &intermediary_pubkeys.temporary_w_sol_token_account_pubkey,
+        &intermediary_pubkeys.temporary_w_sol_token_account_authority_pubkey,
+        &any_mint_token_account_pubkey,
+        &any_mint_token_account_authority_pubkey,
+        &any_mint_token_mint_pubkey,
+        &RAYDIUM_LIQUIDITY_POOL_V4_PROGRAM_PUBKEY,
+        &mock_raydium_pool.filler_pubkey,
+        &mock_raydium_pool.amm_authority_pubkey,
+        &mock_raydium_pool.filler_pubkey,
+        &mock_raydium_pool.amm_coin_vault_pubkey,
+        &mock_raydium_pool.amm_pc_vault_pubkey,
+        &mock_raydium_pool.filler_pubkey,
+        &mock_raydium_pool.filler_pubkey,
+        &mock_raydium_pool.filler_pubkey,
+        &mock_raydium_pool.filler_pubkey,
+        &mock_raydium_pool.filler_pubkey,
+        &mock_raydium_pool.filler_pubkey,
+        &mock_raydium_pool.filler_pubkey,
+        &mock_raydium_pool.filler_pubkey,
+        &intermediary_pubkeys.allowed_mints_pubkey,
+        &intermediary_pubkeys.risk_limits_pubkey,
+        &trader_seat_pubkey,
+        &environment.config_pubkey,
+        &environment.fee_vault_pubkey,
+        LAMPORTS_PER_SOL / 10,
+        1,
+        any_mint_token_account_pubkey_bump_seed,
+        any_mint_token_account_authority_pubkey_bump_seed,
+    )
+    .unwrap();
+    let set_maximum_price_deviation_instruction = |maximum_price_deviation_basis_points: u16| {
+        Instruction::set_maximum_price_deviation(
+            &PROGRAM_PUBKEY,
+            &manager.pubkey(),
+            &intermediary_pubkey,
+            maximum_price_deviation_basis_points,
+        )
+        .unwrap()
+    };
+    // The pool fee takes the output below the constant product output by more than the tolerance.
+    environment
+        .process(
+            [set_maximum_price_deviation_instruction(50)].as_slice(),
+            [&manager].as_slice(),
+        )
+        .await
+        .unwrap();
+    assert_error(
+        environment
+            .process(
+                [buy_on_raydium_instruction.clone()].as_slice(),
+                [&trader].as_slice(),
+            )
+            .await,
+        Error::PriceDeviationExceeded,
+    );
+    environment
+        .process(
+            [set_maximum_price_deviation_instruction(300)].as_slice(),
+            [&manager].as_slice(),
+        )
+        .await
+        .unwrap();
+    environment
+        .process(
+            [buy_on_raydium_instruction].as_slice(),
+            [&trader].as_slice(),
+        )
+        .await
+        .unwrap();
+    assert!(environment.get_token_account_amount(&any_mint_token_account_pubkey).await > 0);
+}
+#[tokio::test]
+async fn buy_on_raydium_is_held_to_risk_limits() {
+    let any_mint_token_mint_pubkey = Pubkey::new_unique();
+    let mut mock_raydium_pools = Vec::<MockRaydiumPool>::with_capacity(1);
+    let mut environment = Environment::start(
+        |program_test, _| {
+            add_mock_raydium_program(program_test);
+            program_test.add_account(
+                any_mint_token_mint_pubkey,
+                create_mint_account(6),
+            );
+            mock_raydium_pools.push(
+                MockRaydiumPool::add(
+                    program_test,
+                    &any_mint_token_mint_pubkey,
+                    &spl_token::native_mint::ID,
+                    1_000_000_000_000,
+                    100 * LAMPORTS_PER_SOL,
+                ),
+            );
+        },
+    )
+    .await;
+    let mock_raydium_pool = mock_raydium_pools.pop().unwrap();
+    let intermediary_pubkey = environment.intermediary.pubkey();
+    let intermediary_pubkeys = environment.intermediary_pubkeys();
+    let trader = environment.trader.insecure_clone();
+    let (any_mint_token_account_pubkey, any_mint_token_account_pubkey_bump_seed) = AnyMintTokenAccountAddressResolver {
+        any_mint_token_mint_pubkey: &any_mint_token_mint_pubkey,
+        intermediary_pubkey: &intermediary_pubkey,
+    }
+    .find();
+    let (any_mint_token_account_authority_pubkey, any_mint_token_account_authority_pubkey_bump_seed) = AnyMintTokenAccountAuthorityAddressResolver {
+        any_mint_token_account_pubkey: &any_mint_token_account_pubkey,
+        intermediary_pubkey: &intermediary_pubkey,
+    }
+    .find();
+    environment.initialize_config().await;
+    environment.initialize_intermediary(10 * LAMPORTS_PER_SOL).await;
+    environment.add_allowed_mint(&any_mint_token_mint_pubkey).await.unwrap();
+    let trader_pubkey = environment.trader.pubkey();
+    environment.add_trader_seat(&trader_pubkey, LAMPORTS_PER_SOL).await.unwrap();
+    let (trader_seat_pubkey, _) = environment.trader_seat_pubkey(&trader_pubkey);
+    let config_pubkey = environment.config_pubkey;
+    let fee_vault_pubkey = environment.fee_vault_pubkey;
+    let buy_on_raydium_instruction = |lamports_from_treasury: u64| {
+        Instruction::buy_on_raydium(
+            &PROGRAM_PUBKEY,
+            &trader.pubkey(),
+            &intermediary_pubkey,
+            &intermediary_pubkeys.w_sol_token_account_pubkey,
+            &intermediary_pubkeys.w_sol_token_account_authority_pubkey,
+            &intermediary_pubkeys.temporary_w_sol_token_account_pubkey,
+            &intermediary_pubkeys.temporary_w_sol_token_account_authority_pubkey,
+            &any_mint_token_account_pubkey,
+            &any_mint_token_account_authority_pubkey,
+            &any_mint_token_mint_pubkey,
+            &RAYDIUM_LIQUIDITY_POOL_V4_PROGRAM_PUBKEY,
+            &mock_raydium_pool.filler_pubkey,
+            &mock_raydium_pool.amm_authority_pubkey,
+            &mock_raydium_pool.filler_pubkey,
+            &mock_raydium_pool.amm_coin_vault_pubkey,
+            &mock_raydium_pool.amm_pc_vault_pubkey,
+            &mock_raydium_pool.filler_pubkey,
+            &mock_raydium_pool.filler_pubkey,
+            &mock_raydium_pool.filler_pubkey,
+            &mock_raydium_pool.filler_pubkey,
+            &mock_raydium_pool.filler_pubkey,
+            &mock_raydium_pool.filler_pubkey,
+            &mock_raydium_pool.filler_pubkey,
+            &mock_raydium_pool.filler_pubkey,
+            &intermediary_pubkeys.allowed_mints_pubkey,
+            &intermediary_pubkeys.risk_limits_pubkey,
+            &trader_seat_pubkey,
+            &config_pubkey,
+            &fee_vault_pubkey,
+            lamports_from_treasury,
+            1,
+            any_mint_token_account_pubkey_bump_seed,
+            any_mint_token_account_authority_pubkey_bump_seed,
+        )
+        .unwrap()
+    };
+    // Until the manager sets risk limits, the trades are not limited.
+    environment
+        .process(
+            [buy_on_raydium_instruction(LAMPORTS_PER_SOL / 10)].as_slice(),
+            [&trader].as_slice(),
+        )
+        .await
+        .unwrap();
+    assert!(environment.get_account(&intermediary_pubkeys.risk_limits_pubkey).await.is_none());
+    environment.set_risk_limits(LAMPORTS_PER_SOL / 20, 10_000, 10, 100).await.unwrap();
+    assert_error(
+        environment
+            .process(
+                [buy_on_raydium_instruction(LAMPORTS_PER_SOL / 10)].as_slice(),
+                [&trader].as_slice(),
+            )
+            .await,
+        Error::RiskLimitsMaximumLamportsPerTradeExceeded,
+    );
+    // A tenth of a SOL is about one percent of the treasury.
+    environment.set_risk_limits(LAMPORTS_PER_SOL, 50, 10, 100).await.unwrap();
+    assert_error(
+        environment
+            .process(
+                [buy_on_raydium_instruction(LAMPORTS_PER_SOL / 10)].as_slice(),
+                [&trader].as_slice(),
+            )
+            .await,
+        Error::RiskLimitsMaximumTreasurySharePerTradeExceeded,
+    );
+    environment.set_risk_limits(LAMPORTS_PER_SOL, 10_000, 1, 100).await.unwrap();
+    environment
+        .process(
+            [buy_on_raydium_instruction(LAMPORTS_PER_SOL / 20)].as_slice(),
+            [&trader].as_slice(),
+        )
+        .await
+        .unwrap();
+    assert_error(
+        environment
+            .process(
+                [buy_on_raydium_instruction(LAMPORTS_PER_SOL / 30)].as_slice(),
+                [&trader].as_slice(),
+            )
+            .await,
+        Error::RiskLimitsMaximumTradesPerSlotWindowExceeded,
+    );
+}
+#[tokio::test]
+async fn buy_and_sell_on_raydium_exact_out() {
+    let any_mint_token_mint_pubkey = Pubkey::new_unique();
+    let mut mock_raydium_pools = Vec::<MockRaydiumPool>::with_capacity(1);
+    let mut environment = Environment::start(
+        |program_test, _| {
+            add_mock_raydium_program(program_test);
+            program_test.add_account(
+                any_mint_token_mint_pubkey,
+                create_mint_account(6),
+            );
+            mock_raydium_pools.push(
+                MockRaydiumPool::add(
+                    program_test,
+                    &any_mint_token_mint_pubkey,
+                    &spl_token::native_mint::ID,
+                    1_000_000_000_000,
+                    100 * LAMPORTS_PER_SOL,
+                ),
+            );
+        },
+    )
+    .await;
+    let mock_raydium_pool = mock_raydium_pools.pop().unwrap();
+    let intermediary_pubkey = environment.intermediary.pubkey();
+    let intermediary_pubkeys = environment.intermediary_pubkeys();
+    let trader = environment.trader.insecure_clone();
+    let (any_mint_token_account_pubkey, any_mint_token_account_pubkey_bump_seed) = AnyMintTokenAccountAddressResolver {
+        any_mint_token_mint_pubkey: &any_mint_token_mint_pubkey,
+        intermediary_pubkey: &intermediary_pubkey,
+    }
+    .find();
+    let (any_mint_token_account_authority_pubkey, any_mint_token_account_authority_pubkey_bump_seed) = AnyMintTokenAccountAuthorityAddressResolver {
+        any_mint_token_account_pubkey: &any_mint_token_account_pubkey,
+        intermediary_pubkey: &intermediary_pubkey,
+    }
+    .find();
+    let (temporary_any_mint_token_account_pubkey, temporary_any_mint_token_account_pubkey_bump_seed) = TemporaryAnyMintTokenAccountAddressResolver {
+        any_mint_token_account_pubkey: &any_mint_token_account_pubkey,
+        intermediary_pubkey: &intermediary_pubkey,
+    }
+    .find();
+    let (temporary_any_mint_token_account_authority_pubkey, temporary_any_mint_token_account_authority_pubkey_bump_seed) =
+        TemporaryAnyMintTokenAccountAuthorityAddressResolver {
+            temporary_any_mint_token_account_pubkey: &temporary_any_mint_token_account_pubkey,
+            intermediary_pubkey: &intermediary_pubkey,
+        }
+        .find();
+    environment.initialize_config().await;
+    environment.initialize_intermediary(10 * LAMPORTS_PER_SOL).await;
+    environment.add_allowed_mint(&any_mint_token_mint_pubkey).await.unwrap();
+    let trader_pubkey = environment.trader.pubkey();
+    environment.add_trader_seat(&trader_pubkey, LAMPORTS_PER_SOL).await.unwrap();
+    let (trader_seat_pubkey, _) = environment.trader_seat_pubkey(&trader_pubkey);
+    let config_pubkey = environment.config_pubkey;
+    let fee_vault_pubkey = environment.fee_vault_pubkey;
+    let buy_on_raydium_exact_out_instruction = |maximum_amount_in: u64, amount_out: u64| {
+        Instruction::buy_on_raydium_exact_out(
+            &PROGRAM_PUBKEY,
+            &trader.pubkey(),
+            &intermediary_pubkey,
+            &intermediary_pubkeys.w_sol_token_account_pubkey,
+            &intermediary_pubkeys.w_sol_token_account_authority_pubkey,
+            &intermediary_pubkeys.temporary_w_sol_token_account_pubkey,
+            &intermediary_pubkeys.temporary_w_sol_token_account_authority_pubkey,
+            &any_mint_token_account_pubkey,
+            &any_mint_token_account_authority_pubkey,
+            &any_mint_token_mint_pubkey,
+            &RAYDIUM_LIQUIDITY_POOL_V4_PROGRAM_PUBKEY,
+            &mock_raydium_pool.filler_pubkey,
+            &mock_raydium_pool.amm_authority_pubkey,
+            &mock_raydium_pool.filler_pubkey,
+            &mock_raydium_pool.amm_coin_vault_pubkey,
+            &mock_raydium_pool.amm_pc_vault_pubkey,
+            &mock_raydium_pool.filler_pubkey,
+            &mock_raydium_pool.filler_pubkey,
+            &mock_raydium_pool.filler_pubkey,
+            &mock_raydium_pool.filler_pubkey,
+            &mock_raydium_pool.filler_pubkey,
+            &mock_raydium_pool.filler_pubkey,
+            &mock_raydium_pool.filler_pubkey,
+            &mock_raydium_pool.filler_pubkey,
+            &intermediary_pubkeys.allowed_mints_pubkey,
+            &intermediary_pubkeys.risk_limits_pubkey,
+            &trader_seat_pubkey,
+            &config_pubkey,
+            &fee_vault_pubkey,
+            maximum_amount_in,
+            amount_out,
+            any_mint_token_account_pubkey_bump_seed,
+            any_mint_token_account_authority_pubkey_bump_seed,
+        )
+        .unwrap()
+    };
+    let bought_token_amount = 1_000_000_000;
+    let spent_lamports = ((100 * LAMPORTS_PER_SOL) as u128 * bought_token_amount as u128).div_ceil((1_000_000_000_000 - bought_token_amount) as u128) as u64;
+    // The pool refuses to take more than the maximum amount in.
+    let result = environment
+        .process(
+            [buy_on_raydium_exact_out_instruction(spent_lamports - 1, bought_token_amount)].as_slice(),
+            [&trader].as_slice(),
+        )
+        .await;
+    assert!(
+        matches!(
+            result,
+            Err(BanksClientError::TransactionError(TransactionError::InstructionError(_, InstructionError::InsufficientFunds))),
+        ),
+    );
+    let versioned_events = environment
+        .process(
+            [buy_on_raydium_exact_out_instruction(LAMPORTS_PER_SOL / 5, bought_token_amount)].as_slice(),
+            [&trader].as_slice(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(environment.get_token_account_amount(&any_mint_token_account_pubkey).await, bought_token_amount);
+    // The unspent part of the maximum amount in is closed back into the treasury together with the temporary wSOL token account rent.
+    let w_sol_token_account_amount_after_buying = 10 * LAMPORTS_PER_SOL - spent_lamports + Rent::default().minimum_balance(TokenAccount::LEN);
+    assert_eq!(environment.get_token_account_amount(&intermediary_pubkeys.w_sol_token_account_pubkey).await, w_sol_token_account_amount_after_buying);
+    assert_eq!(
+        versioned_events.last(),
+        Some(
+            &VersionedEvent::V1(
+                Event::Traded {
+                    intermediary_pubkey,
+                    venue: Venue::Raydium,
+                    side: Side::Buy,
+                    mint_pubkey: any_mint_token_mint_pubkey,
+                    amount_in: 10 * LAMPORTS_PER_SOL - w_sol_token_account_amount_after_buying,
+                    amount_out: bought_token_amount,
+                    treasury_amount: w_sol_token_account_amount_after_buying,
+                },
+            ),
+        ),
+    );
+    let received_lamports = LAMPORTS_PER_SOL / 20;
+    let sold_token_amount = ((1_000_000_000_000 - bought_token_amount) as u128 * received_lamports as u128)
+        .div_ceil((100 * LAMPORTS_PER_SOL + spent_lamports - received_lamports) as u128) as u64;
+    let sell_on_raydium_exact_out_instruction = Instruction::sell_on_raydium_exact_out(
+        &PROGRAM_PUBKEY,
+        &trader.pubkey(),
+        &intermediary_pubkey,
+        &intermediary_pubkeys.w_sol_token_account_pubkey,
+        &any_mint_token_account_pubkey,
+        &any_mint_token_account_authority_pubkey,
+        &temporary_any_mint_token_account_pubkey,
+        &temporary_any_mint_token_account_authority_pubkey,
+        &any_mint_token_mint_pubkey,
+        &RAYDIUM_LIQUIDITY_POOL_V4_PROGRAM_PUBKEY,
+        &mock_raydium_pool.filler_pubkey,
+        &mock_raydium_pool.amm_authority_pubkey,
+        &mock_raydium_pool.filler_pubkey,
+        &mock_raydium_pool.amm_coin_vault_pubkey,
+        &mock_raydium_pool.amm_pc_vault_pubkey,
+        &mock_raydium_pool.filler_pubkey,
+        &mock_raydium_pool.filler_pubkey,
+        &mock_raydium_pool.filler_pubkey,
+        &mock_raydium_pool.filler_pubkey,
+        &mock_raydium_pool.filler_pubkey,
+        &mock_raydium_pool.filler_pubkey,
+        &mock_raydium_pool.filler_pubkey,
+        &mock_raydium_pool.filler_pubkey,
+        &intermediary_pubkeys.risk_limits_pubkey,
+        &trader_seat_pubkey,
+        &config_pubkey,
+        &fee_vault_pubkey,
+        bought_token_amount,
+        received_lamports,
+        any_mint_token_account_pubkey_bump_seed,
+        any_mint_token_account_authority_pubkey_bump_seed,
+        temporary_any_mint_token_account_pubkey_bump_seed,
+        temporary_any_mint_token_account_authority_pubkey_bump_seed,
+    )
+    .unwrap();
+    let versioned_events = environment
+        .process(
+            [sell_on_raydium_exact_out_instruction].as_slice(),
+            [&trader].as_slice(),
+        )
+        .await
+        .unwrap();
+    // The tokens the pool did not take stay in the position.
+    assert_eq!(environment.get_token_account_amount(&any_mint_token_account_pubkey).await, bought_token_amount - sold_token_amount);
+    assert!(environment.get_account(&temporary_any_mint_token_account_pubkey).await.is_none());
+    assert_eq!(
+        environment.get_token_account_amount(&intermediary_pubkeys.w_sol_token_account_pubkey).await,
+        w_sol_token_account_amount_after_buying + received_lamports,
+    );
+    assert_eq!(
+        versioned_events.last(),
+        Some(
+            &VersionedEvent::V1(
+                Event::Traded {
+                    intermediary_pubkey,
+                    venue: Venue::Raydium,
+                    side: Side::Sell,
+                    mint_pubkey: any_mint_token_mint_pubkey,
+                    amount_in: sold_token_amount,
+                    amount_out: received_lamports,
+                    treasury_amount: w_sol_token_account_amount_after_buying + received_lamports,
+                },
+            ),
+        ),
+    );
+}
+#[tokio::test]
+async fn buy_and_sell_on_raydium_cpmm() {
+    let any_mint_token_mint_pubkey = Pubkey::new_unique();
+    let mut mock_raydium_cpmm_pools = Vec::<MockRaydiumCpmmPool>::with_capacity(1);
+    let mut environment = Environment::start(
+        |program_test, _| {
+            add_mock_raydium_cpmm_program(program_test);
+            program_test.add_account(
+                any_mint_token_mint_pubkey,
+                create_mint_account(6),
+            );
+            mock_raydium_cpmm_pools.push(
+                MockRaydiumCpmmPool::add(
+                    program_test,
+                    &any_mint_token_mint_pubkey,
+                    1_000_000_000_000,
+                    100 * LAMPORTS_PER_SOL,
+                ),
+            );
+        },
+    )
+    .await;
+    let mock_raydium_cpmm_pool = mock_raydium_cpmm_pools.pop().unwrap();
+    let intermediary_pubkey = environment.intermediary.pubkey();
+    let intermediary_pubkeys = environment.intermediary_pubkeys();
+    let trader = environment.trader.insecure_clone();
+    let manager = environment.manager.insecure_clone();
+    let config_pubkey = environment.config_pubkey;
+    let fee_vault_pubkey = environment.fee_vault_pubkey;
+    let (any_mint_token_account_pubkey, any_mint_token_account_pubkey_bump_seed) = AnyMintTokenAccountAddressResolver {
+        any_mint_token_mint_pubkey: &any_mint_token_mint_pubkey,
+        intermediary_pubkey: &intermediary_pubkey,
+    }
+    .find();
+    let (any_mint_token_account_authority_pubkey, any_mint_token_account_authority_pubkey_bump_seed) = AnyMintTokenAccountAuthorityAddressResolver {
+        any_mint_token_account_pubkey: &any_mint_token_account_pubkey,
+        intermediary_pubkey: &intermediary_pubkey,
+    }
+    .find();
+    let (temporary_any_mint_token_account_pubkey, temporary_any_mint_token_account_pubkey_bump_seed) = TemporaryAnyMintTokenAccountAddressResolver {
+        any_mint_token_account_pubkey: &any_mint_token_account_pubkey,
+        intermediary_pubkey: &intermediary_pubkey,
+    }
+    .find();
+    let (temporary_any_mint_token_account_authority_pubkey, temporary_any_mint_token_account_authority_pubkey_bump_seed) =
+        TemporaryAnyMintTokenAccountAuthorityAddressResolver {
+            temporary_any_mint_token_account_pubkey: &temporary_any_mint_token_account_pubkey,
+            intermediary_pubkey: &intermediary_pubkey,
+        }
+        .find();
+    environment.initialize_config().await;
+    environment.initialize_intermediary(10 * LAMPORTS_PER_SOL).await;
+    environment.set_risk_limits(LAMPORTS_PER_SOL, 10_000, 10, 100).await.unwrap();
+    environment.add_allowed_mint(&any_mint_token_mint_pubkey).await.unwrap();
+    let trader_pubkey = environment.trader.pubkey();
+    environment.add_trader_seat(&trader_pubkey, LAMPORTS_PER_SOL).await.unwrap();
+    let (trader_seat_pubkey, _) = environment.trader_seat_pubkey(&trader_pubkey);
+    // The swaps are held to the reserves read from the pool, which the fee-less mock matches.
+    environment
+        .process(
+            [
+                Instruction::set_maximum_price_deviation(
+                    &PROGRAM_PUBKEY,
+                    &manager.pubkey(),
+                    &intermediary_pubkey,
+                    10,
+                )
+                .unwrap(),
+            ]
+            .as_slice(),
+            [&manager].as_slice(),
+        )
+        .await
+        .unwrap();
+    let buy_on_raydium_cpmm_instruction = |cpmm_w_sol_vault_pubkey: &Pubkey, cpmm_any_mint_vault_pubkey: &Pubkey| {
+        Instruction::buy_on_raydium_cpmm(
+            &PROGRAM_PUBKEY,
+            &trader.pubkey(),
+            &intermediary_pubkey,
+            &intermediary_pubkeys.w_sol_token_account_pubkey,
+            &intermediary_pubkeys.w_sol_token_account_authority_pubkey,
+            &intermediary_pubkeys.temporary_w_sol_token_account_pubkey,
+            &intermediary_pubkeys.temporary_w_sol_token_account_authority_pubkey,
+            &any_mint_token_account_pubkey,
+            &any_mint_token_account_authority_pubkey,
+            &any_mint_token_mint_pubkey,
+            &spl_token::ID,
+            &mock_raydium_cpmm_pool.authority_pubkey,
+            &mock_raydium_cpmm_pool.filler_pubkey,
+            &mock_raydium_cpmm_pool.filler_pubkey,
+            cpmm_w_sol_vault_pubkey,
+            cpmm_any_mint_vault_pubkey,
+            &mock_raydium_cpmm_pool.filler_pubkey,
+            &intermediary_pubkeys.allowed_mints_pubkey,
+            &intermediary_pubkeys.risk_limits_pubkey,
+            &trader_seat_pubkey,
+            &config_pubkey,
+            &fee_vault_pubkey,
+            LAMPORTS_PER_SOL / 10,
+            1,
+            any_mint_token_account_pubkey_bump_seed,
+            any_mint_token_account_authority_pubkey_bump_seed,
+        )
+        .unwrap()
+    };
+    // The venue rejects a swap whose vaults do not hold the mints they are passed for.
+    assert!(
+        matches!(
+            environment
+                .process(
+                    [
+                        buy_on_raydium_cpmm_instruction(
+                            &mock_raydium_cpmm_pool.any_mint_vault_pubkey,
+                            &mock_raydium_cpmm_pool.w_sol_vault_pubkey,
+                        ),
+                    ]
+                    .as_slice(),
+                    [&trader].as_slice(),
+                )
+                .await,
+            Err(BanksClientError::TransactionError(TransactionError::InstructionError(_, InstructionError::InvalidArgument))),
+        ),
+    );
+    let versioned_events = environment
+        .process(
+            [
+                buy_on_raydium_cpmm_instruction(
+                    &mock_raydium_cpmm_pool.w_sol_vault_pubkey,
+                    &mock_raydium_cpmm_pool.any_mint_vault_pubkey,
+                ),
+            ]
+            .as_slice(),
+            [&trader].as_slice(),
+        )
+        .await
+        .unwrap();
+    let any_mint_token_account_amount = environment.get_token_account_amount(&any_mint_token_account_pubkey).await;
+    assert_eq!(any_mint_token_account_amount, (1_000_000_000_000_u128 * (LAMPORTS_PER_SOL / 10) as u128 / (100 * LAMPORTS_PER_SOL + LAMPORTS_PER_SOL / 10) as u128) as u64);
+    // The temporary wSOL token account is funded by the trader and closed into the treasury.
+    let w_sol_token_account_amount_after_buying = 10 * LAMPORTS_PER_SOL - LAMPORTS_PER_SOL / 10 + Rent::default().minimum_balance(TokenAccount::LEN);
+    assert_eq!(environment.get_token_account_amount(&intermediary_pubkeys.w_sol_token_account_pubkey).await, w_sol_token_account_amount_after_buying);
+    assert_eq!(
+        versioned_events.last(),
+        Some(
+            &VersionedEvent::V1(
+                Event::Traded {
+                    intermediary_pubkey,
+                    venue: Venue::RaydiumCpmm,
+                    side: Side::Buy,
+                    mint_pubkey: any_mint_token_mint_pubkey,
+                    amount_in: 10 * LAMPORTS_PER_SOL - w_sol_token_account_amount_after_buying,
+                    amount_out: any_mint_token_account_amount,
+                    treasury_amount: w_sol_token_account_amount_after_buying,
+                },
+            ),
+        ),
+    );
+    let sell_on_raydium_cpmm_instruction = Instruction::sell_on_raydium_cpmm(
+        &PROGRAM_PUBKEY,
+        &trader.pubkey(),
+        &intermediary_pubkey,
+        &intermediary_pubkeys.w_sol_token_account_pubkey,
+        &any_mint_token_account_pubkey,
+        &any_mint_token_account_authority_pubkey,
+        &temporary_any_mint_token_account_pubkey,
+        &temporary_any_mint_token_account_authority_pubkey,
+        &any_mint_token_mint_pubkey,
+        &spl_token::ID,
+        &mock_raydium_cpmm_pool.authority_pubkey,
+        &mock_raydium_cpmm_pool.filler_pubkey,
+        &mock_raydium_cpmm_pool.filler_pubkey,
+        &mock_raydium_cpmm_pool.w_sol_vault_pubkey,
+        &mock_raydium_cpmm_pool.any_mint_vault_pubkey,
+        &mock_raydium_cpmm_pool.filler_pubkey,
+        &intermediary_pubkeys.risk_limits_pubkey,
+        &trader_seat_pubkey,
+        &config_pubkey,
+        &fee_vault_pubkey,
+        any_mint_token_account_amount,
+        None,
+        any_mint_token_account_pubkey_bump_seed,
+        any_mint_token_account_authority_pubkey_bump_seed,
+        temporary_any_mint_token_account_pubkey_bump_seed,
+        temporary_any_mint_token_account_authority_pubkey_bump_seed,
+    )
+    .unwrap();
+    let versioned_events = environment
+        .process(
+            [sell_on_raydium_cpmm_instruction].as_slice(),
+            [&trader].as_slice(),
+        )
+        .await
+        .unwrap();
+    let w_sol_token_account_amount = environment.get_token_account_amount(&intermediary_pubkeys.w_sol_token_account_pubkey).await;
+    assert!(environment.get_account(&any_mint_token_account_pubkey).await.is_none());
+    let amount_out = ((100 * LAMPORTS_PER_SOL + LAMPORTS_PER_SOL / 10) as u128 * any_mint_token_account_amount as u128 / 1_000_000_000_000_u128) as u64;
+    // The rent of the closed position reaches the treasury too, but is not part of the proceeds.
+    assert_eq!(w_sol_token_account_amount, w_sol_token_account_amount_after_buying + amount_out + Rent::default().minimum_balance(TokenAccount::LEN));
+    assert_eq!(
+        versioned_events.last(),
+        Some(
+            &VersionedEvent::V1(
+                Event::Traded {
+                    intermediary_pubkey,
+                    venue: Venue::RaydiumCpmm,
+                    side: Side::Sell,
+                    mint_pubkey: any_mint_token_mint_pubkey,
+                    amount_in: any_mint_token_account_amount,
+                    amount_out,
+                    treasury_amount: w_sol_token_account_amount,
+                },
+            ),
+        ),
+    );
+    let trader_seat = environment.get_trader_seat(&trader_pubkey).await;
+    assert_eq!(trader_seat.spent_lamports, 10 * LAMPORTS_PER_SOL - w_sol_token_account_amount_after_buying);
+    assert_eq!(trader_seat.received_lamports, amount_out);
+    assert_eq!(trader_seat.trades_quantity, 2);
+}
+#[tokio::test]
+async fn buy_and_sell_on_raydium_clmm() {
+    let any_mint_token_mint_pubkey = Pubkey::new_unique();
+    let mut mock_raydium_clmm_pools = Vec::<MockRaydiumClmmPool>::with_capacity(1);
+    let mut environment = Environment::start(
+        |program_test, _| {
+            add_mock_raydium_clmm_program(program_test);
+            program_test.add_account(
+                any_mint_token_mint_pubkey,
+                create_mint_account(6),
+            );
+            mock_raydium_clmm_pools.push(
+                MockRaydiumClmmPool::add(
+                    program_test,
+                    &any_mint_token_mint_pubkey,
+                    1_000_000_000_000,
+                    100 * LAMPORTS_PER_SOL,
+                ),
+            );
+        },
+    )
+    .await;
+    let mock_raydium_clmm_pool = mock_raydium_clmm_pools.pop().unwrap();
+    let intermediary_pubkey = environment.intermediary.pubkey();
+    let intermediary_pubkeys = environment.intermediary_pubkeys();
+    let trader = environment.trader.insecure_clone();
+    let manager = environment.manager.insecure_clone();
+    let config_pubkey = environment.config_pubkey;
+    let fee_vault_pubkey = environment.fee_vault_pubkey;
+    let (any_mint_token_account_pubkey, any_mint_token_account_pubkey_bump_seed) = AnyMintTokenAccountAddressResolver {
+        any_mint_token_mint_pubkey: &any_mint_token_mint_pubkey,
+        intermediary_pubkey: &intermediary_pubkey,
+    }
+    .find();
+    let (any_mint_token_account_authority_pubkey, any_mint_token_account_authority_pubkey_bump_seed) = AnyMintTokenAccountAuthorityAddressResolver {
+        any_mint_token_account_pubkey: &any_mint_token_account_pubkey,
+        intermediary_pubkey: &intermediary_pubkey,
+    }
+    .find();
+    let (temporary_any_mint_token_account_pubkey, temporary_any_mint_token_account_pubkey_bump_seed) = TemporaryAnyMintTokenAccountAddressResolver {
+        any_mint_token_account_pubkey: &any_mint_token_account_pubkey,
+        intermediary_pubkey: &intermediary_pubkey,
+    }
+    .find();
+    let (temporary_any_mint_token_account_authority_pubkey, temporary_any_mint_token_account_authority_pubkey_bump_seed) =
+        TemporaryAnyMintTokenAccountAuthorityAddressResolver {
+            temporary_any_mint_token_account_pubkey: &temporary_any_mint_token_account_pubkey,
+            intermediary_pubkey: &intermediary_pubkey,
+        }
+        .find();
+    environment.initialize_config().await;
+    environment.initialize_intermediary(10 * LAMPORTS_PER_SOL).await;
+    environment.set_risk_limits(LAMPORTS_PER_SOL, 10_000, 10, 100).await.unwrap();
+    environment.add_allowed_mint(&any_mint_token_mint_pubkey).await.unwrap();
+    let trader_pubkey = environment.trader.pubkey();
+    environment.add_trader_seat(&trader_pubkey, LAMPORTS_PER_SOL).await.unwrap();
+    let (trader_seat_pubkey, _) = environment.trader_seat_pubkey(&trader_pubkey);
+    // The swaps are held to the reserves read from the pool, which the fee-less mock matches.
+    environment
+        .process(
+            [
+                Instruction::set_maximum_price_deviation(
+                    &PROGRAM_PUBKEY,
+                    &manager.pubkey(),
+                    &intermediary_pubkey,
+                    10,
+                )
+                .unwrap(),
+            ]
+            .as_slice(),
+            [&manager].as_slice(),
+        )
+        .await
+        .unwrap();
+    let buy_on_raydium_clmm_instruction = Instruction::buy_on_raydium_clmm(
+        &PROGRAM_PUBKEY,
+        &trader.pubkey(),
+        &intermediary_pubkey,
+        &intermediary_pubkeys.w_sol_token_account_pubkey,
+        &intermediary_pubkeys.w_sol_token_account_authority_pubkey,
+        &intermediary_pubkeys.temporary_w_sol_token_account_pubkey,
+        &intermediary_pubkeys.temporary_w_sol_token_account_authority_pubkey,
+        &any_mint_token_account_pubkey,
+        &any_mint_token_account_authority_pubkey,
+        &any_mint_token_mint_pubkey,
+        &mock_raydium_clmm_pool.filler_pubkey,
+        &mock_raydium_clmm_pool.pool_state_pubkey,
+        &mock_raydium_clmm_pool.w_sol_vault_pubkey,
+        &mock_raydium_clmm_pool.any_mint_vault_pubkey,
+        &mock_raydium_clmm_pool.filler_pubkey,
+        &intermediary_pubkeys.allowed_mints_pubkey,
+        &intermediary_pubkeys.risk_limits_pubkey,
+        &trader_seat_pubkey,
+        &config_pubkey,
+        &fee_vault_pubkey,
+        mock_raydium_clmm_pool.tick_array_pubkeys.as_slice(),
+        LAMPORTS_PER_SOL / 10,
+        1,
+        any_mint_token_account_pubkey_bump_seed,
+        any_mint_token_account_authority_pubkey_bump_seed,
+    )
+    .unwrap();
+    let versioned_events = environment
+        .process(
+            [buy_on_raydium_clmm_instruction].as_slice(),
+            [&trader].as_slice(),
+        )
+        .await
+        .unwrap();
+    let any_mint_token_account_amount = environment.get_token_account_amount(&any_mint_token_account_pubkey).await;
+    assert_eq!(any_mint_token_account_amount, (1_000_000_000_000_u128 * (LAMPORTS_PER_SOL / 10) as u128 / (100 * LAMPORTS_PER_SOL + LAMPORTS_PER_SOL / 10) as u128) as u64);
+    // The temporary wSOL token account is funded by the trader and closed into the treasury.
+    let w_sol_token_account_amount_after_buying = 10 * LAMPORTS_PER_SOL - LAMPORTS_PER_SOL / 10 + Rent::default().minimum_balance(TokenAccount::LEN);
+    assert_eq!(environment.get_token_account_amount(&intermediary_pubkeys.w_sol_token_account_pubkey).await, w_sol_token_account_amount_after_buying);
+    assert_eq!(
+        versioned_events.last(),
+        Some(
+            &VersionedEvent::V1(
+                Event::Traded {
+                    intermediary_pubkey,
+                    venue: Venue::RaydiumClmm,
+                    side: Side::Buy,
+                    mint_pubkey: any_mint_token_mint_pubkey,
+                    amount_in: 10 * LAMPORTS_PER_SOL - w_sol_token_account_amount_after_buying,
+                    amount_out: any_mint_token_account_amount,
+                    treasury_amount: w_sol_token_account_amount_after_buying,
+                },
+            ),
+        ),
+    );
+    let sell_on_raydium_clmm_instruction = Instruction::sell_on_raydium_clmm(
+        &PROGRAM_PUBKEY,
+        &trader.pubkey(),
+        &intermediary_pubkey,
+        &intermediary_pubkeys.w_sol_token_account_pubkey,
+        &any_mint_token_account_pubkey,
+        &any_mint_token_account_authority_pubkey,
+        &temporary_any_mint_token_account_pubkey,
+        &temporary_any_mint_token_account_authority_pubkey,
+        &any_mint_token_mint_pubkey,
+        &mock_raydium_clmm_pool.filler_pubkey,
+        &mock_raydium_clmm_pool.pool_state_pubkey,
+        &mock_raydium_clmm_pool.w_sol_vault_pubkey,
+        &mock_raydium_clmm_pool.any_mint_vault_pubkey,
+        &mock_raydium_clmm_pool.filler_pubkey,
+        &intermediary_pubkeys.risk_limits_pubkey,
+        &trader_seat_pubkey,
+        &config_pubkey,
+        &fee_vault_pubkey,
+        mock_raydium_clmm_pool.tick_array_pubkeys.as_slice(),
+        any_mint_token_account_amount,
+        None,
+        any_mint_token_account_pubkey_bump_seed,
+        any_mint_token_account_authority_pubkey_bump_seed,
+        temporary_any_mint_token_account_pubkey_bump_seed,
+        temporary_any_mint_token_account_authority_pubkey_bump_seed,
+    )
+    .unwrap();
+    let versioned_events = environment
+        .process(
+            [sell_on_raydium_clmm_instruction].as_slice(),
+            [&trader].as_slice(),
+        )
+        .await
+        .unwrap();
+    let w_sol_token_account_amount = environment.get_token_account_amount(&intermediary_pubkeys.w_sol_token_account_pubkey).await;
+    assert!(environment.get_account(&any_mint_token_account_pubkey).await.is_none());
+    let amount_out = ((100 * LAMPORTS_PER_SOL + LAMPORTS_PER_SOL / 10) as u128 * any_mint_token_account_amount as u128 / 1_000_000_000_000_u128) as u64;
+    // The rent of the closed position reaches the treasury too, but is not part of the proceeds.
+    assert_eq!(w_sol_token_account_amount, w_sol_token_account_amount_after_buying + amount_out + Rent::default().minimum_balance(TokenAccount::LEN));
+    assert_eq!(
+        versioned_events.last(),
+        Some(
+            &VersionedEvent::V1(
+                Event::Traded {
+                    intermediary_pubkey,
+                    venue: Venue::RaydiumClmm,
+                    side: Side::Sell,
+                    mint_pubkey: any_mint_token_mint_pubkey,
+                    amount_in: any_mint_token_account_amount,
+                    amount_out,
+                    treasury_amount: w_sol_token_account_amount,
+                },
+            ),
+        ),
+    );
+    let trader_seat = environment.get_trader_seat(&trader_pubkey).await;
+    assert_eq!(trader_seat.spent_lamports, 10 * LAMPORTS_PER_SOL - w_sol_token_account_amount_after_buying);
+    assert_eq!(trader_seat.received_lamports, amount_out);
+    assert_eq!(trader_seat.trades_quantity, 2);
+}
+#[tokio::test]
+async fn buy_and_sell_on_meteora_dlmm() {
+    let any_mint_token_mint_pubkey = Pubkey::new_unique();
+    let mut mock_meteora_dlmm_pools = Vec::<MockMeteoraDlmmPool>::with_capacity(1);
+    let mut environment = Environment::start(
+        |program_test, _| {
+            add_mock_meteora_dlmm_program(program_test);
+            program_test.add_account(
+                any_mint_token_mint_pubkey,
+                create_mint_account(6),
+            );
+            mock_meteora_dlmm_pools.push(
+                MockMeteoraDlmmPool::add(
+                    program_test,
+                    &any_mint_token_mint_pubkey,
+                    1_000_000_000_000,
+                    100 * LAMPORTS_PER_SOL,
+                    10,
+                    2_303,
+                ),
+            );
+        },
+    )
+    .await;
+    let mock_meteora_dlmm_pool = mock_meteora_dlmm_pools.pop().unwrap();
+    let intermediary_pubkey = environment.intermediary.pubkey();
+    let intermediary_pubkeys = environment.intermediary_pubkeys();
+    let trader = environment.trader.insecure_clone();
+    let manager = environment.manager.insecure_clone();
+    let config_pubkey = environment.config_pubkey;
+    let fee_vault_pubkey = environment.fee_vault_pubkey;
+    let (any_mint_token_account_pubkey, any_mint_token_account_pubkey_bump_seed) = AnyMintTokenAccountAddressResolver {
+        any_mint_token_mint_pubkey: &any_mint_token_mint_pubkey,
+        intermediary_pubkey: &intermediary_pubkey,
+    }
+    .find();
+    let (any_mint_token_account_authority_pubkey, any_mint_token_account_authority_pubkey_bump_seed) = AnyMintTokenAccountAuthorityAddressResolver {
+        any_mint_token_account_pubkey: &any_mint_token_account_pubkey,
+        intermediary_pubkey: &intermediary_pubkey,
+    }
+    .find();
+    let (temporary_any_mint_token_account_pubkey, temporary_any_mint_token_account_pubkey_bump_seed) = TemporaryAnyMintTokenAccountAddressResolver {
+        any_mint_token_account_pubkey: &any_mint_token_account_pubkey,
+        intermediary_pubkey: &intermediary_pubkey,
+    }
+    .find();
+    let (temporary_any_mint_token_account_authority_pubkey, temporary_any_mint_token_account_authority_pubkey_bump_seed) =
+        TemporaryAnyMintTokenAccountAuthorityAddressResolver {
+            temporary_any_mint_token_account_pubkey: &temporary_any_mint_token_account_pubkey,
+            intermediary_pubkey: &intermediary_pubkey,
+        }
+        .find();
+    environment.initialize_config().await;
+    environment.initialize_intermediary(10 * LAMPORTS_PER_SOL).await;
+    environment.set_risk_limits(LAMPORTS_PER_SOL, 10_000, 10, 100).await.unwrap();
+    environment.add_allowed_mint(&any_mint_token_mint_pubkey).await.unwrap();
+    let trader_pubkey = environment.trader.pubkey();
+    environment.add_trader_seat(&trader_pubkey, LAMPORTS_PER_SOL).await.unwrap();
+    let (trader_seat_pubkey, _) = environment.trader_seat_pubkey(&trader_pubkey);
+    // The swaps are held to the reserves read from the pool, which the fee-less mock matches.
+    environment
+        .process(
+            [
+                Instruction::set_maximum_price_deviation(
+                    &PROGRAM_PUBKEY,
+                    &manager.pubkey(),
+                    &intermediary_pubkey,
+                    10,
+                )
+                .unwrap(),
+            ]
+            .as_slice(),
+            [&manager].as_slice(),
+        )
+        .await
+        .unwrap();
+    let buy_on_meteora_dlmm_instruction = Instruction::buy_on_meteora_dlmm(
+        &PROGRAM_PUBKEY,
+        &trader.pubkey(),
+        &intermediary_pubkey,
+        &intermediary_pubkeys.w_sol_token_account_pubkey,
+        &intermediary_pubkeys.w_sol_token_account_authority_pubkey,
+        &intermediary_pubkeys.temporary_w_sol_token_account_pubkey,
+        &intermediary_pubkeys.temporary_w_sol_token_account_authority_pubkey,
+        &any_mint_token_account_pubkey,
+        &any_mint_token_account_authority_pubkey,
+        &any_mint_token_mint_pubkey,
+        &spl_token::ID,
+        &mock_meteora_dlmm_pool.lb_pair_pubkey,
+        &mock_meteora_dlmm_pool.filler_pubkey,
+        &mock_meteora_dlmm_pool.w_sol_reserve_pubkey,
+        &mock_meteora_dlmm_pool.any_mint_reserve_pubkey,
+        &mock_meteora_dlmm_pool.filler_pubkey,
+        &mock_meteora_dlmm_pool.filler_pubkey,
+        &intermediary_pubkeys.allowed_mints_pubkey,
+        &intermediary_pubkeys.risk_limits_pubkey,
+        &trader_seat_pubkey,
+        &config_pubkey,
+        &fee_vault_pubkey,
+        mock_meteora_dlmm_pool.bin_array_pubkeys.as_slice(),
+        LAMPORTS_PER_SOL / 10,
+        1,
+        any_mint_token_account_pubkey_bump_seed,
+        any_mint_token_account_authority_pubkey_bump_seed,
+    )
+    .unwrap();
+    let versioned_events = environment
+        .process(
+            [buy_on_meteora_dlmm_instruction].as_slice(),
+            [&trader].as_slice(),
+        )
+        .await
+        .unwrap();
+    // The active bin prices the any mint at about ten per lamport.
+    let price = (1.0 + 10.0 / 10_000.0_f64).powi(2_303);
+    let any_mint_token_account_amount = environment.get_token_account_amount(&any_mint_token_account_pubkey).await;
+    assert_eq!(any_mint_token_account_amount, ((LAMPORTS_PER_SOL / 10) as f64 * price) as u64);
+    // The temporary wSOL token account is funded by the trader and closed into the treasury.
+    let w_sol_token_account_amount_after_buying = 10 * LAMPORTS_PER_SOL - LAMPORTS_PER_SOL / 10 + Rent::default().minimum_balance(TokenAccount::LEN);
+    assert_eq!(environment.get_token_account_amount(&intermediary_pubkeys.w_sol_token_account_pubkey).await, w_sol_token_account_amount_after_buying);
+    assert_eq!(
+        versioned_events.last(),
+        Some(
+            &VersionedEvent::V1(
+                Event::Traded {
+                    intermediary_pubkey,
+                    venue: Venue::MeteoraDlmm,
+                    side: Side::Buy,
+                    mint_pubkey: any_mint_token_mint_pubkey,
+                    amount_in: 10 * LAMPORTS_PER_SOL - w_sol_token_account_amount_after_buying,
+                    amount_out: any_mint_token_account_amount,
+                    treasury_amount: w_sol_token_account_amount_after_buying,
+                },
+            ),
+        ),
+    );
+    let sell_on_meteora_dlmm_instruction = Instruction::sell_on_meteora_dlmm(
+        &PROGRAM_PUBKEY,
+        &trader.pubkey(),
+        &intermediary_pubkey,
+        &intermediary_pubkeys.w_sol_token_account_pubkey,
+        &any_mint_token_account_pubkey,
+        &any_mint_token_account_authority_pubkey,
+        &temporary_any_mint_token_account_pubkey,
+        &temporary_any_mint_token_account_authority_pubkey,
+        &any_mint_token_mint_pubkey,
+        &spl_token::ID,
+        &mock_meteora_dlmm_pool.lb_pair_pubkey,
+        &mock_meteora_dlmm_pool.filler_pubkey,
+        &mock_meteora_dlmm_pool.w_sol_reserve_pubkey,
+        &mock_meteora_dlmm_pool.any_mint_reserve_pubkey,
+        &mock_meteora_dlmm_pool.filler_pubkey,
+        &mock_meteora_dlmm_pool.filler_pubkey,
+        &intermediary_pubkeys.risk_limits_pubkey,
+        &trader_seat_pubkey,
+        &config_pubkey,
+        &fee_vault_pubkey,
+        mock_meteora_dlmm_pool.bin_array_pubkeys.as_slice(),
+        any_mint_token_account_amount,
+        None,
+        any_mint_token_account_pubkey_bump_seed,
+        any_mint_token_account_authority_pubkey_bump_seed,
+        temporary_any_mint_token_account_pubkey_bump_seed,
+        temporary_any_mint_token_account_authority_pubkey_bump_seed,
+    )
+    .unwrap();
+    let versioned_events = environment
+        .process(
+            [sell_on_meteora_dlmm_instruction].as_slice(),
+            [&trader].as_slice(),
+        )
+        .await
+        .unwrap();
+    let w_sol_token_account_amount = environment.get_token_account_amount(&intermediary_pubkeys.w_sol_token_account_pubkey).await;
+    assert!(environment.get_account(&any_mint_token_account_pubkey).await.is_none());
+    let amount_out = (any_mint_token_account_amount as f64 / price) as u64;
+    // The rent of the closed position reaches the treasury too, but is not part of the proceeds.
+    assert_eq!(w_sol_token_account_amount, w_sol_token_account_amount_after_buying + amount_out + Rent::default().minimum_balance(TokenAccount::LEN));
+    assert_eq!(
+        versioned_events.last(),
+        Some(
+            &VersionedEvent::V1(
+                Event::Traded {
+                    intermediary_pubkey,
+                    venue: Venue::MeteoraDlmm,
+                    side: Side::Sell,
+                    mint_pubkey: any_mint_token_mint_pubkey,
+                    amount_in: any_mint_token_account_amount,
+                    amount_out,
+                    treasury_amount: w_sol_token_account_amount,
+                },
+            ),
+        ),
+    );
+    let trader_seat = environment.get_trader_seat(&trader_pubkey).await;
+    assert_eq!(trader_seat.spent_lamports, 10 * LAMPORTS_PER_SOL - w_sol_token_account_amount_after_buying);
+    assert_eq!(trader_seat.received_lamports, amount_out);
+    assert_eq!(trader_seat.trades_quantity, 2);
+}
+#[tokio::test]
+async fn buy_and_sell_on_meteora_dynamic_amm() {
+    let any_mint_token_mint_pubkey = Pubkey::new_unique();
+    let mut mock_meteora_dynamic_amm_pools = Vec::<MockMeteoraDynamicAmmPool>::with_capacity(1);
+    let mut environment = Environment::start(
+        |program_test, _| {
+            add_mock_meteora_dynamic_amm_program(program_test);
+            program_test.add_account(
+                any_mint_token_mint_pubkey,
+                create_mint_account(6),
+            );
+            mock_meteora_dynamic_amm_pools.push(
+                MockMeteoraDynamicAmmPool::add(
+                    program_test,
+                    &any_mint_token_mint_pubkey,
+                    1_000_000_000_000,
+                    100 * LAMPORTS_PER_SOL,
+                ),
+            );
+        },
+    )
+    .await;
+    let mock_meteora_dynamic_amm_pool = mock_meteora_dynamic_amm_pools.pop().unwrap();
+    let intermediary_pubkey = environment.intermediary.pubkey();
+    let intermediary_pubkeys = environment.intermediary_pubkeys();
+    let trader = environment.trader.insecure_clone();
+    let manager = environment.manager.insecure_clone();
+    let config_pubkey = environment.config_pubkey;
+    let fee_vault_pubkey = environment.fee_vault_pubkey;
+    let (any_mint_token_account_pubkey, any_mint_token_account_pubkey_bump_seed) = AnyMintTokenAccountAddressResolver {
+        any_mint_token_mint_pubkey: &any_mint_token_mint_pubkey,
+        intermediary_pubkey: &intermediary_pubkey,
+    }
+    .find();
+    let (any_mint_token_account_authority_pubkey, any_mint_token_account_authority_pubkey_bump_seed) = AnyMintTokenAccountAuthorityAddressResolver {
+        any_mint_token_account_pubkey: &any_mint_token_account_pubkey,
+        intermediary_pubkey: &intermediary_pubkey,
+    }
+    .find();
+    let (temporary_any_mint_token_account_pubkey, temporary_any_mint_token_account_pubkey_bump_seed) = TemporaryAnyMintTokenAccountAddressResolver {
+        any_mint_token_account_pubkey: &any_mint_token_account_pubkey,
+        intermediary_pubkey: &intermediary_pubkey,
+    }
+    .find();
+    let (temporary_any_mint_token_account_authority_pubkey, temporary_any_mint_token_account_authority_pubkey_bump_seed) =
+        TemporaryAnyMintTokenAccountAuthorityAddressResolver {
+            temporary_any_mint_token_account_pubkey: &temporary_any_mint_token_account_pubkey,
+            intermediary_pubkey: &intermediary_pubkey,
+        }
+        .find();
+    environment.initialize_config().await;
+    environment.initialize_intermediary(10 * LAMPORTS_PER_SOL).await;
+    environment.set_risk_limits(LAMPORTS_PER_SOL, 10_000, 10, 100).await.unwrap();
+    environment.add_allowed_mint(&any_mint_token_mint_pubkey).await.unwrap();
+    let trader_pubkey = environment.trader.pubkey();
+    environment.add_trader_seat(&trader_pubkey, LAMPORTS_PER_SOL).await.unwrap();
+    let (trader_seat_pubkey, _) = environment.trader_seat_pubkey(&trader_pubkey);
+    // The swaps are held to the reserves read from the pool, which the fee-less mock matches.
+    environment
+        .process(
+            [
+                Instruction::set_maximum_price_deviation(
+                    &PROGRAM_PUBKEY,
+                    &manager.pubkey(),
+                    &intermediary_pubkey,
+                    10,
+                )
+                .unwrap(),
+            ]
+            .as_slice(),
+            [&manager].as_slice(),
+        )
+        .await
+        .unwrap();
+    let buy_on_meteora_dynamic_amm_instruction = Instruction::buy_on_meteora_dynamic_amm(
+        &PROGRAM_PUBKEY,
+        &trader.pubkey(),
+        &intermediary_pubkey,
+        &intermediary_pubkeys.w_sol_token_account_pubkey,
+        &intermediary_pubkeys.w_sol_token_account_authority_pubkey,
+        &intermediary_pubkeys.temporary_w_sol_token_account_pubkey,
+        &intermediary_pubkeys.temporary_w_sol_token_account_authority_pubkey,
+        &any_mint_token_account_pubkey,
+        &any_mint_token_account_authority_pubkey,
+        &any_mint_token_mint_pubkey,
+        &mock_meteora_dynamic_amm_pool.pool_pubkey,
+        &mock_meteora_dynamic_amm_pool.w_sol_vault_pubkey,
+        &mock_meteora_dynamic_amm_pool.any_mint_vault_pubkey,
+        &mock_meteora_dynamic_amm_pool.w_sol_token_vault_pubkey,
+        &mock_meteora_dynamic_amm_pool.any_mint_token_vault_pubkey,
+        &mock_meteora_dynamic_amm_pool.w_sol_vault_lp_mint_pubkey,
+        &mock_meteora_dynamic_amm_pool.any_mint_vault_lp_mint_pubkey,
+        &mock_meteora_dynamic_amm_pool.w_sol_vault_lp_pubkey,
+        &mock_meteora_dynamic_amm_pool.any_mint_vault_lp_pubkey,
+        &mock_meteora_dynamic_amm_pool.protocol_token_fee_pubkey,
+        &intermediary_pubkeys.allowed_mints_pubkey,
+        &intermediary_pubkeys.risk_limits_pubkey,
+        &trader_seat_pubkey,
+        &config_pubkey,
+        &fee_vault_pubkey,
+        LAMPORTS_PER_SOL / 10,
+        1,
+        any_mint_token_account_pubkey_bump_seed,
+        any_mint_token_account_authority_pubkey_bump_seed,
+    )
+    .unwrap();
+    let versioned_events = environment
+        .process(
+            [buy_on_meteora_dynamic_amm_instruction].as_slice(),
+            [&trader].as_slice(),
+        )
+        .await
+        .unwrap();
+    let any_mint_token_account_amount = environment.get_token_account_amount(&any_mint_token_account_pubkey).await;
+    assert_eq!(any_mint_token_account_amount, (1_000_000_000_000_u128 * (LAMPORTS_PER_SOL / 10) as u128 / (100 * LAMPORTS_PER_SOL + LAMPORTS_PER_SOL / 10) as u128) as u64);
+    // The temporary wSOL token account is funded by the trader and closed into the treasury.
+    let w_sol_token_account_amount_after_buying = 10 * LAMPORTS_PER_SOL - LAMPORTS_PER_SOL / 10 + Rent::default().minimum_balance(TokenAccount::LEN);
+    assert_eq!(environment.get_token_account_amount(&intermediary_pubkeys.w_sol_token_account_pubkey).await, w_sol_token_account_amount_after_buying);
+    assert_eq!(
+        versioned_events.last(),
+        Some(
+            &VersionedEvent::V1(
+                Event::Traded {
+                    intermediary_pubkey,
+                    venue: Venue::MeteoraDynamicAmm,
+                    side: Side::Buy,
+                    mint_pubkey: any_mint_token_mint_pubkey,
+                    amount_in: 10 * LAMPORTS_PER_SOL - w_sol_token_account_amount_after_buying,
+                    amount_out: any_mint_token_account_amount,
+                    treasury_amount: w_sol_token_account_amount_after_buying,
+                },
+            ),
+        ),
+    );
+    let sell_on_meteora_dynamic_amm_instruction = Instruction::sell_on_meteora_dynamic_amm(
+        &PROGRAM_PUBKEY,
+        &trader.pubkey(),
+        &intermediary_pubkey,
+        &intermediary_pubkeys.w_sol_token_account_pubkey,
+        &any_mint_token_account_pubkey,
+        &any_mint_token_account_authority_pubkey,
+        &temporary_any_mint_token_account_pubkey,
+        &temporary_any_mint_token_account_authority_pubkey,
+        &any_mint_token_mint_pubkey,
+        &mock_meteora_dynamic_amm_pool.pool_pubkey,
+        &mock_meteora_dynamic_amm_pool.w_sol_vault_pubkey,
+        &mock_meteora_dynamic_amm_pool.any_mint_vault_pubkey,
+        &mock_meteora_dynamic_amm_pool.w_sol_token_vault_pubkey,
+        &mock_meteora_dynamic_amm_pool.any_mint_token_vault_pubkey,
+        &mock_meteora_dynamic_amm_pool.w_sol_vault_lp_mint_pubkey,
+        &mock_meteora_dynamic_amm_pool.any_mint_vault_lp_mint_pubkey,
+        &mock_meteora_dynamic_amm_pool.w_sol_vault_lp_pubkey,
+        &mock_meteora_dynamic_amm_pool.any_mint_vault_lp_pubkey,
+        &mock_meteora_dynamic_amm_pool.protocol_token_fee_pubkey,
+        &intermediary_pubkeys.risk_limits_pubkey,
+        &trader_seat_pubkey,
+        &config_pubkey,
+        &fee_vault_pubkey,
+        any_mint_token_account_amount,
+        None,
+        any_mint_token_account_pubkey_bump_seed,
+        any_mint_token_account_authority_pubkey_bump_seed,
+        temporary_any_mint_token_account_pubkey_bump_seed,
+        temporary_any_mint_token_account_authority_pubkey_bump_seed,
+    )
+    .unwrap();
+    let versioned_events = environment
+        .process(
+            [sell_on_meteora_dynamic_amm_instruction].as_slice(),
+            [&trader].as_slice(),
+        )
+        .await
+        .unwrap();
+    let w_sol_token_account_amount = environment.get_token_account_amount(&intermediary_pubkeys.w_sol_token_account_pubkey).await;
+    assert!(environment.get_account(&any_mint_token_account_pubkey).await.is_none());
+    let amount_out = ((100 * LAMPORTS_PER_SOL + LAMPORTS_PER_SOL / 10) as u128 * any_mint_token_account_amount as u128 / 1_000_000_000_000_u128) as u64;
+    // The rent of the closed position reaches the treasury too, but is not part of the proceeds.
+    assert_eq!(w_sol_token_account_amount, w_sol_token_account_amount_after_buying + amount_out + Rent::default().minimum_balance(TokenAccount::LEN));
+    assert_eq!(
+        versioned_events.last(),
+        Some(
+            &VersionedEvent::V1(
+                Event::Traded {
+                    intermediary_pubkey,
+                    venue: Venue::MeteoraDynamicAmm,
+                    side: Side::Sell,
+                    mint_pubkey: any_mint_token_mint_pubkey,
+                    amount_in: any_mint_token_account_amount,
+                    amount_out,
+                    treasury_amount: w_sol_token_account_amount,
+                },
+            ),
+        ),
+    );
+    let trader_seat = environment.get_trader_seat(&trader_pubkey).await;
+    assert_eq!(trader_seat.spent_lamports, 10 * LAMPORTS_PER_SOL - w_sol_token_account_amount_after_buying);
+    assert_eq!(trader_seat.received_lamports, amount_out);
+    assert_eq!(trader_seat.trades_quantity, 2);
+}
+#[tokio::test]
+async fn buy_and_sell_on_pumpfun_curve() {
+    let any_mint_token_mint_pubkey = Pubkey::new_unique();
+    let mut mock_pumpfun_curves = Vec::<MockPumpfunCurve>::with_capacity(1);
+    let mut environment = Environment::start(
+        |program_test, _| {
+            add_mock_pumpfun_program(program_test);
+            program_test.add_account(
+                any_mint_token_mint_pubkey,
+                create_mint_account(6),
+            );
+            mock_pumpfun_curves.push(
+                MockPumpfunCurve::add(
+                    program_test,
+                    &any_mint_token_mint_pubkey,
+                    1_000_000_000_000,
+                    30 * LAMPORTS_PER_SOL,
+                ),
+            );
+        },
+    )
+    .await;
+    let mock_pumpfun_curve = mock_pumpfun_curves.pop().unwrap();
+    let intermediary_pubkey = environment.intermediary.pubkey();
+    let intermediary_pubkeys = environment.intermediary_pubkeys();
+    let trader = environment.trader.insecure_clone();
+    let manager = environment.manager.insecure_clone();
+    let config_pubkey = environment.config_pubkey;
+    let fee_vault_pubkey = environment.fee_vault_pubkey;
+    let (any_mint_token_account_pubkey, any_mint_token_account_pubkey_bump_seed) = AnyMintTokenAccountAddressResolver {
+        any_mint_token_mint_pubkey: &any_mint_token_mint_pubkey,
+        intermediary_pubkey: &intermediary_pubkey,
+    }
+    .find();
+    let (any_mint_token_account_authority_pubkey, any_mint_token_account_authority_pubkey_bump_seed) = AnyMintTokenAccountAuthorityAddressResolver {
+        any_mint_token_account_pubkey: &any_mint_token_account_pubkey,
+        intermediary_pubkey: &intermediary_pubkey,
+    }
+    .find();
+    let (temporary_any_mint_token_account_pubkey, temporary_any_mint_token_account_pubkey_bump_seed) = TemporaryAnyMintTokenAccountAddressResolver {
+        any_mint_token_account_pubkey: &any_mint_token_account_pubkey,
+        intermediary_pubkey: &intermediary_pubkey,
+    }
+    .find();
+    let (temporary_any_mint_token_account_authority_pubkey, temporary_any_mint_token_account_authority_pubkey_bump_seed) =
+        TemporaryAnyMintTokenAccountAuthorityAddressResolver {
+            temporary_any_mint_token_account_pubkey: &temporary_any_mint_token_account_pubkey,
+            intermediary_pubkey: &intermediary_pubkey,
+        }
+        .find();
+    environment.initialize_config().await;
+    environment.initialize_intermediary(10 * LAMPORTS_PER_SOL).await;
+    environment.set_risk_limits(LAMPORTS_PER_SOL, 10_000, 10, 100).await.unwrap();
+    environment.add_allowed_mint(&any_mint_token_mint_pubkey).await.unwrap();
+    let trader_pubkey = environment.trader.pubkey();
+    environment.add_trader_seat(&trader_pubkey, LAMPORTS_PER_SOL).await.unwrap();
+    let (trader_seat_pubkey, _) = environment.trader_seat_pubkey(&trader_pubkey);
+    // The swaps are held to the reserves read from the pool, which the fee-less mock matches.
+    environment
+        .process(
+            [
+                Instruction::set_maximum_price_deviation(
+                    &PROGRAM_PUBKEY,
+                    &manager.pubkey(),
+                    &intermediary_pubkey,
+                    10,
+                )
+                .unwrap(),
+            ]
+            .as_slice(),
+            [&manager].as_slice(),
+        )
+        .await
+        .unwrap();
+    let bonding_curve_lamports_before_buying = environment.get_lamports(&mock_pumpfun_curve.bonding_curve_pubkey).await;
+    // The curve buys an exact token amount for at most the lamports given.
+    let buy_on_pumpfun_curve_instruction = Instruction::buy_on_pumpfun_curve(
+        &PROGRAM_PUBKEY,
+        &trader.pubkey(),
+        &intermediary_pubkey,
+        &intermediary_pubkeys.w_sol_token_account_pubkey,
+        &intermediary_pubkeys.w_sol_token_account_authority_pubkey,
+        &intermediary_pubkeys.temporary_w_sol_token_account_pubkey,
+        &intermediary_pubkeys.temporary_w_sol_token_account_authority_pubkey,
+        &any_mint_token_account_pubkey,
+        &any_mint_token_account_authority_pubkey,
+        &any_mint_token_mint_pubkey,
+        &mock_pumpfun_curve.filler_pubkey,
+        &mock_pumpfun_curve.filler_pubkey,
+        &mock_pumpfun_curve.bonding_curve_pubkey,
+        &mock_pumpfun_curve.associated_bonding_curve_pubkey,
+        &mock_pumpfun_curve.filler_pubkey,
+        &intermediary_pubkeys.allowed_mints_pubkey,
+        &intermediary_pubkeys.risk_limits_pubkey,
+        &trader_seat_pubkey,
+        &config_pubkey,
+        &fee_vault_pubkey,
+        1_000_000_000,
+        LAMPORTS_PER_SOL / 10,
+        any_mint_token_account_pubkey_bump_seed,
+        any_mint_token_account_authority_pubkey_bump_seed,
+    )
+    .unwrap();
+    let versioned_events = environment
+        .process(
+            [buy_on_pumpfun_curve_instruction].as_slice(),
+            [&trader].as_slice(),
+        )
+        .await
+        .unwrap();
+    let spent_lamports = (30 * LAMPORTS_PER_SOL as u128 * 1_000_000_000_u128).div_ceil(1_000_000_000_000_u128 - 1_000_000_000_u128) as u64;
+    let any_mint_token_account_amount = environment.get_token_account_amount(&any_mint_token_account_pubkey).await;
+    assert_eq!(any_mint_token_account_amount, 1_000_000_000);
+    // The temporary wSOL token account is unwrapped into its authority to pay the curve, and what the curve did not take is wrapped back
+    // into the treasury together with the rent.
+    let w_sol_token_account_amount_after_buying = 10 * LAMPORTS_PER_SOL - spent_lamports + Rent::default().minimum_balance(TokenAccount::LEN);
+    assert_eq!(environment.get_token_account_amount(&intermediary_pubkeys.w_sol_token_account_pubkey).await, w_sol_token_account_amount_after_buying);
+    assert!(environment.get_account(&intermediary_pubkeys.temporary_w_sol_token_account_pubkey).await.is_none());
+    assert!(environment.get_account(&intermediary_pubkeys.temporary_w_sol_token_account_authority_pubkey).await.is_none());
+    assert_eq!(environment.get_lamports(&mock_pumpfun_curve.bonding_curve_pubkey).await, bonding_curve_lamports_before_buying + spent_lamports);
+    assert_eq!(
+        versioned_events.last(),
+        Some(
+            &VersionedEvent::V1(
+                Event::Traded {
+                    intermediary_pubkey,
+                    venue: Venue::PumpfunCurve,
+                    side: Side::Buy,
+                    mint_pubkey: any_mint_token_mint_pubkey,
+                    amount_in: 10 * LAMPORTS_PER_SOL - w_sol_token_account_amount_after_buying,
+                    amount_out: any_mint_token_account_amount,
+                    treasury_amount: w_sol_token_account_amount_after_buying,
+                },
+            ),
+        ),
+    );
+    let sell_on_pumpfun_curve_instruction = Instruction::sell_on_pumpfun_curve(
+        &PROGRAM_PUBKEY,
+        &trader.pubkey(),
+        &intermediary_pubkey,
+        &intermediary_pubkeys.w_sol_token_account_pubkey,
+        &any_mint_token_account_pubkey,
+        &any_mint_token_account_authority_pubkey,
+        &temporary_any_mint_token_account_pubkey,
+        &temporary_any_mint_token_account_authority_pubkey,
+        &any_mint_token_mint_pubkey,
+        &mock_pumpfun_curve.filler_pubkey,
+        &mock_pumpfun_curve.filler_pubkey,
+        &mock_pumpfun_curve.bonding_curve_pubkey,
+        &mock_pumpfun_curve.associated_bonding_curve_pubkey,
+        &mock_pumpfun_curve.filler_pubkey,
+        &intermediary_pubkeys.risk_limits_pubkey,
+        &trader_seat_pubkey,
+        &config_pubkey,
+        &fee_vault_pubkey,
+        any_mint_token_account_amount,
+        None,
+        any_mint_token_account_pubkey_bump_seed,
+        any_mint_token_account_authority_pubkey_bump_seed,
+        temporary_any_mint_token_account_pubkey_bump_seed,
+        temporary_any_mint_token_account_authority_pubkey_bump_seed,
+    )
+    .unwrap();
+    let versioned_events = environment
+        .process(
+            [sell_on_pumpfun_curve_instruction].as_slice(),
+            [&trader].as_slice(),
+        )
+        .await
+        .unwrap();
+    let w_sol_token_account_amount = environment.get_token_account_amount(&intermediary_pubkeys.w_sol_token_account_pubkey).await;
+    assert!(environment.get_account(&any_mint_token_account_pubkey).await.is_none());
+    // The curve pays the seller in lamports, which are wrapped into the treasury without staying with the swap authority.
+    let received_lamports = ((30 * LAMPORTS_PER_SOL + spent_lamports) as u128 * any_mint_token_account_amount as u128 / 1_000_000_000_000_u128) as u64;
+    assert!(environment.get_account(&any_mint_token_account_authority_pubkey).await.is_none());
+    assert_eq!(environment.get_lamports(&mock_pumpfun_curve.bonding_curve_pubkey).await, bonding_curve_lamports_before_buying + spent_lamports - received_lamports);
+    // The rent of the closed position reaches the treasury too, but is not part of the proceeds.
+    assert_eq!(w_sol_token_account_amount, w_sol_token_account_amount_after_buying + received_lamports + Rent::default().minimum_balance(TokenAccount::LEN));
+    assert_eq!(
+        versioned_events.last(),
+        Some(
+            &VersionedEvent::V1(
+                Event::Traded {
+                    intermediary_pubkey,
+                    venue: Venue::PumpfunCurve,
+                    side: Side::Sell,
+                    mint_pubkey: any_mint_token_mint_pubkey,
+                    amount_in: any_mint_token_account_amount,
+                    amount_out: received_lamports,
+                    treasury_amount: w_sol_token_account_amount,
+                },
+            ),
+        ),
+    );
+    let trader_seat = environment.get_trader_seat(&trader_pubkey).await;
+    assert_eq!(trader_seat.spent_lamports, 10 * LAMPORTS_PER_SOL - w_sol_token_account_amount_after_buying);
+    assert_eq!(trader_seat.received_lamports, received_lamports);
+    assert_eq!(trader_seat.trades_quantity, 2);
+}
@@ -0,0 +1,16 @@
+use thiserror::Error as Error_;
+#[derive(Debug, Error_, PartialEq, Eq)]
+pub enum Error {
+    #[error("Invalid account data.")]
+    InvalidAccountData,
+    #[error("Invalid amount.")]
+    InvalidAmount,
+    #[error("Invalid slippage basis points.")]
+    InvalidSlippageBasisPoints,
+    #[error("Insufficient liquidity.")]
+    InsufficientLiquidity,
+    #[error("Protocol fee recipient is not set.")]
+    ProtocolFeeRecipientIsNotSet,
+    #[error("Arithmetic overflow.")]
+    ArithmeticOverflow,
+}
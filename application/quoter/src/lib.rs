@@ -0,0 +1,71 @@
+pub mod error;
+pub mod pumpswap;
+pub mod raydium;
+use {
+    error::Error,
+    solana_program::pubkey::Pubkey,
+};
+pub const BASIS_POINTS_DENOMINATOR: u64 = 10_000;
+// Amounts are in the smallest units of the respective token, the fee is charged in the token named by the venue module.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Quote {
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub fee_amount: u64,
+}
+// The lowest amount to accept for a quoted output, rounded down.
+pub fn get_minimum_amount_out(amount_out: u64, slippage_basis_points: u16) -> Result<u64, Error> {
+    let slippage_basis_points = slippage_basis_points as u64;
+    if slippage_basis_points > BASIS_POINTS_DENOMINATOR {
+        return Err(Error::InvalidSlippageBasisPoints);
+    }
+    to_u64(
+        mul(
+            amount_out as u128,
+            (BASIS_POINTS_DENOMINATOR - slippage_basis_points) as u128,
+        )? / BASIS_POINTS_DENOMINATOR as u128,
+    )
+}
+// The highest amount to pay for a quoted input, rounded up.
+pub fn get_maximum_amount_in(amount_in: u64, slippage_basis_points: u16) -> Result<u64, Error> {
+    let slippage_basis_points = slippage_basis_points as u64;
+    if slippage_basis_points > BASIS_POINTS_DENOMINATOR {
+        return Err(Error::InvalidSlippageBasisPoints);
+    }
+    to_u64(
+        ceil_div(
+            mul(
+                amount_in as u128,
+                (BASIS_POINTS_DENOMINATOR + slippage_basis_points) as u128,
+            )?,
+            BASIS_POINTS_DENOMINATOR as u128,
+        )?,
+    )
+}
+pub(crate) fn read_u64(data: &[u8], offset: usize) -> Result<u64, Error> {
+    match data.get(offset..(offset + 8)) {
+        Some(bytes) => Ok(u64::from_le_bytes(bytes.try_into().map_err(|_| Error::InvalidAccountData)?)),
+        None => Err(Error::InvalidAccountData),
+    }
+}
+pub(crate) fn read_pubkey(data: &[u8], offset: usize) -> Result<Pubkey, Error> {
+    match data.get(offset..(offset + 32)) {
+        Some(bytes) => Pubkey::try_from(bytes).map_err(|_| Error::InvalidAccountData),
+        None => Err(Error::InvalidAccountData),
+    }
+}
+pub(crate) fn mul(left: u128, right: u128) -> Result<u128, Error> {
+    left.checked_mul(right).ok_or(Error::ArithmeticOverflow)
+}
+pub(crate) fn ceil_div(numerator: u128, denominator: u128) -> Result<u128, Error> {
+    if denominator == 0 {
+        return Err(Error::ArithmeticOverflow);
+    }
+    Ok(numerator.div_ceil(denominator))
+}
+pub(crate) fn floor_div(numerator: u128, denominator: u128) -> Result<u128, Error> {
+    numerator.checked_div(denominator).ok_or(Error::ArithmeticOverflow)
+}
+pub(crate) fn to_u64(value: u128) -> Result<u64, Error> {
+    u64::try_from(value).map_err(|_| Error::ArithmeticOverflow)
+}
@@ -0,0 +1,285 @@
+// From IDL (https://www.npmjs.com/package/@pump-fun/pump-swap-sdk)
+use {
+    crate::{
+        ceil_div,
+        error::Error,
+        floor_div,
+        mul,
+        read_pubkey,
+        read_u64,
+        to_u64,
+        Quote,
+        BASIS_POINTS_DENOMINATOR,
+    },
+    simo_intermediary::{
+        extern_source::get_associated_token_address,
+        PUMPFUN_PROGRAM_PUBKEY,
+        PUMPSWAP_PROGRAM_PUBKEY,
+    },
+    solana_program::pubkey::Pubkey,
+};
+// Pools created by the pump.fun migration use index 0 and the pump.fun pool authority as the creator.
+pub const CANONICAL_POOL_INDEX: u16 = 0;
+pub fn find_canonical_pool_pubkey<'a>(base_mint_pubkey: &'a Pubkey) -> Pubkey {
+    let (pool_authority_pubkey, _) = Pubkey::find_program_address(
+        [
+            b"pool-authority".as_slice(),
+            base_mint_pubkey.as_ref(),
+        ]
+        .as_slice(),
+        &PUMPFUN_PROGRAM_PUBKEY,
+    );
+    Pubkey::find_program_address(
+        [
+            b"pool".as_slice(),
+            CANONICAL_POOL_INDEX.to_le_bytes().as_slice(),
+            pool_authority_pubkey.as_ref(),
+            base_mint_pubkey.as_ref(),
+            spl_token::native_mint::ID.as_ref(),
+        ]
+        .as_slice(),
+        &PUMPSWAP_PROGRAM_PUBKEY,
+    )
+    .0
+}
+pub fn find_global_config_pubkey() -> Pubkey {
+    Pubkey::find_program_address(
+        [
+            b"global_config".as_slice(),
+        ]
+        .as_slice(),
+        &PUMPSWAP_PROGRAM_PUBKEY,
+    )
+    .0
+}
+pub fn find_event_authority_pubkey() -> Pubkey {
+    Pubkey::find_program_address(
+        [
+            b"__event_authority".as_slice(),
+        ]
+        .as_slice(),
+        &PUMPSWAP_PROGRAM_PUBKEY,
+    )
+    .0
+}
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pool {
+    pub base_mint_pubkey: Pubkey,
+    pub quote_mint_pubkey: Pubkey,
+    pub pool_base_token_account_pubkey: Pubkey,
+    pub pool_quote_token_account_pubkey: Pubkey,
+    pub lp_supply: u64,
+}
+impl Pool {
+    pub const MINIMUM_LENGTH: usize = 211;
+    pub fn load<'a>(data: &'a [u8]) -> Result<Self, Error> {
+        if data.len() < Self::MINIMUM_LENGTH {
+            return Err(Error::InvalidAccountData);
+        }
+        Ok(
+            Self {
+                base_mint_pubkey: read_pubkey(data, 43)?,
+                quote_mint_pubkey: read_pubkey(data, 75)?,
+                pool_base_token_account_pubkey: read_pubkey(data, 139)?,
+                pool_quote_token_account_pubkey: read_pubkey(data, 171)?,
+                lp_supply: read_u64(data, 203)?,
+            },
+        )
+    }
+}
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GlobalConfig {
+    pub lp_fee_basis_points: u64,
+    pub protocol_fee_basis_points: u64,
+    pub protocol_fee_recipient_pubkeys: [Pubkey; GlobalConfig::PROTOCOL_FEE_RECIPIENTS_QUANTITY],
+}
+// Buy swaps the quote token into the base token, sell swaps the base token into the quote token.
+// Both fees are charged in the quote token and each one is rounded up separately, as the program does.
+impl GlobalConfig {
+    pub const MINIMUM_LENGTH: usize = 313;
+    pub const PROTOCOL_FEE_RECIPIENTS_QUANTITY: usize = 8;
+    pub fn load<'a>(data: &'a [u8]) -> Result<Self, Error> {
+        if data.len() < Self::MINIMUM_LENGTH {
+            return Err(Error::InvalidAccountData);
+        }
+        let mut protocol_fee_recipient_pubkeys = [Pubkey::default(); Self::PROTOCOL_FEE_RECIPIENTS_QUANTITY];
+        for (index, protocol_fee_recipient_pubkey) in protocol_fee_recipient_pubkeys.iter_mut().enumerate() {
+            *protocol_fee_recipient_pubkey = read_pubkey(data, 57 + index * 32)?;
+        }
+        Ok(
+            Self {
+                lp_fee_basis_points: read_u64(data, 40)?,
+                protocol_fee_basis_points: read_u64(data, 48)?,
+                protocol_fee_recipient_pubkeys,
+            },
+        )
+    }
+    // The first set recipient, the program accepts any of them.
+    pub fn get_protocol_fee_recipient_pubkey(&self) -> Option<Pubkey> {
+        self.protocol_fee_recipient_pubkeys.iter().find(|protocol_fee_recipient_pubkey| **protocol_fee_recipient_pubkey != Pubkey::default()).copied()
+    }
+    pub fn quote_sell_exact_in(&self, base_reserve: u64, quote_reserve: u64, base_amount_in: u64) -> Result<Quote, Error> {
+        if base_amount_in == 0 {
+            return Err(Error::InvalidAmount);
+        }
+        if base_reserve == 0 || quote_reserve == 0 {
+            return Err(Error::InsufficientLiquidity);
+        }
+        let quote_amount_out_before_fee = floor_div(
+            mul(
+                quote_reserve as u128,
+                base_amount_in as u128,
+            )?,
+            (base_reserve as u128) + (base_amount_in as u128),
+        )?;
+        let fee_amount = self.get_fee_amount(quote_amount_out_before_fee)?;
+        let quote_amount_out = quote_amount_out_before_fee.checked_sub(fee_amount).ok_or(Error::InvalidAmount)?;
+        Ok(
+            Quote {
+                amount_in: base_amount_in,
+                amount_out: to_u64(quote_amount_out)?,
+                fee_amount: to_u64(fee_amount)?,
+            },
+        )
+    }
+    pub fn quote_sell_exact_out(&self, base_reserve: u64, quote_reserve: u64, quote_amount_out: u64) -> Result<Quote, Error> {
+        if quote_amount_out == 0 {
+            return Err(Error::InvalidAmount);
+        }
+        let total_fee_basis_points = self.get_total_fee_basis_points()?;
+        if total_fee_basis_points >= BASIS_POINTS_DENOMINATOR as u128 {
+            return Err(Error::InvalidAccountData);
+        }
+        let quote_amount_out_before_fee = ceil_div(
+            mul(
+                quote_amount_out as u128,
+                BASIS_POINTS_DENOMINATOR as u128,
+            )?,
+            (BASIS_POINTS_DENOMINATOR as u128) - total_fee_basis_points,
+        )?;
+        if base_reserve == 0 || quote_amount_out_before_fee >= quote_reserve as u128 {
+            return Err(Error::InsufficientLiquidity);
+        }
+        let base_amount_in = ceil_div(
+            mul(
+                base_reserve as u128,
+                quote_amount_out_before_fee,
+            )?,
+            (quote_reserve as u128) - quote_amount_out_before_fee,
+        )?;
+        Ok(
+            Quote {
+                amount_in: to_u64(base_amount_in)?,
+                amount_out: quote_amount_out,
+                fee_amount: to_u64(quote_amount_out_before_fee - quote_amount_out as u128)?,
+            },
+        )
+    }
+    pub fn quote_buy_exact_in(&self, base_reserve: u64, quote_reserve: u64, quote_amount_in: u64) -> Result<Quote, Error> {
+        if quote_amount_in == 0 {
+            return Err(Error::InvalidAmount);
+        }
+        if base_reserve == 0 || quote_reserve == 0 {
+            return Err(Error::InsufficientLiquidity);
+        }
+        let quote_amount_in_after_fee = floor_div(
+            mul(
+                quote_amount_in as u128,
+                BASIS_POINTS_DENOMINATOR as u128,
+            )?,
+            (BASIS_POINTS_DENOMINATOR as u128) + self.get_total_fee_basis_points()?,
+        )?;
+        let base_amount_out = floor_div(
+            mul(
+                base_reserve as u128,
+                quote_amount_in_after_fee,
+            )?,
+            (quote_reserve as u128) + quote_amount_in_after_fee,
+        )?;
+        Ok(
+            Quote {
+                amount_in: quote_amount_in,
+                amount_out: to_u64(base_amount_out)?,
+                fee_amount: to_u64(quote_amount_in as u128 - quote_amount_in_after_fee)?,
+            },
+        )
+    }
+    pub fn quote_buy_exact_out(&self, base_reserve: u64, quote_reserve: u64, base_amount_out: u64) -> Result<Quote, Error> {
+        if base_amount_out == 0 {
+            return Err(Error::InvalidAmount);
+        }
+        if quote_reserve == 0 || base_amount_out >= base_reserve {
+            return Err(Error::InsufficientLiquidity);
+        }
+        let quote_amount_in_before_fee = ceil_div(
+            mul(
+                quote_reserve as u128,
+                base_amount_out as u128,
+            )?,
+            (base_reserve - base_amount_out) as u128,
+        )?;
+        let fee_amount = self.get_fee_amount(quote_amount_in_before_fee)?;
+        Ok(
+            Quote {
+                amount_in: to_u64(quote_amount_in_before_fee + fee_amount)?,
+                amount_out: base_amount_out,
+                fee_amount: to_u64(fee_amount)?,
+            },
+        )
+    }
+    fn get_total_fee_basis_points(&self) -> Result<u128, Error> {
+        (self.lp_fee_basis_points as u128).checked_add(self.protocol_fee_basis_points as u128).ok_or(Error::ArithmeticOverflow)
+    }
+    fn get_fee_amount(&self, quote_amount: u128) -> Result<u128, Error> {
+        let lp_fee_amount = ceil_div(
+            mul(
+                quote_amount,
+                self.lp_fee_basis_points as u128,
+            )?,
+            BASIS_POINTS_DENOMINATOR as u128,
+        )?;
+        let protocol_fee_amount = ceil_div(
+            mul(
+                quote_amount,
+                self.protocol_fee_basis_points as u128,
+            )?,
+            BASIS_POINTS_DENOMINATOR as u128,
+        )?;
+        Ok(lp_fee_amount + protocol_fee_amount)
+    }
+}
+// Everything `Instruction::buy_on_pumpswap` and `Instruction::sell_on_pumpswap` need besides the pool itself.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PoolAccounts {
+    pub global_config_pubkey: Pubkey,
+    pub pool_base_token_account_pubkey: Pubkey,
+    pub pool_quote_token_account_pubkey: Pubkey,
+    pub protocol_fee_recipient_pubkey: Pubkey,
+    pub protocol_fee_recipient_token_account_pubkey: Pubkey,
+    pub base_token_program_pubkey: Pubkey,
+    pub quote_token_program_pubkey: Pubkey,
+    pub event_authority_pubkey: Pubkey,
+}
+impl PoolAccounts {
+    // The base token program is the owner of the base mint, which is not part of the pool data.
+    pub fn resolve<'a>(pool: &'a Pool, global_config: &'a GlobalConfig, base_token_program_pubkey: &'a Pubkey) -> Result<Self, Error> {
+        let protocol_fee_recipient_pubkey = global_config.get_protocol_fee_recipient_pubkey().ok_or(Error::ProtocolFeeRecipientIsNotSet)?;
+        let quote_token_program_pubkey = spl_token::ID;
+        Ok(
+            Self {
+                global_config_pubkey: find_global_config_pubkey(),
+                pool_base_token_account_pubkey: pool.pool_base_token_account_pubkey,
+                pool_quote_token_account_pubkey: pool.pool_quote_token_account_pubkey,
+                protocol_fee_recipient_pubkey,
+                protocol_fee_recipient_token_account_pubkey: get_associated_token_address(
+                    &protocol_fee_recipient_pubkey,
+                    &pool.quote_mint_pubkey,
+                    &quote_token_program_pubkey,
+                ),
+                base_token_program_pubkey: *base_token_program_pubkey,
+                quote_token_program_pubkey,
+                event_authority_pubkey: find_event_authority_pubkey(),
+            },
+        )
+    }
+}
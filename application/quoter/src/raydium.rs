@@ -0,0 +1,223 @@
+// https://github.com/raydium-io/raydium-amm/tree/d10a8e9fab9f7a3d87b4ae3891e3e4c24b75c041
+use {
+    crate::{
+        ceil_div,
+        error::Error,
+        floor_div,
+        mul,
+        read_pubkey,
+        read_u64,
+        to_u64,
+        Quote,
+    },
+    simo_intermediary::RAYDIUM_LIQUIDITY_POOL_V4_PROGRAM_PUBKEY,
+    solana_program::pubkey::Pubkey,
+};
+// The fields of the 752-byte `AmmInfo` account that the swap and its accounts depend on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AmmInfo {
+    pub status: u64,
+    pub nonce: u64,
+    pub coin_decimals: u64,
+    pub pc_decimals: u64,
+    pub swap_fee_numerator: u64,
+    pub swap_fee_denominator: u64,
+    pub need_take_pnl_coin: u64,
+    pub need_take_pnl_pc: u64,
+    pub coin_vault_pubkey: Pubkey,
+    pub pc_vault_pubkey: Pubkey,
+    pub coin_vault_mint_pubkey: Pubkey,
+    pub pc_vault_mint_pubkey: Pubkey,
+    pub open_orders_pubkey: Pubkey,
+    pub market_pubkey: Pubkey,
+    pub market_program_id_pubkey: Pubkey,
+}
+impl AmmInfo {
+    pub const LENGTH: usize = 752;
+    pub fn load<'a>(data: &'a [u8]) -> Result<Self, Error> {
+        if data.len() != Self::LENGTH {
+            return Err(Error::InvalidAccountData);
+        }
+        Ok(
+            Self {
+                status: read_u64(data, 0)?,
+                nonce: read_u64(data, 8)?,
+                coin_decimals: read_u64(data, 32)?,
+                pc_decimals: read_u64(data, 40)?,
+                swap_fee_numerator: read_u64(data, 176)?,
+                swap_fee_denominator: read_u64(data, 184)?,
+                need_take_pnl_coin: read_u64(data, 192)?,
+                need_take_pnl_pc: read_u64(data, 200)?,
+                coin_vault_pubkey: read_pubkey(data, 336)?,
+                pc_vault_pubkey: read_pubkey(data, 368)?,
+                coin_vault_mint_pubkey: read_pubkey(data, 400)?,
+                pc_vault_mint_pubkey: read_pubkey(data, 432)?,
+                open_orders_pubkey: read_pubkey(data, 496)?,
+                market_pubkey: read_pubkey(data, 528)?,
+                market_program_id_pubkey: read_pubkey(data, 560)?,
+            },
+        )
+    }
+    // Vault balances without the pending PnL, as (coin, pc). Open orders balances are not counted,
+    // which matches pools with the order book disabled.
+    pub fn get_reserves(&self, coin_vault_amount: u64, pc_vault_amount: u64) -> Result<(u64, u64), Error> {
+        Ok(
+            (
+                coin_vault_amount.checked_sub(self.need_take_pnl_coin).ok_or(Error::InsufficientLiquidity)?,
+                pc_vault_amount.checked_sub(self.need_take_pnl_pc).ok_or(Error::InsufficientLiquidity)?,
+            ),
+        )
+    }
+    // Mirrors `swap_base_in`: the fee is taken from the input, rounded up. The fee is in the input token.
+    pub fn quote_exact_in(&self, input_reserve: u64, output_reserve: u64, amount_in: u64) -> Result<Quote, Error> {
+        if amount_in == 0 {
+            return Err(Error::InvalidAmount);
+        }
+        if input_reserve == 0 || output_reserve == 0 {
+            return Err(Error::InsufficientLiquidity);
+        }
+        let fee_amount = ceil_div(
+            mul(
+                amount_in as u128,
+                self.swap_fee_numerator as u128,
+            )?,
+            self.swap_fee_denominator as u128,
+        )?;
+        let amount_in_after_fee = (amount_in as u128).checked_sub(fee_amount).ok_or(Error::ArithmeticOverflow)?;
+        let amount_out = floor_div(
+            mul(
+                output_reserve as u128,
+                amount_in_after_fee,
+            )?,
+            (input_reserve as u128) + amount_in_after_fee,
+        )?;
+        Ok(
+            Quote {
+                amount_in,
+                amount_out: to_u64(amount_out)?,
+                fee_amount: to_u64(fee_amount)?,
+            },
+        )
+    }
+    // Mirrors `swap_base_out`: the input before the fee is rounded up, then grossed up by the fee, rounded up again.
+    pub fn quote_exact_out(&self, input_reserve: u64, output_reserve: u64, amount_out: u64) -> Result<Quote, Error> {
+        if amount_out == 0 {
+            return Err(Error::InvalidAmount);
+        }
+        if input_reserve == 0 || amount_out >= output_reserve {
+            return Err(Error::InsufficientLiquidity);
+        }
+        if self.swap_fee_numerator >= self.swap_fee_denominator {
+            return Err(Error::InvalidAccountData);
+        }
+        let amount_in_before_fee = ceil_div(
+            mul(
+                input_reserve as u128,
+                amount_out as u128,
+            )?,
+            (output_reserve - amount_out) as u128,
+        )?;
+        let amount_in = ceil_div(
+            mul(
+                amount_in_before_fee,
+                self.swap_fee_denominator as u128,
+            )?,
+            (self.swap_fee_denominator - self.swap_fee_numerator) as u128,
+        )?;
+        Ok(
+            Quote {
+                amount_in: to_u64(amount_in)?,
+                amount_out,
+                fee_amount: to_u64(amount_in - amount_in_before_fee)?,
+            },
+        )
+    }
+}
+// The fields of the 388-byte OpenBook (Serum v3) `MarketState` account that the swap depends on.
+// The account starts with the 5-byte "serum" padding.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MarketState {
+    pub own_address_pubkey: Pubkey,
+    pub vault_signer_nonce: u64,
+    pub coin_vault_pubkey: Pubkey,
+    pub pc_vault_pubkey: Pubkey,
+    pub event_queue_pubkey: Pubkey,
+    pub bids_pubkey: Pubkey,
+    pub asks_pubkey: Pubkey,
+}
+impl MarketState {
+    pub const LENGTH: usize = 388;
+    pub fn load<'a>(data: &'a [u8]) -> Result<Self, Error> {
+        if data.len() != Self::LENGTH {
+            return Err(Error::InvalidAccountData);
+        }
+        Ok(
+            Self {
+                own_address_pubkey: read_pubkey(data, 13)?,
+                vault_signer_nonce: read_u64(data, 45)?,
+                coin_vault_pubkey: read_pubkey(data, 117)?,
+                pc_vault_pubkey: read_pubkey(data, 165)?,
+                event_queue_pubkey: read_pubkey(data, 253)?,
+                bids_pubkey: read_pubkey(data, 285)?,
+                asks_pubkey: read_pubkey(data, 317)?,
+            },
+        )
+    }
+}
+// Everything `Instruction::buy_on_raydium` and `Instruction::sell_on_raydium` need besides the pool itself.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AmmAccounts {
+    pub amm_authority_pubkey: Pubkey,
+    pub amm_open_orders_pubkey: Pubkey,
+    pub amm_coin_vault_pubkey: Pubkey,
+    pub amm_pc_vault_pubkey: Pubkey,
+    pub market_program_id_pubkey: Pubkey,
+    pub market_pubkey: Pubkey,
+    pub market_bids_pubkey: Pubkey,
+    pub market_asks_pubkey: Pubkey,
+    pub market_event_queue_pubkey: Pubkey,
+    pub market_coin_vault_pubkey: Pubkey,
+    pub market_pc_vault_pubkey: Pubkey,
+    pub market_vault_signer_pubkey: Pubkey,
+}
+impl AmmAccounts {
+    pub fn resolve<'a>(amm_info: &'a AmmInfo, market_state: &'a MarketState) -> Result<Self, Error> {
+        if market_state.own_address_pubkey != amm_info.market_pubkey {
+            return Err(Error::InvalidAccountData);
+        }
+        let amm_authority_pubkey = Pubkey::create_program_address(
+            [
+                b"amm authority".as_slice(),
+                [amm_info.nonce as u8].as_slice(),
+            ]
+            .as_slice(),
+            &RAYDIUM_LIQUIDITY_POOL_V4_PROGRAM_PUBKEY,
+        )
+        .map_err(|_| Error::InvalidAccountData)?;
+        let market_vault_signer_pubkey = Pubkey::create_program_address(
+            [
+                amm_info.market_pubkey.as_ref(),
+                market_state.vault_signer_nonce.to_le_bytes().as_slice(),
+            ]
+            .as_slice(),
+            &amm_info.market_program_id_pubkey,
+        )
+        .map_err(|_| Error::InvalidAccountData)?;
+        Ok(
+            Self {
+                amm_authority_pubkey,
+                amm_open_orders_pubkey: amm_info.open_orders_pubkey,
+                amm_coin_vault_pubkey: amm_info.coin_vault_pubkey,
+                amm_pc_vault_pubkey: amm_info.pc_vault_pubkey,
+                market_program_id_pubkey: amm_info.market_program_id_pubkey,
+                market_pubkey: amm_info.market_pubkey,
+                market_bids_pubkey: market_state.bids_pubkey,
+                market_asks_pubkey: market_state.asks_pubkey,
+                market_event_queue_pubkey: market_state.event_queue_pubkey,
+                market_coin_vault_pubkey: market_state.coin_vault_pubkey,
+                market_pc_vault_pubkey: market_state.pc_vault_pubkey,
+                market_vault_signer_pubkey,
+            },
+        )
+    }
+}
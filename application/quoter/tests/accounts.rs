@@ -0,0 +1,233 @@
+use {
+    base64::Engine,
+    quoter::{
+        error::Error,
+        pumpswap::{
+            find_event_authority_pubkey,
+            find_global_config_pubkey,
+            GlobalConfig,
+            Pool,
+            PoolAccounts,
+        },
+        raydium::{
+            AmmAccounts,
+            AmmInfo,
+            MarketState,
+        },
+    },
+    simo_intermediary::{
+        extern_source::get_associated_token_address,
+        RAYDIUM_LIQUIDITY_POOL_V4_PROGRAM_PUBKEY,
+    },
+    solana_program::pubkey::Pubkey,
+    std::{
+        fs,
+        path::{
+            Path,
+            PathBuf,
+        },
+        str::FromStr,
+    },
+};
+fn write_u64(data: &mut [u8], offset: usize, value: u64) {
+    data[offset..(offset + 8)].copy_from_slice(value.to_le_bytes().as_slice());
+}
+fn write_pubkey(data: &mut [u8], offset: usize, pubkey: &Pubkey) {
+    data[offset..(offset + 32)].copy_from_slice(pubkey.as_ref());
+}
+// The fixtures are synthetic accounts in the format of quoter/tests/fixtures/dump.sh, with manifests as decoded by it, so the offsets it
+// mirrors from the client are checked against the quoter. dump.sh writes mainnet accounts next to them, under the names without the prefix.
+fn get_fixtures_directory_path() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("fixtures")
+}
+fn read_fixture_account_data<'a>(fixture_accounts_directory_name: &'a str, pubkey: &'a Pubkey) -> Vec<u8> {
+    let fixture_account_file_path = get_fixtures_directory_path().join(fixture_accounts_directory_name).join(format!("{}.json", pubkey));
+    let data = fs::read(&fixture_account_file_path)
+        .unwrap_or_else(|_| panic!("The fixture account {} is missing.", fixture_account_file_path.display()));
+    let value = serde_json::from_slice::<serde_json::Value>(data.as_slice()).unwrap();
+    base64::engine::general_purpose::STANDARD.decode(value["account"]["data"][0].as_str().unwrap()).unwrap()
+}
+fn get_fixture_pubkey<'a>(fixture_manifest: &'a serde_json::Value, key: &'a str) -> Pubkey {
+    Pubkey::from_str(fixture_manifest[key].as_str().unwrap()).unwrap()
+}
+#[test]
+fn raydium_amm_accounts_are_resolved_from_pool_and_market() {
+    let market_program_id_pubkey = Pubkey::new_unique();
+    let market_pubkey = Pubkey::new_unique();
+    let (amm_authority_pubkey, amm_nonce) = Pubkey::find_program_address(
+        [
+            b"amm authority".as_slice(),
+        ]
+        .as_slice(),
+        &RAYDIUM_LIQUIDITY_POOL_V4_PROGRAM_PUBKEY,
+    );
+    let (market_vault_signer_pubkey, market_vault_signer_nonce) = (0..u64::MAX)
+        .find_map(|nonce| {
+            Pubkey::create_program_address(
+                [
+                    market_pubkey.as_ref(),
+                    nonce.to_le_bytes().as_slice(),
+                ]
+                .as_slice(),
+                &market_program_id_pubkey,
+            )
+            .ok()
+            .map(|pubkey| (pubkey, nonce))
+        })
+        .unwrap();
+    let pubkeys = (0..9).map(|_| Pubkey::new_unique()).collect::<Vec<Pubkey>>();
+    let mut amm_info_data = vec![0; AmmInfo::LENGTH];
+    write_u64(&mut amm_info_data, 8, amm_nonce as u64);
+    write_pubkey(&mut amm_info_data, 336, &pubkeys[0]);
+    write_pubkey(&mut amm_info_data, 368, &pubkeys[1]);
+    write_pubkey(&mut amm_info_data, 496, &pubkeys[2]);
+    write_pubkey(&mut amm_info_data, 528, &market_pubkey);
+    write_pubkey(&mut amm_info_data, 560, &market_program_id_pubkey);
+    let mut market_state_data = vec![0; MarketState::LENGTH];
+    market_state_data[..5].copy_from_slice(b"serum");
+    write_pubkey(&mut market_state_data, 13, &market_pubkey);
+    write_u64(&mut market_state_data, 45, market_vault_signer_nonce);
+    write_pubkey(&mut market_state_data, 117, &pubkeys[3]);
+    write_pubkey(&mut market_state_data, 165, &pubkeys[4]);
+    write_pubkey(&mut market_state_data, 253, &pubkeys[5]);
+    write_pubkey(&mut market_state_data, 285, &pubkeys[6]);
+    write_pubkey(&mut market_state_data, 317, &pubkeys[7]);
+    let amm_info = AmmInfo::load(amm_info_data.as_slice()).unwrap();
+    assert_eq!(MarketState::load(&market_state_data[..(MarketState::LENGTH - 1)]), Err(Error::InvalidAccountData));
+    let market_state = MarketState::load(market_state_data.as_slice()).unwrap();
+    assert_eq!(
+        AmmAccounts::resolve(
+            &amm_info,
+            &market_state,
+        ),
+        Ok(
+            AmmAccounts {
+                amm_authority_pubkey,
+                amm_open_orders_pubkey: pubkeys[2],
+                amm_coin_vault_pubkey: pubkeys[0],
+                amm_pc_vault_pubkey: pubkeys[1],
+                market_program_id_pubkey,
+                market_pubkey,
+                market_bids_pubkey: pubkeys[6],
+                market_asks_pubkey: pubkeys[7],
+                market_event_queue_pubkey: pubkeys[5],
+                market_coin_vault_pubkey: pubkeys[3],
+                market_pc_vault_pubkey: pubkeys[4],
+                market_vault_signer_pubkey,
+            },
+        ),
+    );
+    write_pubkey(&mut market_state_data, 13, &pubkeys[8]);
+    assert_eq!(
+        AmmAccounts::resolve(
+            &amm_info,
+            &MarketState::load(market_state_data.as_slice()).unwrap(),
+        ),
+        Err(Error::InvalidAccountData),
+    );
+}
+#[test]
+fn raydium_amm_accounts_are_resolved_from_synthetic_accounts() {
+    let fixture_manifest_file_path = get_fixtures_directory_path().join("synthetic_raydium_amm_v4.json");
+    let data = fs::read(&fixture_manifest_file_path).unwrap_or_else(|_| panic!("The fixture manifest {} is missing.", fixture_manifest_file_path.display()));
+    let fixture_manifest = serde_json::from_slice::<serde_json::Value>(data.as_slice()).unwrap();
+    let amm_pool_pubkey = get_fixture_pubkey(&fixture_manifest, "amm_pool");
+    let market_pubkey = get_fixture_pubkey(&fixture_manifest, "market");
+    let amm_info = AmmInfo::load(read_fixture_account_data("synthetic_raydium_amm_v4", &amm_pool_pubkey).as_slice()).unwrap();
+    let market_state = MarketState::load(read_fixture_account_data("synthetic_raydium_amm_v4", &market_pubkey).as_slice()).unwrap();
+    assert_eq!(
+        AmmAccounts::resolve(
+            &amm_info,
+            &market_state,
+        ),
+        Ok(
+            AmmAccounts {
+                amm_authority_pubkey: get_fixture_pubkey(&fixture_manifest, "amm_authority"),
+                amm_open_orders_pubkey: get_fixture_pubkey(&fixture_manifest, "amm_open_orders"),
+                amm_coin_vault_pubkey: get_fixture_pubkey(&fixture_manifest, "amm_coin_vault"),
+                amm_pc_vault_pubkey: get_fixture_pubkey(&fixture_manifest, "amm_pc_vault"),
+                market_program_id_pubkey: get_fixture_pubkey(&fixture_manifest, "market_program_id"),
+                market_pubkey,
+                market_bids_pubkey: get_fixture_pubkey(&fixture_manifest, "market_bids"),
+                market_asks_pubkey: get_fixture_pubkey(&fixture_manifest, "market_asks"),
+                market_event_queue_pubkey: get_fixture_pubkey(&fixture_manifest, "market_event_queue"),
+                market_coin_vault_pubkey: get_fixture_pubkey(&fixture_manifest, "market_coin_vault"),
+                market_pc_vault_pubkey: get_fixture_pubkey(&fixture_manifest, "market_pc_vault"),
+                market_vault_signer_pubkey: get_fixture_pubkey(&fixture_manifest, "market_vault_signer"),
+            },
+        ),
+    );
+}
+#[test]
+fn pumpswap_pool_accounts_are_resolved_from_pool_and_global_config() {
+    let base_mint_pubkey = Pubkey::new_unique();
+    let pool_base_token_account_pubkey = Pubkey::new_unique();
+    let pool_quote_token_account_pubkey = Pubkey::new_unique();
+    let protocol_fee_recipient_pubkey = Pubkey::new_unique();
+    let mut pool_data = vec![0; Pool::MINIMUM_LENGTH];
+    write_pubkey(&mut pool_data, 43, &base_mint_pubkey);
+    write_pubkey(&mut pool_data, 75, &spl_token::native_mint::ID);
+    write_pubkey(&mut pool_data, 139, &pool_base_token_account_pubkey);
+    write_pubkey(&mut pool_data, 171, &pool_quote_token_account_pubkey);
+    let pool = Pool::load(pool_data.as_slice()).unwrap();
+    let mut global_config_data = vec![0; GlobalConfig::MINIMUM_LENGTH];
+    assert_eq!(
+        PoolAccounts::resolve(
+            &pool,
+            &GlobalConfig::load(global_config_data.as_slice()).unwrap(),
+            &spl_token_2022::ID,
+        ),
+        Err(Error::ProtocolFeeRecipientIsNotSet),
+    );
+    write_pubkey(&mut global_config_data, 121, &protocol_fee_recipient_pubkey);
+    assert_eq!(
+        PoolAccounts::resolve(
+            &pool,
+            &GlobalConfig::load(global_config_data.as_slice()).unwrap(),
+            &spl_token_2022::ID,
+        ),
+        Ok(
+            PoolAccounts {
+                global_config_pubkey: find_global_config_pubkey(),
+                pool_base_token_account_pubkey,
+                pool_quote_token_account_pubkey,
+                protocol_fee_recipient_pubkey,
+                protocol_fee_recipient_token_account_pubkey: get_associated_token_address(
+                    &protocol_fee_recipient_pubkey,
+                    &spl_token::native_mint::ID,
+                    &spl_token::ID,
+                ),
+                base_token_program_pubkey: spl_token_2022::ID,
+                quote_token_program_pubkey: spl_token::ID,
+                event_authority_pubkey: find_event_authority_pubkey(),
+            },
+        ),
+    );
+}
+#[test]
+fn pumpswap_pool_accounts_are_resolved_from_synthetic_accounts() {
+    let fixture_manifest_file_path = get_fixtures_directory_path().join("synthetic_pumpswap.json");
+    let data = fs::read(&fixture_manifest_file_path).unwrap_or_else(|_| panic!("The fixture manifest {} is missing.", fixture_manifest_file_path.display()));
+    let fixture_manifest = serde_json::from_slice::<serde_json::Value>(data.as_slice()).unwrap();
+    let pool = Pool::load(read_fixture_account_data("synthetic_pumpswap", &get_fixture_pubkey(&fixture_manifest, "pool")).as_slice()).unwrap();
+    let global_config = GlobalConfig::load(read_fixture_account_data("synthetic_pumpswap", &get_fixture_pubkey(&fixture_manifest, "global_config")).as_slice()).unwrap();
+    assert_eq!(
+        PoolAccounts::resolve(
+            &pool,
+            &global_config,
+            &get_fixture_pubkey(&fixture_manifest, "base_token_program"),
+        ),
+        Ok(
+            PoolAccounts {
+                global_config_pubkey: get_fixture_pubkey(&fixture_manifest, "global_config"),
+                pool_base_token_account_pubkey: get_fixture_pubkey(&fixture_manifest, "pool_base_token_account"),
+                pool_quote_token_account_pubkey: get_fixture_pubkey(&fixture_manifest, "pool_quote_token_account"),
+                protocol_fee_recipient_pubkey: get_fixture_pubkey(&fixture_manifest, "protocol_fee_recipient"),
+                protocol_fee_recipient_token_account_pubkey: get_fixture_pubkey(&fixture_manifest, "protocol_fee_recipient_token_account"),
+                base_token_program_pubkey: get_fixture_pubkey(&fixture_manifest, "base_token_program"),
+                quote_token_program_pubkey: get_fixture_pubkey(&fixture_manifest, "quote_token_program"),
+                event_authority_pubkey: get_fixture_pubkey(&fixture_manifest, "event_authority"),
+            },
+        ),
+    );
+}
@@ -0,0 +1,192 @@
+#!/bin/bash
+# Dumps mainnet accounts in the format of the synthetic fixtures read by quoter/tests/accounts.rs, next to them and under the names without the synthetic_ prefix.
+# Usage: dump.sh <raydium_amm_v4_pool_pubkey> <pumpswap_pool_pubkey> [solana_rpc_url]
+set -euo pipefail
+if [ "$#" -lt 2 ]; then
+    echo "Usage: $0 <raydium_amm_v4_pool_pubkey> <pumpswap_pool_pubkey> [solana_rpc_url]" >&2
+    exit 1
+fi
+RAYDIUM_AMM_V4_POOL_PUBKEY="$1"
+PUMPSWAP_POOL_PUBKEY="$2"
+SOLANA_RPC_URL="${3:-https://api.mainnet-beta.solana.com}"
+RAYDIUM_LIQUIDITY_POOL_V4_PROGRAM_PUBKEY="675kPX9MHTjS2zt1qfr1NYHuzeLXfQM9H24wFSUt1Mp8"
+PUMPSWAP_PROGRAM_PUBKEY="pAMMBay6oceH9fJKBRHGP5D4bD4sWpmSwMn52FMfXEA"
+FIXTURES_DIRECTORY_PATH="$(cd "$(dirname "$0")" && pwd)"
+dump_account() {
+    local directory_path="$1"
+    local pubkey="$2"
+    solana account "$pubkey" --url "$SOLANA_RPC_URL" --output json --output-file "$directory_path/$pubkey.json" > /dev/null
+}
+# Mirrors the offsets used by the client in cli/src/bin/client/command_processor.rs.
+decode() {
+    python3 - "$@" << 'EOF'
+import base64, hashlib, json, struct, sys
+ALPHABET = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz"
+def b58encode(data):
+    number = int.from_bytes(data, "big")
+    encoded = ""
+    while number > 0:
+        number, remainder = divmod(number, 58)
+        encoded = ALPHABET[remainder] + encoded
+    return "1" * (len(data) - len(data.lstrip(b"\0"))) + encoded
+def b58decode(text):
+    number = 0
+    for character in text:
+        number = number * 58 + ALPHABET.index(character)
+    data = number.to_bytes((number.bit_length() + 7) // 8, "big")
+    return b"\0" * (len(text) - len(text.lstrip("1"))) + data
+# https://github.com/anza-xyz/agave/blob/v2.1.15/sdk/program/src/pubkey.rs
+P = 2 ** 255 - 19
+D = -121665 * pow(121666, P - 2, P) % P
+def is_on_curve(data):
+    y = (int.from_bytes(data, "little") & ((1 << 255) - 1)) % P
+    u = (y * y - 1) % P
+    v = (D * y * y + 1) % P
+    x = u * pow(v, 3, P) * pow(u * pow(v, 7, P), (P - 5) // 8, P) % P
+    if v * x * x % P == u:
+        return True
+    return v * x * x % P == (-u) % P
+def create_program_address(seeds, program_pubkey):
+    data = hashlib.sha256(b"".join(seeds) + b58decode(program_pubkey) + b"ProgramDerivedAddress").digest()
+    if is_on_curve(data):
+        raise ValueError("The program address is on curve.")
+    return b58encode(data)
+def find_program_address(seeds, program_pubkey):
+    for bump_seed in range(255, -1, -1):
+        try:
+            return create_program_address(seeds + [bytes([bump_seed])], program_pubkey)
+        except ValueError:
+            continue
+    raise ValueError("No program address found.")
+def read_account(path):
+    with open(path) as file:
+        return json.load(file)["account"]
+def read_data(path):
+    return base64.b64decode(read_account(path)["data"][0])
+def pubkey_at(data, offset):
+    return b58encode(data[offset:offset + 32])
+W_SOL_MINT_PUBKEY = "So11111111111111111111111111111111111111112"
+TOKEN_PROGRAM_PUBKEY = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+SPL_ASSOCIATED_TOKEN_ACCOUNT_PUBKEY = "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+command = sys.argv[1]
+if command == "raydium_amm_v4_pool":
+    data = read_data(sys.argv[2])
+    amm_nonce = struct.unpack("<Q", data[8:16])[0]
+    coin_mint_pubkey = pubkey_at(data, 400)
+    pc_mint_pubkey = pubkey_at(data, 432)
+    print(
+        json.dumps(
+            {
+                "any_mint_token_mint": pc_mint_pubkey if coin_mint_pubkey == W_SOL_MINT_PUBKEY else coin_mint_pubkey,
+                "amm_authority": create_program_address([b"amm authority", bytes([amm_nonce & 0xff])], sys.argv[3]),
+                "amm_coin_vault": pubkey_at(data, 336),
+                "amm_pc_vault": pubkey_at(data, 368),
+                "amm_open_orders": pubkey_at(data, 496),
+                "market": pubkey_at(data, 528),
+                "market_program_id": pubkey_at(data, 560),
+            },
+        ),
+    )
+elif command == "raydium_market":
+    data = read_data(sys.argv[2])
+    market_vault_signer_nonce = struct.unpack("<Q", data[45:53])[0]
+    print(
+        json.dumps(
+            {
+                "market_coin_vault": pubkey_at(data, 117),
+                "market_pc_vault": pubkey_at(data, 165),
+                "market_event_queue": pubkey_at(data, 253),
+                "market_bids": pubkey_at(data, 285),
+                "market_asks": pubkey_at(data, 317),
+                "market_vault_signer": create_program_address([b58decode(sys.argv[3]), struct.pack("<Q", market_vault_signer_nonce)], sys.argv[4]),
+            },
+        ),
+    )
+elif command == "pumpswap_pool":
+    data = read_data(sys.argv[2])
+    program_pubkey = sys.argv[3]
+    print(
+        json.dumps(
+            {
+                "any_mint_token_mint": pubkey_at(data, 43),
+                "pool_base_token_account": pubkey_at(data, 139),
+                "pool_quote_token_account": pubkey_at(data, 171),
+                "global_config": find_program_address([b"global_config"], program_pubkey),
+                "event_authority": find_program_address([b"__event_authority"], program_pubkey),
+                "quote_token_program": TOKEN_PROGRAM_PUBKEY,
+            },
+        ),
+    )
+elif command == "pumpswap_global_config":
+    data = read_data(sys.argv[2])
+    protocol_fee_recipient_pubkey = next(
+        pubkey_at(data, 57 + index * 32) for index in range(8) if data[57 + index * 32:89 + index * 32] != bytes(32)
+    )
+    print(
+        json.dumps(
+            {
+                "protocol_fee_recipient": protocol_fee_recipient_pubkey,
+                "protocol_fee_recipient_token_account": find_program_address(
+                    [b58decode(protocol_fee_recipient_pubkey), b58decode(TOKEN_PROGRAM_PUBKEY), b58decode(W_SOL_MINT_PUBKEY)],
+                    SPL_ASSOCIATED_TOKEN_ACCOUNT_PUBKEY,
+                ),
+            },
+        ),
+    )
+elif command == "owner":
+    print(read_account(sys.argv[2])["owner"])
+elif command == "merge":
+    manifest = {}
+    for argument in sys.argv[2:]:
+        manifest.update(json.loads(argument))
+    print(json.dumps(manifest, indent=4, sort_keys=True))
+elif command == "get":
+    print(json.loads(sys.argv[2])[sys.argv[3]])
+EOF
+}
+dump_raydium_amm_v4() {
+    local directory_path="$FIXTURES_DIRECTORY_PATH/raydium_amm_v4"
+    rm -rf "$directory_path"
+    mkdir -p "$directory_path"
+    dump_account "$directory_path" "$RAYDIUM_AMM_V4_POOL_PUBKEY"
+    local pool_manifest
+    pool_manifest="$(decode raydium_amm_v4_pool "$directory_path/$RAYDIUM_AMM_V4_POOL_PUBKEY.json" "$RAYDIUM_LIQUIDITY_POOL_V4_PROGRAM_PUBKEY")"
+    local market_pubkey
+    market_pubkey="$(decode get "$pool_manifest" market)"
+    local market_program_id_pubkey
+    market_program_id_pubkey="$(decode get "$pool_manifest" market_program_id)"
+    dump_account "$directory_path" "$market_pubkey"
+    local market_manifest
+    market_manifest="$(decode raydium_market "$directory_path/$market_pubkey.json" "$market_pubkey" "$market_program_id_pubkey")"
+    for key in any_mint_token_mint amm_coin_vault amm_pc_vault amm_open_orders; do
+        dump_account "$directory_path" "$(decode get "$pool_manifest" "$key")"
+    done
+    for key in market_coin_vault market_pc_vault market_event_queue market_bids market_asks; do
+        dump_account "$directory_path" "$(decode get "$market_manifest" "$key")"
+    done
+    decode merge "$pool_manifest" "$market_manifest" "{\"amm_pool\": \"$RAYDIUM_AMM_V4_POOL_PUBKEY\"}" > "$FIXTURES_DIRECTORY_PATH/raydium_amm_v4.json"
+}
+dump_pumpswap() {
+    local directory_path="$FIXTURES_DIRECTORY_PATH/pumpswap"
+    rm -rf "$directory_path"
+    mkdir -p "$directory_path"
+    dump_account "$directory_path" "$PUMPSWAP_POOL_PUBKEY"
+    local pool_manifest
+    pool_manifest="$(decode pumpswap_pool "$directory_path/$PUMPSWAP_POOL_PUBKEY.json" "$PUMPSWAP_PROGRAM_PUBKEY")"
+    local global_config_pubkey
+    global_config_pubkey="$(decode get "$pool_manifest" global_config)"
+    dump_account "$directory_path" "$global_config_pubkey"
+    local global_config_manifest
+    global_config_manifest="$(decode pumpswap_global_config "$directory_path/$global_config_pubkey.json")"
+    for key in any_mint_token_mint pool_base_token_account pool_quote_token_account; do
+        dump_account "$directory_path" "$(decode get "$pool_manifest" "$key")"
+    done
+    dump_account "$directory_path" "$(decode get "$global_config_manifest" protocol_fee_recipient_token_account)"
+    local any_mint_token_mint_pubkey
+    any_mint_token_mint_pubkey="$(decode get "$pool_manifest" any_mint_token_mint)"
+    local base_token_program_pubkey
+    base_token_program_pubkey="$(decode owner "$directory_path/$any_mint_token_mint_pubkey.json")"
+    decode merge "$pool_manifest" "$global_config_manifest" "{\"pool\": \"$PUMPSWAP_POOL_PUBKEY\", \"base_token_program\": \"$base_token_program_pubkey\"}" > "$FIXTURES_DIRECTORY_PATH/pumpswap.json"
+}
+dump_raydium_amm_v4
+dump_pumpswap
@@ -0,0 +1,139 @@
+use {
+    quoter::{
+        error::Error,
+        get_maximum_amount_in,
+        get_minimum_amount_out,
+        pumpswap::{
+            GlobalConfig,
+            Pool,
+        },
+        raydium::AmmInfo,
+        Quote,
+    },
+    solana_program::pubkey::Pubkey,
+};
+fn write_u64(data: &mut [u8], offset: usize, value: u64) {
+    data[offset..(offset + 8)].copy_from_slice(value.to_le_bytes().as_slice());
+}
+fn write_pubkey(data: &mut [u8], offset: usize, pubkey: &Pubkey) {
+    data[offset..(offset + 32)].copy_from_slice(pubkey.as_ref());
+}
+#[test]
+fn raydium_amm_info_quotes() {
+    let coin_vault_mint_pubkey = Pubkey::new_unique();
+    let pc_vault_mint_pubkey = Pubkey::new_unique();
+    let mut data = vec![0; AmmInfo::LENGTH];
+    write_u64(&mut data, 8, 254);
+    write_u64(&mut data, 176, 25);
+    write_u64(&mut data, 184, 10_000);
+    write_u64(&mut data, 192, 7);
+    write_u64(&mut data, 200, 11);
+    write_pubkey(&mut data, 400, &coin_vault_mint_pubkey);
+    write_pubkey(&mut data, 432, &pc_vault_mint_pubkey);
+    assert_eq!(AmmInfo::load(&data[1..]), Err(Error::InvalidAccountData));
+    let amm_info = AmmInfo::load(data.as_slice()).unwrap();
+    assert_eq!(amm_info.nonce, 254);
+    assert_eq!(amm_info.coin_vault_mint_pubkey, coin_vault_mint_pubkey);
+    assert_eq!(amm_info.pc_vault_mint_pubkey, pc_vault_mint_pubkey);
+    assert_eq!(amm_info.get_reserves(1_000_000_007, 2_000_000_011), Ok((1_000_000_000, 2_000_000_000)));
+    assert_eq!(amm_info.get_reserves(6, 2_000_000_011), Err(Error::InsufficientLiquidity));
+    assert_eq!(
+        amm_info.quote_exact_in(1_000_000_000, 2_000_000_000, 1_000_000),
+        Ok(
+            Quote {
+                amount_in: 1_000_000,
+                amount_out: 1_993_011,
+                fee_amount: 2_500,
+            },
+        ),
+    );
+    assert_eq!(
+        amm_info.quote_exact_out(1_000_000_000, 2_000_000_000, 1_993_011),
+        Ok(
+            Quote {
+                amount_in: 1_000_000,
+                amount_out: 1_993_011,
+                fee_amount: 2_500,
+            },
+        ),
+    );
+    assert_eq!(amm_info.quote_exact_out(1_000_000_000, 2_000_000_000, 2_000_000_000), Err(Error::InsufficientLiquidity));
+    assert_eq!(amm_info.quote_exact_in(1_000_000_000, 2_000_000_000, 0), Err(Error::InvalidAmount));
+}
+#[test]
+fn pumpswap_pool_and_global_config_quotes() {
+    let base_mint_pubkey = Pubkey::new_unique();
+    let quote_mint_pubkey = Pubkey::new_unique();
+    let pool_quote_token_account_pubkey = Pubkey::new_unique();
+    let mut data = vec![0; Pool::MINIMUM_LENGTH];
+    write_pubkey(&mut data, 43, &base_mint_pubkey);
+    write_pubkey(&mut data, 75, &quote_mint_pubkey);
+    write_pubkey(&mut data, 171, &pool_quote_token_account_pubkey);
+    write_u64(&mut data, 203, 42);
+    let pool = Pool::load(data.as_slice()).unwrap();
+    assert_eq!(pool.base_mint_pubkey, base_mint_pubkey);
+    assert_eq!(pool.quote_mint_pubkey, quote_mint_pubkey);
+    assert_eq!(pool.pool_quote_token_account_pubkey, pool_quote_token_account_pubkey);
+    assert_eq!(pool.lp_supply, 42);
+    let protocol_fee_recipient_pubkey = Pubkey::new_unique();
+    let mut data = vec![0; GlobalConfig::MINIMUM_LENGTH];
+    write_u64(&mut data, 40, 20);
+    write_u64(&mut data, 48, 5);
+    write_pubkey(&mut data, 89, &protocol_fee_recipient_pubkey);
+    assert_eq!(GlobalConfig::load(&data[..(GlobalConfig::MINIMUM_LENGTH - 1)]), Err(Error::InvalidAccountData));
+    let global_config = GlobalConfig::load(data.as_slice()).unwrap();
+    assert_eq!(global_config.lp_fee_basis_points, 20);
+    assert_eq!(global_config.protocol_fee_basis_points, 5);
+    assert_eq!(global_config.protocol_fee_recipient_pubkeys[0], Pubkey::default());
+    assert_eq!(global_config.protocol_fee_recipient_pubkeys[1], protocol_fee_recipient_pubkey);
+    let (base_reserve, quote_reserve) = (1_000_000_000_000, 50_000_000_000);
+    assert_eq!(
+        global_config.quote_sell_exact_in(base_reserve, quote_reserve, 1_000_000_000),
+        Ok(
+            Quote {
+                amount_in: 1_000_000_000,
+                amount_out: 49_825_172,
+                fee_amount: 124_877,
+            },
+        ),
+    );
+    assert_eq!(
+        global_config.quote_buy_exact_out(base_reserve, quote_reserve, 1_000_000_000),
+        Ok(
+            Quote {
+                amount_in: 50_175_178,
+                amount_out: 1_000_000_000,
+                fee_amount: 125_127,
+            },
+        ),
+    );
+    assert_eq!(
+        global_config.quote_buy_exact_in(base_reserve, quote_reserve, 100_000_000),
+        Ok(
+            Quote {
+                amount_in: 100_000_000,
+                amount_out: 1_991_040_309,
+                fee_amount: 249_377,
+            },
+        ),
+    );
+    assert_eq!(
+        global_config.quote_sell_exact_out(base_reserve, quote_reserve, 100_000_000),
+        Ok(
+            Quote {
+                amount_in: 2_009_040_692,
+                amount_out: 100_000_000,
+                fee_amount: 250_627,
+            },
+        ),
+    );
+    assert_eq!(global_config.quote_buy_exact_out(base_reserve, quote_reserve, base_reserve), Err(Error::InsufficientLiquidity));
+}
+#[test]
+fn slippage_bounds() {
+    assert_eq!(get_minimum_amount_out(1_000, 50), Ok(995));
+    assert_eq!(get_minimum_amount_out(1_000, 10_000), Ok(0));
+    assert_eq!(get_maximum_amount_in(1_000, 50), Ok(1_005));
+    assert_eq!(get_maximum_amount_in(999, 50), Ok(1_004));
+    assert_eq!(get_minimum_amount_out(1_000, 10_001), Err(Error::InvalidSlippageBasisPoints));
+}